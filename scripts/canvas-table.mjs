@@ -0,0 +1,207 @@
+// Deterministic table layout for the Excalidraw canvas. The agent passes
+// structured data (e.g. a product comparison) and this draws an aligned grid
+// in one batch instead of the model placing every cell by hand.
+
+import { jsonSchema, tool } from "ai";
+
+const FONT_SIZE = 16;
+// Rough average glyph width for Excalidraw's hand-drawn font.
+const CHAR_WIDTH = FONT_SIZE * 0.62;
+const CELL_PADDING_X = 16;
+const ROW_HEIGHT = 44;
+const MIN_COLUMN_WIDTH = 90;
+const MAX_COLUMN_WIDTH = 320;
+const TITLE_GAP = 16;
+const PLACEMENT_GAP = 120;
+
+const HEADER_FILL = "#a5d8ff";
+const ROW_LABEL_FILL = "#e7f5ff";
+const STRIPE_FILL = "#f8f9fa";
+const CELL_FILL = "#ffffff";
+const STROKE = "#495057";
+
+function cellText(value) {
+  if (value === null || value === undefined) return "";
+  if (typeof value === "boolean") return value ? "✓" : "✗";
+  return String(value).replace(/\s+/g, " ").trim();
+}
+
+function isNumeric(text) {
+  return /^[-+]?[$€£]?\d[\d,]*(\.\d+)?%?$/.test(text);
+}
+
+/** Round numbers in a column to the same number of decimals. */
+function alignNumbers(column) {
+  const numeric = column.filter((value) => typeof value === "number" && Number.isFinite(value));
+  if (numeric.length === 0) return column.map(cellText);
+  const decimals = Math.min(
+    4,
+    Math.max(...numeric.map((value) => (String(value).split(".")[1] ?? "").length))
+  );
+  return column.map((value) =>
+    typeof value === "number" && Number.isFinite(value)
+      ? value.toLocaleString("en-US", {
+          minimumFractionDigits: decimals,
+          maximumFractionDigits: decimals,
+        })
+      : cellText(value)
+  );
+}
+
+function truncate(text, width) {
+  const maxChars = Math.round((width - CELL_PADDING_X * 2) / CHAR_WIDTH);
+  return text.length > maxChars ? `${text.slice(0, Math.max(1, maxChars - 1))}…` : text;
+}
+
+/**
+ * Read `data` as either a rows × cols array or an object keyed by row then
+ * column label. Missing cells come back empty.
+ */
+export function normalizeTableData(rows, cols, data) {
+  return rows.map((row, r) =>
+    cols.map((col, c) => {
+      if (Array.isArray(data)) {
+        const line = data[r];
+        return Array.isArray(line) ? line[c] : undefined;
+      }
+      const line = data && typeof data === "object" ? data[row] : undefined;
+      return line && typeof line === "object" ? line[col] : undefined;
+    })
+  );
+}
+
+/**
+ * Lay out a comparison table as Excalidraw element specs for
+ * `batch_create_elements`: a header row of `cols`, one labelled row per entry
+ * in `rows`, with column widths fitted to the longest text.
+ */
+export function layoutComparisonTable({ rows, cols, data, title, x = 0, y = 0 }) {
+  const values = normalizeTableData(rows, cols, data);
+  const columns = cols.map((_, c) => alignNumbers(values.map((line) => line[c])));
+  const grid = [
+    ["", ...cols.map(cellText)],
+    ...rows.map((row, r) => [cellText(row), ...columns.map((column) => column[r])]),
+  ];
+
+  const widths = grid[0].map((_, c) =>
+    Math.round(
+      Math.min(
+        MAX_COLUMN_WIDTH,
+        Math.max(
+          MIN_COLUMN_WIDTH,
+          ...grid.map((line) => line[c].length * CHAR_WIDTH + CELL_PADDING_X * 2)
+        )
+      )
+    )
+  );
+
+  const elements = [];
+  let top = y;
+  if (title) {
+    elements.push({
+      type: "text",
+      x,
+      y: top,
+      text: cellText(title),
+      fontSize: FONT_SIZE + 6,
+      strokeColor: "#1e1e1e",
+    });
+    top += FONT_SIZE + 6 + TITLE_GAP;
+  }
+
+  grid.forEach((line, r) => {
+    let left = x;
+    line.forEach((text, c) => {
+      const header = r === 0;
+      const rowLabel = c === 0 && !header;
+      const fill = header
+        ? HEADER_FILL
+        : rowLabel
+          ? ROW_LABEL_FILL
+          : r % 2 === 0
+            ? STRIPE_FILL
+            : CELL_FILL;
+      elements.push({
+        type: "rectangle",
+        x: left,
+        y: top + r * ROW_HEIGHT,
+        width: widths[c],
+        height: ROW_HEIGHT,
+        backgroundColor: fill,
+        fillStyle: "solid",
+        strokeColor: STROKE,
+        strokeWidth: 1,
+        roughness: 0,
+        roundness: null,
+        text: truncate(text, widths[c]),
+        fontSize: FONT_SIZE,
+        textAlign: !header && !rowLabel && isNumeric(text) ? "right" : "center",
+      });
+      left += widths[c];
+    });
+  });
+
+  return {
+    elements,
+    width: widths.reduce((sum, width) => sum + width, 0),
+    height: top - y + grid.length * ROW_HEIGHT,
+  };
+}
+
+/** Top-left corner below everything already on the canvas. */
+export function placementBelow(existing) {
+  const boxes = existing.filter(
+    (element) => Number.isFinite(Number(element?.x)) && Number.isFinite(Number(element?.y))
+  );
+  if (boxes.length === 0) return { x: 0, y: 0 };
+  const left = Math.min(...boxes.map((element) => Number(element.x)));
+  const bottom = Math.max(
+    ...boxes.map((element) => Number(element.y) + (Number(element.height) || 0))
+  );
+  return { x: Math.round(left), y: Math.round(bottom + PLACEMENT_GAP) };
+}
+
+/**
+ * `create_comparison_table` tool backed by the server's
+ * `batch_create_elements`. `getExisting` returns the current canvas
+ * elements so the table lands in empty space.
+ */
+export function createComparisonTableTool({ batchTool, getExisting }) {
+  return tool({
+    description:
+      "Draw a neatly aligned comparison table (header row, labelled rows, fitted column widths) in one call. Use this for any tabular or side-by-side data instead of drawing cells one by one.",
+    inputSchema: jsonSchema({
+      type: "object",
+      properties: {
+        title: { type: "string", description: "Optional caption above the table." },
+        rows: { type: "array", items: { type: "string" }, description: "Row labels, e.g. product names." },
+        cols: { type: "array", items: { type: "string" }, description: "Column headers, e.g. attributes." },
+        data: {
+          description:
+            "Cell values as a rows × cols array, or an object keyed by row label then column header.",
+          anyOf: [
+            { type: "array", items: { type: "array", items: {} } },
+            { type: "object", additionalProperties: { type: "object" } },
+          ],
+        },
+        x: { type: "number", description: "Optional left edge; defaults to below existing content." },
+        y: { type: "number", description: "Optional top edge." },
+      },
+      required: ["rows", "cols", "data"],
+    }),
+    async execute({ title, rows, cols, data, x, y }, options) {
+      const rowLabels = Array.isArray(rows) ? rows : [];
+      const colLabels = Array.isArray(cols) ? cols : [];
+      if (rowLabels.length === 0 || colLabels.length === 0) {
+        return { content: [{ type: "text", text: "create_comparison_table needs rows and cols." }], isError: true };
+      }
+      const origin =
+        Number.isFinite(x) && Number.isFinite(y) ? { x, y } : placementBelow(await getExisting());
+      const layout = layoutComparisonTable({ rows: rowLabels, cols: colLabels, data, title, ...origin });
+      console.error(
+        `[excalidraw] comparison table ${rowLabels.length}x${colLabels.length} at (${origin.x}, ${origin.y})`
+      );
+      return batchTool.execute.call(batchTool, { elements: layout.elements }, options);
+    },
+  });
+}
@@ -0,0 +1,186 @@
+// Read side of the Context7 documentation cache. The Rust layer owns the
+// cache (src-tauri/src/docs_cache.rs): it writes entries from finished agent
+// runs, prunes them, and passes the location and TTL through the env below.
+// Key and hash must match `entry_key` / `key_hash` there.
+
+import fs from "node:fs";
+import path from "node:path";
+import process from "node:process";
+
+import { jsonSchema, tool } from "ai";
+
+const DOC_TOOL_NAMES = ["get-library-docs", "query-docs"];
+
+const cacheDir = String(process.env.HEYJAMIE_DOCS_CACHE_DIR ?? "").trim();
+const ttlMs = Number.parseInt(process.env.HEYJAMIE_DOCS_CACHE_TTL_MS ?? "", 10) || 7 * 24 * 3600 * 1000;
+export const docsOfflineMode = process.env.HEYJAMIE_DOCS_OFFLINE === "1";
+
+export function isContext7Server(name, server) {
+  const haystack = [name, server?.command, server?.url, ...(Array.isArray(server?.args) ? server.args : [])]
+    .map((value) => String(value ?? "").toLowerCase())
+    .join(" ");
+  return haystack.includes("context7");
+}
+
+function parseLibraryId(id) {
+  const trimmed = String(id ?? "").trim();
+  const at = trimmed.lastIndexOf("@");
+  if (at > 0) {
+    return { library: trimmed.slice(0, at), version: trimmed.slice(at + 1) };
+  }
+  const parts = trimmed.replace(/^\/+/, "").split("/");
+  if (parts.length > 2) {
+    return { library: `/${parts.slice(0, 2).join("/")}`, version: parts.slice(2).join("/") };
+  }
+  return { library: trimmed, version: null };
+}
+
+function entryKey(library, version, topic) {
+  return `${library.trim().toLowerCase()}@${(version ?? "latest").trim().toLowerCase()}#${String(
+    topic ?? ""
+  )
+    .trim()
+    .toLowerCase()}`;
+}
+
+function keyHash(key) {
+  let hash = 0x811c9dc5;
+  for (const byte of new TextEncoder().encode(key)) {
+    hash ^= byte;
+    hash = Math.imul(hash, 0x01000193) >>> 0;
+  }
+  return hash.toString(16).padStart(8, "0");
+}
+
+function lookupArgs(args) {
+  const id = args?.context7CompatibleLibraryID ?? args?.libraryId ?? "";
+  const topic = args?.topic ?? args?.query ?? "";
+  return { ...parseLibraryId(id), topic };
+}
+
+function readEntry(file) {
+  try {
+    return JSON.parse(fs.readFileSync(file, "utf-8"));
+  } catch {
+    return null;
+  }
+}
+
+function readAllEntries() {
+  try {
+    return fs
+      .readdirSync(cacheDir)
+      .filter((file) => file.endsWith(".json"))
+      .map((file) => readEntry(path.join(cacheDir, file)))
+      .filter(Boolean);
+  } catch {
+    return [];
+  }
+}
+
+function findEntry(args, { anyTopic = false } = {}) {
+  const { library, version, topic } = lookupArgs(args);
+  if (!library) return null;
+  const key = entryKey(library, version, topic);
+  const exact = readEntry(path.join(cacheDir, `${keyHash(key)}.json`));
+  if (exact?.key === key) return exact;
+  if (!anyTopic) return null;
+  // Offline: any cached page for the library beats nothing.
+  return (
+    readAllEntries()
+      .filter((entry) => entry.library?.toLowerCase() === library.toLowerCase())
+      .sort((a, b) => (b.fetchedAt ?? 0) - (a.fetchedAt ?? 0))[0] ?? null
+  );
+}
+
+function cachedResult(entry, extra = {}) {
+  const output = entry.output && typeof entry.output === "object" ? entry.output : { content: [] };
+  return { ...output, cached: true, fetchedAt: entry.fetchedAt, ...extra };
+}
+
+function textResult(text) {
+  return { content: [{ type: "text", text }], cached: true };
+}
+
+/**
+ * Serve fresh cache hits without a round trip, fall back to stale entries
+ * when the live tool fails, and register cache-only stand-ins when Context7
+ * is configured but could not be reached (or offline mode is on).
+ */
+export function installDocsCache(tools, { context7Configured }) {
+  if (!cacheDir) return;
+
+  let hasLiveDocsTool = false;
+  for (const [name, original] of Object.entries(tools)) {
+    const baseName = name.split(":").pop();
+    if (!DOC_TOOL_NAMES.includes(baseName) || typeof original?.execute !== "function") continue;
+    hasLiveDocsTool = true;
+    tools[name] = {
+      ...original,
+      async execute(args, ...rest) {
+        const entry = findEntry(args);
+        if (entry && Date.now() - (entry.fetchedAt ?? 0) < ttlMs) {
+          console.error(`[docs-cache] hit ${entry.key}`);
+          return cachedResult(entry);
+        }
+        try {
+          return await original.execute.call(original, args, ...rest);
+        } catch (error) {
+          if (entry) {
+            console.error(`[docs-cache] live lookup failed, serving stale ${entry.key}`);
+            return cachedResult(entry, { stale: true });
+          }
+          throw error;
+        }
+      },
+    };
+  }
+
+  if (hasLiveDocsTool || !context7Configured) return;
+
+  console.error(
+    `[docs-cache] Context7 ${docsOfflineMode ? "offline mode" : "unreachable"}; serving cached docs only`
+  );
+  tools["resolve-library-id"] = tool({
+    description:
+      "Find Context7-compatible library IDs among cached documentation (offline). Returns matching IDs.",
+    inputSchema: jsonSchema({
+      type: "object",
+      properties: { libraryName: { type: "string" } },
+      required: ["libraryName"],
+    }),
+    async execute({ libraryName }) {
+      const needle = String(libraryName ?? "").toLowerCase();
+      const ids = [
+        ...new Set(
+          readAllEntries()
+            .filter((entry) => entry.library?.toLowerCase().includes(needle))
+            .map((entry) => (entry.version ? `${entry.library}/${entry.version}` : entry.library))
+        ),
+      ];
+      return textResult(
+        ids.length > 0
+          ? `Cached libraries (offline):\n${ids.map((id) => `- ${id}`).join("\n")}`
+          : `No cached documentation for "${libraryName}" while offline.`
+      );
+    },
+  });
+  tools["get-library-docs"] = tool({
+    description:
+      "Fetch cached documentation for a Context7-compatible library ID (offline; may be out of date).",
+    inputSchema: jsonSchema({
+      type: "object",
+      properties: {
+        context7CompatibleLibraryID: { type: "string" },
+        topic: { type: "string" },
+      },
+      required: ["context7CompatibleLibraryID"],
+    }),
+    async execute(args) {
+      const entry = findEntry(args, { anyTopic: true });
+      return entry
+        ? cachedResult(entry, { stale: true })
+        : textResult(`No cached documentation for ${args?.context7CompatibleLibraryID} while offline.`);
+    },
+  });
+}
@@ -0,0 +1,110 @@
+// Text embeddings for similarity checks (canvas deduplication).
+//
+// Uses the OpenAI-compatible `/embeddings` endpoint of the configured LLM
+// provider and falls back to local character-trigram vectors when that is
+// unavailable, so deduplication still works offline. The two backends score
+// differently, so callers get thresholds from `similarityThresholds`.
+
+const DEFAULT_EMBEDDING_BASE_URL = "https://openrouter.ai/api/v1";
+const DEFAULT_EMBEDDING_MODEL = "openai/text-embedding-3-small";
+const TRIGRAM_DIMENSIONS = 512;
+
+export const similarityThresholds = {
+  api: { duplicate: 0.93, related: 0.85 },
+  trigram: { duplicate: 0.85, related: 0.65 },
+};
+
+function normalizeText(text) {
+  return String(text ?? "")
+    .toLowerCase()
+    .replace(/[^\p{L}\p{N}\s]/gu, " ")
+    .replace(/\s+/g, " ")
+    .trim();
+}
+
+function hashTrigram(trigram) {
+  let hash = 2166136261;
+  for (let i = 0; i < trigram.length; i += 1) {
+    hash ^= trigram.charCodeAt(i);
+    hash = Math.imul(hash, 16777619);
+  }
+  return (hash >>> 0) % TRIGRAM_DIMENSIONS;
+}
+
+export function trigramVector(text) {
+  const vector = new Array(TRIGRAM_DIMENSIONS).fill(0);
+  const padded = ` ${normalizeText(text)} `;
+  for (let i = 0; i + 3 <= padded.length; i += 1) {
+    vector[hashTrigram(padded.slice(i, i + 3))] += 1;
+  }
+  return vector;
+}
+
+export function cosineSimilarity(a, b) {
+  let dot = 0;
+  let normA = 0;
+  let normB = 0;
+  const length = Math.min(a.length, b.length);
+  for (let i = 0; i < length; i += 1) {
+    dot += a[i] * b[i];
+    normA += a[i] * a[i];
+    normB += b[i] * b[i];
+  }
+  if (normA === 0 || normB === 0) return 0;
+  return dot / Math.sqrt(normA * normB);
+}
+
+/**
+ * Create an embedder bound to one provider. `embed(texts)` resolves to
+ * `{ backend: "api" | "trigram", vectors }`; once the API fails the embedder
+ * stays on trigrams so a run never mixes vector spaces.
+ */
+export function createEmbedder({ apiKey, baseUrl, model, signal } = {}) {
+  const endpoint = `${(baseUrl || DEFAULT_EMBEDDING_BASE_URL).replace(/\/+$/, "")}/embeddings`;
+  const modelName =
+    String(process.env.HEYJAMIE_EMBEDDING_MODEL ?? "").trim() || model || DEFAULT_EMBEDDING_MODEL;
+  let useApi = Boolean(apiKey);
+
+  async function embedViaApi(texts) {
+    const response = await fetch(endpoint, {
+      method: "POST",
+      headers: {
+        Authorization: `Bearer ${apiKey}`,
+        "Content-Type": "application/json",
+      },
+      body: JSON.stringify({ model: modelName, input: texts }),
+      signal,
+    });
+    if (!response.ok) {
+      throw new Error(`embeddings HTTP ${response.status}`);
+    }
+    const body = await response.json();
+    const data = Array.isArray(body?.data) ? body.data : [];
+    if (data.length !== texts.length) {
+      throw new Error("embeddings response size mismatch");
+    }
+    return data
+      .slice()
+      .sort((a, b) => (a.index ?? 0) - (b.index ?? 0))
+      .map((item) => item.embedding);
+  }
+
+  return {
+    async embed(texts) {
+      if (texts.length === 0) {
+        return { backend: useApi ? "api" : "trigram", vectors: [] };
+      }
+      if (useApi) {
+        try {
+          return { backend: "api", vectors: await embedViaApi(texts) };
+        } catch (error) {
+          console.error(
+            `[embeddings] ${modelName} unavailable (${error?.message || error}); using local trigram vectors`
+          );
+          useApi = false;
+        }
+      }
+      return { backend: "trigram", vectors: texts.map(trigramVector) };
+    },
+  };
+}
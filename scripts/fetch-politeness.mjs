@@ -0,0 +1,191 @@
+// Politeness for the agent's browser navigation: per-domain spacing and
+// concurrency caps, and robots.txt checks. Settings come from the Rust
+// layer (src-tauri/src/politeness.rs `agent_env`), which applies the same
+// rules to the backend's own fetches. Navigation times are shared between
+// agent processes through a small state file.
+
+import fs from "node:fs";
+import os from "node:os";
+import path from "node:path";
+import process from "node:process";
+
+const NAVIGATION_TOOL_NAMES = ["navigate_page", "new_page"];
+const ROBOTS_AGENT = "heyjamie";
+const ROBOTS_TIMEOUT_MS = 5_000;
+const MAX_CRAWL_DELAY_MS = 30_000;
+const SLOT_POLL_MS = 100;
+const STATE_FILE = path.join(os.tmpdir(), "heyjamie-fetch-politeness.json");
+
+const minIntervalMs = Number.parseInt(process.env.HEYJAMIE_FETCH_MIN_INTERVAL_MS ?? "", 10) || 1000;
+const maxConcurrent = Math.max(1, Number.parseInt(process.env.HEYJAMIE_FETCH_MAX_CONCURRENT ?? "", 10) || 2);
+const respectRobots = process.env.HEYJAMIE_FETCH_ROBOTS !== "0";
+
+const robotsCache = new Map();
+const inFlight = new Map();
+
+function sleep(ms) {
+  return new Promise((resolve) => setTimeout(resolve, ms));
+}
+
+function readState() {
+  try {
+    return JSON.parse(fs.readFileSync(STATE_FILE, "utf-8"));
+  } catch {
+    return {};
+  }
+}
+
+function writeState(state) {
+  try {
+    fs.writeFileSync(STATE_FILE, JSON.stringify(state), "utf-8");
+  } catch {
+    // Best effort: spacing still applies within this process.
+  }
+}
+
+// Same grouping and matching rules as `parse_robots` / `Robots::allows`.
+function parseRobots(content) {
+  const groups = [];
+  let inAgentLines = false;
+  for (const raw of content.split(/\r?\n/)) {
+    const line = raw.split("#")[0].trim();
+    const colon = line.indexOf(":");
+    if (colon < 0) continue;
+    const key = line.slice(0, colon).trim().toLowerCase();
+    const value = line.slice(colon + 1).trim();
+    if (key === "user-agent") {
+      if (!inAgentLines || groups.length === 0) {
+        groups.push({ agents: [], rules: [], crawlDelayMs: null });
+      }
+      groups[groups.length - 1].agents.push(value.toLowerCase());
+      inAgentLines = true;
+    } else if (key === "allow" || key === "disallow") {
+      inAgentLines = false;
+      if (groups.length > 0 && value) {
+        groups[groups.length - 1].rules.push({ allow: key === "allow", pattern: value });
+      }
+    } else if (key === "crawl-delay") {
+      inAgentLines = false;
+      const seconds = Number.parseFloat(value);
+      if (groups.length > 0 && Number.isFinite(seconds)) {
+        groups[groups.length - 1].crawlDelayMs = Math.min(Math.max(seconds, 0) * 1000, MAX_CRAWL_DELAY_MS);
+      }
+    } else {
+      inAgentLines = false;
+    }
+  }
+  return (
+    groups.find((group) => group.agents.some((agent) => agent.includes(ROBOTS_AGENT))) ??
+    groups.find((group) => group.agents.includes("*")) ?? { rules: [], crawlDelayMs: null }
+  );
+}
+
+function patternMatches(rawPattern, target) {
+  const anchored = rawPattern.endsWith("$");
+  const pattern = anchored ? rawPattern.slice(0, -1) : rawPattern;
+  const [first, ...parts] = pattern.split("*");
+  if (!target.startsWith(first)) return false;
+  let rest = target.slice(first.length);
+  for (let i = 0; i < parts.length; i += 1) {
+    if (anchored && i === parts.length - 1) return rest.endsWith(parts[i]);
+    const at = rest.indexOf(parts[i]);
+    if (at < 0) return false;
+    rest = rest.slice(at + parts[i].length);
+  }
+  return !anchored || rest.length === 0;
+}
+
+function robotsAllow(robots, target) {
+  let best = null;
+  for (const rule of robots.rules) {
+    if (!patternMatches(rule.pattern, target)) continue;
+    const better =
+      !best ||
+      rule.pattern.length > best.pattern.length ||
+      (rule.pattern.length === best.pattern.length && rule.allow);
+    if (better) best = rule;
+  }
+  return !best || best.allow;
+}
+
+async function robotsFor(url) {
+  const cached = robotsCache.get(url.origin);
+  if (cached) return cached;
+  let content = "";
+  try {
+    const response = await fetch(`${url.origin}/robots.txt`, {
+      signal: AbortSignal.timeout(ROBOTS_TIMEOUT_MS),
+    });
+    if (response.ok) content = await response.text();
+  } catch {
+    // Missing or unreachable robots.txt: nothing is disallowed.
+  }
+  const robots = parseRobots(content);
+  robotsCache.set(url.origin, robots);
+  return robots;
+}
+
+function hostKey(url) {
+  return url.hostname.replace(/^www\./, "").toLowerCase();
+}
+
+/**
+ * Wait for a navigation slot on the URL's domain. Returns a release
+ * function, or an error message when robots.txt disallows the page.
+ */
+async function acquire(rawUrl) {
+  let url;
+  try {
+    url = new URL(rawUrl);
+  } catch {
+    return { release: () => {} };
+  }
+  if (url.protocol !== "http:" && url.protocol !== "https:") {
+    return { release: () => {} };
+  }
+  const host = hostKey(url);
+  let intervalMs = minIntervalMs;
+  if (respectRobots) {
+    const robots = await robotsFor(url);
+    if (!robotsAllow(robots, `${url.pathname}${url.search}`)) {
+      return { error: `${host} disallows automated visits to ${url.pathname} (robots.txt).` };
+    }
+    intervalMs = Math.max(intervalMs, robots.crawlDelayMs ?? 0);
+  }
+  for (;;) {
+    const state = readState();
+    const waitMs = (state[host] ?? 0) + intervalMs - Date.now();
+    if ((inFlight.get(host) ?? 0) < maxConcurrent && waitMs <= 0) {
+      state[host] = Date.now();
+      writeState(state);
+      inFlight.set(host, (inFlight.get(host) ?? 0) + 1);
+      return { release: () => inFlight.set(host, Math.max(0, (inFlight.get(host) ?? 1) - 1)) };
+    }
+    await sleep(Math.max(SLOT_POLL_MS, waitMs));
+  }
+}
+
+/**
+ * Space out and robots-check navigations made through the browser tools.
+ */
+export function installFetchPoliteness(tools) {
+  for (const [name, original] of Object.entries(tools)) {
+    const baseName = name.split(":").pop();
+    if (!NAVIGATION_TOOL_NAMES.includes(baseName) || typeof original?.execute !== "function") continue;
+    tools[name] = {
+      ...original,
+      async execute(args, ...rest) {
+        const slot = await acquire(args?.url);
+        if (slot.error) {
+          console.error(`[politeness] ${slot.error}`);
+          return { content: [{ type: "text", text: slot.error }], isError: true };
+        }
+        try {
+          return await original.execute.call(original, args, ...rest);
+        } finally {
+          slot.release();
+        }
+      },
+    };
+  }
+}
@@ -12,6 +12,11 @@ import { createOpenRouter } from "@openrouter/ai-sdk-provider";
 import { experimental_createMCPClient as createMCPClient } from "@ai-sdk/mcp";
 import { Experimental_StdioMCPTransport } from "@ai-sdk/mcp/mcp-stdio";
 
+import { createComparisonTableTool } from "./canvas-table.mjs";
+import { docsOfflineMode, installDocsCache, isContext7Server } from "./docs-cache.mjs";
+import { cosineSimilarity, createEmbedder, similarityThresholds } from "./embeddings.mjs";
+import { installFetchPoliteness } from "./fetch-politeness.mjs";
+
 const DEFAULT_MCP_LOAD_TIMEOUT_MS = 12_000;
 const DEFAULT_AGENT_TIMEOUT_MS = 45_000;
 const DEFAULT_INTENT_TIMEOUT_MS = 90_000;
@@ -81,11 +86,15 @@ const topicShiftGenerateTimeoutMs = parseEnvInt(
   2000
 );
 const excalidrawMaxSteps = parseEnvInt("HEYJAMIE_EXCALIDRAW_MAX_STEPS", 12, 1);
+// Per-run limits that stop runaway tool loops; see `createToolBudget`.
+const maxToolCallsPerRun = parseEnvInt("HEYJAMIE_MAX_TOOL_CALLS", 40, 1);
+const toolCallTimeoutMs = parseEnvInt("HEYJAMIE_TOOL_CALL_TIMEOUT_MS", 60_000, 1000);
 
 const EXCALIDRAW_ACTIVE_TOOLS = new Set([
   "read_diagram_guide",
   "create_element",
   "batch_create_elements",
+  "create_comparison_table",
   "update_element",
   "delete_element",
   "query_elements",
@@ -271,6 +280,47 @@ function resolveOpenRouterApiKey(configuredApiKey) {
   };
 }
 
+/**
+ * Build the OpenRouter provider, honoring the endpoint the Rust layer picked
+ * from the LLM failover chain (`settings.baseUrl`). Any OpenAI-compatible
+ * chat completions endpoint works, e.g. a local Ollama server.
+ */
+/**
+ * Append the reply-language hint chosen by the Rust layer (from the
+ * speaker's language or the show's setting). Tool use stays in English so
+ * MCP arguments and search queries behave the same in every language.
+ */
+function withResponseLanguage(instructions, payload) {
+  instructions = withArtifactsDir(instructions);
+  const language = isObject(payload?.responseLanguage) ? payload.responseLanguage : null;
+  const name = asString(language?.name).trim();
+  if (!name || asString(language?.code) === "en") return instructions;
+  return [
+    instructions,
+    `The user is speaking ${name}. Write every message meant for the user in ${name}.`,
+    "Keep tool calls, tool arguments, and your internal reasoning in English.",
+  ]
+    .filter(Boolean)
+    .join("\n\n");
+}
+
+/**
+ * The active session's artifacts folder, set by the Rust layer. Files the
+ * run saves (screenshots, downloads, clips) go there instead of temp or
+ * Downloads, so they end up in the session bundle.
+ */
+const artifactsDir = asString(process.env.HEYJAMIE_ARTIFACTS_DIR).trim();
+
+function withArtifactsDir(instructions) {
+  if (!artifactsDir || !instructions) return instructions;
+  return `${instructions}\n\nWhen a tool saves a file (screenshots, downloads, exports), save it in ${artifactsDir}.`;
+}
+
+function createModelProvider(settings, apiKey) {
+  const baseUrl = asString(settings?.baseUrl).trim();
+  return createOpenRouter(baseUrl ? { apiKey, baseURL: baseUrl } : { apiKey });
+}
+
 function normalizePlannerSuggestionType(value) {
   const normalized = asString(value).trim().toLowerCase();
   if (normalized === "search") return "Search";
@@ -725,19 +775,84 @@ function isServerEnabled(server) {
   return server.enabled !== false;
 }
 
+function toolPatternList(server, key) {
+  const list = isObject(server) && Array.isArray(server[key]) ? server[key] : [];
+  return list.map((entry) => asString(entry).trim()).filter(Boolean);
+}
+
+// `*` matches any run of characters, e.g. "evaluate_*".
+function matchesToolPattern(toolName, pattern) {
+  const escaped = pattern.replace(/[.+?^${}()|[\]\\]/g, "\\$&").replace(/\*/g, ".*");
+  return new RegExp(`^${escaped}$`, "i").test(toolName);
+}
+
+// Per-server `allowTools` / `denyTools` in mcp.json. A non-empty allow list
+// exposes only matching tools; the deny list always wins.
+function isToolAllowed(server, toolName) {
+  const allow = toolPatternList(server, "allowTools");
+  const deny = toolPatternList(server, "denyTools");
+  if (deny.some((pattern) => matchesToolPattern(toolName, pattern))) return false;
+  return allow.length === 0 || allow.some((pattern) => matchesToolPattern(toolName, pattern));
+}
+
+// `${account:<provider>}` in an env value or header is replaced with the
+// OAuth token the Rust side resolved for that connected account, so MCP
+// servers can use signed-in accounts instead of pasted API keys.
+function resolveAccountPlaceholders(record) {
+  if (!isObject(record)) return record;
+  return Object.fromEntries(
+    Object.entries(record).map(([key, value]) => [
+      key,
+      typeof value === "string"
+        ? value.replace(/\$\{account:([a-z0-9_-]+)\}/gi, (_, provider) =>
+            asString(process.env[`HEYJAMIE_ACCOUNT_TOKEN_${provider.toUpperCase().replace(/-/g, "_")}`])
+          )
+        : value,
+    ])
+  );
+}
+
+// Trace ID of the interaction this run belongs to, set from the request.
+// It goes to MCP servers as `HEYJAMIE_TRACE_ID` (stdio), an `X-Trace-Id`
+// header (http/sse) and `_meta.traceId` on every tool call, so their logs
+// can be matched with the app log.
+let traceId = "";
+
+function attachTraceMetadata(client) {
+  if (!traceId || typeof client?.request !== "function") return client;
+  const request = client.request.bind(client);
+  client.request = (args) => {
+    if (args?.request?.method !== "tools/call") return request(args);
+    const params = isObject(args.request.params) ? args.request.params : {};
+    const meta = isObject(params._meta) ? params._meta : {};
+    return request({
+      ...args,
+      request: { ...args.request, params: { ...params, _meta: { ...meta, traceId } } },
+    });
+  };
+  return client;
+}
+
 async function createClientForServer(name, server) {
   if (!isObject(server)) {
     throw new Error(`Invalid config for server "${name}".`);
   }
 
   if (typeof server.command === "string" && server.command.trim()) {
+    const env = isObject(server.env) ? resolveAccountPlaceholders(server.env) : undefined;
     const transport = new Experimental_StdioMCPTransport({
       command: server.command,
       args: Array.isArray(server.args) ? server.args : [],
-      env: isObject(server.env) ? server.env : undefined,
+      env: traceId || artifactsDir
+        ? {
+            ...env,
+            ...(traceId ? { HEYJAMIE_TRACE_ID: traceId } : {}),
+            ...(artifactsDir ? { HEYJAMIE_ARTIFACTS_DIR: artifactsDir } : {}),
+          }
+        : env,
       cwd: typeof server.cwd === "string" ? server.cwd : undefined,
     });
-    const client = await createMCPClient({ transport });
+    const client = attachTraceMetadata(await createMCPClient({ transport }));
     return {
       client,
       transport: "stdio",
@@ -749,21 +864,26 @@ async function createClientForServer(name, server) {
     const transportTypeRaw = asString(server.transport).trim().toLowerCase();
     const transportType = transportTypeRaw === "sse" ? "sse" : "http";
 
-    const headers = isObject(server.headers)
+    let headers = isObject(server.headers)
       ? Object.fromEntries(
-          Object.entries(server.headers)
+          Object.entries(resolveAccountPlaceholders(server.headers))
             .filter(([, value]) => typeof value === "string")
             .map(([key, value]) => [key, asString(value)])
         )
       : undefined;
+    if (traceId) {
+      headers = { ...headers, "X-Trace-Id": traceId };
+    }
 
-    const client = await createMCPClient({
-      transport: {
-        type: transportType,
-        url,
-        headers,
-      },
-    });
+    const client = attachTraceMetadata(
+      await createMCPClient({
+        transport: {
+          type: transportType,
+          url,
+          headers,
+        },
+      })
+    );
 
     return {
       client,
@@ -796,6 +916,7 @@ async function loadMcpTools(configPath, options = {}) {
   let browserOsConnected = false;
   let excalidrawConnected = false;
   let excalidrawCanvasUrl = "";
+  let context7Configured = false;
 
   for (let [name, server] of Object.entries(servers)) {
     const isBrowserOs = isBrowserOsServer(name, server);
@@ -827,6 +948,18 @@ async function loadMcpTools(configPath, options = {}) {
       });
       continue;
     }
+    if (isContext7Server(name, server)) {
+      context7Configured = true;
+      if (docsOfflineMode) {
+        serverSummaries.push({
+          name,
+          skipped: true,
+          reason: "docsOffline",
+          browserOs: false,
+        });
+        continue;
+      }
+    }
 
     // For chrome-devtools-mcp: strip --isolated and --auto-connect (we handle
     // Chrome reuse ourselves), then check if Chrome is already running from a
@@ -923,7 +1056,12 @@ async function loadMcpTools(configPath, options = {}) {
     if (activeClient && activeTools) {
       clients.push(activeClient);
       activeMcpClients.add(activeClient);
+      const blockedTools = [];
       for (const [toolName, tool] of Object.entries(activeTools)) {
+        if (!isToolAllowed(server, toolName)) {
+          blockedTools.push(toolName);
+          continue;
+        }
         const wrappedTool = isBrowserOs ? wrapToolWithOutputSanitizer(tool) : tool;
         if (!tools[toolName]) {
           tools[toolName] = wrappedTool;
@@ -934,12 +1072,17 @@ async function loadMcpTools(configPath, options = {}) {
         }
       }
 
+      if (blockedTools.length > 0) {
+        console.error(`[mcp] ${name}: tools disabled by config: ${blockedTools.join(", ")}`);
+      }
+
       const configuredUrl = asString(server?.url).trim();
       const resolvedUrl = asString(activeServer?.url).trim();
       serverSummaries.push({
         name,
         transport: activeTransport,
-        toolCount: Object.keys(activeTools).length,
+        toolCount: Object.keys(activeTools).length - blockedTools.length,
+        blockedTools: blockedTools.length > 0 ? blockedTools : undefined,
         browserOs: isBrowserOs,
         resolvedUrl: resolvedUrl || undefined,
         usedFallback:
@@ -970,6 +1113,9 @@ async function loadMcpTools(configPath, options = {}) {
     console.error(`[mcp] failed to load "${name}": ${failureMessage}`);
   }
 
+  installDocsCache(tools, { context7Configured });
+  installFetchPoliteness(tools);
+
   if (options.requireBrowserOs && !browserOsConnected) {
     throw new Error(
       "Chrome DevTools MCP server is not connected. Add a Chrome DevTools MCP server in Settings -> MCP Config (e.g., name \"chrome-devtools\" with command \"npx\" and args [\"-y\", \"chrome-devtools-mcp@latest\"])."
@@ -1046,6 +1192,102 @@ function buildBrowserOsPrompt(payload) {
   return lines.join("\n");
 }
 
+/**
+ * Shared tool-call budget for one agent run: at most `maxCalls` calls, each
+ * given `callTimeoutMs`. The first breach is recorded in `exceeded`, later
+ * calls are refused, and `stopWhen` ends the loop after the current step.
+ */
+function createToolBudget({ maxCalls = maxToolCallsPerRun, callTimeoutMs = toolCallTimeoutMs } = {}) {
+  const budget = {
+    maxCalls,
+    callTimeoutMs,
+    calls: 0,
+    exceeded: null,
+    stopWhen: () => Boolean(budget.exceeded),
+    report() {
+      return {
+        maxToolCalls: maxCalls,
+        toolCallTimeoutMs: callTimeoutMs,
+        toolCallsUsed: budget.calls,
+        exceeded: budget.exceeded,
+      };
+    },
+  };
+  return budget;
+}
+
+function budgetExceededOutput(budget) {
+  return {
+    content: [
+      {
+        type: "text",
+        text: `Tool budget exceeded (${budget.exceeded.reason}). Stop calling tools and report what you have so far.`,
+      },
+    ],
+    isError: true,
+  };
+}
+
+function applyToolBudget(tools, budget) {
+  for (const [name, original] of Object.entries(tools)) {
+    if (!isObject(original) || typeof original.execute !== "function") continue;
+    tools[name] = {
+      ...original,
+      async execute(...args) {
+        if (budget.exceeded) return budgetExceededOutput(budget);
+        if (budget.calls >= budget.maxCalls) {
+          budget.exceeded = { reason: "maxToolCalls", limit: budget.maxCalls, tool: name };
+          console.error(`[budget] ${budget.maxCalls} tool calls used; refusing ${name}`);
+          return budgetExceededOutput(budget);
+        }
+        budget.calls += 1;
+        try {
+          return await withTimeout(
+            Promise.resolve(original.execute.apply(original, args)),
+            budget.callTimeoutMs,
+            `tool ${name}`
+          );
+        } catch (error) {
+          if (!String(error?.message).startsWith(`tool ${name} timed out`)) throw error;
+          budget.exceeded = { reason: "toolTimeout", limit: budget.callTimeoutMs, tool: name };
+          console.error(`[budget] ${name} exceeded ${budget.callTimeoutMs}ms`);
+          return budgetExceededOutput(budget);
+        }
+      },
+    };
+  }
+}
+
+function budgetExceededMessage(budget, toolCallDetails) {
+  const { reason, limit, tool } = budget.exceeded;
+  const cause =
+    reason === "toolTimeout"
+      ? `${tool} ran longer than ${limit}ms`
+      : `the limit of ${limit} tool calls was reached`;
+  const done = [...new Set(toolCallDetails.map((call) => call.name).filter(Boolean))];
+  return `Stopped early: ${cause}.${done.length > 0 ? ` Completed: ${done.join(", ")}.` : ""}`;
+}
+
+// Token usage across every model call in this process, reported with the
+// result so the app can total cost per session.
+const runUsage = { inputTokens: 0, outputTokens: 0, costUsd: null };
+
+function trackUsage(result) {
+  const usage = isObject(result?.totalUsage) ? result.totalUsage : result?.usage;
+  runUsage.inputTokens += Number(usage?.inputTokens) || 0;
+  runUsage.outputTokens += Number(usage?.outputTokens) || 0;
+  const steps = Array.isArray(result?.steps) ? result.steps : [];
+  for (const step of steps) {
+    const cost = Number(step?.providerMetadata?.openrouter?.usage?.cost);
+    if (Number.isFinite(cost)) runUsage.costUsd = (runUsage.costUsd ?? 0) + cost;
+  }
+}
+
+function withUsage(result) {
+  if (!isObject(result)) return result;
+  return { ...result, usage: { ...runUsage }, ...(traceId ? { traceId } : {}) };
+}
+
 function summarizeToolCalls(result) {
   const steps = Array.isArray(result?.steps) ? result.steps : [];
   const toolCalls = steps.flatMap((step) =>
@@ -1455,7 +1697,7 @@ ${recentTranscript}
 
 Has the user shifted to a new topic?`;
 
-  const openrouter = createOpenRouter({ apiKey });
+  const openrouter = createModelProvider(settings, apiKey);
   const model =
     typeof openrouter.chat === "function"
       ? openrouter.chat(modelSelection.modelName)
@@ -1474,6 +1716,7 @@ Has the user shifted to a new topic?`;
       topicShiftGenerateTimeoutMs,
       "topic-shift-detect.generate"
     );
+    trackUsage(result);
   } catch (error) {
     return {
       ok: false,
@@ -1518,6 +1761,58 @@ Has the user shifted to a new topic?`;
   };
 }
 
+async function runTextPrompt(payload) {
+  const settings = isObject(payload?.settings) ? payload.settings : {};
+  const apiKeySelection = resolveOpenRouterApiKey(settings.apiKey);
+  const apiKey = apiKeySelection.apiKey;
+  if (!apiKey) {
+    return { ok: false, error: "Missing OpenRouter API key for the prompt." };
+  }
+
+  const modelSelection = resolveModel(settings);
+  if (!modelSelection.modelName) {
+    return { ok: false, error: "Missing model for the prompt." };
+  }
+
+  const prompt = asString(payload?.prompt).trim();
+  if (!prompt) {
+    return { ok: false, error: "Missing prompt text." };
+  }
+
+  const openrouter = createModelProvider(settings, apiKey);
+  const model =
+    typeof openrouter.chat === "function"
+      ? openrouter.chat(modelSelection.modelName)
+      : openrouter(modelSelection.modelName);
+
+  // No tools: this is a single completion, not an agent run.
+  const agent = new ToolLoopAgent({
+    model,
+    instructions: asString(payload?.instructions),
+    tools: {},
+  });
+
+  let result;
+  try {
+    result = await agent.generate({ prompt, abortSignal: globalAbort.signal });
+    trackUsage(result);
+  } catch (error) {
+    return {
+      ok: false,
+      error: `Prompt failed: ${error?.message || String(error)}`,
+      modelName: modelSelection.modelName,
+      modelSource: modelSelection.source,
+    };
+  }
+
+  return {
+    ok: true,
+    text: asString(result?.text).trim(),
+    modelName: modelSelection.modelName,
+    modelSource: modelSelection.source,
+  };
+}
+
 async function runBrowserOsIntentPlanner(payload) {
   const settings = isObject(payload?.settings) ? payload.settings : {};
   const apiKeySelection = resolveOpenRouterApiKey(settings.apiKey);
@@ -1549,7 +1844,7 @@ async function runBrowserOsIntentPlanner(payload) {
     };
   }
 
-  const openrouter = createOpenRouter({ apiKey });
+  const openrouter = createModelProvider(settings, apiKey);
   const model =
     typeof openrouter.chat === "function"
       ? openrouter.chat(modelSelection.modelName)
@@ -1566,6 +1861,7 @@ async function runBrowserOsIntentPlanner(payload) {
     intentGenerateTimeoutMs,
     "browseros-intent.generate"
   );
+  trackUsage(result);
 
   const text = asString(result?.text).trim();
   if (!text) {
@@ -1738,19 +2034,21 @@ async function runBrowserOsAutomation(payload) {
     };
   }
 
-  const openrouter = createOpenRouter({ apiKey });
+  const openrouter = createModelProvider(settings, apiKey);
   const model =
     typeof openrouter.chat === "function"
       ? openrouter.chat(modelName)
       : openrouter(modelName);
 
-  const instructions =
+  const instructions = withResponseLanguage(
     asString(payload?.instructions).trim() ||
-    [
-      "You are HeyJamie's browser automation copilot.",
-      "Use Chrome DevTools MCP tools to interact with the browser.",
-      "Be safe and avoid destructive actions.",
-    ].join(" ");
+      [
+        "You are HeyJamie's browser automation copilot.",
+        "Use Chrome DevTools MCP tools to interact with the browser.",
+        "Be safe and avoid destructive actions.",
+      ].join(" "),
+    payload
+  );
 
   const prompt = buildBrowserOsPrompt(payload);
 
@@ -1768,12 +2066,17 @@ async function runBrowserOsAutomation(payload) {
     // not just after the first navigate_page call.
     await activateCurrentPage(tools);
 
+    // Budget only the model's own calls; setup and snapshot use `tools`.
+    const budget = createToolBudget();
+    const budgetedTools = { ...tools };
+    applyToolBudget(budgetedTools, budget);
+
     const agent = new ToolLoopAgent({
       model,
       instructions,
-      tools,
+      tools: budgetedTools,
       activeTools: activeBrowserOsTools,
-      stopWhen: stepCountIs(browserOsMaxSteps),
+      stopWhen: [stepCountIs(browserOsMaxSteps), budget.stopWhen],
       maxOutputTokens: browserOsMaxOutputTokens,
     });
 
@@ -1782,6 +2085,7 @@ async function runBrowserOsAutomation(payload) {
       browserOsGenerateTimeoutMs,
       "browseros.generate"
     );
+    trackUsage(result);
 
     const text = asString(result?.text).trim();
     const parsedText = extractJsonPayload(text);
@@ -1803,6 +2107,7 @@ async function runBrowserOsAutomation(payload) {
       ? asString(parsedText.message || parsedText.summary).trim()
       : "";
     const message =
+      (budget.exceeded ? budgetExceededMessage(budget, toolCallDetails) : "") ||
       parsedMessage ||
       text ||
       (toolCallDetails.length > 0
@@ -1821,7 +2126,8 @@ async function runBrowserOsAutomation(payload) {
         ? parsedCompleted
         : toolCallDetails.length > 0 && toolErrors.length === 0;
 
-    const ok = completed && toolErrors.length === 0 && toolCallDetails.length > 0;
+    const ok =
+      completed && toolErrors.length === 0 && toolCallDetails.length > 0 && !budget.exceeded;
 
     const contentSnapshot = await captureContentSnapshot(tools, "browser");
 
@@ -1841,6 +2147,7 @@ async function runBrowserOsAutomation(payload) {
       fallbackApplied,
       llmToolCallCount: initialToolCallDetails.length,
       contentSnapshot,
+      budget: budget.report(),
     };
   } catch (error) {
     return {
@@ -1879,6 +2186,7 @@ function buildExcalidrawPrompt(payload) {
     "1. Call `read_diagram_guide` to learn best-practice color palettes, sizing rules, and layout patterns.",
     "2. If the user asks to modify, rename, or delete existing elements, first call `describe_scene` to get the current elements and their IDs.",
     "3. Use `batch_create_elements` (preferred for multiple elements) or `create_element` to add new elements. Use rectangles, arrows, text, ellipses, and diamonds as appropriate. Position elements clearly with enough spacing.",
+    "   For comparisons or any rows-and-columns data, call `create_comparison_table` with `rows`, `cols` and `data` instead of drawing cells yourself; it lays out an aligned grid in one step.",
     "4. Use `update_element` with the element ID to modify existing elements. When updating text, you MUST pass the complete new text value in the `text` property - do not pass empty strings. When updating position, pass `x` and `y`. Use `delete_element` with the element ID to remove elements.",
     "5. Optionally call `describe_scene` to verify the diagram looks correct.",
     "6. Call `set_viewport` with scrollToContent=true to auto-fit the diagram.",
@@ -1926,6 +2234,191 @@ function buildExcalidrawPrompt(payload) {
   return lines.join("\n");
 }
 
+// Shorter labels ("API", "Step 2") repeat legitimately in diagrams.
+const CANVAS_DEDUP_MIN_TEXT_LENGTH = 16;
+
+function canvasElementText(element) {
+  if (!isObject(element)) return "";
+  const label = isObject(element.label) ? element.label.text : element.label;
+  return asString(element.text || label || element.originalText).trim();
+}
+
+function normalizeCanvasText(text) {
+  return asString(text).toLowerCase().replace(/\s+/g, " ").trim();
+}
+
+async function fetchCanvasElements(canvasUrl) {
+  try {
+    const response = await fetch(`${canvasUrl}/api/elements`);
+    if (!response.ok) return [];
+    const body = await response.json();
+    const elements = Array.isArray(body) ? body : body?.elements;
+    return Array.isArray(elements) ? elements : [];
+  } catch {
+    return [];
+  }
+}
+
+/**
+ * Compare new text elements with what is already on the canvas and decide
+ * per element: "skip" near-identical text, "merge" a longer restatement into
+ * the existing element, or "link" a related one to it with a dashed arrow.
+ */
+async function planCanvasDedup(embedder, existing, incoming) {
+  const existingText = existing
+    .map((element) => ({ element, text: canvasElementText(element) }))
+    .filter((item) => item.text.length >= CANVAS_DEDUP_MIN_TEXT_LENGTH && item.element.id);
+  const candidates = incoming
+    .map((element, index) => ({ index, text: canvasElementText(element) }))
+    .filter((item) => item.text.length >= CANVAS_DEDUP_MIN_TEXT_LENGTH);
+  if (existingText.length === 0 || candidates.length === 0) return [];
+
+  const { backend, vectors } = await embedder.embed([
+    ...existingText.map((item) => item.text),
+    ...candidates.map((item) => item.text),
+  ]);
+  const thresholds = similarityThresholds[backend];
+  const existingVectors = vectors.slice(0, existingText.length);
+
+  const decisions = [];
+  candidates.forEach((candidate, offset) => {
+    const vector = vectors[existingText.length + offset];
+    let best = null;
+    existingVectors.forEach((existingVector, i) => {
+      const similarity = cosineSimilarity(vector, existingVector);
+      if (!best || similarity > best.similarity) {
+        best = { similarity, ...existingText[i] };
+      }
+    });
+    if (!best || best.similarity < thresholds.related) return;
+    const newText = normalizeCanvasText(candidate.text);
+    const oldText = normalizeCanvasText(best.text);
+    const action =
+      best.similarity >= thresholds.duplicate
+        ? "skip"
+        : newText.length > oldText.length && newText.includes(oldText)
+          ? "merge"
+          : "link";
+    decisions.push({
+      action,
+      index: candidate.index,
+      text: candidate.text,
+      existingId: asString(best.element.id),
+      existingText: best.text,
+      similarity: Number(best.similarity.toFixed(3)),
+      backend,
+      existing: best.element,
+    });
+  });
+  return decisions;
+}
+
+function centerOf(element) {
+  const x = Number(element?.x) || 0;
+  const y = Number(element?.y) || 0;
+  return {
+    x: x + (Number(element?.width) || 0) / 2,
+    y: y + (Number(element?.height) || 0) / 2,
+  };
+}
+
+/**
+ * Wrap `create_element` / `batch_create_elements` so text that repeats
+ * something already on the canvas is skipped, merged, or linked instead of
+ * drawn again. Decisions are appended to `log` for the run result.
+ */
+function wrapCanvasDedup(tools, canvasUrl, embedder, log) {
+  const updateTool = tools.update_element;
+  const createTool = tools.create_element;
+
+  async function applyDecisions(elements) {
+    const existing = await fetchCanvasElements(canvasUrl);
+    const decisions = await planCanvasDedup(embedder, existing, elements);
+    const dropped = new Set();
+    const links = [];
+    for (const decision of decisions) {
+      const { existing: existingElement, ...entry } = decision;
+      log.push(entry);
+      console.error(
+        `[excalidraw] dedup ${decision.action} (${decision.similarity} ${decision.backend}) "${decision.text.slice(0, 60)}" ~ ${decision.existingId}`
+      );
+      if (decision.action === "skip") {
+        dropped.add(decision.index);
+      } else if (decision.action === "merge" && isObject(updateTool)) {
+        await updateTool.execute({ id: decision.existingId, text: decision.text });
+        dropped.add(decision.index);
+      } else if (decision.action === "link") {
+        links.push({ from: elements[decision.index], to: existingElement });
+      }
+    }
+    return { kept: elements.filter((_, index) => !dropped.has(index)), links, decisions };
+  }
+
+  async function drawLinks(links) {
+    if (!isObject(createTool)) return;
+    for (const link of links) {
+      const from = centerOf(link.from);
+      const to = centerOf(link.to);
+      try {
+        await createTool.execute({
+          type: "arrow",
+          x: from.x,
+          y: from.y,
+          width: to.x - from.x,
+          height: to.y - from.y,
+          points: [[0, 0], [to.x - from.x, to.y - from.y]],
+          strokeStyle: "dashed",
+          strokeColor: "#868e96",
+        });
+      } catch (error) {
+        console.error(`[excalidraw] dedup link failed: ${error?.message || String(error)}`);
+      }
+    }
+  }
+
+  function skippedResult(decisions) {
+    return {
+      content: [
+        {
+          type: "text",
+          text: `Skipped: already on the canvas (${decisions
+            .map((d) => `${d.action} -> ${d.existingId}`)
+            .join(", ")}). Do not add this again.`,
+        },
+      ],
+    };
+  }
+
+  if (isObject(createTool) && typeof createTool.execute === "function") {
+    const original = createTool;
+    tools.create_element = {
+      ...original,
+      async execute(args, ...rest) {
+        const { kept, links, decisions } = await applyDecisions([args]);
+        if (kept.length === 0) return skippedResult(decisions);
+        const result = await original.execute.call(original, args, ...rest);
+        await drawLinks(links);
+        return result;
+      },
+    };
+  }
+
+  const batchTool = tools.batch_create_elements;
+  if (isObject(batchTool) && typeof batchTool.execute === "function") {
+    tools.batch_create_elements = {
+      ...batchTool,
+      async execute(args, ...rest) {
+        const elements = Array.isArray(args?.elements) ? args.elements : [];
+        const { kept, links, decisions } = await applyDecisions(elements);
+        if (elements.length > 0 && kept.length === 0) return skippedResult(decisions);
+        const result = await batchTool.execute.call(batchTool, { ...args, elements: kept }, ...rest);
+        await drawLinks(links);
+        return result;
+      },
+    };
+  }
+}
+
 async function runExcalidrawAutomation(payload) {
   const settings = isObject(payload?.settings) ? payload.settings : {};
   const apiKeySelection = resolveOpenRouterApiKey(settings.apiKey);
@@ -1976,6 +2469,15 @@ async function runExcalidrawAutomation(payload) {
     };
   }
 
+  // Registered on the raw batch tool so table cells bypass canvas dedup.
+  const rawBatchTool = tools.batch_create_elements;
+  if (isObject(rawBatchTool) && typeof rawBatchTool.execute === "function") {
+    tools.create_comparison_table = createComparisonTableTool({
+      batchTool: rawBatchTool,
+      getExisting: () => (excalidrawCanvasUrl ? fetchCanvasElements(excalidrawCanvasUrl) : []),
+    });
+  }
+
   const activeExcalidrawTools = Object.keys(tools).filter((toolName) =>
     EXCALIDRAW_ACTIVE_TOOLS.has(toolName)
   );
@@ -1991,15 +2493,17 @@ async function runExcalidrawAutomation(payload) {
     };
   }
 
-  const openrouter = createOpenRouter({ apiKey });
+  const openrouter = createModelProvider(settings, apiKey);
   const model =
     typeof openrouter.chat === "function"
       ? openrouter.chat(modelName)
       : openrouter(modelName);
 
-  const instructions =
+  const instructions = withResponseLanguage(
     asString(payload?.instructions).trim() ||
-    "You are HeyJamie's Excalidraw diagram creator. Use MCP tools to create diagrams.";
+      "You are HeyJamie's Excalidraw diagram creator. Use MCP tools to create diagrams.",
+    payload
+  );
 
   const prompt = buildExcalidrawPrompt(payload);
 
@@ -2007,9 +2511,22 @@ async function runExcalidrawAutomation(payload) {
   // only when the agent actually starts drawing, not at session start.
   // This preserves the previous diagram if the agent is cancelled before drawing.
   const shouldClear = Boolean(isObject(payload?.context) && payload.context.clearCanvas);
+
+  // Dedup only matters when drawing onto an existing board; it wraps the
+  // raw tools first so the lazy-clear wrapper below stays outermost.
+  const canvasDedup = [];
+  if (!shouldClear && excalidrawCanvasUrl) {
+    const embedder = createEmbedder({
+      apiKey,
+      baseUrl: asString(settings.baseUrl).trim(),
+      signal: globalAbort.signal,
+    });
+    wrapCanvasDedup(tools, excalidrawCanvasUrl, embedder, canvasDedup);
+  }
+
   if (shouldClear && excalidrawCanvasUrl) {
     let canvasCleared = false;
-    const CLEAR_BEFORE_TOOLS = ["create_element", "batch_create_elements"];
+    const CLEAR_BEFORE_TOOLS = ["create_element", "batch_create_elements", "create_comparison_table"];
     for (const toolName of CLEAR_BEFORE_TOOLS) {
       const original = tools[toolName];
       if (isObject(original) && typeof original.execute === "function") {
@@ -2040,12 +2557,16 @@ async function runExcalidrawAutomation(payload) {
   }
 
   try {
+    const budget = createToolBudget();
+    const budgetedTools = { ...tools };
+    applyToolBudget(budgetedTools, budget);
+
     const agent = new ToolLoopAgent({
       model,
       instructions,
-      tools,
+      tools: budgetedTools,
       activeTools: activeExcalidrawTools,
-      stopWhen: stepCountIs(excalidrawMaxSteps),
+      stopWhen: [stepCountIs(excalidrawMaxSteps), budget.stopWhen],
     });
 
     const result = await withTimeout(
@@ -2053,6 +2574,7 @@ async function runExcalidrawAutomation(payload) {
       excalidrawGenerateTimeoutMs,
       "excalidraw.generate"
     );
+    trackUsage(result);
 
     const text = asString(result?.text).trim();
     const parsedText = extractJsonPayload(text);
@@ -2102,7 +2624,9 @@ async function runExcalidrawAutomation(payload) {
     const noToolCallsError = toolCallDetails.length === 0
       ? `Model ${modelName} did not use Excalidraw tools. Try a different model (e.g. anthropic/claude-sonnet-4).`
       : "";
-    const message = noToolCallsError || parsedMessage || text || "Excalidraw diagram created.";
+    const budgetError = budget.exceeded ? budgetExceededMessage(budget, toolCallDetails) : "";
+    const message =
+      noToolCallsError || budgetError || parsedMessage || text || "Excalidraw diagram created.";
 
     const parsedCompleted =
       isObject(parsedText) && typeof parsedText.completed === "boolean"
@@ -2114,7 +2638,7 @@ async function runExcalidrawAutomation(payload) {
         ? parsedCompleted
         : Boolean(diagramUrl);
 
-    const ok = completed && Boolean(diagramUrl);
+    const ok = completed && Boolean(diagramUrl) && !budget.exceeded;
 
     const contentSnapshot = await captureContentSnapshot(tools, "excalidraw");
 
@@ -2123,13 +2647,15 @@ async function runExcalidrawAutomation(payload) {
       completed,
       message,
       diagramUrl,
-      error: noToolCallsError || undefined,
+      error: noToolCallsError || budgetError || undefined,
       toolCalls: toolCallDetails,
       modelName,
       modelSource: modelSelection.source,
       mcpServers: serverSummaries,
       rawText: text || null,
       contentSnapshot,
+      canvasDedup,
+      budget: budget.report(),
     };
   } catch (error) {
     return {
@@ -2228,9 +2754,128 @@ async function runBrowserOsNavigate(payload) {
   // MCP clients intentionally not closed (see runBrowserOsAutomation).
 }
 
+function fillModeTemplate(template, payload) {
+  const context = payload?.context == null ? "" : JSON.stringify(payload.context, null, 2);
+  const values = {
+    instructions: asString(payload?.instructions).trim(),
+    prompt: asString(payload?.prompt).trim(),
+    context,
+  };
+  return asString(template).replace(/\{\{\s*(instructions|prompt|context)\s*\}\}/g, (_, key) => values[key]);
+}
+
+// Checks the parts of a JSON Schema a model usually gets wrong: the
+// top-level type and required keys.
+function outputSchemaError(value, schema) {
+  const type = asString(schema?.type);
+  if (type === "object" && !isObject(value)) return "expected a JSON object";
+  if (type === "array" && !Array.isArray(value)) return "expected a JSON array";
+  const required = Array.isArray(schema?.required) ? schema.required : [];
+  const missing = isObject(value) ? required.filter((key) => !(key in value)) : [];
+  return missing.length > 0 ? `missing ${missing.join(", ")}` : null;
+}
+
+// A mode defined in Settings: its instructions template, only the tools it
+// allows (`*` wildcards, matched with or without the server prefix), and
+// a JSON reply when it has an output schema.
+async function runCustomMode(payload) {
+  const mode = payload.customMode;
+  const name = asString(mode.name).trim();
+  const settings = isObject(payload?.settings) ? payload.settings : {};
+  const apiKey = resolveOpenRouterApiKey(settings.apiKey).apiKey;
+  const modelSelection = resolveModel(settings);
+  if (!apiKey || !modelSelection.modelName) {
+    return { ok: false, error: `Missing OpenRouter settings for the "${name}" mode.` };
+  }
+
+  const template = asString(mode.instructions).trim() || "{{instructions}}";
+  const schema = isObject(mode.outputSchema) ? mode.outputSchema : null;
+  const instructions = withResponseLanguage(
+    [
+      fillModeTemplate(template, payload),
+      schema
+        ? `Reply with only a JSON value matching this JSON Schema, no other text:\n${JSON.stringify(schema)}`
+        : "",
+    ]
+      .filter(Boolean)
+      .join("\n\n"),
+    payload
+  );
+  const prompt = asString(payload?.prompt).trim() || "Begin.";
+
+  const openrouter = createModelProvider(settings, apiKey);
+  const model =
+    typeof openrouter.chat === "function"
+      ? openrouter.chat(modelSelection.modelName)
+      : openrouter(modelSelection.modelName);
+
+  const allowed = (Array.isArray(mode.allowedTools) ? mode.allowedTools : [])
+    .map((entry) => asString(entry).trim())
+    .filter(Boolean);
+  let clients = [];
+  try {
+    let tools = {};
+    if (allowed.length > 0) {
+      const loaded = await withTimeout(
+        loadMcpTools(asString(payload?.mcpConfigPath).trim(), { requireBrowserOs: false }),
+        mcpLoadTimeoutMs,
+        "loadMcpTools"
+      );
+      clients = loaded.clients;
+      tools = Object.fromEntries(
+        Object.entries(loaded.tools).filter(([toolName]) =>
+          allowed.some(
+            (pattern) =>
+              matchesToolPattern(toolName, pattern) ||
+              matchesToolPattern(toolName.split(":").pop(), pattern)
+          )
+        )
+      );
+      console.error(`[custom-mode] ${name}: ${Object.keys(tools).length} tools allowed`);
+    }
+
+    const budget = createToolBudget();
+    applyToolBudget(tools, budget);
+    const agent = new ToolLoopAgent({
+      model,
+      instructions,
+      tools,
+      stopWhen: [stepCountIs(20), budget.stopWhen],
+    });
+    const result = await agent.generate({ prompt, abortSignal: globalAbort.signal });
+    trackUsage(result);
+
+    const text = asString(result?.text).trim();
+    const response = {
+      ok: true,
+      mode: name,
+      text,
+      toolCalls: summarizeToolCalls(result),
+      budget: budget.report(),
+      modelName: modelSelection.modelName,
+      modelSource: modelSelection.source,
+    };
+    if (!schema) return response;
+    const output = extractJsonPayload(text);
+    const problem = output == null ? "reply was not JSON" : outputSchemaError(output, schema);
+    return problem
+      ? { ...response, ok: false, error: `The "${name}" mode's reply doesn't match its schema: ${problem}.` }
+      : { ...response, output };
+  } finally {
+    for (const client of clients) {
+      try {
+        await client.close();
+      } catch {
+        // ignore close errors
+      }
+      activeMcpClients.delete(client);
+    }
+  }
+}
+
 async function runGeneralAgent(payload) {
   const settings = isObject(payload?.settings) ? payload.settings : {};
-  const instructions = asString(payload?.instructions).trim();
+  const instructions = withResponseLanguage(asString(payload?.instructions).trim(), payload);
   const prompt = asString(payload?.prompt).trim();
   const mcpConfigPath = asString(payload?.mcpConfigPath).trim();
 
@@ -2243,7 +2888,7 @@ async function runGeneralAgent(payload) {
     throw new Error("Missing OpenRouter settings.");
   }
 
-  const openrouter = createOpenRouter({ apiKey: effectiveApiKey });
+  const openrouter = createModelProvider(settings, effectiveApiKey);
   const model =
     typeof openrouter.chat === "function"
       ? openrouter.chat(effectiveModel)
@@ -2261,10 +2906,14 @@ async function runGeneralAgent(payload) {
     clients = loaded.clients;
     tools = loaded.tools;
 
+    const budget = createToolBudget();
+    applyToolBudget(tools, budget);
+
     const agent = new ToolLoopAgent({
       model,
       instructions,
       tools,
+      stopWhen: [stepCountIs(20), budget.stopWhen],
     });
 
     const result = await withTimeout(
@@ -2272,9 +2921,12 @@ async function runGeneralAgent(payload) {
       agentGenerateTimeoutMs,
       "agent.generate"
     );
+    trackUsage(result);
 
-    const text = asString(result?.text).trim();
     const toolCallDetails = summarizeToolCalls(result);
+    const text =
+      asString(result?.text).trim() ||
+      (budget.exceeded ? budgetExceededMessage(budget, toolCallDetails) : "");
     if (!text) {
       throw new Error("LLM agent returned empty text.");
     }
@@ -2282,6 +2934,7 @@ async function runGeneralAgent(payload) {
     return {
       text,
       toolCalls: toolCallDetails,
+      budget: budget.report(),
     };
   } finally {
     for (const client of clients) {
@@ -2346,6 +2999,13 @@ async function testMcpServers(configPath) {
   return { ok: results.every((item) => item.ok), servers: results };
 }
 
+// Lets the caller (e.g. a TTS voice picker) know which language the reply
+// was requested in.
+function tagResponseLanguage(result, payload) {
+  const code = asString(payload?.responseLanguage?.code).trim();
+  return code && isObject(result) ? { ...result, responseLanguage: code } : result;
+}
+
 async function main() {
   const input = await readStdin();
   if (!input.trim()) {
@@ -2353,6 +3013,7 @@ async function main() {
   }
 
   const payload = JSON.parse(input);
+  traceId = asString(payload?.traceId).trim();
 
   if (payload?.mode === "mcp-test") {
     const summary = await testMcpServers(asString(payload?.mcpConfigPath).trim());
@@ -2373,7 +3034,7 @@ async function main() {
         error: error?.message || String(error),
       };
     }
-    process.stdout.write(JSON.stringify(result));
+    process.stdout.write(JSON.stringify(withUsage(result)));
     return;
   }
 
@@ -2390,7 +3051,22 @@ async function main() {
         error: error?.message || String(error),
       };
     }
-    process.stdout.write(JSON.stringify(result));
+    process.stdout.write(JSON.stringify(withUsage(result)));
+    return;
+  }
+
+  if (payload?.mode === "text-prompt") {
+    let result;
+    try {
+      result = await runTextPrompt(payload);
+    } catch (error) {
+      console.error(`[text-prompt] fatal failure: ${error?.message || String(error)}`);
+      result = {
+        ok: false,
+        error: error?.message || String(error),
+      };
+    }
+    process.stdout.write(JSON.stringify(withUsage(result)));
     return;
   }
 
@@ -2405,7 +3081,7 @@ async function main() {
         error: error?.message || String(error),
       };
     }
-    await writeAndDrain(JSON.stringify(result));
+    await writeAndDrain(JSON.stringify(withUsage(tagResponseLanguage(result, payload))));
     process.exit(0);
   }
 
@@ -2420,7 +3096,7 @@ async function main() {
         error: error?.message || String(error),
       };
     }
-    await writeAndDrain(JSON.stringify(result));
+    await writeAndDrain(JSON.stringify(withUsage(result)));
     process.exit(0);
   }
 
@@ -2435,12 +3111,33 @@ async function main() {
         error: error?.message || String(error),
       };
     }
-    await writeAndDrain(JSON.stringify(result));
+    await writeAndDrain(JSON.stringify(withUsage(tagResponseLanguage(result, payload))));
     process.exit(0);
   }
 
+  // Agenda research runs through the custom-mode pipeline with every MCP
+  // tool allowed, so it can search and browse like the general agent.
+  const customMode =
+    payload?.mode === "agenda-research"
+      ? { name: "agenda-research", instructions: "{{instructions}}", allowedTools: ["*"] }
+      : payload?.customMode;
+  if (isObject(customMode)) {
+    let result;
+    try {
+      result = await runCustomMode({ ...payload, customMode });
+    } catch (error) {
+      console.error(`[custom-mode] fatal failure: ${error?.message || String(error)}`);
+      result = {
+        ok: false,
+        error: error?.message || String(error),
+      };
+    }
+    process.stdout.write(JSON.stringify(withUsage(tagResponseLanguage(result, payload))));
+    return;
+  }
+
   const genericResult = await runGeneralAgent(payload);
-  process.stdout.write(JSON.stringify(genericResult));
+  process.stdout.write(JSON.stringify(withUsage(tagResponseLanguage(genericResult, payload))));
 }
 
 main().catch(async (error) => {
@@ -15,7 +15,17 @@ cd "$WHISPER_DIR"
 
 sh ./models/download-ggml-model.sh "$MODEL"
 
-cmake -B build
+# Metal is on by default on macOS. CUDA needs the toolkit; Core ML also
+# needs the encoder generated with models/generate-coreml-model.sh.
+CMAKE_FLAGS=()
+if command -v nvidia-smi >/dev/null 2>&1 && command -v nvcc >/dev/null 2>&1; then
+  CMAKE_FLAGS+=(-DGGML_CUDA=1)
+fi
+if [ "${WHISPER_COREML:-0}" = "1" ]; then
+  CMAKE_FLAGS+=(-DWHISPER_COREML=1)
+fi
+
+cmake -B build ${CMAKE_FLAGS[@]+"${CMAKE_FLAGS[@]}"}
 cmake --build build -j
 
 echo ""
@@ -0,0 +1,28 @@
+#!/usr/bin/env bash
+# Refresh src-tauri/whisper-models.json with the SHA256 HuggingFace publishes
+# (the `x-linked-etag` of each LFS file) for every model in the app catalog.
+set -euo pipefail
+
+ROOT_DIR="$(cd "$(dirname "${BASH_SOURCE[0]}")/.." && pwd)"
+MANIFEST="$ROOT_DIR/src-tauri/whisper-models.json"
+BASE_URL="https://huggingface.co/ggerganov/whisper.cpp/resolve/main"
+MODELS=(tiny.en tiny base.en base small.en small medium.en medium large-v3-turbo large-v3)
+
+entries=()
+for model in "${MODELS[@]}"; do
+  file="ggml-$model.bin"
+  sha=$(curl -sI "$BASE_URL/$file" | tr -d '\r' | awk -F'"' 'tolower($1) ~ /^x-linked-etag/ {print $2}')
+  if [ -z "$sha" ]; then
+    echo "no checksum published for $file" >&2
+    exit 1
+  fi
+  echo "$file $sha"
+  entries+=("  \"$file\": \"$sha\"")
+done
+
+{
+  echo "{"
+  (IFS=$'\n'; echo "${entries[*]}" | sed '$!s/$/,/')
+  echo "}"
+} > "$MANIFEST"
+echo "Wrote $MANIFEST"
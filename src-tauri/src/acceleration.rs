@@ -0,0 +1,135 @@
+//! GPU acceleration for local whisper: what the machine offers, the
+//! `transcription.acceleration` setting, and the flags that apply it to
+//! `whisper-cli` and the in-process engine.
+
+use std::process::Command;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Mutex;
+
+use serde::{Deserialize, Serialize};
+
+use crate::{log_line, model_variants, settings, whisper_models};
+
+#[derive(Serialize, Deserialize, Clone, Copy, Debug, Default, PartialEq, Eq, specta::Type)]
+#[serde(rename_all = "kebab-case")]
+pub enum WhisperAcceleration {
+    /// Use the GPU (Metal, CUDA) when whisper was built with it.
+    #[default]
+    Auto,
+    /// Always transcribe on the CPU, e.g. when the GPU is needed elsewhere.
+    Cpu,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, specta::Type)]
+#[serde(rename_all = "camelCase")]
+pub struct TranscriptionCapabilities {
+    /// macOS, where whisper.cpp builds with Metal by default.
+    pub metal: bool,
+    /// An NVIDIA driver answers `nvidia-smi`.
+    pub cuda: bool,
+    /// A Core ML encoder (`<model>-encoder.mlmodelc`) sits next to the
+    /// active model; used by whisper.cpp builds with `WHISPER_COREML`.
+    pub core_ml: bool,
+    /// Built with the `whisper-rs` feature.
+    pub in_process: bool,
+    pub acceleration: WhisperAcceleration,
+    /// Backend the last local `whisper-cli` run reported, e.g. "Metal" or
+    /// "CPU". `None` until something was transcribed locally.
+    pub last_backend: Option<String>,
+}
+
+static FORCE_CPU: AtomicBool = AtomicBool::new(false);
+static LAST_BACKEND: Mutex<Option<String>> = Mutex::new(None);
+
+/// Read the acceleration setting. Called at startup and whenever settings
+/// are saved.
+pub fn reload(app: &tauri::AppHandle) {
+    let acceleration = settings::load_settings(app).transcription.acceleration;
+    FORCE_CPU.store(acceleration == WhisperAcceleration::Cpu, Ordering::Relaxed);
+}
+
+/// Whether local whisper may use the GPU.
+pub fn use_gpu() -> bool {
+    !FORCE_CPU.load(Ordering::Relaxed)
+}
+
+/// Add the acceleration flags to a `whisper-cli` command.
+pub fn apply_cli_flags(command: &mut Command) {
+    if !use_gpu() {
+        command.arg("--no-gpu");
+    }
+}
+
+/// The backend named in `whisper-cli` stderr, e.g.
+/// `whisper_backend_init_gpu: using Metal backend` or `... no GPU found`.
+fn parse_backend(stderr: &str) -> Option<String> {
+    if stderr.contains("loading Core ML model") {
+        return Some("Core ML".to_string());
+    }
+    for line in stderr.lines() {
+        let backend = line
+            .contains("whisper_backend_init")
+            .then(|| line.split("using ").nth(1))
+            .flatten()
+            .and_then(|rest| rest.trim().strip_suffix(" backend"));
+        if let Some(name) = backend {
+            return Some(name.to_string());
+        }
+        if line.contains("no GPU found") {
+            return Some("CPU".to_string());
+        }
+    }
+    None
+}
+
+/// Remember the backend a `whisper-cli` run used.
+pub fn note_cli_output(stderr: &str) {
+    let backend = parse_backend(stderr).or_else(|| (!use_gpu()).then(|| "CPU".to_string()));
+    if let Some(backend) = backend {
+        let mut last = LAST_BACKEND.lock().unwrap();
+        if last.as_deref() != Some(backend.as_str()) {
+            log_line(&format!("[acceleration] whisper-cli is using {}", backend));
+        }
+        *last = Some(backend);
+    }
+}
+
+fn has_nvidia_gpu() -> bool {
+    Command::new("nvidia-smi")
+        .arg("-L")
+        .output()
+        .is_ok_and(|output| output.status.success() && !output.stdout.is_empty())
+}
+
+/// What acceleration this machine and build offer, and what's in use.
+#[tauri::command]
+#[specta::specta]
+pub fn get_transcription_capabilities(app: tauri::AppHandle) -> TranscriptionCapabilities {
+    TranscriptionCapabilities {
+        metal: cfg!(target_os = "macos"),
+        cuda: !cfg!(target_os = "macos") && has_nvidia_gpu(),
+        core_ml: cfg!(target_os = "macos")
+            && whisper_models::resolve(&app, None).is_ok_and(|path| model_variants::has_core_ml_encoder(&path)),
+        in_process: cfg!(feature = "whisper-rs"),
+        acceleration: settings::load_settings(&app).transcription.acceleration,
+        last_backend: LAST_BACKEND.lock().unwrap().clone(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::parse_backend;
+
+    #[test]
+    fn reads_backend_from_whisper_cli_stderr() {
+        let metal = "whisper_init_with_params_no_state: use gpu    = 1\nwhisper_backend_init_gpu: using Metal backend\n";
+        assert_eq!(parse_backend(metal).as_deref(), Some("Metal"));
+        assert_eq!(parse_backend("whisper_backend_init_gpu: using CUDA0 backend").as_deref(), Some("CUDA0"));
+        assert_eq!(parse_backend("whisper_backend_init_gpu: no GPU found").as_deref(), Some("CPU"));
+        assert_eq!(
+            parse_backend("whisper_init_state: loading Core ML model from 'ggml-base.en-encoder.mlmodelc'").as_deref(),
+            Some("Core ML")
+        );
+        assert_eq!(parse_backend("system_info: n_threads = 4"), None);
+    }
+}
@@ -0,0 +1,484 @@
+//! A research agenda that outlives sessions: queued tasks ("find three
+//! recent studies on X before Thursday's episode") are worked through
+//! while the app is idle, across launches. Results collect until the next
+//! session starts and are written into its `prep.md`.
+//!
+//! Only the webview holds the OpenRouter key, so the scheduler announces a
+//! due item with `agenda-due` and the webview runs it with
+//! `run_agenda_item`.
+
+use std::fs;
+use std::path::PathBuf;
+use std::sync::atomic::Ordering;
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
+
+use serde::{Deserialize, Serialize};
+use serde_json::Value as JsonValue;
+use tauri::Manager;
+use tauri_specta::Event;
+
+use crate::sessions::{self, SessionState};
+use crate::{
+    background, log_line, now_millis, power, recording, shutdown, system_load, truncate_for_log, LlmAgentRequest,
+    LlmAgentSettings, LlmAgentState,
+};
+
+const TICK_INTERVAL: Duration = Duration::from_secs(60);
+/// The app must have been idle this long before a task starts.
+const IDLE_BEFORE_RUN: Duration = Duration::from_secs(120);
+/// An announced item nobody picked up (no API key, say) is offered again
+/// after this long.
+const REOFFER_AFTER: Duration = Duration::from_secs(600);
+const MAX_ATTEMPTS: u32 = 3;
+/// Wait after the first failure; doubles with each further one.
+const RETRY_BACKOFF_MS: u64 = 15 * 60 * 1000;
+const MAX_TASK_CHARS: usize = 1000;
+
+const INSTRUCTIONS: &str = "You are preparing a podcast host for an upcoming episode. \
+Research the task below with the tools you have, preferring recent and primary sources. \
+Reply in Markdown with concise findings and a source link for each claim. \
+If you can't find enough, say what is missing instead of guessing.";
+
+#[derive(Serialize, Deserialize, Clone, Copy, Debug, PartialEq, Eq, specta::Type)]
+#[serde(rename_all = "kebab-case")]
+pub enum AgendaStatus {
+    Pending,
+    Running,
+    Done,
+    Failed,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, specta::Type)]
+#[serde(rename_all = "camelCase")]
+pub struct AgendaItem {
+    pub id: String,
+    pub task: String,
+    pub created_at: u64,
+    /// When the result is needed; earlier deadlines run first.
+    pub due_by: Option<u64>,
+    pub status: AgendaStatus,
+    pub attempts: u32,
+    /// Not retried before this, after a failure.
+    pub not_before: Option<u64>,
+    pub last_error: Option<String>,
+    pub result: Option<String>,
+    pub completed_at: Option<u64>,
+    /// Session whose prep material holds the result.
+    pub session_id: Option<String>,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, specta::Type)]
+#[serde(rename_all = "camelCase")]
+pub struct AgendaStatusSummary {
+    pub pending: u32,
+    pub running: u32,
+    pub done: u32,
+    pub failed: u32,
+    /// Whether the app counts as idle right now.
+    pub idle: bool,
+    /// Why it doesn't, when it doesn't.
+    pub busy_reason: Option<String>,
+}
+
+/// Payload of the `agenda-due` event: the webview should run this item.
+#[derive(Serialize, Deserialize, Clone, specta::Type, tauri_specta::Event)]
+#[serde(rename_all = "camelCase")]
+pub struct AgendaDue {
+    pub id: String,
+    pub task: String,
+}
+
+#[derive(Default)]
+pub struct AgendaState {
+    /// Serializes read-modify-write of `agenda.json`.
+    lock: Mutex<()>,
+    /// Item last announced, and when.
+    offered: Mutex<Option<(String, Instant)>>,
+}
+
+/// Next item to run at `now`: pending and out of backoff, earliest
+/// deadline first (items without one last), then oldest.
+fn next_due(items: &[AgendaItem], now: u64) -> Option<&AgendaItem> {
+    items
+        .iter()
+        .filter(|item| item.status == AgendaStatus::Pending)
+        .filter(|item| item.not_before.is_none_or(|at| at <= now))
+        .min_by_key(|item| (item.due_by.unwrap_or(u64::MAX), item.created_at))
+}
+
+/// Record the outcome of a run: done with its result, or pending again
+/// with backoff until `MAX_ATTEMPTS` is used up.
+fn finish(item: &mut AgendaItem, outcome: Result<String, String>, now: u64) {
+    item.attempts += 1;
+    match outcome {
+        Ok(result) => {
+            item.status = AgendaStatus::Done;
+            item.result = Some(result);
+            item.last_error = None;
+            item.not_before = None;
+            item.completed_at = Some(now);
+        }
+        Err(error) => {
+            item.last_error = Some(error);
+            if item.attempts >= MAX_ATTEMPTS {
+                item.status = AgendaStatus::Failed;
+                item.not_before = None;
+            } else {
+                item.status = AgendaStatus::Pending;
+                item.not_before = Some(now + RETRY_BACKOFF_MS * (1 << (item.attempts - 1)));
+            }
+        }
+    }
+}
+
+/// Finished results not yet handed to a session, as Markdown.
+fn prep_markdown(items: &[AgendaItem]) -> String {
+    items
+        .iter()
+        .filter(|item| item.status == AgendaStatus::Done && item.session_id.is_none())
+        .filter_map(|item| Some(format!("## {}\n\n{}\n", item.task, item.result.as_deref()?.trim())))
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+fn agenda_path(app: &tauri::AppHandle) -> Result<PathBuf, String> {
+    let sessions_dir = sessions::sessions_dir(app)?;
+    Ok(sessions_dir.parent().unwrap_or(&sessions_dir).join("agenda.json"))
+}
+
+fn load(app: &tauri::AppHandle) -> Vec<AgendaItem> {
+    agenda_path(app)
+        .ok()
+        .and_then(|path| fs::read_to_string(path).ok())
+        .and_then(|content| serde_json::from_str(&content).ok())
+        .unwrap_or_default()
+}
+
+fn save(app: &tauri::AppHandle, items: &[AgendaItem]) -> Result<(), String> {
+    let content =
+        serde_json::to_string_pretty(items).map_err(|err| format!("failed to format agenda: {}", err))?;
+    fs::write(agenda_path(app)?, content.as_bytes()).map_err(|err| format!("failed to write agenda: {}", err))
+}
+
+/// Load, change and save the agenda under its lock.
+fn update<T>(
+    app: &tauri::AppHandle,
+    change: impl FnOnce(&mut Vec<AgendaItem>) -> Result<T, String>,
+) -> Result<T, String> {
+    let state = app.state::<AgendaState>();
+    let _guard = state.lock.lock().unwrap();
+    let mut items = load(app);
+    let value = change(&mut items)?;
+    save(app, &items)?;
+    Ok(value)
+}
+
+/// Why the app isn't idle, if it isn't.
+fn busy_reason(app: &tauri::AppHandle) -> Option<String> {
+    if shutdown::is_shutting_down() {
+        return Some("quitting".to_string());
+    }
+    if app.state::<SessionState>().active_id().is_some() {
+        return Some("a session is running".to_string());
+    }
+    if recording::is_recording(app) {
+        return Some("recording".to_string());
+    }
+    if app.state::<LlmAgentState>().running.load(Ordering::SeqCst) > 0 {
+        return Some("an agent run is in progress".to_string());
+    }
+    if let Some(reason) = background::busy(app) {
+        return Some(format!("waiting on {}", reason));
+    }
+    if power::current(app).low_power {
+        return Some("on battery".to_string());
+    }
+    system_load::current(app)
+        .filter(|pressure| pressure.pressured)
+        .map(|pressure| pressure.reason.unwrap_or_else(|| "the system is under load".to_string()))
+}
+
+/// Write unassigned results into `session_id`'s `prep.md` and mark them as
+/// handed over. Called when a session starts.
+pub fn attach_to_session(app: &tauri::AppHandle, session_id: &str) {
+    let result = update(app, |items| {
+        let markdown = prep_markdown(items);
+        if markdown.is_empty() {
+            return Ok(0);
+        }
+        let path = sessions::session_dir(app, session_id)?.join("prep.md");
+        let existing = fs::read_to_string(&path).unwrap_or_default();
+        let content = if existing.is_empty() {
+            format!("# Research agenda\n\n{}", markdown)
+        } else {
+            format!("{}\n{}", existing.trim_end(), markdown)
+        };
+        fs::write(&path, content.as_bytes()).map_err(|err| format!("failed to write prep material: {}", err))?;
+        let mut count = 0;
+        for item in items
+            .iter_mut()
+            .filter(|item| item.status == AgendaStatus::Done && item.session_id.is_none())
+        {
+            item.session_id = Some(session_id.to_string());
+            count += 1;
+        }
+        Ok(count)
+    });
+    match result {
+        Ok(0) => {}
+        Ok(count) => log_line(&format!("[agenda] added {} result(s) to the prep of {}", count, session_id)),
+        Err(err) => log_line(&format!("[agenda] failed to prepare {}: {}", session_id, err)),
+    }
+}
+
+/// Offer the next due item once the app has been idle for a while.
+pub fn spawn_scheduler(app: tauri::AppHandle) {
+    // A run cut short by quitting counts as pending again.
+    let _ = update(&app, |items| {
+        for item in items.iter_mut().filter(|item| item.status == AgendaStatus::Running) {
+            item.status = AgendaStatus::Pending;
+        }
+        Ok(())
+    });
+    tauri::async_runtime::spawn(async move {
+        let mut idle_since: Option<Instant> = None;
+        loop {
+            tokio::time::sleep(TICK_INTERVAL).await;
+            if busy_reason(&app).is_some() {
+                idle_since = None;
+                continue;
+            }
+            let since = *idle_since.get_or_insert_with(Instant::now);
+            if since.elapsed() < IDLE_BEFORE_RUN {
+                continue;
+            }
+            let Some(item) = next_due(&load(&app), now_millis()).cloned() else {
+                continue;
+            };
+            let state = app.state::<AgendaState>();
+            let mut offered = state.offered.lock().unwrap();
+            if offered
+                .as_ref()
+                .is_some_and(|(id, at)| *id == item.id && at.elapsed() < REOFFER_AFTER)
+            {
+                continue;
+            }
+            *offered = Some((item.id.clone(), Instant::now()));
+            drop(offered);
+            log_line(&format!("[agenda] {} is due: {}", item.id, truncate_for_log(&item.task, 120)));
+            let _ = AgendaDue {
+                id: item.id,
+                task: item.task,
+            }
+            .emit(&app);
+        }
+    });
+}
+
+#[tauri::command]
+#[specta::specta]
+pub fn list_agenda_items(app: tauri::AppHandle) -> Vec<AgendaItem> {
+    let state = app.state::<AgendaState>();
+    let _guard = state.lock.lock().unwrap();
+    load(&app)
+}
+
+#[tauri::command]
+#[specta::specta]
+pub fn add_agenda_item(app: tauri::AppHandle, task: String, due_by: Option<u64>) -> Result<AgendaItem, String> {
+    let task = task.trim().to_string();
+    if task.is_empty() {
+        return Err("the agenda task is empty".to_string());
+    }
+    if task.chars().count() > MAX_TASK_CHARS {
+        return Err(format!("agenda tasks are limited to {} characters", MAX_TASK_CHARS));
+    }
+    let created_at = now_millis();
+    let item = AgendaItem {
+        id: format!("agenda-{}", created_at),
+        task,
+        created_at,
+        due_by,
+        status: AgendaStatus::Pending,
+        attempts: 0,
+        not_before: None,
+        last_error: None,
+        result: None,
+        completed_at: None,
+        session_id: None,
+    };
+    update(&app, |items| {
+        if items.iter().any(|other| other.id == item.id) {
+            return Err("added too quickly; try again".to_string());
+        }
+        items.push(item.clone());
+        Ok(())
+    })?;
+    log_line(&format!("[agenda] queued {}", item.id));
+    Ok(item)
+}
+
+#[tauri::command]
+#[specta::specta]
+pub fn remove_agenda_item(app: tauri::AppHandle, id: String) -> Result<(), String> {
+    update(&app, |items| {
+        let before = items.len();
+        items.retain(|item| item.id != id || item.status == AgendaStatus::Running);
+        if items.len() == before {
+            return Err(format!("no removable agenda item {}", id));
+        }
+        Ok(())
+    })
+}
+
+/// Put a failed or finished item back in the queue.
+#[tauri::command]
+#[specta::specta]
+pub fn retry_agenda_item(app: tauri::AppHandle, id: String) -> Result<AgendaItem, String> {
+    update(&app, |items| {
+        let item = items
+            .iter_mut()
+            .find(|item| item.id == id && item.status != AgendaStatus::Running)
+            .ok_or_else(|| format!("no agenda item {} to retry", id))?;
+        item.status = AgendaStatus::Pending;
+        item.attempts = 0;
+        item.not_before = None;
+        item.last_error = None;
+        Ok(item.clone())
+    })
+}
+
+#[tauri::command]
+#[specta::specta]
+pub fn get_agenda_status(app: tauri::AppHandle) -> AgendaStatusSummary {
+    let items = list_agenda_items(app.clone());
+    let count = |status| items.iter().filter(|item| item.status == status).count() as u32;
+    let busy_reason = busy_reason(&app);
+    AgendaStatusSummary {
+        pending: count(AgendaStatus::Pending),
+        running: count(AgendaStatus::Running),
+        done: count(AgendaStatus::Done),
+        failed: count(AgendaStatus::Failed),
+        idle: busy_reason.is_none(),
+        busy_reason,
+    }
+}
+
+/// Results waiting for the next session, as they'll appear in its prep.
+#[tauri::command]
+#[specta::specta]
+pub fn get_session_prep(app: tauri::AppHandle) -> String {
+    prep_markdown(&list_agenda_items(app))
+}
+
+/// Research one agenda item with the general agent and its MCP tools.
+#[tauri::command]
+#[specta::specta]
+pub async fn run_agenda_item(
+    app: tauri::AppHandle,
+    id: String,
+    settings: LlmAgentSettings,
+    state: tauri::State<'_, LlmAgentState>,
+) -> Result<AgendaItem, String> {
+    let item = update(&app, |items| {
+        let item = items
+            .iter_mut()
+            .find(|item| item.id == id && item.status == AgendaStatus::Pending)
+            .ok_or_else(|| format!("agenda item {} isn't pending", id))?;
+        item.status = AgendaStatus::Running;
+        Ok(item.clone())
+    })?;
+    *app.state::<AgendaState>().offered.lock().unwrap() = None;
+    log_line(&format!("[agenda] running {}", item.id));
+    let due = item
+        .due_by
+        .map(|due_by| format!("\n\nNeeded by (Unix ms): {}", due_by))
+        .unwrap_or_default();
+    let payload = LlmAgentRequest {
+        settings,
+        instructions: INSTRUCTIONS.to_string(),
+        prompt: format!("{}{}", item.task, due),
+        mode: Some("agenda-research".to_string()),
+        context: None,
+        trace_id: None,
+    };
+    let outcome = crate::run_llm_agent(app.clone(), payload, state)
+        .await
+        .and_then(|output| {
+            serde_json::from_str::<JsonValue>(&output).map_err(|err| format!("invalid agenda result: {}", err))
+        })
+        .and_then(|result| {
+            if result.get("ok").and_then(JsonValue::as_bool) != Some(true) {
+                let error = result.get("error").and_then(JsonValue::as_str).unwrap_or("research failed");
+                return Err(error.to_string());
+            }
+            Some(result.get("text").and_then(JsonValue::as_str).unwrap_or_default().trim())
+                .filter(|text| !text.is_empty())
+                .map(str::to_string)
+                .ok_or_else(|| "the research came back empty".to_string())
+        });
+    if let Err(err) = &outcome {
+        log_line(&format!("[agenda] {} failed: {}", item.id, err));
+    }
+    let item = update(&app, |items| {
+        let item = items
+            .iter_mut()
+            .find(|other| other.id == item.id)
+            .ok_or_else(|| format!("agenda item {} was removed", item.id))?;
+        finish(item, outcome, now_millis());
+        Ok(item.clone())
+    })?;
+    // Finished during a session: it goes straight into that session's prep.
+    if item.status == AgendaStatus::Done {
+        if let Some(session_id) = app.state::<SessionState>().active_id() {
+            attach_to_session(&app, &session_id);
+        }
+    }
+    Ok(item)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{finish, next_due, prep_markdown, AgendaItem, AgendaStatus, MAX_ATTEMPTS};
+
+    fn item(id: &str, created_at: u64, due_by: Option<u64>) -> AgendaItem {
+        AgendaItem {
+            id: id.to_string(),
+            task: format!("task {}", id),
+            created_at,
+            due_by,
+            status: AgendaStatus::Pending,
+            attempts: 0,
+            not_before: None,
+            last_error: None,
+            result: None,
+            completed_at: None,
+            session_id: None,
+        }
+    }
+
+    #[test]
+    fn runs_earliest_deadline_first_and_backs_off_failures() {
+        let mut items = vec![item("a", 1, None), item("b", 2, Some(500)), item("c", 3, Some(100))];
+        assert_eq!(next_due(&items, 10).unwrap().id, "c");
+
+        finish(&mut items[2], Err("timeout".to_string()), 10);
+        assert_eq!(items[2].status, AgendaStatus::Pending);
+        assert_eq!(next_due(&items, 10).unwrap().id, "b");
+        let retry_at = items[2].not_before.unwrap();
+        assert_eq!(next_due(&items, retry_at).unwrap().id, "c");
+
+        for _ in 1..MAX_ATTEMPTS {
+            finish(&mut items[2], Err("timeout".to_string()), retry_at);
+        }
+        assert_eq!(items[2].status, AgendaStatus::Failed);
+
+        finish(&mut items[1], Ok("Three studies.".to_string()), 20);
+        items[0].status = AgendaStatus::Running;
+        assert!(next_due(&items, u64::MAX).is_none());
+        assert_eq!(prep_markdown(&items), "## task b\n\nThree studies.\n");
+        items[1].session_id = Some("session-1".to_string());
+        assert!(prep_markdown(&items).is_empty());
+    }
+}
@@ -0,0 +1,122 @@
+//! Modes `run_llm_agent` knows: the built-in ones `scripts/llm-agent.mjs`
+//! implements, and custom ones from settings that run through its generic
+//! pipeline with their own instructions, tools and output schema.
+
+use crate::settings::CustomAgentMode;
+
+/// Timeout for modes without an entry below, overridable per run kind.
+const GENERAL_TIMEOUT_MS: u128 = 45_000;
+const GENERAL_TIMEOUT_ENV: &str = "HEYJAMIE_LLM_TIMEOUT_MS";
+
+/// `(mode, default timeout, env var overriding it)`.
+const BUILTIN_MODES: &[(&str, u128, &str)] = &[
+    ("browseros-act", 180_000, "HEYJAMIE_BROWSEROS_TIMEOUT_MS"),
+    ("browseros-navigate", 30_000, "HEYJAMIE_BROWSEROS_TIMEOUT_MS"),
+    ("excalidraw-act", 120_000, "HEYJAMIE_EXCALIDRAW_TIMEOUT_MS"),
+    ("browseros-intent", 90_000, "HEYJAMIE_INTENT_TIMEOUT_MS"),
+    ("topic-shift-detect", 15_000, "HEYJAMIE_TOPIC_SHIFT_TIMEOUT_MS"),
+    ("text-prompt", GENERAL_TIMEOUT_MS, GENERAL_TIMEOUT_ENV),
+    ("mcp-test", GENERAL_TIMEOUT_MS, GENERAL_TIMEOUT_ENV),
+    ("agenda-research", 300_000, "HEYJAMIE_AGENDA_TIMEOUT_MS"),
+];
+
+/// How a run in some mode is set up.
+#[derive(Debug, PartialEq)]
+pub struct ResolvedMode {
+    pub timeout_ms: u128,
+    /// Set for custom modes; handed to the agent script.
+    pub custom: Option<CustomAgentMode>,
+}
+
+fn env_timeout(key: &str) -> Option<u128> {
+    std::env::var(key)
+        .ok()
+        .and_then(|raw| raw.trim().parse::<u128>().ok())
+        .filter(|ms| *ms >= 1_000)
+}
+
+fn resolve_with(mode: Option<&str>, custom: &[CustomAgentMode], env: impl Fn(&str) -> Option<u128>) -> ResolvedMode {
+    let mode = mode.map(str::trim).unwrap_or_default();
+    if let Some((_, default_ms, env_key)) = BUILTIN_MODES.iter().find(|(name, _, _)| *name == mode) {
+        return ResolvedMode {
+            timeout_ms: env(env_key).unwrap_or(*default_ms),
+            custom: None,
+        };
+    }
+    let general_ms = env(GENERAL_TIMEOUT_ENV).unwrap_or(GENERAL_TIMEOUT_MS);
+    match custom.iter().find(|candidate| !mode.is_empty() && candidate.name.trim() == mode) {
+        Some(found) => ResolvedMode {
+            timeout_ms: found
+                .timeout_ms
+                .map(|ms| (ms as u128).max(1_000))
+                .unwrap_or(general_ms),
+            custom: Some(found.clone()),
+        },
+        None => ResolvedMode {
+            timeout_ms: general_ms,
+            custom: None,
+        },
+    }
+}
+
+/// Timeout and custom definition for `mode`. Built-in modes take
+/// precedence; unknown modes run the general agent.
+pub fn resolve(mode: Option<&str>, custom: &[CustomAgentMode]) -> ResolvedMode {
+    resolve_with(mode, custom, env_timeout)
+}
+
+/// Custom modes need a unique name that isn't a built-in mode's.
+pub fn validate(custom: &[CustomAgentMode]) -> Result<(), String> {
+    for (i, mode) in custom.iter().enumerate() {
+        let name = mode.name.trim();
+        if name.is_empty() {
+            return Err("custom agent modes need a name".to_string());
+        }
+        if BUILTIN_MODES.iter().any(|(builtin, _, _)| *builtin == name) {
+            return Err(format!("\"{}\" is a built-in agent mode", name));
+        }
+        if custom[..i].iter().any(|other| other.name.trim() == name) {
+            return Err(format!("agent mode \"{}\" is defined twice", name));
+        }
+        if mode.output_schema.as_ref().is_some_and(|schema| !schema.is_object()) {
+            return Err(format!("the output schema of \"{}\" must be a JSON object", name));
+        }
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{resolve_with, validate};
+    use crate::settings::CustomAgentMode;
+
+    fn mode(name: &str, timeout_ms: Option<u32>) -> CustomAgentMode {
+        CustomAgentMode {
+            name: name.to_string(),
+            timeout_ms,
+            ..CustomAgentMode::default()
+        }
+    }
+
+    #[test]
+    fn resolves_builtin_custom_and_unknown_modes() {
+        let custom = [mode("standup-notes", Some(60_000)), mode("tldr", None)];
+        let no_env = |_: &str| None;
+        assert_eq!(resolve_with(Some("browseros-act"), &custom, no_env).timeout_ms, 180_000);
+        assert_eq!(resolve_with(Some("topic-shift-detect"), &custom, |_: &str| Some(5_000)).timeout_ms, 5_000);
+
+        let standup = resolve_with(Some("standup-notes"), &custom, no_env);
+        assert_eq!(standup.timeout_ms, 60_000);
+        assert_eq!(standup.custom.map(|m| m.name), Some("standup-notes".to_string()));
+        let tldr = resolve_with(Some("tldr"), &custom, |key: &str| (key == "HEYJAMIE_LLM_TIMEOUT_MS").then_some(20_000));
+        assert_eq!((tldr.timeout_ms, tldr.custom.is_some()), (20_000, true));
+
+        let unknown = resolve_with(None, &custom, no_env);
+        assert_eq!((unknown.timeout_ms, unknown.custom), (45_000, None));
+
+        assert!(validate(&custom).is_ok());
+        assert!(validate(&[mode("excalidraw-act", None)]).is_err());
+        assert!(validate(&[mode("tldr", None), mode(" tldr ", None)]).is_err());
+        assert!(validate(&[mode("  ", None)]).is_err());
+    }
+}
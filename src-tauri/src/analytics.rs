@@ -0,0 +1,285 @@
+use std::collections::BTreeMap;
+
+use serde::{Deserialize, Serialize};
+
+use crate::{clock, now_millis};
+use crate::session_report::{self, AgentRun};
+use crate::sessions::{self, SessionInfo, TranscriptSegment};
+use crate::topic_graph::{self, TopicGraph};
+
+const DAY_MS: u64 = 24 * 60 * 60 * 1000;
+const WEEK_MS: u64 = 7 * DAY_MS;
+/// The Unix epoch was a Thursday; weeks start on Monday.
+const EPOCH_TO_MONDAY_MS: u64 = 3 * DAY_MS;
+const TOP_TOPICS: usize = 10;
+
+/// How far back `get_analytics` looks, from now.
+#[derive(Serialize, Deserialize, Clone, Copy, Debug, PartialEq, Eq, specta::Type)]
+#[serde(rename_all = "camelCase")]
+pub enum AnalyticsRange {
+    Week,
+    Month,
+    Quarter,
+    Year,
+    All,
+}
+
+impl AnalyticsRange {
+    fn since(self, now_ms: u64) -> u64 {
+        let days = match self {
+            AnalyticsRange::Week => 7,
+            AnalyticsRange::Month => 30,
+            AnalyticsRange::Quarter => 90,
+            AnalyticsRange::Year => 365,
+            AnalyticsRange::All => return 0,
+        };
+        now_ms.saturating_sub(days * DAY_MS)
+    }
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, specta::Type)]
+#[serde(rename_all = "camelCase")]
+pub struct WeeklyTalkTime {
+    /// Monday 00:00 in the local timezone.
+    pub week_start: u64,
+    pub sessions: u32,
+    pub duration_ms: u64,
+    pub words_transcribed: u32,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, specta::Type)]
+#[serde(rename_all = "camelCase")]
+pub struct TopicTotal {
+    /// Most recent label used for the topic.
+    pub label: String,
+    pub duration_ms: u64,
+    /// Sessions the topic came up in.
+    pub sessions: u32,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, specta::Type)]
+#[serde(rename_all = "camelCase")]
+pub struct ModeUsage {
+    pub mode: String,
+    pub runs: u32,
+    pub failures: u32,
+    pub avg_latency_ms: u64,
+}
+
+/// Trends across the sessions started within a range.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, specta::Type)]
+#[serde(rename_all = "camelCase")]
+pub struct Analytics {
+    pub sessions: u32,
+    pub total_duration_ms: u64,
+    /// Oldest week first; weeks without sessions are left out.
+    pub weeks: Vec<WeeklyTalkTime>,
+    /// Longest-discussed first.
+    pub top_topics: Vec<TopicTotal>,
+    /// Most-used first.
+    pub agent_modes: Vec<ModeUsage>,
+    /// Across all agent runs; `None` when there were none.
+    pub avg_agent_latency_ms: Option<u64>,
+}
+
+/// What a session left on disk, as far as analytics are concerned.
+struct SessionData {
+    info: SessionInfo,
+    transcript: Vec<TranscriptSegment>,
+    runs: Vec<AgentRun>,
+    topics: TopicGraph,
+}
+
+/// Start of the week containing `ms`, for a timezone `offset_ms` ahead
+/// of UTC.
+fn week_start(ms: u64, offset_ms: i64) -> u64 {
+    let shifted = (ms as i64 + offset_ms).max(0) as u64 + EPOCH_TO_MONDAY_MS;
+    let local_monday = (shifted - shifted % WEEK_MS).saturating_sub(EPOCH_TO_MONDAY_MS);
+    (local_monday as i64 - offset_ms).max(0) as u64
+}
+
+/// Sessions still running count up to `now_ms`.
+fn duration_ms(info: &SessionInfo, now_ms: u64) -> u64 {
+    info.ended_at.unwrap_or(now_ms).saturating_sub(info.started_at)
+}
+
+fn aggregate(sessions: &[SessionData], now_ms: u64) -> Analytics {
+    let mut weeks: BTreeMap<u64, WeeklyTalkTime> = BTreeMap::new();
+    let mut topics: BTreeMap<String, TopicTotal> = BTreeMap::new();
+    let mut modes: BTreeMap<&str, (ModeUsage, u64)> = BTreeMap::new();
+    for session in sessions {
+        let duration = duration_ms(&session.info, now_ms);
+        let week = week_start(session.info.started_at, clock::local_offset_ms(session.info.started_at));
+        let entry = weeks.entry(week).or_insert_with(|| WeeklyTalkTime {
+            week_start: week,
+            sessions: 0,
+            duration_ms: 0,
+            words_transcribed: 0,
+        });
+        entry.sessions += 1;
+        entry.duration_ms += duration;
+        entry.words_transcribed += session
+            .transcript
+            .iter()
+            .map(|segment| segment.text.split_whitespace().count() as u32)
+            .sum::<u32>();
+
+        for node in session.topics.clone().with_open_visit_until(duration).nodes {
+            let key = node.label.trim().to_lowercase();
+            if key.is_empty() {
+                continue;
+            }
+            let total = topics.entry(key).or_insert_with(|| TopicTotal {
+                label: node.label.trim().to_string(),
+                duration_ms: 0,
+                sessions: 0,
+            });
+            total.label = node.label.trim().to_string();
+            total.duration_ms += node.duration_ms;
+            total.sessions += 1;
+        }
+
+        for run in &session.runs {
+            let (usage, total_latency) = modes.entry(run.mode.as_str()).or_insert_with(|| {
+                let usage = ModeUsage {
+                    mode: run.mode.clone(),
+                    runs: 0,
+                    failures: 0,
+                    avg_latency_ms: 0,
+                };
+                (usage, 0)
+            });
+            usage.runs += 1;
+            if !run.ok {
+                usage.failures += 1;
+            }
+            *total_latency += run.duration_ms;
+        }
+    }
+
+    let mut top_topics: Vec<TopicTotal> = topics.into_values().collect();
+    top_topics.sort_by_key(|topic| std::cmp::Reverse(topic.duration_ms));
+    top_topics.truncate(TOP_TOPICS);
+
+    let total_runs: u64 = modes.values().map(|(usage, _)| usage.runs as u64).sum();
+    let total_latency: u64 = modes.values().map(|(_, latency)| latency).sum();
+    let mut agent_modes: Vec<ModeUsage> = modes
+        .into_values()
+        .map(|(mut usage, latency)| {
+            usage.avg_latency_ms = latency / usage.runs as u64;
+            usage
+        })
+        .collect();
+    agent_modes.sort_by_key(|usage| std::cmp::Reverse(usage.runs));
+
+    Analytics {
+        sessions: sessions.len() as u32,
+        total_duration_ms: weeks.values().map(|week| week.duration_ms).sum(),
+        weeks: weeks.into_values().collect(),
+        top_topics,
+        agent_modes,
+        avg_agent_latency_ms: (total_runs > 0).then(|| total_latency / total_runs),
+    }
+}
+
+/// Trends across stored sessions started within `range`: talk time per
+/// week, most-discussed topics, and agent usage and latency by mode.
+#[tauri::command]
+#[specta::specta]
+pub async fn get_analytics(app: tauri::AppHandle, range: AnalyticsRange) -> Result<Analytics, String> {
+    tauri::async_runtime::spawn_blocking(move || {
+        let now_ms = now_millis();
+        let since = range.since(now_ms);
+        let sessions: Vec<SessionData> = sessions::list_sessions(app.clone())?
+            .into_iter()
+            .filter(|info| info.started_at >= since)
+            .map(|info| SessionData {
+                transcript: sessions::read_transcript(&app, &info.id).unwrap_or_default(),
+                runs: session_report::read_runs(&app, &info.id),
+                topics: topic_graph::read_graph(&app, &info.id),
+                info,
+            })
+            .collect();
+        Ok(aggregate(&sessions, now_ms))
+    })
+    .await
+    .map_err(|err| format!("analytics task failed: {}", err))?
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{aggregate, week_start, SessionData, DAY_MS};
+    use crate::session_report::AgentRun;
+    use crate::sessions::{SessionInfo, TranscriptSegment};
+    use crate::topic_graph::TopicGraph;
+
+    fn session(started_at: u64, minutes: u64, text: &str, runs: &[(&str, u64, bool)]) -> SessionData {
+        SessionData {
+            info: SessionInfo {
+                id: started_at.to_string(),
+                title: None,
+                started_at,
+                ended_at: Some(started_at + minutes * 60_000),
+                segment_count: 1,
+            },
+            transcript: vec![TranscriptSegment {
+                index: 0,
+                offset_ms: 0,
+                text: text.to_string(),
+                source: None,
+            }],
+            runs: runs
+                .iter()
+                .map(|(mode, duration_ms, ok)| AgentRun {
+                    mode: mode.to_string(),
+                    finished_at: started_at,
+                    duration_ms: *duration_ms,
+                    ok: *ok,
+                    input_tokens: 0,
+                    output_tokens: 0,
+                    cost_usd: None,
+                    tool_calls: 0,
+                    artifacts: Vec::new(),
+                    trace_id: None,
+                })
+                .collect(),
+            topics: TopicGraph::default(),
+        }
+    }
+
+    #[test]
+    fn groups_sessions_by_week_and_agent_runs_by_mode() {
+        // 2024-01-01 was a Monday.
+        let monday = 1_704_067_200_000;
+        assert_eq!(week_start(monday + 3 * DAY_MS + 5, 0), monday);
+        assert_eq!(week_start(monday - 1, 0), monday - 7 * DAY_MS);
+        // Sunday 23:00 in UTC-8 is already Monday in UTC.
+        let pst = -8 * 3_600_000;
+        assert_eq!(week_start(monday + 7 * 3_600_000, pst), monday - 7 * DAY_MS + 8 * 3_600_000);
+
+        let mut first = session(monday + DAY_MS, 30, "hello there", &[("browseros-act", 4000, true)]);
+        first.topics.enter("c1", "Rust async", &["rust".to_string()], 0, Some(0));
+        let mut second = session(monday + 2 * DAY_MS, 15, "again", &[("browseros-act", 2000, false), ("excalidraw-act", 9000, true)]);
+        second.topics.enter("c1", "rust async", &["rust".to_string()], 60_000, Some(0));
+        let third = session(monday + 8 * DAY_MS, 60, "next week", &[]);
+
+        let analytics = aggregate(&[first, second, third], monday + 30 * DAY_MS);
+        assert_eq!(analytics.sessions, 3);
+        assert_eq!(analytics.weeks.len(), 2);
+        assert_eq!(analytics.weeks[0].sessions, 2);
+        assert_eq!(analytics.weeks[0].duration_ms, 45 * 60_000);
+        assert_eq!(analytics.weeks[0].words_transcribed, 3);
+        assert_eq!(analytics.total_duration_ms, 105 * 60_000);
+
+        assert_eq!(analytics.top_topics.len(), 1);
+        assert_eq!(analytics.top_topics[0].label, "rust async");
+        assert_eq!(analytics.top_topics[0].sessions, 2);
+        assert_eq!(analytics.top_topics[0].duration_ms, 30 * 60_000 + 14 * 60_000);
+
+        assert_eq!(analytics.agent_modes[0].mode, "browseros-act");
+        assert_eq!(analytics.agent_modes[0].runs, 2);
+        assert_eq!(analytics.agent_modes[0].failures, 1);
+        assert_eq!(analytics.agent_modes[0].avg_latency_ms, 3000);
+        assert_eq!(analytics.avg_agent_latency_ms, Some(5000));
+    }
+}
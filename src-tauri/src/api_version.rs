@@ -0,0 +1,165 @@
+use std::{
+    collections::{HashMap, HashSet},
+    sync::Mutex,
+};
+
+use serde::Serialize;
+
+use crate::log_line;
+
+/// Command contract implemented by this build. Bump when a command changes
+/// its response shape, gains a required argument or is deprecated, and keep
+/// the previous shape reachable (via `ApiVersionState::client_version`)
+/// until it drops below `MIN_SUPPORTED_API_VERSION`. A new optional
+/// argument needs no bump: older bundles leave it out and get `None`.
+///
+/// - v2: `transcribe_audio` returns `{ text, translation }` and takes a
+///   `mode`; v1 webviews still get the bare text. `get_mcp_config` and
+///   `save_mcp_config` are deprecated.
+pub const API_VERSION: u32 = 2;
+
+/// Oldest frontend contract this build can still serve.
+pub const MIN_SUPPORTED_API_VERSION: u32 = 1;
+
+/// Version assumed for webviews that never called `negotiate_api_version`,
+/// i.e. bundles cached from before negotiation existed.
+const LEGACY_CLIENT_VERSION: u32 = 1;
+
+/// A command name kept as an alias for an older frontend bundle.
+#[derive(Serialize, Clone, specta::Type)]
+#[serde(rename_all = "camelCase")]
+pub struct DeprecatedCommand {
+    pub command: &'static str,
+    pub replacement: &'static str,
+    pub deprecated_in: u32,
+    /// First API version that no longer registers the alias.
+    pub removed_in: u32,
+}
+
+/// Aliases still registered for older bundles. Each entry should have a
+/// matching shim command that calls `note_deprecated_call` and forwards to
+/// the replacement.
+pub const DEPRECATED_COMMANDS: &[DeprecatedCommand] = &[
+    DeprecatedCommand {
+        command: "open_settings_window_command",
+        replacement: "show_settings_window",
+        deprecated_in: 1,
+        removed_in: 3,
+    },
+    DeprecatedCommand {
+        command: "get_mcp_config",
+        replacement: "list_mcp_servers",
+        deprecated_in: 2,
+        removed_in: 4,
+    },
+    DeprecatedCommand {
+        command: "save_mcp_config",
+        replacement: "add_mcp_server / update_mcp_server / remove_mcp_server",
+        deprecated_in: 2,
+        removed_in: 4,
+    },
+];
+
+#[derive(Serialize, specta::Type)]
+#[serde(rename_all = "camelCase")]
+pub struct ApiHandshake {
+    pub backend_version: u32,
+    pub min_supported_version: u32,
+    /// Version the backend will speak to this webview.
+    pub negotiated_version: u32,
+    /// The frontend is older than anything the backend still serves and
+    /// should reload to pick up the bundled assets.
+    pub reload_required: bool,
+    pub deprecated_commands: Vec<DeprecatedCommand>,
+}
+
+/// Negotiated contract per webview label.
+#[derive(Default)]
+pub struct ApiVersionState {
+    clients: Mutex<HashMap<String, u32>>,
+    warned: Mutex<HashSet<String>>,
+}
+
+impl ApiVersionState {
+    /// Contract spoken by the webview `label`. Commands whose shape changed
+    /// between versions branch on this to keep older bundles working.
+    pub fn client_version(&self, label: &str) -> u32 {
+        self.clients
+            .lock()
+            .unwrap()
+            .get(label)
+            .copied()
+            .unwrap_or(LEGACY_CLIENT_VERSION)
+    }
+
+    /// Log the first call to a deprecated command so stale bundles show up
+    /// in the log without flooding it.
+    pub fn note_deprecated_call(&self, label: &str, command: &str) {
+        if !self.warned.lock().unwrap().insert(command.to_string()) {
+            return;
+        }
+        let client = self.client_version(label);
+        match DEPRECATED_COMMANDS.iter().find(|d| d.command == command) {
+            Some(d) => log_line(&format!(
+                "[api] {} (v{}) called deprecated `{}`; use `{}` (removed in API v{})",
+                label, client, d.command, d.replacement, d.removed_in
+            )),
+            None => log_line(&format!(
+                "[api] {} (v{}) called deprecated `{}`",
+                label, client, command
+            )),
+        }
+    }
+}
+
+/// Version the backend should speak to a frontend that supports `client`.
+fn negotiate(client: u32) -> (u32, bool) {
+    if client < MIN_SUPPORTED_API_VERSION {
+        (MIN_SUPPORTED_API_VERSION, true)
+    } else {
+        (client.min(API_VERSION), false)
+    }
+}
+
+/// Called by each webview at startup with the contract it was built
+/// against. Webviews that never call this are treated as API v1.
+#[tauri::command]
+#[specta::specta]
+pub fn negotiate_api_version(
+    window: tauri::WebviewWindow,
+    state: tauri::State<'_, ApiVersionState>,
+    client_version: u32,
+) -> ApiHandshake {
+    let (negotiated_version, reload_required) = negotiate(client_version);
+    log_line(&format!(
+        "[api] {} speaks v{} (backend v{}, negotiated v{})",
+        window.label(),
+        client_version,
+        API_VERSION,
+        negotiated_version
+    ));
+    state
+        .clients
+        .lock()
+        .unwrap()
+        .insert(window.label().to_string(), negotiated_version);
+    ApiHandshake {
+        backend_version: API_VERSION,
+        min_supported_version: MIN_SUPPORTED_API_VERSION,
+        negotiated_version,
+        reload_required,
+        deprecated_commands: DEPRECATED_COMMANDS.to_vec(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{negotiate, API_VERSION, MIN_SUPPORTED_API_VERSION};
+
+    #[test]
+    fn negotiates_down_to_the_older_side() {
+        assert_eq!(negotiate(API_VERSION), (API_VERSION, false));
+        assert_eq!(negotiate(API_VERSION + 5), (API_VERSION, false));
+        assert_eq!(negotiate(0), (MIN_SUPPORTED_API_VERSION, true));
+    }
+}
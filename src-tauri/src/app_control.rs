@@ -0,0 +1,167 @@
+use serde::{Deserialize, Serialize};
+use tauri::Manager;
+use tauri_specta::Event;
+
+use crate::log_line;
+use crate::recording_segment::{self, HeldKind};
+
+/// Something the user asked HeyJamie itself to do, handled without an LLM.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Eq, specta::Type)]
+#[serde(rename_all = "camelCase", tag = "kind")]
+pub enum AppAction {
+    OpenSettings,
+    ShowCanvas,
+    ShowMain,
+    /// Spoken persona name; the frontend resolves it. Empty clears it.
+    SwitchPersona { persona: String },
+    /// Stop volunteering suggestions; direct commands still run.
+    Mute,
+    Unmute,
+    /// Tape is rolling: hold anything visible until the segment ends.
+    StartRecordingSegment,
+    EndRecordingSegment,
+}
+
+/// Sent to the main window for actions it owns (persona, mute, canvas).
+#[derive(Serialize, Deserialize, Clone, specta::Type, tauri_specta::Event)]
+#[serde(rename_all = "camelCase")]
+pub struct AppControl {
+    pub action: AppAction,
+}
+
+struct AppCommand {
+    phrases: &'static [&'static str],
+    /// Builds the action from whatever follows the phrase.
+    action: fn(&str) -> Option<AppAction>,
+}
+
+fn persona_name(rest: &str) -> Option<AppAction> {
+    let name = rest
+        .trim()
+        .trim_start_matches("to ")
+        .trim_start_matches("the ")
+        .trim_end_matches(" persona")
+        .trim();
+    (!name.is_empty()).then(|| AppAction::SwitchPersona {
+        persona: name.to_string(),
+    })
+}
+
+/// Spoken phrases for each app action, matched as whole words at the start
+/// of the command after the wake name. Keep `appControlCommandPattern` in
+/// `src/App.tsx` in step.
+const COMMANDS: &[AppCommand] = &[
+    AppCommand {
+        phrases: &["open settings", "open the settings", "show settings", "show the settings", "open preferences"],
+        action: |_| Some(AppAction::OpenSettings),
+    },
+    AppCommand {
+        phrases: &["show the canvas", "show canvas", "open the canvas", "show the diagram", "open the whiteboard"],
+        action: |_| Some(AppAction::ShowCanvas),
+    },
+    AppCommand {
+        phrases: &["show yourself", "show the app"],
+        action: |_| Some(AppAction::ShowMain),
+    },
+    AppCommand {
+        phrases: &["turn off the persona", "turn off persona", "clear the persona", "no persona"],
+        action: |_| {
+            Some(AppAction::SwitchPersona {
+                persona: String::new(),
+            })
+        },
+    },
+    AppCommand {
+        phrases: &["switch persona", "change persona", "switch your persona", "switch to persona", "use persona"],
+        action: persona_name,
+    },
+    AppCommand {
+        phrases: &["start the segment", "start recording segment", "start a recording segment", "we are rolling", "we're rolling"],
+        action: |_| Some(AppAction::StartRecordingSegment),
+    },
+    AppCommand {
+        phrases: &["end the segment", "end recording segment", "end the recording segment", "we are clear", "we're clear"],
+        action: |_| Some(AppAction::EndRecordingSegment),
+    },
+    AppCommand {
+        phrases: &["unmute yourself", "unmute", "you can talk again", "start talking"],
+        action: |_| Some(AppAction::Unmute),
+    },
+    AppCommand {
+        phrases: &["mute yourself", "mute", "be quiet", "stop talking"],
+        action: |_| Some(AppAction::Mute),
+    },
+];
+
+/// Match a command (the words after the wake name) against the registry.
+pub fn parse_command(command: &str) -> Option<AppAction> {
+    let normalized = command
+        .to_lowercase()
+        .split_whitespace()
+        .map(|word| word.trim_matches(|c: char| !c.is_alphanumeric()))
+        .filter(|word| !word.is_empty() && *word != "please")
+        .collect::<Vec<_>>()
+        .join(" ");
+    COMMANDS.iter().find_map(|command| {
+        command.phrases.iter().find_map(|phrase| {
+            let rest = normalized.strip_prefix(phrase)?;
+            if !(rest.is_empty() || rest.starts_with(' ')) {
+                return None;
+            }
+            (command.action)(rest)
+        })
+    })
+}
+
+/// Carry out an action: windows and recording segments are handled here,
+/// the rest by the frontend.
+pub fn perform(app: &tauri::AppHandle, action: AppAction) -> Result<(), String> {
+    log_line(&format!("[app-control] {:?}", action));
+    let shows_window = matches!(
+        action,
+        AppAction::OpenSettings | AppAction::ShowMain | AppAction::ShowCanvas
+    );
+    if shows_window && recording_segment::hold(app, HeldKind::Window, &format!("{:?}", action)) {
+        return Ok(());
+    }
+    match &action {
+        AppAction::StartRecordingSegment | AppAction::EndRecordingSegment => {
+            recording_segment::set_active(app, action == AppAction::StartRecordingSegment);
+            return Ok(());
+        }
+        AppAction::OpenSettings => return crate::show_settings_window(app.clone()),
+        AppAction::ShowMain => {
+            if let Some(window) = app.get_webview_window("main") {
+                let _ = window.show();
+                let _ = window.set_focus();
+            }
+            return Ok(());
+        }
+        _ => {}
+    }
+    AppControl { action }
+        .emit_to(app, "main")
+        .map_err(|err| format!("failed to notify main window: {}", err))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{parse_command, AppAction};
+
+    #[test]
+    fn matches_registry_phrases() {
+        assert_eq!(parse_command("open settings, please"), Some(AppAction::OpenSettings));
+        assert_eq!(parse_command("Show the canvas."), Some(AppAction::ShowCanvas));
+        assert_eq!(parse_command("unmute yourself"), Some(AppAction::Unmute));
+        assert_eq!(parse_command("mute yourself"), Some(AppAction::Mute));
+        assert_eq!(parse_command("we're rolling"), Some(AppAction::StartRecordingSegment));
+        assert_eq!(
+            parse_command("switch persona to the fact checker"),
+            Some(AppAction::SwitchPersona {
+                persona: "fact checker".to_string()
+            })
+        );
+        assert_eq!(parse_command("muted trumpet history"), None);
+        assert_eq!(parse_command("find the settings for my router"), None);
+    }
+}
@@ -0,0 +1,90 @@
+//! Each session's folder is its working directory: exports, canvas
+//! snapshots and kept audio already live there, and files the agent and its
+//! MCP servers save (screenshots, downloads, clips) go to its `artifacts/`
+//! folder via `HEYJAMIE_ARTIFACTS_DIR`. `export_session_bundle` packs the
+//! whole folder into one archive.
+
+use std::path::PathBuf;
+use std::process::Command;
+
+use tauri::Manager;
+use tauri_plugin_opener::OpenerExt;
+
+use crate::log_line;
+use crate::process_runner::ProcessRunner;
+use crate::sessions::{self, SessionState};
+
+const ARTIFACTS_DIR: &str = "artifacts";
+
+/// `<session>/artifacts`, created on first use.
+pub fn artifacts_dir(app: &tauri::AppHandle, session_id: &str) -> Result<PathBuf, String> {
+    let dir = sessions::session_dir(app, session_id)?.join(ARTIFACTS_DIR);
+    std::fs::create_dir_all(&dir).map_err(|err| format!("failed to create artifacts dir: {}", err))?;
+    Ok(dir)
+}
+
+/// Environment telling an agent run where to save files: the active
+/// session's artifacts folder. Empty outside a session.
+pub fn agent_env(app: &tauri::AppHandle) -> Vec<(String, String)> {
+    let Some(session_id) = app.state::<SessionState>().active_id() else {
+        return Vec::new();
+    };
+    match artifacts_dir(app, &session_id) {
+        Ok(dir) => vec![("HEYJAMIE_ARTIFACTS_DIR".to_string(), dir.display().to_string())],
+        Err(err) => {
+            log_line(&format!("[artifacts] {}", err));
+            Vec::new()
+        }
+    }
+}
+
+/// Show a session's folder in Finder, Explorer or the file manager.
+#[tauri::command]
+#[specta::specta]
+pub fn reveal_session_folder(app: tauri::AppHandle, session_id: String) -> Result<(), String> {
+    let dir = sessions::session_dir(&app, &session_id)?;
+    if !dir.is_dir() {
+        return Err(format!("session not found: {}", session_id));
+    }
+    app.opener()
+        .reveal_item_in_dir(&dir)
+        .map_err(|err| format!("failed to reveal {}: {}", dir.display(), err))
+}
+
+/// Pack a session's folder (transcript, exports, snapshots, audio and
+/// artifacts) into `<session id>.tar.gz` in `destination`, by default the
+/// Downloads folder. Returns the archive's path.
+#[tauri::command]
+#[specta::specta]
+pub async fn export_session_bundle(
+    app: tauri::AppHandle,
+    session_id: String,
+    destination: Option<String>,
+) -> Result<String, String> {
+    let dir = sessions::session_dir(&app, &session_id)?;
+    if !dir.is_dir() {
+        return Err(format!("session not found: {}", session_id));
+    }
+    let destination = match destination.map(|d| d.trim().to_string()).filter(|d| !d.is_empty()) {
+        Some(destination) => PathBuf::from(destination),
+        None => dirs::download_dir()
+            .or_else(dirs::home_dir)
+            .ok_or("no Downloads folder; pass a destination")?,
+    };
+    let archive = destination.join(format!("{}.tar.gz", session_id));
+    let mut command = Command::new("tar");
+    command
+        .arg("-czf")
+        .arg(&archive)
+        .arg("-C")
+        .arg(sessions::sessions_dir(&app)?)
+        .arg(&session_id);
+    let output = tauri::async_runtime::spawn_blocking(move || ProcessRunner::new("tar", command).run())
+        .await
+        .map_err(|err| format!("bundle task failed: {}", err))??;
+    if !output.status.success() {
+        return Err(format!("tar failed: {}", String::from_utf8_lossy(&output.stderr).trim()));
+    }
+    log_line(&format!("[artifacts] bundled {} -> {}", session_id, archive.display()));
+    Ok(archive.display().to_string())
+}
@@ -0,0 +1,141 @@
+//! Compressed audio (webm/ogg/mp3/m4a) from `MediaRecorder` or a file,
+//! turned into the 16 kHz mono WAV the transcription path expects.
+
+use std::io::{Cursor, ErrorKind, Write};
+use std::process::{Command, Stdio};
+
+use symphonia::core::audio::SampleBuffer;
+use symphonia::core::codecs::{DecoderOptions, CODEC_TYPE_NULL};
+use symphonia::core::errors::Error as SymphoniaError;
+use symphonia::core::formats::FormatOptions;
+use symphonia::core::io::MediaSourceStream;
+use symphonia::core::meta::MetadataOptions;
+use symphonia::core::probe::Hint;
+
+use crate::{log_line, recording, wav};
+
+const WHISPER_SAMPLE_RATE: u32 = 16_000;
+
+fn is_wav(bytes: &[u8]) -> bool {
+    bytes.len() >= 12 && &bytes[0..4] == b"RIFF" && &bytes[8..12] == b"WAVE"
+}
+
+/// Average interleaved frames down to one channel.
+fn downmix(interleaved: &[f32], channels: usize) -> Vec<f32> {
+    if channels <= 1 {
+        return interleaved.to_vec();
+    }
+    interleaved
+        .chunks(channels)
+        .map(|frame| frame.iter().sum::<f32>() / frame.len() as f32)
+        .collect()
+}
+
+/// Decode with symphonia: mp3, AAC/ALAC in m4a, Vorbis/FLAC in ogg or webm.
+/// Opus, what `MediaRecorder` usually produces, is not supported.
+fn decode_with_symphonia(bytes: &[u8]) -> Result<(u32, Vec<f32>), String> {
+    let source = MediaSourceStream::new(Box::new(Cursor::new(bytes.to_vec())), Default::default());
+    let probed = symphonia::default::get_probe()
+        .format(&Hint::new(), source, &FormatOptions::default(), &MetadataOptions::default())
+        .map_err(|err| format!("unrecognized audio format: {}", err))?;
+    let mut format = probed.format;
+    let track = format
+        .tracks()
+        .iter()
+        .find(|track| track.codec_params.codec != CODEC_TYPE_NULL)
+        .ok_or_else(|| "no audio track".to_string())?;
+    let track_id = track.id;
+    let mut sample_rate = track.codec_params.sample_rate.unwrap_or(0);
+    let mut decoder = symphonia::default::get_codecs()
+        .make(&track.codec_params, &DecoderOptions::default())
+        .map_err(|err| format!("unsupported codec: {}", err))?;
+
+    let mut samples = Vec::new();
+    loop {
+        let packet = match format.next_packet() {
+            Ok(packet) => packet,
+            Err(SymphoniaError::IoError(err)) if err.kind() == ErrorKind::UnexpectedEof => break,
+            Err(err) => return Err(format!("failed to read audio: {}", err)),
+        };
+        if packet.track_id() != track_id {
+            continue;
+        }
+        match decoder.decode(&packet) {
+            Ok(decoded) => {
+                let spec = *decoded.spec();
+                let mut buffer = SampleBuffer::<f32>::new(decoded.capacity() as u64, spec);
+                buffer.copy_interleaved_ref(decoded);
+                sample_rate = spec.rate;
+                samples.extend(downmix(buffer.samples(), spec.channels.count()));
+            }
+            // A corrupt packet; skip it like players do.
+            Err(SymphoniaError::DecodeError(_)) => continue,
+            Err(err) => return Err(format!("failed to decode audio: {}", err)),
+        }
+    }
+    if sample_rate == 0 || samples.is_empty() {
+        return Err("no audio decoded".to_string());
+    }
+    Ok((sample_rate, samples))
+}
+
+/// Let `ffmpeg` (on `PATH` or `HEYJAMIE_FFMPEG_PATH`) convert the payload.
+fn convert_with_ffmpeg(bytes: &[u8]) -> Result<Vec<u8>, String> {
+    let ffmpeg = std::env::var("HEYJAMIE_FFMPEG_PATH").unwrap_or_else(|_| "ffmpeg".to_string());
+    let mut child = Command::new(&ffmpeg)
+        .args(["-hide_banner", "-loglevel", "error", "-i", "pipe:0"])
+        .args(["-ar", &WHISPER_SAMPLE_RATE.to_string(), "-ac", "1", "-c:a", "pcm_s16le", "-f", "wav", "pipe:1"])
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .spawn()
+        .map_err(|err| format!("failed to run {}: {}", ffmpeg, err))?;
+    // Feed stdin from its own thread so a full stdout pipe can't deadlock.
+    let mut stdin = child.stdin.take().ok_or_else(|| "failed to open ffmpeg stdin".to_string())?;
+    let input = bytes.to_vec();
+    let writer = std::thread::spawn(move || stdin.write_all(&input));
+    let output = child
+        .wait_with_output()
+        .map_err(|err| format!("failed to wait for ffmpeg: {}", err))?;
+    let _ = writer.join();
+    if !output.status.success() || !is_wav(&output.stdout) {
+        return Err(format!(
+            "ffmpeg could not convert the audio: {}",
+            String::from_utf8_lossy(&output.stderr).trim()
+        ));
+    }
+    Ok(output.stdout)
+}
+
+/// `bytes` as a WAV: WAV input is passed through untouched, anything else
+/// is decoded with symphonia, then ffmpeg, and resampled to 16 kHz mono.
+pub fn to_wav(bytes: Vec<u8>) -> Result<Vec<u8>, String> {
+    if is_wav(&bytes) {
+        return Ok(bytes);
+    }
+    match decode_with_symphonia(&bytes) {
+        Ok((sample_rate, samples)) => {
+            let samples = recording::downsample(&samples, sample_rate, WHISPER_SAMPLE_RATE);
+            Ok(wav::encode_pcm16_mono(WHISPER_SAMPLE_RATE, &samples))
+        }
+        Err(err) => {
+            log_line(&format!("[audio-decode] {}; trying ffmpeg", err));
+            convert_with_ffmpeg(&bytes).map_err(|ffmpeg_err| format!("{}; {}", err, ffmpeg_err))
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{downmix, is_wav, to_wav};
+    use crate::wav;
+
+    #[test]
+    fn passes_wav_through_and_downmixes_frames() {
+        let wav_bytes = wav::encode_pcm16_mono(16_000, &[0.0, 0.5]);
+        assert!(is_wav(&wav_bytes));
+        assert_eq!(to_wav(wav_bytes.clone()).unwrap(), wav_bytes);
+        assert!(!is_wav(b"\x1aE\xdf\xa3webm"));
+        assert_eq!(downmix(&[1.0, 0.0, 0.5, 0.5], 2), vec![0.5, 0.5]);
+    }
+}
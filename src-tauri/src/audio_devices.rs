@@ -0,0 +1,178 @@
+//! Input device enumeration and selection. The chosen microphone is kept
+//! by name in `transcription.inputDevice` (empty follows the OS default)
+//! and opened by native recording; the webview matches it to a media
+//! device label. cpal has no hotplug notifications, so the device list
+//! and the default are polled and `device-changed` is emitted when either
+//! changes (AirPods connecting, an interface unplugged).
+
+use std::sync::Mutex;
+use std::time::Duration;
+
+use cpal::traits::{DeviceTrait, HostTrait};
+use serde::{Deserialize, Serialize};
+use tauri::Manager;
+use tauri_specta::Event;
+
+use crate::log_line;
+use crate::settings;
+
+const DEVICE_CHECK_INTERVAL: Duration = Duration::from_secs(3);
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, specta::Type)]
+#[serde(rename_all = "camelCase")]
+pub struct AudioDevice {
+    pub name: String,
+    /// The OS default input.
+    pub is_default: bool,
+    /// The device in `transcription.inputDevice`.
+    pub selected: bool,
+    pub channels: Option<u16>,
+    pub sample_rate: Option<u32>,
+}
+
+/// Emitted as `device-changed` when an input appears or disappears or the
+/// OS default switches.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, specta::Type, tauri_specta::Event)]
+#[serde(rename_all = "camelCase")]
+pub struct DeviceChanged {
+    pub default_device: Option<String>,
+    pub devices: Vec<String>,
+    /// The chosen device, if one is set and it's connected. Recording
+    /// falls back to the default while it isn't.
+    pub selected: Option<String>,
+}
+
+#[derive(Default)]
+pub struct AudioDeviceState {
+    last: Mutex<Option<(Option<String>, Vec<String>)>>,
+}
+
+fn default_name(host: &cpal::Host) -> Option<String> {
+    host.default_input_device().and_then(|device| device.name().ok())
+}
+
+fn input_names(host: &cpal::Host) -> Vec<String> {
+    host.input_devices()
+        .map(|devices| devices.filter_map(|device| device.name().ok()).collect())
+        .unwrap_or_default()
+}
+
+/// `selected` when it's set and among `devices`, matched like the webview
+/// matches labels: ignoring case.
+fn resolve_selected(selected: &str, devices: &[String]) -> Option<String> {
+    let selected = selected.trim();
+    if selected.is_empty() {
+        return None;
+    }
+    devices.iter().find(|name| name.eq_ignore_ascii_case(selected)).cloned()
+}
+
+/// The microphone to record from: the chosen one, or the OS default when
+/// none is chosen or it's disconnected.
+pub fn input_device(app: &tauri::AppHandle) -> Result<cpal::Device, String> {
+    let host = cpal::default_host();
+    let selected = settings::load_settings(app).transcription.input_device;
+    if !selected.trim().is_empty() {
+        let found = host.input_devices().ok().and_then(|mut devices| {
+            devices.find(|device| device.name().is_ok_and(|name| name.eq_ignore_ascii_case(selected.trim())))
+        });
+        match found {
+            Some(device) => return Ok(device),
+            None => log_line(&format!("[audio-devices] {} is not connected; using the default input", selected)),
+        }
+    }
+    host.default_input_device().ok_or_else(|| "no microphone found".to_string())
+}
+
+fn check(app: &tauri::AppHandle) {
+    let host = cpal::default_host();
+    let current = (default_name(&host), input_names(&host));
+    let previous = app.state::<AudioDeviceState>().last.lock().unwrap().replace(current.clone());
+    // The first reading is the baseline, not a change.
+    if previous.is_none() || previous.as_ref() == Some(&current) {
+        return;
+    }
+    let (default_device, devices) = current;
+    log_line(&format!(
+        "[audio-devices] inputs changed; default is {}",
+        default_device.as_deref().unwrap_or("none")
+    ));
+    let selected = resolve_selected(&settings::load_settings(app).transcription.input_device, &devices);
+    let event = DeviceChanged {
+        default_device,
+        devices,
+        selected,
+    };
+    if let Err(err) = event.emit(app) {
+        log_line(&format!("[audio-devices] failed to emit device-changed: {}", err));
+    }
+}
+
+/// Poll the inputs for hotplug and default switches.
+pub fn spawn_monitor(app: tauri::AppHandle) {
+    tauri::async_runtime::spawn(async move {
+        loop {
+            let handle = app.clone();
+            let _ = tauri::async_runtime::spawn_blocking(move || check(&handle)).await;
+            tokio::time::sleep(DEVICE_CHECK_INTERVAL).await;
+        }
+    });
+}
+
+#[tauri::command]
+#[specta::specta]
+pub fn list_audio_devices(app: tauri::AppHandle) -> Result<Vec<AudioDevice>, String> {
+    let host = cpal::default_host();
+    let default_device = default_name(&host);
+    let selected = settings::load_settings(&app).transcription.input_device;
+    let devices = host
+        .input_devices()
+        .map_err(|err| format!("failed to list input devices: {}", err))?;
+    Ok(devices
+        .filter_map(|device| {
+            let name = device.name().ok()?;
+            let config = device.default_input_config().ok();
+            Some(AudioDevice {
+                is_default: default_device.as_deref() == Some(name.as_str()),
+                selected: !selected.trim().is_empty() && name.eq_ignore_ascii_case(selected.trim()),
+                channels: config.as_ref().map(|config| config.channels()),
+                sample_rate: config.map(|config| config.sample_rate().0),
+                name,
+            })
+        })
+        .collect())
+}
+
+/// Record from `name` from the next recording on; `None` or empty follows
+/// the OS default.
+#[tauri::command]
+#[specta::specta]
+pub fn set_audio_device(app: tauri::AppHandle, name: Option<String>) -> Result<(), String> {
+    let name = name.map(|name| name.trim().to_string()).unwrap_or_default();
+    if !name.is_empty() && resolve_selected(&name, &input_names(&cpal::default_host())).is_none() {
+        return Err(format!("no input device named {}", name));
+    }
+    let mut settings = settings::load_settings(&app);
+    settings.transcription.input_device = name;
+    settings::save_settings(&app, &settings)?;
+    log_line(&format!(
+        "[audio-devices] input set to {}",
+        Some(settings.transcription.input_device.as_str())
+            .filter(|name| !name.is_empty())
+            .unwrap_or("the OS default")
+    ));
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::resolve_selected;
+
+    #[test]
+    fn selection_falls_back_when_disconnected() {
+        let devices = vec!["MacBook Pro Microphone".to_string(), "Scarlett 2i2 USB".to_string()];
+        assert_eq!(resolve_selected("scarlett 2i2 usb", &devices).as_deref(), Some("Scarlett 2i2 USB"));
+        assert_eq!(resolve_selected("AirPods Pro", &devices), None);
+        assert_eq!(resolve_selected(" ", &devices), None);
+    }
+}
@@ -0,0 +1,445 @@
+use std::{collections::HashMap, sync::Mutex, time::Duration};
+
+use serde::{Deserialize, Serialize};
+use serde_json::Value as JsonValue;
+use tauri::Manager;
+use tauri_specta::Event;
+
+use crate::settings::{self, OAuthProviderConfig};
+use crate::{keychain, log_line, now_millis, truncate_for_log};
+
+const DEVICE_CODE_GRANT: &str = "urn:ietf:params:oauth:grant-type:device_code";
+/// Refresh tokens this long before they expire so in-flight requests
+/// don't race the expiry.
+const REFRESH_MARGIN_MS: u64 = 60_000;
+
+/// Providers known to support the device authorization grant. Client ids
+/// come from settings (or `HEYJAMIE_<ID>_CLIENT_ID`) since they are tied to
+/// whoever registered the OAuth app.
+fn builtin_providers() -> Vec<OAuthProviderConfig> {
+    vec![
+        OAuthProviderConfig {
+            id: "google".to_string(),
+            name: "Google".to_string(),
+            client_id: String::new(),
+            client_secret: None,
+            device_authorization_url: "https://oauth2.googleapis.com/device/code".to_string(),
+            token_url: "https://oauth2.googleapis.com/token".to_string(),
+            revoke_url: Some("https://oauth2.googleapis.com/revoke".to_string()),
+            scopes: vec![
+                "https://www.googleapis.com/auth/drive.file".to_string(),
+                "https://www.googleapis.com/auth/documents".to_string(),
+                "https://www.googleapis.com/auth/spreadsheets".to_string(),
+            ],
+        },
+        OAuthProviderConfig {
+            id: "github".to_string(),
+            name: "GitHub".to_string(),
+            client_id: String::new(),
+            client_secret: None,
+            device_authorization_url: "https://github.com/login/device/code".to_string(),
+            token_url: "https://github.com/login/oauth/access_token".to_string(),
+            revoke_url: None,
+            scopes: vec!["read:user".to_string()],
+        },
+    ]
+}
+
+/// Settings entries override built-ins with the same id; a built-in with no
+/// configured client id falls back to the environment.
+fn provider_config(app: &tauri::AppHandle, provider: &str) -> Result<OAuthProviderConfig, String> {
+    let configured = settings::load_settings(app).auth.providers;
+    let mut config = configured
+        .into_iter()
+        .find(|p| p.id == provider)
+        .or_else(|| builtin_providers().into_iter().find(|p| p.id == provider))
+        .ok_or_else(|| format!("unknown account provider: {}", provider))?;
+    if config.client_id.trim().is_empty() {
+        let env_key = format!("HEYJAMIE_{}_CLIENT_ID", provider.to_uppercase().replace('-', "_"));
+        config.client_id = std::env::var(env_key).unwrap_or_default();
+    }
+    if config.client_id.trim().is_empty() {
+        return Err(format!("no OAuth client id configured for {}", config.name));
+    }
+    Ok(config)
+}
+
+fn all_providers(app: &tauri::AppHandle) -> Vec<OAuthProviderConfig> {
+    let mut providers = settings::load_settings(app).auth.providers;
+    for builtin in builtin_providers() {
+        if !providers.iter().any(|p| p.id == builtin.id) {
+            providers.push(builtin);
+        }
+    }
+    providers
+}
+
+#[derive(Serialize, Deserialize, Clone)]
+#[serde(rename_all = "camelCase")]
+struct StoredToken {
+    access_token: String,
+    refresh_token: Option<String>,
+    expires_at: Option<u64>,
+    scope: Option<String>,
+}
+
+fn keychain_account(provider: &str) -> String {
+    format!("oauth:{}", provider)
+}
+
+fn load_token(provider: &str) -> Result<Option<StoredToken>, String> {
+    Ok(keychain::get_secret(&keychain_account(provider))?
+        .and_then(|raw| serde_json::from_str(&raw).ok()))
+}
+
+fn store_token(provider: &str, token: &StoredToken) -> Result<(), String> {
+    let raw = serde_json::to_string(token).map_err(|err| format!("failed to format token: {}", err))?;
+    keychain::set_secret(&keychain_account(provider), &raw)
+}
+
+#[derive(Serialize, Clone, specta::Type)]
+#[serde(rename_all = "camelCase")]
+pub struct DeviceAuthorization {
+    pub provider: String,
+    /// Code the user types at `verification_uri`.
+    pub user_code: String,
+    pub verification_uri: String,
+    pub verification_uri_complete: Option<String>,
+    pub expires_in: u64,
+}
+
+#[derive(Serialize, Clone, specta::Type)]
+#[serde(rename_all = "camelCase")]
+pub struct AccountStatus {
+    pub provider: String,
+    pub name: String,
+    pub configured: bool,
+    pub connected: bool,
+    pub scopes: Vec<String>,
+    pub expires_at: Option<u64>,
+}
+
+/// Emitted when a device flow finishes (either way) or an account is
+/// disconnected.
+#[derive(Serialize, Deserialize, Clone, specta::Type, tauri_specta::Event)]
+#[serde(rename_all = "camelCase")]
+pub struct AccountChanged {
+    pub provider: String,
+    pub connected: bool,
+    pub error: Option<String>,
+}
+
+/// In-flight device flows, keyed by provider. Starting a new flow or
+/// disconnecting replaces the id, which stops the old poller.
+#[derive(Default)]
+pub struct AuthState {
+    pending: Mutex<HashMap<String, u64>>,
+}
+
+impl AuthState {
+    fn is_current(&self, provider: &str, flow_id: u64) -> bool {
+        self.pending.lock().unwrap().get(provider) == Some(&flow_id)
+    }
+}
+
+pub(crate) fn http_client() -> Result<reqwest::Client, String> {
+    reqwest::Client::builder()
+        .timeout(Duration::from_secs(20))
+        .build()
+        .map_err(|err| format!("failed to build HTTP client: {}", err))
+}
+
+/// POST a form and parse the JSON reply. OAuth errors come back as JSON
+/// bodies with 4xx statuses, so the body is returned either way.
+async fn post_form(client: &reqwest::Client, url: &str, form: &[(&str, &str)]) -> Result<JsonValue, String> {
+    let response = client
+        .post(url)
+        .header("Accept", "application/json")
+        .form(form)
+        .send()
+        .await
+        .map_err(|err| format!("request to {} failed: {}", url, err))?;
+    let status = response.status();
+    let body = response
+        .text()
+        .await
+        .map_err(|err| format!("failed to read response: {}", err))?;
+    serde_json::from_str(&body).map_err(|_| format!("HTTP {}: {}", status, truncate_for_log(body.trim(), 300)))
+}
+
+fn token_from_response(body: &JsonValue, previous_refresh: Option<String>) -> Option<StoredToken> {
+    let access_token = body.get("access_token")?.as_str()?.to_string();
+    Some(StoredToken {
+        access_token,
+        refresh_token: body
+            .get("refresh_token")
+            .and_then(|v| v.as_str())
+            .map(str::to_string)
+            .or(previous_refresh),
+        expires_at: body
+            .get("expires_in")
+            .and_then(|v| v.as_u64())
+            .map(|secs| now_millis() + secs * 1000),
+        scope: body.get("scope").and_then(|v| v.as_str()).map(str::to_string),
+    })
+}
+
+async fn poll_device_flow(
+    app: tauri::AppHandle,
+    config: OAuthProviderConfig,
+    flow_id: u64,
+    device_code: String,
+    mut interval: u64,
+    expires_in: u64,
+) {
+    let finish = |connected: bool, error: Option<String>| {
+        app.state::<AuthState>()
+            .pending
+            .lock()
+            .unwrap()
+            .remove(&config.id);
+        if let Some(err) = error.as_deref() {
+            log_line(&format!("[auth] {} device flow failed: {}", config.id, err));
+        }
+        let _ = AccountChanged {
+            provider: config.id.clone(),
+            connected,
+            error,
+        }
+        .emit(&app);
+    };
+    let client = match http_client() {
+        Ok(client) => client,
+        Err(err) => return finish(false, Some(err)),
+    };
+    let deadline = now_millis() + expires_in * 1000;
+    let secret = config.client_secret.clone().unwrap_or_default();
+
+    while now_millis() < deadline {
+        tokio::time::sleep(Duration::from_secs(interval)).await;
+        if !app.state::<AuthState>().is_current(&config.id, flow_id) {
+            return;
+        }
+        let mut form = vec![
+            ("grant_type", DEVICE_CODE_GRANT),
+            ("device_code", device_code.as_str()),
+            ("client_id", config.client_id.as_str()),
+        ];
+        if !secret.is_empty() {
+            form.push(("client_secret", secret.as_str()));
+        }
+        let body = match post_form(&client, &config.token_url, &form).await {
+            Ok(body) => body,
+            Err(err) => {
+                log_line(&format!("[auth] {} poll error: {}", config.id, err));
+                continue;
+            }
+        };
+        if let Some(token) = token_from_response(&body, None) {
+            let result = store_token(&config.id, &token);
+            log_line(&format!("[auth] {} connected", config.id));
+            return finish(result.is_ok(), result.err());
+        }
+        match body.get("error").and_then(|v| v.as_str()).unwrap_or("") {
+            "authorization_pending" => {}
+            "slow_down" => interval += 5,
+            "access_denied" => return finish(false, Some("access was denied".to_string())),
+            "expired_token" => break,
+            other => {
+                let description = body
+                    .get("error_description")
+                    .and_then(|v| v.as_str())
+                    .unwrap_or(other);
+                return finish(false, Some(description.to_string()));
+            }
+        }
+    }
+    finish(false, Some("the sign-in code expired".to_string()));
+}
+
+/// A valid access token for `provider`, refreshed first when it is about to
+/// expire. Used by export adapters and other integrations.
+pub async fn access_token(app: &tauri::AppHandle, provider: &str) -> Result<String, String> {
+    let token = load_token(provider)?.ok_or_else(|| format!("{} account is not connected", provider))?;
+    let expiring = token
+        .expires_at
+        .is_some_and(|at| at <= now_millis() + REFRESH_MARGIN_MS);
+    if !expiring {
+        return Ok(token.access_token);
+    }
+    let refresh_token = token
+        .refresh_token
+        .clone()
+        .ok_or_else(|| format!("{} session expired; reconnect the account", provider))?;
+    let config = provider_config(app, provider)?;
+    let secret = config.client_secret.clone().unwrap_or_default();
+    let mut form = vec![
+        ("grant_type", "refresh_token"),
+        ("refresh_token", refresh_token.as_str()),
+        ("client_id", config.client_id.as_str()),
+    ];
+    if !secret.is_empty() {
+        form.push(("client_secret", secret.as_str()));
+    }
+    let body = post_form(&http_client()?, &config.token_url, &form).await?;
+    let refreshed = token_from_response(&body, Some(refresh_token)).ok_or_else(|| {
+        let error = body.get("error").and_then(|v| v.as_str()).unwrap_or("unknown error");
+        format!("failed to refresh {} token: {}", provider, error)
+    })?;
+    store_token(provider, &refreshed)?;
+    log_line(&format!("[auth] refreshed {} token", provider));
+    Ok(refreshed.access_token)
+}
+
+/// Providers referenced as `${account:<provider>}` in `text`.
+fn account_placeholders(text: &str) -> Vec<String> {
+    let mut providers: Vec<String> = text
+        .split("${account:")
+        .skip(1)
+        .filter_map(|rest| rest.split_once('}').map(|(provider, _)| provider.trim().to_lowercase()))
+        .filter(|provider| {
+            !provider.is_empty()
+                && provider
+                    .chars()
+                    .all(|c| c.is_ascii_alphanumeric() || c == '-' || c == '_')
+        })
+        .collect();
+    providers.sort();
+    providers.dedup();
+    providers
+}
+
+/// `HEYJAMIE_ACCOUNT_TOKEN_<PROVIDER>` variables for the agent process, one
+/// per account placeholder in the MCP config. Blocks on token refresh, so
+/// call it from a blocking context.
+pub fn account_token_env(app: &tauri::AppHandle, mcp_config: &str) -> Vec<(String, String)> {
+    account_placeholders(mcp_config)
+        .into_iter()
+        .filter_map(|provider| {
+            match tauri::async_runtime::block_on(access_token(app, &provider)) {
+                Ok(token) => Some((
+                    format!("HEYJAMIE_ACCOUNT_TOKEN_{}", provider.to_uppercase().replace('-', "_")),
+                    token,
+                )),
+                Err(err) => {
+                    log_line(&format!("[auth] MCP config needs {}: {}", provider, err));
+                    None
+                }
+            }
+        })
+        .collect()
+}
+
+/// Start an OAuth device flow. Returns the code to show the user; the
+/// result arrives later as an `account-changed` event.
+#[tauri::command]
+#[specta::specta]
+pub async fn connect_account(app: tauri::AppHandle, provider: String) -> Result<DeviceAuthorization, String> {
+    let config = provider_config(&app, &provider)?;
+    let scope = config.scopes.join(" ");
+    let body = post_form(
+        &http_client()?,
+        &config.device_authorization_url,
+        &[("client_id", config.client_id.as_str()), ("scope", scope.as_str())],
+    )
+    .await?;
+    let field = |name: &str| body.get(name).and_then(|v| v.as_str()).map(str::to_string);
+    let device_code = field("device_code").ok_or_else(|| {
+        format!(
+            "{} did not start a device flow: {}",
+            config.name,
+            field("error_description").or_else(|| field("error")).unwrap_or_default()
+        )
+    })?;
+    let authorization = DeviceAuthorization {
+        provider: config.id.clone(),
+        user_code: field("user_code").unwrap_or_default(),
+        // Google uses `verification_url`, RFC 8628 uses `verification_uri`.
+        verification_uri: field("verification_uri")
+            .or_else(|| field("verification_url"))
+            .unwrap_or_default(),
+        verification_uri_complete: field("verification_uri_complete"),
+        expires_in: body.get("expires_in").and_then(|v| v.as_u64()).unwrap_or(900),
+    };
+    let interval = body.get("interval").and_then(|v| v.as_u64()).unwrap_or(5).max(1);
+
+    let flow_id = now_millis();
+    app.state::<AuthState>()
+        .pending
+        .lock()
+        .unwrap()
+        .insert(config.id.clone(), flow_id);
+    log_line(&format!("[auth] {} device flow started", config.id));
+    tauri::async_runtime::spawn(poll_device_flow(
+        app.clone(),
+        config,
+        flow_id,
+        device_code,
+        interval,
+        authorization.expires_in,
+    ));
+    Ok(authorization)
+}
+
+/// Forget the stored token (revoking it where the provider supports that)
+/// and cancel any pending sign-in.
+#[tauri::command]
+#[specta::specta]
+pub async fn disconnect_account(app: tauri::AppHandle, provider: String) -> Result<(), String> {
+    app.state::<AuthState>().pending.lock().unwrap().remove(&provider);
+    if let Some(token) = load_token(&provider)? {
+        let revoke_url = all_providers(&app)
+            .into_iter()
+            .find(|p| p.id == provider)
+            .and_then(|p| p.revoke_url);
+        if let Some(url) = revoke_url {
+            let revoked = post_form(&http_client()?, &url, &[("token", token.access_token.as_str())]).await;
+            if let Err(err) = revoked {
+                log_line(&format!("[auth] {} revoke failed (ignored): {}", provider, err));
+            }
+        }
+    }
+    keychain::delete_secret(&keychain_account(&provider))?;
+    log_line(&format!("[auth] {} disconnected", provider));
+    let _ = AccountChanged {
+        provider,
+        connected: false,
+        error: None,
+    }
+    .emit(&app);
+    Ok(())
+}
+
+#[tauri::command]
+#[specta::specta]
+pub fn list_accounts(app: tauri::AppHandle) -> Vec<AccountStatus> {
+    all_providers(&app)
+        .into_iter()
+        .map(|config| {
+            let token = load_token(&config.id).ok().flatten();
+            AccountStatus {
+                configured: provider_config(&app, &config.id).is_ok(),
+                connected: token.is_some(),
+                scopes: token
+                    .as_ref()
+                    .and_then(|t| t.scope.as_deref())
+                    .map(|s| s.split_whitespace().map(str::to_string).collect())
+                    .unwrap_or_default(),
+                expires_at: token.and_then(|t| t.expires_at),
+                provider: config.id,
+                name: config.name,
+            }
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::account_placeholders;
+
+    #[test]
+    fn finds_account_placeholders() {
+        let config = r#"{"env":{"GITHUB_TOKEN":"${account:GitHub}","B":"Bearer ${account:google}","C":"${account:github}","D":"${account:bad id}"}}"#;
+        assert_eq!(account_placeholders(config), vec!["github", "google"]);
+    }
+}
@@ -0,0 +1,163 @@
+//! Keeps background jobs out of the way of latency-sensitive work. An
+//! interactive agent run or a whisper transcription holds a `Burst` while
+//! in flight; background jobs (endpoint and MCP health checks, model
+//! verification, the research agenda) call `wait_for_quiet` first and
+//! hold off until no burst has been running for a few seconds, so they
+//! don't compete for bandwidth and CPU at the worst moment. The wait is
+//! capped so a long live session can't starve them.
+
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
+
+use serde::{Deserialize, Serialize};
+use tauri::Manager;
+
+use crate::log_line;
+
+/// Quiet needed after a burst ends, bridging the gaps between segments.
+const RESUME_AFTER: Duration = Duration::from_secs(3);
+const POLL_INTERVAL: Duration = Duration::from_millis(500);
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum BurstKind {
+    AgentRun,
+    Transcription,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, specta::Type)]
+#[serde(rename_all = "camelCase")]
+pub struct BackgroundActivity {
+    /// Background jobs are holding off.
+    pub paused: bool,
+    pub agent_runs: u32,
+    pub transcriptions: u32,
+}
+
+#[derive(Default)]
+struct Bursts {
+    agent_runs: u32,
+    transcriptions: u32,
+    last_end: Option<Instant>,
+}
+
+impl Bursts {
+    fn begin(&mut self, kind: BurstKind) {
+        match kind {
+            BurstKind::AgentRun => self.agent_runs += 1,
+            BurstKind::Transcription => self.transcriptions += 1,
+        }
+    }
+
+    fn end(&mut self, kind: BurstKind, now: Instant) {
+        match kind {
+            BurstKind::AgentRun => self.agent_runs = self.agent_runs.saturating_sub(1),
+            BurstKind::Transcription => self.transcriptions = self.transcriptions.saturating_sub(1),
+        }
+        self.last_end = Some(now);
+    }
+
+    /// What background jobs are waiting on at `now`, if anything.
+    fn busy(&self, now: Instant) -> Option<&'static str> {
+        if self.agent_runs > 0 {
+            Some("an agent run")
+        } else if self.transcriptions > 0 {
+            Some("transcription")
+        } else if self.last_end.is_some_and(|end| now.duration_since(end) < RESUME_AFTER) {
+            Some("a burst that just ended")
+        } else {
+            None
+        }
+    }
+}
+
+#[derive(Default)]
+pub struct BackgroundState {
+    bursts: Mutex<Bursts>,
+}
+
+/// Marks latency-sensitive work in flight until dropped.
+pub struct Burst {
+    app: tauri::AppHandle,
+    kind: BurstKind,
+}
+
+impl Drop for Burst {
+    fn drop(&mut self) {
+        self.app
+            .state::<BackgroundState>()
+            .bursts
+            .lock()
+            .unwrap()
+            .end(self.kind, Instant::now());
+    }
+}
+
+pub fn burst(app: &tauri::AppHandle, kind: BurstKind) -> Burst {
+    app.state::<BackgroundState>().bursts.lock().unwrap().begin(kind);
+    Burst {
+        app: app.clone(),
+        kind,
+    }
+}
+
+/// What background jobs are waiting on right now, if anything.
+pub fn busy(app: &tauri::AppHandle) -> Option<&'static str> {
+    app.state::<BackgroundState>().bursts.lock().unwrap().busy(Instant::now())
+}
+
+/// Hold `job` off until no burst is running, or `max_wait` has passed.
+pub async fn wait_for_quiet(app: &tauri::AppHandle, job: &str, max_wait: Duration) {
+    let Some(reason) = busy(app) else {
+        return;
+    };
+    log_line(&format!("[background] holding {} during {}", job, reason));
+    let started = Instant::now();
+    while busy(app).is_some() && started.elapsed() < max_wait {
+        tokio::time::sleep(POLL_INTERVAL).await;
+    }
+}
+
+/// `wait_for_quiet` for jobs on their own thread.
+pub fn wait_for_quiet_blocking(app: &tauri::AppHandle, job: &str, max_wait: Duration) {
+    tauri::async_runtime::block_on(wait_for_quiet(app, job, max_wait));
+}
+
+#[tauri::command]
+#[specta::specta]
+pub fn get_background_activity(app: tauri::AppHandle) -> BackgroundActivity {
+    let state = app.state::<BackgroundState>();
+    let bursts = state.bursts.lock().unwrap();
+    BackgroundActivity {
+        paused: bursts.busy(Instant::now()).is_some(),
+        agent_runs: bursts.agent_runs,
+        transcriptions: bursts.transcriptions,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{BurstKind, Bursts, RESUME_AFTER};
+    use std::time::{Duration, Instant};
+
+    #[test]
+    fn pauses_during_bursts_and_briefly_after() {
+        let start = Instant::now();
+        let mut bursts = Bursts::default();
+        assert_eq!(bursts.busy(start), None);
+
+        bursts.begin(BurstKind::Transcription);
+        bursts.begin(BurstKind::AgentRun);
+        assert_eq!(bursts.busy(start), Some("an agent run"));
+        bursts.end(BurstKind::AgentRun, start);
+        assert_eq!(bursts.busy(start), Some("transcription"));
+
+        let ended = start + Duration::from_secs(1);
+        bursts.end(BurstKind::Transcription, ended);
+        assert!(bursts.busy(ended + Duration::from_secs(1)).is_some());
+        assert_eq!(bursts.busy(ended + RESUME_AFTER), None);
+
+        // An unmatched end doesn't underflow.
+        bursts.end(BurstKind::AgentRun, ended);
+        assert_eq!(bursts.agent_runs, 0);
+    }
+}
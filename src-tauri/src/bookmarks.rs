@@ -0,0 +1,164 @@
+use std::{fs, path::PathBuf, time::Duration};
+
+use serde::{Deserialize, Serialize};
+use tauri::Manager;
+use tauri_specta::Event;
+
+use crate::sessions::{self, SessionState};
+use crate::{excalidraw_canvas_url, log_line, now_millis, trace};
+
+const CANVAS_SNAPSHOT_TIMEOUT: Duration = Duration::from_secs(3);
+
+#[derive(Serialize, Deserialize, Clone, Copy, Debug, specta::Type)]
+#[serde(rename_all = "kebab-case")]
+pub enum BookmarkSource {
+    Manual,
+    Voice,
+    Webhook,
+    Script,
+}
+
+/// A saved moment in a session. Offsets and the excerpt point into the
+/// session transcript; `canvas_snapshot` is relative to the session dir.
+#[derive(Serialize, Deserialize, Clone, specta::Type)]
+#[serde(rename_all = "camelCase")]
+pub struct Bookmark {
+    pub id: String,
+    pub session_id: String,
+    pub label: Option<String>,
+    pub created_at: u64,
+    pub offset_ms: u64,
+    pub segment_index: Option<u32>,
+    pub transcript_excerpt: Option<String>,
+    pub browser_url: Option<String>,
+    pub canvas_snapshot: Option<String>,
+    pub source: BookmarkSource,
+}
+
+/// Emitted when a bookmark is saved, including from a voice trigger.
+#[derive(Serialize, Deserialize, Clone, specta::Type, tauri_specta::Event)]
+pub struct BookmarkCreated(Bookmark);
+
+/// Emitted for "Jamie, jump back"; the frontend restores the referenced
+/// browser page and scrolls the transcript.
+#[derive(Serialize, Deserialize, Clone, specta::Type, tauri_specta::Event)]
+pub struct BookmarkJump(Bookmark);
+
+fn bookmarks_path(app: &tauri::AppHandle, session_id: &str) -> Result<PathBuf, String> {
+    Ok(sessions::session_dir(app, session_id)?.join("bookmarks.json"))
+}
+
+pub fn read_bookmarks(app: &tauri::AppHandle, session_id: &str) -> Result<Vec<Bookmark>, String> {
+    let path = bookmarks_path(app, session_id)?;
+    Ok(fs::read_to_string(path)
+        .ok()
+        .and_then(|content| serde_json::from_str(&content).ok())
+        .unwrap_or_default())
+}
+
+fn write_bookmarks(app: &tauri::AppHandle, session_id: &str, bookmarks: &[Bookmark]) -> Result<(), String> {
+    let content = serde_json::to_string_pretty(bookmarks)
+        .map_err(|err| format!("failed to format bookmarks: {}", err))?;
+    fs::write(bookmarks_path(app, session_id)?, content.as_bytes())
+        .map_err(|err| format!("failed to write bookmarks: {}", err))
+}
+
+/// Save the current canvas elements next to the session so the bookmark
+/// still shows what was on the board after it has been cleared.
+async fn snapshot_canvas(app: &tauri::AppHandle, session_id: &str, bookmark_id: &str) -> Option<String> {
+    let url = format!("{}/api/elements", excalidraw_canvas_url(app));
+    let client = reqwest::Client::builder()
+        .timeout(CANVAS_SNAPSHOT_TIMEOUT)
+        .build()
+        .ok()?;
+    let body = match client.get(&url).send().await {
+        Ok(response) if response.status().is_success() => response.text().await.ok()?,
+        Ok(response) => {
+            log_line(&format!("[bookmarks] canvas snapshot HTTP {}", response.status()));
+            return None;
+        }
+        Err(err) => {
+            log_line(&format!("[bookmarks] canvas unreachable: {}", err));
+            return None;
+        }
+    };
+    let relative = format!("canvas/{}.json", bookmark_id);
+    let path = sessions::session_dir(app, session_id).ok()?.join(&relative);
+    fs::create_dir_all(path.parent()?).ok()?;
+    fs::write(&path, body.as_bytes()).ok()?;
+    Some(relative)
+}
+
+pub async fn create_bookmark(
+    app: &tauri::AppHandle,
+    label: Option<String>,
+    source: BookmarkSource,
+) -> Result<Bookmark, String> {
+    let moment = app
+        .state::<SessionState>()
+        .current_moment()
+        .ok_or_else(|| "no active session to bookmark".to_string())?;
+    let created_at = now_millis();
+    let id = format!("bm-{}", trace::new_id());
+    let canvas_snapshot = snapshot_canvas(app, &moment.session_id, &id).await;
+    let bookmark = Bookmark {
+        id,
+        session_id: moment.session_id.clone(),
+        label: label.filter(|l| !l.trim().is_empty()),
+        created_at,
+        offset_ms: moment.offset_ms,
+        segment_index: moment.last_segment.as_ref().map(|s| s.index),
+        transcript_excerpt: moment.last_segment.map(|s| s.text),
+        browser_url: moment.browser_url,
+        canvas_snapshot,
+        source,
+    };
+
+    let mut bookmarks = read_bookmarks(app, &moment.session_id)?;
+    bookmarks.push(bookmark.clone());
+    write_bookmarks(app, &moment.session_id, &bookmarks)?;
+    log_line(&format!(
+        "[bookmarks] {} saved in {} at {}ms",
+        bookmark.id, bookmark.session_id, bookmark.offset_ms
+    ));
+    let _ = BookmarkCreated(bookmark.clone()).emit(app);
+    Ok(bookmark)
+}
+
+/// Most recent bookmark in the active session whose label contains `label`
+/// (or simply the most recent one), announced via `bookmark-jump`.
+pub fn jump_back(app: &tauri::AppHandle, label: Option<&str>) -> Result<(), String> {
+    let session_id = app
+        .state::<SessionState>()
+        .active_id()
+        .ok_or_else(|| "no active session".to_string())?;
+    let bookmarks = read_bookmarks(app, &session_id)?;
+    let wanted = label.map(str::to_lowercase);
+    let target = bookmarks
+        .iter()
+        .rev()
+        .find(|bookmark| match (&wanted, &bookmark.label) {
+            (None, _) => true,
+            (Some(wanted), Some(label)) => label.to_lowercase().contains(wanted.as_str()),
+            (Some(_), None) => false,
+        })
+        .ok_or_else(|| match label {
+            Some(label) => format!("no bookmark matching \"{}\"", label),
+            None => "no bookmarks in this session yet".to_string(),
+        })?;
+    BookmarkJump(target.clone())
+        .emit(app)
+        .map_err(|err| err.to_string())
+}
+
+#[tauri::command]
+#[specta::specta]
+pub async fn bookmark_moment(app: tauri::AppHandle, label: Option<String>) -> Result<Bookmark, String> {
+    create_bookmark(&app, label, BookmarkSource::Manual).await
+}
+
+#[tauri::command]
+#[specta::specta]
+pub fn get_bookmarks(app: tauri::AppHandle, session_id: String) -> Result<Vec<Bookmark>, String> {
+    read_bookmarks(&app, &session_id)
+}
@@ -0,0 +1,257 @@
+use std::net::{SocketAddr, TcpStream};
+use std::path::PathBuf;
+use std::time::{Duration, SystemTime};
+
+use serde::{Deserialize, Serialize};
+use tauri_specta::Event;
+
+use crate::log_line;
+
+/// Port and profile `scripts/llm-agent.mjs` launches automation Chrome with.
+const DEFAULT_DEBUG_PORT: u16 = 9224;
+/// Lockfile the agent holds while launching Chrome; older ones are stale.
+const LAUNCH_LOCKFILE: &str = "heyjamie-chrome-launch.lock";
+const LAUNCH_LOCK_MAX_AGE: Duration = Duration::from_secs(15);
+const PROBE_TIMEOUT: Duration = Duration::from_millis(500);
+
+/// A Chrome browser process left over from automation.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, specta::Type)]
+#[serde(rename_all = "camelCase")]
+pub struct BrowserSession {
+    pub pid: u32,
+    pub debug_port: Option<u16>,
+    pub user_data_dir: Option<String>,
+    /// Listening on the port the agent connects to, so the next run can
+    /// reuse it instead of launching another.
+    pub reattachable: bool,
+}
+
+#[derive(Serialize, Deserialize, Clone, Copy, Debug, PartialEq, Eq, specta::Type)]
+#[serde(rename_all = "camelCase")]
+pub enum BrowserCleanupAction {
+    /// Only list what was found.
+    Report,
+    /// Close every leftover automation browser.
+    Close,
+    /// Keep the one the agent can reuse and close the rest.
+    Reattach,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, specta::Type)]
+#[serde(rename_all = "camelCase")]
+pub struct BrowserCleanupReport {
+    pub sessions: Vec<BrowserSession>,
+    pub closed: Vec<u32>,
+    /// The session kept for the agent to reuse.
+    pub reattached: Option<u32>,
+}
+
+/// Emitted to the main window at startup when leftover automation
+/// browsers are found.
+#[derive(Serialize, Deserialize, Clone, Debug, specta::Type, tauri_specta::Event)]
+#[serde(rename_all = "camelCase")]
+pub struct StaleBrowserSessions {
+    pub sessions: Vec<BrowserSession>,
+}
+
+fn debug_port() -> u16 {
+    std::env::var("HEYJAMIE_CHROME_DEBUG_PORT")
+        .ok()
+        .and_then(|raw| raw.trim().parse::<u16>().ok())
+        .filter(|port| *port >= 1024)
+        .unwrap_or(DEFAULT_DEBUG_PORT)
+}
+
+fn automation_profile() -> Option<String> {
+    dirs::home_dir().map(|home| {
+        home.join(".cache")
+            .join("chrome-devtools-mcp")
+            .join("chrome-profile")
+            .display()
+            .to_string()
+    })
+}
+
+/// Value of `--name=value` in a command line; values may contain spaces
+/// (profile paths), so they run up to the next ` --`.
+fn flag_value<'a>(command: &'a str, name: &str) -> Option<&'a str> {
+    let start = command.find(&format!("{}=", name))? + name.len() + 1;
+    let rest = &command[start..];
+    let end = rest.find(" --").unwrap_or(rest.len());
+    Some(rest[..end].trim().trim_matches('"'))
+}
+
+/// Browser processes among `(pid, command line)` pairs that use the
+/// automation profile or debug port. Renderer and helper processes
+/// (`--type=`) go with their browser and are skipped.
+fn find_sessions(processes: &[(u32, String)], profile: Option<&str>, port: u16) -> Vec<(u32, Option<u16>, Option<String>)> {
+    processes
+        .iter()
+        .filter(|(_, command)| !command.contains("--type="))
+        .filter_map(|(pid, command)| {
+            let user_data_dir = flag_value(command, "--user-data-dir").map(str::to_string);
+            let debug_port = flag_value(command, "--remote-debugging-port").and_then(|p| p.parse::<u16>().ok());
+            let ours = profile.is_some_and(|profile| user_data_dir.as_deref() == Some(profile)) || debug_port == Some(port);
+            ours.then_some((*pid, debug_port, user_data_dir))
+        })
+        .collect()
+}
+
+/// `pid command...` lines.
+fn parse_process_list(output: &str) -> Vec<(u32, String)> {
+    output
+        .lines()
+        .filter_map(|line| {
+            let (pid, command) = line.trim().split_once(char::is_whitespace)?;
+            Some((pid.parse().ok()?, command.trim().to_string()))
+        })
+        .collect()
+}
+
+#[cfg(unix)]
+fn process_list() -> Vec<(u32, String)> {
+    match std::process::Command::new("ps").args(["-axo", "pid=,command="]).output() {
+        Ok(output) => parse_process_list(&String::from_utf8_lossy(&output.stdout)),
+        Err(err) => {
+            log_line(&format!("[browser-sessions] ps failed: {}", err));
+            Vec::new()
+        }
+    }
+}
+
+#[cfg(windows)]
+fn process_list() -> Vec<(u32, String)> {
+    let script = "Get-CimInstance Win32_Process -Filter \"Name like 'chrome%'\" | ForEach-Object { \"$($_.ProcessId) $($_.CommandLine)\" }";
+    match std::process::Command::new("powershell").args(["-NoProfile", "-Command", script]).output() {
+        Ok(output) => parse_process_list(&String::from_utf8_lossy(&output.stdout)),
+        Err(err) => {
+            log_line(&format!("[browser-sessions] process query failed: {}", err));
+            Vec::new()
+        }
+    }
+}
+
+#[cfg(unix)]
+fn close(pid: u32) -> bool {
+    unsafe { libc::kill(pid as i32, libc::SIGTERM) == 0 }
+}
+
+#[cfg(windows)]
+fn close(pid: u32) -> bool {
+    std::process::Command::new("taskkill")
+        .args(["/PID", &pid.to_string(), "/T", "/F"])
+        .status()
+        .is_ok_and(|status| status.success())
+}
+
+fn listening(port: u16) -> bool {
+    TcpStream::connect_timeout(&SocketAddr::from(([127, 0, 0, 1], port)), PROBE_TIMEOUT).is_ok()
+}
+
+fn scan() -> Vec<BrowserSession> {
+    let port = debug_port();
+    let reachable = listening(port);
+    find_sessions(&process_list(), automation_profile().as_deref(), port)
+        .into_iter()
+        .map(|(pid, debug_port, user_data_dir)| BrowserSession {
+            pid,
+            reattachable: reachable && debug_port == Some(port),
+            debug_port,
+            user_data_dir,
+        })
+        .collect()
+}
+
+/// A launch lockfile left by a crashed agent makes the next one wait it out.
+fn remove_stale_lockfile() {
+    let path: PathBuf = std::env::temp_dir().join(LAUNCH_LOCKFILE);
+    let stale = std::fs::metadata(&path)
+        .and_then(|meta| meta.modified())
+        .ok()
+        .and_then(|modified| SystemTime::now().duration_since(modified).ok())
+        .is_some_and(|age| age > LAUNCH_LOCK_MAX_AGE);
+    if stale && std::fs::remove_file(&path).is_ok() {
+        log_line("[browser-sessions] removed stale Chrome launch lockfile");
+    }
+}
+
+fn cleanup(action: BrowserCleanupAction) -> BrowserCleanupReport {
+    remove_stale_lockfile();
+    let sessions = scan();
+    let reattached = match action {
+        BrowserCleanupAction::Reattach => sessions.iter().find(|session| session.reattachable).map(|session| session.pid),
+        _ => None,
+    };
+    let closed = match action {
+        BrowserCleanupAction::Report => Vec::new(),
+        _ => sessions
+            .iter()
+            .map(|session| session.pid)
+            .filter(|pid| Some(*pid) != reattached)
+            .filter(|pid| close(*pid))
+            .collect(),
+    };
+    if !sessions.is_empty() {
+        log_line(&format!(
+            "[browser-sessions] {} found, closed {:?}, kept {:?}",
+            sessions.len(),
+            closed,
+            reattached
+        ));
+    }
+    BrowserCleanupReport {
+        sessions,
+        closed,
+        reattached,
+    }
+}
+
+/// Look for automation browsers left by a crash and tell the main window.
+pub fn spawn_startup_check(app: tauri::AppHandle) {
+    std::thread::spawn(move || {
+        let report = cleanup(BrowserCleanupAction::Report);
+        if report.sessions.is_empty() {
+            return;
+        }
+        let event = StaleBrowserSessions {
+            sessions: report.sessions,
+        };
+        if let Err(err) = event.emit_to(&app, "main") {
+            log_line(&format!("[browser-sessions] failed to notify main window: {}", err));
+        }
+    });
+}
+
+/// Find Chrome instances launched for automation (by the agent's profile
+/// folder or debug port) and report them, close them, or keep the one the
+/// agent can reattach to and close the rest. Defaults to reporting.
+#[tauri::command]
+#[specta::specta]
+pub async fn cleanup_browser_sessions(action: Option<BrowserCleanupAction>) -> Result<BrowserCleanupReport, String> {
+    let action = action.unwrap_or(BrowserCleanupAction::Report);
+    tauri::async_runtime::spawn_blocking(move || cleanup(action))
+        .await
+        .map_err(|err| format!("browser cleanup task failed: {}", err))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{find_sessions, parse_process_list};
+
+    #[test]
+    fn finds_automation_browsers_by_profile_or_port() {
+        let ps = "\
+  101 /Applications/Google Chrome.app/Contents/MacOS/Google Chrome --remote-debugging-port=9224 --user-data-dir=/Users/me/.cache/chrome-devtools-mcp/chrome-profile --no-first-run about:blank
+  102 /Applications/Google Chrome.app/Contents/Frameworks/Helper --type=renderer --user-data-dir=/Users/me/.cache/chrome-devtools-mcp/chrome-profile
+  103 /Applications/Google Chrome.app/Contents/MacOS/Google Chrome
+  104 /usr/bin/chromium --user-data-dir=/Users/me/.cache/chrome-devtools-mcp/chrome-profile --remote-debugging-port=9300
+  105 /usr/bin/chromium --remote-debugging-port=9224
+";
+        let processes = parse_process_list(ps);
+        assert_eq!(processes.len(), 5);
+        let found = find_sessions(&processes, Some("/Users/me/.cache/chrome-devtools-mcp/chrome-profile"), 9224);
+        assert_eq!(found.iter().map(|(pid, _, _)| *pid).collect::<Vec<_>>(), vec![101, 104, 105]);
+        assert_eq!(found[1].1, Some(9300));
+        assert_eq!(found[2].2, None);
+    }
+}
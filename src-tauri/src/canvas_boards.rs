@@ -0,0 +1,314 @@
+//! Named Excalidraw boards. The canvas server holds a single scene, so the
+//! boards that aren't showing are kept as JSON next to the session (or in
+//! `canvas-boards` outside one). Switching saves the live scene to the
+//! board being left, clears the server and loads the other board into it.
+
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Mutex;
+
+use serde::{Deserialize, Serialize};
+use serde_json::Value as JsonValue;
+use tauri::Manager;
+use tauri_specta::Event;
+
+use crate::sessions::{self, SessionState};
+use crate::{excalidraw_canvas_url, log_line, now_millis};
+
+/// The board every session starts on; it exists even before it's saved.
+const DEFAULT_BOARD: &str = "main";
+const MAX_NAME_CHARS: usize = 40;
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, specta::Type)]
+#[serde(rename_all = "camelCase")]
+pub struct CanvasBoard {
+    pub name: String,
+    /// As of the last switch away from it; the active board is live.
+    pub element_count: u32,
+    pub updated_at: Option<u64>,
+}
+
+/// The boards of the current session. Emitted as `canvas-boards` after a
+/// switch so the canvas window can follow `url`.
+#[derive(Serialize, Deserialize, Clone, Debug, specta::Type, tauri_specta::Event)]
+#[serde(rename_all = "camelCase")]
+pub struct CanvasBoards {
+    pub active: String,
+    /// Canvas URL for the active board.
+    pub url: String,
+    pub boards: Vec<CanvasBoard>,
+}
+
+#[derive(Serialize, Deserialize, Default)]
+#[serde(rename_all = "camelCase", default)]
+struct StoredBoard {
+    name: String,
+    updated_at: u64,
+    elements: Vec<JsonValue>,
+}
+
+/// The board on the server and the folder it's saved to, which changes
+/// with the session.
+#[derive(Default)]
+pub struct CanvasBoardsState {
+    active: Mutex<Option<(PathBuf, String)>>,
+    switching: AtomicBool,
+}
+
+/// File-safe key for a board name; names that only differ in case or
+/// punctuation are the same board.
+pub(crate) fn slug(name: &str) -> Option<String> {
+    let name = name.trim();
+    if name.chars().count() > MAX_NAME_CHARS {
+        return None;
+    }
+    let slug = name
+        .to_lowercase()
+        .split(|c: char| !c.is_alphanumeric())
+        .filter(|part| !part.is_empty())
+        .collect::<Vec<_>>()
+        .join("-");
+    (!slug.is_empty()).then_some(slug)
+}
+
+fn board_path(dir: &Path, name: &str) -> Result<PathBuf, String> {
+    let slug = slug(name).ok_or_else(|| format!("invalid board name: {:?}", name))?;
+    Ok(dir.join(format!("{}.json", slug)))
+}
+
+fn boards_dir(app: &tauri::AppHandle) -> Result<PathBuf, String> {
+    let dir = match app.state::<SessionState>().active_id() {
+        Some(session_id) => sessions::session_dir(app, &session_id)?.join("boards"),
+        None => {
+            let sessions_dir = sessions::sessions_dir(app)?;
+            sessions_dir.parent().unwrap_or(&sessions_dir).join("canvas-boards")
+        }
+    };
+    fs::create_dir_all(&dir).map_err(|err| format!("failed to create boards dir: {}", err))?;
+    Ok(dir)
+}
+
+fn read_board(path: &Path) -> Option<StoredBoard> {
+    fs::read_to_string(path)
+        .ok()
+        .and_then(|content| serde_json::from_str(&content).ok())
+}
+
+fn write_board(path: &Path, board: &StoredBoard) -> Result<(), String> {
+    let content = serde_json::to_string_pretty(board).map_err(|err| format!("failed to format board: {}", err))?;
+    fs::write(path, content.as_bytes()).map_err(|err| format!("failed to write board: {}", err))
+}
+
+/// The canvas server answers `/api/elements` with a bare array or with
+/// `{ elements: [...] }` depending on its version.
+fn parse_elements(body: JsonValue) -> Vec<JsonValue> {
+    match body {
+        JsonValue::Array(elements) => elements,
+        JsonValue::Object(mut map) => match map.remove("elements") {
+            Some(JsonValue::Array(elements)) => elements,
+            _ => Vec::new(),
+        },
+        _ => Vec::new(),
+    }
+}
+
+fn board_url(base: &str, name: &str) -> String {
+    format!("{}/?board={}", base, slug(name).unwrap_or_else(|| DEFAULT_BOARD.to_string()))
+}
+
+/// Active board for `dir`; a new session starts on the default board.
+fn active_in(state: &CanvasBoardsState, dir: &Path) -> String {
+    match state.active.lock().unwrap().as_ref() {
+        Some((active_dir, name)) if active_dir == dir => name.clone(),
+        _ => DEFAULT_BOARD.to_string(),
+    }
+}
+
+/// Name of the board on the canvas now.
+pub fn active_board(app: &tauri::AppHandle) -> String {
+    match boards_dir(app) {
+        Ok(dir) => active_in(&app.state::<CanvasBoardsState>(), &dir),
+        Err(_) => DEFAULT_BOARD.to_string(),
+    }
+}
+
+fn list(app: &tauri::AppHandle, dir: &Path) -> CanvasBoards {
+    let active = active_in(&app.state::<CanvasBoardsState>(), dir);
+    let mut boards: Vec<CanvasBoard> = fs::read_dir(dir)
+        .map(|entries| {
+            entries
+                .flatten()
+                .filter_map(|entry| read_board(&entry.path()))
+                .map(|board| CanvasBoard {
+                    element_count: board.elements.len() as u32,
+                    updated_at: Some(board.updated_at),
+                    name: board.name,
+                })
+                .collect()
+        })
+        .unwrap_or_default();
+    for name in [DEFAULT_BOARD, active.as_str()] {
+        if !boards.iter().any(|board| slug(&board.name) == slug(name)) {
+            boards.push(CanvasBoard {
+                name: name.to_string(),
+                element_count: 0,
+                updated_at: None,
+            });
+        }
+    }
+    boards.sort_by_key(|board| board.name.to_lowercase());
+    CanvasBoards {
+        url: board_url(&excalidraw_canvas_url(app), &active),
+        active,
+        boards,
+    }
+}
+
+pub(crate) async fn fetch_scene(client: &reqwest::Client, base: &str) -> Result<Vec<JsonValue>, String> {
+    let response = client
+        .get(format!("{}/api/elements", base))
+        .send()
+        .await
+        .map_err(|err| format!("canvas server unreachable: {}", err))?;
+    let body = response
+        .bytes()
+        .await
+        .map_err(|err| format!("failed to read canvas elements: {}", err))?;
+    let body: JsonValue =
+        serde_json::from_slice(&body).map_err(|err| format!("invalid canvas elements: {}", err))?;
+    Ok(parse_elements(body))
+}
+
+async fn replace_scene(client: &reqwest::Client, base: &str, elements: Vec<JsonValue>) -> Result<(), String> {
+    let response = client
+        .delete(format!("{}/api/elements/clear", base))
+        .send()
+        .await
+        .map_err(|err| format!("failed to clear canvas: {}", err))?;
+    if !response.status().is_success() {
+        return Err(format!("failed to clear canvas: HTTP {}", response.status()));
+    }
+    if elements.is_empty() {
+        return Ok(());
+    }
+    let body = serde_json::to_vec(&serde_json::json!({ "elements": elements }))
+        .map_err(|err| format!("failed to format board: {}", err))?;
+    let response = client
+        .post(format!("{}/api/elements/batch", base))
+        .header("Content-Type", "application/json")
+        .body(body)
+        .send()
+        .await
+        .map_err(|err| format!("failed to load board: {}", err))?;
+    if !response.status().is_success() {
+        return Err(format!("failed to load board: HTTP {}", response.status()));
+    }
+    Ok(())
+}
+
+/// Save the live scene to the board being left, then show `name`.
+async fn show(app: &tauri::AppHandle, dir: &Path, name: &str, create: bool) -> Result<CanvasBoards, String> {
+    let state = app.state::<CanvasBoardsState>();
+    let target_path = board_path(dir, name)?;
+    let previous = state.active.lock().unwrap().clone();
+    let current = active_in(&state, dir);
+    let exists = target_path.exists() || slug(name) == slug(DEFAULT_BOARD) || slug(name) == slug(&current);
+    if create && exists {
+        return Err(format!("a board named {:?} already exists", name));
+    }
+    if !create && !exists {
+        return Err(format!("no board named {:?}", name));
+    }
+    if !create && slug(name) == slug(&current) {
+        return Ok(list(app, dir));
+    }
+
+    let base = excalidraw_canvas_url(app);
+    let client = crate::auth::http_client()?;
+    let scene = fetch_scene(&client, &base).await?;
+    let (left_dir, left_name) = previous.unwrap_or_else(|| (dir.to_path_buf(), DEFAULT_BOARD.to_string()));
+    write_board(
+        &board_path(&left_dir, &left_name)?,
+        &StoredBoard {
+            name: left_name.clone(),
+            updated_at: now_millis(),
+            elements: scene,
+        },
+    )?;
+
+    let target = read_board(&target_path).unwrap_or_else(|| StoredBoard {
+        name: name.trim().to_string(),
+        updated_at: now_millis(),
+        elements: Vec::new(),
+    });
+    if create {
+        write_board(&target_path, &target)?;
+    }
+    replace_scene(&client, &base, target.elements.clone()).await?;
+    *state.active.lock().unwrap() = Some((dir.to_path_buf(), target.name.clone()));
+    log_line(&format!(
+        "[canvas] switched from board {:?} to {:?} ({} elements)",
+        left_name,
+        target.name,
+        target.elements.len()
+    ));
+
+    let boards = list(app, dir);
+    let _ = boards.clone().emit(app);
+    Ok(boards)
+}
+
+async fn show_exclusive(app: &tauri::AppHandle, name: &str, create: bool) -> Result<CanvasBoards, String> {
+    let state = app.state::<CanvasBoardsState>();
+    if state.switching.swap(true, Ordering::SeqCst) {
+        return Err("another board switch is in progress".to_string());
+    }
+    let result = match boards_dir(app) {
+        Ok(dir) => show(app, &dir, name, create).await,
+        Err(err) => Err(err),
+    };
+    state.switching.store(false, Ordering::SeqCst);
+    result
+}
+
+/// Boards of the current session, with the one on the canvas.
+#[tauri::command]
+#[specta::specta]
+pub fn list_boards(app: tauri::AppHandle) -> Result<CanvasBoards, String> {
+    Ok(list(&app, &boards_dir(&app)?))
+}
+
+/// Start an empty board and show it; the current scene is kept on the
+/// board it was drawn on.
+#[tauri::command]
+#[specta::specta]
+pub async fn create_board(app: tauri::AppHandle, name: String) -> Result<CanvasBoards, String> {
+    show_exclusive(&app, &name, true).await
+}
+
+/// Show another board of the current session.
+#[tauri::command]
+#[specta::specta]
+pub async fn switch_board(app: tauri::AppHandle, name: String) -> Result<CanvasBoards, String> {
+    show_exclusive(&app, &name, false).await
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{board_url, parse_elements, slug};
+    use serde_json::json;
+
+    #[test]
+    fn slugs_names_and_reads_either_element_shape() {
+        assert_eq!(slug("  Episode Outline! ").as_deref(), Some("episode-outline"));
+        assert_eq!(slug("Research"), slug("research"));
+        assert_eq!(slug("../.."), None);
+        assert_eq!(slug(&"x".repeat(41)), None);
+        assert_eq!(board_url("http://localhost:3000", "Episode Outline"), "http://localhost:3000/?board=episode-outline");
+
+        assert_eq!(parse_elements(json!([{ "id": "a" }])).len(), 1);
+        assert_eq!(parse_elements(json!({ "elements": [{ "id": "a" }, { "id": "b" }] })).len(), 2);
+        assert!(parse_elements(json!({ "error": "nope" })).is_empty());
+    }
+}
@@ -0,0 +1,213 @@
+use std::{collections::BTreeSet, fs, path::PathBuf};
+
+use serde::{Deserialize, Serialize};
+use serde_json::Value as JsonValue;
+use tauri::Manager;
+
+use crate::sessions::{self, SessionMoment, SessionState};
+use crate::{log_line, now_millis};
+
+/// Excalidraw MCP tools whose input or output names the elements they
+/// created or changed.
+const ELEMENT_TOOLS: &[&str] = &[
+    "create_element",
+    "batch_create_elements",
+    "update_element",
+    "create_from_mermaid",
+    "group_elements",
+    "align_elements",
+];
+
+#[derive(Serialize, Deserialize, Clone, Copy, Debug, specta::Type)]
+#[serde(rename_all = "kebab-case")]
+pub enum CanvasLinkSource {
+    Agent,
+    Direct,
+}
+
+/// Ties a canvas element to the transcript segment that prompted it.
+#[derive(Serialize, Deserialize, Clone, specta::Type)]
+#[serde(rename_all = "camelCase")]
+pub struct CanvasLink {
+    pub element_id: String,
+    pub session_id: String,
+    pub segment_index: Option<u32>,
+    pub offset_ms: u64,
+    pub transcript_excerpt: Option<String>,
+    pub source: CanvasLinkSource,
+    pub created_at: u64,
+}
+
+fn links_path(app: &tauri::AppHandle, session_id: &str) -> Result<PathBuf, String> {
+    Ok(sessions::session_dir(app, session_id)?.join("canvas-links.json"))
+}
+
+fn read_links(app: &tauri::AppHandle, session_id: &str) -> Vec<CanvasLink> {
+    links_path(app, session_id)
+        .ok()
+        .and_then(|path| fs::read_to_string(path).ok())
+        .and_then(|content| serde_json::from_str(&content).ok())
+        .unwrap_or_default()
+}
+
+/// Store links for `element_ids`, replacing earlier links for the same ids
+/// so an element updated later points at the most recent request.
+pub fn record_links(
+    app: &tauri::AppHandle,
+    moment: &SessionMoment,
+    element_ids: &[String],
+    source: CanvasLinkSource,
+) -> Result<(), String> {
+    if element_ids.is_empty() {
+        return Ok(());
+    }
+    let mut links = read_links(app, &moment.session_id);
+    links.retain(|link| !element_ids.contains(&link.element_id));
+    let created_at = now_millis();
+    for element_id in element_ids {
+        links.push(CanvasLink {
+            element_id: element_id.clone(),
+            session_id: moment.session_id.clone(),
+            segment_index: moment.last_segment.as_ref().map(|s| s.index),
+            offset_ms: moment.offset_ms,
+            transcript_excerpt: moment.last_segment.as_ref().map(|s| s.text.clone()),
+            source,
+            created_at,
+        });
+    }
+    let content = serde_json::to_string_pretty(&links)
+        .map_err(|err| format!("failed to format canvas links: {}", err))?;
+    fs::write(links_path(app, &moment.session_id)?, content.as_bytes())
+        .map_err(|err| format!("failed to write canvas links: {}", err))?;
+    log_line(&format!(
+        "[canvas] linked {} element(s) to segment {:?} of {}",
+        element_ids.len(),
+        moment.last_segment.as_ref().map(|s| s.index),
+        moment.session_id
+    ));
+    Ok(())
+}
+
+/// Collect ids of element-like objects (anything with `id` and `type`),
+/// looking inside MCP text content that itself holds JSON.
+fn collect_element_ids(value: &JsonValue, ids: &mut BTreeSet<String>) {
+    match value {
+        JsonValue::Object(map) => {
+            if let (Some(JsonValue::String(id)), Some(JsonValue::String(_))) = (map.get("id"), map.get("type")) {
+                if !id.trim().is_empty() {
+                    ids.insert(id.trim().to_string());
+                }
+            }
+            for (key, nested) in map {
+                if key == "elementIds" || key == "element_ids" {
+                    if let Some(list) = nested.as_array() {
+                        ids.extend(list.iter().filter_map(|v| v.as_str()).map(str::to_string));
+                    }
+                }
+                collect_element_ids(nested, ids);
+            }
+        }
+        JsonValue::Array(items) => items.iter().for_each(|item| collect_element_ids(item, ids)),
+        JsonValue::String(text) => {
+            let trimmed = text.trim();
+            if trimmed.starts_with('{') || trimmed.starts_with('[') {
+                if let Ok(parsed) = serde_json::from_str::<JsonValue>(trimmed) {
+                    collect_element_ids(&parsed, ids);
+                }
+            }
+        }
+        _ => {}
+    }
+}
+
+/// Element ids touched by the Excalidraw tool calls in an agent result.
+fn element_ids_from_agent_output(output: &JsonValue) -> Vec<String> {
+    let mut ids = BTreeSet::new();
+    let calls = output
+        .get("toolCalls")
+        .and_then(|v| v.as_array())
+        .cloned()
+        .unwrap_or_default();
+    for call in calls {
+        let name = call.get("name").and_then(|v| v.as_str()).unwrap_or("");
+        if !ELEMENT_TOOLS.contains(&name) {
+            continue;
+        }
+        if let Some(input) = call.get("input") {
+            collect_element_ids(input, &mut ids);
+        }
+        if let Some(output) = call.get("output") {
+            collect_element_ids(output, &mut ids);
+        }
+    }
+    ids.into_iter().collect()
+}
+
+/// Link elements created during an `excalidraw-act` run to the moment the
+/// run started.
+pub fn record_from_agent_output(app: &tauri::AppHandle, moment: &SessionMoment, stdout: &str) {
+    let Ok(output) = serde_json::from_str::<JsonValue>(stdout) else {
+        return;
+    };
+    let ids = element_ids_from_agent_output(&output);
+    if let Err(err) = record_links(app, moment, &ids, CanvasLinkSource::Agent) {
+        log_line(&format!("[canvas] {}", err));
+    }
+}
+
+/// Register elements created outside the agent (e.g. direct canvas
+/// commands) against the current transcript position.
+#[tauri::command]
+#[specta::specta]
+pub fn link_canvas_elements(
+    app: tauri::AppHandle,
+    state: tauri::State<'_, SessionState>,
+    element_ids: Vec<String>,
+) -> Result<(), String> {
+    let moment = state
+        .current_moment()
+        .ok_or_else(|| "no active session".to_string())?;
+    record_links(&app, &moment, &element_ids, CanvasLinkSource::Direct)
+}
+
+/// The conversation moment that produced `element_id`, searching the active
+/// session first and then older sessions.
+#[tauri::command]
+#[specta::specta]
+pub fn get_canvas_provenance(app: tauri::AppHandle, element_id: String) -> Result<Option<CanvasLink>, String> {
+    let mut session_ids: Vec<String> = app.state::<SessionState>().active_id().into_iter().collect();
+    session_ids.extend(sessions::list_sessions(app.clone())?.into_iter().map(|s| s.id));
+    for session_id in session_ids {
+        if let Some(link) = read_links(&app, &session_id)
+            .into_iter()
+            .find(|link| link.element_id == element_id)
+        {
+            return Ok(Some(link));
+        }
+    }
+    Ok(None)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::element_ids_from_agent_output;
+    use serde_json::json;
+
+    #[test]
+    fn extracts_ids_from_tool_inputs_and_text_outputs() {
+        let output = json!({
+            "toolCalls": [
+                {
+                    "name": "batch_create_elements",
+                    "input": { "elements": [{ "id": "box-1", "type": "rectangle" }] },
+                    "output": { "content": [{ "type": "text", "text": "{\"elements\":[{\"id\":\"box-1\",\"type\":\"rectangle\"},{\"id\":\"arrow-2\",\"type\":\"arrow\"}]}" }] }
+                },
+                {
+                    "name": "get_resource",
+                    "output": { "id": "ignored", "type": "rectangle" }
+                }
+            ]
+        });
+        assert_eq!(element_ids_from_agent_output(&output), vec!["arrow-2", "box-1"]);
+    }
+}
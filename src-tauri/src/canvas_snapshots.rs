@@ -0,0 +1,199 @@
+//! A visual history of the whiteboard: when the conversation shifts topic,
+//! the board on the canvas is saved as an `.excalidraw` scene (and a PNG
+//! when the canvas server can render one), labeled with the topic it was
+//! drawn for.
+
+use std::fs;
+use std::path::PathBuf;
+
+use base64::{engine::general_purpose, Engine as _};
+use serde::{Deserialize, Serialize};
+use serde_json::Value as JsonValue;
+use sha2::{Digest, Sha256};
+use tauri_specta::Event;
+
+use crate::canvas_boards;
+use crate::sessions;
+use crate::topic_graph::TopicShift;
+use crate::{excalidraw_canvas_url, log_line, now_millis, truncate_for_log};
+
+/// One saved board, listed in `<session>/canvas-snapshots.json`.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, specta::Type)]
+#[serde(rename_all = "camelCase")]
+pub struct CanvasSnapshot {
+    /// The topic being left when the board was saved.
+    pub topic: String,
+    pub board: String,
+    pub offset_ms: u64,
+    pub taken_at: u64,
+    pub element_count: u32,
+    pub scene_path: String,
+    /// Missing when the canvas server couldn't render the board.
+    pub image_path: Option<String>,
+    /// Hash of the elements; an unchanged board isn't saved twice.
+    pub scene_hash: String,
+}
+
+fn snapshots_dir(app: &tauri::AppHandle, session_id: &str) -> Result<PathBuf, String> {
+    let dir = sessions::session_dir(app, session_id)?.join("canvas-snapshots");
+    fs::create_dir_all(&dir).map_err(|err| format!("failed to create snapshots dir: {}", err))?;
+    Ok(dir)
+}
+
+fn index_path(app: &tauri::AppHandle, session_id: &str) -> Result<PathBuf, String> {
+    Ok(sessions::session_dir(app, session_id)?.join("canvas-snapshots.json"))
+}
+
+fn read_index(app: &tauri::AppHandle, session_id: &str) -> Vec<CanvasSnapshot> {
+    index_path(app, session_id)
+        .ok()
+        .and_then(|path| fs::read_to_string(path).ok())
+        .and_then(|content| serde_json::from_str(&content).ok())
+        .unwrap_or_default()
+}
+
+fn scene_hash(elements: &[JsonValue]) -> String {
+    let bytes = serde_json::to_vec(elements).unwrap_or_default();
+    Sha256::digest(&bytes).iter().map(|byte| format!("{:02x}", byte)).collect()
+}
+
+/// The scene as a file Excalidraw can open.
+fn scene_file(elements: Vec<JsonValue>) -> JsonValue {
+    serde_json::json!({
+        "type": "excalidraw",
+        "version": 2,
+        "source": "heyjamie",
+        "elements": elements,
+        "appState": { "viewBackgroundColor": "#ffffff" },
+        "files": {},
+    })
+}
+
+/// PNG of the board, rendered by the canvas page through the server's
+/// export endpoint. Older canvas servers don't have one.
+async fn export_png(client: &reqwest::Client, base: &str) -> Result<Vec<u8>, String> {
+    let response = client
+        .post(format!("{}/api/export/image", base))
+        .header("Content-Type", "application/json")
+        .body(r#"{"format":"png","background":true}"#)
+        .send()
+        .await
+        .map_err(|err| format!("export request failed: {}", err))?;
+    let status = response.status();
+    let body = response
+        .bytes()
+        .await
+        .map_err(|err| format!("failed to read export: {}", err))?;
+    if !status.is_success() {
+        return Err(format!("HTTP {}: {}", status, truncate_for_log(&String::from_utf8_lossy(&body), 200)));
+    }
+    let body: JsonValue = serde_json::from_slice(&body).map_err(|err| format!("invalid export reply: {}", err))?;
+    let data = body
+        .get("data")
+        .and_then(|data| data.as_str())
+        .ok_or_else(|| "export reply has no image data".to_string())?;
+    let data = data.split_once("base64,").map(|(_, data)| data).unwrap_or(data);
+    general_purpose::STANDARD
+        .decode(data.trim())
+        .map_err(|err| format!("invalid image data: {}", err))
+}
+
+async fn snapshot(app: &tauri::AppHandle, shift: &TopicShift) -> Result<Option<CanvasSnapshot>, String> {
+    let base = excalidraw_canvas_url(app);
+    let client = crate::auth::http_client()?;
+    let elements = canvas_boards::fetch_scene(&client, &base).await?;
+    if elements.is_empty() {
+        return Ok(None);
+    }
+    let hash = scene_hash(&elements);
+    let mut index = read_index(app, &shift.session_id);
+    if index.last().is_some_and(|last| last.scene_hash == hash) {
+        return Ok(None);
+    }
+
+    let board = canvas_boards::active_board(app);
+    let dir = snapshots_dir(app, &shift.session_id)?;
+    let stem = format!(
+        "{:08}-{}",
+        shift.offset_ms / 1000,
+        canvas_boards::slug(&shift.from_topic).unwrap_or_else(|| "topic".to_string())
+    );
+    let element_count = elements.len() as u32;
+    let scene_path = dir.join(format!("{}.excalidraw", stem));
+    let scene = serde_json::to_string_pretty(&scene_file(elements))
+        .map_err(|err| format!("failed to format scene: {}", err))?;
+    fs::write(&scene_path, scene.as_bytes()).map_err(|err| format!("failed to write scene: {}", err))?;
+
+    let image_path = match export_png(&client, &base).await {
+        Ok(png) => {
+            let path = dir.join(format!("{}.png", stem));
+            fs::write(&path, png).map_err(|err| format!("failed to write image: {}", err))?;
+            Some(path.display().to_string())
+        }
+        Err(err) => {
+            log_line(&format!("[canvas] no image for snapshot {}: {}", stem, err));
+            None
+        }
+    };
+
+    let snapshot = CanvasSnapshot {
+        topic: shift.from_topic.clone(),
+        board,
+        offset_ms: shift.offset_ms,
+        taken_at: now_millis(),
+        element_count,
+        scene_path: scene_path.display().to_string(),
+        image_path,
+        scene_hash: hash,
+    };
+    index.push(snapshot.clone());
+    let content = serde_json::to_string_pretty(&index)
+        .map_err(|err| format!("failed to format snapshot index: {}", err))?;
+    fs::write(index_path(app, &shift.session_id)?, content.as_bytes())
+        .map_err(|err| format!("failed to write snapshot index: {}", err))?;
+    Ok(Some(snapshot))
+}
+
+/// Snapshot the board whenever the topic tracker reports a shift.
+pub fn listen(app: &tauri::AppHandle) {
+    let handle = app.clone();
+    TopicShift::listen_any(app, move |event| {
+        let app = handle.clone();
+        let shift = event.payload;
+        tauri::async_runtime::spawn(async move {
+            match snapshot(&app, &shift).await {
+                Ok(Some(snapshot)) => log_line(&format!(
+                    "[canvas] snapshot of {:?} for topic {:?} ({} elements)",
+                    snapshot.board, snapshot.topic, snapshot.element_count
+                )),
+                Ok(None) => {}
+                Err(err) => log_line(&format!("[canvas] snapshot skipped: {}", err)),
+            }
+        });
+    });
+}
+
+/// Board snapshots of a session, oldest first.
+#[tauri::command]
+#[specta::specta]
+pub fn list_canvas_snapshots(app: tauri::AppHandle, session_id: String) -> Result<Vec<CanvasSnapshot>, String> {
+    sessions::session_dir(&app, &session_id)?;
+    Ok(read_index(&app, &session_id))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{scene_file, scene_hash};
+    use serde_json::json;
+
+    #[test]
+    fn hashes_scenes_and_wraps_them_as_excalidraw_files() {
+        let a = vec![json!({ "id": "a", "type": "rectangle" })];
+        let b = vec![json!({ "id": "b", "type": "rectangle" })];
+        assert_eq!(scene_hash(&a), scene_hash(&a.clone()));
+        assert_ne!(scene_hash(&a), scene_hash(&b));
+        let file = scene_file(a);
+        assert_eq!(file["type"], "excalidraw");
+        assert_eq!(file["elements"][0]["id"], "a");
+    }
+}
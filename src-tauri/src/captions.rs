@@ -0,0 +1,178 @@
+use std::{
+    fs,
+    path::{Path, PathBuf},
+};
+
+use serde::{Deserialize, Serialize};
+
+use crate::log_line;
+use crate::sessions::TranscriptSegment;
+use crate::settings::{self, CaptionSettings};
+
+/// Cap on a cue's length when there is no earlier segment to start it from
+/// (first segment, or after a long pause).
+const MAX_CUE_MS: u64 = 15_000;
+
+#[derive(Serialize, Deserialize, Clone, Copy, Debug, Default, PartialEq, Eq, specta::Type)]
+#[serde(rename_all = "kebab-case")]
+pub enum CaptionFormat {
+    #[default]
+    Srt,
+    Vtt,
+}
+
+impl CaptionFormat {
+    fn extension(self) -> &'static str {
+        match self {
+            CaptionFormat::Srt => "srt",
+            CaptionFormat::Vtt => "vtt",
+        }
+    }
+}
+
+pub fn format_timestamp(ms: u64, format: CaptionFormat) -> String {
+    let separator = match format {
+        CaptionFormat::Srt => ',',
+        CaptionFormat::Vtt => '.',
+    };
+    format!(
+        "{:02}:{:02}:{:02}{}{:03}",
+        ms / 3_600_000,
+        (ms / 60_000) % 60,
+        (ms / 1000) % 60,
+        separator,
+        ms % 1000
+    )
+}
+
+pub fn render_cue(number: u32, start_ms: u64, end_ms: u64, text: &str, format: CaptionFormat) -> String {
+    format!(
+        "{}\n{} --> {}\n{}\n\n",
+        number,
+        format_timestamp(start_ms, format),
+        format_timestamp(end_ms.max(start_ms + 1), format),
+        text.trim()
+    )
+}
+
+/// Cue start for a segment recorded at `end_ms`: where the previous one
+/// ended, but no more than `MAX_CUE_MS` earlier.
+pub fn cue_start(previous_end_ms: Option<u64>, end_ms: u64) -> u64 {
+    previous_end_ms
+        .unwrap_or(0)
+        .max(end_ms.saturating_sub(MAX_CUE_MS))
+        .min(end_ms)
+}
+
+/// The configured path is either a caption file (`.srt`/`.vtt`, which also
+/// picks the format) or a folder that gets `<session id>.<ext>`.
+fn resolve_output(config: &CaptionSettings, session_id: &str) -> Option<(PathBuf, CaptionFormat)> {
+    let path = PathBuf::from(config.output_path.trim());
+    if path.as_os_str().is_empty() {
+        return None;
+    }
+    match path.extension().and_then(|ext| ext.to_str()).map(str::to_lowercase).as_deref() {
+        Some("srt") => Some((path, CaptionFormat::Srt)),
+        Some("vtt") => Some((path, CaptionFormat::Vtt)),
+        _ => Some((
+            path.join(format!("{}.{}", session_id, config.format.extension())),
+            config.format,
+        )),
+    }
+}
+
+/// Replace `path` with `content` via a temp file and rename, so an editor
+/// watching the file never reads a half-written cue.
+fn write_atomic(path: &Path, content: &str) -> Result<(), String> {
+    if let Some(dir) = path.parent() {
+        fs::create_dir_all(dir).map_err(|err| format!("failed to create {}: {}", dir.display(), err))?;
+    }
+    let file_name = path.file_name().and_then(|n| n.to_str()).unwrap_or("captions");
+    let tmp = path.with_file_name(format!(".{}.tmp", file_name));
+    fs::write(&tmp, content.as_bytes()).map_err(|err| format!("failed to write {}: {}", tmp.display(), err))?;
+    fs::rename(&tmp, path).map_err(|err| format!("failed to replace {}: {}", path.display(), err))
+}
+
+/// Add `segment` to the live caption file when live output is enabled. The
+/// first segment of a session starts the file over.
+pub fn append_live(app: &tauri::AppHandle, session_id: &str, segment: &TranscriptSegment, previous_end_ms: Option<u64>) {
+    let config = settings::load_settings(app).captions;
+    if !config.live_output {
+        return;
+    }
+    let Some((path, format)) = resolve_output(&config, session_id) else {
+        return;
+    };
+    let mut content = if segment.index == 0 {
+        String::new()
+    } else {
+        fs::read_to_string(&path).unwrap_or_default()
+    };
+    if format == CaptionFormat::Vtt && !content.starts_with("WEBVTT") {
+        content = format!("WEBVTT\n\n{}", content);
+    }
+    content.push_str(&render_cue(
+        segment.index + 1,
+        cue_start(previous_end_ms, segment.offset_ms),
+        segment.offset_ms,
+        &segment.attributed_text(),
+        format,
+    ));
+    if let Err(err) = write_atomic(&path, &content) {
+        log_line(&format!("[captions] {}", err));
+    }
+}
+
+/// Write the live caption file again from `segments`, after one was
+/// revised or deleted.
+pub fn rewrite_live(app: &tauri::AppHandle, session_id: &str, segments: &[TranscriptSegment]) {
+    let config = settings::load_settings(app).captions;
+    if !config.live_output {
+        return;
+    }
+    let Some((path, format)) = resolve_output(&config, session_id) else {
+        return;
+    };
+    let mut content = match format {
+        CaptionFormat::Vtt => "WEBVTT\n\n".to_string(),
+        CaptionFormat::Srt => String::new(),
+    };
+    let mut previous_end_ms = None;
+    for segment in segments {
+        content.push_str(&render_cue(
+            segment.index + 1,
+            cue_start(previous_end_ms, segment.offset_ms),
+            segment.offset_ms,
+            &segment.attributed_text(),
+            format,
+        ));
+        previous_end_ms = Some(segment.offset_ms);
+    }
+    if let Err(err) = write_atomic(&path, &content) {
+        log_line(&format!("[captions] {}", err));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{cue_start, render_cue, CaptionFormat};
+
+    #[test]
+    fn renders_srt_and_vtt_cues() {
+        assert_eq!(
+            render_cue(3, 61_250, 3_723_004, " hello ", CaptionFormat::Srt),
+            "3\n00:01:01,250 --> 01:02:03,004\nhello\n\n"
+        );
+        assert_eq!(
+            render_cue(1, 0, 2_500, "hi", CaptionFormat::Vtt),
+            "1\n00:00:00.000 --> 00:00:02.500\nhi\n\n"
+        );
+    }
+
+    #[test]
+    fn cue_starts_follow_previous_segment_within_cap() {
+        assert_eq!(cue_start(Some(4_000), 7_000), 4_000);
+        assert_eq!(cue_start(None, 40_000), 25_000);
+        assert_eq!(cue_start(Some(1_000), 60_000), 45_000);
+    }
+}
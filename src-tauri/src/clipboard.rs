@@ -0,0 +1,166 @@
+use std::collections::hash_map::DefaultHasher;
+use std::hash::{Hash, Hasher};
+use std::time::Duration;
+
+use serde::{Deserialize, Serialize};
+use tauri::Url;
+use tauri_specta::Event;
+
+use crate::settings::{self, ClipboardSettings};
+use crate::log_line;
+
+const POLL_INTERVAL: Duration = Duration::from_millis(1500);
+const PREVIEW_CHARS: usize = 140;
+/// Query parameters that usually carry a credential.
+const SECRET_PARAMS: &[&str] = &["token", "key", "apikey", "api_key", "secret", "password", "auth", "code", "sig"];
+
+#[derive(Serialize, Deserialize, Clone, Copy, Debug, PartialEq, Eq, specta::Type)]
+#[serde(rename_all = "camelCase")]
+pub enum ClipboardKind {
+    Url,
+    Text,
+}
+
+/// Pipeline actions the main window can offer for copied content.
+#[derive(Serialize, Deserialize, Clone, Copy, Debug, PartialEq, Eq, specta::Type)]
+#[serde(rename_all = "camelCase")]
+pub enum ClipboardAction {
+    /// Open the URL in the browser.
+    Open,
+    /// Summarize the article or text.
+    Summarize,
+    /// Draw the text on the Excalidraw canvas.
+    Canvas,
+}
+
+/// Emitted to the main window when something worth acting on is copied.
+/// The content itself is never logged or stored.
+#[derive(Serialize, Deserialize, Clone, Debug, specta::Type, tauri_specta::Event)]
+#[serde(rename_all = "camelCase")]
+pub struct ClipboardSuggestion {
+    pub kind: ClipboardKind,
+    pub content: String,
+    pub preview: String,
+    pub actions: Vec<ClipboardAction>,
+}
+
+/// Single tokens that look like API keys or passwords rather than prose.
+fn looks_like_secret(text: &str) -> bool {
+    if text.contains("-----BEGIN") {
+        return true;
+    }
+    let token = text.trim();
+    !token.contains(char::is_whitespace)
+        && token.len() >= 16
+        && token.chars().any(|c| c.is_ascii_digit())
+        && token.chars().any(|c| c.is_ascii_alphabetic())
+}
+
+fn url_is_private(url: &Url) -> bool {
+    !url.username().is_empty()
+        || url.password().is_some()
+        || url
+            .query_pairs()
+            .any(|(name, _)| SECRET_PARAMS.contains(&name.to_ascii_lowercase().as_str()))
+}
+
+/// What to suggest for `text` under `settings`, if anything.
+fn classify(text: &str, settings: &ClipboardSettings) -> Option<ClipboardSuggestion> {
+    let content = text.trim();
+    if content.is_empty() {
+        return None;
+    }
+    let preview: String = content.chars().take(PREVIEW_CHARS).collect();
+    if !content.contains(char::is_whitespace) && content.starts_with("http") {
+        let url = Url::parse(content).ok().filter(|url| matches!(url.scheme(), "http" | "https"))?;
+        if !settings.urls || url_is_private(&url) {
+            return None;
+        }
+        return Some(ClipboardSuggestion {
+            kind: ClipboardKind::Url,
+            content: content.to_string(),
+            preview,
+            actions: vec![ClipboardAction::Open, ClipboardAction::Summarize],
+        });
+    }
+    if !settings.text || looks_like_secret(content) || content.chars().count() < settings.min_text_chars as usize {
+        return None;
+    }
+    Some(ClipboardSuggestion {
+        kind: ClipboardKind::Text,
+        content: content.to_string(),
+        preview,
+        actions: vec![ClipboardAction::Summarize, ClipboardAction::Canvas],
+    })
+}
+
+fn fingerprint(text: &str) -> u64 {
+    let mut hasher = DefaultHasher::new();
+    text.hash(&mut hasher);
+    hasher.finish()
+}
+
+/// Poll the clipboard while `clipboard.enabled` is on. Only a hash of the
+/// last seen content is kept, and whatever was on the clipboard when the
+/// watcher was switched on is never suggested.
+pub fn spawn_watcher(app: tauri::AppHandle) {
+    std::thread::spawn(move || {
+        let mut clipboard: Option<arboard::Clipboard> = None;
+        let mut last_seen: Option<u64> = None;
+        loop {
+            std::thread::sleep(POLL_INTERVAL);
+            let settings = settings::load_settings(&app).clipboard;
+            if !settings.enabled {
+                clipboard = None;
+                last_seen = None;
+                continue;
+            }
+            if clipboard.is_none() {
+                match arboard::Clipboard::new() {
+                    Ok(opened) => clipboard = Some(opened),
+                    Err(err) => {
+                        log_line(&format!("[clipboard] failed to open clipboard: {}", err));
+                        continue;
+                    }
+                }
+            }
+            // Images and files have no text; treat them as a change.
+            let text = clipboard.as_mut().and_then(|c| c.get_text().ok()).unwrap_or_default();
+            let seen = fingerprint(&text);
+            let previous = last_seen.replace(seen);
+            if previous.is_none_or(|previous| previous == seen) {
+                continue;
+            }
+            if let Some(suggestion) = classify(&text, &settings) {
+                log_line(&format!("[clipboard] suggesting actions for copied {:?}", suggestion.kind));
+                if let Err(err) = suggestion.emit_to(&app, "main") {
+                    log_line(&format!("[clipboard] failed to notify main window: {}", err));
+                }
+            }
+        }
+    });
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{classify, ClipboardAction, ClipboardKind};
+    use crate::settings::ClipboardSettings;
+
+    #[test]
+    fn suggests_for_urls_and_long_text_but_not_secrets() {
+        let settings = ClipboardSettings::default();
+        let url = classify(" https://example.com/article ", &settings).unwrap();
+        assert_eq!(url.kind, ClipboardKind::Url);
+        assert_eq!(url.actions, vec![ClipboardAction::Open, ClipboardAction::Summarize]);
+        assert!(classify("https://example.com/reset?token=abc", &settings).is_none());
+        assert!(classify("https://user:pw@example.com/", &settings).is_none());
+
+        let prose = "word ".repeat(200);
+        assert_eq!(classify(&prose, &settings).unwrap().kind, ClipboardKind::Text);
+        assert!(classify("a short note", &settings).is_none());
+        assert!(classify(&"sk1a".repeat(200), &settings).is_none());
+
+        let no_text = ClipboardSettings { text: false, ..settings };
+        assert!(classify(&prose, &no_text).is_none());
+    }
+}
@@ -0,0 +1,99 @@
+//! Wall-clock time for every store and export. Instants are stored as UTC
+//! milliseconds; anything people read gets an RFC 3339 UTC string or a
+//! date in the system's timezone and locale, detected automatically.
+
+use std::sync::OnceLock;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use chrono::{DateTime, FixedOffset, Local, Locale, Offset, SecondsFormat, TimeZone, Utc};
+
+/// An instant in the forms stores and exports need.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Timestamp {
+    /// RFC 3339 in UTC, e.g. `2026-10-17T14:03:09.250Z`.
+    pub utc: String,
+    /// Date and time in the local timezone and locale, with the UTC offset.
+    pub local: String,
+}
+
+pub fn now_millis() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|d| d.as_millis() as u64)
+        .unwrap_or_default()
+}
+
+fn utc_datetime(ms: u64) -> DateTime<Utc> {
+    Utc.timestamp_millis_opt(ms as i64).single().unwrap_or_default()
+}
+
+/// `en-US` / `de_DE.UTF-8` style identifiers to a chrono locale, falling
+/// back to the language's own territory (`de` → `de_DE`) and then POSIX.
+fn parse_locale(tag: &str) -> Locale {
+    let tag = tag.split(['.', '@']).next().unwrap_or_default().replace('-', "_");
+    let language = tag.split('_').next().unwrap_or_default().to_string();
+    [tag, format!("{}_{}", language, language.to_uppercase())]
+        .iter()
+        .find_map(|candidate| Locale::try_from(candidate.as_str()).ok())
+        .unwrap_or(Locale::POSIX)
+}
+
+fn system_locale() -> Locale {
+    static LOCALE: OnceLock<Locale> = OnceLock::new();
+    *LOCALE.get_or_init(|| sys_locale::get_locale().map(|tag| parse_locale(&tag)).unwrap_or(Locale::POSIX))
+}
+
+/// RFC 3339 UTC with milliseconds.
+pub fn utc_string(ms: u64) -> String {
+    utc_datetime(ms).to_rfc3339_opts(SecondsFormat::Millis, true)
+}
+
+fn format_local(ms: u64, offset: FixedOffset, locale: Locale) -> String {
+    let local = utc_datetime(ms).with_timezone(&offset);
+    format!("{} (UTC{})", local.format_localized("%x %X", locale), local.format("%:z"))
+}
+
+/// Offset of the system timezone from UTC at `ms`, in milliseconds.
+pub fn local_offset_ms(ms: u64) -> i64 {
+    Local
+        .timestamp_millis_opt(ms as i64)
+        .single()
+        .map(|local| local.offset().local_minus_utc() as i64 * 1000)
+        .unwrap_or_default()
+}
+
+/// Date and time in the system timezone (with its offset at that instant,
+/// so DST is right) and locale.
+pub fn local_string(ms: u64) -> String {
+    let offset = FixedOffset::east_opt((local_offset_ms(ms) / 1000) as i32).unwrap_or(Utc.fix());
+    format_local(ms, offset, system_locale())
+}
+
+pub fn timestamp(ms: u64) -> Timestamp {
+    Timestamp {
+        utc: utc_string(ms),
+        local: local_string(ms),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{format_local, parse_locale, utc_string};
+    use chrono::{FixedOffset, Locale};
+
+    #[test]
+    fn formats_utc_and_localized_times() {
+        // 2026-03-29T01:30:00.250Z, the night clocks go forward in Europe.
+        let ms = 1_774_747_800_250;
+        assert_eq!(utc_string(ms), "2026-03-29T01:30:00.250Z");
+        let cest = FixedOffset::east_opt(2 * 3600).unwrap();
+        assert_eq!(format_local(ms, cest, Locale::de_DE), "29.03.2026 03:30:00 (UTC+02:00)");
+        let pdt = FixedOffset::west_opt(7 * 3600).unwrap();
+        assert_eq!(format_local(ms, pdt, Locale::en_US), "03/28/2026 06:30:00 PM (UTC-07:00)");
+
+        assert_eq!(parse_locale("en-GB"), Locale::en_GB);
+        assert_eq!(parse_locale("de_DE.UTF-8"), Locale::de_DE);
+        assert_eq!(parse_locale("fr"), Locale::fr_FR);
+        assert_eq!(parse_locale("xx-YY"), Locale::POSIX);
+    }
+}
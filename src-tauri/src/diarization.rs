@@ -0,0 +1,198 @@
+use std::path::Path;
+use std::process::Command;
+
+use serde::{Deserialize, Serialize};
+
+use crate::transcription_queue::{self, Lane};
+use crate::{clean_transcript_fragment, language, log_line, settings, truncate_for_log, vad, whisper_models};
+
+/// Tinydiarize model used when `transcription.diarizationModel` is empty.
+const DEFAULT_MODEL: &str = "ggml-small.en-tdrz.bin";
+const TURN_MARKER: &str = "[SPEAKER_TURN]";
+
+/// A stretch of speech attributed to one speaker.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, specta::Type)]
+#[serde(rename_all = "camelCase")]
+pub struct SpeakerSegment {
+    /// 1-based. tinydiarize only marks where the speaker changes, so
+    /// labels alternate between speakers 1 and 2 on every turn, which
+    /// matches a two-person interview.
+    pub speaker: u32,
+    pub start_ms: u64,
+    pub end_ms: u64,
+    pub text: String,
+}
+
+/// `00:01:02.345` in milliseconds.
+fn parse_timestamp(value: &str) -> Option<u64> {
+    let (clock, millis) = value.trim().split_once('.')?;
+    let mut parts = clock.split(':').map(|part| part.parse::<u64>().ok());
+    let (hours, minutes, seconds) = (parts.next()??, parts.next()??, parts.next()??);
+    Some(((hours * 60 + minutes) * 60 + seconds) * 1000 + millis.parse::<u64>().ok()?)
+}
+
+/// Read `[00:00:00.000 --> 00:00:04.000]  Hi there [SPEAKER_TURN]` lines
+/// from `whisper-cli -tdrz` as `(start_ms, end_ms, text, speaker_turn_next)`.
+fn parse_cli_output(output: &str) -> Vec<(u64, u64, String, bool)> {
+    output
+        .lines()
+        .filter_map(|line| {
+            let (times, text) = line.trim().strip_prefix('[')?.split_once(']')?;
+            let (start, end) = times.split_once("-->")?;
+            let turn = text.contains(TURN_MARKER);
+            let text = text.replace(TURN_MARKER, "");
+            Some((parse_timestamp(start)?, parse_timestamp(end)?, text, turn))
+        })
+        .collect()
+}
+
+/// Attribute whisper segments to speakers, merging consecutive segments
+/// from the same speaker.
+fn label_turns(raw: Vec<(u64, u64, String, bool)>) -> Vec<SpeakerSegment> {
+    let mut segments: Vec<SpeakerSegment> = Vec::new();
+    let mut speaker = 1;
+    for (start_ms, end_ms, text, turn_next) in raw {
+        if let Some(text) = clean_transcript_fragment(text.trim()) {
+            match segments.last_mut() {
+                Some(last) if last.speaker == speaker => {
+                    last.end_ms = end_ms;
+                    last.text = format!("{} {}", last.text, text);
+                }
+                _ => segments.push(SpeakerSegment {
+                    speaker,
+                    start_ms,
+                    end_ms,
+                    text,
+                }),
+            }
+        }
+        if turn_next {
+            speaker = if speaker == 1 { 2 } else { 1 };
+        }
+    }
+    segments
+}
+
+fn transcribe_turns_locally(
+    wav_bytes: &[u8],
+    model_path: &Path,
+    language: &str,
+    prompt: Option<&str>,
+) -> Result<Vec<(u64, u64, String, bool)>, String> {
+    #[cfg(feature = "whisper-rs")]
+    match crate::whisper_engine::decode_wav(wav_bytes)
+        .and_then(|samples| crate::whisper_engine::transcribe_turns(model_path, &samples, language, prompt))
+    {
+        Ok(raw) => return Ok(raw),
+        Err(err) => log_line(&format!("[whisper-rs] {}; falling back to whisper-cli", err)),
+    }
+
+    let cli_path = crate::resolve_whisper_cli()?;
+    let wav_file = crate::temp_files::write_wav(wav_bytes)?;
+    let mut command = Command::new(&cli_path);
+    crate::acceleration::apply_cli_flags(&mut command);
+    command
+        .arg("-m")
+        .arg(model_path)
+        .arg("-l")
+        .arg(language)
+        .arg("-f")
+        .arg(wav_file.path())
+        .arg("-tdrz")
+        .arg("-np");
+    if let Some(prompt) = prompt {
+        command.arg("--prompt").arg(prompt);
+    }
+    let output = command
+        .output()
+        .map_err(|err| format!("failed to run whisper-cli: {}", err));
+    drop(wav_file);
+    let output = output?;
+    if !output.status.success() {
+        let stderr = String::from_utf8_lossy(&output.stderr);
+        return Err(whisper_models::explain_load_failure(&stderr, model_path)
+            .unwrap_or_else(|| format!("whisper-cli failed: {}", truncate_for_log(stderr.trim(), 300))));
+    }
+    Ok(parse_cli_output(&String::from_utf8_lossy(&output.stdout)))
+}
+
+/// The diarization mode of `transcribe_audio`: the same local transcription,
+/// but returned as segments with speaker labels and timestamps instead of
+/// one joined string. Needs a tinydiarize model (`ggml-small.en-tdrz.bin`)
+/// in the models folder; remote whisper servers are not used.
+#[tauri::command]
+#[specta::specta]
+pub async fn transcribe_audio_diarized(
+    app: tauri::AppHandle,
+    audio_base64: String,
+    language: Option<String>,
+) -> Result<Vec<SpeakerSegment>, String> {
+    let wav_bytes = crate::decode_audio_payload(&audio_base64).await?;
+    if !vad::wav_has_speech(&wav_bytes) {
+        return Ok(Vec::new());
+    }
+    let settings = settings::load_settings(&app);
+    let language = language::whisper_language(language.as_deref().unwrap_or(&settings.transcription.language));
+    let prompt = settings.transcription.whisper_prompt();
+    let model = match settings.transcription.diarization_model.trim() {
+        "" => DEFAULT_MODEL,
+        model => model,
+    };
+    let model_path = whisper_models::find(&app, model).ok_or_else(|| {
+        format!(
+            "speaker labels need the tinydiarize model {}; put it in the whisper models folder",
+            model
+        )
+    })?;
+    log_line(&format!(
+        "transcribe_audio_diarized called (payload bytes: {}, model: {})",
+        audio_base64.len(),
+        model_path.display()
+    ));
+    let _permit = transcription_queue::acquire(&app, Lane::Batch).await;
+    let raw = tauri::async_runtime::spawn_blocking(move || transcribe_turns_locally(&wav_bytes, &model_path, &language, prompt.as_deref()))
+        .await
+        .map_err(|err| format!("transcription task failed: {}", err))??;
+    let segments = label_turns(raw);
+    log_line(&format!("[diarization] {} speaker segments", segments.len()));
+    Ok(segments)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{label_turns, parse_cli_output, SpeakerSegment};
+
+    #[test]
+    fn labels_speakers_from_turn_markers() {
+        let output = "\
+[00:00:00.000 --> 00:00:02.500]   So what brought you here?
+[00:00:02.500 --> 00:00:04.000]   Tell us a bit. [SPEAKER_TURN]
+[00:00:04.000 --> 00:00:09.120]   Honestly, the telescope. [SPEAKER_TURN]
+[00:01:09.120 --> 00:01:10.000]   Great.
+";
+        let segments = label_turns(parse_cli_output(output));
+        assert_eq!(
+            segments,
+            vec![
+                SpeakerSegment {
+                    speaker: 1,
+                    start_ms: 0,
+                    end_ms: 4000,
+                    text: "So what brought you here? Tell us a bit.".to_string(),
+                },
+                SpeakerSegment {
+                    speaker: 2,
+                    start_ms: 4000,
+                    end_ms: 9120,
+                    text: "Honestly, the telescope.".to_string(),
+                },
+                SpeakerSegment {
+                    speaker: 1,
+                    start_ms: 69_120,
+                    end_ms: 70_000,
+                    text: "Great.".to_string(),
+                },
+            ]
+        );
+    }
+}
@@ -0,0 +1,266 @@
+//! System-wide dictation: while it's on, every finalized transcript segment
+//! is typed into whichever app has focus. A global shortcut
+//! (`dictation.shortcut`) toggles it; the webview starts listening when it
+//! turns on and stops what it started when it turns off. Typing goes
+//! through CGEvent on macOS, `xdotool` on Linux (X11) and `SendKeys` on
+//! Windows, one segment at a time in order. Nothing is typed while a
+//! HeyJamie window has focus.
+
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::mpsc::{self, Sender};
+use std::sync::Mutex;
+
+use serde::{Deserialize, Serialize};
+use tauri::Manager;
+use tauri_plugin_global_shortcut::{GlobalShortcutExt, Shortcut, ShortcutState};
+use tauri_specta::Event;
+
+use crate::{log_line, settings};
+
+/// Dictation was turned on or off.
+#[derive(Serialize, Deserialize, Clone, Debug, specta::Type, tauri_specta::Event)]
+#[serde(rename_all = "camelCase")]
+pub struct Dictation {
+    pub active: bool,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, specta::Type)]
+#[serde(rename_all = "camelCase")]
+pub struct DictationStatus {
+    pub enabled: bool,
+    pub shortcut: String,
+    /// Segments are being typed.
+    pub active: bool,
+    /// The shortcut is registered with the OS.
+    pub registered: bool,
+    /// Why registering failed, e.g. another app owns the shortcut.
+    pub error: Option<String>,
+}
+
+#[derive(Default)]
+pub struct DictationState {
+    active: AtomicBool,
+    registered: Mutex<Option<Shortcut>>,
+    error: Mutex<Option<String>>,
+    /// Feeds the typing thread, started on first use.
+    typist: Mutex<Option<Sender<String>>>,
+}
+
+/// CGEvent takes at most 20 UTF-16 units per keyboard event.
+#[cfg_attr(not(target_os = "macos"), allow(dead_code))]
+const MAX_EVENT_UNITS: usize = 20;
+
+/// `text` as UTF-16 chunks of at most `max` units, never splitting a
+/// surrogate pair.
+#[cfg_attr(not(target_os = "macos"), allow(dead_code))]
+fn utf16_chunks(text: &str, max: usize) -> Vec<Vec<u16>> {
+    let mut chunks: Vec<Vec<u16>> = Vec::new();
+    let mut buf = [0u16; 2];
+    for c in text.chars() {
+        let units = c.encode_utf16(&mut buf);
+        match chunks.last_mut() {
+            Some(chunk) if chunk.len() + units.len() <= max => chunk.extend_from_slice(units),
+            _ => chunks.push(units.to_vec()),
+        }
+    }
+    chunks
+}
+
+#[cfg(target_os = "macos")]
+fn type_text(text: &str) -> Result<(), String> {
+    use core_graphics::event::{CGEvent, CGEventTapLocation};
+    use core_graphics::event_source::{CGEventSource, CGEventSourceStateID};
+
+    let source = CGEventSource::new(CGEventSourceStateID::HIDSystemState)
+        .map_err(|_| "could not create a keyboard event source".to_string())?;
+    for chunk in utf16_chunks(text, MAX_EVENT_UNITS) {
+        for keydown in [true, false] {
+            let event = CGEvent::new_keyboard_event(source.clone(), 0, keydown)
+                .map_err(|_| "could not create a keyboard event; is Accessibility access granted?".to_string())?;
+            event.set_string_from_utf16_unchecked(&chunk);
+            event.post(CGEventTapLocation::HID);
+        }
+    }
+    Ok(())
+}
+
+/// X11 only; Wayland doesn't let clients type into other apps.
+#[cfg(target_os = "linux")]
+fn type_text(text: &str) -> Result<(), String> {
+    let mut command = std::process::Command::new("xdotool");
+    command.args(["type", "--clearmodifiers", "--delay", "5", "--", text]);
+    let output = crate::process_runner::ProcessRunner::new("xdotool", command)
+        .timeout(std::time::Duration::from_secs(30))
+        .run()?;
+    if !output.status.success() {
+        return Err(format!(
+            "xdotool type failed: {}",
+            crate::truncate_for_log(String::from_utf8_lossy(&output.stderr).trim(), 300)
+        ));
+    }
+    Ok(())
+}
+
+/// `SendKeys` treats `+^%~(){}[]` as commands; braces make them literal.
+#[cfg_attr(not(target_os = "windows"), allow(dead_code))]
+fn send_keys_literal(text: &str) -> String {
+    text.chars()
+        .map(|c| match c {
+            '+' | '^' | '%' | '~' | '(' | ')' | '{' | '}' | '[' | ']' => format!("{{{}}}", c),
+            '\'' => "''".to_string(),
+            c => c.to_string(),
+        })
+        .collect()
+}
+
+#[cfg(target_os = "windows")]
+fn type_text(text: &str) -> Result<(), String> {
+    let script = format!(
+        "Add-Type -AssemblyName System.Windows.Forms;[System.Windows.Forms.SendKeys]::SendWait('{}')",
+        send_keys_literal(text)
+    );
+    let mut command = std::process::Command::new("powershell");
+    command.args(["-NoProfile", "-NonInteractive", "-Command", &script]);
+    let output = crate::process_runner::ProcessRunner::new("SendKeys", command)
+        .timeout(std::time::Duration::from_secs(30))
+        .run()?;
+    if !output.status.success() {
+        return Err("SendKeys failed".to_string());
+    }
+    Ok(())
+}
+
+#[cfg(not(any(target_os = "windows", target_os = "linux", target_os = "macos")))]
+fn type_text(_text: &str) -> Result<(), String> {
+    Err("dictation isn't supported on this platform".to_string())
+}
+
+fn set_active(app: &tauri::AppHandle, active: bool) {
+    let state = app.state::<DictationState>();
+    if state.active.swap(active, Ordering::SeqCst) == active {
+        return;
+    }
+    log_line(&format!("[dictation] {}", if active { "on" } else { "off" }));
+    let _ = Dictation { active }.emit(app);
+}
+
+/// Type a finalized segment when dictation is on and another app has
+/// focus. Segments are typed in the order they arrive.
+pub fn type_segment(app: &tauri::AppHandle, text: &str) {
+    let state = app.state::<DictationState>();
+    if !state.active.load(Ordering::SeqCst) {
+        return;
+    }
+    if app.webview_windows().values().any(|window| window.is_focused().unwrap_or(false)) {
+        return;
+    }
+    let mut typist = state.typist.lock().unwrap();
+    let sender = typist.get_or_insert_with(|| {
+        let (sender, receiver) = mpsc::channel::<String>();
+        std::thread::spawn(move || {
+            for text in receiver {
+                if let Err(err) = type_text(&text) {
+                    log_line(&format!("[dictation] {}", err));
+                }
+            }
+        });
+        sender
+    });
+    // Separate consecutive segments the way they'd be typed by hand.
+    let _ = sender.send(format!("{} ", text.trim()));
+}
+
+fn parse(shortcut: &str) -> Result<Shortcut, String> {
+    shortcut
+        .trim()
+        .parse::<Shortcut>()
+        .map_err(|err| format!("invalid shortcut {:?}: {}", shortcut.trim(), err))
+}
+
+/// Register the configured shortcut, replacing the previous one, and turn
+/// dictation off when it's disabled. Called at startup and whenever
+/// settings are saved.
+pub fn reload(app: &tauri::AppHandle) {
+    let config = settings::load_settings(app).dictation;
+    if !config.enabled {
+        set_active(app, false);
+    }
+    let state = app.state::<DictationState>();
+    let mut registered = state.registered.lock().unwrap();
+    let wanted = if config.enabled { parse(&config.shortcut).map(Some) } else { Ok(None) };
+    if wanted.as_ref().is_ok_and(|wanted| *wanted == *registered) {
+        return;
+    }
+    if let Some(previous) = registered.take() {
+        let _ = app.global_shortcut().unregister(previous);
+    }
+    let result = wanted.and_then(|wanted| match wanted {
+        Some(shortcut) => app
+            .global_shortcut()
+            .on_shortcut(shortcut, |app, _, event| {
+                if event.state == ShortcutState::Pressed {
+                    let active = app.state::<DictationState>().active.load(Ordering::SeqCst);
+                    set_active(app, !active);
+                }
+            })
+            .map(|_| Some(shortcut))
+            .map_err(|err| format!("could not register {}: {}", config.shortcut.trim(), err)),
+        None => Ok(None),
+    });
+    match result {
+        Ok(shortcut) => {
+            if shortcut.is_some() {
+                log_line(&format!("[dictation] registered {}", config.shortcut.trim()));
+            }
+            *registered = shortcut;
+            *state.error.lock().unwrap() = None;
+        }
+        Err(err) => {
+            log_line(&format!("[dictation] {}", err));
+            *state.error.lock().unwrap() = Some(err);
+        }
+    }
+}
+
+#[tauri::command]
+#[specta::specta]
+pub fn get_dictation_status(app: tauri::AppHandle) -> DictationStatus {
+    let config = settings::load_settings(&app).dictation;
+    let state = app.state::<DictationState>();
+    let registered = state.registered.lock().unwrap().is_some();
+    let error = state.error.lock().unwrap().clone();
+    DictationStatus {
+        enabled: config.enabled,
+        shortcut: config.shortcut,
+        active: state.active.load(Ordering::SeqCst),
+        registered,
+        error,
+    }
+}
+
+/// Turn dictation on or off without the shortcut.
+#[tauri::command]
+#[specta::specta]
+pub fn set_dictation_active(app: tauri::AppHandle, active: bool) -> Result<DictationStatus, String> {
+    if active && !settings::load_settings(&app).dictation.enabled {
+        return Err("dictation is off; turn it on in settings".to_string());
+    }
+    set_active(&app, active);
+    Ok(get_dictation_status(app))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{send_keys_literal, utf16_chunks};
+
+    #[test]
+    fn chunks_text_for_key_events() {
+        let chunks = utf16_chunks("Hello 👋 there", 7);
+        assert_eq!(chunks.iter().map(Vec::len).collect::<Vec<_>>(), vec![6, 7, 1]);
+        let joined: Vec<u16> = chunks.concat();
+        assert_eq!(String::from_utf16(&joined).unwrap(), "Hello 👋 there");
+        assert!(utf16_chunks("", 20).is_empty());
+
+        assert_eq!(send_keys_literal("1+1 (it's ~50%)"), "1{+}1 {(}it''s {~}50{%}{)}");
+    }
+}
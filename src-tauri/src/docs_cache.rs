@@ -0,0 +1,251 @@
+use std::{fs, path::PathBuf, time::Duration};
+
+use serde::{Deserialize, Serialize};
+use serde_json::Value as JsonValue;
+use tauri::Manager;
+
+use crate::settings;
+use crate::{log_line, now_millis};
+
+/// Context7 tools whose results are cached (`query-docs` is the newer name).
+const DOC_TOOLS: &[&str] = &["get-library-docs", "query-docs"];
+
+/// Expired entries are kept this many TTLs for use when Context7 is down.
+const STALE_RETENTION: u64 = 4;
+
+/// One cached Context7 lookup, stored as `<app cache>/context7/<hash>.json`.
+/// `scripts/llm-agent.mjs` reads these to answer repeat lookups and to stand
+/// in for the MCP server when it is unreachable.
+#[derive(Serialize, Deserialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct DocsCacheEntry {
+    pub key: String,
+    pub library: String,
+    pub version: Option<String>,
+    pub topic: String,
+    pub fetched_at: u64,
+    pub output: JsonValue,
+}
+
+#[derive(Serialize, Clone, specta::Type)]
+#[serde(rename_all = "camelCase")]
+pub struct DocsCacheStats {
+    pub entries: u32,
+    pub bytes: u64,
+    pub libraries: Vec<String>,
+}
+
+/// Split a Context7 id like `/vercel/next.js/v14.3.0` (or `…@v14.3.0`) into
+/// the library and optional version.
+pub fn parse_library_id(id: &str) -> (String, Option<String>) {
+    let id = id.trim();
+    if let Some((library, version)) = id.rsplit_once('@').filter(|(l, _)| !l.is_empty()) {
+        return (library.to_string(), Some(version.to_string()));
+    }
+    let parts: Vec<&str> = id.trim_start_matches('/').split('/').collect();
+    if parts.len() > 2 {
+        (format!("/{}", parts[..2].join("/")), Some(parts[2..].join("/")))
+    } else {
+        (id.to_string(), None)
+    }
+}
+
+/// Cache key shared with the agent script; keep both in sync.
+pub fn entry_key(library: &str, version: Option<&str>, topic: &str) -> String {
+    format!(
+        "{}@{}#{}",
+        library.trim().to_lowercase(),
+        version.unwrap_or("latest").trim().to_lowercase(),
+        topic.trim().to_lowercase()
+    )
+}
+
+/// 32-bit FNV-1a of the key, used as the file name.
+pub fn key_hash(key: &str) -> String {
+    let hash = key
+        .bytes()
+        .fold(0x811c9dc5u32, |hash, byte| (hash ^ byte as u32).wrapping_mul(0x01000193));
+    format!("{:08x}", hash)
+}
+
+fn cache_dir(app: &tauri::AppHandle) -> Result<PathBuf, String> {
+    let dir = app
+        .path()
+        .app_cache_dir()
+        .map_err(|err| format!("failed to resolve app cache dir: {}", err))?
+        .join("context7");
+    fs::create_dir_all(&dir).map_err(|err| format!("failed to create docs cache dir: {}", err))?;
+    Ok(dir)
+}
+
+fn ttl(app: &tauri::AppHandle) -> Duration {
+    Duration::from_secs(settings::load_settings(app).docs.ttl_hours.max(1) * 3600)
+}
+
+/// Environment for the agent process so it can read the cache.
+pub fn agent_env(app: &tauri::AppHandle) -> Vec<(String, String)> {
+    let Ok(dir) = cache_dir(app) else {
+        return Vec::new();
+    };
+    let config = settings::load_settings(app).docs;
+    vec![
+        ("HEYJAMIE_DOCS_CACHE_DIR".to_string(), dir.display().to_string()),
+        ("HEYJAMIE_DOCS_CACHE_TTL_MS".to_string(), ttl(app).as_millis().to_string()),
+        ("HEYJAMIE_DOCS_OFFLINE".to_string(), if config.offline { "1" } else { "0" }.to_string()),
+    ]
+}
+
+fn is_error_output(output: &JsonValue) -> bool {
+    output.get("isError").and_then(|v| v.as_bool()).unwrap_or(false)
+}
+
+/// Cache the Context7 doc lookups made during an agent run.
+pub fn record_from_agent_output(app: &tauri::AppHandle, stdout: &str) {
+    let Ok(output) = serde_json::from_str::<JsonValue>(stdout) else {
+        return;
+    };
+    let Some(calls) = output.get("toolCalls").and_then(|v| v.as_array()) else {
+        return;
+    };
+    let Ok(dir) = cache_dir(app) else {
+        return;
+    };
+    for call in calls {
+        let name = call.get("name").and_then(|v| v.as_str()).unwrap_or("");
+        let name = name.rsplit(':').next().unwrap_or(name);
+        if !DOC_TOOLS.contains(&name) {
+            continue;
+        }
+        let (Some(input), Some(result)) = (call.get("input"), call.get("output")) else {
+            continue;
+        };
+        if is_error_output(result) || result.get("cached").is_some() {
+            continue;
+        }
+        let Some(id) = ["context7CompatibleLibraryID", "libraryId"]
+            .iter()
+            .find_map(|key| input.get(*key).and_then(|v| v.as_str()))
+        else {
+            continue;
+        };
+        let topic = ["topic", "query"]
+            .iter()
+            .find_map(|key| input.get(*key).and_then(|v| v.as_str()))
+            .unwrap_or("");
+        let (library, version) = parse_library_id(id);
+        let key = entry_key(&library, version.as_deref(), topic);
+        let entry = DocsCacheEntry {
+            key: key.clone(),
+            library,
+            version,
+            topic: topic.to_string(),
+            fetched_at: now_millis(),
+            output: result.clone(),
+        };
+        let path = dir.join(format!("{}.json", key_hash(&key)));
+        match serde_json::to_string(&entry) {
+            Ok(content) => {
+                if let Err(err) = fs::write(&path, content.as_bytes()) {
+                    log_line(&format!("[docs-cache] failed to write {}: {}", path.display(), err));
+                } else {
+                    log_line(&format!("[docs-cache] cached {}", key));
+                }
+            }
+            Err(err) => log_line(&format!("[docs-cache] failed to format entry: {}", err)),
+        }
+    }
+}
+
+fn read_entries(app: &tauri::AppHandle) -> Vec<(PathBuf, u64, Option<DocsCacheEntry>)> {
+    let Ok(dir) = cache_dir(app) else {
+        return Vec::new();
+    };
+    let Ok(entries) = fs::read_dir(dir) else {
+        return Vec::new();
+    };
+    entries
+        .filter_map(|entry| entry.ok())
+        .map(|entry| entry.path())
+        .filter(|path| path.extension().is_some_and(|ext| ext == "json"))
+        .map(|path| {
+            let size = fs::metadata(&path).map(|m| m.len()).unwrap_or(0);
+            let parsed = fs::read_to_string(&path)
+                .ok()
+                .and_then(|content| serde_json::from_str(&content).ok());
+            (path, size, parsed)
+        })
+        .collect()
+}
+
+/// Drop entries well past the TTL. Entries between one and
+/// `STALE_RETENTION` TTLs old are no longer served normally but are kept as
+/// the fallback for when the server can't be reached; offline mode keeps
+/// everything.
+pub fn prune_expired(app: &tauri::AppHandle) {
+    if settings::load_settings(app).docs.offline {
+        return;
+    }
+    let retention = ttl(app).as_millis() as u64 * STALE_RETENTION;
+    let cutoff = now_millis().saturating_sub(retention);
+    let mut removed = 0;
+    for (path, _, entry) in read_entries(app) {
+        if entry.is_none_or(|entry| entry.fetched_at < cutoff) && fs::remove_file(&path).is_ok() {
+            removed += 1;
+        }
+    }
+    if removed > 0 {
+        log_line(&format!("[docs-cache] pruned {} expired entries", removed));
+    }
+}
+
+#[tauri::command]
+#[specta::specta]
+pub fn get_docs_cache_stats(app: tauri::AppHandle) -> DocsCacheStats {
+    let entries = read_entries(&app);
+    let mut libraries: Vec<String> = entries
+        .iter()
+        .filter_map(|(_, _, entry)| entry.as_ref().map(|e| e.library.clone()))
+        .collect();
+    libraries.sort();
+    libraries.dedup();
+    DocsCacheStats {
+        entries: entries.len() as u32,
+        bytes: entries.iter().map(|(_, size, _)| size).sum(),
+        libraries,
+    }
+}
+
+#[tauri::command]
+#[specta::specta]
+pub fn clear_docs_cache(app: tauri::AppHandle) -> Result<(), String> {
+    for (path, _, _) in read_entries(&app) {
+        fs::remove_file(&path).map_err(|err| format!("failed to remove {}: {}", path.display(), err))?;
+    }
+    log_line("[docs-cache] cleared");
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{entry_key, key_hash, parse_library_id};
+
+    #[test]
+    fn parses_versioned_library_ids() {
+        assert_eq!(
+            parse_library_id("/vercel/next.js/v14.3.0"),
+            ("/vercel/next.js".to_string(), Some("v14.3.0".to_string()))
+        );
+        assert_eq!(parse_library_id("/tokio-rs/tokio"), ("/tokio-rs/tokio".to_string(), None));
+        assert_eq!(
+            parse_library_id("/tokio-rs/tokio@1.40"),
+            ("/tokio-rs/tokio".to_string(), Some("1.40".to_string()))
+        );
+    }
+
+    #[test]
+    fn key_and_hash_match_agent_script() {
+        let key = entry_key("/tokio-rs/tokio", None, " Select! ");
+        assert_eq!(key, "/tokio-rs/tokio@latest#select!");
+        assert_eq!(key_hash(&key), "ca1d8728");
+    }
+}
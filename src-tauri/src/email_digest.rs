@@ -0,0 +1,189 @@
+use lettre::{
+    message::{Mailbox, MultiPart},
+    transport::smtp::authentication::Credentials,
+    AsyncSmtpTransport, AsyncTransport, Message, Tokio1Executor,
+};
+
+use crate::export::{self, ShowNotes, TRANSCRIPT_HEADING};
+use crate::sessions::SessionInfo;
+use crate::settings::{self, EmailSettings};
+use crate::{keychain, log_line};
+
+fn password_account(username: &str) -> String {
+    format!("smtp:{}", username)
+}
+
+fn escape_html(text: &str) -> String {
+    text.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+}
+
+/// Digest body: session stats plus every show-notes section except the
+/// full transcript, which stays in the app.
+pub fn render_html(info: &SessionInfo, notes: &ShowNotes) -> String {
+    let minutes = info
+        .ended_at
+        .map(|ended| ended.saturating_sub(info.started_at) / 60_000)
+        .unwrap_or_default();
+    let mut html = format!(
+        "<!doctype html><html><body style=\"font-family:-apple-system,Segoe UI,sans-serif;line-height:1.5\">\
+         <h1 style=\"font-size:20px\">{}</h1><p style=\"color:#666\">{} min · {} transcript segments</p>",
+        escape_html(&notes.title),
+        minutes,
+        info.segment_count
+    );
+    for (heading, lines) in notes.sections.iter().filter(|(heading, _)| heading != TRANSCRIPT_HEADING) {
+        html.push_str(&format!("<h2 style=\"font-size:16px\">{}</h2><ul>", escape_html(heading)));
+        for line in lines {
+            html.push_str(&format!("<li>{}</li>", escape_html(line)));
+        }
+        html.push_str("</ul>");
+    }
+    html.push_str("<p style=\"color:#999;font-size:12px\">Sent by HeyJamie</p></body></html>");
+    html
+}
+
+fn render_text(notes: &ShowNotes) -> String {
+    ShowNotes {
+        title: notes.title.clone(),
+        sections: notes
+            .sections
+            .iter()
+            .filter(|(heading, _)| heading != TRANSCRIPT_HEADING)
+            .cloned()
+            .collect(),
+    }
+    .to_markdown()
+}
+
+async fn send_digest(config: &EmailSettings, info: &SessionInfo, notes: &ShowNotes, recipients: &[String]) -> Result<(), String> {
+    if config.smtp_host.trim().is_empty() || config.username.trim().is_empty() {
+        return Err("email is not configured; set an SMTP host and username in settings".to_string());
+    }
+    let password = keychain::get_secret(&password_account(&config.username))?
+        .ok_or_else(|| "no SMTP password saved; set one in settings".to_string())?;
+    let from: Mailbox = config
+        .from
+        .as_deref()
+        .unwrap_or(&config.username)
+        .parse()
+        .map_err(|err| format!("invalid sender address: {}", err))?;
+    let mut builder = Message::builder()
+        .from(from)
+        .subject(format!("Session recap: {}", notes.title));
+    for recipient in recipients {
+        let mailbox: Mailbox = recipient
+            .parse()
+            .map_err(|err| format!("invalid recipient {}: {}", recipient, err))?;
+        builder = builder.to(mailbox);
+    }
+    let message = builder
+        .multipart(MultiPart::alternative_plain_html(render_text(notes), render_html(info, notes)))
+        .map_err(|err| format!("failed to build email: {}", err))?;
+
+    let transport = if config.smtp_port == 465 {
+        AsyncSmtpTransport::<Tokio1Executor>::relay(&config.smtp_host)
+    } else {
+        AsyncSmtpTransport::<Tokio1Executor>::starttls_relay(&config.smtp_host)
+    }
+    .map_err(|err| format!("invalid SMTP host {}: {}", config.smtp_host, err))?
+    .port(config.smtp_port)
+    .credentials(Credentials::new(config.username.clone(), password))
+    .build();
+    transport
+        .send(message)
+        .await
+        .map_err(|err| format!("failed to send email: {}", err))?;
+    log_line(&format!("[email] sent recap of {} to {} recipient(s)", info.id, recipients.len()));
+    Ok(())
+}
+
+/// Mail the default recipients when `sendOnSessionEnd` is set. Runs in the
+/// background so ending a session never waits on SMTP.
+pub fn send_on_session_end(app: &tauri::AppHandle, info: &SessionInfo) {
+    let config = settings::load_settings(app).email;
+    if !config.send_on_session_end || config.default_recipients.is_empty() {
+        return;
+    }
+    let app = app.clone();
+    let info = info.clone();
+    tauri::async_runtime::spawn(async move {
+        let result = match export::session_show_notes(&app, &info) {
+            Ok(notes) => send_digest(&config, &info, &notes, &config.default_recipients).await,
+            Err(err) => Err(err),
+        };
+        if let Err(err) = result {
+            log_line(&format!("[email] automatic recap failed: {}", err));
+        }
+    });
+}
+
+/// Email a session recap. Empty `recipients` means the configured defaults.
+#[tauri::command]
+#[specta::specta]
+pub async fn email_session_summary(
+    app: tauri::AppHandle,
+    session_id: String,
+    recipients: Vec<String>,
+) -> Result<(), String> {
+    let config = settings::load_settings(&app).email;
+    let recipients = if recipients.is_empty() {
+        config.default_recipients.clone()
+    } else {
+        recipients
+    };
+    if recipients.is_empty() {
+        return Err("no recipients given and no default recipients configured".to_string());
+    }
+    let info = export::load_session(&app, &session_id)?;
+    let notes = export::session_show_notes(&app, &info)?;
+    send_digest(&config, &info, &notes, &recipients).await
+}
+
+/// Store the SMTP password for the configured username in the keychain.
+/// An empty password removes it.
+#[tauri::command]
+#[specta::specta]
+pub fn set_smtp_password(app: tauri::AppHandle, password: String) -> Result<(), String> {
+    let username = settings::load_settings(&app).email.username;
+    if username.trim().is_empty() {
+        return Err("set an SMTP username before saving a password".to_string());
+    }
+    if password.is_empty() {
+        keychain::delete_secret(&password_account(&username))
+    } else {
+        keychain::set_secret(&password_account(&username), &password)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::render_html;
+    use crate::export::ShowNotes;
+    use crate::sessions::SessionInfo;
+
+    #[test]
+    fn renders_escaped_sections_without_transcript() {
+        let info = SessionInfo {
+            id: "session-1".to_string(),
+            title: None,
+            started_at: 0,
+            ended_at: Some(30 * 60_000),
+            segment_count: 12,
+        };
+        let notes = ShowNotes {
+            title: "R&D <chat>".to_string(),
+            sections: vec![
+                ("Links".to_string(), vec!["https://a.example/?q=1&r=2".to_string()]),
+                ("Transcript".to_string(), vec!["[0:01] hello".to_string()]),
+            ],
+        };
+        let html = render_html(&info, &notes);
+        assert!(html.contains("R&amp;D &lt;chat&gt;"));
+        assert!(html.contains("30 min · 12 transcript segments"));
+        assert!(html.contains("q=1&amp;r=2"));
+        assert!(!html.contains("hello"));
+    }
+}
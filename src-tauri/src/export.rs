@@ -0,0 +1,413 @@
+use std::{fs, path::PathBuf};
+
+use serde::{Deserialize, Serialize};
+
+use crate::bookmarks::{self, Bookmark};
+use crate::captions::{self, CaptionFormat};
+use crate::sessions::{self, SessionInfo, TranscriptSegment};
+use crate::timecode::{self, SessionTimecode};
+use crate::{clock, google_export, log_line};
+
+/// A table of structured research output (e.g. a product comparison),
+/// stored per session in `research-tables.json`.
+#[derive(Serialize, Deserialize, Clone, specta::Type)]
+#[serde(rename_all = "camelCase")]
+pub struct ResearchTable {
+    pub title: String,
+    pub columns: Vec<String>,
+    pub rows: Vec<Vec<String>>,
+}
+
+#[derive(Serialize, Deserialize, Clone, Copy, Debug, PartialEq, Eq, specta::Type)]
+#[serde(rename_all = "kebab-case")]
+pub enum ExportTarget {
+    /// `show-notes.md` in the session's `exports/` folder.
+    Markdown,
+    /// Show notes as a new Google Doc.
+    GoogleDocs,
+    /// Research tables as a new Google Sheet (one tab per table).
+    GoogleSheets,
+}
+
+/// File formats for `export_transcript`.
+#[derive(Serialize, Deserialize, Clone, Copy, Debug, PartialEq, Eq, specta::Type)]
+#[serde(rename_all = "kebab-case")]
+pub enum TranscriptFormat {
+    Srt,
+    Vtt,
+    Json,
+    Markdown,
+}
+
+impl TranscriptFormat {
+    fn extension(self) -> &'static str {
+        match self {
+            TranscriptFormat::Srt => "srt",
+            TranscriptFormat::Vtt => "vtt",
+            TranscriptFormat::Json => "json",
+            TranscriptFormat::Markdown => "md",
+        }
+    }
+}
+
+/// A segment with its start and end, as written by the JSON export.
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+struct TimedSegment<'a> {
+    index: u32,
+    start_ms: u64,
+    end_ms: u64,
+    text: &'a str,
+    source: Option<&'a str>,
+}
+
+#[derive(Serialize, Clone, specta::Type)]
+#[serde(rename_all = "camelCase")]
+pub struct ExportResult {
+    pub target: ExportTarget,
+    /// File path or document URL.
+    pub location: String,
+}
+
+/// Show notes as a title plus headed sections of plain lines, so each
+/// adapter can render headings natively.
+pub struct ShowNotes {
+    pub title: String,
+    pub sections: Vec<(String, Vec<String>)>,
+}
+
+impl ShowNotes {
+    pub fn to_markdown(&self) -> String {
+        let mut out = format!("# {}\n", self.title);
+        for (heading, lines) in &self.sections {
+            out.push_str(&format!("\n## {}\n\n", heading));
+            for line in lines {
+                out.push_str(&format!("- {}\n", line));
+            }
+        }
+        out
+    }
+}
+
+pub const TRANSCRIPT_HEADING: &str = "Transcript";
+
+/// `h:mm:ss` (or `m:ss` under an hour) for a session offset.
+pub fn format_offset(offset_ms: u64) -> String {
+    let total = offset_ms / 1000;
+    let (h, m, s) = (total / 3600, (total % 3600) / 60, total % 60);
+    if h > 0 {
+        format!("{}:{:02}:{:02}", h, m, s)
+    } else {
+        format!("{}:{:02}", m, s)
+    }
+}
+
+/// Recorder timecode when the session was anchored to one, otherwise the
+/// session offset.
+fn format_moment(offset_ms: u64, timecode: Option<&SessionTimecode>) -> String {
+    match timecode {
+        Some(timecode) => timecode::timecode_at(timecode, offset_ms),
+        None => format_offset(offset_ms),
+    }
+}
+
+pub fn build_show_notes(
+    info: &SessionInfo,
+    bookmarks: &[Bookmark],
+    transcript: &[TranscriptSegment],
+    timecode: Option<&SessionTimecode>,
+) -> ShowNotes {
+    let title = info
+        .title
+        .clone()
+        .unwrap_or_else(|| format!("Show notes — {}", info.id));
+    let mut sections = Vec::new();
+
+    if info.started_at > 0 {
+        let started = clock::timestamp(info.started_at);
+        sections.push(("Recorded".to_string(), vec![format!("{} — {}", started.local, started.utc)]));
+    }
+
+    if !bookmarks.is_empty() {
+        let lines = bookmarks
+            .iter()
+            .map(|bookmark| {
+                let mut line = format!(
+                    "[{}] {}",
+                    format_moment(bookmark.offset_ms, timecode),
+                    bookmark
+                        .label
+                        .clone()
+                        .or_else(|| bookmark.transcript_excerpt.clone())
+                        .unwrap_or_else(|| "Bookmark".to_string())
+                );
+                if let Some(url) = &bookmark.browser_url {
+                    line.push_str(&format!(" — {}", url));
+                }
+                line
+            })
+            .collect();
+        sections.push(("Key moments".to_string(), lines));
+    }
+
+    let mut links: Vec<String> = bookmarks
+        .iter()
+        .filter_map(|bookmark| bookmark.browser_url.clone())
+        .collect();
+    links.dedup();
+    if !links.is_empty() {
+        sections.push(("Links".to_string(), links));
+    }
+
+    if !transcript.is_empty() {
+        let lines = transcript
+            .iter()
+            .map(|segment| {
+                format!(
+                    "[{}] {}",
+                    format_moment(segment.offset_ms, timecode),
+                    segment.attributed_text()
+                )
+            })
+            .collect();
+        sections.push((TRANSCRIPT_HEADING.to_string(), lines));
+    }
+
+    ShowNotes { title, sections }
+}
+
+pub fn read_research_tables(app: &tauri::AppHandle, session_id: &str) -> Result<Vec<ResearchTable>, String> {
+    let path = sessions::session_dir(app, session_id)?.join("research-tables.json");
+    Ok(fs::read_to_string(path)
+        .ok()
+        .and_then(|content| serde_json::from_str(&content).ok())
+        .unwrap_or_default())
+}
+
+pub fn load_session(app: &tauri::AppHandle, session_id: &str) -> Result<SessionInfo, String> {
+    sessions::list_sessions(app.clone())?
+        .into_iter()
+        .find(|session| session.id == session_id)
+        .ok_or_else(|| format!("session not found: {}", session_id))
+}
+
+pub fn session_show_notes(app: &tauri::AppHandle, info: &SessionInfo) -> Result<ShowNotes, String> {
+    Ok(build_show_notes(
+        info,
+        &bookmarks::read_bookmarks(app, &info.id)?,
+        &sessions::read_transcript(app, &info.id)?,
+        timecode::session_timecode(app, &info.id).as_ref(),
+    ))
+}
+
+/// Write `content` to `file_name` in the session's `exports/` folder and
+/// return the path.
+pub fn write_export(app: &tauri::AppHandle, session_id: &str, file_name: &str, content: &str) -> Result<String, String> {
+    let dir = sessions::session_dir(app, session_id)?.join("exports");
+    fs::create_dir_all(&dir).map_err(|err| format!("failed to create exports dir: {}", err))?;
+    let path = dir.join(file_name);
+    fs::write(&path, content.as_bytes()).map_err(|err| format!("failed to write {}: {}", file_name, err))?;
+    Ok(path.display().to_string())
+}
+
+/// Segments are stored when they finish, so each starts where the
+/// previous one ended (capped like live captions).
+fn timed_segments(transcript: &[TranscriptSegment]) -> Vec<TimedSegment<'_>> {
+    let mut previous_end_ms = None;
+    transcript
+        .iter()
+        .map(|segment| {
+            let start_ms = captions::cue_start(previous_end_ms, segment.offset_ms);
+            previous_end_ms = Some(segment.offset_ms);
+            TimedSegment {
+                index: segment.index,
+                start_ms,
+                end_ms: segment.offset_ms,
+                text: segment.text.trim(),
+                source: segment.source.as_deref(),
+            }
+        })
+        .collect()
+}
+
+fn render_transcript(
+    info: &SessionInfo,
+    transcript: &[TranscriptSegment],
+    timecode: Option<&SessionTimecode>,
+    format: TranscriptFormat,
+) -> Result<String, String> {
+    let segments = timed_segments(transcript);
+    let captions = |caption_format: CaptionFormat| {
+        transcript
+            .iter()
+            .zip(&segments)
+            .enumerate()
+            .map(|(number, (segment, timed))| {
+                captions::render_cue(
+                    number as u32 + 1,
+                    timed.start_ms,
+                    timed.end_ms,
+                    &segment.attributed_text(),
+                    caption_format,
+                )
+            })
+            .collect::<String>()
+    };
+    Ok(match format {
+        TranscriptFormat::Srt => captions(CaptionFormat::Srt),
+        // Cue times stay session offsets; SRT has no room for the start
+        // instant, WebVTT gets it as a note.
+        TranscriptFormat::Vtt if info.started_at > 0 => format!(
+            "WEBVTT\n\nNOTE Recorded {}\n\n{}",
+            clock::utc_string(info.started_at),
+            captions(CaptionFormat::Vtt)
+        ),
+        TranscriptFormat::Vtt => format!("WEBVTT\n\n{}", captions(CaptionFormat::Vtt)),
+        TranscriptFormat::Json => serde_json::to_string_pretty(&segments)
+            .map_err(|err| format!("failed to format transcript: {}", err))?,
+        TranscriptFormat::Markdown => {
+            let title = info.title.clone().unwrap_or_else(|| format!("Transcript — {}", info.id));
+            let mut out = format!("# {}\n\n", title);
+            if info.started_at > 0 {
+                out.push_str(&format!("_Recorded {}_\n\n", clock::local_string(info.started_at)));
+            }
+            for segment in transcript {
+                out.push_str(&format!(
+                    "**[{}]** {}\n\n",
+                    format_moment(segment.offset_ms, timecode),
+                    segment.attributed_text()
+                ));
+            }
+            out
+        }
+    })
+}
+
+/// Write a session's stored transcript, with its timing, as SRT, WebVTT,
+/// JSON or Markdown. `destination` is a file, a folder that gets
+/// `<session id>.<ext>`, or empty for the session's `exports/` folder.
+/// Returns the path written.
+#[tauri::command]
+#[specta::specta]
+pub fn export_transcript(
+    app: tauri::AppHandle,
+    session_id: String,
+    format: TranscriptFormat,
+    destination: Option<String>,
+) -> Result<String, String> {
+    let info = load_session(&app, &session_id)?;
+    let transcript = sessions::read_transcript(&app, &session_id)?;
+    if transcript.is_empty() {
+        return Err("this session has no transcript to export".to_string());
+    }
+    let timecode = timecode::session_timecode(&app, &session_id);
+    let content = render_transcript(&info, &transcript, timecode.as_ref(), format)?;
+    let file_name = format!("transcript.{}", format.extension());
+    let destination = destination.map(|d| d.trim().to_string()).unwrap_or_default();
+    let location = if destination.is_empty() {
+        write_export(&app, &session_id, &file_name, &content)?
+    } else {
+        let mut path = PathBuf::from(&destination);
+        if path.is_dir() {
+            path = path.join(format!("{}.{}", session_id, format.extension()));
+        }
+        fs::write(&path, content.as_bytes()).map_err(|err| format!("failed to write {}: {}", path.display(), err))?;
+        path.display().to_string()
+    };
+    log_line(&format!("[export] {} transcript -> {:?}: {}", session_id, format, location));
+    Ok(location)
+}
+
+/// Save a research table with the active session so it can be exported.
+#[tauri::command]
+#[specta::specta]
+pub fn record_research_table(
+    app: tauri::AppHandle,
+    state: tauri::State<'_, sessions::SessionState>,
+    table: ResearchTable,
+) -> Result<(), String> {
+    let session_id = state
+        .active_id()
+        .ok_or_else(|| "no active session".to_string())?;
+    let mut tables = read_research_tables(&app, &session_id)?;
+    tables.push(table);
+    let content = serde_json::to_string_pretty(&tables)
+        .map_err(|err| format!("failed to format research tables: {}", err))?;
+    fs::write(
+        sessions::session_dir(&app, &session_id)?.join("research-tables.json"),
+        content.as_bytes(),
+    )
+    .map_err(|err| format!("failed to write research tables: {}", err))
+}
+
+#[tauri::command]
+#[specta::specta]
+pub async fn export_session(
+    app: tauri::AppHandle,
+    session_id: String,
+    target: ExportTarget,
+) -> Result<ExportResult, String> {
+    let info = load_session(&app, &session_id)?;
+    let location = match target {
+        ExportTarget::Markdown | ExportTarget::GoogleDocs => {
+            let notes = session_show_notes(&app, &info)?;
+            if target == ExportTarget::Markdown {
+                write_export(&app, &session_id, "show-notes.md", &notes.to_markdown())?
+            } else {
+                google_export::create_doc(&app, &notes).await?
+            }
+        }
+        ExportTarget::GoogleSheets => {
+            let tables = read_research_tables(&app, &session_id)?;
+            if tables.is_empty() {
+                return Err("this session has no research tables to export".to_string());
+            }
+            let title = info.title.clone().unwrap_or_else(|| info.id.clone());
+            google_export::create_sheet(&app, &format!("{} — research", title), &tables).await?
+        }
+    };
+    log_line(&format!("[export] {} -> {:?}: {}", session_id, target, location));
+    Ok(ExportResult { target, location })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{format_offset, render_transcript, TranscriptFormat};
+    use crate::sessions::{SessionInfo, TranscriptSegment};
+
+    #[test]
+    fn formats_offsets() {
+        assert_eq!(format_offset(0), "0:00");
+        assert_eq!(format_offset(65_000), "1:05");
+        assert_eq!(format_offset(3_725_000), "1:02:05");
+    }
+
+    #[test]
+    fn renders_transcripts_with_timing() {
+        let info = SessionInfo {
+            id: "s1".to_string(),
+            title: Some("Episode 4".to_string()),
+            started_at: 0,
+            ended_at: None,
+            segment_count: 2,
+        };
+        let segment = |index, offset_ms, text: &str, source: Option<&str>| TranscriptSegment {
+            index,
+            offset_ms,
+            text: text.to_string(),
+            source: source.map(str::to_string),
+        };
+        let transcript = vec![segment(0, 4_000, "Welcome back.", Some("Host")), segment(1, 9_500, "Thanks.", None)];
+
+        let srt = render_transcript(&info, &transcript, None, TranscriptFormat::Srt).unwrap();
+        assert!(srt.starts_with("1\n00:00:00,000 --> 00:00:04,000\nHost: Welcome back.\n\n2\n00:00:04,000 --> 00:00:09,500\n"));
+        let vtt = render_transcript(&info, &transcript, None, TranscriptFormat::Vtt).unwrap();
+        assert!(vtt.starts_with("WEBVTT\n\n1\n00:00:00.000 --> 00:00:04.000\n"));
+        let json = render_transcript(&info, &transcript, None, TranscriptFormat::Json).unwrap();
+        let parsed: serde_json::Value = serde_json::from_str(&json).unwrap();
+        assert_eq!(parsed[1]["startMs"], 4_000);
+        assert_eq!(parsed[0]["source"], "Host");
+        let markdown = render_transcript(&info, &transcript, None, TranscriptFormat::Markdown).unwrap();
+        assert_eq!(markdown, "# Episode 4\n\n**[0:04]** Host: Welcome back.\n\n**[0:09]** Thanks.\n\n");
+    }
+}
@@ -0,0 +1,369 @@
+use std::{
+    collections::HashMap,
+    sync::Mutex,
+    time::{Duration, Instant},
+};
+
+use serde::{Deserialize, Serialize};
+use tauri::Manager;
+use tauri_specta::Event;
+
+use crate::{background, log_line, power, trace};
+use crate::settings::{self, AppSettings, LlmEndpoint, RemoteWhisperServer};
+
+/// How long an endpoint that failed stays out of rotation before it is
+/// tried again without waiting for the next health probe.
+const UNHEALTHY_COOLDOWN: Duration = Duration::from_secs(60);
+const HEALTH_CHECK_INTERVAL: Duration = Duration::from_secs(30);
+const HEALTH_CHECK_TIMEOUT: Duration = Duration::from_secs(5);
+
+/// Label used in failover events when transcription falls through to the
+/// bundled CLI.
+pub const LOCAL_WHISPER_ENDPOINT: &str = "local-whisper-cli";
+
+#[derive(Serialize, Deserialize, Clone, Copy, PartialEq, Eq, Hash, Debug, specta::Type)]
+#[serde(rename_all = "kebab-case")]
+pub enum FailoverService {
+    Transcription,
+    Llm,
+}
+
+#[derive(Serialize, Clone, specta::Type)]
+#[serde(rename_all = "camelCase")]
+pub struct EndpointHealth {
+    pub url: String,
+    pub healthy: bool,
+    pub consecutive_failures: u32,
+    pub last_error: Option<String>,
+    #[serde(skip)]
+    last_failure_at: Option<Instant>,
+}
+
+/// Payload of the `endpoint-failover` event.
+#[derive(Serialize, Deserialize, Clone, specta::Type, tauri_specta::Event)]
+#[serde(rename_all = "camelCase")]
+pub struct EndpointFailover {
+    service: FailoverService,
+    from: Option<String>,
+    to: String,
+    reason: Option<String>,
+    /// Trace of the request that caused the switch, if any.
+    trace_id: Option<String>,
+}
+
+#[derive(Default)]
+struct FailoverInner {
+    health: HashMap<String, EndpointHealth>,
+    active: HashMap<FailoverService, String>,
+}
+
+/// Shared health table for every endpoint in the transcription and LLM
+/// failover chains.
+#[derive(Default)]
+pub struct FailoverState {
+    inner: Mutex<FailoverInner>,
+}
+
+impl FailoverState {
+    /// An endpoint is usable if it has never failed, is marked healthy, or its
+    /// cooldown has elapsed.
+    pub fn is_available(&self, url: &str) -> bool {
+        let inner = self.inner.lock().unwrap();
+        match inner.health.get(url) {
+            None => true,
+            Some(health) if health.healthy => true,
+            Some(health) => health
+                .last_failure_at
+                .map(|at| at.elapsed() >= UNHEALTHY_COOLDOWN)
+                .unwrap_or(true),
+        }
+    }
+
+    pub fn record_success(&self, url: &str) {
+        let mut inner = self.inner.lock().unwrap();
+        let entry = inner
+            .health
+            .entry(url.to_string())
+            .or_insert_with(|| new_health(url));
+        if !entry.healthy {
+            log_line(&format!("[failover] {} is healthy again", url));
+        }
+        entry.healthy = true;
+        entry.consecutive_failures = 0;
+        entry.last_error = None;
+        entry.last_failure_at = None;
+    }
+
+    pub fn record_failure(&self, url: &str, error: &str) {
+        let mut inner = self.inner.lock().unwrap();
+        let entry = inner
+            .health
+            .entry(url.to_string())
+            .or_insert_with(|| new_health(url));
+        if entry.healthy {
+            log_line(&format!("[failover] {} marked unhealthy: {}", url, error));
+        }
+        entry.healthy = false;
+        entry.consecutive_failures = entry.consecutive_failures.saturating_add(1);
+        entry.last_error = Some(error.to_string());
+        entry.last_failure_at = Some(Instant::now());
+    }
+
+    /// Remember which endpoint is serving `service` and emit
+    /// `endpoint-failover` when it differs from the previous one.
+    pub fn note_active(
+        &self,
+        app: &tauri::AppHandle,
+        service: FailoverService,
+        endpoint: &str,
+        reason: Option<String>,
+    ) {
+        let previous = {
+            let mut inner = self.inner.lock().unwrap();
+            inner.active.insert(service, endpoint.to_string())
+        };
+        if previous.as_deref() == Some(endpoint) {
+            return;
+        }
+        // The first selection is not a switch; only announce real changes.
+        if previous.is_none() && reason.is_none() {
+            return;
+        }
+        log_line(&format!(
+            "[failover] {:?} switched {} -> {}",
+            service,
+            previous.as_deref().unwrap_or("(none)"),
+            endpoint
+        ));
+        let _ = EndpointFailover {
+            service,
+            from: previous,
+            to: endpoint.to_string(),
+            reason,
+            trace_id: trace::current(),
+        }
+        .emit(app);
+    }
+
+    fn snapshot(&self) -> Vec<EndpointHealth> {
+        let inner = self.inner.lock().unwrap();
+        let mut entries: Vec<EndpointHealth> = inner.health.values().cloned().collect();
+        entries.sort_by(|a, b| a.url.cmp(&b.url));
+        entries
+    }
+}
+
+fn new_health(url: &str) -> EndpointHealth {
+    EndpointHealth {
+        url: url.to_string(),
+        healthy: true,
+        consecutive_failures: 0,
+        last_error: None,
+        last_failure_at: None,
+    }
+}
+
+/// Enabled remote whisper servers in the order they should be tried.
+pub fn transcription_chain(settings: &AppSettings) -> Vec<&RemoteWhisperServer> {
+    settings
+        .transcription
+        .remote_server
+        .iter()
+        .chain(settings.transcription.fallback_servers.iter())
+        .filter(|server| server.enabled && !server.url.trim().is_empty())
+        .collect()
+}
+
+/// First LLM endpoint that is currently available, skipping the base URLs
+/// in `failed` (the ones a run already tried). Falls back to the head of
+/// the remaining chain when everything is marked unhealthy so runs are
+/// never blocked purely on stale health data; `None` once every endpoint
+/// was tried.
+pub fn select_llm_endpoint(state: &FailoverState, settings: &AppSettings, failed: &[String]) -> Option<LlmEndpoint> {
+    let endpoints: Vec<&LlmEndpoint> = settings
+        .llm
+        .endpoints
+        .iter()
+        .filter(|endpoint| !endpoint.base_url.trim().is_empty())
+        .filter(|endpoint| !failed.iter().any(|url| url == endpoint.base_url.trim()))
+        .collect();
+    endpoints
+        .iter()
+        .find(|endpoint| state.is_available(endpoint.base_url.trim()))
+        .or_else(|| endpoints.first())
+        .map(|endpoint| (*endpoint).clone())
+}
+
+/// Heuristic for agent failures caused by the provider endpoint itself
+/// (as opposed to tool or prompt failures), which should trigger failover.
+pub fn looks_like_endpoint_failure(error: &str) -> bool {
+    let lowered = error.to_lowercase();
+    [
+        "fetch failed",
+        "econnrefused",
+        "econnreset",
+        "enotfound",
+        "etimedout",
+        "socket hang up",
+        "service unavailable",
+        "bad gateway",
+        "gateway timeout",
+        " 502",
+        " 503",
+        " 504",
+    ]
+    .iter()
+    .any(|pattern| lowered.contains(pattern))
+}
+
+fn health_url(base: &str, path: &str) -> String {
+    let base = base.trim().trim_end_matches('/');
+    let path = path.trim();
+    if path.is_empty() || path == "/" {
+        return format!("{}/", base);
+    }
+    format!("{}/{}", base, path.trim_start_matches('/'))
+}
+
+fn health_targets(settings: &AppSettings) -> Vec<(String, String)> {
+    let mut targets = Vec::new();
+    for server in transcription_chain(settings) {
+        let path = server.health_path.clone().unwrap_or_else(|| "/".to_string());
+        targets.push((server.url.trim().to_string(), health_url(&server.url, &path)));
+    }
+    for endpoint in &settings.llm.endpoints {
+        if endpoint.base_url.trim().is_empty() {
+            continue;
+        }
+        let path = endpoint
+            .health_path
+            .clone()
+            .unwrap_or_else(|| "/models".to_string());
+        targets.push((
+            endpoint.base_url.trim().to_string(),
+            health_url(&endpoint.base_url, &path),
+        ));
+    }
+    targets
+}
+
+/// Probe every configured endpoint on a fixed interval. Any HTTP response
+/// below 500 counts as healthy — we only care that the server is reachable.
+pub fn spawn_health_checks(app: tauri::AppHandle) {
+    tauri::async_runtime::spawn(async move {
+        let client = match reqwest::Client::builder()
+            .timeout(HEALTH_CHECK_TIMEOUT)
+            .build()
+        {
+            Ok(c) => c,
+            Err(err) => {
+                log_line(&format!("[failover] health checks disabled: {}", err));
+                return;
+            }
+        };
+        loop {
+            if power::current(&app).health_checks_paused {
+                tokio::time::sleep(HEALTH_CHECK_INTERVAL).await;
+                continue;
+            }
+            background::wait_for_quiet(&app, "endpoint health checks", HEALTH_CHECK_INTERVAL).await;
+            let settings = settings::load_settings(&app);
+            let state = app.state::<FailoverState>();
+            for (key, url) in health_targets(&settings) {
+                match client.get(&url).send().await {
+                    Ok(response) if !response.status().is_server_error() => {
+                        state.record_success(&key);
+                    }
+                    Ok(response) => {
+                        state.record_failure(&key, &format!("health check HTTP {}", response.status()));
+                    }
+                    Err(err) => {
+                        state.record_failure(&key, &format!("health check failed: {}", err));
+                    }
+                }
+            }
+            tokio::time::sleep(HEALTH_CHECK_INTERVAL).await;
+        }
+    });
+}
+
+#[tauri::command]
+#[specta::specta]
+pub fn get_endpoint_health(state: tauri::State<'_, FailoverState>) -> Vec<EndpointHealth> {
+    state.snapshot()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{health_url, looks_like_endpoint_failure, select_llm_endpoint, FailoverState, UNHEALTHY_COOLDOWN};
+    use crate::settings::{AppSettings, LlmEndpoint};
+    use std::time::Instant;
+
+    fn settings_with(urls: &[&str]) -> AppSettings {
+        let mut settings = AppSettings::default();
+        settings.llm.endpoints = urls
+            .iter()
+            .map(|url| LlmEndpoint {
+                name: url.to_string(),
+                base_url: url.to_string(),
+                ..LlmEndpoint::default()
+            })
+            .collect();
+        settings
+    }
+
+    #[test]
+    fn failed_endpoints_come_back_after_the_cooldown() {
+        let state = FailoverState::default();
+        assert!(state.is_available("http://a"));
+        state.record_failure("http://a", "fetch failed");
+        assert!(!state.is_available("http://a"));
+
+        let expired = Instant::now().checked_sub(UNHEALTHY_COOLDOWN).unwrap();
+        state.inner.lock().unwrap().health.get_mut("http://a").unwrap().last_failure_at = Some(expired);
+        assert!(state.is_available("http://a"));
+
+        state.record_failure("http://a", "fetch failed");
+        state.record_success("http://a");
+        assert!(state.is_available("http://a"));
+    }
+
+    #[test]
+    fn selects_endpoints_in_order_and_skips_failed_ones() {
+        let state = FailoverState::default();
+        let settings = settings_with(&["http://a", " ", "http://b", "http://c"]);
+        let pick = |failed: &[String]| select_llm_endpoint(&state, &settings, failed).map(|e| e.base_url);
+
+        assert_eq!(pick(&[]).as_deref(), Some("http://a"));
+        state.record_failure("http://a", "fetch failed");
+        assert_eq!(pick(&[]).as_deref(), Some("http://b"));
+
+        // Everything unhealthy: the head of the chain is tried anyway.
+        state.record_failure("http://b", "fetch failed");
+        state.record_failure("http://c", "fetch failed");
+        assert_eq!(pick(&[]).as_deref(), Some("http://a"));
+
+        // A run moves past the endpoints it already tried, then gives up.
+        assert_eq!(pick(&["http://a".to_string()]).as_deref(), Some("http://b"));
+        let all = ["http://a", "http://b", "http://c"].map(str::to_string);
+        assert_eq!(pick(&all), None);
+        assert_eq!(select_llm_endpoint(&state, &AppSettings::default(), &[]).map(|e| e.base_url), None);
+    }
+
+    #[test]
+    fn joins_health_paths() {
+        assert_eq!(health_url("http://host:8080/", ""), "http://host:8080/");
+        assert_eq!(health_url("http://host:8080", "/"), "http://host:8080/");
+        assert_eq!(health_url("http://host/v1/", "/models"), "http://host/v1/models");
+        assert_eq!(health_url(" http://host/v1 ", "health"), "http://host/v1/health");
+    }
+
+    #[test]
+    fn recognizes_endpoint_failures() {
+        assert!(looks_like_endpoint_failure("TypeError: fetch failed"));
+        assert!(looks_like_endpoint_failure("connect ECONNREFUSED 127.0.0.1:11434"));
+        assert!(looks_like_endpoint_failure("Request failed with status 503"));
+        assert!(!looks_like_endpoint_failure("tool excalidraw_create failed: invalid element"));
+        assert!(!looks_like_endpoint_failure("context length exceeded"));
+    }
+}
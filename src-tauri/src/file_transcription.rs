@@ -0,0 +1,230 @@
+use std::collections::VecDeque;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+
+use serde::{Deserialize, Serialize};
+use tauri::Manager;
+use tauri_specta::Event;
+
+use crate::jobs::{self, JobSpec};
+use crate::{audio_decode, log_line, long_form};
+
+/// Files picked up when a folder is queued.
+const AUDIO_EXTENSIONS: &[&str] = &["wav", "mp3", "m4a", "aac", "ogg", "oga", "opus", "webm", "flac"];
+/// Local whisper already uses several threads per file.
+const MAX_PARALLEL: u32 = 4;
+
+#[derive(Serialize, Deserialize, Clone, Copy, Debug, PartialEq, Eq, specta::Type)]
+#[serde(rename_all = "camelCase")]
+pub enum FileTranscriptionStatus {
+    Running,
+    Done,
+    Failed,
+    /// Never started because the batch was cancelled.
+    Skipped,
+}
+
+/// Emitted to the main window as each queued file starts and finishes.
+#[derive(Serialize, Deserialize, Clone, Debug, specta::Type, tauri_specta::Event)]
+#[serde(rename_all = "camelCase")]
+pub struct FileTranscription {
+    pub path: String,
+    pub index: u32,
+    pub total: u32,
+    pub status: FileTranscriptionStatus,
+    /// `<name>.transcript.txt` next to the audio, once done.
+    pub transcript_path: Option<String>,
+    pub error: Option<String>,
+}
+
+/// Cancel flag of the batch being transcribed, if any.
+#[derive(Default)]
+pub struct FileTranscriptionState {
+    running: Mutex<Option<Arc<AtomicBool>>>,
+}
+
+fn is_audio(path: &Path) -> bool {
+    path.extension()
+        .and_then(|ext| ext.to_str())
+        .is_some_and(|ext| AUDIO_EXTENSIONS.contains(&ext.to_ascii_lowercase().as_str()))
+}
+
+/// Files as given plus the audio files directly inside any folders, in
+/// name order.
+fn expand(paths: &[String]) -> Result<Vec<PathBuf>, String> {
+    let mut files = Vec::new();
+    for path in paths.iter().map(PathBuf::from) {
+        if path.is_dir() {
+            let mut entries: Vec<PathBuf> = fs::read_dir(&path)
+                .map_err(|err| format!("failed to read {}: {}", path.display(), err))?
+                .filter_map(|entry| entry.ok().map(|entry| entry.path()))
+                .filter(|entry| entry.is_file() && is_audio(entry))
+                .collect();
+            entries.sort();
+            files.extend(entries);
+        } else if path.is_file() {
+            files.push(path);
+        } else {
+            return Err(format!("no such file: {}", path.display()));
+        }
+    }
+    Ok(files)
+}
+
+fn transcript_path(audio: &Path) -> PathBuf {
+    audio.with_extension("transcript.txt")
+}
+
+/// Transcribe `path` as a resumable job: a failed or interrupted attempt
+/// picks up after the last window it finished.
+async fn transcribe_file(app: &tauri::AppHandle, path: &Path) -> Result<PathBuf, String> {
+    let spec = JobSpec::FileTranscription {
+        path: path.display().to_string(),
+    };
+    let (job_id, resume) = match jobs::start_logged::<long_form::Checkpoint>(app, spec) {
+        Some((job_id, resume)) => (Some(job_id), resume),
+        None => (None, None),
+    };
+    let result = transcribe_file_from(app, path, job_id.as_deref(), resume).await;
+    if let Some(job_id) = &job_id {
+        jobs::finish(app, job_id, &result);
+    }
+    result
+}
+
+async fn transcribe_file_from(
+    app: &tauri::AppHandle,
+    path: &Path,
+    job_id: Option<&str>,
+    resume: Option<long_form::Checkpoint>,
+) -> Result<PathBuf, String> {
+    let bytes = fs::read(path).map_err(|err| format!("failed to read {}: {}", path.display(), err))?;
+    let wav_bytes = tauri::async_runtime::spawn_blocking(move || audio_decode::to_wav(bytes))
+        .await
+        .map_err(|err| format!("audio decode task failed: {}", err))??;
+    let text = long_form::transcribe_resumable(app, wav_bytes, None, job_id, resume).await?;
+    let output = transcript_path(path);
+    fs::write(&output, format!("{}\n", text.trim()))
+        .map_err(|err| format!("failed to write {}: {}", output.display(), err))?;
+    Ok(output)
+}
+
+async fn worker(
+    app: tauri::AppHandle,
+    queue: Arc<Mutex<VecDeque<(u32, PathBuf)>>>,
+    total: u32,
+    cancel: Arc<AtomicBool>,
+) {
+    let notify = |progress: FileTranscription| {
+        if let Err(err) = progress.emit_to(&app, "main") {
+            log_line(&format!("[file-transcription] failed to notify main window: {}", err));
+        }
+    };
+    loop {
+        let Some((index, path)) = queue.lock().unwrap().pop_front() else {
+            break;
+        };
+        let progress = |status, transcript_path: Option<&Path>, error| FileTranscription {
+            path: path.display().to_string(),
+            index,
+            total,
+            status,
+            transcript_path: transcript_path.map(|p| p.display().to_string()),
+            error,
+        };
+        if cancel.load(Ordering::SeqCst) {
+            notify(progress(FileTranscriptionStatus::Skipped, None, None));
+            continue;
+        }
+        notify(progress(FileTranscriptionStatus::Running, None, None));
+        match transcribe_file(&app, &path).await {
+            Ok(output) => notify(progress(FileTranscriptionStatus::Done, Some(&output), None)),
+            Err(err) => {
+                log_line(&format!("[file-transcription] {} failed: {}", path.display(), err));
+                notify(progress(FileTranscriptionStatus::Failed, None, Some(err)));
+            }
+        }
+    }
+}
+
+/// Transcribe local audio files, or every audio file in a folder, without
+/// the microphone path. Each transcript is written next to its file as
+/// `<name>.transcript.txt`, and `file-transcription` events report
+/// progress. `parallel` files (1 to 4, default 1) are worked on at once.
+/// Returns how many files were queued.
+#[tauri::command]
+#[specta::specta]
+pub fn transcribe_files(app: tauri::AppHandle, paths: Vec<String>, parallel: Option<u32>) -> Result<u32, String> {
+    let files = expand(&paths)?;
+    if files.is_empty() {
+        return Err("no audio files to transcribe".to_string());
+    }
+    let cancel = Arc::new(AtomicBool::new(false));
+    {
+        let state = app.state::<FileTranscriptionState>();
+        let mut running = state.running.lock().unwrap();
+        if running.is_some() {
+            return Err("files are already being transcribed".to_string());
+        }
+        *running = Some(cancel.clone());
+    }
+    let total = files.len() as u32;
+    let queue = Arc::new(Mutex::new((0..total).zip(files).collect::<VecDeque<_>>()));
+    let workers = parallel.unwrap_or(1).clamp(1, MAX_PARALLEL).min(total);
+    log_line(&format!("[file-transcription] queued {} files ({} at a time)", total, workers));
+    tauri::async_runtime::spawn(async move {
+        let handles: Vec<_> = (0..workers)
+            .map(|_| tauri::async_runtime::spawn(worker(app.clone(), queue.clone(), total, cancel.clone())))
+            .collect();
+        for handle in handles {
+            let _ = handle.await;
+        }
+        *app.state::<FileTranscriptionState>().running.lock().unwrap() = None;
+        log_line("[file-transcription] batch finished");
+    });
+    Ok(total)
+}
+
+/// Let the files already being transcribed finish and skip the rest.
+#[tauri::command]
+#[specta::specta]
+pub fn cancel_file_transcription(app: tauri::AppHandle) {
+    let state = app.state::<FileTranscriptionState>();
+    let running = state.running.lock().unwrap();
+    if let Some(cancel) = running.as_ref() {
+        cancel.store(true, Ordering::SeqCst);
+    }
+}
+
+/// Cancel the batch and wait for the files in progress, for quitting.
+pub fn cancel_and_wait(app: &tauri::AppHandle, timeout: Duration) {
+    cancel_file_transcription(app.clone());
+    let state = app.state::<FileTranscriptionState>();
+    crate::shutdown::wait_until(timeout, || state.running.lock().unwrap().is_none());
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{expand, transcript_path};
+    use std::fs;
+    use std::path::Path;
+
+    #[test]
+    fn expands_folders_to_their_audio_files() {
+        let dir = std::env::temp_dir().join(format!("heyjamie-batch-{}", std::process::id()));
+        fs::create_dir_all(&dir).unwrap();
+        for name in ["b.mp3", "a.WAV", "notes.txt"] {
+            fs::write(dir.join(name), b"").unwrap();
+        }
+        let files = expand(&[dir.display().to_string()]).unwrap();
+        let names: Vec<_> = files.iter().map(|f| f.file_name().unwrap().to_str().unwrap()).collect();
+        assert_eq!(names, vec!["a.WAV", "b.mp3"]);
+        assert!(expand(&[dir.join("missing.wav").display().to_string()]).is_err());
+        fs::remove_dir_all(&dir).unwrap();
+
+        assert_eq!(transcript_path(Path::new("/x/ep1.mp3")), Path::new("/x/ep1.transcript.txt"));
+    }
+}
@@ -0,0 +1,176 @@
+//! Mic gating by application focus: with `transcription.focusGate` on,
+//! microphone audio is only processed while one of the listed apps (the
+//! recording software, a browser) or HeyJamie itself is frontmost, so
+//! speech on an unrelated call in another app is ignored. The frontmost
+//! app is polled; when it can't be read (Wayland, say) the gate stays open.
+
+use std::sync::Mutex;
+use std::time::Duration;
+
+use serde::{Deserialize, Serialize};
+use tauri::Manager;
+use tauri_specta::Event;
+
+use crate::log_line;
+use crate::settings::{self, FocusGateSettings};
+
+const FOCUS_CHECK_INTERVAL: Duration = Duration::from_secs(1);
+
+/// HeyJamie's own window, which always keeps the gate open.
+const OWN_APPS: &[&str] = &["heyjamie", "tauri-app"];
+
+/// Emitted to the main window as `mic-gate` when the gate opens or closes.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, specta::Type, tauri_specta::Event)]
+#[serde(rename_all = "camelCase")]
+pub struct MicGate {
+    /// Microphone audio is processed.
+    pub open: bool,
+    /// Name of the frontmost app, when known.
+    pub frontmost: Option<String>,
+}
+
+impl Default for MicGate {
+    fn default() -> Self {
+        Self {
+            open: true,
+            frontmost: None,
+        }
+    }
+}
+
+#[derive(Default)]
+pub struct FocusGateState {
+    current: Mutex<MicGate>,
+}
+
+/// Whether `frontmost` may feed the microphone into transcription.
+pub fn gate_open(settings: &FocusGateSettings, frontmost: Option<&str>) -> bool {
+    let Some(frontmost) = frontmost.map(str::to_lowercase) else {
+        return true;
+    };
+    !settings.enabled
+        || OWN_APPS.iter().any(|own| frontmost.contains(own))
+        || settings
+            .apps
+            .iter()
+            .map(|app| app.trim().to_lowercase())
+            .any(|app| !app.is_empty() && frontmost.contains(&app))
+}
+
+/// The app name in `lsappinfo info -only name` output
+/// (`"LSDisplayName"="Safari"`).
+#[cfg_attr(not(target_os = "macos"), allow(dead_code))]
+pub fn parse_lsappinfo_name(output: &str) -> Option<String> {
+    let (_, value) = output.trim().split_once('=')?;
+    Some(value.trim().trim_matches('"').to_string()).filter(|name| !name.is_empty())
+}
+
+#[cfg(target_os = "macos")]
+fn frontmost_app() -> Option<String> {
+    let run = |args: &[&str]| {
+        std::process::Command::new("lsappinfo")
+            .args(args)
+            .output()
+            .ok()
+            .filter(|output| output.status.success())
+            .map(|output| String::from_utf8_lossy(&output.stdout).trim().to_string())
+    };
+    let front = run(&["front"])?;
+    parse_lsappinfo_name(&run(&["info", "-only", "name", &front])?)
+}
+
+#[cfg(target_os = "windows")]
+fn frontmost_app() -> Option<String> {
+    const SCRIPT: &str = "Add-Type 'using System;using System.Runtime.InteropServices;\
+public class W{[DllImport(\"user32.dll\")]public static extern IntPtr GetForegroundWindow();\
+[DllImport(\"user32.dll\")]public static extern int GetWindowThreadProcessId(IntPtr h,out int p);}';\
+$p=0;[void][W]::GetWindowThreadProcessId([W]::GetForegroundWindow(),[ref]$p);(Get-Process -Id $p).ProcessName";
+    let output = std::process::Command::new("powershell")
+        .args(["-NoProfile", "-NonInteractive", "-Command", SCRIPT])
+        .output()
+        .ok()?;
+    Some(String::from_utf8_lossy(&output.stdout).trim().to_string()).filter(|name| !name.is_empty())
+}
+
+/// X11 only; Wayland compositors don't tell other clients what is focused.
+#[cfg(target_os = "linux")]
+fn frontmost_app() -> Option<String> {
+    let output = std::process::Command::new("xdotool")
+        .args(["getactivewindow", "getwindowpid"])
+        .output()
+        .ok()
+        .filter(|output| output.status.success())?;
+    let pid = String::from_utf8_lossy(&output.stdout).trim().to_string();
+    let comm = std::fs::read_to_string(format!("/proc/{}/comm", pid)).ok()?;
+    Some(comm.trim().to_string()).filter(|name| !name.is_empty())
+}
+
+#[cfg(not(any(target_os = "windows", target_os = "linux", target_os = "macos")))]
+fn frontmost_app() -> Option<String> {
+    None
+}
+
+fn refresh(app: &tauri::AppHandle) {
+    let settings = settings::load_settings(app).transcription.focus_gate;
+    let frontmost = if settings.enabled { frontmost_app() } else { None };
+    let gate = MicGate {
+        open: gate_open(&settings, frontmost.as_deref()),
+        frontmost,
+    };
+    let previous = std::mem::replace(&mut *app.state::<FocusGateState>().current.lock().unwrap(), gate.clone());
+    if previous.open == gate.open {
+        return;
+    }
+    log_line(&format!(
+        "[focus-gate] mic {} ({})",
+        if gate.open { "open" } else { "gated" },
+        gate.frontmost.as_deref().unwrap_or("unknown app")
+    ));
+    if let Err(err) = gate.emit_to(app, "main") {
+        log_line(&format!("[focus-gate] failed to notify main window: {}", err));
+    }
+}
+
+/// Whether microphone audio should be processed right now.
+pub fn is_open(app: &tauri::AppHandle) -> bool {
+    app.state::<FocusGateState>().current.lock().unwrap().open
+}
+
+/// Watch which app is frontmost.
+pub fn spawn_monitor(app: tauri::AppHandle) {
+    tauri::async_runtime::spawn(async move {
+        loop {
+            let handle = app.clone();
+            let _ = tauri::async_runtime::spawn_blocking(move || refresh(&handle)).await;
+            tokio::time::sleep(FOCUS_CHECK_INTERVAL).await;
+        }
+    });
+}
+
+#[tauri::command]
+#[specta::specta]
+pub fn get_mic_gate(app: tauri::AppHandle) -> MicGate {
+    app.state::<FocusGateState>().current.lock().unwrap().clone()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{gate_open, parse_lsappinfo_name};
+    use crate::settings::FocusGateSettings;
+
+    #[test]
+    fn only_listed_apps_open_the_gate() {
+        let settings = FocusGateSettings {
+            enabled: true,
+            apps: vec!["Riverside".to_string(), "chrome".to_string(), " ".to_string()],
+        };
+        assert!(gate_open(&settings, Some("Google Chrome")));
+        assert!(gate_open(&settings, Some("heyjamie")));
+        assert!(!gate_open(&settings, Some("zoom.us")));
+        assert!(gate_open(&settings, None));
+        assert!(gate_open(&FocusGateSettings::default(), Some("zoom.us")));
+
+        assert_eq!(parse_lsappinfo_name("\"LSDisplayName\"=\"Safari\"\n").as_deref(), Some("Safari"));
+        assert_eq!(parse_lsappinfo_name(""), None);
+    }
+}
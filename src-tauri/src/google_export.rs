@@ -0,0 +1,163 @@
+use serde_json::{json, Value as JsonValue};
+
+use crate::auth;
+use crate::export::{ResearchTable, ShowNotes};
+
+const DOCS_API: &str = "https://docs.googleapis.com/v1/documents";
+const SHEETS_API: &str = "https://sheets.googleapis.com/v4/spreadsheets";
+
+/// POST JSON with the connected Google account's token and parse the reply.
+async fn post_json(app: &tauri::AppHandle, url: &str, body: &JsonValue) -> Result<JsonValue, String> {
+    let token = auth::access_token(app, "google").await?;
+    let response = auth::http_client()?
+        .post(url)
+        .bearer_auth(token)
+        .header("Content-Type", "application/json")
+        .body(body.to_string())
+        .send()
+        .await
+        .map_err(|err| format!("request to {} failed: {}", url, err))?;
+    let status = response.status();
+    let text = response
+        .text()
+        .await
+        .map_err(|err| format!("failed to read Google response: {}", err))?;
+    if !status.is_success() {
+        let message = serde_json::from_str::<JsonValue>(&text)
+            .ok()
+            .and_then(|v| v.pointer("/error/message").and_then(|m| m.as_str()).map(str::to_string))
+            .unwrap_or(text);
+        return Err(format!("Google API error ({}): {}", status, message));
+    }
+    serde_json::from_str(&text).map_err(|err| format!("invalid Google response: {}", err))
+}
+
+/// Document text plus `batchUpdate` requests that style the title and
+/// section headings. Docs indexes count UTF-16 units from 1.
+fn doc_requests(notes: &ShowNotes) -> Vec<JsonValue> {
+    let mut text = String::new();
+    let mut headings = Vec::new();
+    let mut push_line = |line: &str, style: Option<&str>, text: &mut String| {
+        let start = 1 + text.encode_utf16().count();
+        text.push_str(line);
+        text.push('\n');
+        if let Some(style) = style {
+            headings.push((start, 1 + text.encode_utf16().count(), style.to_string()));
+        }
+    };
+    push_line(&notes.title, Some("TITLE"), &mut text);
+    for (heading, lines) in &notes.sections {
+        push_line(heading, Some("HEADING_2"), &mut text);
+        for line in lines {
+            push_line(line, None, &mut text);
+        }
+    }
+
+    let mut requests = vec![json!({ "insertText": { "location": { "index": 1 }, "text": text } })];
+    for (start, end, style) in headings {
+        requests.push(json!({
+            "updateParagraphStyle": {
+                "range": { "startIndex": start, "endIndex": end },
+                "paragraphStyle": { "namedStyleType": style },
+                "fields": "namedStyleType"
+            }
+        }));
+    }
+    requests
+}
+
+/// Create a Google Doc with the show notes and return its URL.
+pub async fn create_doc(app: &tauri::AppHandle, notes: &ShowNotes) -> Result<String, String> {
+    let created = post_json(app, DOCS_API, &json!({ "title": notes.title })).await?;
+    let document_id = created
+        .get("documentId")
+        .and_then(|v| v.as_str())
+        .ok_or_else(|| "Google Docs did not return a document id".to_string())?;
+    post_json(
+        app,
+        &format!("{}/{}:batchUpdate", DOCS_API, document_id),
+        &json!({ "requests": doc_requests(notes) }),
+    )
+    .await?;
+    Ok(format!("https://docs.google.com/document/d/{}/edit", document_id))
+}
+
+/// Sheet tab names can't contain `[]*?/\:` and are capped at 100 chars.
+fn sheet_title(title: &str, index: usize) -> String {
+    let cleaned: String = title
+        .chars()
+        .filter(|c| !"[]*?/\\:".contains(*c))
+        .take(100)
+        .collect();
+    if cleaned.trim().is_empty() {
+        format!("Table {}", index + 1)
+    } else {
+        cleaned.trim().to_string()
+    }
+}
+
+/// Create a spreadsheet with one tab per research table and return its URL.
+pub async fn create_sheet(app: &tauri::AppHandle, title: &str, tables: &[ResearchTable]) -> Result<String, String> {
+    let mut titles: Vec<String> = Vec::new();
+    for (index, table) in tables.iter().enumerate() {
+        let mut name = sheet_title(&table.title, index);
+        if titles.contains(&name) {
+            name = format!("{} ({})", name, index + 1);
+        }
+        titles.push(name);
+    }
+    let sheets: Vec<JsonValue> = titles
+        .iter()
+        .map(|name| json!({ "properties": { "title": name } }))
+        .collect();
+    let created = post_json(
+        app,
+        SHEETS_API,
+        &json!({ "properties": { "title": title }, "sheets": sheets }),
+    )
+    .await?;
+    let spreadsheet_id = created
+        .get("spreadsheetId")
+        .and_then(|v| v.as_str())
+        .ok_or_else(|| "Google Sheets did not return a spreadsheet id".to_string())?;
+
+    let data: Vec<JsonValue> = tables
+        .iter()
+        .zip(&titles)
+        .map(|(table, name)| {
+            let mut values = vec![table.columns.clone()];
+            values.extend(table.rows.iter().cloned());
+            json!({ "range": format!("'{}'!A1", name.replace('\'', "''")), "values": values })
+        })
+        .collect();
+    post_json(
+        app,
+        &format!("{}/{}/values:batchUpdate", SHEETS_API, spreadsheet_id),
+        &json!({ "valueInputOption": "RAW", "data": data }),
+    )
+    .await?;
+    Ok(created
+        .get("spreadsheetUrl")
+        .and_then(|v| v.as_str())
+        .map(str::to_string)
+        .unwrap_or_else(|| format!("https://docs.google.com/spreadsheets/d/{}/edit", spreadsheet_id)))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::doc_requests;
+    use crate::export::ShowNotes;
+
+    #[test]
+    fn styles_headings_by_utf16_range() {
+        let notes = ShowNotes {
+            title: "Ép 1".to_string(),
+            sections: vec![("Links".to_string(), vec!["https://a.example".to_string()])],
+        };
+        let requests = doc_requests(&notes);
+        assert_eq!(requests[0]["insertText"]["text"], "Ép 1\nLinks\nhttps://a.example\n");
+        assert_eq!(requests[1]["updateParagraphStyle"]["range"]["endIndex"], 6);
+        assert_eq!(requests[2]["updateParagraphStyle"]["range"]["startIndex"], 6);
+        assert_eq!(requests[2]["updateParagraphStyle"]["paragraphStyle"]["namedStyleType"], "HEADING_2");
+    }
+}
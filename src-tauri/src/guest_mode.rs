@@ -0,0 +1,169 @@
+use std::fs;
+use std::path::PathBuf;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Mutex;
+
+use serde::{Deserialize, Serialize};
+use tauri::Manager;
+use tauri_specta::Event;
+
+use crate::sessions::SessionState;
+use crate::settings::{self, AppSettings};
+use crate::log_line;
+
+/// Also read by code without an `AppHandle`, like the keychain and the log.
+static ACTIVE: AtomicBool = AtomicBool::new(false);
+
+/// Emitted to every window when guest mode starts or ends so they reload
+/// what they show.
+#[derive(Serialize, Deserialize, Clone, Debug, specta::Type, tauri_specta::Event)]
+#[serde(rename_all = "camelCase")]
+pub struct GuestMode {
+    pub active: bool,
+}
+
+/// The guest's settings. They are never written to `settings.json`.
+#[derive(Default)]
+pub struct GuestModeState {
+    settings: Mutex<Option<AppSettings>>,
+}
+
+pub fn is_active() -> bool {
+    ACTIVE.load(Ordering::SeqCst)
+}
+
+/// Where guest sessions and everything derived from them are kept.
+pub fn guest_dir(app: &tauri::AppHandle) -> Result<PathBuf, String> {
+    Ok(app
+        .path()
+        .app_data_dir()
+        .map_err(|err| format!("failed to resolve app data dir: {}", err))?
+        .join("guest"))
+}
+
+/// A fresh profile that keeps only this machine's setup (model, mics,
+/// display, power policy) from the owner's settings: no servers or keys,
+/// accounts, show overrides or delivery targets.
+pub fn guest_settings(owner: &AppSettings) -> AppSettings {
+    let mut guest = AppSettings::default();
+    guest.transcription.whisper_model = owner.transcription.whisper_model.clone();
+    guest.transcription.language = owner.transcription.language.clone();
+    guest.transcription.input_tracks = owner.transcription.input_tracks.clone();
+    guest.transcription.webview_capture = owner.transcription.webview_capture;
+    guest.transcription.capture_source = owner.transcription.capture_source;
+    guest.transcription.preprocessing = owner.transcription.preprocessing.clone();
+    guest.transcription.long_form = owner.transcription.long_form;
+    guest.transcription.focus_gate = owner.transcription.focus_gate.clone();
+    guest.transcription.input_device = owner.transcription.input_device.clone();
+    guest.ui = owner.ui.clone();
+    guest.power = owner.power.clone();
+    guest.load = owner.load.clone();
+    guest
+}
+
+/// The guest's settings while guest mode is on.
+pub fn settings(app: &tauri::AppHandle) -> Option<AppSettings> {
+    if !is_active() {
+        return None;
+    }
+    app.state::<GuestModeState>().settings.lock().unwrap().clone()
+}
+
+pub fn store_settings(app: &tauri::AppHandle, settings: &AppSettings) {
+    *app.state::<GuestModeState>().settings.lock().unwrap() = Some(settings.clone());
+}
+
+fn purge(app: &tauri::AppHandle) -> Result<(), String> {
+    let dir = guest_dir(app)?;
+    if dir.exists() {
+        fs::remove_dir_all(&dir).map_err(|err| format!("failed to erase guest data: {}", err))?;
+    }
+    Ok(())
+}
+
+/// Erase a guest profile left behind when the app quit during guest mode.
+pub fn purge_leftovers(app: &tauri::AppHandle) {
+    if let Err(err) = purge(app) {
+        log_line(&format!("[guest-mode] {}", err));
+    }
+}
+
+fn notify(app: &tauri::AppHandle, active: bool) {
+    if let Err(err) = (GuestMode { active }).emit(app) {
+        log_line(&format!("[guest-mode] failed to notify windows: {}", err));
+    }
+}
+
+/// Restart what reads the settings or `mcp.json`, so it follows the profile
+/// just switched to.
+fn reload_features(app: &tauri::AppHandle) {
+    settings::reload_all(app);
+    crate::mcp_supervisor::reload(app);
+}
+
+fn ensure_no_session(app: &tauri::AppHandle) -> Result<(), String> {
+    if app.state::<SessionState>().active_id().is_some() {
+        return Err("end the current session before switching profiles".to_string());
+    }
+    Ok(())
+}
+
+/// Switch to a temporary profile with no access to the owner's sessions,
+/// settings or keychain. Everything it creates is erased when it ends.
+#[tauri::command]
+#[specta::specta]
+pub fn start_guest_mode(app: tauri::AppHandle) -> Result<(), String> {
+    if is_active() {
+        return Ok(());
+    }
+    ensure_no_session(&app)?;
+    purge(&app)?;
+    let guest = guest_settings(&settings::load_settings(&app));
+    store_settings(&app, &guest);
+    ACTIVE.store(true, Ordering::SeqCst);
+    reload_features(&app);
+    log_line("[guest-mode] started");
+    notify(&app, true);
+    Ok(())
+}
+
+/// Leave guest mode and erase its sessions and settings.
+#[tauri::command]
+#[specta::specta]
+pub fn end_guest_mode(app: tauri::AppHandle) -> Result<(), String> {
+    if !is_active() {
+        return Ok(());
+    }
+    ensure_no_session(&app)?;
+    ACTIVE.store(false, Ordering::SeqCst);
+    *app.state::<GuestModeState>().settings.lock().unwrap() = None;
+    purge(&app)?;
+    reload_features(&app);
+    log_line("[guest-mode] ended and erased");
+    notify(&app, false);
+    Ok(())
+}
+
+#[tauri::command]
+#[specta::specta]
+pub fn get_guest_mode() -> bool {
+    is_active()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::guest_settings;
+    use crate::settings::{AppSettings, LlmEndpoint};
+
+    #[test]
+    fn guest_profile_keeps_only_machine_setup() {
+        let mut owner = AppSettings::default();
+        owner.transcription.whisper_model = "ggml-small.bin".to_string();
+        owner.llm.endpoints.push(LlmEndpoint::default());
+        owner.email.default_recipients.push("me@example.com".to_string());
+        let guest = guest_settings(&owner);
+        assert_eq!(guest.transcription.whisper_model, "ggml-small.bin");
+        assert!(guest.llm.endpoints.is_empty());
+        assert!(guest.email.default_recipients.is_empty());
+    }
+}
@@ -0,0 +1,453 @@
+//! SQLite index of session transcripts (`history.sqlite3` in the sessions
+//! folder) for listing and full-text lookups across sessions. The
+//! per-session files stay the source of truth; segments are mirrored here
+//! as they are recorded, and sessions missing from the database are
+//! imported at startup. `segments_fts` is an FTS5 index over segment text,
+//! kept in sync by triggers.
+
+use std::time::Duration;
+
+use rusqlite::{params, Connection, OptionalExtension};
+use serde::{Deserialize, Serialize};
+
+use crate::log_line;
+use crate::sessions::{self, SessionInfo, TranscriptSegment};
+
+const DB_FILE: &str = "history.sqlite3";
+const SCHEMA: &str = "
+CREATE TABLE IF NOT EXISTS sessions (
+    id TEXT PRIMARY KEY,
+    title TEXT,
+    started_at INTEGER NOT NULL,
+    ended_at INTEGER
+);
+CREATE TABLE IF NOT EXISTS segments (
+    session_id TEXT NOT NULL REFERENCES sessions(id) ON DELETE CASCADE,
+    idx INTEGER NOT NULL,
+    offset_ms INTEGER NOT NULL,
+    text TEXT NOT NULL,
+    source TEXT,
+    PRIMARY KEY (session_id, idx)
+);
+";
+const FTS_SCHEMA: &str = "
+CREATE VIRTUAL TABLE segments_fts USING fts5(
+    text, content='segments', content_rowid='rowid', tokenize='porter unicode61'
+);
+CREATE TRIGGER segments_fts_insert AFTER INSERT ON segments BEGIN
+    INSERT INTO segments_fts (rowid, text) VALUES (new.rowid, new.text);
+END;
+CREATE TRIGGER segments_fts_delete AFTER DELETE ON segments BEGIN
+    INSERT INTO segments_fts (segments_fts, rowid, text) VALUES ('delete', old.rowid, old.text);
+END;
+CREATE TRIGGER segments_fts_update AFTER UPDATE ON segments BEGIN
+    INSERT INTO segments_fts (segments_fts, rowid, text) VALUES ('delete', old.rowid, old.text);
+    INSERT INTO segments_fts (rowid, text) VALUES (new.rowid, new.text);
+END;
+INSERT INTO segments_fts (segments_fts) VALUES ('rebuild');
+";
+const DEFAULT_LIMIT: u32 = 50;
+/// Matching segments shown per session in `search_transcripts`.
+const HITS_PER_SESSION: usize = 5;
+/// Words that make a question ("when did we talk about pricing") rather
+/// than what it's about.
+const QUERY_STOPWORDS: &[&str] = &[
+    "a", "about", "an", "and", "are", "did", "discuss", "discussed", "do", "does", "for", "i", "in", "is", "it",
+    "mention", "mentioned", "of", "on", "our", "say", "said", "talk", "talked", "the", "to", "us", "was", "we",
+    "were", "what", "when", "where", "who", "you",
+];
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, specta::Type)]
+#[serde(rename_all = "camelCase")]
+pub struct HistorySession {
+    pub id: String,
+    pub title: Option<String>,
+    pub started_at: u64,
+    pub ended_at: Option<u64>,
+    pub segment_count: u32,
+    /// First words of the transcript.
+    pub preview: String,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, specta::Type)]
+#[serde(rename_all = "camelCase")]
+pub struct TranscriptHit {
+    pub index: u32,
+    pub offset_ms: u64,
+    /// Wall-clock time of the segment.
+    pub at: u64,
+    /// Text around the match, matched words wrapped in `<mark>`/`</mark>`.
+    pub snippet: String,
+}
+
+/// A session with transcript segments matching a search, best first.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, specta::Type)]
+#[serde(rename_all = "camelCase")]
+pub struct TranscriptSearchResult {
+    pub session_id: String,
+    pub title: Option<String>,
+    pub started_at: u64,
+    pub hits: Vec<TranscriptHit>,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, specta::Type)]
+#[serde(rename_all = "camelCase")]
+pub struct HistoryMatch {
+    pub session_id: String,
+    pub title: Option<String>,
+    pub started_at: u64,
+    pub index: u32,
+    pub offset_ms: u64,
+    pub text: String,
+}
+
+fn open_at(path: &std::path::Path) -> Result<Connection, String> {
+    let conn = Connection::open(path).map_err(|err| format!("failed to open history: {}", err))?;
+    // Recursive triggers make `INSERT OR REPLACE` fire the delete trigger
+    // for the row it replaces.
+    conn.busy_timeout(Duration::from_secs(5))
+        .and_then(|_| conn.execute_batch("PRAGMA foreign_keys = ON; PRAGMA recursive_triggers = ON;"))
+        .and_then(|_| conn.execute_batch(SCHEMA))
+        .and_then(|_| {
+            let indexed = conn
+                .query_row("SELECT 1 FROM sqlite_master WHERE name = 'segments_fts'", [], |_| Ok(()))
+                .optional()?
+                .is_some();
+            if indexed {
+                Ok(())
+            } else {
+                conn.execute_batch(&format!("BEGIN; {} COMMIT;", FTS_SCHEMA))
+            }
+        })
+        .map_err(|err| format!("failed to prepare history: {}", err))?;
+    Ok(conn)
+}
+
+fn open(app: &tauri::AppHandle) -> Result<Connection, String> {
+    open_at(&sessions::sessions_dir(app)?.join(DB_FILE))
+}
+
+fn upsert_session(conn: &Connection, info: &SessionInfo) -> rusqlite::Result<()> {
+    conn.execute(
+        "INSERT INTO sessions (id, title, started_at, ended_at) VALUES (?1, ?2, ?3, ?4)
+         ON CONFLICT(id) DO UPDATE SET title = excluded.title, ended_at = excluded.ended_at",
+        params![info.id, info.title, info.started_at as i64, info.ended_at.map(|ms| ms as i64)],
+    )
+    .map(|_| ())
+}
+
+fn insert_segment(conn: &Connection, session_id: &str, segment: &TranscriptSegment) -> rusqlite::Result<()> {
+    conn.execute(
+        "INSERT OR REPLACE INTO segments (session_id, idx, offset_ms, text, source) VALUES (?1, ?2, ?3, ?4, ?5)",
+        params![session_id, segment.index, segment.offset_ms as i64, segment.text, segment.source],
+    )
+    .map(|_| ())
+}
+
+/// Replace whatever is stored for a session with `info` and `transcript`.
+fn import(conn: &mut Connection, info: &SessionInfo, transcript: &[TranscriptSegment]) -> rusqlite::Result<()> {
+    let tx = conn.transaction()?;
+    upsert_session(&tx, info)?;
+    tx.execute("DELETE FROM segments WHERE session_id = ?1", params![info.id])?;
+    for segment in transcript {
+        insert_segment(&tx, &info.id, segment)?;
+    }
+    tx.commit()
+}
+
+/// `%` and `_` are literal in user queries.
+fn like_pattern(query: &str) -> String {
+    let escaped = query.replace('\\', "\\\\").replace('%', "\\%").replace('_', "\\_");
+    format!("%{}%", escaped)
+}
+
+/// An FTS5 query for the words of `query`, prefix-matched and required
+/// together (`all`) or any of them. Stopwords are dropped unless nothing
+/// else is left.
+fn fts_query(query: &str, all: bool) -> Option<String> {
+    let words: Vec<String> = query
+        .split(|c: char| !c.is_alphanumeric())
+        .filter(|word| !word.is_empty())
+        .map(str::to_lowercase)
+        .collect();
+    let terms: Vec<&String> = match words.iter().filter(|word| !QUERY_STOPWORDS.contains(&word.as_str())).collect::<Vec<_>>() {
+        terms if terms.is_empty() => words.iter().collect(),
+        terms => terms,
+    };
+    if terms.is_empty() {
+        return None;
+    }
+    let terms: Vec<String> = terms.iter().map(|term| format!("\"{}\"*", term)).collect();
+    Some(terms.join(if all { " AND " } else { " OR " }))
+}
+
+/// Sessions with segments matching `query` ranked by their best segment
+/// (BM25). Falls back to matching any word when no segment has them all.
+fn search_fts(conn: &Connection, query: &str, limit: u32) -> rusqlite::Result<Vec<TranscriptSearchResult>> {
+    let mut stmt = conn.prepare(
+        "SELECT g.session_id, s.title, s.started_at, g.idx, g.offset_ms,
+                snippet(segments_fts, 0, '<mark>', '</mark>', '…', 16)
+         FROM segments_fts JOIN segments g ON g.rowid = segments_fts.rowid
+         JOIN sessions s ON s.id = g.session_id
+         WHERE segments_fts MATCH ?1
+         ORDER BY bm25(segments_fts), s.started_at DESC, g.idx
+         LIMIT ?2",
+    )?;
+    let row_limit = limit.saturating_mul(HITS_PER_SESSION as u32);
+    let mut results: Vec<TranscriptSearchResult> = Vec::new();
+    for all in [true, false] {
+        let Some(fts) = fts_query(query, all) else {
+            break;
+        };
+        let rows = stmt.query_map(params![fts, row_limit], |row| {
+            let started_at = row.get::<_, i64>(2)? as u64;
+            let offset_ms = row.get::<_, i64>(4)? as u64;
+            let result = TranscriptSearchResult {
+                session_id: row.get(0)?,
+                title: row.get(1)?,
+                started_at,
+                hits: Vec::new(),
+            };
+            let hit = TranscriptHit {
+                index: row.get(3)?,
+                offset_ms,
+                at: started_at + offset_ms,
+                snippet: row.get(5)?,
+            };
+            Ok((result, hit))
+        })?;
+        for row in rows {
+            let (result, hit) = row?;
+            match results.iter().position(|existing| existing.session_id == result.session_id) {
+                Some(at) if results[at].hits.len() < HITS_PER_SESSION => results[at].hits.push(hit),
+                Some(_) => {}
+                None if results.len() < limit as usize => results.push(TranscriptSearchResult { hits: vec![hit], ..result }),
+                None => {}
+            }
+        }
+        if !results.is_empty() {
+            break;
+        }
+    }
+    for result in &mut results {
+        result.hits.sort_by_key(|hit| hit.index);
+    }
+    Ok(results)
+}
+
+fn list(conn: &Connection, limit: u32, offset: u32) -> rusqlite::Result<Vec<HistorySession>> {
+    let mut stmt = conn.prepare(
+        "SELECT s.id, s.title, s.started_at, s.ended_at,
+                (SELECT COUNT(*) FROM segments WHERE session_id = s.id),
+                COALESCE((SELECT substr(text, 1, 120) FROM segments WHERE session_id = s.id ORDER BY idx LIMIT 1), '')
+         FROM sessions s ORDER BY s.started_at DESC LIMIT ?1 OFFSET ?2",
+    )?;
+    let rows = stmt.query_map(params![limit, offset], |row| {
+        Ok(HistorySession {
+            id: row.get(0)?,
+            title: row.get(1)?,
+            started_at: row.get::<_, i64>(2)? as u64,
+            ended_at: row.get::<_, Option<i64>>(3)?.map(|ms| ms as u64),
+            segment_count: row.get(4)?,
+            preview: row.get(5)?,
+        })
+    })?;
+    rows.collect()
+}
+
+fn search(conn: &Connection, query: &str, limit: u32) -> rusqlite::Result<Vec<HistoryMatch>> {
+    let mut stmt = conn.prepare(
+        "SELECT g.session_id, s.title, s.started_at, g.idx, g.offset_ms, g.text
+         FROM segments g JOIN sessions s ON s.id = g.session_id
+         WHERE g.text LIKE ?1 ESCAPE '\\'
+         ORDER BY s.started_at DESC, g.idx LIMIT ?2",
+    )?;
+    let rows = stmt.query_map(params![like_pattern(query), limit], |row| {
+        Ok(HistoryMatch {
+            session_id: row.get(0)?,
+            title: row.get(1)?,
+            started_at: row.get::<_, i64>(2)? as u64,
+            index: row.get(3)?,
+            offset_ms: row.get::<_, i64>(4)? as u64,
+            text: row.get(5)?,
+        })
+    })?;
+    rows.collect()
+}
+
+/// Mirror a session's metadata; called when it starts and ends.
+pub fn record_session(app: &tauri::AppHandle, info: &SessionInfo) {
+    if let Err(err) = open(app).and_then(|conn| upsert_session(&conn, info).map_err(|err| err.to_string())) {
+        log_line(&format!("[history] failed to record {}: {}", info.id, err));
+    }
+}
+
+/// Mirror a freshly recorded segment.
+pub fn record_segment(app: &tauri::AppHandle, session_id: &str, segment: &TranscriptSegment) {
+    if let Err(err) = open(app).and_then(|conn| insert_segment(&conn, session_id, segment).map_err(|err| err.to_string())) {
+        log_line(&format!("[history] failed to record segment of {}: {}", session_id, err));
+    }
+}
+
+/// Remove a deleted segment from the history and its search index.
+pub fn delete_segment(app: &tauri::AppHandle, session_id: &str, index: u32) {
+    let result = open(app).and_then(|conn| {
+        conn.execute(
+            "DELETE FROM segments WHERE session_id = ?1 AND idx = ?2",
+            params![session_id, index],
+        )
+        .map_err(|err| err.to_string())
+    });
+    if let Err(err) = result {
+        log_line(&format!("[history] failed to delete segment {} of {}: {}", index, session_id, err));
+    }
+}
+
+/// Import stored sessions the database doesn't know about yet, e.g. ones
+/// recorded before it existed.
+pub fn spawn_backfill(app: tauri::AppHandle) {
+    std::thread::spawn(move || {
+        let result = open(&app).and_then(|mut conn| {
+            let mut imported = 0;
+            for info in sessions::list_sessions(app.clone())? {
+                let known = conn
+                    .query_row("SELECT 1 FROM sessions WHERE id = ?1", params![info.id], |_| Ok(()))
+                    .optional()
+                    .map_err(|err| err.to_string())?
+                    .is_some();
+                if known {
+                    continue;
+                }
+                let transcript = sessions::read_transcript(&app, &info.id)?;
+                import(&mut conn, &info, &transcript).map_err(|err| err.to_string())?;
+                imported += 1;
+            }
+            Ok(imported)
+        });
+        match result {
+            Ok(0) => {}
+            Ok(imported) => log_line(&format!("[history] imported {} sessions", imported)),
+            Err(err) => log_line(&format!("[history] backfill failed: {}", err)),
+        }
+    });
+}
+
+/// Save a stored session (metadata and full transcript) into the history
+/// database, replacing what it had for it.
+#[tauri::command]
+#[specta::specta]
+pub fn save_history_session(app: tauri::AppHandle, session_id: String) -> Result<(), String> {
+    let info = crate::export::load_session(&app, &session_id)?;
+    let transcript = sessions::read_transcript(&app, &session_id)?;
+    let mut conn = open(&app)?;
+    import(&mut conn, &info, &transcript).map_err(|err| format!("failed to save {}: {}", session_id, err))
+}
+
+/// Sessions in the history, newest first.
+#[tauri::command]
+#[specta::specta]
+pub fn list_history(app: tauri::AppHandle, limit: Option<u32>, offset: Option<u32>) -> Result<Vec<HistorySession>, String> {
+    list(&open(&app)?, limit.unwrap_or(DEFAULT_LIMIT), offset.unwrap_or(0))
+        .map_err(|err| format!("failed to list history: {}", err))
+}
+
+/// Transcript segments containing `query` (case-insensitive for ASCII),
+/// newest session first.
+#[tauri::command]
+#[specta::specta]
+pub fn search_history(app: tauri::AppHandle, query: String, limit: Option<u32>) -> Result<Vec<HistoryMatch>, String> {
+    let query = query.trim();
+    if query.is_empty() {
+        return Ok(Vec::new());
+    }
+    search(&open(&app)?, query, limit.unwrap_or(DEFAULT_LIMIT)).map_err(|err| format!("failed to search history: {}", err))
+}
+
+/// Full-text search over saved transcripts, e.g. "when did we talk about
+/// pricing". Returns matching sessions, best first, with highlighted
+/// snippets and the time of each matching segment to jump to.
+#[tauri::command]
+#[specta::specta]
+pub fn search_transcripts(app: tauri::AppHandle, query: String, limit: Option<u32>) -> Result<Vec<TranscriptSearchResult>, String> {
+    search_fts(&open(&app)?, &query, limit.unwrap_or(DEFAULT_LIMIT))
+        .map_err(|err| format!("failed to search transcripts: {}", err))
+}
+
+/// Delete a session from the history and its folder from disk. The
+/// session being recorded can't be deleted.
+#[tauri::command]
+#[specta::specta]
+pub fn delete_history_session(
+    app: tauri::AppHandle,
+    state: tauri::State<'_, sessions::SessionState>,
+    session_id: String,
+) -> Result<(), String> {
+    if state.active_id().as_deref() == Some(session_id.as_str()) {
+        return Err("can't delete the session being recorded".to_string());
+    }
+    let dir = sessions::session_dir(&app, &session_id)?;
+    open(&app)?
+        .execute("DELETE FROM sessions WHERE id = ?1", params![session_id])
+        .map_err(|err| format!("failed to delete {}: {}", session_id, err))?;
+    if dir.exists() {
+        std::fs::remove_dir_all(&dir).map_err(|err| format!("failed to delete {}: {}", dir.display(), err))?;
+    }
+    log_line(&format!("[history] deleted {}", session_id));
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{fts_query, import, list, open_at, search, search_fts};
+    use crate::sessions::{SessionInfo, TranscriptSegment};
+
+    fn segment(index: u32, text: &str) -> TranscriptSegment {
+        TranscriptSegment {
+            index,
+            offset_ms: index as u64 * 1000,
+            text: text.to_string(),
+            source: None,
+        }
+    }
+
+    #[test]
+    fn imports_lists_searches_and_deletes_sessions() {
+        let path = std::env::temp_dir().join(format!("heyjamie-history-{}.sqlite3", std::process::id()));
+        let _ = std::fs::remove_file(&path);
+        let mut conn = open_at(&path).unwrap();
+        let info = |id: &str, started_at| SessionInfo {
+            id: id.to_string(),
+            title: None,
+            started_at,
+            ended_at: Some(started_at + 60_000),
+            segment_count: 2,
+        };
+        import(&mut conn, &info("a", 1), &[segment(0, "Rust async runtimes"), segment(1, "100% sure")]).unwrap();
+        import(&mut conn, &info("b", 2), &[segment(0, "GPU prices")]).unwrap();
+        import(&mut conn, &info("a", 1), &[segment(0, "Rust async runtimes"), segment(1, "100% sure")]).unwrap();
+
+        let sessions = list(&conn, 10, 0).unwrap();
+        assert_eq!(sessions.iter().map(|s| s.id.as_str()).collect::<Vec<_>>(), vec!["b", "a"]);
+        assert_eq!(sessions[1].segment_count, 2);
+        assert_eq!(sessions[1].preview, "Rust async runtimes");
+
+        let matches = search(&conn, "ASYNC", 10).unwrap();
+        assert_eq!((matches[0].session_id.as_str(), matches[0].index), ("a", 0));
+        assert_eq!(search(&conn, "0%", 10).unwrap().len(), 1);
+        assert!(search(&conn, "_", 10).unwrap().is_empty());
+
+        let results = search_fts(&conn, "when did we talk about runtime?", 10).unwrap();
+        assert_eq!(results.len(), 1);
+        assert_eq!(results[0].hits[0].snippet, "Rust async <mark>runtimes</mark>");
+        assert_eq!(results[0].hits[0].at, 1);
+        assert_eq!(search_fts(&conn, "gpu rust", 10).unwrap().len(), 2);
+        assert_eq!(fts_query("what about the", true).as_deref(), Some("\"what\"* AND \"about\"* AND \"the\"*"));
+        assert!(search_fts(&conn, "?!", 10).unwrap().is_empty());
+
+        conn.execute("DELETE FROM sessions WHERE id = 'a'", []).unwrap();
+        assert!(search(&conn, "async", 10).unwrap().is_empty());
+        assert!(search_fts(&conn, "async", 10).unwrap().is_empty());
+        drop(conn);
+        std::fs::remove_file(&path).unwrap();
+    }
+}
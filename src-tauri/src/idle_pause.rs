@@ -0,0 +1,201 @@
+//! Pausing a session nobody is using. While the main window is listening,
+//! every transcribed segment and every key press or click counts as
+//! activity, as does system-wide input where the idle time can be read
+//! (macOS, and X11 with `xprintidle`). After `idlePause.idleMinutes`
+//! without any, an `idle-pause` event tells the main window to stop
+//! listening, and a desktop notification says so.
+
+use std::process::Command;
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
+
+use serde::{Deserialize, Serialize};
+use tauri::Manager;
+use tauri_specta::Event;
+
+use crate::process_runner::ProcessRunner;
+use crate::{log_line, settings};
+
+const IDLE_CHECK_INTERVAL: Duration = Duration::from_secs(30);
+const NOTIFY_TIMEOUT: Duration = Duration::from_secs(5);
+
+/// Emitted to the main window as `idle-pause` when listening should pause.
+#[derive(Serialize, Deserialize, Clone, Debug, specta::Type, tauri_specta::Event)]
+#[serde(rename_all = "camelCase")]
+pub struct IdlePause {
+    pub idle_minutes: u32,
+}
+
+#[derive(Default)]
+struct Watch {
+    /// When the main window started listening; `None` while it isn't.
+    listening_since: Option<Instant>,
+    last_activity: Option<Instant>,
+}
+
+#[derive(Default)]
+pub struct IdlePauseState {
+    watch: Mutex<Watch>,
+}
+
+/// How long nothing has happened, or `None` when not listening. System
+/// idle time, when known, can only shorten it.
+fn quiet_for(watch: &Watch, now: Instant, system_idle: Option<Duration>) -> Option<Duration> {
+    let listening_since = watch.listening_since?;
+    let since = watch.last_activity.map_or(listening_since, |activity| activity.max(listening_since));
+    let quiet = now.saturating_duration_since(since);
+    Some(system_idle.map_or(quiet, |idle| quiet.min(idle)))
+}
+
+/// `HIDIdleTime` (nanoseconds) in `ioreg -c IOHIDSystem` output.
+#[cfg_attr(not(target_os = "macos"), allow(dead_code))]
+fn parse_hid_idle_time(output: &str) -> Option<Duration> {
+    output
+        .lines()
+        .find_map(|line| line.split_once("\"HIDIdleTime\" = "))
+        .and_then(|(_, nanos)| nanos.trim().parse().ok())
+        .map(Duration::from_nanos)
+}
+
+#[cfg(target_os = "macos")]
+fn system_idle() -> Option<Duration> {
+    let output = Command::new("ioreg")
+        .args(["-c", "IOHIDSystem", "-d", "4"])
+        .output()
+        .ok()
+        .filter(|output| output.status.success())?;
+    parse_hid_idle_time(&String::from_utf8_lossy(&output.stdout))
+}
+
+/// X11 only, and only with `xprintidle` installed.
+#[cfg(target_os = "linux")]
+fn system_idle() -> Option<Duration> {
+    let output = Command::new("xprintidle")
+        .output()
+        .ok()
+        .filter(|output| output.status.success())?;
+    String::from_utf8_lossy(&output.stdout)
+        .trim()
+        .parse()
+        .ok()
+        .map(Duration::from_millis)
+}
+
+#[cfg(not(any(target_os = "linux", target_os = "macos")))]
+fn system_idle() -> Option<Duration> {
+    None
+}
+
+/// Best-effort desktop notification.
+fn notify(title: &str, body: &str) {
+    let command = if cfg!(target_os = "macos") {
+        let mut command = Command::new("osascript");
+        command
+            .arg("-e")
+            .arg(format!("display notification {:?} with title {:?}", body, title));
+        command
+    } else if cfg!(target_os = "linux") {
+        let mut command = Command::new("notify-send");
+        command.arg(title).arg(body);
+        command
+    } else {
+        return;
+    };
+    if let Err(err) = ProcessRunner::new("notification", command).timeout(NOTIFY_TIMEOUT).run() {
+        log_line(&format!("[idle] {}", err));
+    }
+}
+
+/// Something happened: speech was transcribed or the user did something.
+pub fn note_activity(app: &tauri::AppHandle) {
+    app.state::<IdlePauseState>().watch.lock().unwrap().last_activity = Some(Instant::now());
+}
+
+fn check(app: &tauri::AppHandle) {
+    let settings = settings::load_settings(app).idle_pause;
+    if !settings.enabled || settings.idle_minutes == 0 {
+        return;
+    }
+    let state = app.state::<IdlePauseState>();
+    if state.watch.lock().unwrap().listening_since.is_none() {
+        return;
+    }
+    let system_idle = system_idle();
+    let quiet = {
+        let mut watch = state.watch.lock().unwrap();
+        let Some(quiet) = quiet_for(&watch, Instant::now(), system_idle) else {
+            return;
+        };
+        if quiet < Duration::from_secs(settings.idle_minutes as u64 * 60) {
+            return;
+        }
+        // Once per listening stretch; resuming arms it again.
+        watch.listening_since = None;
+        quiet
+    };
+    let idle_minutes = (quiet.as_secs() / 60) as u32;
+    log_line(&format!("[idle] no speech or input for {} minutes; pausing", idle_minutes));
+    if let Err(err) = (IdlePause { idle_minutes }).emit_to(app, "main") {
+        log_line(&format!("[idle] failed to notify main window: {}", err));
+    }
+    notify(
+        "HeyJamie paused listening",
+        &format!("Nothing was said for {} minutes. Open HeyJamie to resume.", idle_minutes),
+    );
+}
+
+/// Check for an idle session every half minute.
+pub fn spawn_monitor(app: tauri::AppHandle) {
+    tauri::async_runtime::spawn(async move {
+        loop {
+            tokio::time::sleep(IDLE_CHECK_INTERVAL).await;
+            let handle = app.clone();
+            let _ = tauri::async_runtime::spawn_blocking(move || check(&handle)).await;
+        }
+    });
+}
+
+/// Called by the main window when it starts (`true`) or stops listening.
+#[tauri::command]
+#[specta::specta]
+pub fn set_idle_watch(app: tauri::AppHandle, listening: bool) {
+    let state = app.state::<IdlePauseState>();
+    let mut watch = state.watch.lock().unwrap();
+    watch.listening_since = listening.then(Instant::now);
+    watch.last_activity = None;
+}
+
+/// A key press or click in a HeyJamie window.
+#[tauri::command]
+#[specta::specta]
+pub fn note_user_activity(app: tauri::AppHandle) {
+    note_activity(&app);
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::{Duration, Instant};
+
+    use super::{parse_hid_idle_time, quiet_for, Watch};
+
+    #[test]
+    fn quiet_time_counts_from_the_latest_activity() {
+        let minutes = |n: u64| Duration::from_secs(n * 60);
+        let now = Instant::now() + minutes(60);
+        let mut watch = Watch::default();
+        assert_eq!(quiet_for(&watch, now, None), None);
+
+        watch.listening_since = Some(now - minutes(30));
+        assert_eq!(quiet_for(&watch, now, None), Some(minutes(30)));
+        watch.last_activity = Some(now - minutes(5));
+        assert_eq!(quiet_for(&watch, now, None), Some(minutes(5)));
+        // Typing in another app keeps the session going.
+        watch.last_activity = None;
+        assert_eq!(quiet_for(&watch, now, Some(minutes(2))), Some(minutes(2)));
+        assert_eq!(quiet_for(&watch, now, Some(minutes(60))), Some(minutes(30)));
+
+        let ioreg = "    | |   \"HIDIdleTime\" = 125000000000\n    | |   \"HIDKeyboardModifierMappingPairs\" = ()";
+        assert_eq!(parse_hid_idle_time(ioreg), Some(Duration::from_secs(125)));
+        assert_eq!(parse_hid_idle_time(""), None);
+    }
+}
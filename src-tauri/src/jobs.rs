@@ -0,0 +1,310 @@
+//! Resumable long-running jobs: model downloads, file transcriptions and
+//! session retranscriptions. Each job is recorded in
+//! `<app data>/jobs/jobs.json` while it runs, with a checkpoint next to it
+//! (`<id>.checkpoint.json`) that the job updates as it goes: the bytes on
+//! disk for a download, the windows already transcribed for a file, the
+//! segments done for a session. A job that fails, or was running when the
+//! app quit, stays listed and `resume_job` picks it up from its checkpoint
+//! instead of starting over. Finished jobs are dropped.
+
+use std::fs;
+use std::path::PathBuf;
+use std::sync::Mutex;
+
+use serde::de::DeserializeOwned;
+use serde::{Deserialize, Serialize};
+use tauri::Manager;
+use tauri_specta::Event;
+
+use crate::{file_transcription, log_line, now_millis, trace, transcript_versions, whisper_models};
+
+/// What a job does; starting the same spec again resumes it.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Eq, specta::Type)]
+#[serde(tag = "kind", rename_all = "camelCase")]
+pub enum JobSpec {
+    ModelDownload { name: String },
+    FileTranscription { path: String },
+    SessionRetranscription {
+        #[serde(rename = "sessionId")]
+        session_id: String,
+        model: String,
+    },
+}
+
+#[derive(Serialize, Deserialize, Clone, Copy, Debug, PartialEq, Eq, specta::Type)]
+#[serde(rename_all = "kebab-case")]
+pub enum JobStatus {
+    Running,
+    Failed,
+    /// Was running when the app quit or crashed.
+    Interrupted,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, specta::Type)]
+#[serde(rename_all = "camelCase")]
+pub struct Job {
+    pub id: String,
+    pub spec: JobSpec,
+    pub status: JobStatus,
+    /// How far the checkpoint got, 0–1, when known.
+    pub progress: Option<f32>,
+    pub error: Option<String>,
+    pub started_at: u64,
+    pub updated_at: u64,
+}
+
+/// Emitted as `job-changed` when a job starts, fails or finishes.
+#[derive(Serialize, Deserialize, Clone, Debug, specta::Type, tauri_specta::Event)]
+#[serde(rename_all = "camelCase")]
+pub struct JobChanged {
+    pub id: String,
+    /// The job as it is now; `None` once it finished or was discarded.
+    pub job: Option<Job>,
+}
+
+#[derive(Default)]
+pub struct JobsState {
+    /// Serializes read-modify-write of `jobs.json`.
+    lock: Mutex<()>,
+}
+
+/// The unfinished job for `spec`, restarted, or a new one. Returns it and
+/// whether it resumes earlier work.
+fn begin(jobs: &mut Vec<Job>, spec: JobSpec, now: u64) -> (Job, bool) {
+    if let Some(job) = jobs.iter_mut().find(|job| job.spec == spec) {
+        job.status = JobStatus::Running;
+        job.error = None;
+        job.updated_at = now;
+        return (job.clone(), true);
+    }
+    let job = Job {
+        id: trace::new_id(),
+        spec,
+        status: JobStatus::Running,
+        progress: None,
+        error: None,
+        started_at: now,
+        updated_at: now,
+    };
+    jobs.push(job.clone());
+    (job, false)
+}
+
+/// Jobs still marked running from a previous run of the app.
+fn mark_interrupted(jobs: &mut [Job], now: u64) -> usize {
+    let mut count = 0;
+    for job in jobs.iter_mut().filter(|job| job.status == JobStatus::Running) {
+        job.status = JobStatus::Interrupted;
+        job.updated_at = now;
+        count += 1;
+    }
+    count
+}
+
+fn jobs_dir(app: &tauri::AppHandle) -> Result<PathBuf, String> {
+    let dir = app
+        .path()
+        .app_data_dir()
+        .map_err(|err| format!("failed to resolve app data dir: {}", err))?
+        .join("jobs");
+    fs::create_dir_all(&dir).map_err(|err| format!("failed to create jobs dir: {}", err))?;
+    Ok(dir)
+}
+
+fn checkpoint_path(app: &tauri::AppHandle, id: &str) -> Result<PathBuf, String> {
+    Ok(jobs_dir(app)?.join(format!("{}.checkpoint.json", id)))
+}
+
+fn load(app: &tauri::AppHandle) -> Vec<Job> {
+    jobs_dir(app)
+        .ok()
+        .and_then(|dir| fs::read_to_string(dir.join("jobs.json")).ok())
+        .and_then(|content| serde_json::from_str(&content).ok())
+        .unwrap_or_default()
+}
+
+/// Load, change and save the job list under its lock.
+fn update<T>(app: &tauri::AppHandle, change: impl FnOnce(&mut Vec<Job>) -> T) -> Result<T, String> {
+    let state = app.state::<JobsState>();
+    let _guard = state.lock.lock().unwrap();
+    let mut jobs = load(app);
+    let value = change(&mut jobs);
+    let content = serde_json::to_string_pretty(&jobs).map_err(|err| format!("failed to format jobs: {}", err))?;
+    fs::write(jobs_dir(app)?.join("jobs.json"), content.as_bytes())
+        .map_err(|err| format!("failed to write jobs: {}", err))?;
+    Ok(value)
+}
+
+fn notify(app: &tauri::AppHandle, id: &str, job: Option<Job>) {
+    let event = JobChanged { id: id.to_string(), job };
+    if let Err(err) = event.emit(app) {
+        log_line(&format!("[jobs] failed to emit job-changed: {}", err));
+    }
+}
+
+/// Record `spec` as running. Returns the job ID and, when it picks up an
+/// unfinished job, that job's checkpoint.
+pub fn start<T: DeserializeOwned>(app: &tauri::AppHandle, spec: JobSpec) -> Result<(String, Option<T>), String> {
+    let (job, resumed) = update(app, |jobs| begin(jobs, spec, now_millis()))?;
+    let checkpoint = if resumed { load_checkpoint(app, &job.id) } else { None };
+    if checkpoint.is_some() {
+        log_line(&format!("[jobs] resuming {} from its checkpoint", job.id));
+    }
+    notify(app, &job.id, Some(job.clone()));
+    Ok((job.id, checkpoint))
+}
+
+/// `start`, logging instead of failing: the work runs either way, just
+/// without being resumable.
+pub fn start_logged<T: DeserializeOwned>(app: &tauri::AppHandle, spec: JobSpec) -> Option<(String, Option<T>)> {
+    start(app, spec)
+        .map_err(|err| log_line(&format!("[jobs] not tracking this job: {}", err)))
+        .ok()
+}
+
+fn load_checkpoint<T: DeserializeOwned>(app: &tauri::AppHandle, id: &str) -> Option<T> {
+    let content = fs::read_to_string(checkpoint_path(app, id).ok()?).ok()?;
+    serde_json::from_str(&content).ok()
+}
+
+/// Record how far job `id` got, 0–1.
+pub fn progress(app: &tauri::AppHandle, id: &str, progress: Option<f32>) {
+    let result = update(app, |jobs| {
+        if let Some(job) = jobs.iter_mut().find(|job| job.id == id) {
+            job.progress = progress.map(|progress| progress.clamp(0.0, 1.0));
+            job.updated_at = now_millis();
+        }
+    });
+    if let Err(err) = result {
+        log_line(&format!("[jobs] progress of {} not saved: {}", id, err));
+    }
+}
+
+/// Save the state job `id` can resume from. Failures are logged; the job
+/// carries on.
+pub fn checkpoint<T: Serialize>(app: &tauri::AppHandle, id: &str, checkpoint: &T, fraction: Option<f32>) {
+    let result = checkpoint_path(app, id).and_then(|path| {
+        let content = serde_json::to_string(checkpoint).map_err(|err| format!("failed to format checkpoint: {}", err))?;
+        fs::write(path, content).map_err(|err| format!("failed to write checkpoint: {}", err))
+    });
+    match result {
+        Ok(()) => progress(app, id, fraction),
+        Err(err) => log_line(&format!("[jobs] checkpoint of {} not saved: {}", id, err)),
+    }
+}
+
+/// Record how job `id` ended: dropped with its checkpoint when it
+/// succeeded, kept for `resume_job` when it failed.
+pub fn finish<T>(app: &tauri::AppHandle, id: &str, result: &Result<T, String>) {
+    let job = update(app, |jobs| match result {
+        Ok(_) => {
+            jobs.retain(|job| job.id != id);
+            None
+        }
+        Err(err) => jobs.iter_mut().find(|job| job.id == id).map(|job| {
+            job.status = JobStatus::Failed;
+            job.error = Some(err.clone());
+            job.updated_at = now_millis();
+            job.clone()
+        }),
+    });
+    if result.is_ok() {
+        if let Ok(path) = checkpoint_path(app, id) {
+            let _ = fs::remove_file(path);
+        }
+    }
+    match job {
+        Ok(job) => notify(app, id, job),
+        Err(err) => log_line(&format!("[jobs] failed to record the end of {}: {}", id, err)),
+    }
+}
+
+/// Mark jobs left running by the last run as interrupted. Called at startup.
+pub fn recover(app: &tauri::AppHandle) {
+    match update(app, |jobs| mark_interrupted(jobs, now_millis())) {
+        Ok(0) => {}
+        Ok(count) => log_line(&format!("[jobs] {} job(s) were interrupted; resume them from Settings", count)),
+        Err(err) => log_line(&format!("[jobs] failed to check for interrupted jobs: {}", err)),
+    }
+}
+
+/// Unfinished jobs, oldest first.
+#[tauri::command]
+#[specta::specta]
+pub fn list_jobs(app: tauri::AppHandle) -> Vec<Job> {
+    let mut jobs = load(&app);
+    jobs.sort_by_key(|job| job.started_at);
+    jobs
+}
+
+/// Pick a failed or interrupted job up from its checkpoint. Progress is
+/// reported the way the job usually reports it (`model-download-progress`,
+/// `file-transcription`) and by `job-changed`.
+#[tauri::command]
+#[specta::specta]
+pub async fn resume_job(app: tauri::AppHandle, id: String) -> Result<(), String> {
+    let job = load(&app)
+        .into_iter()
+        .find(|job| job.id == id)
+        .ok_or_else(|| format!("no unfinished job {}", id))?;
+    if job.status == JobStatus::Running {
+        return Err("that job is still running".to_string());
+    }
+    match job.spec {
+        JobSpec::ModelDownload { name } => whisper_models::download_whisper_model(app, name).await.map(|_| ()),
+        JobSpec::FileTranscription { path } => file_transcription::transcribe_files(app, vec![path], None).map(|_| ()),
+        JobSpec::SessionRetranscription { session_id, model } => {
+            transcript_versions::retranscribe_session(app, session_id, Some(model), None)
+                .await
+                .map(|_| ())
+        }
+    }
+}
+
+/// Forget a job that isn't running, with its checkpoint and partial output.
+#[tauri::command]
+#[specta::specta]
+pub fn discard_job(app: tauri::AppHandle, id: String) -> Result<(), String> {
+    let removed = update(&app, |jobs| {
+        let index = jobs
+            .iter()
+            .position(|job| job.id == id && job.status != JobStatus::Running)?;
+        Some(jobs.remove(index))
+    })?
+    .ok_or_else(|| format!("no stopped job {}", id))?;
+    let _ = fs::remove_file(checkpoint_path(&app, &id)?);
+    if let JobSpec::ModelDownload { name } = &removed.spec {
+        whisper_models::remove_partial(&app, name);
+    }
+    log_line(&format!("[jobs] discarded {}", id));
+    notify(&app, &id, None);
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{begin, mark_interrupted, JobSpec, JobStatus};
+
+    #[test]
+    fn starting_the_same_spec_resumes_the_unfinished_job() {
+        let mut jobs = Vec::new();
+        let download = JobSpec::ModelDownload {
+            name: "base.en".to_string(),
+        };
+        let (first, resumed) = begin(&mut jobs, download.clone(), 1);
+        assert!(!resumed);
+
+        assert_eq!(mark_interrupted(&mut jobs, 2), 1);
+        assert_eq!(jobs[0].status, JobStatus::Interrupted);
+
+        let (again, resumed) = begin(&mut jobs, download, 3);
+        assert!(resumed);
+        assert_eq!(again.id, first.id);
+        assert_eq!(again.status, JobStatus::Running);
+
+        let (other, resumed) = begin(&mut jobs, JobSpec::FileTranscription { path: "a.wav".to_string() }, 4);
+        assert!(!resumed);
+        assert_ne!(other.id, first.id);
+        assert_eq!(jobs.len(), 2);
+    }
+}
@@ -0,0 +1,40 @@
+use crate::guest_mode;
+
+/// Service name for secrets (OAuth tokens, SMTP passwords, …) kept in the
+/// OS credential store rather than `settings.json`.
+const SERVICE: &str = "HeyJamie";
+
+fn entry(account: &str) -> Result<keyring::Entry, String> {
+    keyring::Entry::new(SERVICE, account).map_err(|err| format!("keychain unavailable: {}", err))
+}
+
+/// Guests see an empty keychain and can't change it.
+pub fn get_secret(account: &str) -> Result<Option<String>, String> {
+    if guest_mode::is_active() {
+        return Ok(None);
+    }
+    match entry(account)?.get_password() {
+        Ok(secret) => Ok(Some(secret)),
+        Err(keyring::Error::NoEntry) => Ok(None),
+        Err(err) => Err(format!("failed to read {} from keychain: {}", account, err)),
+    }
+}
+
+pub fn set_secret(account: &str, secret: &str) -> Result<(), String> {
+    if guest_mode::is_active() {
+        return Err("credentials can't be saved in guest mode".to_string());
+    }
+    entry(account)?
+        .set_password(secret)
+        .map_err(|err| format!("failed to store {} in keychain: {}", account, err))
+}
+
+pub fn delete_secret(account: &str) -> Result<(), String> {
+    if guest_mode::is_active() {
+        return Err("credentials can't be changed in guest mode".to_string());
+    }
+    match entry(account)?.delete_credential() {
+        Ok(()) | Err(keyring::Error::NoEntry) => Ok(()),
+        Err(err) => Err(format!("failed to remove {} from keychain: {}", account, err)),
+    }
+}
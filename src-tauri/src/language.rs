@@ -0,0 +1,201 @@
+use serde::Serialize;
+use serde_json::Value as JsonValue;
+
+use crate::settings::LanguageSettings;
+
+/// Languages the agent can be asked to reply in, as (code, English name).
+const LANGUAGE_NAMES: &[(&str, &str)] = &[
+    ("en", "English"),
+    ("es", "Spanish"),
+    ("fr", "French"),
+    ("de", "German"),
+    ("it", "Italian"),
+    ("pt", "Portuguese"),
+    ("nl", "Dutch"),
+    ("ja", "Japanese"),
+    ("ko", "Korean"),
+    ("zh", "Chinese"),
+    ("ru", "Russian"),
+    ("ar", "Arabic"),
+    ("hi", "Hindi"),
+    ("el", "Greek"),
+    ("he", "Hebrew"),
+    ("th", "Thai"),
+];
+
+/// Frequent short words that rarely appear in the other listed languages.
+const STOPWORDS: &[(&str, &[&str])] = &[
+    ("en", &["the", "and", "is", "are", "what", "this", "that", "with", "you", "of", "to", "it"]),
+    ("es", &["el", "los", "las", "que", "es", "por", "para", "con", "una", "pero", "como", "qué", "está"]),
+    ("fr", &["le", "les", "des", "est", "et", "une", "pour", "avec", "que", "pas", "c'est", "vous", "je"]),
+    ("de", &["der", "die", "das", "und", "ist", "nicht", "ein", "eine", "mit", "ich", "wir", "sie", "auf"]),
+    ("it", &["il", "gli", "che", "è", "di", "una", "per", "non", "sono", "come", "questo", "anche"]),
+    ("pt", &["o", "os", "que", "não", "uma", "para", "com", "é", "você", "isso", "mas", "está"]),
+    ("nl", &["de", "het", "een", "en", "is", "niet", "dat", "van", "ik", "je", "wat", "met"]),
+];
+
+/// Minimum stopword hits before a Latin-script guess is trusted.
+const MIN_STOPWORD_HITS: usize = 2;
+
+#[derive(Serialize, Clone, Debug, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub struct ResponseLanguage {
+    pub code: String,
+    pub name: String,
+}
+
+fn language(code: &str) -> Option<ResponseLanguage> {
+    let code = code.trim().to_lowercase();
+    LANGUAGE_NAMES
+        .iter()
+        .find(|(c, _)| *c == code)
+        .map(|(c, name)| ResponseLanguage {
+            code: c.to_string(),
+            name: name.to_string(),
+        })
+}
+
+fn script_language(text: &str) -> Option<&'static str> {
+    let mut kana = 0;
+    let mut counts = [0usize; 8];
+    for c in text.chars() {
+        match c as u32 {
+            0x3040..=0x30FF => kana += 1,
+            0xAC00..=0xD7AF | 0x1100..=0x11FF => counts[0] += 1,
+            0x4E00..=0x9FFF => counts[1] += 1,
+            0x0400..=0x04FF => counts[2] += 1,
+            0x0600..=0x06FF => counts[3] += 1,
+            0x0900..=0x097F => counts[4] += 1,
+            0x0370..=0x03FF => counts[5] += 1,
+            0x0590..=0x05FF => counts[6] += 1,
+            0x0E00..=0x0E7F => counts[7] += 1,
+            _ => {}
+        }
+    }
+    if kana > 0 {
+        return Some("ja");
+    }
+    let (index, count) = counts.iter().enumerate().max_by_key(|(_, count)| **count)?;
+    if *count < 2 {
+        return None;
+    }
+    Some(["ko", "zh", "ru", "ar", "hi", "el", "he", "th"][index])
+}
+
+/// Best guess at the language of a spoken utterance, or `None` when the
+/// text is too short or ambiguous.
+pub fn detect_language(text: &str) -> Option<&'static str> {
+    if let Some(code) = script_language(text) {
+        return Some(code);
+    }
+    let words: Vec<String> = text
+        .split(|c: char| !(c.is_alphanumeric() || c == '\''))
+        .filter(|w| !w.is_empty())
+        .map(str::to_lowercase)
+        .collect();
+    let mut scores: Vec<(&'static str, usize)> = STOPWORDS
+        .iter()
+        .map(|(code, stopwords)| {
+            (
+                *code,
+                words.iter().filter(|w| stopwords.contains(&w.as_str())).count(),
+            )
+        })
+        .collect();
+    scores.sort_by_key(|(_, hits)| std::cmp::Reverse(*hits));
+    let (best, hits) = scores[0];
+    let runner_up = scores[1].1;
+    (hits >= MIN_STOPWORD_HITS && hits > runner_up).then_some(best)
+}
+
+/// The reply language for a request: the setting for the show whose name
+/// prefixes the session title, else the default. `auto` follows the
+/// utterance; `off` (or an unknown code) sends no hint.
+pub fn resolve_response_language(
+    settings: &LanguageSettings,
+    session_title: Option<&str>,
+    utterance: &str,
+) -> Option<ResponseLanguage> {
+    let title = session_title.unwrap_or_default().trim().to_lowercase();
+    let mode = settings
+        .shows
+        .iter()
+        .find(|show| !show.show.trim().is_empty() && title.starts_with(&show.show.trim().to_lowercase()))
+        .map(|show| show.response_language.as_str())
+        .unwrap_or(settings.response_language.as_str())
+        .trim()
+        .to_lowercase();
+    match mode.as_str() {
+        "" | "off" => None,
+        "auto" => detect_language(utterance).and_then(language),
+        code => language(code),
+    }
+}
+
+/// The language passed to whisper: `auto`, a lowercase code such as `es`,
+/// or `en` when unset.
+pub fn whisper_language(code: &str) -> String {
+    match code.trim().to_lowercase().as_str() {
+        "" => "en".to_string(),
+        code => code.to_string(),
+    }
+}
+
+/// Whether `model_path` is an English-only ggml model (`*.en.bin`).
+pub fn is_english_only_model(model_path: &std::path::Path) -> bool {
+    model_path
+        .file_name()
+        .and_then(|name| name.to_str())
+        .is_some_and(|name| name.ends_with(".en.bin"))
+}
+
+/// The most recent spoken text behind an agent request: an explicit
+/// direct command or utterance in the request context, else the session's
+/// latest transcript segment.
+pub fn request_utterance(context: Option<&JsonValue>, last_segment: Option<&str>) -> String {
+    context
+        .and_then(|ctx| {
+            ["directCommand", "utterance", "latestSpeech"]
+                .iter()
+                .find_map(|key| ctx.get(*key).and_then(|v| v.as_str()))
+        })
+        .filter(|text| !text.trim().is_empty())
+        .or(last_segment)
+        .unwrap_or_default()
+        .to_string()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{detect_language, resolve_response_language};
+    use crate::settings::{LanguageSettings, ShowLanguage};
+
+    #[test]
+    fn detects_common_languages() {
+        assert_eq!(detect_language("what is the latest version of the Rust compiler"), Some("en"));
+        assert_eq!(detect_language("¿qué es lo que está pasando con los precios?"), Some("es"));
+        assert_eq!(detect_language("Ich weiß nicht, ob das die richtige Antwort ist"), Some("de"));
+        assert_eq!(detect_language("東京の天気を調べて"), Some("ja"));
+        assert_eq!(detect_language("ok"), None);
+    }
+
+    #[test]
+    fn show_setting_overrides_default() {
+        let settings = LanguageSettings {
+            response_language: "auto".to_string(),
+            shows: vec![ShowLanguage {
+                show: "Café Tech".to_string(),
+                response_language: "fr".to_string(),
+            }],
+        };
+        let spanish = "¿qué es lo que está pasando con los precios?";
+        assert_eq!(
+            resolve_response_language(&settings, Some("Café Tech #12"), spanish).map(|l| l.code),
+            Some("fr".to_string())
+        );
+        assert_eq!(
+            resolve_response_language(&settings, Some("Other show"), spanish).map(|l| l.code),
+            Some("es".to_string())
+        );
+    }
+}
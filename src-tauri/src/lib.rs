@@ -1,25 +1,118 @@
 use std::{
     env, fs,
-    io::{BufRead, BufReader, Read, Write},
-    path::PathBuf,
+    io::Write,
+    path::{Path, PathBuf},
     process::Command,
-    sync::{Arc, Mutex},
-    time::{Instant, SystemTime, UNIX_EPOCH},
+    sync::Arc,
+    time::Instant,
 };
 
 use base64::{engine::general_purpose, Engine as _};
 use serde::{Deserialize, Serialize};
 use serde_json::Value as JsonValue;
-use std::process::Stdio;
-use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::atomic::{AtomicBool, AtomicU32, Ordering};
 use std::time::Duration;
+use tauri_specta::Event;
+use process_runner::{graceful_kill, ProcessRunner};
+use transcription_backend::TranscriptionRequest;
+use transcription_queue::Lane;
 #[cfg(desktop)]
 use tauri::{
     menu::{Menu, MenuItem, MenuItemKind, Submenu},
-    Emitter, Manager, Url, WebviewUrl, WebviewWindowBuilder,
+    Manager, Url, WebviewUrl, WebviewWindowBuilder,
 };
 
-#[derive(Serialize)]
+mod acceleration;
+mod agenda;
+mod agent_modes;
+mod analytics;
+mod api_version;
+mod app_control;
+mod artifacts;
+mod audio_decode;
+mod audio_devices;
+mod auth;
+mod background;
+mod bookmarks;
+mod browser_sessions;
+mod canvas_boards;
+mod canvas_provenance;
+mod canvas_snapshots;
+mod captions;
+mod clipboard;
+mod clock;
+mod diarization;
+mod dictation;
+mod docs_cache;
+mod email_digest;
+mod export;
+mod failover;
+mod file_transcription;
+mod focus_gate;
+mod google_export;
+mod guest_mode;
+mod history;
+mod idle_pause;
+mod jobs;
+mod keychain;
+mod language;
+mod long_form;
+mod loopback;
+mod mcp_health;
+mod mcp_history;
+mod mcp_servers;
+mod mcp_supervisor;
+mod meeting;
+mod mic_level;
+mod model_variants;
+mod politeness;
+mod post_session;
+mod power;
+mod preflight;
+mod preprocess;
+mod process_runner;
+mod prompts;
+mod punctuation;
+mod push_to_talk;
+mod quick_capture;
+mod recording;
+mod recording_segment;
+mod redaction;
+mod release_notes;
+mod sandbox;
+mod scenarios;
+mod scripting;
+mod selfcheck;
+mod remote_whisper;
+mod session_audio;
+mod session_report;
+mod sessions;
+mod settings;
+mod settings_transfer;
+mod shutdown;
+mod system_load;
+mod temp_files;
+mod timecode;
+mod topic_graph;
+mod trace;
+mod transcript_detail;
+mod transcript_filters;
+mod transcript_sync;
+mod transcript_versions;
+mod transcription_backend;
+mod transcription_queue;
+mod transcription_metrics;
+mod ui_scale;
+mod vad;
+mod voice_triggers;
+mod wav;
+mod webhooks;
+mod whisper_benchmark;
+mod whisper_engine;
+mod whisper_models;
+mod whisper_worker;
+
+#[derive(Serialize, specta::Type)]
 #[serde(rename_all = "camelCase")]
 struct WhisperStatus {
     cli_found: bool,
@@ -28,7 +121,7 @@ struct WhisperStatus {
     model_path: Option<String>,
 }
 
-#[derive(Serialize)]
+#[derive(Serialize, specta::Type)]
 #[serde(rename_all = "camelCase")]
 struct ExcalidrawStatus {
     dir_found: bool,
@@ -37,14 +130,14 @@ struct ExcalidrawStatus {
     install_path: Option<String>,
 }
 
-#[derive(Serialize)]
+#[derive(Serialize, specta::Type)]
 #[serde(rename_all = "camelCase")]
 struct McpConfigResponse {
     path: String,
     content: String,
 }
 
-#[derive(Deserialize)]
+#[derive(Deserialize, specta::Type)]
 #[serde(rename_all = "camelCase")]
 struct LlmAgentSettings {
     api_key: String,
@@ -52,7 +145,7 @@ struct LlmAgentSettings {
     reasoning: bool,
 }
 
-#[derive(Deserialize)]
+#[derive(Deserialize, specta::Type)]
 #[serde(rename_all = "camelCase")]
 struct LlmAgentRequest {
     settings: LlmAgentSettings,
@@ -62,189 +155,645 @@ struct LlmAgentRequest {
     mode: Option<String>,
     #[serde(default)]
     context: Option<JsonValue>,
+    /// Set by the webview so every run for one interaction shares a trace.
+    #[serde(default)]
+    trace_id: Option<String>,
+}
+
+/// Payload of the `browser-control` event forwarded to the main window.
+#[derive(Serialize, Deserialize, Clone, specta::Type, tauri_specta::Event)]
+struct BrowserControl(String);
+
+/// What `transcribe_audio` asks whisper for.
+#[derive(Serialize, Deserialize, Clone, Copy, Debug, Default, PartialEq, Eq, specta::Type)]
+#[serde(rename_all = "camelCase")]
+enum TranscribeMode {
+    #[default]
+    Transcribe,
+    /// Also translate the speech to English.
+    Translate,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, specta::Type)]
+#[serde(rename_all = "camelCase")]
+struct Transcription {
+    /// In the spoken language.
+    text: String,
+    /// English translation, in `translate` mode.
+    translation: Option<String>,
+}
+
+/// `transcribe_audio`'s response: the bare text for API v1 webviews, a
+/// `Transcription` from v2 on.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, specta::Type)]
+#[serde(untagged)]
+enum TranscribeAudioResponse {
+    Text(String),
+    Transcription(Transcription),
+}
+
+impl TranscribeAudioResponse {
+    fn for_client(client_version: u32, transcription: Transcription) -> Self {
+        if client_version < 2 {
+            TranscribeAudioResponse::Text(transcription.text)
+        } else {
+            TranscribeAudioResponse::Transcription(transcription)
+        }
+    }
 }
 
+/// One whisper segment from `transcribe_audio_stream`, sent as soon as it is
+/// decoded so long recordings show text before the whole chunk is done.
+#[derive(Serialize, Deserialize, Clone, specta::Type, tauri_specta::Event)]
+#[serde(rename_all = "camelCase")]
+struct TranscriptionPartial {
+    stream_id: String,
+    index: u32,
+    text: String,
+}
+
+/// Receives each cleaned segment as whisper produces it.
+type SegmentSink = Arc<dyn Fn(&str) + Send + Sync>;
+
 struct LlmAgentState {
     cancel_requested: Arc<AtomicBool>,
+    /// Runs in flight, so quitting can wait for them to stop.
+    running: Arc<AtomicU32>,
 }
 
 impl Default for LlmAgentState {
     fn default() -> Self {
         Self {
             cancel_requested: Arc::new(AtomicBool::new(false)),
+            running: Arc::new(AtomicU32::new(0)),
         }
     }
 }
 
-struct ExcalidrawServerState {
-    child: Mutex<Option<std::process::Child>>,
-}
-
-/// Start the Excalidraw Express/WebSocket canvas server on app launch.
-/// Reads the MCP config to find the excalidraw server entry and spawns
-/// `node dist/server.js` from its configured cwd.
-fn start_excalidraw_server(app: &tauri::AppHandle) -> Option<std::process::Child> {
-    let config_path = match mcp_config_path(app) {
-        Ok(p) => p,
-        Err(e) => {
-            log_line(&format!("[excalidraw] failed to resolve MCP config path: {}", e));
-            return None;
-        }
-    };
-
+/// The MCP config as JSON, falling back to the default config when the file
+/// is missing or unreadable.
+fn load_mcp_config_value(app: &tauri::AppHandle) -> Result<JsonValue, String> {
+    let config_path = mcp_config_path(app)
+        .map_err(|e| format!("failed to resolve MCP config path: {}", e))?;
     let config_str = if config_path.exists() {
         fs::read_to_string(&config_path).unwrap_or_else(|_| default_mcp_config())
     } else {
         default_mcp_config()
     };
+    serde_json::from_str(&config_str).map_err(|e| format!("failed to parse MCP config: {}", e))
+}
 
-    let config: JsonValue = match serde_json::from_str(&config_str) {
-        Ok(v) => v,
-        Err(e) => {
-            log_line(&format!("[excalidraw] failed to parse MCP config: {}", e));
-            return None;
-        }
-    };
+/// Base URL of the Excalidraw canvas server (`EXPRESS_SERVER_URL` in the
+/// excalidraw MCP entry).
+pub(crate) fn excalidraw_canvas_url(app: &tauri::AppHandle) -> String {
+    load_mcp_config_value(app)
+        .ok()
+        .and_then(|config| {
+            config
+                .pointer("/mcpServers/excalidraw/env/EXPRESS_SERVER_URL")
+                .and_then(|v| v.as_str())
+                .map(|url| url.trim().trim_end_matches('/').to_string())
+        })
+        .filter(|url| !url.is_empty())
+        .unwrap_or_else(|| "http://localhost:3000".to_string())
+}
 
-    let excalidraw = match config.get("mcpServers").and_then(|s| s.get("excalidraw")) {
-        Some(e) => e,
-        None => {
-            log_line("[excalidraw] no excalidraw server entry in MCP config");
-            return None;
-        }
-    };
+// Learn more about Tauri commands at https://tauri.app/develop/calling-rust/
+#[tauri::command]
+#[specta::specta]
+fn greet(name: &str) -> String {
+    format!("Hello, {}! You've been greeted from Rust!", name)
+}
 
-    if excalidraw.get("enabled").and_then(|v| v.as_bool()) == Some(false) {
-        log_line("[excalidraw] server disabled in config, skipping auto-start");
-        return None;
+#[tauri::command]
+#[specta::specta]
+/// `language` overrides `transcription.language` for this call: a whisper
+/// code such as `es`, or `auto`. `translate` mode runs whisper a second
+/// time to translate the speech to English. The set fields of `options`
+/// override `transcription.options`. `input` names the input track or
+/// device, for its preprocessing filters. API v1 webviews get the text
+/// alone and can't translate.
+#[allow(clippy::too_many_arguments)]
+async fn transcribe_audio(
+    app: tauri::AppHandle,
+    window: tauri::WebviewWindow,
+    api: tauri::State<'_, api_version::ApiVersionState>,
+    audio_base64: String,
+    language: Option<String>,
+    mode: Option<TranscribeMode>,
+    options: Option<settings::TranscriptionOptions>,
+    input: Option<String>,
+) -> Result<TranscribeAudioResponse, String> {
+    let client_version = api.client_version(window.label());
+    let mode = mode.unwrap_or_default();
+    if client_version < 2 && mode == TranscribeMode::Translate {
+        return Err("translate mode needs API v2; reload the window".to_string());
     }
-
-    let cwd = match excalidraw.get("cwd").and_then(|v| v.as_str()) {
-        Some(c) => {
-            if c.starts_with("~/") {
-                dirs::home_dir()
-                    .map(|h| h.join(&c[2..]))
-                    .unwrap_or_else(|| PathBuf::from(c))
-            } else {
-                PathBuf::from(c)
-            }
-        }
-        None => {
-            log_line("[excalidraw] no cwd in server config");
-            return None;
-        }
+    log_line(&format!(
+        "transcribe_audio called ({:?}, payload bytes: {})",
+        mode,
+        audio_base64.len()
+    ));
+    let translate = mode == TranscribeMode::Translate;
+    let (text, translation) = transcribe_payload(&app, &audio_base64, language, options, input, translate).await?;
+    Ok(TranscribeAudioResponse::for_client(
+        client_version,
+        Transcription { text, translation },
+    ))
+}
+
+/// The transcript of a webview payload and, with `translate`, its English
+/// translation.
+async fn transcribe_payload(
+    app: &tauri::AppHandle,
+    audio_base64: &str,
+    language: Option<String>,
+    options: Option<settings::TranscriptionOptions>,
+    input: Option<String>,
+    translate: bool,
+) -> Result<(String, Option<String>), String> {
+    if let Some(options) = &options {
+        options.validate()?;
+    }
+    let payload_started = Instant::now();
+    let wav_bytes = decode_audio_payload(audio_base64).await?;
+    let wav_bytes = preprocess_input(app, input.as_deref(), wav_bytes);
+    let payload_ms = Some(payload_started.elapsed().as_millis() as u64);
+    let _permit = transcription_queue::acquire(app, Lane::Live).await;
+    let text = run_whisper(app, wav_bytes.clone(), language.clone(), false, None, payload_ms, options).await?;
+    let translation = match translate {
+        false => None,
+        true if text.trim().is_empty() => Some(String::new()),
+        true => Some(run_whisper(app, wav_bytes, language, true, None, None, options).await?),
     };
+    Ok((text, translation))
+}
 
-    let server_js = cwd.join("dist/server.js");
-    if !server_js.exists() {
-        log_line(&format!(
-            "[excalidraw] dist/server.js not found at {}",
-            server_js.display()
-        ));
-        return None;
+/// Like `transcribe_audio`, but also emits a `transcription-partial` event
+/// tagged with `stream_id` for every segment as it is decoded. Remote
+/// servers answer in one piece, so they produce a single partial.
+#[tauri::command]
+#[specta::specta]
+async fn transcribe_audio_stream(
+    app: tauri::AppHandle,
+    audio_base64: String,
+    stream_id: String,
+    language: Option<String>,
+    options: Option<settings::TranscriptionOptions>,
+    input: Option<String>,
+) -> Result<String, String> {
+    if let Some(options) = &options {
+        options.validate()?;
     }
+    log_line(&format!(
+        "transcribe_audio_stream called ({}, payload bytes: {})",
+        stream_id,
+        audio_base64.len()
+    ));
+    let payload_started = Instant::now();
+    let wav_bytes = decode_audio_payload(&audio_base64).await?;
+    let wav_bytes = preprocess_input(&app, input.as_deref(), wav_bytes);
+    let payload_ms = Some(payload_started.elapsed().as_millis() as u64);
+    let sink = partial_sink(&app, stream_id);
+    let permit = transcription_queue::acquire(&app, Lane::Live).await;
+    let result = run_whisper(&app, wav_bytes.clone(), language, false, Some(sink), payload_ms, options).await;
+    drop(permit);
+    if result.as_ref().is_ok_and(|text| !text.trim().is_empty()) {
+        session_audio::keep_segment(&app, &wav_bytes);
+    }
+    result
+}
+
+/// Run a webview segment through the preprocessing filters of `input`.
+fn preprocess_input(app: &tauri::AppHandle, input: Option<&str>, wav_bytes: Vec<u8>) -> Vec<u8> {
+    let settings = settings::load_settings(app).transcription.preprocessing;
+    preprocess::process_wav(&settings, input, wav_bytes)
+}
+
+/// Emits each cleaned segment as a `transcription-partial` event.
+fn partial_sink(app: &tauri::AppHandle, stream_id: String) -> SegmentSink {
+    let emitter = app.clone();
+    let next_index = AtomicU32::new(0);
+    Arc::new(move |text: &str| {
+        let Some(text) = clean_transcript_fragment(text) else {
+            return;
+        };
+        let partial = TranscriptionPartial {
+            stream_id: stream_id.clone(),
+            index: next_index.fetch_add(1, Ordering::SeqCst),
+            text,
+        };
+        if let Err(err) = partial.emit(&emitter) {
+            log_line(&format!("failed to emit transcription partial: {}", err));
+        }
+    })
+}
 
-    let mut cmd = Command::new("node");
-    cmd.arg("dist/server.js")
-        .current_dir(&cwd)
-        .stdout(Stdio::null())
-        .stderr(Stdio::null());
+/// Decode a base64 audio payload into WAV bytes. Compressed formats such
+/// as webm or mp3 are converted to 16 kHz mono (see `audio_decode`).
+async fn decode_audio_payload(audio_base64: &str) -> Result<Vec<u8>, String> {
+    let bytes = general_purpose::STANDARD
+        .decode(audio_base64.as_bytes())
+        .map_err(|err| format!("invalid audio payload: {}", err))?;
+    tauri::async_runtime::spawn_blocking(move || audio_decode::to_wav(bytes))
+        .await
+        .map_err(|err| format!("audio decode task failed: {}", err))?
+}
 
-    if let Some(env_obj) = excalidraw.get("env").and_then(|v| v.as_object()) {
-        for (k, v) in env_obj {
-            if let Some(val) = v.as_str() {
-                cmd.env(k, val);
+/// Transcribe a WAV through the remote failover chain, then local whisper,
+/// once `lane` has a free slot.
+async fn transcribe_wav(
+    app: &tauri::AppHandle,
+    wav_bytes: Vec<u8>,
+    language: Option<String>,
+    on_segment: Option<SegmentSink>,
+    lane: Lane,
+) -> Result<String, String> {
+    let _permit = transcription_queue::acquire(app, lane).await;
+    run_whisper(app, wav_bytes, language, false, on_segment, None, None).await
+}
+
+/// `transcribe_wav`, or with `translate` the English translation.
+/// `payload_ms` is how long decoding the webview payload took, for the
+/// `transcription-metrics` event. `options` overrides the saved defaults.
+async fn run_whisper(
+    app: &tauri::AppHandle,
+    wav_bytes: Vec<u8>,
+    language: Option<String>,
+    translate: bool,
+    on_segment: Option<SegmentSink>,
+    payload_ms: Option<u64>,
+    options: Option<settings::TranscriptionOptions>,
+) -> Result<String, String> {
+    let started = Instant::now();
+    if !vad::wav_has_speech(&wav_bytes) {
+        log_line("[vad] no speech in segment; skipping whisper");
+        return Ok(String::new());
+    }
+    let _burst = background::burst(app, background::BurstKind::Transcription);
+    let settings = settings::load_settings(app);
+    let options = settings.transcription.options.with(options);
+    let punctuate = options.punctuate == Some(true);
+    let vocabulary = &settings.transcription.vocabulary;
+    let on_segment = match on_segment {
+        Some(sink) if punctuate => Some(punctuation::wrap_sink(sink, vocabulary.clone())),
+        on_segment => on_segment,
+    };
+    let request = TranscriptionRequest {
+        language: language::whisper_language(language.as_deref().unwrap_or(&settings.transcription.language)),
+        prompt: settings.transcription.whisper_prompt(),
+        options,
+        wav_bytes,
+        translate,
+        on_segment,
+        payload_ms,
+        started,
+    };
+    let text = transcription_backend::transcribe(app, &settings.transcription, request).await?;
+    if !punctuate {
+        return Ok(text);
+    }
+    Ok(punctuation::restore(&settings.transcription.punctuation, vocabulary, text).await)
+}
+
+/// The local whisper backend: remote whisper servers in failover order,
+/// then the warm worker or `whisper-cli`.
+async fn transcribe_with_whisper(app: &tauri::AppHandle, request: TranscriptionRequest) -> Result<String, String> {
+    let TranscriptionRequest {
+        wav_bytes,
+        language,
+        prompt,
+        translate,
+        options,
+        on_segment,
+        payload_ms,
+        started,
+    } = request;
+    let settings = settings::load_settings(app);
+    let chain = failover::transcription_chain(&settings);
+    let failover_state = app.state::<failover::FailoverState>();
+    let mut allow_local = true;
+    let mut last_error: Option<String> = None;
+    for server in &chain {
+        let key = server.url.trim();
+        if !failover_state.is_available(key) {
+            log_line(&format!("[failover] skipping unhealthy whisper server {}", key));
+            continue;
+        }
+        let request_started = Instant::now();
+        match remote_whisper::transcribe(server, &wav_bytes, &language, prompt.as_deref(), translate, &options).await {
+            Ok(transcript) => {
+                let timings = transcription_metrics::Timings {
+                    backend: "remote".to_string(),
+                    model: Some(key.to_string()),
+                    decode_ms: request_started.elapsed().as_millis() as u64,
+                    ..Default::default()
+                };
+                transcription_metrics::record(app, &wav_bytes, payload_ms, started, timings);
+                failover_state.record_success(key);
+                failover_state.note_active(
+                    app,
+                    failover::FailoverService::Transcription,
+                    key,
+                    last_error.take(),
+                );
+                log_line(&format!("remote whisper transcript: {}", transcript));
+                if let Some(sink) = &on_segment {
+                    sink(&transcript);
+                }
+                return Ok(transcript);
             }
+            Err(err) => {
+                failover_state.record_failure(key, &err);
+                allow_local &= server.fallback_to_local;
+                last_error = Some(err);
+            }
+        }
+    }
+
+    if !chain.is_empty() {
+        if !allow_local {
+            return Err(last_error
+                .unwrap_or_else(|| "no healthy remote whisper server available".to_string()));
         }
+        log_line("no remote whisper server succeeded; falling back to local whisper-cli");
+        failover_state.note_active(
+            app,
+            failover::FailoverService::Transcription,
+            failover::LOCAL_WHISPER_ENDPOINT,
+            Some(last_error.unwrap_or_else(|| "remote whisper servers unhealthy".to_string())),
+        );
     }
 
-    match cmd.spawn() {
-        Ok(child) => {
-            log_line(&format!(
-                "[excalidraw] canvas server started (pid: {})",
-                child.id()
-            ));
-            Some(child)
+    let model = system_load::current(app)
+        .and_then(|pressure| pressure.whisper_model)
+        .or(power::current(app).whisper_model);
+    let model_path = whisper_models::resolve(app, model.as_deref())?;
+    if whisper_worker::enabled(app) {
+        let worker_started = Instant::now();
+        match whisper_worker::transcribe(app, &model_path, &wav_bytes, &language, prompt.as_deref(), translate, &options).await {
+            Ok((transcript, startup)) => {
+                let model_load_ms = startup.map_or(0, |startup| startup.as_millis() as u64);
+                let timings = transcription_metrics::Timings {
+                    backend: "whisper-server".to_string(),
+                    model: model_path.file_name().map(|name| name.to_string_lossy().into_owned()),
+                    model_load_ms: Some(model_load_ms),
+                    decode_ms: (worker_started.elapsed().as_millis() as u64).saturating_sub(model_load_ms),
+                    ..Default::default()
+                };
+                transcription_metrics::record(app, &wav_bytes, payload_ms, started, timings);
+                log_line(&format!("whisper-server transcript: {}", transcript));
+                if let Some(sink) = &on_segment {
+                    sink(&transcript);
+                }
+                return Ok(transcript);
+            }
+            Err(err) => log_line(&format!("[whisper-worker] {}; falling back to whisper-cli", err)),
         }
-        Err(e) => {
-            log_line(&format!("[excalidraw] failed to start canvas server: {}", e));
-            None
+    }
+    let app = app.clone();
+    tauri::async_runtime::spawn_blocking(move || {
+        whisper_models::ensure_intact(&app, &model_path)?;
+        let (transcript, mut timings) = transcription_metrics::collect(|| {
+            transcribe_wav_locally(&wav_bytes, &model_path, &language, prompt.as_deref(), translate, &options, on_segment)
+        });
+        if transcript.is_ok() {
+            timings.model = model_path.file_name().map(|name| name.to_string_lossy().into_owned());
+            transcription_metrics::record(&app, &wav_bytes, payload_ms, started, timings);
         }
+        transcript
+    })
+        .await
+        .map_err(|err| format!("transcription task failed: {}", err))?
+}
+
+/// Spoken language of a clip as guessed by whisper.
+#[derive(Serialize, Deserialize, Clone, specta::Type)]
+#[serde(rename_all = "camelCase")]
+struct DetectedLanguage {
+    /// Whisper language code, e.g. `es`.
+    code: String,
+    probability: Option<f32>,
+}
+
+/// Read `auto-detected language: es (p = 0.97)` from `whisper-cli` output.
+fn parse_detected_language(output: &str) -> Option<DetectedLanguage> {
+    let rest = output.split("auto-detected language:").nth(1)?.trim_start();
+    let code: String = rest.chars().take_while(|c| c.is_ascii_alphabetic()).collect();
+    if code.is_empty() {
+        return None;
     }
+    let probability = rest
+        .split("p = ")
+        .nth(1)
+        .and_then(|p| p.split(')').next())
+        .and_then(|p| p.trim().parse().ok());
+    Some(DetectedLanguage { code, probability })
 }
 
-// Learn more about Tauri commands at https://tauri.app/develop/calling-rust/
-#[tauri::command]
-fn greet(name: &str) -> String {
-    format!("Hello, {}! You've been greeted from Rust!", name)
+fn detect_language_locally(wav_bytes: &[u8], model_path: &Path) -> Result<DetectedLanguage, String> {
+    #[cfg(feature = "whisper-rs")]
+    match whisper_engine::decode_wav(wav_bytes)
+        .and_then(|samples| whisper_engine::detect_language(model_path, &samples))
+    {
+        Ok((code, probability)) => {
+            return Ok(DetectedLanguage {
+                code,
+                probability: Some(probability),
+            })
+        }
+        Err(err) => log_line(&format!("[whisper-rs] {}; falling back to whisper-cli", err)),
+    }
+
+    let cli_path = resolve_whisper_cli()?;
+    let wav_file = temp_files::write_wav(wav_bytes)?;
+    let mut command = Command::new(&cli_path);
+    acceleration::apply_cli_flags(&mut command);
+    command
+        .arg("-m")
+        .arg(model_path)
+        .arg("-l")
+        .arg("auto")
+        .arg("-dl")
+        .arg("-f")
+        .arg(wav_file.path());
+    let output = ProcessRunner::new("whisper-cli", command).run();
+    drop(wav_file);
+    let output = output?;
+    let combined = format!(
+        "{}\n{}",
+        String::from_utf8_lossy(&output.stderr),
+        String::from_utf8_lossy(&output.stdout)
+    );
+    parse_detected_language(&combined).ok_or_else(|| {
+        format!(
+            "whisper-cli did not report a language: {}",
+            truncate_for_log(combined.trim(), 300)
+        )
+    })
 }
 
+/// Guess the spoken language of a clip with the local whisper model, or
+/// the first downloaded multilingual one if the active model is English-only.
 #[tauri::command]
-fn transcribe_audio(audio_base64: String) -> Result<String, String> {
-    log_line(&format!(
-        "transcribe_audio called (payload bytes: {})",
-        audio_base64.len()
-    ));
-    let wav_bytes = general_purpose::STANDARD
-        .decode(audio_base64.as_bytes())
-        .map_err(|err| format!("invalid audio payload: {}", err))?;
-
-    let wav_path = write_temp_wav(&wav_bytes)?;
+#[specta::specta]
+async fn detect_language(app: tauri::AppHandle, audio_base64: String) -> Result<DetectedLanguage, String> {
+    let wav_bytes = decode_audio_payload(&audio_base64).await?;
+    let mut model_path = whisper_models::resolve(&app, None)?;
+    if language::is_english_only_model(&model_path) {
+        model_path = whisper_models::find_multilingual(&app).ok_or_else(|| {
+            "detecting the language needs a multilingual model; download one under Settings → Whisper Models"
+                .to_string()
+        })?;
+    }
+    let detected = tauri::async_runtime::spawn_blocking(move || {
+        whisper_models::ensure_intact(&app, &model_path)?;
+        detect_language_locally(&wav_bytes, &model_path)
+    })
+        .await
+        .map_err(|err| format!("language detection task failed: {}", err))??;
+    log_line(&format!("detected language: {} ({:?})", detected.code, detected.probability));
+    Ok(detected)
+}
+
+/// Transcribe a WAV segment locally and return the cleaned transcript: in
+/// process when built with the `whisper-rs` feature, otherwise (or if that
+/// fails) with the bundled `whisper-cli`, using the model at `model_path`
+/// (see `whisper_models::resolve`) and `language` (a code or `auto`).
+/// With `translate` the transcript is an English translation.
+/// `on_segment` sees each segment as soon as whisper prints it.
+fn transcribe_wav_locally(
+    wav_bytes: &[u8],
+    model_path: &Path,
+    language: &str,
+    prompt: Option<&str>,
+    translate: bool,
+    options: &settings::TranscriptionOptions,
+    on_segment: Option<SegmentSink>,
+) -> Result<String, String> {
     log_line(&format!(
         "current_dir: {}",
         env::current_dir()
             .map(|path| path.display().to_string())
             .unwrap_or_else(|_| "unknown".to_string())
     ));
-    let cli_path = resolve_whisper_cli()?;
-    let model_path = resolve_whisper_model()?;
+    if language != "en" && language::is_english_only_model(model_path) {
+        log_line(&format!(
+            "whisper model {} is English-only; language {} needs a multilingual model",
+            model_path.display(),
+            language
+        ));
+    }
+
+    if *options != settings::TranscriptionOptions::default() {
+        log_line(&format!("whisper options: {:?}", options));
+    }
+
+    #[cfg(feature = "whisper-rs")]
+    let engine_started = Instant::now();
+    #[cfg(feature = "whisper-rs")]
+    match whisper_engine::decode_wav(wav_bytes).and_then(|samples| {
+        whisper_engine::transcribe(
+            model_path,
+            &samples,
+            language,
+            prompt,
+            translate,
+            options,
+            on_segment.clone(),
+        )
+    }) {
+        Ok(segments) => {
+            transcription_metrics::note_decode(transcription_metrics::IN_PROCESS, engine_started.elapsed());
+            let transcript = segments
+                .iter()
+                .filter_map(|segment| clean_transcript_fragment(segment.trim()))
+                .collect::<Vec<_>>()
+                .join(" ");
+            log_line(&format!("whisper-rs transcript: {}", transcript));
+            return Ok(transcript);
+        }
+        Err(err) => log_line(&format!("[whisper-rs] {}; falling back to whisper-cli", err)),
+    }
 
+    let cli_path = resolve_whisper_cli()?;
+    let write_started = Instant::now();
+    let wav_file = temp_files::write_wav(wav_bytes)?;
+    transcription_metrics::note_temp_file(write_started.elapsed());
     log_line(&format!(
         "whisper-cli: {} | model: {} | wav: {}",
         cli_path.display(),
         model_path.display(),
-        wav_path.display()
+        wav_file.path().display()
     ));
 
-    let whisper_logprob_thold = parse_env_float("HEYJAMIE_WHISPER_LOGPROB_THOLD", -2.0, 1.0);
-    let whisper_no_speech_thold = parse_env_float("HEYJAMIE_WHISPER_NO_SPEECH_THOLD", 0.0, 1.0);
-    if let Some(value) = whisper_logprob_thold {
-        log_line(&format!("whisper logprob threshold override: {:.2}", value));
-    }
-    if let Some(value) = whisper_no_speech_thold {
-        log_line(&format!("whisper no-speech threshold override: {:.2}", value));
-    }
-
     let mut command = Command::new(&cli_path);
+    acceleration::apply_cli_flags(&mut command);
     command
         .arg("-m")
-        .arg(&model_path)
+        .arg(model_path)
+        .arg("-l")
+        .arg(language)
         .arg("-f")
-        .arg(&wav_path)
+        .arg(wav_file.path())
         .arg("-nt")
         .arg("-sns")
         .arg("-np");
-    if let Some(value) = whisper_logprob_thold {
+    if let Some(prompt) = prompt {
+        command.arg("--prompt").arg(prompt);
+    }
+    if translate {
+        command.arg("-tr");
+    }
+    if let Some(value) = options.logprob_thold {
         command.arg("-lpt").arg(format!("{:.2}", value));
     }
-    if let Some(value) = whisper_no_speech_thold {
+    if let Some(value) = options.no_speech_thold {
         command.arg("-nth").arg(format!("{:.2}", value));
     }
+    if let Some(value) = options.beam_size {
+        command.arg("-bs").arg(value.to_string());
+    }
+    if let Some(value) = options.temperature {
+        command.arg("-tp").arg(format!("{:.2}", value));
+    }
+    if let Some(value) = options.temperature_inc {
+        command.arg("-tpi").arg(format!("{:.2}", value));
+    }
+    if let Some(value) = options.entropy_thold {
+        command.arg("-et").arg(format!("{:.2}", value));
+    }
+    if let Some(value) = options.max_segment_len.filter(|value| *value > 0) {
+        command.arg("-ml").arg(value.to_string());
+    }
 
-    let output = command
-        .output()
-        .map_err(|err| format!("failed to run whisper-cli: {}", err))?;
+    let run_started = Instant::now();
+    let mut runner = ProcessRunner::new("whisper-cli", command);
+    if let Some(sink) = on_segment {
+        // The CLI prints every segment as soon as it is decoded.
+        runner = runner.on_stdout_line(move |line| {
+            let fragment = extract_transcript(line);
+            if !fragment.is_empty() {
+                sink(&fragment);
+            }
+        });
+    }
+    let output = runner.run();
+    transcription_metrics::note_decode("whisper-cli", run_started.elapsed());
 
-    let _ = fs::remove_file(&wav_path);
+    drop(wav_file);
+    let output = output?;
 
     if !output.status.success() {
         let stderr = String::from_utf8_lossy(&output.stderr);
         log_line(&format!("whisper-cli failed: {}", stderr.trim()));
-        return Err(format!("whisper-cli failed: {}", stderr));
+        return Err(whisper_models::explain_load_failure(&stderr, model_path)
+            .unwrap_or_else(|| format!("whisper-cli failed: {}", stderr)));
     }
 
     let stdout = String::from_utf8_lossy(&output.stdout);
     let stderr = String::from_utf8_lossy(&output.stderr);
+    acceleration::note_cli_output(&stderr);
     let transcript = extract_transcript(&stdout);
     log_line(&format!("whisper-cli stdout bytes: {}", stdout.len()));
     if !stderr.trim().is_empty() {
@@ -258,9 +807,10 @@ fn transcribe_audio(audio_base64: String) -> Result<String, String> {
 }
 
 #[tauri::command]
-fn check_whisper() -> WhisperStatus {
+#[specta::specta]
+fn check_whisper(app: tauri::AppHandle) -> WhisperStatus {
     let cli_path = find_whisper_cli();
-    let model_path = find_whisper_model();
+    let model_path = whisper_models::resolve(&app, None).ok();
 
     WhisperStatus {
         cli_found: cli_path.is_some(),
@@ -271,6 +821,7 @@ fn check_whisper() -> WhisperStatus {
 }
 
 #[tauri::command]
+#[specta::specta]
 fn check_excalidraw(app: tauri::AppHandle) -> ExcalidrawStatus {
     let install_path = find_excalidraw_install_path(&app);
     let dir_found = install_path.is_dir();
@@ -286,6 +837,7 @@ fn check_excalidraw(app: tauri::AppHandle) -> ExcalidrawStatus {
 }
 
 #[tauri::command]
+#[specta::specta]
 fn setup_excalidraw() -> Result<String, String> {
     let manifest_dir = PathBuf::from(env!("CARGO_MANIFEST_DIR"));
     let root_dir = manifest_dir
@@ -303,10 +855,9 @@ fn setup_excalidraw() -> Result<String, String> {
         script_path.display()
     ));
 
-    let output = Command::new(&script_path)
-        .current_dir(&root_dir)
-        .output()
-        .map_err(|err| format!("failed to run setup script: {}", err))?;
+    let mut command = Command::new(&script_path);
+    command.current_dir(&root_dir);
+    let output = ProcessRunner::new("setup-excalidraw.sh", command).run()?;
 
     let stdout = String::from_utf8_lossy(&output.stdout);
     let stderr = String::from_utf8_lossy(&output.stderr);
@@ -330,7 +881,8 @@ fn setup_excalidraw() -> Result<String, String> {
 }
 
 #[tauri::command]
-fn setup_whisper() -> Result<String, String> {
+#[specta::specta]
+fn setup_whisper(app: tauri::AppHandle) -> Result<String, String> {
     let manifest_dir = PathBuf::from(env!("CARGO_MANIFEST_DIR"));
     let root_dir = manifest_dir
         .parent()
@@ -341,16 +893,16 @@ fn setup_whisper() -> Result<String, String> {
     if !script_path.exists() {
         return Err("setup-whisper.sh not found in scripts/".to_string());
     }
+    preflight::ensure(&app, preflight::PreflightOperation::ModelDownload, &root_dir)?;
 
     log_line(&format!(
         "Running whisper setup script: {}",
         script_path.display()
     ));
 
-    let output = Command::new(&script_path)
-        .current_dir(&root_dir)
-        .output()
-        .map_err(|err| format!("failed to run setup script: {}", err))?;
+    let mut command = Command::new(&script_path);
+    command.current_dir(&root_dir);
+    let output = ProcessRunner::new("setup-whisper.sh", command).run()?;
 
     let stdout = String::from_utf8_lossy(&output.stdout);
     let stderr = String::from_utf8_lossy(&output.stderr);
@@ -374,7 +926,9 @@ fn setup_whisper() -> Result<String, String> {
 }
 
 #[tauri::command]
-async fn fetch_url(url: String) -> Result<String, String> {
+#[specta::specta]
+async fn fetch_url(app: tauri::AppHandle, url: String) -> Result<String, String> {
+    let _permit = politeness::acquire(&app, &url).await?;
     let client = reqwest::Client::builder()
         .timeout(std::time::Duration::from_secs(10))
         .build()
@@ -395,42 +949,31 @@ async fn fetch_url(url: String) -> Result<String, String> {
 }
 
 #[tauri::command]
+#[specta::specta]
 fn log_frontend(message: String) {
     log_line(&format!("[frontend] {}", message));
 }
 
 #[tauri::command]
+#[specta::specta]
 fn browser_control(app: tauri::AppHandle, action: String) -> Result<(), String> {
     log_line(&format!("[browser-control] {}", action));
-    app.emit_to("main", "browser-control", action)
+    BrowserControl(action)
+        .emit_to(&app, "main")
         .map_err(|err| err.to_string())
 }
 
 #[tauri::command]
+#[specta::specta]
 fn cancel_llm_agent(state: tauri::State<'_, LlmAgentState>) {
     state.cancel_requested.store(true, Ordering::SeqCst);
 }
 
-/// Send SIGTERM first to allow graceful MCP client cleanup, then SIGKILL
-/// if the process hasn't exited within the grace period.
-fn graceful_kill(child: &mut std::process::Child) {
-    let pid = child.id() as i32;
-    // Send SIGTERM so the Node.js process can close MCP clients cleanly.
-    unsafe { libc::kill(pid, libc::SIGTERM); }
-
-    // Wait up to 2 seconds for graceful exit.
-    for _ in 0..40 {
-        match child.try_wait() {
-            Ok(Some(_)) => return, // exited cleanly
-            Ok(None) => {}
-            Err(_) => break,
-        }
-        std::thread::sleep(Duration::from_millis(50));
-    }
-
-    // Still running — force kill.
-    let _ = child.kill();
-    let _ = child.wait(); // reap to ensure pipe cleanup before returning
+/// Cancel agent runs in flight and wait for their processes to exit.
+fn cancel_agent_runs(app: &tauri::AppHandle, timeout: Duration) {
+    let state = app.state::<LlmAgentState>();
+    state.cancel_requested.store(true, Ordering::SeqCst);
+    shutdown::wait_until(timeout, || state.running.load(Ordering::SeqCst) == 0);
 }
 
 fn test_log_path() -> PathBuf {
@@ -444,6 +987,7 @@ fn test_log_path() -> PathBuf {
 }
 
 #[tauri::command]
+#[specta::specta]
 fn reset_test_log(run_label: Option<String>) -> Result<String, String> {
     let path = test_log_path();
     if let Some(parent) = path.parent() {
@@ -451,15 +995,12 @@ fn reset_test_log(run_label: Option<String>) -> Result<String, String> {
     }
 
     let mut file = fs::File::create(&path).map_err(|err| format!("failed to reset log: {}", err))?;
-    let stamp = SystemTime::now()
-        .duration_since(UNIX_EPOCH)
-        .map_err(|err| err.to_string())?
-        .as_millis();
+    let stamp = clock::timestamp(now_millis());
     let label = run_label
         .unwrap_or_else(|| "integration-run".to_string())
         .trim()
         .to_string();
-    let header = format!("# HeyJamie Integration Test Log [{}] {}\n", stamp, label);
+    let header = format!("# HeyJamie Integration Test Log [{} / {}] {}\n", stamp.utc, stamp.local, label);
     file.write_all(header.as_bytes())
         .map_err(|err| format!("failed to write log header: {}", err))?;
 
@@ -467,6 +1008,7 @@ fn reset_test_log(run_label: Option<String>) -> Result<String, String> {
 }
 
 #[tauri::command]
+#[specta::specta]
 fn append_test_log(line: String) -> Result<(), String> {
     let path = test_log_path();
     if let Some(parent) = path.parent() {
@@ -519,11 +1061,16 @@ fn default_mcp_config() -> String {
     serde_json::to_string_pretty(&value).unwrap_or_else(|_| "{}".to_string())
 }
 
+/// `mcp.json`, or in guest mode a separate one in the guest profile so the
+/// owner's servers and their keys stay out of reach.
 fn mcp_config_path(app: &tauri::AppHandle) -> Result<PathBuf, String> {
-    let dir = app
-        .path()
-        .app_config_dir()
-        .map_err(|err| format!("failed to resolve app config dir: {}", err))?;
+    let dir = if guest_mode::is_active() {
+        guest_mode::guest_dir(app)?
+    } else {
+        app.path()
+            .app_config_dir()
+            .map_err(|err| format!("failed to resolve app config dir: {}", err))?
+    };
     fs::create_dir_all(&dir).map_err(|err| format!("failed to create config dir: {}", err))?;
     Ok(dir.join("mcp.json"))
 }
@@ -532,6 +1079,12 @@ fn mcp_config_path(app: &tauri::AppHandle) -> Result<PathBuf, String> {
 /// 1. Replace old "browseros" HTTP entry with "chrome-devtools" stdio entry.
 /// 2. Strip "--isolated" and "--auto-connect" flags — Chrome instance reuse is
 ///    handled at runtime by the Node agent layer (via DevToolsActivePort probe).
+/// 3. Add stealth Chrome flags and drop deprecated ones.
+///
+/// Every migration that changes the file is recorded via
+/// `release_notes::record_migrations` so users can see silent rewrites, and
+/// in `mcp_history` so they can be undone. Migrations the user undid are
+/// skipped.
 fn ensure_mcp_config_migrated(path: &std::path::Path) {
     let content = match fs::read_to_string(path) {
         Ok(c) => c,
@@ -546,10 +1099,15 @@ fn ensure_mcp_config_migrated(path: &std::path::Path) {
         None => return,
     };
 
-    let mut changed = false;
+    let mut applied: Vec<release_notes::AppliedMigration> = Vec::new();
+    let declined = mcp_history::declined_migrations(path);
+    let allowed = |id: &str| !declined.iter().any(|declined| declined == id);
 
     // Migration 1: browseros → chrome-devtools
-    if servers.contains_key("browseros") && !servers.contains_key("chrome-devtools") {
+    if allowed("mcp-browseros-to-chrome-devtools")
+        && servers.contains_key("browseros")
+        && !servers.contains_key("chrome-devtools")
+    {
         servers.remove("browseros");
         servers.insert(
             "chrome-devtools".to_string(),
@@ -559,11 +1117,17 @@ fn ensure_mcp_config_migrated(path: &std::path::Path) {
             }),
         );
         log_line("[mcp] migrated old \"browseros\" config to \"chrome-devtools\"");
-        changed = true;
+        applied.push(release_notes::AppliedMigration::new(
+            "mcp-browseros-to-chrome-devtools",
+            "Replaced the \"browseros\" HTTP server with the \"chrome-devtools\" stdio server.",
+        ));
     }
 
     // Migration 2: strip --isolated / --auto-connect (runtime handles reuse)
-    if let Some(entry) = servers.get_mut("chrome-devtools") {
+    if let Some(entry) = servers
+        .get_mut("chrome-devtools")
+        .filter(|_| allowed("mcp-chrome-devtools-strip-isolated"))
+    {
         if let Some(args) = entry.get_mut("args").and_then(|v| v.as_array_mut()) {
             let before_len = args.len();
             args.retain(|a| {
@@ -572,7 +1136,10 @@ fn ensure_mcp_config_migrated(path: &std::path::Path) {
             });
             if args.len() != before_len {
                 log_line("[mcp] stripped --isolated/--auto-connect from chrome-devtools config (runtime handles Chrome reuse)");
-                changed = true;
+                applied.push(release_notes::AppliedMigration::new(
+                    "mcp-chrome-devtools-strip-isolated",
+                    "Removed --isolated/--auto-connect from chrome-devtools args; HeyJamie now reuses its own Chrome instance.",
+                ));
             }
         }
     }
@@ -582,51 +1149,117 @@ fn ensure_mcp_config_migrated(path: &std::path::Path) {
         if let Some(args) = entry.get_mut("args").and_then(|v| v.as_array_mut()) {
             // Remove deprecated Chrome flags from earlier migrations
             let before_len = args.len();
-            args.retain(|a| {
-                let s = a.as_str().unwrap_or("");
-                s != "--chromeArg=--disable-infobars"
-                    && s != "--chromeArg=--disable-blink-features=AutomationControlled"
-            });
+            if allowed("mcp-chrome-devtools-drop-deprecated-flags") {
+                args.retain(|a| {
+                    let s = a.as_str().unwrap_or("");
+                    s != "--chromeArg=--disable-infobars"
+                        && s != "--chromeArg=--disable-blink-features=AutomationControlled"
+                });
+            }
             if args.len() != before_len {
-                changed = true;
+                applied.push(release_notes::AppliedMigration::new(
+                    "mcp-chrome-devtools-drop-deprecated-flags",
+                    "Removed deprecated --chromeArg flags from chrome-devtools args.",
+                ));
             }
 
             let stealth_flags: &[&str] = &[
                 "--ignore-default-chrome-arg=--enable-automation",
             ];
             let mut added_stealth = false;
-            for &flag in stealth_flags {
-                if !args.iter().any(|a| a.as_str() == Some(flag)) {
-                    args.push(JsonValue::String(flag.to_string()));
-                    added_stealth = true;
+            if allowed("mcp-chrome-devtools-stealth-flags") {
+                for &flag in stealth_flags {
+                    if !args.iter().any(|a| a.as_str() == Some(flag)) {
+                        args.push(JsonValue::String(flag.to_string()));
+                        added_stealth = true;
+                    }
                 }
             }
             if added_stealth {
                 log_line("[mcp] added stealth Chrome flags to chrome-devtools config");
-                changed = true;
+                applied.push(release_notes::AppliedMigration::new(
+                    "mcp-chrome-devtools-stealth-flags",
+                    "Added --ignore-default-chrome-arg=--enable-automation to hide the automation banner.",
+                ));
             }
         }
     }
 
-    if changed {
+    if !applied.is_empty() {
         if let Ok(migrated) = serde_json::to_string_pretty(&root) {
-            let _ = fs::write(path, migrated.as_bytes());
+            if fs::write(path, migrated.as_bytes()).is_ok() {
+                let ids = applied.iter().map(|migration| migration.id.clone()).collect();
+                mcp_history::record(path, Some(&content), &migrated, mcp_history::McpEditSource::Migration, ids);
+            }
         }
+        release_notes::record_migrations(path, applied);
     }
 }
 
-#[tauri::command]
-fn get_mcp_config(app: tauri::AppHandle) -> Result<McpConfigResponse, String> {
-    let path = mcp_config_path(&app)?;
+/// Path of `mcp.json`, written with the default config when missing and
+/// migrated.
+fn prepare_mcp_config(app: &tauri::AppHandle) -> Result<PathBuf, String> {
+    let path = mcp_config_path(app)?;
     if !path.exists() {
         let content = default_mcp_config();
         fs::write(&path, content.as_bytes())
             .map_err(|err| format!("failed to write default config: {}", err))?;
+        mcp_history::record(&path, None, &content, mcp_history::McpEditSource::Default, Vec::new());
     }
-
     ensure_mcp_config_migrated(&path);
+    Ok(path)
+}
+
+/// The raw MCP config, for bundles from before the typed server commands.
+#[tauri::command]
+#[specta::specta]
+fn get_mcp_config(
+    app: tauri::AppHandle,
+    window: tauri::WebviewWindow,
+    api: tauri::State<'_, api_version::ApiVersionState>,
+) -> Result<McpConfigResponse, String> {
+    api.note_deprecated_call(window.label(), "get_mcp_config");
+    let path = prepare_mcp_config(&app)?;
+    let content = fs::read_to_string(&path).map_err(|err| format!("failed to read config: {}", err))?;
+    Ok(McpConfigResponse {
+        path: path.display().to_string(),
+        content,
+    })
+}
+
+/// Save a whole MCP config from an older bundle's JSON editor. Only
+/// `mcpServers` is taken, and each server is validated like
+/// `add_mcp_server`.
+#[tauri::command]
+#[specta::specta]
+fn save_mcp_config(
+    app: tauri::AppHandle,
+    window: tauri::WebviewWindow,
+    api: tauri::State<'_, api_version::ApiVersionState>,
+    content: String,
+) -> Result<McpConfigResponse, String> {
+    api.note_deprecated_call(window.label(), "save_mcp_config");
+    let parsed: JsonValue = serde_json::from_str(&content).map_err(|err| format!("invalid JSON: {}", err))?;
+    let entries = parsed
+        .get("mcpServers")
+        .and_then(JsonValue::as_object)
+        .ok_or_else(|| "\"mcpServers\" is missing or not an object".to_string())?;
+    let listing = mcp_servers::replace_all(&app, entries)?;
+    let content = fs::read_to_string(&listing.path).map_err(|err| format!("failed to read config: {}", err))?;
+    Ok(McpConfigResponse {
+        path: listing.path,
+        content,
+    })
+}
 
-    let content = fs::read_to_string(&path).unwrap_or_else(|_| default_mcp_config());
+/// Undo the last change to the MCP config, including automatic
+/// migrations, which then stay off until redone.
+#[tauri::command]
+#[specta::specta]
+fn mcp_config_undo(app: tauri::AppHandle) -> Result<McpConfigResponse, String> {
+    let path = mcp_config_path(&app)?;
+    let content = mcp_history::step(&path, false)?;
+    mcp_supervisor::reload(&app);
     Ok(McpConfigResponse {
         path: path.display().to_string(),
         content,
@@ -634,21 +1267,25 @@ fn get_mcp_config(app: tauri::AppHandle) -> Result<McpConfigResponse, String> {
 }
 
 #[tauri::command]
-fn save_mcp_config(app: tauri::AppHandle, content: String) -> Result<McpConfigResponse, String> {
-    let parsed: JsonValue =
-        serde_json::from_str(&content).map_err(|err| format!("invalid JSON: {}", err))?;
-    let pretty = serde_json::to_string_pretty(&parsed)
-        .map_err(|err| format!("failed to format JSON: {}", err))?;
+#[specta::specta]
+fn mcp_config_redo(app: tauri::AppHandle) -> Result<McpConfigResponse, String> {
     let path = mcp_config_path(&app)?;
-    fs::write(&path, pretty.as_bytes())
-        .map_err(|err| format!("failed to write config: {}", err))?;
+    let content = mcp_history::step(&path, true)?;
+    mcp_supervisor::reload(&app);
     Ok(McpConfigResponse {
         path: path.display().to_string(),
-        content: pretty,
+        content,
     })
 }
 
 #[tauri::command]
+#[specta::specta]
+fn get_mcp_config_history(app: tauri::AppHandle) -> Result<mcp_history::McpHistoryStatus, String> {
+    Ok(mcp_history::status(&mcp_config_path(&app)?))
+}
+
+#[tauri::command]
+#[specta::specta]
 async fn test_mcp_config(app: tauri::AppHandle) -> Result<String, String> {
     let app = app.clone();
     tauri::async_runtime::spawn_blocking(move || {
@@ -668,25 +1305,14 @@ async fn test_mcp_config(app: tauri::AppHandle) -> Result<String, String> {
             "mode": "mcp-test",
             "mcpConfigPath": mcp_path.display().to_string()
         });
+        let account_env = auth::account_token_env(&app, &fs::read_to_string(&mcp_path).unwrap_or_default());
 
-        let mut child = Command::new("node")
-            .arg(script_path)
-            .current_dir(&root_dir)
-            .stdin(Stdio::piped())
-            .stdout(Stdio::piped())
-            .stderr(Stdio::piped())
-            .spawn()
-            .map_err(|err| format!("failed to start mcp test: {}", err))?;
-
-        if let Some(mut stdin) = child.stdin.take() {
-            stdin
-                .write_all(request.to_string().as_bytes())
-                .map_err(|err| format!("failed to write mcp test input: {}", err))?;
-        }
-
-        let output = child
-            .wait_with_output()
-            .map_err(|err| format!("failed to read mcp test output: {}", err))?;
+        let mut command = sandbox::command(&app, "node");
+        command.arg(script_path).current_dir(&root_dir);
+        let output = ProcessRunner::new("mcp test", command)
+            .envs(account_env)
+            .input(request.to_string())
+            .run()?;
 
         if !output.status.success() {
             let stderr = String::from_utf8_lossy(&output.stderr);
@@ -710,16 +1336,48 @@ async fn test_mcp_config(app: tauri::AppHandle) -> Result<String, String> {
 }
 
 #[tauri::command]
+#[specta::specta]
 async fn run_llm_agent(
     app: tauri::AppHandle,
     payload: LlmAgentRequest,
     state: tauri::State<'_, LlmAgentState>,
 ) -> Result<String, String> {
     let app = app.clone();
+    let trace_id = payload
+        .trace_id
+        .as_deref()
+        .and_then(trace::sanitize)
+        .unwrap_or_else(trace::new_id);
+    let visible_mode = matches!(
+        payload.mode.as_deref(),
+        Some("browseros-act" | "browseros-navigate" | "excalidraw-act")
+    );
+    if visible_mode
+        && trace::scope(&trace_id, || {
+            recording_segment::hold(
+                &app,
+                recording_segment::HeldKind::Agent,
+                &truncate_for_log(&payload.prompt, 200),
+            )
+        })
+    {
+        return Err("held until the recording segment ends".to_string());
+    }
+    if shutdown::is_shutting_down() {
+        return Err("HeyJamie is quitting".to_string());
+    }
     state.cancel_requested.store(false, Ordering::SeqCst);
     let cancel_requested = state.cancel_requested.clone();
-    tauri::async_runtime::spawn_blocking(move || {
-        log_line("[llm-agent] starting request");
+    let running = state.running.clone();
+    running.fetch_add(1, Ordering::SeqCst);
+    // Agenda research is itself background work; everything else is waited on.
+    let _burst = (payload.mode.as_deref() != Some("agenda-research"))
+        .then(|| background::burst(&app, background::BurstKind::AgentRun));
+    let result = tauri::async_runtime::spawn_blocking(move || trace::scope(&trace_id.clone(), || {
+        log_line(&format!(
+            "[llm-agent] starting request (mode {})",
+            payload.mode.as_deref().unwrap_or("general")
+        ));
         let manifest_dir = PathBuf::from(env!("CARGO_MANIFEST_DIR"));
         let root_dir = manifest_dir
             .parent()
@@ -732,164 +1390,185 @@ async fn run_llm_agent(
 
         let mcp_path = mcp_config_path(&app)?;
         ensure_mcp_config_migrated(&mcp_path);
-        let request = serde_json::json!({
-            "mode": payload.mode,
-            "settings": {
-                "apiKey": payload.settings.api_key,
-                "model": payload.settings.model,
-                "reasoning": payload.settings.reasoning
-            },
-            "instructions": payload.instructions,
-            "prompt": payload.prompt,
-            "context": payload.context,
-            "mcpConfigPath": mcp_path.display().to_string()
-        });
-
-        let mut child = Command::new("node")
-            .arg(script_path)
-            .current_dir(&root_dir)
-            .stdin(Stdio::piped())
-            .stdout(Stdio::piped())
-            .stderr(Stdio::piped())
-            .spawn()
-            .map_err(|err| format!("failed to start llm agent: {}", err))?;
-
-        if let Some(mut stdin) = child.stdin.take() {
-            stdin
-                .write_all(request.to_string().as_bytes())
-                .map_err(|err| format!("failed to write llm agent input: {}", err))?;
+        // Canvas elements are linked to the transcript position at the time
+        // the request was made, not when the (slow) run finishes.
+        let session_moment = app.state::<sessions::SessionState>().current_moment();
+        let app_settings = settings::load_settings(&app);
+        let session_state = app.state::<sessions::SessionState>();
+        let response_language = language::resolve_response_language(
+            &app_settings.language,
+            session_state.active_title().as_deref(),
+            &language::request_utterance(
+                payload.context.as_ref(),
+                session_moment.as_ref().and_then(|m| m.last_segment.as_ref()).map(|s| s.text.as_str()),
+            ),
+        );
+        if let Some(language) = response_language.as_ref() {
+            log_line(&format!("[llm-agent] response language: {}", language.name));
         }
-
-        let mut stdout = child
-            .stdout
-            .take()
-            .ok_or_else(|| "failed to capture llm agent stdout".to_string())?;
-        let stderr = child
-            .stderr
-            .take()
-            .ok_or_else(|| "failed to capture llm agent stderr".to_string())?;
-        let stderr_thread = std::thread::spawn(move || {
-            let reader = BufReader::new(stderr);
-            for line_result in reader.lines() {
-                match line_result {
-                    Ok(line) => {
-                        if !line.trim().is_empty() {
-                            log_line(&format!(
-                                "[llm-agent] stderr: {}",
-                                truncate_for_log(&line, 600)
-                            ));
-                        }
-                    }
-                    Err(_) => break,
-                }
-            }
-        });
-        let is_browseros_mode = payload.mode.as_deref() == Some("browseros-act");
-        let is_navigate_mode = payload.mode.as_deref() == Some("browseros-navigate");
+        let failover_state = app.state::<failover::FailoverState>();
+        let resolved_mode = agent_modes::resolve(payload.mode.as_deref(), &app_settings.agent_modes.custom);
+        let account_env = auth::account_token_env(&app, &fs::read_to_string(&mcp_path).unwrap_or_default());
+        docs_cache::prune_expired(&app);
         let is_excalidraw_mode = payload.mode.as_deref() == Some("excalidraw-act");
-        let is_intent_mode = payload.mode.as_deref() == Some("browseros-intent");
-        let is_topic_shift_mode = payload.mode.as_deref() == Some("topic-shift-detect");
-        let default_timeout_ms: u128 = if is_browseros_mode {
-            180_000
-        } else if is_navigate_mode {
-            30_000
-        } else if is_excalidraw_mode {
-            120_000
-        } else if is_intent_mode {
-            90_000
-        } else if is_topic_shift_mode {
-            15_000
-        } else {
-            45_000
-        };
-        let timeout_env_key = if is_browseros_mode || is_navigate_mode {
-            "HEYJAMIE_BROWSEROS_TIMEOUT_MS"
-        } else if is_excalidraw_mode {
-            "HEYJAMIE_EXCALIDRAW_TIMEOUT_MS"
-        } else if is_intent_mode {
-            "HEYJAMIE_INTENT_TIMEOUT_MS"
-        } else if is_topic_shift_mode {
-            "HEYJAMIE_TOPIC_SHIFT_TIMEOUT_MS"
-        } else {
-            "HEYJAMIE_LLM_TIMEOUT_MS"
-        };
-        let timeout_ms: u128 = env::var(timeout_env_key)
-            .ok()
-            .and_then(|raw| raw.trim().parse::<u128>().ok())
-            .filter(|ms| *ms >= 1_000)
-            .unwrap_or(default_timeout_ms);
+        let timeout_ms = resolved_mode.timeout_ms;
         let started_at = Instant::now();
-        let mut terminal_error: Option<String> = None;
-
-        loop {
-            if cancel_requested.load(Ordering::SeqCst) {
-                graceful_kill(&mut child);
-                log_line("[llm-agent] cancelled");
-                terminal_error = Some("llm agent cancelled".to_string());
-                break;
+        // Endpoints that failed this run; the run moves on to the next one.
+        let mut failed_endpoints: Vec<String> = Vec::new();
+        let mut failover_reason: Option<String> = None;
+        let stdout_text = loop {
+            let endpoint = failover::select_llm_endpoint(&failover_state, &app_settings, &failed_endpoints);
+            if let Some(endpoint) = endpoint.as_ref() {
+                log_line(&format!(
+                    "[llm-agent] using endpoint {} ({})",
+                    endpoint.name, endpoint.base_url
+                ));
+                failover_state.note_active(
+                    &app,
+                    failover::FailoverService::Llm,
+                    endpoint.base_url.trim(),
+                    failover_reason.take(),
+                );
             }
+            let request = serde_json::json!({
+                "mode": payload.mode,
+                "customMode": resolved_mode.custom,
+                "settings": {
+                    "apiKey": endpoint
+                        .as_ref()
+                        .and_then(|e| e.api_key.clone())
+                        .unwrap_or_else(|| payload.settings.api_key.clone()),
+                    "model": endpoint
+                        .as_ref()
+                        .and_then(|e| e.model.clone())
+                        .unwrap_or_else(|| payload.settings.model.clone()),
+                    "reasoning": payload.settings.reasoning,
+                    "baseUrl": endpoint.as_ref().map(|e| e.base_url.trim().to_string())
+                },
+                "instructions": payload.instructions,
+                "prompt": payload.prompt,
+                "context": payload.context,
+                "responseLanguage": response_language,
+                "mcpConfigPath": mcp_path.display().to_string(),
+                "traceId": trace_id
+            });
 
-            if started_at.elapsed().as_millis() > timeout_ms {
-                graceful_kill(&mut child);
-                log_line(&format!("[llm-agent] timed out after {}ms", timeout_ms));
-                terminal_error = Some(format!("llm agent timed out after {}ms", timeout_ms));
-                break;
+            let mut command = sandbox::command(&app, "node");
+            command.arg(&script_path).current_dir(&root_dir);
+            let stderr_trace = trace_id.clone();
+            let output = ProcessRunner::new("llm agent", command)
+                .envs(account_env.clone())
+                .envs(docs_cache::agent_env(&app))
+                .envs(politeness::agent_env(&app))
+                .envs(artifacts::agent_env(&app))
+                .input(request.to_string())
+                .timeout(Duration::from_millis(timeout_ms as u64))
+                .cancel(cancel_requested.clone())
+                .on_stderr_line(move |line| {
+                    if !line.trim().is_empty() {
+                        trace::scope(&stderr_trace, || {
+                            log_line(&format!("[llm-agent] stderr: {}", truncate_for_log(line, 600)))
+                        });
+                    }
+                })
+                .run()?;
+            let stdout_text = String::from_utf8_lossy(&output.stdout).trim().to_string();
+            if stdout_text.is_empty() {
+                return Err("llm agent returned empty output".to_string());
             }
-
-            match child.try_wait() {
-                Ok(Some(_status)) => break,
-                Ok(None) => {}
-                Err(err) => {
-                    terminal_error = Some(format!("failed to poll llm agent: {}", err));
-                    break;
+            let Some(endpoint) = endpoint else {
+                break stdout_text;
+            };
+            let key = endpoint.base_url.trim().to_string();
+            let agent_error = serde_json::from_str::<JsonValue>(&stdout_text)
+                .ok()
+                .and_then(|v| v.get("error").and_then(|e| e.as_str()).map(str::to_string));
+            match agent_error {
+                Some(err) if failover::looks_like_endpoint_failure(&err) => {
+                    failover_state.record_failure(&key, &err);
+                    failed_endpoints.push(key);
+                    if cancel_requested.load(Ordering::SeqCst)
+                        || failover::select_llm_endpoint(&failover_state, &app_settings, &failed_endpoints).is_none()
+                    {
+                        break stdout_text;
+                    }
+                    log_line(&format!("[llm-agent] {} failed ({}); retrying on the next endpoint", endpoint.name, err));
+                    failover_reason = Some(err);
+                }
+                _ => {
+                    failover_state.record_success(&key);
+                    break stdout_text;
                 }
             }
+        };
 
-            std::thread::sleep(std::time::Duration::from_millis(50));
-        }
-
-        if let Some(error_message) = terminal_error {
-            let _ = stderr_thread.join();
-            return Err(error_message);
+        if is_excalidraw_mode {
+            if let Some(moment) = session_moment.as_ref() {
+                canvas_provenance::record_from_agent_output(&app, moment, &stdout_text);
+            }
         }
-
-        let mut stdout_text = String::new();
-        if let Err(err) = stdout.read_to_string(&mut stdout_text) {
-            let _ = stderr_thread.join();
-            return Err(format!("failed to read llm agent stdout: {}", err));
+        docs_cache::record_from_agent_output(&app, &stdout_text);
+        scripting::fire(
+            &app,
+            scripting::ON_AGENT_RESULT,
+            serde_json::json!({
+                "mode": payload.mode.as_deref().unwrap_or("general"),
+                "prompt": payload.prompt,
+                "traceId": trace_id,
+                "output": serde_json::from_str::<JsonValue>(&stdout_text)
+                    .unwrap_or_else(|_| JsonValue::String(stdout_text.clone())),
+            }),
+        );
+        if let Some(moment) = session_moment.as_ref() {
+            let mut run = session_report::run_from_output(
+                payload.mode.as_deref().unwrap_or("general"),
+                started_at.elapsed().as_millis() as u64,
+                &stdout_text,
+            );
+            run.trace_id = Some(trace_id.clone());
+            session_report::record_agent_run(&app, &moment.session_id, &run);
         }
-        let _ = stderr_thread.join();
-
-        let stdout_text = stdout_text.trim().to_string();
-        if stdout_text.is_empty() {
-            return Err("llm agent returned empty output".to_string());
+        if let Some(exceeded) = serde_json::from_str::<JsonValue>(&stdout_text)
+            .ok()
+            .and_then(|v| v.pointer("/budget/exceeded").filter(|e| !e.is_null()).cloned())
+        {
+            log_line(&format!("[llm-agent] tool budget exceeded: {}", exceeded));
         }
 
         log_line("[llm-agent] completed");
         Ok(stdout_text)
-    })
+    }))
     .await
-    .map_err(|err| format!("llm agent task failed: {}", err))?
+    .map_err(|err| format!("llm agent task failed: {}", err));
+    running.fetch_sub(1, Ordering::SeqCst);
+    result?
 }
 
 #[cfg(desktop)]
 #[tauri::command]
-fn open_browser_window(_app: tauri::AppHandle, url: String, new_tab: bool) -> Result<(), String> {
+#[specta::specta]
+fn open_browser_window(app: tauri::AppHandle, url: String, new_tab: bool) -> Result<(), String> {
     let parsed = Url::parse(&url).map_err(|err| format!("invalid url: {}", err))?;
     let target = parsed.to_string();
+    if recording_segment::hold(&app, recording_segment::HeldKind::Url, &target) {
+        return Ok(());
+    }
     let launcher = launch_external_url(&target, new_tab)?;
     log_line(&format!("[browser] opened via {}: {}", launcher, target));
+    sessions::note_session_browser_url(app.state(), target);
     Ok(())
 }
 
 #[cfg(not(desktop))]
 #[tauri::command]
+#[specta::specta]
 fn open_browser_window(_app: tauri::AppHandle, _url: String, _new_tab: bool) -> Result<(), String> {
     Err("Browser window not supported on mobile.".to_string())
 }
 
 #[cfg(all(desktop, target_os = "macos"))]
 #[tauri::command]
+#[specta::specta]
 fn focus_chrome_window() -> Result<(), String> {
     let script = r#"tell application "Google Chrome" to activate"#;
     if run_browser_launcher("osascript", &["-e", script]).is_ok() {
@@ -900,6 +1579,7 @@ fn focus_chrome_window() -> Result<(), String> {
 
 #[cfg(all(desktop, target_os = "linux"))]
 #[tauri::command]
+#[specta::specta]
 fn focus_chrome_window() -> Result<(), String> {
     // Best-effort: try xdotool, silently succeed if unavailable
     let _ = std::process::Command::new("xdotool")
@@ -910,18 +1590,21 @@ fn focus_chrome_window() -> Result<(), String> {
 
 #[cfg(all(desktop, not(any(target_os = "macos", target_os = "linux"))))]
 #[tauri::command]
+#[specta::specta]
 fn focus_chrome_window() -> Result<(), String> {
     Ok(())
 }
 
 #[cfg(not(desktop))]
 #[tauri::command]
+#[specta::specta]
 fn focus_chrome_window() -> Result<(), String> {
     Ok(())
 }
 
 #[cfg(all(desktop, target_os = "macos"))]
 #[tauri::command]
+#[specta::specta]
 fn reload_chrome_tab(url_prefix: String) -> Result<(), String> {
     let script = format!(
         r#"tell application "Google Chrome"
@@ -947,18 +1630,21 @@ end tell"#,
 
 #[cfg(all(desktop, not(target_os = "macos")))]
 #[tauri::command]
+#[specta::specta]
 fn reload_chrome_tab(_url_prefix: String) -> Result<(), String> {
     Err("not supported".to_string())
 }
 
 #[cfg(not(desktop))]
 #[tauri::command]
+#[specta::specta]
 fn reload_chrome_tab(_url_prefix: String) -> Result<(), String> {
     Err("not supported".to_string())
 }
 
 #[cfg(all(desktop, target_os = "macos"))]
 #[tauri::command]
+#[specta::specta]
 fn focus_chrome_tab(url_prefix: String) -> Result<(), String> {
     let app_name = env::var("HEYJAMIE_BROWSEROS_APP_NAME")
         .unwrap_or_else(|_| "Google Chrome".to_string());
@@ -987,12 +1673,14 @@ end tell"#,
 
 #[cfg(all(desktop, not(target_os = "macos")))]
 #[tauri::command]
+#[specta::specta]
 fn focus_chrome_tab(_url_prefix: String) -> Result<(), String> {
     Err("not supported".to_string())
 }
 
 #[cfg(not(desktop))]
 #[tauri::command]
+#[specta::specta]
 fn focus_chrome_tab(_url_prefix: String) -> Result<(), String> {
     Err("not supported".to_string())
 }
@@ -1070,18 +1758,6 @@ fn launch_external_url(_url: &str, _new_tab: bool) -> Result<String, String> {
     Err("unsupported platform for external URL launch".to_string())
 }
 
-fn write_temp_wav(bytes: &[u8]) -> Result<PathBuf, String> {
-    let tmp_dir = env::temp_dir();
-    let stamp = SystemTime::now()
-        .duration_since(UNIX_EPOCH)
-        .map_err(|err| err.to_string())?
-        .as_millis();
-    let path = tmp_dir.join(format!("heyjamie-{}.wav", stamp));
-    fs::write(&path, bytes).map_err(|err| err.to_string())?;
-    log_line(&format!("wrote wav segment to {}", path.display()));
-    Ok(path)
-}
-
 fn resolve_whisper_cli() -> Result<PathBuf, String> {
     if let Some(path) = find_whisper_cli() {
         return Ok(path);
@@ -1138,16 +1814,20 @@ fn find_whisper_model() -> Option<PathBuf> {
 
     let cwd = env::current_dir().ok()?;
     let manifest_dir = PathBuf::from(env!("CARGO_MANIFEST_DIR"));
-    let candidates = [
-        cwd.join("whisper_cpp/models/ggml-base.en.bin"),
-        cwd.join("../whisper_cpp/models/ggml-base.en.bin"),
-        cwd.join("whisper.cpp/models/ggml-base.en.bin"),
-        cwd.join("../whisper.cpp/models/ggml-base.en.bin"),
-        manifest_dir.join("../whisper_cpp/models/ggml-base.en.bin"),
-        manifest_dir.join("../whisper.cpp/models/ggml-base.en.bin"),
+    let dirs = [
+        cwd.join("whisper_cpp/models"),
+        cwd.join("../whisper_cpp/models"),
+        cwd.join("whisper.cpp/models"),
+        cwd.join("../whisper.cpp/models"),
+        manifest_dir.join("../whisper_cpp/models"),
+        manifest_dir.join("../whisper.cpp/models"),
     ];
+    // The full model, or failing that a quantized one of the same size.
+    let files: Vec<String> = std::iter::once("ggml-base.en.bin".to_string())
+        .chain(model_variants::QUANTIZATIONS.iter().map(|quantization| format!("ggml-base.en-{}.bin", quantization)))
+        .collect();
 
-    for candidate in candidates {
+    for candidate in dirs.iter().flat_map(|dir| files.iter().map(move |file| dir.join(file))) {
         if candidate.exists() {
             log_line(&format!("whisper model found at {}", candidate.display()));
             return Some(candidate);
@@ -1177,9 +1857,9 @@ fn find_excalidraw_install_path(app: &tauri::AppHandle) -> PathBuf {
                         .and_then(|e| e.get("cwd"))
                         .and_then(|v| v.as_str())
                     {
-                        if cwd.starts_with("~/") {
+                        if let Some(rest) = cwd.strip_prefix("~/") {
                             if let Some(home) = dirs::home_dir() {
-                                return home.join(&cwd[2..]);
+                                return home.join(rest);
                             }
                         }
                         return PathBuf::from(cwd);
@@ -1195,18 +1875,6 @@ fn find_excalidraw_install_path(app: &tauri::AppHandle) -> PathBuf {
         .unwrap_or_else(|| PathBuf::from("mcp_excalidraw"))
 }
 
-fn parse_env_float(name: &str, min: f32, max: f32) -> Option<f32> {
-    let value = env::var(name).ok()?;
-    let parsed = value.trim().parse::<f32>().ok()?;
-    if !parsed.is_finite() {
-        return None;
-    }
-    if parsed < min || parsed > max {
-        return None;
-    }
-    Some(parsed)
-}
-
 fn extract_transcript(output: &str) -> String {
     let mut lines = Vec::new();
 
@@ -1238,7 +1906,7 @@ fn extract_transcript(output: &str) -> String {
     lines.join(" ")
 }
 
-fn clean_transcript_fragment(text: &str) -> Option<String> {
+pub(crate) fn clean_transcript_fragment(text: &str) -> Option<String> {
     let mut cleaned = text.trim();
     while let Some(rest) = cleaned.strip_prefix(">>") {
         cleaned = rest.trim_start();
@@ -1252,7 +1920,7 @@ fn clean_transcript_fragment(text: &str) -> Option<String> {
     if collapsed.is_empty() || is_low_information_fragment(&collapsed) {
         None
     } else {
-        Some(collapsed)
+        Some(redaction::apply(&collapsed).into_owned())
     }
 }
 
@@ -1288,22 +1956,7 @@ fn is_non_speech_marker(text: &str) -> bool {
         return true;
     }
 
-    let phrase = words.join(" ");
-    if matches!(
-        phrase.as_str(),
-        "blank_audio"
-            | "blank audio"
-            | "music"
-            | "inaudible"
-            | "speaking in a foreign language"
-            | "foreign language"
-    ) {
-        return true;
-    }
-
-    words
-        .iter()
-        .all(|word| word == "inaudible" || word == "music")
+    transcript_filters::active().is_non_speech(&words)
 }
 
 fn is_low_information_fragment(text: &str) -> bool {
@@ -1312,14 +1965,10 @@ fn is_low_information_fragment(text: &str) -> bool {
         return true;
     }
 
-    if words.len() == 1 {
-        return matches!(words[0].as_str(), "you");
-    }
-
-    words.len() <= 3 && words.iter().all(|word| word == "you")
+    transcript_filters::active().is_low_information(text, &words)
 }
 
-fn normalize_fragment_tokens(text: &str) -> Vec<String> {
+pub(crate) fn normalize_fragment_tokens(text: &str) -> Vec<String> {
     text.split_whitespace()
         .map(|token| {
             token
@@ -1342,7 +1991,16 @@ fn normalize_fragment_tokens(text: &str) -> Vec<String> {
 
 #[cfg(test)]
 mod transcript_tests {
-    use super::{clean_transcript_fragment, extract_transcript, is_non_speech_marker};
+    use super::{clean_transcript_fragment, extract_transcript, is_non_speech_marker, parse_detected_language};
+
+    #[test]
+    fn parses_detected_language() {
+        let stderr = "whisper_full_with_state: auto-detected language: es (p = 0.973267)\n";
+        let detected = parse_detected_language(stderr).unwrap();
+        assert_eq!(detected.code, "es");
+        assert_eq!(detected.probability, Some(0.973267));
+        assert!(parse_detected_language("whisper_init_from_file: loading model").is_none());
+    }
 
     #[test]
     fn strips_non_speech_samples() {
@@ -1389,21 +2047,65 @@ fn log_path() -> PathBuf {
     env::temp_dir().join("heyjamie.log")
 }
 
-fn truncate_for_log(text: &str, max_len: usize) -> String {
+/// `text` cut to at most `max_len` bytes, on a character boundary.
+pub(crate) fn truncate_for_log(text: &str, max_len: usize) -> String {
     if text.len() <= max_len {
         return text.to_string();
     }
-    format!("{}…", &text[..max_len])
+    let mut end = max_len;
+    while !text.is_char_boundary(end) {
+        end -= 1;
+    }
+    format!("{}…", &text[..end])
 }
 
-fn log_line(message: &str) {
-    let stamp = SystemTime::now()
-        .duration_since(UNIX_EPOCH)
-        .ok()
-        .map(|duration| duration.as_millis())
-        .unwrap_or_default();
-    let line = format!("[{}] {}\n", stamp, message);
+#[cfg(test)]
+mod transcribe_response_tests {
+    use super::{TranscribeAudioResponse, Transcription};
+
+    #[test]
+    fn v1_clients_get_the_bare_text() {
+        let transcription = Transcription {
+            text: "hola".to_string(),
+            translation: Some("hello".to_string()),
+        };
+        assert_eq!(
+            serde_json::to_value(TranscribeAudioResponse::for_client(1, transcription.clone())).unwrap(),
+            serde_json::json!("hola")
+        );
+        assert_eq!(
+            serde_json::to_value(TranscribeAudioResponse::for_client(2, transcription)).unwrap(),
+            serde_json::json!({ "text": "hola", "translation": "hello" })
+        );
+    }
+}
+
+#[cfg(test)]
+mod log_tests {
+    use super::truncate_for_log;
+
+    #[test]
+    fn truncates_on_a_char_boundary() {
+        assert_eq!(truncate_for_log("short", 10), "short");
+        assert_eq!(truncate_for_log("abcdef", 3), "abc…");
+        // "é" is two bytes; cutting at byte 2 would split it.
+        assert_eq!(truncate_for_log("aéb", 2), "a…");
+        assert_eq!(truncate_for_log("日本語", 4), "日…");
+    }
+}
+
+pub(crate) use clock::now_millis;
+
+pub(crate) fn log_line(message: &str) {
+    let line = match trace::current() {
+        Some(id) => format!("[{}] [trace:{}] {}\n", clock::utc_string(now_millis()), id, message),
+        None => format!("[{}] {}\n", clock::utc_string(now_millis()), message),
+    };
     eprint!("{}", line);
+    // Guest transcripts and URLs must not outlive guest mode.
+    if guest_mode::is_active() {
+        return;
+    }
     if let Ok(mut file) = fs::OpenOptions::new()
         .create(true)
         .append(true)
@@ -1423,6 +2125,13 @@ fn build_menu<R: tauri::Runtime>(app: &tauri::AppHandle<R>) -> tauri::Result<Men
         true,
         Some("CmdOrCtrl+,"),
     )?;
+    let segment_item = MenuItem::with_id(
+        app,
+        "toggle_recording_segment",
+        "Start/End Recording Segment",
+        true,
+        Some("CmdOrCtrl+Shift+R"),
+    )?;
 
     let mut file_submenu = None;
     if let Ok(items) = menu.items() {
@@ -1438,8 +2147,9 @@ fn build_menu<R: tauri::Runtime>(app: &tauri::AppHandle<R>) -> tauri::Result<Men
 
     if let Some(submenu) = file_submenu {
         submenu.insert(&settings_item, 0)?;
+        submenu.insert(&segment_item, 1)?;
     } else {
-        let file_menu = Submenu::with_items(app, "File", true, &[&settings_item])?;
+        let file_menu = Submenu::with_items(app, "File", true, &[&settings_item, &segment_item])?;
         menu.insert(&file_menu, 0)?;
     }
 
@@ -1447,7 +2157,7 @@ fn build_menu<R: tauri::Runtime>(app: &tauri::AppHandle<R>) -> tauri::Result<Men
 }
 
 #[cfg(desktop)]
-fn open_settings_window<R: tauri::Runtime>(app: &tauri::AppHandle<R>) -> tauri::Result<()> {
+fn open_settings_window(app: &tauri::AppHandle) -> tauri::Result<()> {
     if let Some(window) = app.get_webview_window("settings") {
         let _ = window.show();
         let _ = window.set_focus();
@@ -1460,23 +2170,43 @@ fn open_settings_window<R: tauri::Runtime>(app: &tauri::AppHandle<R>) -> tauri::
         .min_inner_size(520.0, 600.0)
         .disable_drag_drop_handler()
         .build()
-        .map(|_| ())
+        .map(|window| ui_scale::apply(app, &window))
 }
 
 #[cfg(desktop)]
 #[tauri::command]
-fn open_settings_window_command(app: tauri::AppHandle) -> Result<(), String> {
+#[specta::specta]
+fn show_settings_window(app: tauri::AppHandle) -> Result<(), String> {
     open_settings_window(&app).map_err(|err| format!("failed to open settings window: {}", err))
 }
 
 #[cfg(not(desktop))]
 #[tauri::command]
-fn open_settings_window_command() -> Result<(), String> {
+#[specta::specta]
+fn show_settings_window() -> Result<(), String> {
     Err("not supported".to_string())
 }
 
+/// Old name of `show_settings_window`, kept for cached frontend bundles.
+#[tauri::command]
+#[specta::specta]
+fn open_settings_window_command(
+    app: tauri::AppHandle,
+    window: tauri::WebviewWindow,
+    api: tauri::State<'_, api_version::ApiVersionState>,
+) -> Result<(), String> {
+    api.note_deprecated_call(window.label(), "open_settings_window_command");
+    #[cfg(desktop)]
+    return show_settings_window(app);
+    #[cfg(not(desktop))]
+    {
+        let _ = app;
+        show_settings_window()
+    }
+}
+
 #[cfg(desktop)]
-fn open_dev_settings_window<R: tauri::Runtime>(app: &tauri::AppHandle<R>) -> tauri::Result<()> {
+fn open_dev_settings_window(app: &tauri::AppHandle) -> tauri::Result<()> {
     if let Some(window) = app.get_webview_window("dev-settings") {
         let _ = window.show();
         let _ = window.set_focus();
@@ -1493,10 +2223,11 @@ fn open_dev_settings_window<R: tauri::Runtime>(app: &tauri::AppHandle<R>) -> tau
     .min_inner_size(480.0, 400.0)
     .disable_drag_drop_handler()
     .build()
-    .map(|_| ())
+    .map(|window| ui_scale::apply(app, &window))
 }
 
 #[tauri::command]
+#[specta::specta]
 fn get_personas_dir() -> String {
     let manifest_dir = env!("CARGO_MANIFEST_DIR");
     let project_root = std::path::Path::new(manifest_dir)
@@ -1527,6 +2258,7 @@ fn find_file_submenu<R: tauri::Runtime>(
 
 #[cfg(desktop)]
 #[tauri::command]
+#[specta::specta]
 fn set_dev_settings_menu_visible(app: tauri::AppHandle, visible: bool) -> Result<(), String> {
     let file_submenu = find_file_submenu(&app).ok_or("File submenu not found")?;
     let items = file_submenu.items().map_err(|e| e.to_string())?;
@@ -1560,39 +2292,170 @@ fn set_dev_settings_menu_visible(app: tauri::AppHandle, visible: bool) -> Result
 
 #[cfg(not(desktop))]
 #[tauri::command]
+#[specta::specta]
 fn set_dev_settings_menu_visible(_visible: bool) -> Result<(), String> {
     Ok(())
 }
 
-#[cfg_attr(mobile, tauri::mobile_entry_point)]
-pub fn run() {
-    log_line(&format!(
-        "HeyJamie starting. Log file: {}",
-        log_path().display()
-    ));
-    let builder = tauri::Builder::default()
-        .plugin(tauri_plugin_opener::init())
-        .manage(LlmAgentState::default())
-        .manage(ExcalidrawServerState {
-            child: Mutex::new(None),
-        })
-        .setup(|app| {
-            let child = start_excalidraw_server(app.handle());
-            let state = app.state::<ExcalidrawServerState>();
-            *state.child.lock().unwrap() = child;
-            Ok(())
-        })
-        .invoke_handler(tauri::generate_handler![
+/// Path of the generated frontend bindings, relative to this crate.
+#[cfg(debug_assertions)]
+const BINDINGS_PATH: &str = concat!(env!("CARGO_MANIFEST_DIR"), "/../src/bindings.ts");
+
+/// Every command and event exposed to the webview. `src/bindings.ts` is
+/// generated from this list, so new commands must be registered here.
+fn specta_builder() -> tauri_specta::Builder<tauri::Wry> {
+    tauri_specta::Builder::<tauri::Wry>::new()
+        .commands(tauri_specta::collect_commands![
             greet,
+            api_version::negotiate_api_version,
             transcribe_audio,
+            transcribe_audio_stream,
+            diarization::transcribe_audio_diarized,
+            transcript_detail::transcribe_audio_detailed,
+            detect_language,
+            settings::get_app_settings,
+            settings::save_app_settings,
+            failover::get_endpoint_health,
+            release_notes::get_release_notes,
+            release_notes::mark_release_notes_seen,
+            preflight::preflight_check,
+            sessions::start_session,
+            sessions::end_session,
+            sessions::get_active_session,
+            sessions::list_sessions,
+            sessions::get_session_transcript,
+            sessions::record_transcript_segment,
+            sessions::revise_transcript_segment,
+            sessions::delete_transcript_segment,
+            sessions::note_session_browser_url,
+            bookmarks::bookmark_moment,
+            bookmarks::get_bookmarks,
+            canvas_provenance::link_canvas_elements,
+            canvas_provenance::get_canvas_provenance,
+            canvas_boards::list_boards,
+            canvas_boards::create_board,
+            canvas_boards::switch_board,
+            canvas_snapshots::list_canvas_snapshots,
+            push_to_talk::get_push_to_talk_status,
+            push_to_talk::register_push_to_talk,
+            push_to_talk::unregister_push_to_talk,
+            dictation::get_dictation_status,
+            dictation::set_dictation_active,
+            webhooks::get_webhook_status,
+            agenda::list_agenda_items,
+            agenda::add_agenda_item,
+            agenda::remove_agenda_item,
+            agenda::retry_agenda_item,
+            agenda::get_agenda_status,
+            agenda::get_session_prep,
+            agenda::run_agenda_item,
+            long_form::transcribe_long_audio,
+            focus_gate::get_mic_gate,
+            idle_pause::set_idle_watch,
+            idle_pause::note_user_activity,
+            transcription_backend::set_transcription_api_key,
+            transcription_backend::has_transcription_api_key,
+            transcription_queue::get_transcription_queue,
+            scripting::list_scripts,
+            scripting::reload_scripts,
+            audio_devices::list_audio_devices,
+            audio_devices::set_audio_device,
+            background::get_background_activity,
+            jobs::list_jobs,
+            jobs::resume_job,
+            jobs::discard_job,
+            session_audio::get_session_audio,
+            session_audio::read_session_audio_segment,
+            session_audio::delete_session_audio,
+            transcript_versions::retranscribe_session,
+            transcript_versions::list_transcript_versions,
+            transcript_versions::get_transcript_version,
+            sandbox::get_sandbox_profile,
+            auth::connect_account,
+            auth::disconnect_account,
+            auth::list_accounts,
+            export::export_session,
+            artifacts::reveal_session_folder,
+            artifacts::export_session_bundle,
+            export::record_research_table,
+            email_digest::email_session_summary,
+            email_digest::set_smtp_password,
+            post_session::run_post_session_pipeline,
+            post_session::get_post_session_status,
+            post_session::set_slack_webhook,
+            whisper_models::list_whisper_models,
+            whisper_models::download_whisper_model,
+            whisper_models::delete_whisper_model,
+            whisper_models::verify_whisper_setup,
+            whisper_benchmark::benchmark_whisper,
+            model_variants::prepare_model_variant,
+            topic_graph::record_topic,
+            topic_graph::get_topic_graph,
+            docs_cache::get_docs_cache_stats,
+            docs_cache::clear_docs_cache,
+            session_report::get_session_report,
+            ui_scale::set_ui_scale,
+            power::get_power_state,
+            system_load::get_load_pressure,
+            timecode::sync_session_clock,
+            timecode::anchor_session_ltc,
+            timecode::get_session_timecode,
+            recording_segment::set_recording_segment,
+            recording_segment::get_recording_segment,
+            recording_segment::hold_for_review,
+            recording_segment::take_held_responses,
+            recording::start_recording,
+            recording::stop_recording,
+            settings_transfer::export_settings,
+            settings_transfer::import_settings,
+            guest_mode::start_guest_mode,
+            guest_mode::end_guest_mode,
+            guest_mode::get_guest_mode,
+            scenarios::list_scenarios,
+            scenarios::run_scenario,
+            scenarios::cancel_scenario,
+            prompts::list_prompts,
+            prompts::run_prompt_on_text,
+            meeting::get_meeting,
+            file_transcription::transcribe_files,
+            file_transcription::cancel_file_transcription,
+            selfcheck::run_e2e_selfcheck,
+            analytics::get_analytics,
+            export::export_transcript,
+            history::save_history_session,
+            history::list_history,
+            history::search_history,
+            history::search_transcripts,
+            transcript_filters::get_transcript_filters,
+            transcript_filters::save_transcript_filters,
+            history::delete_history_session,
+            browser_sessions::cleanup_browser_sessions,
             check_whisper,
+            acceleration::get_transcription_capabilities,
+            transcription_metrics::get_transcription_metrics,
+            shutdown::confirm_shutdown_flushed,
+            whisper_worker::get_whisper_worker_status,
             setup_whisper,
             check_excalidraw,
             setup_excalidraw,
             log_frontend,
             browser_control,
+            mcp_servers::list_mcp_servers,
+            mcp_servers::add_mcp_server,
+            mcp_servers::update_mcp_server,
+            mcp_servers::remove_mcp_server,
+            mcp_servers::set_mcp_server_enabled,
+            mcp_supervisor::mcp_server_status,
+            mcp_supervisor::start_mcp_server,
+            mcp_supervisor::stop_mcp_server,
+            mcp_supervisor::restart_mcp_server,
             get_mcp_config,
             save_mcp_config,
+            mcp_config_undo,
+            mcp_config_redo,
+            get_mcp_config_history,
+            mcp_health::get_mcp_server_health,
+            mcp_health::check_mcp_server_health,
             run_llm_agent,
             test_mcp_config,
             cancel_llm_agent,
@@ -1604,18 +2467,173 @@ pub fn run() {
             append_test_log,
             fetch_url,
             get_personas_dir,
+            show_settings_window,
             open_settings_window_command,
-            set_dev_settings_menu_visible
-        ]);
+            set_dev_settings_menu_visible,
+        ])
+        .events(tauri_specta::collect_events![
+            BrowserControl,
+            TranscriptionPartial,
+            whisper_models::ModelDownloadProgress,
+            whisper_models::ModelIntegrity,
+            failover::EndpointFailover,
+            bookmarks::BookmarkCreated,
+            bookmarks::BookmarkJump,
+            auth::AccountChanged,
+            quick_capture::QuickCapture,
+            session_report::SessionReport,
+            app_control::AppControl,
+            power::PowerState,
+            system_load::LoadPressure,
+            recording_segment::RecordingSegment,
+            recording::RecordedTranscript,
+            recording::RecordingLevel,
+            guest_mode::GuestMode,
+            scenarios::ScenarioProgress,
+            clipboard::ClipboardSuggestion,
+            meeting::MeetingDetected,
+            file_transcription::FileTranscription,
+            browser_sessions::StaleBrowserSessions,
+            mcp_health::McpServerHealth,
+            mcp_supervisor::McpProcessChanged,
+            transcription_metrics::TranscriptionMetrics,
+            shutdown::AppShutdown,
+            canvas_boards::CanvasBoards,
+            topic_graph::TopicShift,
+            push_to_talk::PttDown,
+            push_to_talk::PttUp,
+            dictation::Dictation,
+            webhooks::WebhookTriggered,
+            agenda::AgendaDue,
+            focus_gate::MicGate,
+            idle_pause::IdlePause,
+            audio_devices::DeviceChanged,
+            mic_level::MicLevel,
+            jobs::JobChanged
+        ])
+        .error_handling(tauri_specta::ErrorHandlingMode::Throw)
+}
+
+/// Regenerate `src/bindings.ts`. Runs on every debug launch and from
+/// `cargo test`, so the checked-in file never drifts from the Rust types.
+#[cfg(debug_assertions)]
+fn export_bindings(builder: &tauri_specta::Builder<tauri::Wry>) -> Result<(), String> {
+    let exporter = specta_typescript::Typescript::default()
+        .bigint(specta_typescript::BigIntExportBehavior::Number)
+        .header("// @ts-nocheck\n/* eslint-disable */");
+    builder
+        .export(exporter, BINDINGS_PATH)
+        .map_err(|err| format!("failed to export TypeScript bindings: {}", err))
+}
+
+#[cfg(test)]
+mod bindings_tests {
+    #[test]
+    fn exports_typescript_bindings() {
+        super::export_bindings(&super::specta_builder()).expect("bindings export");
+    }
+}
+
+#[cfg_attr(mobile, tauri::mobile_entry_point)]
+pub fn run() {
+    log_line(&format!(
+        "HeyJamie starting. Log file: {}",
+        log_path().display()
+    ));
+    let specta = specta_builder();
+    #[cfg(debug_assertions)]
+    if let Err(err) = export_bindings(&specta) {
+        log_line(&err);
+    }
+
+    let builder = tauri::Builder::default()
+        .plugin(tauri_plugin_opener::init())
+        .plugin(tauri_plugin_global_shortcut::Builder::new().build())
+        .manage(LlmAgentState::default())
+        .manage(api_version::ApiVersionState::default())
+        .manage(failover::FailoverState::default())
+        .manage(sessions::SessionState::default())
+        .manage(auth::AuthState::default())
+        .manage(quick_capture::QuickCaptureState::default())
+        .manage(recording_segment::RecordingSegmentState::default())
+        .manage(power::PowerMonitorState::default())
+        .manage(system_load::SystemLoadState::default())
+        .manage(whisper_models::ModelDownloadState::default())
+        .manage(recording::RecordingState::default())
+        .manage(guest_mode::GuestModeState::default())
+        .manage(scenarios::ScenarioState::default())
+        .manage(meeting::MeetingMonitorState::default())
+        .manage(file_transcription::FileTranscriptionState::default())
+        .manage(politeness::PolitenessState::default())
+        .manage(transcription_metrics::TranscriptionMetricsState::default())
+        .manage(shutdown::ShutdownState::default())
+        .manage(whisper_worker::WhisperWorkerState::default())
+        .manage(canvas_boards::CanvasBoardsState::default())
+        .manage(push_to_talk::PushToTalkState::default())
+        .manage(dictation::DictationState::default())
+        .manage(transcription_queue::TranscriptionQueueState::default())
+        .manage(webhooks::WebhookState::default())
+        .manage(transcript_sync::TranscriptSyncState::default())
+        .manage(agenda::AgendaState::default())
+        .manage(focus_gate::FocusGateState::default())
+        .manage(idle_pause::IdlePauseState::default())
+        .manage(scripting::ScriptingState::default())
+        .manage(audio_devices::AudioDeviceState::default())
+        .manage(background::BackgroundState::default())
+        .manage(jobs::JobsState::default())
+        .manage(mcp_health::McpHealthState::default())
+        .manage(mcp_supervisor::McpSupervisorState::default())
+        .invoke_handler(specta.invoke_handler())
+        .setup(move |app| {
+            specta.mount_events(app);
+            mcp_supervisor::spawn_monitor(app.handle().clone());
+            failover::spawn_health_checks(app.handle().clone());
+            mcp_health::spawn_monitor(app.handle().clone());
+            power::spawn_monitor(app.handle().clone());
+            system_load::spawn_monitor(app.handle().clone());
+            whisper_models::spawn_verification(app.handle().clone());
+            jobs::recover(app.handle());
+            session_audio::spawn_prune(app.handle());
+            guest_mode::purge_leftovers(app.handle());
+            temp_files::cleanup_stale();
+            transcript_filters::reload(app.handle());
+            acceleration::reload(app.handle());
+            whisper_worker::reload(app.handle());
+            transcription_queue::reload(app.handle());
+            canvas_snapshots::listen(app.handle());
+            push_to_talk::reload(app.handle());
+            dictation::reload(app.handle());
+            webhooks::reload(app.handle());
+            scripting::reload(app.handle());
+            redaction::reload(app.handle());
+            agenda::spawn_scheduler(app.handle().clone());
+            clipboard::spawn_watcher(app.handle().clone());
+            meeting::spawn_monitor(app.handle().clone());
+            focus_gate::spawn_monitor(app.handle().clone());
+            idle_pause::spawn_monitor(app.handle().clone());
+            audio_devices::spawn_monitor(app.handle().clone());
+            history::spawn_backfill(app.handle().clone());
+            browser_sessions::spawn_startup_check(app.handle().clone());
+            if let Some(window) = app.get_webview_window("main") {
+                ui_scale::apply(app.handle(), &window);
+            }
+            #[cfg(desktop)]
+            if let Err(err) = quick_capture::install_tray(app.handle()) {
+                log_line(&format!("[quick-capture] failed to create tray icon: {}", err));
+            }
+            Ok(())
+        });
 
     #[cfg(desktop)]
     let builder = builder
-        .menu(|app| build_menu(app))
+        .menu(build_menu)
         .on_menu_event(|app, event| {
             if event.id() == "open_settings" {
                 if let Err(err) = open_settings_window(app) {
                     log_line(&format!("Failed to open settings window: {}", err));
                 }
+            } else if event.id() == "toggle_recording_segment" {
+                recording_segment::toggle(app);
             } else if event.id() == "open_dev_settings" {
                 if let Err(err) = open_dev_settings_window(app) {
                     log_line(&format!("Failed to open dev settings window: {}", err));
@@ -1627,18 +2645,9 @@ pub fn run() {
         .build(tauri::generate_context!())
         .expect("error while building tauri application");
 
-    app.run(|app_handle, event| {
-        if let tauri::RunEvent::Exit = event {
-            let child = app_handle
-                .state::<ExcalidrawServerState>()
-                .child
-                .lock()
-                .unwrap()
-                .take();
-            if let Some(mut child) = child {
-                log_line("[excalidraw] shutting down canvas server");
-                graceful_kill(&mut child);
-            }
-        }
+    app.run(|app_handle, event| match event {
+        tauri::RunEvent::ExitRequested { api, .. } => shutdown::on_exit_requested(app_handle, &api),
+        tauri::RunEvent::Exit => shutdown::on_exit(app_handle),
+        _ => {}
     });
 }
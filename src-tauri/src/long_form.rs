@@ -0,0 +1,194 @@
+//! Long recordings transcribed in overlapping windows. Each window shares
+//! a few seconds with the next, so a word cut at one window's edge is
+//! heard whole in the other; the transcripts are joined at the longest
+//! run of words they share, dropping the duplicate and the cut words.
+
+use std::ops::Range;
+
+use serde::{Deserialize, Serialize};
+
+use crate::settings::{self, LongFormSettings};
+use crate::transcription_queue::Lane;
+use crate::{jobs, log_line, wav};
+
+/// Words at each side of a join searched for the shared run.
+const STITCH_WORDS: usize = 40;
+/// Shorter shared runs are taken as chance, not overlap.
+const MIN_SHARED_WORDS: usize = 2;
+
+/// How far a windowed transcription got, saved after every window by
+/// file transcription jobs so a failed or interrupted one resumes there.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct Checkpoint {
+    /// The windowing it started with, kept so the windows line up.
+    pub long_form: LongFormSettings,
+    /// Length of the audio, to notice the file changed.
+    pub samples: usize,
+    pub windows_done: usize,
+    pub text: String,
+}
+
+/// Sample ranges of the windows covering `len` samples.
+fn windows(len: usize, sample_rate: u32, long_form: &LongFormSettings) -> Vec<Range<usize>> {
+    let window = (long_form.window_seconds as usize * sample_rate as usize).max(1);
+    let overlap = (long_form.overlap_seconds as usize * sample_rate as usize).min(window / 2);
+    let mut ranges = Vec::new();
+    let mut start = 0;
+    loop {
+        let end = (start + window).min(len);
+        ranges.push(start..end);
+        if end >= len {
+            return ranges;
+        }
+        start = end - overlap;
+    }
+}
+
+fn normalize(word: &str) -> String {
+    word.chars()
+        .filter(|c| c.is_alphanumeric())
+        .flat_map(char::to_lowercase)
+        .collect()
+}
+
+/// Join `next` onto `text` at the longest run of words the end of `text`
+/// and the start of `next` share, ignoring case and punctuation. The run
+/// is kept as `next` has it, since its punctuation fits what follows.
+/// Words of `text` after the run and of `next` before it were cut by the
+/// window edge and are dropped. Without a shared run the two are
+/// concatenated.
+fn stitch(text: &str, next: &str) -> String {
+    let words: Vec<&str> = text.split_whitespace().collect();
+    let next_words: Vec<&str> = next.split_whitespace().collect();
+    let tail_start = words.len().saturating_sub(STITCH_WORDS);
+    let tail: Vec<String> = words[tail_start..].iter().map(|word| normalize(word)).collect();
+    let head: Vec<String> = next_words.iter().take(STITCH_WORDS).map(|word| normalize(word)).collect();
+
+    // Longest common run: runs[i][j] ends at tail[i - 1] and head[j - 1].
+    let mut runs = vec![vec![0usize; head.len() + 1]; tail.len() + 1];
+    let mut best = (0, 0, 0);
+    for i in 1..=tail.len() {
+        for j in 1..=head.len() {
+            if !tail[i - 1].is_empty() && tail[i - 1] == head[j - 1] {
+                runs[i][j] = runs[i - 1][j - 1] + 1;
+                if runs[i][j] > best.0 {
+                    best = (runs[i][j], i, j);
+                }
+            }
+        }
+    }
+    let (length, tail_end, head_end) = best;
+    let (kept, added) = if length >= MIN_SHARED_WORDS {
+        (&words[..tail_start + tail_end - length], &next_words[head_end - length..])
+    } else {
+        (&words[..], &next_words[..])
+    };
+    kept.iter().chain(added).copied().collect::<Vec<_>>().join(" ")
+}
+
+/// Transcribe `wav_bytes` window by window and stitch the results. Audio
+/// that fits in one window, or isn't 16-bit mono PCM, goes to whisper
+/// whole.
+pub async fn transcribe(app: &tauri::AppHandle, wav_bytes: Vec<u8>, language: Option<String>) -> Result<String, String> {
+    transcribe_resumable(app, wav_bytes, language, None, None).await
+}
+
+/// `transcribe`, checkpointing job `job_id` after every window and
+/// starting from `resume` when it matches this audio.
+pub async fn transcribe_resumable(
+    app: &tauri::AppHandle,
+    wav_bytes: Vec<u8>,
+    language: Option<String>,
+    job_id: Option<&str>,
+    resume: Option<Checkpoint>,
+) -> Result<String, String> {
+    let Ok((sample_rate, samples)) = wav::decode_pcm16_mono(&wav_bytes) else {
+        return crate::transcribe_wav(app, wav_bytes, language, None, Lane::Batch).await;
+    };
+    let mut checkpoint = resume
+        .filter(|checkpoint| checkpoint.samples == samples.len())
+        .unwrap_or_else(|| Checkpoint {
+            long_form: settings::load_settings(app).transcription.long_form,
+            samples: samples.len(),
+            windows_done: 0,
+            text: String::new(),
+        });
+    let ranges = windows(samples.len(), sample_rate, &checkpoint.long_form);
+    if ranges.len() < 2 {
+        return crate::transcribe_wav(app, wav_bytes, language, None, Lane::Batch).await;
+    }
+    let total = ranges.len();
+    log_line(&format!(
+        "[long-form] {} s of audio in {} windows{}",
+        samples.len() / sample_rate.max(1) as usize,
+        total,
+        if checkpoint.windows_done > 0 {
+            format!(", resuming after window {}", checkpoint.windows_done)
+        } else {
+            String::new()
+        }
+    ));
+    for (index, range) in ranges.into_iter().enumerate().skip(checkpoint.windows_done) {
+        let window = wav::encode_pcm16_mono(sample_rate, &samples[range]);
+        let part = crate::transcribe_wav(app, window, language.clone(), None, Lane::Batch).await?;
+        log_line(&format!("[long-form] window {} done ({} chars)", index + 1, part.len()));
+        if !part.trim().is_empty() {
+            checkpoint.text = if checkpoint.text.is_empty() {
+                part.trim().to_string()
+            } else {
+                stitch(&checkpoint.text, &part)
+            };
+        }
+        checkpoint.windows_done = index + 1;
+        if let Some(job_id) = job_id {
+            jobs::checkpoint(app, job_id, &checkpoint, Some(checkpoint.windows_done as f32 / total as f32));
+        }
+    }
+    Ok(checkpoint.text)
+}
+
+/// Transcribe a whole recording from the webview (any length) with the
+/// long-recording windowing, instead of slicing it in the webview.
+/// `input` names the input track, for its preprocessing filters.
+#[tauri::command]
+#[specta::specta]
+pub async fn transcribe_long_audio(
+    app: tauri::AppHandle,
+    audio_base64: String,
+    language: Option<String>,
+    input: Option<String>,
+) -> Result<String, String> {
+    log_line(&format!("[long-form] transcribe_long_audio called (payload bytes: {})", audio_base64.len()));
+    let wav_bytes = crate::decode_audio_payload(&audio_base64).await?;
+    let wav_bytes = crate::preprocess_input(&app, input.as_deref(), wav_bytes);
+    transcribe(&app, wav_bytes, language).await
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{stitch, windows};
+    use crate::settings::LongFormSettings;
+
+    #[test]
+    fn overlapping_windows_stitch_without_duplicates() {
+        let long_form = LongFormSettings {
+            window_seconds: 10,
+            overlap_seconds: 2,
+        };
+        assert_eq!(windows(25, 1, &long_form), vec![0..10, 8..18, 16..25]);
+        assert_eq!(windows(10, 1, &long_form), vec![0..10]);
+
+        // The overlap is heard twice; its edges were cut mid-word.
+        assert_eq!(
+            stitch("so the main thing about whisper is conve", "ut whisper is context, really. That's why"),
+            "so the main thing about whisper is context, really. That's why"
+        );
+        assert_eq!(
+            stitch("We should find a cute Cat.", "a cute cat, and then a dog"),
+            "We should find a cute cat, and then a dog"
+        );
+        // A single shared word isn't enough to call it overlap.
+        assert_eq!(stitch("we talked about it", "it was fine"), "we talked about it it was fine");
+    }
+}
@@ -0,0 +1,111 @@
+//! System audio (what's playing through the speakers) as a capture source,
+//! so the remote side of a call is transcribed too. Each platform exposes
+//! it differently:
+//! - Windows: WASAPI loopback, which cpal opens as an input stream on the
+//!   default output device.
+//! - Linux: the PulseAudio/PipeWire monitor of the default sink, read
+//!   through the ALSA `pulse` device with `PULSE_SOURCE` pointed at it.
+//! - macOS: a loopback input device such as BlackHole. ScreenCaptureKit
+//!   audio isn't reachable through cpal.
+
+use serde::{Deserialize, Serialize};
+
+#[derive(Serialize, Deserialize, Clone, Copy, Debug, Default, PartialEq, Eq, specta::Type)]
+#[serde(rename_all = "kebab-case")]
+pub enum CaptureSource {
+    /// The default microphone.
+    #[default]
+    Microphone,
+    /// Only what's playing through the speakers.
+    System,
+    /// The microphone and system audio mixed, for calls on headphones.
+    Both,
+}
+
+impl CaptureSource {
+    pub fn microphone(self) -> bool {
+        self != CaptureSource::System
+    }
+
+    pub fn system(self) -> bool {
+        self != CaptureSource::Microphone
+    }
+}
+
+#[cfg(target_os = "windows")]
+pub fn system_device() -> Result<(cpal::Device, cpal::SupportedStreamConfig), String> {
+    use cpal::traits::{DeviceTrait, HostTrait};
+
+    let device = cpal::default_host()
+        .default_output_device()
+        .ok_or_else(|| "no output device to capture system audio from".to_string())?;
+    let config = device
+        .default_output_config()
+        .map_err(|err| format!("failed to read the output format: {}", err))?;
+    Ok((device, config))
+}
+
+/// `<default sink>.monitor`, via `pactl` (PulseAudio or PipeWire).
+#[cfg(target_os = "linux")]
+fn monitor_source() -> Result<String, String> {
+    let output = std::process::Command::new("pactl")
+        .arg("get-default-sink")
+        .output()
+        .map_err(|err| format!("pactl not found ({}); system audio needs PulseAudio or PipeWire", err))?;
+    let sink = String::from_utf8_lossy(&output.stdout).trim().to_string();
+    if !output.status.success() || sink.is_empty() {
+        return Err("pactl did not report a default output".to_string());
+    }
+    Ok(format!("{}.monitor", sink))
+}
+
+#[cfg(target_os = "linux")]
+pub fn system_device() -> Result<(cpal::Device, cpal::SupportedStreamConfig), String> {
+    use cpal::traits::{DeviceTrait, HostTrait};
+
+    let source = monitor_source()?;
+    // cpal opens ALSA handles while enumerating, so the variable only has
+    // to be set for the lookup; the microphone is opened without it.
+    std::env::set_var("PULSE_SOURCE", &source);
+    let device = cpal::default_host()
+        .input_devices()
+        .ok()
+        .and_then(|mut devices| devices.find(|device| device.name().is_ok_and(|name| name == "pulse")));
+    std::env::remove_var("PULSE_SOURCE");
+    let device = device.ok_or_else(|| {
+        "the ALSA pulse device is missing; install pipewire-alsa or pulseaudio-alsa".to_string()
+    })?;
+    let config = device
+        .default_input_config()
+        .map_err(|err| format!("failed to read the format of {}: {}", source, err))?;
+    crate::log_line(&format!("[recording] system audio from {}", source));
+    Ok((device, config))
+}
+
+/// Virtual devices that loop the system output back as an input.
+#[cfg(not(any(target_os = "windows", target_os = "linux")))]
+const LOOPBACK_DEVICES: &[&str] = &["blackhole", "loopback", "soundflower", "background music"];
+
+#[cfg(not(any(target_os = "windows", target_os = "linux")))]
+pub fn system_device() -> Result<(cpal::Device, cpal::SupportedStreamConfig), String> {
+    use cpal::traits::{DeviceTrait, HostTrait};
+
+    let device = cpal::default_host()
+        .input_devices()
+        .ok()
+        .and_then(|mut devices| {
+            devices.find(|device| {
+                device.name().is_ok_and(|name| {
+                    let name = name.to_lowercase();
+                    LOOPBACK_DEVICES.iter().any(|loopback| name.contains(loopback))
+                })
+            })
+        })
+        .ok_or_else(|| {
+            "no loopback device found; install BlackHole and send the call's output to it".to_string()
+        })?;
+    let config = device
+        .default_input_config()
+        .map_err(|err| format!("failed to read the loopback device format: {}", err))?;
+    Ok((device, config))
+}
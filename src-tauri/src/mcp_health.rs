@@ -0,0 +1,399 @@
+//! Liveness checks for the MCP servers in `mcp.json` and the Excalidraw
+//! canvas server, so a server that is running but wedged shows up instead
+//! of only one that exited. URL servers get an HTTP JSON-RPC `ping`, stdio
+//! servers are started and pinged over stdin (less often, since that means
+//! a fresh process), and the canvas server gets a TCP connect.
+
+use std::collections::BTreeMap;
+use std::io::{BufRead, BufReader, Write};
+use std::net::{TcpStream, ToSocketAddrs};
+use std::path::PathBuf;
+use std::process::{Command, Stdio};
+use std::sync::{mpsc, Mutex};
+use std::time::{Duration, Instant};
+
+use serde::{Deserialize, Serialize};
+use serde_json::{json, Value as JsonValue};
+use tauri::Manager;
+use tauri_specta::Event;
+
+use crate::{background, excalidraw_canvas_url, graceful_kill, load_mcp_config_value, log_line, now_millis, power, sandbox};
+
+const CHECK_INTERVAL: Duration = Duration::from_secs(30);
+/// Stdio servers are pinged every this many rounds (five minutes).
+const STDIO_EVERY: u32 = 10;
+const HTTP_TIMEOUT: Duration = Duration::from_secs(5);
+const TCP_TIMEOUT: Duration = Duration::from_secs(2);
+/// Generous, since `npx -y` may download the server first.
+const STDIO_TIMEOUT: Duration = Duration::from_secs(30);
+/// Name the canvas server is reported under.
+pub const CANVAS_SERVER: &str = "excalidraw-canvas";
+
+#[derive(Serialize, Deserialize, Clone, Copy, Debug, PartialEq, Eq, specta::Type)]
+#[serde(rename_all = "kebab-case")]
+pub enum ProbeKind {
+    Http,
+    Stdio,
+    Tcp,
+}
+
+#[derive(Serialize, Deserialize, Clone, Copy, Debug, PartialEq, Eq, specta::Type)]
+#[serde(rename_all = "kebab-case")]
+pub enum Liveness {
+    Alive,
+    Down,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, specta::Type)]
+#[serde(rename_all = "camelCase")]
+pub struct ServerHealth {
+    pub name: String,
+    pub probe: ProbeKind,
+    pub liveness: Liveness,
+    pub checked_at: u64,
+    pub latency_ms: Option<u64>,
+    pub consecutive_failures: u32,
+    pub last_error: Option<String>,
+}
+
+/// Payload of the `mcp-server-health` event, sent when a server goes up
+/// or down.
+#[derive(Serialize, Deserialize, Clone, specta::Type, tauri_specta::Event)]
+#[serde(rename_all = "camelCase")]
+pub struct McpServerHealth {
+    pub server: ServerHealth,
+}
+
+#[derive(Default)]
+pub struct McpHealthState {
+    servers: Mutex<BTreeMap<String, ServerHealth>>,
+}
+
+impl McpHealthState {
+    /// Store a probe result. Returns the entry when liveness changed.
+    fn record(&self, name: &str, probe: ProbeKind, result: Result<u64, String>) -> Option<ServerHealth> {
+        let mut servers = self.servers.lock().unwrap();
+        let previous = servers.get(name);
+        let was = previous.map(|health| health.liveness);
+        let failures = previous.map_or(0, |health| health.consecutive_failures);
+        let health = match result {
+            Ok(latency_ms) => ServerHealth {
+                name: name.to_string(),
+                probe,
+                liveness: Liveness::Alive,
+                checked_at: now_millis(),
+                latency_ms: Some(latency_ms),
+                consecutive_failures: 0,
+                last_error: None,
+            },
+            Err(err) => ServerHealth {
+                name: name.to_string(),
+                probe,
+                liveness: Liveness::Down,
+                checked_at: now_millis(),
+                latency_ms: None,
+                consecutive_failures: failures + 1,
+                last_error: Some(err),
+            },
+        };
+        servers.insert(name.to_string(), health.clone());
+        (was != Some(health.liveness)).then_some(health)
+    }
+
+    /// Forget servers that were removed from the config.
+    fn retain(&self, names: &[String]) {
+        self.servers.lock().unwrap().retain(|name, _| names.contains(name));
+    }
+
+    fn snapshot(&self) -> Vec<ServerHealth> {
+        self.servers.lock().unwrap().values().cloned().collect()
+    }
+}
+
+/// A server to check, from its `mcp.json` entry.
+#[derive(Debug, PartialEq)]
+enum Target {
+    Http(String),
+    Stdio {
+        command: String,
+        args: Vec<String>,
+        cwd: Option<PathBuf>,
+        env: Vec<(String, String)>,
+    },
+}
+
+fn expand_home(path: &str) -> PathBuf {
+    match path.strip_prefix("~/") {
+        Some(rest) => dirs::home_dir().map(|home| home.join(rest)).unwrap_or_else(|| PathBuf::from(path)),
+        None => PathBuf::from(path),
+    }
+}
+
+/// Enabled servers in the config, by name.
+fn targets(config: &JsonValue) -> Vec<(String, Target)> {
+    let Some(servers) = config.get("mcpServers").and_then(|servers| servers.as_object()) else {
+        return Vec::new();
+    };
+    servers
+        .iter()
+        .filter(|(_, entry)| entry.get("enabled").and_then(|v| v.as_bool()) != Some(false))
+        .filter_map(|(name, entry)| {
+            if let Some(url) = entry.get("url").and_then(|v| v.as_str()) {
+                return Some((name.clone(), Target::Http(url.trim().to_string())));
+            }
+            let command = entry.get("command").and_then(|v| v.as_str())?.to_string();
+            let strings = |value: Option<&JsonValue>| -> Vec<String> {
+                value
+                    .and_then(|v| v.as_array())
+                    .map(|items| items.iter().filter_map(|item| item.as_str().map(str::to_string)).collect())
+                    .unwrap_or_default()
+            };
+            let env = entry
+                .get("env")
+                .and_then(|v| v.as_object())
+                .map(|env| {
+                    env.iter()
+                        .filter_map(|(key, value)| value.as_str().map(|value| (key.clone(), value.to_string())))
+                        .collect()
+                })
+                .unwrap_or_default();
+            Some((
+                name.clone(),
+                Target::Stdio {
+                    command,
+                    args: strings(entry.get("args")),
+                    cwd: entry.get("cwd").and_then(|v| v.as_str()).map(expand_home),
+                    env,
+                },
+            ))
+        })
+        .collect()
+}
+
+fn ping_request(id: u64) -> JsonValue {
+    json!({ "jsonrpc": "2.0", "id": id, "method": "ping" })
+}
+
+/// Any answer short of a server error means the server is serving; it may
+/// still want a session before it answers the ping itself.
+async fn probe_http(client: &reqwest::Client, url: &str) -> Result<(), String> {
+    let response = client
+        .post(url)
+        .header("Content-Type", "application/json")
+        .header("Accept", "application/json, text/event-stream")
+        .body(ping_request(1).to_string())
+        .send()
+        .await
+        .map_err(|err| format!("ping failed: {}", err))?;
+    if response.status().is_server_error() {
+        return Err(format!("ping returned HTTP {}", response.status()));
+    }
+    Ok(())
+}
+
+/// Start the server, initialize, ping, and stop it again.
+fn probe_stdio(mut cmd: Command, args: &[String], cwd: Option<&PathBuf>, env: &[(String, String)]) -> Result<(), String> {
+    cmd.args(args)
+        .envs(env.iter().map(|(key, value)| (key, value)))
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::null());
+    if let Some(cwd) = cwd {
+        cmd.current_dir(cwd);
+    }
+    let mut child = cmd.spawn().map_err(|err| format!("failed to start: {}", err))?;
+    let (tx, rx) = mpsc::channel();
+    if let Some(stdout) = child.stdout.take() {
+        std::thread::spawn(move || {
+            for line in BufReader::new(stdout).lines().map_while(Result::ok) {
+                let answered = serde_json::from_str::<JsonValue>(&line)
+                    .is_ok_and(|message| message.get("id").and_then(|id| id.as_u64()) == Some(2));
+                if answered {
+                    let _ = tx.send(());
+                    break;
+                }
+            }
+        });
+    }
+    let initialize = json!({
+        "jsonrpc": "2.0",
+        "id": 1,
+        "method": "initialize",
+        "params": {
+            "protocolVersion": "2025-03-26",
+            "capabilities": {},
+            "clientInfo": { "name": "heyjamie-health", "version": env!("CARGO_PKG_VERSION") }
+        }
+    });
+    let initialized = json!({ "jsonrpc": "2.0", "method": "notifications/initialized" });
+    let written = child.stdin.as_mut().map(|stdin| {
+        [initialize, initialized, ping_request(2)]
+            .iter()
+            .try_for_each(|message| writeln!(stdin, "{}", message))
+    });
+    let result = match written {
+        Some(Ok(())) => rx
+            .recv_timeout(STDIO_TIMEOUT)
+            .map_err(|_| format!("no answer to ping within {}s", STDIO_TIMEOUT.as_secs())),
+        Some(Err(err)) => Err(format!("failed to write to server: {}", err)),
+        None => Err("no stdin".to_string()),
+    };
+    drop(child.stdin.take());
+    graceful_kill(&mut child);
+    result
+}
+
+fn probe_tcp(url: &str) -> Result<(), String> {
+    let parsed = reqwest::Url::parse(url).map_err(|err| format!("invalid canvas URL: {}", err))?;
+    let host = parsed.host_str().ok_or_else(|| "canvas URL has no host".to_string())?;
+    let port = parsed.port_or_known_default().unwrap_or(80);
+    let addresses = (host, port)
+        .to_socket_addrs()
+        .map_err(|err| format!("failed to resolve {}: {}", host, err))?;
+    let mut last_error = format!("{} did not resolve", host);
+    for address in addresses {
+        match TcpStream::connect_timeout(&address, TCP_TIMEOUT) {
+            Ok(_) => return Ok(()),
+            Err(err) => last_error = format!("connect to {} failed: {}", address, err),
+        }
+    }
+    Err(last_error)
+}
+
+async fn timed<F: std::future::Future<Output = Result<(), String>>>(probe: F) -> Result<u64, String> {
+    let started = Instant::now();
+    probe.await.map(|_| started.elapsed().as_millis() as u64)
+}
+
+async fn blocking<F: FnOnce() -> Result<(), String> + Send + 'static>(probe: F) -> Result<(), String> {
+    tauri::async_runtime::spawn_blocking(probe)
+        .await
+        .map_err(|err| format!("probe task failed: {}", err))?
+}
+
+/// Probe every server (stdio ones only with `include_stdio`) and emit an
+/// event for each one that went up or down.
+async fn check_all(app: &tauri::AppHandle, client: &reqwest::Client, include_stdio: bool) {
+    let config = load_mcp_config_value(app).unwrap_or(JsonValue::Null);
+    let state = app.state::<McpHealthState>();
+    let targets = targets(&config);
+    let mut names: Vec<String> = targets.iter().map(|(name, _)| name.clone()).collect();
+    let mut results = Vec::new();
+
+    let canvas_enabled = config
+        .pointer("/mcpServers/excalidraw")
+        .is_some_and(|entry| entry.get("enabled").and_then(|v| v.as_bool()) != Some(false));
+    if canvas_enabled {
+        names.push(CANVAS_SERVER.to_string());
+        let url = excalidraw_canvas_url(app);
+        results.push((CANVAS_SERVER.to_string(), ProbeKind::Tcp, timed(blocking(move || probe_tcp(&url))).await));
+    }
+    for (name, target) in targets {
+        match target {
+            Target::Http(url) => {
+                results.push((name, ProbeKind::Http, timed(probe_http(client, &url)).await));
+            }
+            Target::Stdio { command, args, cwd, env } if include_stdio => {
+                let cmd = sandbox::command(app, &command);
+                let result = timed(blocking(move || probe_stdio(cmd, &args, cwd.as_ref(), &env))).await;
+                results.push((name, ProbeKind::Stdio, result));
+            }
+            Target::Stdio { .. } => {}
+        }
+    }
+
+    state.retain(&names);
+    for (name, probe, result) in results {
+        if let Some(changed) = state.record(&name, probe, result) {
+            log_line(&format!(
+                "[mcp-health] {} is {:?}{}",
+                changed.name,
+                changed.liveness,
+                changed.last_error.as_deref().map(|err| format!(": {}", err)).unwrap_or_default()
+            ));
+            if let Err(err) = (McpServerHealth { server: changed }).emit(app) {
+                log_line(&format!("[mcp-health] failed to emit event: {}", err));
+            }
+        }
+    }
+}
+
+fn http_client() -> Result<reqwest::Client, String> {
+    reqwest::Client::builder()
+        .timeout(HTTP_TIMEOUT)
+        .build()
+        .map_err(|err| format!("failed to build HTTP client: {}", err))
+}
+
+/// Check the servers every `CHECK_INTERVAL`, pausing like the endpoint
+/// health checks while battery saver asks for it.
+pub fn spawn_monitor(app: tauri::AppHandle) {
+    tauri::async_runtime::spawn(async move {
+        let client = match http_client() {
+            Ok(client) => client,
+            Err(err) => {
+                log_line(&format!("[mcp-health] checks disabled: {}", err));
+                return;
+            }
+        };
+        let mut round: u32 = 0;
+        loop {
+            if !power::current(&app).health_checks_paused {
+                background::wait_for_quiet(&app, "MCP health checks", CHECK_INTERVAL).await;
+                check_all(&app, &client, round.is_multiple_of(STDIO_EVERY)).await;
+                round = round.wrapping_add(1);
+            }
+            tokio::time::sleep(CHECK_INTERVAL).await;
+        }
+    });
+}
+
+#[tauri::command]
+#[specta::specta]
+pub fn get_mcp_server_health(state: tauri::State<'_, McpHealthState>) -> Vec<ServerHealth> {
+    state.snapshot()
+}
+
+/// Probe every server now, stdio ones included.
+#[tauri::command]
+#[specta::specta]
+pub async fn check_mcp_server_health(app: tauri::AppHandle) -> Result<Vec<ServerHealth>, String> {
+    check_all(&app, &http_client()?, true).await;
+    Ok(app.state::<McpHealthState>().snapshot())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{targets, Liveness, McpHealthState, ProbeKind, Target};
+
+    #[test]
+    fn reads_targets_and_reports_changes() {
+        let config = serde_json::json!({
+            "mcpServers": {
+                "docs": { "url": " https://mcp.example/mcp " },
+                "context7": { "command": "npx", "args": ["-y", "@upstash/context7-mcp"], "env": { "A": "1" } },
+                "off": { "command": "node", "enabled": false },
+                "broken": { "args": [] }
+            }
+        });
+        let found = targets(&config);
+        assert_eq!(found.len(), 2);
+        assert_eq!(found[0], ("context7".to_string(), Target::Stdio {
+            command: "npx".to_string(),
+            args: vec!["-y".to_string(), "@upstash/context7-mcp".to_string()],
+            cwd: None,
+            env: vec![("A".to_string(), "1".to_string())],
+        }));
+        assert_eq!(found[1], ("docs".to_string(), Target::Http("https://mcp.example/mcp".to_string())));
+
+        let state = McpHealthState::default();
+        assert!(state.record("docs", ProbeKind::Http, Ok(12)).is_some());
+        assert!(state.record("docs", ProbeKind::Http, Ok(15)).is_none());
+        let down = state.record("docs", ProbeKind::Http, Err("ping failed".to_string())).unwrap();
+        assert_eq!((down.liveness, down.consecutive_failures), (Liveness::Down, 1));
+        assert!(state.record("docs", ProbeKind::Http, Err("ping failed".to_string())).is_none());
+        assert_eq!(state.snapshot()[0].consecutive_failures, 2);
+        state.retain(&[]);
+        assert!(state.snapshot().is_empty());
+    }
+}
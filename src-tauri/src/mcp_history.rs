@@ -0,0 +1,233 @@
+//! Versioned edit history of `mcp.json` (`mcp-history.json` next to it)
+//! behind undo/redo in the settings window. Every write is recorded: saves
+//! from the editor, the default config, and automatic migrations. Undoing
+//! a migration declines it, so `ensure_mcp_config_migrated` doesn't simply
+//! apply it again.
+
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use serde::{Deserialize, Serialize};
+
+use crate::{log_line, now_millis};
+
+const HISTORY_FILE: &str = "mcp-history.json";
+/// Older versions are dropped beyond this.
+const MAX_VERSIONS: usize = 50;
+
+#[derive(Serialize, Deserialize, Clone, Copy, Debug, PartialEq, Eq, specta::Type)]
+#[serde(rename_all = "camelCase")]
+pub enum McpEditSource {
+    Default,
+    Editor,
+    Migration,
+    /// Changed on disk outside the app.
+    External,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+#[serde(rename_all = "camelCase")]
+struct Version {
+    content: String,
+    source: McpEditSource,
+    saved_at: u64,
+    /// Migration ids, for `Migration` versions.
+    #[serde(default)]
+    migrations: Vec<String>,
+}
+
+#[derive(Serialize, Deserialize, Default, Debug)]
+#[serde(rename_all = "camelCase", default)]
+struct History {
+    versions: Vec<Version>,
+    /// Index of the version on disk.
+    current: usize,
+    /// Migrations the user undid.
+    declined_migrations: Vec<String>,
+}
+
+/// What undo and redo would do, for the editor's buttons.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, specta::Type)]
+#[serde(rename_all = "camelCase")]
+pub struct McpHistoryStatus {
+    pub can_undo: bool,
+    pub can_redo: bool,
+    /// The change undo reverts, e.g. "automatic migration".
+    pub undo_label: Option<String>,
+    pub redo_label: Option<String>,
+}
+
+fn describe(version: &Version) -> String {
+    match version.source {
+        McpEditSource::Default => "default config".to_string(),
+        McpEditSource::Editor => "edit".to_string(),
+        McpEditSource::Migration => format!("automatic migration ({})", version.migrations.join(", ")),
+        McpEditSource::External => "change made outside the app".to_string(),
+    }
+}
+
+fn same(a: &str, b: &str) -> bool {
+    a.trim() == b.trim()
+}
+
+impl History {
+    /// Record what's on disk when it isn't the current version, e.g. a
+    /// hand edit or the config from before history was kept.
+    fn sync(&mut self, on_disk: &str, now: u64) {
+        if self.versions.get(self.current).is_some_and(|version| same(&version.content, on_disk)) {
+            return;
+        }
+        self.push(on_disk, McpEditSource::External, Vec::new(), now);
+    }
+
+    fn push(&mut self, content: &str, source: McpEditSource, migrations: Vec<String>, now: u64) {
+        self.versions.truncate(self.current + 1);
+        self.versions.push(Version {
+            content: content.to_string(),
+            source,
+            saved_at: now,
+            migrations,
+        });
+        let excess = self.versions.len().saturating_sub(MAX_VERSIONS);
+        self.versions.drain(..excess);
+        self.current = self.versions.len() - 1;
+    }
+
+    /// `previous` is the file's content before the write, if it existed.
+    fn record(&mut self, previous: Option<&str>, content: &str, source: McpEditSource, migrations: Vec<String>, now: u64) {
+        if let Some(previous) = previous {
+            self.sync(previous, now);
+        }
+        if self.versions.get(self.current).is_some_and(|version| same(&version.content, content)) {
+            return;
+        }
+        self.push(content, source, migrations, now);
+    }
+
+    /// Move one version back or forward and return its content.
+    fn step(&mut self, forward: bool) -> Option<String> {
+        if forward {
+            let next = self.versions.get(self.current + 1)?;
+            if next.source == McpEditSource::Migration {
+                self.declined_migrations.retain(|id| !next.migrations.contains(id));
+            }
+            self.current += 1;
+        } else {
+            if self.current == 0 || self.versions.is_empty() {
+                return None;
+            }
+            let undone = &self.versions[self.current];
+            if undone.source == McpEditSource::Migration {
+                for id in &undone.migrations {
+                    if !self.declined_migrations.contains(id) {
+                        self.declined_migrations.push(id.clone());
+                    }
+                }
+            }
+            self.current -= 1;
+        }
+        Some(self.versions[self.current].content.clone())
+    }
+
+    fn status(&self) -> McpHistoryStatus {
+        let undo = (self.current > 0).then(|| self.versions.get(self.current)).flatten();
+        let redo = self.versions.get(self.current + 1);
+        McpHistoryStatus {
+            can_undo: undo.is_some(),
+            can_redo: redo.is_some(),
+            undo_label: undo.map(describe),
+            redo_label: redo.map(describe),
+        }
+    }
+}
+
+fn history_path(mcp_path: &Path) -> PathBuf {
+    mcp_path.with_file_name(HISTORY_FILE)
+}
+
+fn load(mcp_path: &Path) -> History {
+    fs::read_to_string(history_path(mcp_path))
+        .ok()
+        .and_then(|content| serde_json::from_str(&content).ok())
+        .unwrap_or_default()
+}
+
+fn save(mcp_path: &Path, history: &History) {
+    let result = serde_json::to_string(history)
+        .map_err(|err| err.to_string())
+        .and_then(|content| fs::write(history_path(mcp_path), content.as_bytes()).map_err(|err| err.to_string()));
+    if let Err(err) = result {
+        log_line(&format!("[mcp-history] failed to save: {}", err));
+    }
+}
+
+/// Record a write of `content` to `mcp_path`.
+pub fn record(mcp_path: &Path, previous: Option<&str>, content: &str, source: McpEditSource, migrations: Vec<String>) {
+    let mut history = load(mcp_path);
+    history.record(previous, content, source, migrations, now_millis());
+    save(mcp_path, &history);
+}
+
+/// Migrations undone by the user, which shouldn't be applied again.
+pub fn declined_migrations(mcp_path: &Path) -> Vec<String> {
+    load(mcp_path).declined_migrations
+}
+
+/// Undo (`forward` false) or redo the last change to `mcp_path` and return
+/// the restored content.
+pub fn step(mcp_path: &Path, forward: bool) -> Result<String, String> {
+    let mut history = load(mcp_path);
+    if let Ok(on_disk) = fs::read_to_string(mcp_path) {
+        history.sync(&on_disk, now_millis());
+    }
+    let content = history
+        .step(forward)
+        .ok_or_else(|| format!("nothing to {}", if forward { "redo" } else { "undo" }))?;
+    fs::write(mcp_path, content.as_bytes()).map_err(|err| format!("failed to write config: {}", err))?;
+    save(mcp_path, &history);
+    log_line(&format!("[mcp-history] {} to version {}", if forward { "redid" } else { "undid" }, history.current));
+    Ok(content)
+}
+
+pub fn status(mcp_path: &Path) -> McpHistoryStatus {
+    let mut history = load(mcp_path);
+    if let Ok(on_disk) = fs::read_to_string(mcp_path) {
+        history.sync(&on_disk, now_millis());
+    }
+    history.status()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{History, McpEditSource};
+
+    #[test]
+    fn undoes_and_redoes_edits_and_migrations() {
+        let mut history = History::default();
+        history.record(None, "{\"a\":1}", McpEditSource::Default, Vec::new(), 1);
+        history.record(Some("{\"a\":1}"), "{\"a\":2}", McpEditSource::Migration, vec!["m1".to_string()], 2);
+        // A hand edit on disk before the next save is kept as its own version.
+        history.record(Some("{\"a\":3}"), "{\"a\":4}", McpEditSource::Editor, Vec::new(), 3);
+        assert_eq!(history.versions.len(), 4);
+        assert_eq!(history.status().undo_label.as_deref(), Some("edit"));
+
+        assert_eq!(history.step(false).as_deref(), Some("{\"a\":3}"));
+        assert_eq!(history.step(false).as_deref(), Some("{\"a\":2}"));
+        assert_eq!(history.status().undo_label.as_deref(), Some("automatic migration (m1)"));
+        assert_eq!(history.step(false).as_deref(), Some("{\"a\":1}"));
+        assert_eq!(history.declined_migrations, vec!["m1".to_string()]);
+        assert_eq!(history.step(false), None);
+        assert!(!history.status().can_undo);
+
+        assert_eq!(history.step(true).as_deref(), Some("{\"a\":2}"));
+        assert!(history.declined_migrations.is_empty());
+
+        // A new edit drops the redo versions.
+        history.record(Some("{\"a\":2}"), "{\"a\":5}", McpEditSource::Editor, Vec::new(), 4);
+        assert!(!history.status().can_redo);
+        assert_eq!(history.versions.len(), 3);
+        // Saving unchanged content adds nothing.
+        history.record(Some("{\"a\":5}"), " {\"a\":5}\n", McpEditSource::Editor, Vec::new(), 5);
+        assert_eq!(history.versions.len(), 3);
+    }
+}
@@ -0,0 +1,447 @@
+//! Typed editing of `mcp.json`. Each entry under `mcpServers` is read into
+//! an `McpServer`, validated, and merged back over the entry on disk, so
+//! keys the app doesn't know about (headers, timeouts) survive an edit.
+//! The file keeps the shape the agent reads: `"enabled": false` only on
+//! disabled servers. Every change is recorded in `mcp_history`, so undo
+//! and redo keep working.
+
+use std::collections::BTreeMap;
+use std::fs;
+use std::path::Path;
+
+use serde::{Deserialize, Serialize};
+use serde_json::{Map, Value as JsonValue};
+
+use crate::{log_line, mcp_history, mcp_supervisor};
+
+#[derive(Serialize, Deserialize, Clone, Copy, Debug, PartialEq, Eq, specta::Type)]
+#[serde(rename_all = "lowercase")]
+pub enum McpTransport {
+    Http,
+    Sse,
+}
+
+/// A process HeyJamie keeps running for a URL server (see
+/// `mcp_supervisor`).
+#[derive(Serialize, Deserialize, Clone, Debug, Default, PartialEq, specta::Type)]
+#[serde(rename_all = "camelCase", default)]
+pub struct McpLaunch {
+    pub command: String,
+    pub args: Vec<String>,
+    pub cwd: Option<String>,
+    pub env: BTreeMap<String, String>,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, Default, PartialEq, specta::Type)]
+#[serde(rename_all = "camelCase")]
+pub struct McpServer {
+    /// The key under `mcpServers`; tool names are prefixed with it.
+    pub name: String,
+    pub enabled: bool,
+    /// Program to start for a stdio server.
+    pub command: Option<String>,
+    pub args: Vec<String>,
+    /// Working directory; `~/` is expanded when the server starts.
+    pub cwd: Option<String>,
+    pub env: BTreeMap<String, String>,
+    /// Endpoint of a remote server, instead of `command`.
+    pub url: Option<String>,
+    /// How to talk to `url`; HTTP when unset.
+    pub transport: Option<McpTransport>,
+    /// Started and kept running by HeyJamie so `url` answers.
+    pub launch: Option<McpLaunch>,
+    /// Only these tools are offered to the agent.
+    pub allow_tools: Option<Vec<String>>,
+    /// These tools are hidden from the agent.
+    pub deny_tools: Option<Vec<String>>,
+}
+
+#[derive(Serialize, Clone, Debug, specta::Type)]
+#[serde(rename_all = "camelCase")]
+pub struct McpServers {
+    pub path: String,
+    pub servers: Vec<McpServer>,
+}
+
+fn strings(value: Option<&JsonValue>) -> Option<Vec<String>> {
+    value?
+        .as_array()
+        .map(|items| items.iter().filter_map(|item| item.as_str().map(str::to_string)).collect())
+}
+
+fn text(entry: &Map<String, JsonValue>, key: &str) -> Option<String> {
+    entry.get(key).and_then(JsonValue::as_str).map(str::to_string)
+}
+
+/// An entry from the file. Malformed fields read as unset, which
+/// `validate` then reports when the server is saved.
+fn from_entry(name: &str, entry: &JsonValue) -> McpServer {
+    let Some(entry) = entry.as_object() else {
+        return McpServer {
+            name: name.to_string(),
+            ..McpServer::default()
+        };
+    };
+    McpServer {
+        name: name.to_string(),
+        enabled: entry.get("enabled").and_then(JsonValue::as_bool) != Some(false),
+        command: text(entry, "command"),
+        args: strings(entry.get("args")).unwrap_or_default(),
+        cwd: text(entry, "cwd"),
+        env: entry
+            .get("env")
+            .and_then(JsonValue::as_object)
+            .map(|env| {
+                env.iter()
+                    .filter_map(|(key, value)| value.as_str().map(|value| (key.clone(), value.to_string())))
+                    .collect()
+            })
+            .unwrap_or_default(),
+        url: text(entry, "url"),
+        transport: entry
+            .get("transport")
+            .and_then(|value| serde_json::from_value(value.clone()).ok()),
+        launch: entry
+            .get("launch")
+            .and_then(|value| serde_json::from_value(value.clone()).ok()),
+        allow_tools: strings(entry.get("allowTools")),
+        deny_tools: strings(entry.get("denyTools")),
+    }
+}
+
+/// `server` written over `existing`, keeping keys this struct doesn't cover.
+fn to_entry(server: &McpServer, existing: Option<&JsonValue>) -> JsonValue {
+    let mut entry = existing.and_then(JsonValue::as_object).cloned().unwrap_or_default();
+    let mut set = |key: &str, value: Option<JsonValue>| match value {
+        Some(value) => {
+            entry.insert(key.to_string(), value);
+        }
+        None => {
+            entry.remove(key);
+        }
+    };
+    set("enabled", (!server.enabled).then_some(JsonValue::Bool(false)));
+    set("command", server.command.clone().map(JsonValue::String));
+    set("args", (!server.args.is_empty()).then(|| serde_json::json!(server.args)));
+    set("cwd", server.cwd.clone().map(JsonValue::String));
+    set("env", (!server.env.is_empty()).then(|| serde_json::json!(server.env)));
+    set("url", server.url.clone().map(JsonValue::String));
+    set("transport", server.transport.map(|transport| serde_json::json!(transport)));
+    set(
+        "launch",
+        server.launch.as_ref().map(|launch| {
+            let mut value = serde_json::json!({ "command": launch.command, "args": launch.args });
+            if let Some(cwd) = &launch.cwd {
+                value["cwd"] = JsonValue::String(cwd.clone());
+            }
+            if !launch.env.is_empty() {
+                value["env"] = serde_json::json!(launch.env);
+            }
+            value
+        }),
+    );
+    set("allowTools", server.allow_tools.as_ref().map(|tools| serde_json::json!(tools)));
+    set("denyTools", server.deny_tools.as_ref().map(|tools| serde_json::json!(tools)));
+    JsonValue::Object(entry)
+}
+
+/// Trim every field and drop empty ones. Environment variable names that
+/// only differ by surrounding spaces are rejected rather than merged.
+fn normalize(mut server: McpServer) -> Result<McpServer, String> {
+    let clean = |value: Option<String>| value.map(|value| value.trim().to_string()).filter(|value| !value.is_empty());
+    let clean_list = |items: Vec<String>| -> Vec<String> {
+        items.iter().map(|item| item.trim().to_string()).filter(|item| !item.is_empty()).collect()
+    };
+    server.name = server.name.trim().to_string();
+    server.command = clean(server.command);
+    server.cwd = clean(server.cwd);
+    server.url = clean(server.url);
+    server.args.retain(|arg| !arg.trim().is_empty());
+    let mut env = BTreeMap::new();
+    for (key, value) in server.env {
+        let trimmed = key.trim().to_string();
+        if env.insert(trimmed.clone(), value).is_some() {
+            return Err(format!(
+                "{}: environment variable {:?} is set more than once",
+                server.name, trimmed
+            ));
+        }
+    }
+    server.env = env;
+    server.launch = server.launch.and_then(|mut launch| {
+        launch.command = launch.command.trim().to_string();
+        launch.cwd = clean(launch.cwd);
+        launch.args.retain(|arg| !arg.trim().is_empty());
+        (!launch.command.is_empty()).then_some(launch)
+    });
+    server.allow_tools = server.allow_tools.map(clean_list);
+    server.deny_tools = server.deny_tools.map(clean_list);
+    Ok(server)
+}
+
+fn validate(server: &McpServer) -> Result<(), String> {
+    if server.name.is_empty() {
+        return Err("server name is empty".to_string());
+    }
+    if !server
+        .name
+        .chars()
+        .all(|c| c.is_ascii_alphanumeric() || matches!(c, '-' | '_' | '.'))
+    {
+        return Err(format!(
+            "server name {:?} may only use letters, digits, '-', '_' and '.'",
+            server.name
+        ));
+    }
+    match (&server.command, &server.url) {
+        (Some(_), Some(_)) => return Err(format!("{}: set either a command or a URL, not both", server.name)),
+        (None, None) => return Err(format!("{}: set a command (stdio) or a URL (HTTP/SSE)", server.name)),
+        (Some(_), None) if server.transport.is_some() => {
+            return Err(format!("{}: a transport only applies to URL servers", server.name));
+        }
+        (None, Some(url)) => {
+            let parsed = reqwest::Url::parse(url).map_err(|err| format!("{}: invalid URL: {}", server.name, err))?;
+            if !matches!(parsed.scheme(), "http" | "https") {
+                return Err(format!("{}: the URL must be http or https", server.name));
+            }
+        }
+        (Some(_), None) => {}
+    }
+    if server.url.is_some() && (!server.args.is_empty() || server.cwd.is_some() || !server.env.is_empty()) {
+        return Err(format!("{}: args, cwd and env only apply to command servers", server.name));
+    }
+    if server.launch.is_some() && server.url.is_none() {
+        return Err(format!(
+            "{}: a launch command only applies to URL servers; the agent starts command servers itself",
+            server.name
+        ));
+    }
+    let launch_env = server.launch.iter().flat_map(|launch| launch.env.keys());
+    if let Some(key) = server.env.keys().chain(launch_env).find(|key| key.is_empty() || key.contains('=')) {
+        return Err(format!("{}: invalid environment variable name {:?}", server.name, key));
+    }
+    Ok(())
+}
+
+fn read_config(path: &Path) -> Result<Map<String, JsonValue>, String> {
+    let content = fs::read_to_string(path).map_err(|err| format!("failed to read {}: {}", path.display(), err))?;
+    serde_json::from_str(&content).map_err(|err| {
+        format!(
+            "{} is not valid JSON ({}); undo the last change or fix the file by hand",
+            path.display(),
+            err
+        )
+    })
+}
+
+fn servers_of(config: &Map<String, JsonValue>) -> Vec<McpServer> {
+    config
+        .get("mcpServers")
+        .and_then(JsonValue::as_object)
+        .map(|servers| servers.iter().map(|(name, entry)| from_entry(name, entry)).collect())
+        .unwrap_or_default()
+}
+
+fn listing(path: &Path, config: &Map<String, JsonValue>) -> McpServers {
+    McpServers {
+        path: path.display().to_string(),
+        servers: servers_of(config),
+    }
+}
+
+/// Apply `change` to the `mcpServers` object, write the file and record
+/// the edit.
+fn edit(
+    app: &tauri::AppHandle,
+    change: impl FnOnce(&mut Map<String, JsonValue>) -> Result<(), String>,
+) -> Result<McpServers, String> {
+    let path = crate::prepare_mcp_config(app)?;
+    let previous = fs::read_to_string(&path).ok();
+    let mut config = read_config(&path)?;
+    let servers = config
+        .entry("mcpServers")
+        .or_insert_with(|| JsonValue::Object(Map::new()));
+    let servers = servers
+        .as_object_mut()
+        .ok_or_else(|| "\"mcpServers\" in the MCP config is not an object".to_string())?;
+    change(servers)?;
+    let content = serde_json::to_string_pretty(&config).map_err(|err| format!("failed to format JSON: {}", err))?;
+    fs::write(&path, content.as_bytes()).map_err(|err| format!("failed to write config: {}", err))?;
+    mcp_history::record(&path, previous.as_deref(), &content, mcp_history::McpEditSource::Editor, Vec::new());
+    mcp_supervisor::reload(app);
+    Ok(listing(&path, &config))
+}
+
+fn checked(server: McpServer) -> Result<McpServer, String> {
+    let server = normalize(server)?;
+    validate(&server)?;
+    Ok(server)
+}
+
+/// Replace every server with the entries of a whole `mcpServers` object, as
+/// the old JSON editor saved it. Each entry is validated like `add_mcp_server`.
+pub(crate) fn replace_all(app: &tauri::AppHandle, entries: &Map<String, JsonValue>) -> Result<McpServers, String> {
+    let servers = entries
+        .iter()
+        .map(|(name, entry)| {
+            let server = checked(from_entry(name, entry))?;
+            if &server.name != name {
+                return Err(format!("server name {:?} has surrounding spaces", name));
+            }
+            Ok((server.name.clone(), to_entry(&server, Some(entry))))
+        })
+        .collect::<Result<Map<String, JsonValue>, String>>()?;
+    edit(app, |current| {
+        *current = servers;
+        Ok(())
+    })
+}
+
+#[tauri::command]
+#[specta::specta]
+pub fn list_mcp_servers(app: tauri::AppHandle) -> Result<McpServers, String> {
+    let path = crate::prepare_mcp_config(&app)?;
+    Ok(listing(&path, &read_config(&path)?))
+}
+
+#[tauri::command]
+#[specta::specta]
+pub fn add_mcp_server(app: tauri::AppHandle, server: McpServer) -> Result<McpServers, String> {
+    let server = checked(server)?;
+    let result = edit(&app, |servers| {
+        if servers.contains_key(&server.name) {
+            return Err(format!("a server named {} already exists", server.name));
+        }
+        servers.insert(server.name.clone(), to_entry(&server, None));
+        Ok(())
+    })?;
+    log_line(&format!("[mcp] added server {}", server.name));
+    Ok(result)
+}
+
+/// Replace the server called `name`; a different `server.name` renames it.
+#[tauri::command]
+#[specta::specta]
+pub fn update_mcp_server(app: tauri::AppHandle, name: String, server: McpServer) -> Result<McpServers, String> {
+    let server = checked(server)?;
+    let result = edit(&app, |servers| {
+        let existing = servers
+            .remove(&name)
+            .ok_or_else(|| format!("no server named {}", name))?;
+        if server.name != name && servers.contains_key(&server.name) {
+            servers.insert(name.clone(), existing);
+            return Err(format!("a server named {} already exists", server.name));
+        }
+        servers.insert(server.name.clone(), to_entry(&server, Some(&existing)));
+        Ok(())
+    })?;
+    log_line(&format!("[mcp] updated server {}", server.name));
+    Ok(result)
+}
+
+#[tauri::command]
+#[specta::specta]
+pub fn remove_mcp_server(app: tauri::AppHandle, name: String) -> Result<McpServers, String> {
+    let result = edit(&app, |servers| {
+        servers
+            .remove(&name)
+            .map(|_| ())
+            .ok_or_else(|| format!("no server named {}", name))
+    })?;
+    log_line(&format!("[mcp] removed server {}", name));
+    Ok(result)
+}
+
+#[tauri::command]
+#[specta::specta]
+pub fn set_mcp_server_enabled(app: tauri::AppHandle, name: String, enabled: bool) -> Result<McpServers, String> {
+    edit(&app, |servers| {
+        let entry = servers
+            .get_mut(&name)
+            .and_then(JsonValue::as_object_mut)
+            .ok_or_else(|| format!("no server named {}", name))?;
+        if enabled {
+            entry.remove("enabled");
+        } else {
+            entry.insert("enabled".to_string(), JsonValue::Bool(false));
+        }
+        Ok(())
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{checked, from_entry, to_entry, McpLaunch, McpServer, McpTransport};
+
+    #[test]
+    fn round_trips_entries_and_rejects_invalid_servers() {
+        let entry = serde_json::json!({
+            "command": "node",
+            "args": ["dist/index.js"],
+            "env": {"EXPRESS_SERVER_URL": "http://localhost:3000"},
+            "enabled": false,
+            "timeoutMs": 5000
+        });
+        let mut server = from_entry("excalidraw", &entry);
+        assert!(!server.enabled);
+        assert_eq!(server.env["EXPRESS_SERVER_URL"], "http://localhost:3000");
+
+        server.enabled = true;
+        server.args.push("--verbose".to_string());
+        let written = to_entry(&server, Some(&entry));
+        assert_eq!(written.get("enabled"), None);
+        assert_eq!(written["args"], serde_json::json!(["dist/index.js", "--verbose"]));
+        // Keys the struct doesn't know about are kept.
+        assert_eq!(written["timeoutMs"], 5000);
+
+        let remote = McpServer {
+            name: " docs ".to_string(),
+            enabled: true,
+            url: Some("https://mcp.example.com/sse".to_string()),
+            transport: Some(McpTransport::Sse),
+            ..McpServer::default()
+        };
+        let remote = checked(remote).unwrap();
+        assert_eq!(remote.name, "docs");
+        assert_eq!(to_entry(&remote, None)["transport"], "sse");
+        let launched = McpServer {
+            launch: Some(McpLaunch {
+                command: "uvx".to_string(),
+                args: vec!["docs-mcp".to_string()],
+                ..McpLaunch::default()
+            }),
+            ..remote.clone()
+        };
+        let written = to_entry(&checked(launched).unwrap(), None);
+        assert_eq!(written["launch"], serde_json::json!({"command": "uvx", "args": ["docs-mcp"]}));
+        assert_eq!(from_entry("docs", &written).launch.unwrap().command, "uvx");
+
+        let neither = McpServer {
+            name: "empty".to_string(),
+            ..McpServer::default()
+        };
+        assert!(checked(neither).unwrap_err().contains("command"));
+        let bad_name = McpServer {
+            name: "my server".to_string(),
+            command: Some("npx".to_string()),
+            ..McpServer::default()
+        };
+        assert!(checked(bad_name).is_err());
+        let ftp = McpServer {
+            name: "files".to_string(),
+            url: Some("ftp://example.com".to_string()),
+            ..McpServer::default()
+        };
+        assert!(checked(ftp).is_err());
+        let twice = McpServer {
+            name: "dup".to_string(),
+            command: Some("npx".to_string()),
+            env: [("FOO", "1"), ("FOO ", "2")]
+                .into_iter()
+                .map(|(key, value)| (key.to_string(), value.to_string()))
+                .collect(),
+            ..McpServer::default()
+        };
+        assert!(checked(twice).unwrap_err().contains("FOO"));
+    }
+}
@@ -0,0 +1,572 @@
+//! Long-running MCP processes: the Excalidraw canvas server (`node
+//! dist/server.js` in the excalidraw entry's `cwd`) and the `launch`
+//! command of any URL server. Each is started at launch, watched, restarted
+//! with backoff when it exits on its own, and stopped gracefully on quit.
+//! After `MAX_CRASHES` crashes within `CRASH_WINDOW` it is left stopped
+//! until restarted by hand. Command (stdio) servers are started by the
+//! agent for each run and only show up in the status.
+
+use std::collections::BTreeMap;
+use std::path::{Path, PathBuf};
+use std::process::Child;
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
+
+use serde::{Deserialize, Serialize};
+use serde_json::Value as JsonValue;
+use tauri::Manager;
+use tauri_specta::Event;
+
+use crate::process_runner::{graceful_kill, ProcessRunner};
+use crate::{load_mcp_config_value, log_line, now_millis, sandbox, shutdown};
+
+/// The canvas server is supervised under the excalidraw entry's name.
+const CANVAS_SERVER: &str = "excalidraw";
+const POLL_INTERVAL: Duration = Duration::from_secs(1);
+const CRASH_WINDOW: Duration = Duration::from_secs(10 * 60);
+const MAX_CRASHES: usize = 5;
+const MAX_BACKOFF: Duration = Duration::from_secs(30);
+
+#[derive(Serialize, Deserialize, Clone, Copy, Debug, PartialEq, Eq, specta::Type)]
+#[serde(rename_all = "camelCase")]
+pub enum McpProcessState {
+    Running,
+    /// Starting, or exited on its own and starting again shortly.
+    Restarting,
+    /// Stopped by hand, or turned off in the config.
+    Stopped,
+    /// Crashed too often and was left stopped.
+    Crashed,
+    /// Could not be started, e.g. its files are missing.
+    Failed,
+    /// A command server the agent starts for each run.
+    Agent,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, specta::Type)]
+#[serde(rename_all = "camelCase")]
+pub struct McpProcessStatus {
+    pub name: String,
+    pub state: McpProcessState,
+    pub pid: Option<u32>,
+    /// Automatic restarts since it was last started by hand.
+    pub restarts: u32,
+    pub started_at: Option<u64>,
+    /// How the last run ended, or why it couldn't start.
+    pub last_error: Option<String>,
+}
+
+/// Emitted as `mcp-process-changed` whenever a supervised process starts,
+/// exits or is stopped.
+#[derive(Serialize, Deserialize, Clone, Debug, specta::Type, tauri_specta::Event)]
+#[serde(rename_all = "camelCase")]
+pub struct McpProcessChanged {
+    pub process: McpProcessStatus,
+}
+
+#[derive(Clone, Debug, PartialEq)]
+struct Launch {
+    program: String,
+    args: Vec<String>,
+    cwd: Option<PathBuf>,
+    env: Vec<(String, String)>,
+    /// A file that must exist before starting, e.g. the built canvas server.
+    requires: Option<PathBuf>,
+}
+
+struct Process {
+    launch: Launch,
+    child: Option<Child>,
+    state: McpProcessState,
+    restarts: u32,
+    crashes: Vec<Instant>,
+    restart_at: Option<Instant>,
+    started_at: Option<u64>,
+    last_error: Option<String>,
+}
+
+impl Process {
+    fn new(launch: Launch) -> Self {
+        Process {
+            launch,
+            child: None,
+            state: McpProcessState::Stopped,
+            restarts: 0,
+            crashes: Vec::new(),
+            restart_at: None,
+            started_at: None,
+            last_error: None,
+        }
+    }
+
+    fn status(&self, name: &str) -> McpProcessStatus {
+        McpProcessStatus {
+            name: name.to_string(),
+            state: self.state,
+            pid: self.child.as_ref().map(Child::id),
+            restarts: self.restarts,
+            started_at: self.started_at,
+            last_error: self.last_error.clone(),
+        }
+    }
+}
+
+#[derive(Default)]
+pub struct McpSupervisorState {
+    processes: Mutex<BTreeMap<String, Process>>,
+}
+
+fn expand_home(path: &str, home: Option<&Path>) -> PathBuf {
+    match (path.strip_prefix("~/"), home) {
+        (Some(rest), Some(home)) => home.join(rest),
+        _ => PathBuf::from(path),
+    }
+}
+
+fn env_of(value: Option<&JsonValue>) -> Vec<(String, String)> {
+    value
+        .and_then(JsonValue::as_object)
+        .map(|env| {
+            env.iter()
+                .filter_map(|(key, value)| value.as_str().map(|value| (key.clone(), value.to_string())))
+                .collect()
+        })
+        .unwrap_or_default()
+}
+
+/// What to keep running for each enabled server in `config`: its `launch`
+/// command, or the canvas server for the excalidraw entry.
+fn launches(config: &JsonValue, home: Option<&Path>) -> BTreeMap<String, Launch> {
+    let Some(servers) = config.get("mcpServers").and_then(JsonValue::as_object) else {
+        return BTreeMap::new();
+    };
+    servers
+        .iter()
+        .filter(|(_, entry)| entry.get("enabled").and_then(JsonValue::as_bool) != Some(false))
+        .filter_map(|(name, entry)| {
+            if let Some(launch) = entry.get("launch") {
+                let program = launch.get("command").and_then(JsonValue::as_str)?.trim().to_string();
+                let args = launch
+                    .get("args")
+                    .and_then(JsonValue::as_array)
+                    .map(|args| args.iter().filter_map(|arg| arg.as_str().map(str::to_string)).collect())
+                    .unwrap_or_default();
+                let cwd = launch.get("cwd").and_then(JsonValue::as_str).map(|cwd| expand_home(cwd, home));
+                return (!program.is_empty()).then(|| {
+                    let launch = Launch {
+                        program,
+                        args,
+                        cwd,
+                        env: env_of(launch.get("env")),
+                        requires: None,
+                    };
+                    (name.clone(), launch)
+                });
+            }
+            if name != CANVAS_SERVER {
+                return None;
+            }
+            let cwd = expand_home(entry.get("cwd").and_then(JsonValue::as_str)?, home);
+            let launch = Launch {
+                program: "node".to_string(),
+                args: vec!["dist/server.js".to_string()],
+                requires: Some(cwd.join("dist/server.js")),
+                cwd: Some(cwd),
+                env: env_of(entry.get("env")),
+            };
+            Some((name.clone(), launch))
+        })
+        .collect()
+}
+
+/// How long to wait before restarting after `crashes` recent crashes, or
+/// `None` to give up.
+fn backoff(crashes: usize) -> Option<Duration> {
+    if crashes >= MAX_CRASHES {
+        return None;
+    }
+    let exponent = crashes.saturating_sub(1).min(5) as u32;
+    Some(Duration::from_secs(1 << exponent).min(MAX_BACKOFF))
+}
+
+fn spawn_process(app: &tauri::AppHandle, name: &str, launch: &Launch) -> Result<Child, String> {
+    if let Some(required) = launch.requires.as_ref().filter(|path| !path.exists()) {
+        return Err(format!("{} not found", required.display()));
+    }
+    if let Some(cwd) = launch.cwd.as_ref().filter(|cwd| !cwd.is_dir()) {
+        return Err(format!("working directory {} not found", cwd.display()));
+    }
+    let mut command = sandbox::command(app, &launch.program);
+    command.args(&launch.args);
+    if let Some(cwd) = &launch.cwd {
+        command.current_dir(cwd);
+    }
+    ProcessRunner::new(format!("MCP server {}", name), command)
+        .envs(launch.env.clone())
+        .spawn_detached()
+}
+
+/// Mark `process` as starting and return the command to spawn once the
+/// lock is released.
+fn begin_start(process: &mut Process) -> Launch {
+    process.restart_at = None;
+    process.state = McpProcessState::Restarting;
+    process.launch.clone()
+}
+
+/// Whether a child spawned from `launch` is still wanted: the process wasn't
+/// stopped, started by another caller or given a new command meanwhile.
+fn awaits_start(process: &Process, launch: &Launch) -> bool {
+    process.state == McpProcessState::Restarting && process.child.is_none() && process.launch == *launch
+}
+
+/// Spawn `pending` without holding the lock, so a slow start doesn't block
+/// the commands, then record each outcome. Children that are no longer
+/// wanted are stopped again. `restarted` counts the starts as automatic
+/// restarts.
+fn finish_starts(app: &tauri::AppHandle, pending: Vec<(String, Launch)>, restarted: bool) -> Vec<McpProcessStatus> {
+    let state = app.state::<McpSupervisorState>();
+    let mut changed = Vec::new();
+    for (name, launch) in pending {
+        let spawned = spawn_process(app, &name, &launch);
+        let mut processes = state.processes.lock().unwrap();
+        match processes.get_mut(&name) {
+            Some(process) if awaits_start(process, &launch) => {
+                if restarted {
+                    process.restarts += 1;
+                }
+                record_start(&name, process, spawned);
+                changed.push(process.status(&name));
+            }
+            _ => {
+                drop(processes);
+                stop_children(spawned.ok().map(|child| (name, child)).into_iter().collect());
+            }
+        }
+    }
+    changed
+}
+
+fn record_start(name: &str, process: &mut Process, spawned: Result<Child, String>) {
+    match spawned {
+        Ok(child) => {
+            process.child = Some(child);
+            process.state = McpProcessState::Running;
+            process.started_at = Some(now_millis());
+            process.last_error = None;
+        }
+        Err(err) => {
+            log_line(&format!("[mcp-supervisor] {}: {}", name, err));
+            process.state = McpProcessState::Failed;
+            process.last_error = Some(err);
+        }
+    }
+}
+
+fn emit(app: &tauri::AppHandle, statuses: Vec<McpProcessStatus>) {
+    for process in statuses {
+        if let Err(err) = (McpProcessChanged { process }).emit(app) {
+            log_line(&format!("[mcp-supervisor] failed to send status: {}", err));
+        }
+    }
+}
+
+fn stop_children(children: Vec<(String, Child)>) {
+    for (name, mut child) in children {
+        log_line(&format!("[mcp-supervisor] stopping {}", name));
+        graceful_kill(&mut child);
+    }
+}
+
+/// Notice exits, schedule restarts and start processes whose backoff has
+/// passed. Returns the statuses that changed.
+fn poll(app: &tauri::AppHandle) -> Vec<McpProcessStatus> {
+    let state = app.state::<McpSupervisorState>();
+    let now = Instant::now();
+    let mut changed = Vec::new();
+    let mut due = Vec::new();
+    {
+        let mut processes = state.processes.lock().unwrap();
+        for (name, process) in processes.iter_mut() {
+            let exited = match process.child.as_mut().map(Child::try_wait) {
+                Some(Ok(Some(status))) => Some(format!("exited with {}", status)),
+                Some(Err(err)) => Some(format!("could not be checked: {}", err)),
+                _ => None,
+            };
+            if let Some(exit) = exited {
+                process.child = None;
+                process.crashes.retain(|crash| now.duration_since(*crash) < CRASH_WINDOW);
+                process.crashes.push(now);
+                match backoff(process.crashes.len()) {
+                    Some(delay) => {
+                        log_line(&format!("[mcp-supervisor] {} {}; restarting in {:?}", name, exit, delay));
+                        process.state = McpProcessState::Restarting;
+                        process.restart_at = Some(now + delay);
+                    }
+                    None => {
+                        log_line(&format!("[mcp-supervisor] {} {}; crashed too often, leaving it stopped", name, exit));
+                        process.state = McpProcessState::Crashed;
+                    }
+                }
+                process.last_error = Some(exit);
+                changed.push(process.status(name));
+            }
+            if process.restart_at.is_some_and(|at| at <= now) {
+                due.push((name.clone(), begin_start(process)));
+            }
+        }
+    }
+    changed.extend(finish_starts(app, due, true));
+    changed
+}
+
+/// Match the supervised processes to the MCP config: start new ones, stop
+/// removed or disabled ones, and restart ones whose command changed.
+/// Called at startup and after every config edit.
+pub fn reload(app: &tauri::AppHandle) {
+    let config = match load_mcp_config_value(app) {
+        Ok(config) => config,
+        Err(err) => {
+            log_line(&format!("[mcp-supervisor] {}", err));
+            return;
+        }
+    };
+    let wanted = launches(&config, dirs::home_dir().as_deref());
+    let state = app.state::<McpSupervisorState>();
+    let mut stopped = Vec::new();
+    let mut changed = Vec::new();
+    let mut pending = Vec::new();
+    {
+        let mut processes = state.processes.lock().unwrap();
+        processes.retain(|name, process| {
+            let keep = wanted.contains_key(name);
+            if !keep {
+                stopped.extend(process.child.take().map(|child| (name.clone(), child)));
+                let mut status = process.status(name);
+                status.state = McpProcessState::Stopped;
+                changed.push(status);
+            }
+            keep
+        });
+        for (name, launch) in wanted {
+            match processes.get_mut(&name) {
+                Some(process) if process.launch == launch => continue,
+                Some(process) => {
+                    stopped.extend(process.child.take().map(|child| (name.clone(), child)));
+                    process.launch = launch;
+                    // Stopped by hand stays stopped, with the new command.
+                    if process.state == McpProcessState::Stopped {
+                        changed.push(process.status(&name));
+                    } else {
+                        pending.push((name, begin_start(process)));
+                    }
+                }
+                None => {
+                    let mut process = Process::new(launch);
+                    pending.push((name.clone(), begin_start(&mut process)));
+                    processes.insert(name, process);
+                }
+            }
+        }
+    }
+    // Old commands go first, so they release their ports.
+    stop_children(stopped);
+    changed.extend(finish_starts(app, pending, false));
+    emit(app, changed);
+}
+
+/// Start the configured processes and watch them until the app quits.
+pub fn spawn_monitor(app: tauri::AppHandle) {
+    reload(&app);
+    std::thread::spawn(move || {
+        while !shutdown::is_shutting_down() {
+            let changed = poll(&app);
+            emit(&app, changed);
+            std::thread::sleep(POLL_INTERVAL);
+        }
+    });
+}
+
+/// Stop every supervised process, for quitting.
+pub fn stop_all(app: &tauri::AppHandle) {
+    let children: Vec<(String, Child)> = {
+        let state = app.state::<McpSupervisorState>();
+        let mut processes = state.processes.lock().unwrap();
+        processes
+            .iter_mut()
+            .filter_map(|(name, process)| {
+                process.state = McpProcessState::Stopped;
+                process.restart_at = None;
+                process.child.take().map(|child| (name.clone(), child))
+            })
+            .collect()
+    };
+    stop_children(children);
+}
+
+/// Supervised processes, then the command servers the agent starts.
+#[tauri::command]
+#[specta::specta]
+pub fn mcp_server_status(app: tauri::AppHandle) -> Vec<McpProcessStatus> {
+    let mut statuses: Vec<McpProcessStatus> = {
+        let state = app.state::<McpSupervisorState>();
+        let processes = state.processes.lock().unwrap();
+        processes.iter().map(|(name, process)| process.status(name)).collect()
+    };
+    let config = load_mcp_config_value(&app).unwrap_or(JsonValue::Null);
+    if let Some(servers) = config.get("mcpServers").and_then(JsonValue::as_object) {
+        for (name, entry) in servers {
+            if statuses.iter().any(|status| status.name == *name) || entry.get("command").is_none() {
+                continue;
+            }
+            let enabled = entry.get("enabled").and_then(JsonValue::as_bool) != Some(false);
+            statuses.push(McpProcessStatus {
+                name: name.clone(),
+                state: if enabled { McpProcessState::Agent } else { McpProcessState::Stopped },
+                pid: None,
+                restarts: 0,
+                started_at: None,
+                last_error: None,
+            });
+        }
+    }
+    statuses
+}
+
+fn not_supervised(name: &str) -> String {
+    format!(
+        "{} isn't supervised; command servers are started by the agent, and URL servers need a launch command",
+        name
+    )
+}
+
+/// Apply `change` to the supervised process `name`, then stop whatever
+/// child it took out and report the new status.
+fn with_process(
+    app: &tauri::AppHandle,
+    name: &str,
+    change: impl FnOnce(&mut Process) -> Option<Child>,
+) -> Result<McpProcessStatus, String> {
+    let (status, child) = {
+        let state = app.state::<McpSupervisorState>();
+        let mut processes = state.processes.lock().unwrap();
+        let process = processes.get_mut(name).ok_or_else(|| not_supervised(name))?;
+        let child = change(process);
+        (process.status(name), child)
+    };
+    stop_children(child.map(|child| (name.to_string(), child)).into_iter().collect());
+    emit(app, vec![status.clone()]);
+    Ok(status)
+}
+
+#[tauri::command]
+#[specta::specta]
+pub fn start_mcp_server(app: tauri::AppHandle, name: String) -> Result<McpProcessStatus, String> {
+    let state = app.state::<McpSupervisorState>();
+    let launch = {
+        let mut processes = state.processes.lock().unwrap();
+        let process = processes.get_mut(&name).ok_or_else(|| not_supervised(&name))?;
+        if process.child.is_some() {
+            return Ok(process.status(&name));
+        }
+        process.restarts = 0;
+        process.crashes.clear();
+        begin_start(process)
+    };
+    emit(&app, finish_starts(&app, vec![(name.clone(), launch)], false));
+    let processes = state.processes.lock().unwrap();
+    processes
+        .get(&name)
+        .map(|process| process.status(&name))
+        .ok_or_else(|| not_supervised(&name))
+}
+
+#[tauri::command]
+#[specta::specta]
+pub fn stop_mcp_server(app: tauri::AppHandle, name: String) -> Result<McpProcessStatus, String> {
+    with_process(&app, &name, |process| {
+        process.state = McpProcessState::Stopped;
+        process.restart_at = None;
+        process.child.take()
+    })
+}
+
+/// Stop the server and start it again, clearing its crash count.
+#[tauri::command]
+#[specta::specta]
+pub async fn restart_mcp_server(app: tauri::AppHandle, name: String) -> Result<McpProcessStatus, String> {
+    tauri::async_runtime::spawn_blocking(move || {
+        stop_mcp_server(app.clone(), name.clone())?;
+        start_mcp_server(app, name)
+    })
+    .await
+    .map_err(|err| format!("restart task failed: {}", err))?
+}
+
+#[cfg(test)]
+mod tests {
+    use std::path::{Path, PathBuf};
+    use std::time::Duration;
+
+    use super::{awaits_start, backoff, begin_start, launches, Launch, McpProcessState, Process, MAX_CRASHES};
+
+    fn config() -> serde_json::Value {
+        serde_json::json!({ "mcpServers": {
+            "excalidraw": { "command": "node", "args": ["dist/index.js"], "cwd": "~/mcp_excalidraw",
+                            "env": { "EXPRESS_SERVER_URL": "http://localhost:3000" } },
+            "docs": { "url": "http://localhost:8000/mcp", "launch": { "command": "uvx", "args": ["docs-mcp"] } },
+            "off": { "url": "http://localhost:9000/mcp", "launch": { "command": "uvx" }, "enabled": false },
+            "chrome-devtools": { "command": "npx" }
+        } })
+    }
+
+    fn launch(program: &str) -> Launch {
+        Launch {
+            program: program.to_string(),
+            args: Vec::new(),
+            cwd: None,
+            env: Vec::new(),
+            requires: None,
+        }
+    }
+
+    #[test]
+    fn supervises_enabled_launch_commands_and_the_canvas_only() {
+        let wanted = launches(&config(), Some(Path::new("/Users/jamie")));
+        assert_eq!(wanted.keys().collect::<Vec<_>>(), vec!["docs", "excalidraw"]);
+        assert_eq!(wanted["docs"].program, "uvx");
+        assert_eq!(wanted["docs"].args, vec!["docs-mcp"]);
+    }
+
+    #[test]
+    fn runs_the_canvas_server_from_the_excalidraw_checkout() {
+        let wanted = launches(&config(), Some(Path::new("/Users/jamie")));
+        let canvas = &wanted["excalidraw"];
+        assert_eq!(canvas.args, vec!["dist/server.js"]);
+        assert_eq!(canvas.cwd, Some(PathBuf::from("/Users/jamie/mcp_excalidraw")));
+        assert_eq!(canvas.requires, Some(PathBuf::from("/Users/jamie/mcp_excalidraw/dist/server.js")));
+        assert_eq!(canvas.env, vec![("EXPRESS_SERVER_URL".to_string(), "http://localhost:3000".to_string())]);
+    }
+
+    #[test]
+    fn backs_off_exponentially_then_gives_up() {
+        assert_eq!(backoff(1), Some(Duration::from_secs(1)));
+        assert_eq!(backoff(3), Some(Duration::from_secs(4)));
+        assert_eq!(backoff(MAX_CRASHES), None);
+    }
+
+    #[test]
+    fn drops_a_start_overtaken_while_spawning() {
+        let mut process = Process::new(launch("uvx"));
+        let pending = begin_start(&mut process);
+        assert!(awaits_start(&process, &pending));
+
+        process.launch = launch("npx");
+        assert!(!awaits_start(&process, &pending));
+
+        let mut process = Process::new(launch("uvx"));
+        let pending = begin_start(&mut process);
+        process.state = McpProcessState::Stopped;
+        assert!(!awaits_start(&process, &pending));
+    }
+}
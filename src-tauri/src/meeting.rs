@@ -0,0 +1,195 @@
+use std::{sync::Mutex, time::Duration};
+
+use serde::{Deserialize, Serialize};
+use tauri::Manager;
+use tauri_specta::Event;
+
+use crate::log_line;
+use crate::settings;
+
+const MEETING_CHECK_INTERVAL: Duration = Duration::from_secs(5);
+
+/// Substrings of a microphone user's process name or path, and the
+/// conferencing app they belong to.
+const KNOWN_APPS: &[(&str, &str)] = &[
+    ("cpthost", "Zoom"),
+    ("zoom", "Zoom"),
+    ("teams", "Microsoft Teams"),
+    ("webex", "Webex"),
+    ("slack", "Slack"),
+    ("discord", "Discord"),
+    ("facetime", "FaceTime"),
+    ("chrome", "a browser call (e.g. Google Meet)"),
+    ("firefox", "a browser call (e.g. Google Meet)"),
+    ("brave", "a browser call (e.g. Google Meet)"),
+    ("arc.exe", "a browser call (e.g. Google Meet)"),
+    ("msedge.exe", "a browser call (e.g. Google Meet)"),
+];
+
+/// HeyJamie's own capture, which must not count as a meeting.
+const OWN_PROCESSES: &[&str] = &["heyjamie", "tauri-app", "msedgewebview2", "webkit"];
+
+/// Emitted to the main window when a conferencing app starts or stops
+/// using the microphone.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, specta::Type, tauri_specta::Event)]
+#[serde(rename_all = "camelCase")]
+pub struct MeetingDetected {
+    /// The app in the meeting; `None` once it has ended.
+    pub app: Option<String>,
+    /// Start listening without asking.
+    pub auto_start: bool,
+    /// Persona to switch to when auto-starting; empty keeps the current one.
+    pub persona_id: String,
+}
+
+#[derive(Default)]
+pub struct MeetingMonitorState {
+    current: Mutex<Option<String>>,
+}
+
+/// The conferencing app among the processes using the microphone.
+pub fn meeting_app(mic_users: &[String]) -> Option<&'static str> {
+    mic_users
+        .iter()
+        .map(|user| user.to_lowercase())
+        .filter(|user| !OWN_PROCESSES.iter().any(|own| user.contains(own)))
+        .find_map(|user| {
+            KNOWN_APPS
+                .iter()
+                .find(|(pattern, _)| user.contains(pattern))
+                .map(|(_, app)| *app)
+        })
+}
+
+/// Apps with an open microphone stream in `reg query` output of the
+/// microphone consent store: `LastUsedTimeStop` is 0 while in use.
+#[cfg_attr(not(target_os = "windows"), allow(dead_code))]
+pub fn parse_consent_store(output: &str) -> Vec<String> {
+    let mut in_use = Vec::new();
+    let mut key = "";
+    for line in output.lines() {
+        let line = line.trim();
+        if line.starts_with("HKEY_") {
+            key = line;
+        } else if line.starts_with("LastUsedTimeStop") && line.ends_with(" 0x0") {
+            in_use.push(key.rsplit('\\').next().unwrap_or(key).to_string());
+        }
+    }
+    in_use
+}
+
+/// `application.process.binary` of every recording stream in
+/// `pactl list source-outputs`.
+#[cfg_attr(not(target_os = "linux"), allow(dead_code))]
+pub fn parse_source_outputs(output: &str) -> Vec<String> {
+    output
+        .lines()
+        .filter_map(|line| line.trim().strip_prefix("application.process.binary = "))
+        .map(|value| value.trim_matches('"').to_string())
+        .collect()
+}
+
+#[cfg(target_os = "windows")]
+fn mic_users() -> Vec<String> {
+    let key = r"HKCU\Software\Microsoft\Windows\CurrentVersion\CapabilityAccessManager\ConsentStore\microphone";
+    match std::process::Command::new("reg").args(["query", key, "/s"]).output() {
+        Ok(output) => parse_consent_store(&String::from_utf8_lossy(&output.stdout)),
+        Err(err) => {
+            log_line(&format!("[meeting] reg query failed: {}", err));
+            Vec::new()
+        }
+    }
+}
+
+#[cfg(target_os = "linux")]
+fn mic_users() -> Vec<String> {
+    match std::process::Command::new("pactl").args(["list", "source-outputs"]).output() {
+        Ok(output) => parse_source_outputs(&String::from_utf8_lossy(&output.stdout)),
+        Err(_) => Vec::new(),
+    }
+}
+
+/// macOS has no command-line view of who holds the microphone; Zoom runs
+/// `CptHost` only while in a meeting, so that is what gets reported.
+#[cfg(target_os = "macos")]
+fn mic_users() -> Vec<String> {
+    match std::process::Command::new("pgrep").args(["-x", "CptHost"]).output() {
+        Ok(output) if output.status.success() => vec!["CptHost".to_string()],
+        _ => Vec::new(),
+    }
+}
+
+#[cfg(not(any(target_os = "windows", target_os = "linux", target_os = "macos")))]
+fn mic_users() -> Vec<String> {
+    Vec::new()
+}
+
+fn refresh(app: &tauri::AppHandle) {
+    let settings = settings::load_settings(app).meeting;
+    let detected = if settings.enabled {
+        meeting_app(&mic_users()).map(str::to_string)
+    } else {
+        None
+    };
+    let previous = std::mem::replace(
+        &mut *app.state::<MeetingMonitorState>().current.lock().unwrap(),
+        detected.clone(),
+    );
+    if previous == detected {
+        return;
+    }
+    log_line(&format!("[meeting] {}", detected.as_deref().unwrap_or("ended")));
+    let event = MeetingDetected {
+        app: detected,
+        auto_start: settings.auto_start,
+        persona_id: settings.persona_id,
+    };
+    if let Err(err) = event.emit_to(app, "main") {
+        log_line(&format!("[meeting] failed to notify main window: {}", err));
+    }
+}
+
+/// Watch for conferencing apps taking the microphone.
+pub fn spawn_monitor(app: tauri::AppHandle) {
+    tauri::async_runtime::spawn(async move {
+        loop {
+            let handle = app.clone();
+            let _ = tauri::async_runtime::spawn_blocking(move || refresh(&handle)).await;
+            tokio::time::sleep(MEETING_CHECK_INTERVAL).await;
+        }
+    });
+}
+
+#[tauri::command]
+#[specta::specta]
+pub fn get_meeting(app: tauri::AppHandle) -> Option<String> {
+    app.state::<MeetingMonitorState>().current.lock().unwrap().clone()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{meeting_app, parse_consent_store, parse_source_outputs};
+
+    #[test]
+    fn finds_conferencing_apps_using_the_mic() {
+        let reg = r"
+HKEY_CURRENT_USER\Software\Microsoft\Windows\CurrentVersion\CapabilityAccessManager\ConsentStore\microphone\NonPackaged\C:#Users#me#AppData#Roaming#Zoom#bin#Zoom.exe
+    LastUsedTimeStart    REG_QWORD    0x1da2b3c4d5e6f70
+    LastUsedTimeStop    REG_QWORD    0x0
+
+HKEY_CURRENT_USER\Software\Microsoft\Windows\CurrentVersion\CapabilityAccessManager\ConsentStore\microphone\NonPackaged\C:#Program Files#Slack#slack.exe
+    LastUsedTimeStart    REG_QWORD    0x1da2b3c4d5e6f70
+    LastUsedTimeStop    REG_QWORD    0x1da2b3c4d5e6f99
+";
+        let users = parse_consent_store(reg);
+        assert_eq!(users, vec!["C:#Users#me#AppData#Roaming#Zoom#bin#Zoom.exe"]);
+        assert_eq!(meeting_app(&users), Some("Zoom"));
+
+        let pactl = "Source Output #42\n\tProperties:\n\t\tapplication.process.binary = \"tauri-app\"\n\
+Source Output #43\n\tProperties:\n\t\tapplication.process.binary = \"teams-for-linux\"\n";
+        let users = parse_source_outputs(pactl);
+        assert_eq!(users.len(), 2);
+        assert_eq!(meeting_app(&users), Some("Microsoft Teams"));
+        assert_eq!(meeting_app(&["tauri-app".to_string()]), None);
+    }
+}
@@ -0,0 +1,122 @@
+//! Microphone level metering for native recording. The capture loop feeds
+//! every microphone chunk to a `LevelMeter` and sends a `mic-level` event
+//! about ten times a second, with flags for clipping and for a mic that
+//! has produced nothing for a while (the wrong device, a hardware mute).
+
+use std::time::{Duration, Instant};
+
+use serde::{Deserialize, Serialize};
+
+use crate::log_line;
+
+/// Samples at or above this magnitude count as clipped.
+const CLIP_LEVEL: f32 = 0.99;
+/// `clipping` stays set this long after the last clipped sample.
+const CLIP_HOLD: Duration = Duration::from_secs(2);
+/// Below about -60 dBFS RMS is treated as no signal.
+const SILENCE_RMS: f32 = 0.001;
+const SILENT_AFTER: Duration = Duration::from_secs(5);
+
+/// Emitted to the main window as `mic-level`.
+#[derive(Serialize, Deserialize, Clone, Debug, Default, PartialEq, specta::Type, tauri_specta::Event)]
+#[serde(rename_all = "camelCase")]
+pub struct MicLevel {
+    /// RMS since the previous event, 0–1.
+    pub rms: f32,
+    /// Largest sample magnitude since the previous event, 0–1.
+    pub peak: f32,
+    /// A sample clipped in the last couple of seconds.
+    pub clipping: bool,
+    /// Nothing above the noise floor for several seconds.
+    pub silent: bool,
+}
+
+pub struct LevelMeter {
+    sum_squares: f64,
+    count: usize,
+    peak: f32,
+    clipped_at: Option<Instant>,
+    signal_at: Instant,
+    last: MicLevel,
+}
+
+impl LevelMeter {
+    pub fn new(now: Instant) -> Self {
+        Self {
+            sum_squares: 0.0,
+            count: 0,
+            peak: 0.0,
+            clipped_at: None,
+            signal_at: now,
+            last: MicLevel::default(),
+        }
+    }
+
+    pub fn push(&mut self, samples: &[f32]) {
+        for sample in samples {
+            self.sum_squares += f64::from(sample * sample);
+            self.peak = self.peak.max(sample.abs());
+        }
+        self.count += samples.len();
+    }
+
+    /// The level since the last reading; resets the window.
+    pub fn take(&mut self, now: Instant) -> MicLevel {
+        let rms = if self.count == 0 {
+            0.0
+        } else {
+            (self.sum_squares / self.count as f64).sqrt() as f32
+        };
+        if self.peak >= CLIP_LEVEL {
+            self.clipped_at = Some(now);
+        }
+        if rms >= SILENCE_RMS {
+            self.signal_at = now;
+        }
+        let level = MicLevel {
+            rms,
+            peak: self.peak.min(1.0),
+            clipping: self.clipped_at.is_some_and(|at| now.duration_since(at) < CLIP_HOLD),
+            silent: now.duration_since(self.signal_at) >= SILENT_AFTER,
+        };
+        if level.clipping && !self.last.clipping {
+            log_line("[mic-level] input is clipping");
+        }
+        if level.silent && !self.last.silent {
+            log_line("[mic-level] no signal from the microphone; check the selected input");
+        }
+        self.sum_squares = 0.0;
+        self.count = 0;
+        self.peak = 0.0;
+        self.last = level.clone();
+        level
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::LevelMeter;
+    use std::time::{Duration, Instant};
+
+    #[test]
+    fn reports_rms_peak_clipping_and_silence() {
+        let start = Instant::now();
+        let mut meter = LevelMeter::new(start);
+        meter.push(&[0.5, -0.5, 0.5, -0.5]);
+        let level = meter.take(start);
+        assert!((level.rms - 0.5).abs() < 1e-6);
+        assert_eq!(level.peak, 0.5);
+        assert!(!level.clipping && !level.silent);
+
+        meter.push(&[0.2, -1.0]);
+        assert!(meter.take(start + Duration::from_secs(1)).clipping);
+        meter.push(&[0.0; 4]);
+        let level = meter.take(start + Duration::from_secs(2));
+        assert_eq!(level.rms, 0.0);
+        assert!(level.clipping && !level.silent);
+
+        meter.push(&[0.0; 4]);
+        let level = meter.take(start + Duration::from_secs(6));
+        assert!(!level.clipping && level.silent);
+    }
+}
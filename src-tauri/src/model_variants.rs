@@ -0,0 +1,211 @@
+//! Faster variants of a whisper model that can sit next to it: quantized
+//! files (`ggml-<name>-q5_1.bin`, ...) and Core ML encoders
+//! (`ggml-<name>-encoder.mlmodelc`, picked up by whisper.cpp builds with
+//! `WHISPER_COREML`). Resolving a model loads a quantized file in its place
+//! unless `transcription.exactModel` is set. `prepare_model_variant`
+//! downloads one, or builds a quantized file with whisper.cpp's quantize
+//! tool when there is none to download.
+
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::process::Command;
+use std::sync::Mutex;
+
+use crate::process_runner::ProcessRunner;
+use crate::{auth, find_whisper_cli, log_line, settings, whisper_models};
+
+/// Quantization suffixes whisper.cpp publishes, closest to full
+/// precision first.
+pub const QUANTIZATIONS: &[&str] = &["q8_0", "q5_1", "q5_0"];
+/// `prepare_model_variant` name for the Core ML encoder.
+const CORE_ML: &str = "coreml";
+
+static LAST_SUBSTITUTION: Mutex<Option<PathBuf>> = Mutex::new(None);
+
+/// The model name and quantization in a file name:
+/// `ggml-base.en-q5_1.bin` is `("base.en", Some("q5_1"))`.
+pub fn split(file_name: &str) -> Option<(&str, Option<&str>)> {
+    let name = file_name.strip_prefix("ggml-")?.strip_suffix(".bin")?;
+    Some(
+        QUANTIZATIONS
+            .iter()
+            .find_map(|quantization| {
+                name.strip_suffix(quantization)
+                    .and_then(|base| base.strip_suffix('-'))
+                    .map(|base| (base, Some(*quantization)))
+            })
+            .unwrap_or((name, None)),
+    )
+}
+
+/// The Core ML encoder whisper.cpp looks for next to `file_name`; it
+/// ignores the quantization suffix.
+pub fn encoder_name(file_name: &str) -> Option<String> {
+    split(file_name).map(|(base, _)| format!("ggml-{}-encoder.mlmodelc", base))
+}
+
+/// The quantized file among `present` to load instead of full-precision
+/// `file_name`. An already quantized file is used as is.
+fn pick(file_name: &str, present: &[String]) -> Option<String> {
+    let (base, None) = split(file_name)? else {
+        return None;
+    };
+    QUANTIZATIONS
+        .iter()
+        .map(|quantization| format!("ggml-{}-{}.bin", base, quantization))
+        .find(|candidate| present.contains(candidate))
+}
+
+/// Whether a Core ML encoder for `model_path` sits next to it.
+pub fn has_core_ml_encoder(model_path: &Path) -> bool {
+    model_path
+        .file_name()
+        .and_then(|name| name.to_str())
+        .and_then(encoder_name)
+        .is_some_and(|encoder| model_path.with_file_name(encoder).is_dir())
+}
+
+/// The quantized variant next to `path`, if there is one to use.
+pub fn variant_of(path: &Path) -> Option<PathBuf> {
+    let file_name = path.file_name()?.to_str()?;
+    let present: Vec<String> = fs::read_dir(path.parent()?)
+        .ok()?
+        .filter_map(|entry| entry.ok()?.file_name().into_string().ok())
+        .collect();
+    pick(file_name, &present).map(|variant| path.with_file_name(variant))
+}
+
+/// `path`, or the quantized variant to load in its place.
+pub fn prefer(app: &tauri::AppHandle, path: PathBuf) -> PathBuf {
+    if settings::load_settings(app).transcription.exact_model {
+        return path;
+    }
+    let Some(variant) = variant_of(&path) else {
+        return path;
+    };
+    let mut last = LAST_SUBSTITUTION.lock().unwrap();
+    if last.as_ref() != Some(&variant) {
+        log_line(&format!("[models] using {} in place of {}", variant.display(), path.display()));
+        *last = Some(variant.clone());
+    }
+    variant
+}
+
+/// Build `<name>-<quantization>` from the installed full model with
+/// whisper.cpp's quantize tool.
+fn quantize_locally(app: &tauri::AppHandle, name: &str, quantization: &str) -> Result<PathBuf, String> {
+    let source = whisper_models::find(app, &whisper_models::file_name(name))
+        .ok_or_else(|| format!("{} is not installed, so there is nothing to quantize", name))?;
+    let cli = find_whisper_cli().ok_or("whisper.cpp not found; run scripts/setup-whisper.sh")?;
+    let tool = ["whisper-quantize", "quantize"]
+        .iter()
+        .map(|tool| cli.with_file_name(tool))
+        .find(|tool| tool.is_file())
+        .ok_or("whisper.cpp's quantize tool is not built")?;
+    let target = whisper_models::models_dir(app)?.join(format!("ggml-{}-{}.bin", name, quantization));
+    let mut command = Command::new(tool);
+    command.arg(&source).arg(&target).arg(quantization);
+    let output = ProcessRunner::new("whisper-quantize", command).run()?;
+    if !output.status.success() {
+        let _ = fs::remove_file(&target);
+        return Err(format!(
+            "quantizing {} failed: {}",
+            name,
+            crate::truncate_for_log(&String::from_utf8_lossy(&output.stderr), 500)
+        ));
+    }
+    log_line(&format!("[models] quantized {} to {}", source.display(), target.display()));
+    Ok(target)
+}
+
+/// Download and unpack the Core ML encoder for `name` next to the model.
+async fn download_encoder(app: &tauri::AppHandle, name: &str) -> Result<PathBuf, String> {
+    if !cfg!(target_os = "macos") {
+        return Err("Core ML encoders only work on macOS".to_string());
+    }
+    let file_name = whisper_models::file_name(name);
+    let dir = match whisper_models::find(app, &file_name) {
+        Some(model) => model.parent().map(Path::to_path_buf).ok_or("model has no folder")?,
+        None => whisper_models::models_dir(app)?,
+    };
+    let encoder = encoder_name(&file_name).ok_or("invalid model name")?;
+    let url = format!("{}/{}.zip", whisper_models::DOWNLOAD_BASE, encoder);
+    log_line(&format!("[models] downloading {}", url));
+    let response = auth::http_client()?
+        .get(&url)
+        .send()
+        .await
+        .map_err(|err| format!("download of {} failed: {}", encoder, err))?;
+    if !response.status().is_success() {
+        return Err(format!("download of {} failed: HTTP {}", encoder, response.status()));
+    }
+    let bytes = response
+        .bytes()
+        .await
+        .map_err(|err| format!("download of {} interrupted: {}", encoder, err))?;
+    let zip = dir.join(format!("{}.zip", encoder));
+    fs::write(&zip, &bytes).map_err(|err| format!("failed to write {}: {}", zip.display(), err))?;
+    let mut command = Command::new("unzip");
+    command.arg("-q").arg("-o").arg(&zip).arg("-d").arg(&dir);
+    let output = tauri::async_runtime::spawn_blocking(move || ProcessRunner::new("unzip", command).run())
+        .await
+        .map_err(|err| format!("unzip task failed: {}", err))?;
+    let _ = fs::remove_file(&zip);
+    let output = output?;
+    if !output.status.success() {
+        return Err(format!("failed to unpack {}: {}", encoder, String::from_utf8_lossy(&output.stderr).trim()));
+    }
+    log_line(&format!("[models] installed {}", dir.join(&encoder).display()));
+    Ok(dir.join(encoder))
+}
+
+/// Install a faster variant of catalog model `name`: `q8_0`, `q5_1` or
+/// `q5_0` (downloaded, or quantized locally when the download fails), or
+/// `coreml` for the Core ML encoder. Returns where it was put.
+#[tauri::command]
+#[specta::specta]
+pub async fn prepare_model_variant(app: tauri::AppHandle, name: String, variant: String) -> Result<String, String> {
+    let name = whisper_models::known_model(&name)?;
+    if split(&whisper_models::file_name(&name)).is_some_and(|(_, quantization)| quantization.is_some()) {
+        return Err(format!("{} is already a variant", name));
+    }
+    let path = if variant == CORE_ML {
+        download_encoder(&app, &name).await?
+    } else if QUANTIZATIONS.contains(&variant.as_str()) {
+        match whisper_models::download_whisper_model(app.clone(), format!("{}-{}", name, variant)).await {
+            Ok(path) => PathBuf::from(path),
+            Err(err) => {
+                log_line(&format!("[models] {}; quantizing locally instead", err));
+                tauri::async_runtime::spawn_blocking(move || quantize_locally(&app, &name, &variant))
+                    .await
+                    .map_err(|err| format!("quantize task failed: {}", err))??
+            }
+        }
+    } else {
+        return Err(format!("unknown model variant: {}", variant));
+    };
+    Ok(path.display().to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{encoder_name, pick, split};
+
+    #[test]
+    fn prefers_the_most_precise_quantized_file_present() {
+        assert_eq!(split("ggml-base.en-q5_1.bin"), Some(("base.en", Some("q5_1"))));
+        assert_eq!(split("ggml-large-v3-turbo.bin"), Some(("large-v3-turbo", None)));
+        assert_eq!(split("model.bin"), None);
+        assert_eq!(encoder_name("ggml-small-q8_0.bin").as_deref(), Some("ggml-small-encoder.mlmodelc"));
+
+        let present = vec![
+            "ggml-base.en.bin".to_string(),
+            "ggml-base.en-q5_0.bin".to_string(),
+            "ggml-base.en-q5_1.bin".to_string(),
+            "ggml-small.en-q8_0.bin".to_string(),
+        ];
+        assert_eq!(pick("ggml-base.en.bin", &present).as_deref(), Some("ggml-base.en-q5_1.bin"));
+        assert_eq!(pick("ggml-base.en-q5_0.bin", &present), None);
+        assert_eq!(pick("ggml-small.bin", &present), None);
+    }
+}
@@ -0,0 +1,278 @@
+//! Per-domain rate limits, concurrency caps and robots.txt checks for the
+//! backend's own fetches. The agent's browser navigation gets the same
+//! settings through `agent_env` (see `scripts/fetch-politeness.mjs`).
+
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+
+use tauri::{Manager, Url};
+
+use crate::log_line;
+use crate::settings::{self, FetchSettings};
+
+/// Identifies us to robots.txt groups; `*` rules apply otherwise.
+const ROBOTS_AGENT: &str = "heyjamie";
+const ROBOTS_TTL: Duration = Duration::from_secs(60 * 60);
+const ROBOTS_TIMEOUT: Duration = Duration::from_secs(5);
+/// Sites asking for longer crawl delays are still fetched this often.
+const MAX_CRAWL_DELAY: Duration = Duration::from_secs(30);
+const SLOT_POLL: Duration = Duration::from_millis(100);
+
+/// The rules of the robots.txt group that applies to us.
+#[derive(Debug, Default, PartialEq)]
+struct Robots {
+    /// `(allow, path pattern)`.
+    rules: Vec<(bool, String)>,
+    crawl_delay: Option<Duration>,
+}
+
+#[derive(Default)]
+struct Group {
+    agents: Vec<String>,
+    rules: Vec<(bool, String)>,
+    crawl_delay: Option<Duration>,
+}
+
+fn parse_robots(content: &str) -> Robots {
+    let mut groups: Vec<Group> = Vec::new();
+    let mut in_agent_lines = false;
+    for line in content.lines() {
+        let line = line.split('#').next().unwrap_or_default().trim();
+        let Some((key, value)) = line.split_once(':') else {
+            continue;
+        };
+        let value = value.trim();
+        match key.trim().to_ascii_lowercase().as_str() {
+            "user-agent" => {
+                if !in_agent_lines || groups.is_empty() {
+                    groups.push(Group::default());
+                }
+                if let Some(group) = groups.last_mut() {
+                    group.agents.push(value.to_ascii_lowercase());
+                }
+                in_agent_lines = true;
+            }
+            key @ ("allow" | "disallow") => {
+                in_agent_lines = false;
+                if let Some(group) = groups.last_mut() {
+                    // An empty Disallow allows everything.
+                    if !value.is_empty() {
+                        group.rules.push((key == "allow", value.to_string()));
+                    }
+                }
+            }
+            "crawl-delay" => {
+                in_agent_lines = false;
+                if let (Some(group), Ok(seconds)) = (groups.last_mut(), value.parse::<f64>()) {
+                    group.crawl_delay = Some(Duration::from_secs_f64(seconds.max(0.0)).min(MAX_CRAWL_DELAY));
+                }
+            }
+            _ => in_agent_lines = false,
+        }
+    }
+    let ours = groups
+        .iter()
+        .position(|group| group.agents.iter().any(|agent| agent.contains(ROBOTS_AGENT)))
+        .or_else(|| groups.iter().position(|group| group.agents.iter().any(|agent| agent == "*")));
+    match ours.map(|index| groups.swap_remove(index)) {
+        Some(group) => Robots {
+            rules: group.rules,
+            crawl_delay: group.crawl_delay,
+        },
+        None => Robots::default(),
+    }
+}
+
+/// robots.txt path patterns: `*` matches anything, a trailing `$` anchors.
+fn pattern_matches(pattern: &str, path: &str) -> bool {
+    let (pattern, anchored) = match pattern.strip_suffix('$') {
+        Some(pattern) => (pattern, true),
+        None => (pattern, false),
+    };
+    let mut parts = pattern.split('*');
+    let Some(mut rest) = path.strip_prefix(parts.next().unwrap_or_default()) else {
+        return false;
+    };
+    let parts: Vec<&str> = parts.collect();
+    for (i, part) in parts.iter().enumerate() {
+        let last = i + 1 == parts.len();
+        if last && anchored {
+            return rest.ends_with(part);
+        }
+        match rest.find(part) {
+            Some(at) => rest = &rest[at + part.len()..],
+            None => return false,
+        }
+    }
+    !anchored || rest.is_empty()
+}
+
+impl Robots {
+    /// The longest matching rule decides; Allow wins a tie.
+    fn allows(&self, path: &str) -> bool {
+        self.rules
+            .iter()
+            .filter(|(_, pattern)| pattern_matches(pattern, path))
+            .max_by_key(|(allow, pattern)| (pattern.len(), *allow))
+            .is_none_or(|(allow, _)| *allow)
+    }
+}
+
+struct DomainState {
+    next_start: Instant,
+    in_flight: u32,
+}
+
+#[derive(Default)]
+pub struct PolitenessState {
+    domains: Mutex<HashMap<String, DomainState>>,
+    robots: Mutex<HashMap<String, (Instant, Arc<Robots>)>>,
+}
+
+/// Held while a request to `host` is in flight.
+pub struct Permit {
+    app: tauri::AppHandle,
+    host: String,
+}
+
+impl Drop for Permit {
+    fn drop(&mut self) {
+        let state = self.app.state::<PolitenessState>();
+        let mut domains = state.domains.lock().unwrap();
+        if let Some(domain) = domains.get_mut(&self.host) {
+            domain.in_flight = domain.in_flight.saturating_sub(1);
+        }
+    }
+}
+
+/// robots.txt of `url`'s origin, cached for an hour.
+async fn robots_for(app: &tauri::AppHandle, url: &Url) -> Arc<Robots> {
+    let origin = format!("{}://{}", url.scheme(), url.authority());
+    {
+        let state = app.state::<PolitenessState>();
+        let cache = state.robots.lock().unwrap();
+        if let Some((_, robots)) = cache.get(&origin).filter(|(fetched, _)| fetched.elapsed() < ROBOTS_TTL) {
+            return robots.clone();
+        }
+    }
+    let robots_url = format!("{}/robots.txt", origin);
+    let content = match reqwest::Client::builder().timeout(ROBOTS_TIMEOUT).build() {
+        Ok(client) => match client.get(&robots_url).send().await {
+            Ok(response) if response.status().is_success() => response.text().await.unwrap_or_default(),
+            // Missing or broken robots.txt: nothing is disallowed.
+            _ => String::new(),
+        },
+        Err(_) => String::new(),
+    };
+    let robots = Arc::new(parse_robots(&content));
+    app.state::<PolitenessState>()
+        .robots
+        .lock()
+        .unwrap()
+        .insert(origin, (Instant::now(), robots.clone()));
+    robots
+}
+
+/// Wait until `url` may be fetched under the configured politeness rules
+/// and return a permit to hold for the duration of the request. Fails when
+/// robots.txt disallows the page.
+pub async fn acquire(app: &tauri::AppHandle, url: &str) -> Result<Permit, String> {
+    let parsed = Url::parse(url).map_err(|err| format!("invalid url: {}", err))?;
+    let host = parsed
+        .host_str()
+        .map(|host| host.trim_start_matches("www.").to_ascii_lowercase())
+        .ok_or_else(|| format!("no host in {}", url))?;
+    let config: FetchSettings = settings::load_settings(app).fetch;
+    let mut interval = Duration::from_millis(config.min_interval_ms as u64);
+    if config.respect_robots {
+        let robots = robots_for(app, &parsed).await;
+        let path = match parsed.query() {
+            Some(query) => format!("{}?{}", parsed.path(), query),
+            None => parsed.path().to_string(),
+        };
+        if !robots.allows(&path) {
+            log_line(&format!("[politeness] robots.txt disallows {}", url));
+            return Err(format!("{} disallows automated fetches of {}", host, parsed.path()));
+        }
+        interval = interval.max(robots.crawl_delay.unwrap_or_default());
+    }
+    let max_in_flight = config.max_concurrent_per_domain.max(1);
+    loop {
+        let wait = {
+            let state = app.state::<PolitenessState>();
+            let mut domains = state.domains.lock().unwrap();
+            let now = Instant::now();
+            let domain = domains.entry(host.clone()).or_insert(DomainState {
+                next_start: now,
+                in_flight: 0,
+            });
+            if domain.in_flight < max_in_flight && now >= domain.next_start {
+                domain.in_flight += 1;
+                domain.next_start = now + interval;
+                None
+            } else if domain.in_flight >= max_in_flight {
+                Some(SLOT_POLL)
+            } else {
+                Some(domain.next_start - now)
+            }
+        };
+        match wait {
+            None => {
+                return Ok(Permit {
+                    app: app.clone(),
+                    host,
+                })
+            }
+            Some(wait) => tokio::time::sleep(wait).await,
+        }
+    }
+}
+
+/// Environment for the agent process so browser navigation follows the
+/// same rules.
+pub fn agent_env(app: &tauri::AppHandle) -> Vec<(String, String)> {
+    let config = settings::load_settings(app).fetch;
+    vec![
+        ("HEYJAMIE_FETCH_MIN_INTERVAL_MS".to_string(), config.min_interval_ms.to_string()),
+        (
+            "HEYJAMIE_FETCH_MAX_CONCURRENT".to_string(),
+            config.max_concurrent_per_domain.max(1).to_string(),
+        ),
+        ("HEYJAMIE_FETCH_ROBOTS".to_string(), if config.respect_robots { "1" } else { "0" }.to_string()),
+    ]
+}
+
+#[cfg(test)]
+mod tests {
+    use super::parse_robots;
+    use std::time::Duration;
+
+    #[test]
+    fn applies_the_matching_robots_group() {
+        let robots = parse_robots(
+            "\
+User-agent: Googlebot
+Disallow:
+
+User-agent: *
+Crawl-delay: 2
+Disallow: /search
+Disallow: /*.pdf$
+Allow: /search/about
+# Disallow: /commented
+",
+        );
+        assert_eq!(robots.crawl_delay, Some(Duration::from_secs(2)));
+        assert!(!robots.allows("/search?q=rust"));
+        assert!(robots.allows("/search/about"));
+        assert!(!robots.allows("/papers/attention.pdf"));
+        assert!(robots.allows("/papers/attention.pdf?download=1"));
+        assert!(robots.allows("/commented"));
+
+        let ours = parse_robots("User-agent: *\nDisallow: /\n\nUser-agent: HeyJamie\nUser-agent: other\nDisallow: /private\n");
+        assert!(ours.allows("/docs"));
+        assert!(!ours.allows("/private/x"));
+        assert!(parse_robots("").allows("/anything"));
+    }
+}
@@ -0,0 +1,238 @@
+use std::fs;
+
+use serde::{Deserialize, Serialize};
+use serde_json::json;
+
+use crate::export::{self, format_offset};
+use crate::sessions::{self, SessionInfo};
+use crate::settings::{self, PostSessionSettings};
+use crate::topic_graph::{self, TopicGraph};
+use crate::{auth, keychain, log_line, now_millis, transcript_versions};
+
+const STATUS_FILE: &str = "pipeline.json";
+const SLACK_WEBHOOK_ACCOUNT: &str = "slack-webhook";
+/// Slack rejects messages over 40k characters.
+const SLACK_MAX_CHARS: usize = 35_000;
+
+#[derive(Serialize, Deserialize, Clone, Copy, Debug, PartialEq, Eq, specta::Type)]
+#[serde(rename_all = "kebab-case")]
+pub enum PipelineStep {
+    FinalizeTranscript,
+    Retranscribe,
+    ShowNotes,
+    Chapters,
+    Slack,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, specta::Type)]
+#[serde(rename_all = "camelCase")]
+pub struct StepResult {
+    pub step: PipelineStep,
+    pub ok: bool,
+    /// Written file, posted channel or the error.
+    pub detail: String,
+}
+
+/// Outcome of one pipeline run, saved as `<session>/pipeline.json`.
+#[derive(Serialize, Deserialize, Clone, Debug, specta::Type)]
+#[serde(rename_all = "camelCase")]
+pub struct PipelineRun {
+    pub session_id: String,
+    pub finished_at: u64,
+    pub steps: Vec<StepResult>,
+}
+
+fn enabled_steps(config: &PostSessionSettings) -> Vec<PipelineStep> {
+    [
+        (config.finalize_transcript, PipelineStep::FinalizeTranscript),
+        (config.retranscribe, PipelineStep::Retranscribe),
+        (config.show_notes, PipelineStep::ShowNotes),
+        (config.chapters, PipelineStep::Chapters),
+        (config.deliver_to_slack, PipelineStep::Slack),
+    ]
+    .into_iter()
+    .filter_map(|(enabled, step)| enabled.then_some(step))
+    .collect()
+}
+
+/// Chapter lines (`0:00 Intro`) from topic visits in the order they
+/// happened. The first chapter starts at 0:00 as YouTube requires.
+pub fn build_chapters(graph: &TopicGraph) -> Vec<String> {
+    let mut visits: Vec<(u64, &str)> = graph
+        .nodes
+        .iter()
+        .flat_map(|node| node.visits.iter().map(|visit| (visit.start_ms, node.label.as_str())))
+        .collect();
+    visits.sort_by_key(|(start_ms, _)| *start_ms);
+    visits.dedup_by(|next, previous| next.1 == previous.1);
+    visits
+        .iter()
+        .enumerate()
+        .map(|(index, (start_ms, label))| {
+            format!("{} {}", format_offset(if index == 0 { 0 } else { *start_ms }), label)
+        })
+        .collect()
+}
+
+fn finalize_transcript(app: &tauri::AppHandle, info: &SessionInfo) -> Result<String, String> {
+    let lines: Vec<String> = sessions::read_transcript(app, &info.id)?
+        .iter()
+        .map(|segment| format!("[{}] {}", format_offset(segment.offset_ms), segment.attributed_text()))
+        .collect();
+    if lines.is_empty() {
+        return Err("the session has no transcript".to_string());
+    }
+    export::write_export(app, &info.id, "transcript.txt", &lines.join("\n"))
+}
+
+/// Retranscribe into a new transcript version and `transcript-hq.txt`.
+fn retranscribe(app: &tauri::AppHandle, session_id: &str, model: &str) -> Result<String, String> {
+    let (_, segments) = transcript_versions::retranscribe(app, session_id, model, None)?;
+    let lines: Vec<String> = segments
+        .iter()
+        .map(|segment| format!("[{}] {}", format_offset(segment.offset_ms), segment.attributed_text()))
+        .collect();
+    export::write_export(app, session_id, "transcript-hq.txt", &lines.join("\n"))
+}
+
+async fn deliver_to_slack(app: &tauri::AppHandle, info: &SessionInfo) -> Result<String, String> {
+    let webhook = keychain::get_secret(SLACK_WEBHOOK_ACCOUNT)?
+        .ok_or_else(|| "no Slack webhook saved".to_string())?;
+    let mut text = export::session_show_notes(app, info)?.to_markdown();
+    let chapters = build_chapters(&topic_graph::read_graph(app, &info.id));
+    if !chapters.is_empty() {
+        text.push_str(&format!("\n## Chapters\n\n{}\n", chapters.join("\n")));
+    }
+    if text.chars().count() > SLACK_MAX_CHARS {
+        text = text.chars().take(SLACK_MAX_CHARS).collect::<String>() + "\n…";
+    }
+    let response = auth::http_client()?
+        .post(&webhook)
+        .header("Content-Type", "application/json")
+        .body(json!({ "text": text }).to_string())
+        .send()
+        .await
+        .map_err(|err| format!("Slack request failed: {}", err))?;
+    let status = response.status();
+    if !status.is_success() {
+        let body = response.text().await.unwrap_or_default();
+        return Err(format!("Slack webhook error ({}): {}", status, body));
+    }
+    Ok("posted to Slack".to_string())
+}
+
+async fn run_step(
+    app: &tauri::AppHandle,
+    info: &SessionInfo,
+    config: &PostSessionSettings,
+    step: PipelineStep,
+) -> Result<String, String> {
+    match step {
+        PipelineStep::FinalizeTranscript => finalize_transcript(app, info),
+        PipelineStep::Retranscribe => {
+            let (app, info, model) = (app.clone(), info.clone(), config.retranscribe_model.clone());
+            tauri::async_runtime::spawn_blocking(move || retranscribe(&app, &info.id, &model))
+                .await
+                .map_err(|err| format!("retranscription task failed: {}", err))?
+        }
+        PipelineStep::ShowNotes => {
+            let notes = export::session_show_notes(app, info)?;
+            export::write_export(app, &info.id, "show-notes.md", &notes.to_markdown())
+        }
+        PipelineStep::Chapters => {
+            let chapters = build_chapters(&topic_graph::read_graph(app, &info.id));
+            if chapters.is_empty() {
+                return Err("no topics were recorded".to_string());
+            }
+            export::write_export(app, &info.id, "chapters.txt", &chapters.join("\n"))
+        }
+        PipelineStep::Slack => deliver_to_slack(app, info).await,
+    }
+}
+
+async fn run_pipeline(app: &tauri::AppHandle, info: &SessionInfo, config: &PostSessionSettings) -> PipelineRun {
+    let mut steps = Vec::new();
+    for step in enabled_steps(config) {
+        let result = run_step(app, info, config, step).await;
+        log_line(&format!("[post-session] {} {:?}: {:?}", info.id, step, result));
+        steps.push(match result {
+            Ok(detail) => StepResult { step, ok: true, detail },
+            Err(detail) => StepResult { step, ok: false, detail },
+        });
+    }
+    let run = PipelineRun {
+        session_id: info.id.clone(),
+        finished_at: now_millis(),
+        steps,
+    };
+    let written = sessions::session_dir(app, &info.id).and_then(|dir| {
+        let content = serde_json::to_string_pretty(&run)
+            .map_err(|err| format!("failed to format pipeline status: {}", err))?;
+        fs::write(dir.join(STATUS_FILE), content.as_bytes())
+            .map_err(|err| format!("failed to write pipeline status: {}", err))
+    });
+    if let Err(err) = written {
+        log_line(&format!("[post-session] {}", err));
+    }
+    run
+}
+
+/// Start the wrap-up pipeline in the background when it is enabled.
+pub fn run_on_session_end(app: &tauri::AppHandle, info: &SessionInfo) {
+    let config = settings::load_settings(app).post_session;
+    if !config.enabled {
+        return;
+    }
+    let app = app.clone();
+    let info = info.clone();
+    tauri::async_runtime::spawn(async move {
+        run_pipeline(&app, &info, &config).await;
+    });
+}
+
+/// Run the enabled steps again for a finished session.
+#[tauri::command]
+#[specta::specta]
+pub async fn run_post_session_pipeline(app: tauri::AppHandle, session_id: String) -> Result<PipelineRun, String> {
+    let info = export::load_session(&app, &session_id)?;
+    let config = settings::load_settings(&app).post_session;
+    Ok(run_pipeline(&app, &info, &config).await)
+}
+
+#[tauri::command]
+#[specta::specta]
+pub fn get_post_session_status(app: tauri::AppHandle, session_id: String) -> Result<Option<PipelineRun>, String> {
+    let path = sessions::session_dir(&app, &session_id)?.join(STATUS_FILE);
+    Ok(fs::read_to_string(path)
+        .ok()
+        .and_then(|content| serde_json::from_str(&content).ok()))
+}
+
+/// Store the Slack incoming webhook URL in the keychain. Empty removes it.
+#[tauri::command]
+#[specta::specta]
+pub fn set_slack_webhook(url: String) -> Result<(), String> {
+    let url = url.trim();
+    if url.is_empty() {
+        return keychain::delete_secret(SLACK_WEBHOOK_ACCOUNT);
+    }
+    if !url.starts_with("https://") {
+        return Err("the Slack webhook must be an https:// URL".to_string());
+    }
+    keychain::set_secret(SLACK_WEBHOOK_ACCOUNT, url)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::build_chapters;
+    use crate::topic_graph::TopicGraph;
+
+    #[test]
+    fn chapters_follow_visit_order() {
+        let mut graph = TopicGraph::default();
+        graph.enter("c1", "Intro", &["hello".to_string()], 4_000, Some(0));
+        graph.enter("c2", "GPU prices", &["gpu".to_string()], 65_000, Some(3));
+        graph.enter("c3", "Back to intro", &["hello".to_string()], 125_000, Some(9));
+        assert_eq!(build_chapters(&graph), vec!["0:00 Intro", "1:05 GPU prices", "2:05 Intro"]);
+    }
+}
@@ -0,0 +1,195 @@
+use std::{sync::Mutex, time::Duration};
+
+use serde::{Deserialize, Serialize};
+use tauri::Manager;
+use tauri_specta::Event;
+
+use crate::log_line;
+use crate::settings::{self, PowerSettings};
+
+const POWER_CHECK_INTERVAL: Duration = Duration::from_secs(30);
+
+#[derive(Serialize, Deserialize, Clone, Copy, PartialEq, Eq, Debug, specta::Type)]
+#[serde(rename_all = "camelCase")]
+pub enum PowerSource {
+    Ac,
+    Battery,
+    /// No battery found (desktops) or the platform can't tell.
+    Unknown,
+}
+
+/// Current power source and the workload policy derived from it. Emitted as
+/// `power-state` whenever either changes.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, specta::Type, tauri_specta::Event)]
+#[serde(rename_all = "camelCase")]
+pub struct PowerState {
+    pub source: PowerSource,
+    pub battery_percent: Option<u8>,
+    pub low_power: bool,
+    /// Seconds of audio per transcription segment; `None` keeps the default.
+    pub segment_seconds: Option<u32>,
+    /// Whisper model file to use instead of the default one.
+    pub whisper_model: Option<String>,
+    pub health_checks_paused: bool,
+}
+
+#[derive(Default)]
+pub struct PowerMonitorState {
+    current: Mutex<Option<PowerState>>,
+}
+
+/// Parse `pmset -g batt`, e.g. `Now drawing from 'Battery Power'` followed
+/// by `-InternalBattery-0 (id=…) 83%; discharging; …`.
+#[cfg_attr(not(target_os = "macos"), allow(dead_code))]
+pub fn parse_pmset(output: &str) -> (PowerSource, Option<u8>) {
+    let source = if output.contains("'Battery Power'") {
+        PowerSource::Battery
+    } else if output.contains("'AC Power'") {
+        PowerSource::Ac
+    } else {
+        PowerSource::Unknown
+    };
+    let percent = output
+        .split_whitespace()
+        .find_map(|word| word.trim_end_matches(';').strip_suffix('%')?.parse().ok());
+    (source, percent)
+}
+
+#[cfg(target_os = "macos")]
+fn read_power_source() -> (PowerSource, Option<u8>) {
+    match std::process::Command::new("pmset").args(["-g", "batt"]).output() {
+        Ok(output) => parse_pmset(&String::from_utf8_lossy(&output.stdout)),
+        Err(err) => {
+            log_line(&format!("[power] pmset failed: {}", err));
+            (PowerSource::Unknown, None)
+        }
+    }
+}
+
+#[cfg(target_os = "linux")]
+fn read_power_source() -> (PowerSource, Option<u8>) {
+    let read = |path: &std::path::Path, name: &str| {
+        std::fs::read_to_string(path.join(name))
+            .map(|value| value.trim().to_string())
+            .unwrap_or_default()
+    };
+    let Ok(entries) = std::fs::read_dir("/sys/class/power_supply") else {
+        return (PowerSource::Unknown, None);
+    };
+    let mut mains_online = false;
+    let mut discharging = false;
+    let mut percent = None;
+    for entry in entries.filter_map(|entry| entry.ok()) {
+        let path = entry.path();
+        match read(&path, "type").as_str() {
+            "Mains" => mains_online |= read(&path, "online") == "1",
+            "Battery" => {
+                discharging |= read(&path, "status") == "Discharging";
+                percent = percent.or_else(|| read(&path, "capacity").parse().ok());
+            }
+            _ => {}
+        }
+    }
+    let source = if discharging && !mains_online {
+        PowerSource::Battery
+    } else if percent.is_some() || mains_online {
+        PowerSource::Ac
+    } else {
+        PowerSource::Unknown
+    };
+    (source, percent)
+}
+
+#[cfg(not(any(target_os = "macos", target_os = "linux")))]
+fn read_power_source() -> (PowerSource, Option<u8>) {
+    (PowerSource::Unknown, None)
+}
+
+/// Apply the user's policy to a power reading.
+pub fn power_state(source: PowerSource, battery_percent: Option<u8>, policy: &PowerSettings) -> PowerState {
+    let low_power = policy.enabled && source == PowerSource::Battery;
+    PowerState {
+        source,
+        battery_percent,
+        low_power,
+        segment_seconds: (low_power && policy.battery_segment_seconds > 0)
+            .then_some(policy.battery_segment_seconds),
+        whisper_model: Some(policy.battery_whisper_model.trim().to_string())
+            .filter(|model| low_power && !model.is_empty()),
+        health_checks_paused: low_power && policy.pause_health_checks,
+    }
+}
+
+fn refresh(app: &tauri::AppHandle) -> PowerState {
+    let (source, percent) = read_power_source();
+    let state = power_state(source, percent, &settings::load_settings(app).power);
+    let previous = app
+        .state::<PowerMonitorState>()
+        .current
+        .lock()
+        .unwrap()
+        .replace(state.clone());
+    let changed = previous.as_ref().is_none_or(|previous| {
+        previous.source != state.source || previous.low_power != state.low_power
+    });
+    if changed {
+        log_line(&format!(
+            "[power] {:?}{} (low power: {})",
+            state.source,
+            state.battery_percent.map(|p| format!(" {}%", p)).unwrap_or_default(),
+            state.low_power
+        ));
+    }
+    if previous.as_ref() != Some(&state) {
+        if let Err(err) = state.clone().emit_to(app, "main") {
+            log_line(&format!("[power] failed to notify main window: {}", err));
+        }
+    }
+    state
+}
+
+/// The last reading, taking one if the monitor hasn't run yet.
+pub fn current(app: &tauri::AppHandle) -> PowerState {
+    let cached = app.state::<PowerMonitorState>().current.lock().unwrap().clone();
+    cached.unwrap_or_else(|| refresh(app))
+}
+
+/// Re-read the power source periodically so policy follows the charger.
+pub fn spawn_monitor(app: tauri::AppHandle) {
+    tauri::async_runtime::spawn(async move {
+        loop {
+            let handle = app.clone();
+            let _ = tauri::async_runtime::spawn_blocking(move || refresh(&handle)).await;
+            tokio::time::sleep(POWER_CHECK_INTERVAL).await;
+        }
+    });
+}
+
+#[tauri::command]
+#[specta::specta]
+pub fn get_power_state(app: tauri::AppHandle) -> PowerState {
+    refresh(&app)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{parse_pmset, power_state, PowerSource};
+    use crate::settings::PowerSettings;
+
+    #[test]
+    fn battery_reading_enables_policy() {
+        let output = "Now drawing from 'Battery Power'\n -InternalBattery-0 (id=1234)\t83%; discharging; 4:12 remaining present: true\n";
+        assert_eq!(parse_pmset(output), (PowerSource::Battery, Some(83)));
+        assert_eq!(parse_pmset("Now drawing from 'AC Power'\n").0, PowerSource::Ac);
+
+        let policy = PowerSettings::default();
+        let state = power_state(PowerSource::Battery, Some(83), &policy);
+        assert!(state.low_power && state.health_checks_paused);
+        assert_eq!(state.segment_seconds, Some(policy.battery_segment_seconds));
+        assert_eq!(state.whisper_model.as_deref(), Some("ggml-tiny.en.bin"));
+
+        let disabled = PowerSettings { enabled: false, ..PowerSettings::default() };
+        assert!(!power_state(PowerSource::Battery, None, &disabled).low_power);
+        assert!(!power_state(PowerSource::Ac, None, &policy).low_power);
+    }
+}
@@ -0,0 +1,322 @@
+use std::{
+    fmt, fs,
+    path::{Path, PathBuf},
+};
+
+use serde::{Deserialize, Serialize};
+
+use crate::{log_line, sessions};
+
+const MB: u64 = 1024 * 1024;
+
+/// Work that needs a resource check before it starts.
+#[derive(Serialize, Deserialize, Clone, Copy, Debug, PartialEq, Eq, specta::Type)]
+#[serde(rename_all = "kebab-case")]
+pub enum PreflightOperation {
+    Recording,
+    ModelDownload,
+    Retranscription,
+}
+
+impl PreflightOperation {
+    /// Conservative (disk, memory) estimates in bytes. Recording covers a
+    /// multi-hour transcript plus segment WAVs; model download covers the
+    /// whisper.cpp checkout, build, and model; retranscription covers the
+    /// model loaded in memory.
+    fn requirements(self) -> (u64, u64) {
+        match self {
+            PreflightOperation::Recording => (500 * MB, 300 * MB),
+            PreflightOperation::ModelDownload => (1_200 * MB, 200 * MB),
+            PreflightOperation::Retranscription => (300 * MB, 800 * MB),
+        }
+    }
+}
+
+#[derive(Serialize, Deserialize, Clone, Copy, Debug, PartialEq, Eq, specta::Type)]
+#[serde(rename_all = "kebab-case")]
+pub enum PreflightResource {
+    Disk,
+    Memory,
+}
+
+#[derive(Serialize, Clone, specta::Type)]
+#[serde(rename_all = "camelCase")]
+pub struct PreflightCheck {
+    pub resource: PreflightResource,
+    pub required_bytes: u64,
+    /// `None` when the platform doesn't report it; the check then passes.
+    pub available_bytes: Option<u64>,
+    /// Volume checked for disk requirements.
+    pub path: Option<String>,
+    pub ok: bool,
+}
+
+#[derive(Serialize, Clone, specta::Type)]
+#[serde(rename_all = "camelCase")]
+pub struct PreflightReport {
+    pub operation: PreflightOperation,
+    pub ok: bool,
+    pub checks: Vec<PreflightCheck>,
+    pub suggestions: Vec<String>,
+}
+
+/// Returned by operations refused by a failed preflight.
+pub struct PreflightError(pub PreflightReport);
+
+impl fmt::Display for PreflightError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let failed: Vec<String> = self
+            .0
+            .checks
+            .iter()
+            .filter(|check| !check.ok)
+            .map(|check| {
+                format!(
+                    "{} needs {} but only {} is available",
+                    match check.resource {
+                        PreflightResource::Disk => "disk",
+                        PreflightResource::Memory => "memory",
+                    },
+                    format_bytes(check.required_bytes),
+                    format_bytes(check.available_bytes.unwrap_or_default())
+                )
+            })
+            .collect();
+        write!(f, "Not enough resources: {}.", failed.join("; "))?;
+        for suggestion in &self.0.suggestions {
+            write!(f, " {}", suggestion)?;
+        }
+        Ok(())
+    }
+}
+
+impl From<PreflightError> for String {
+    fn from(err: PreflightError) -> Self {
+        err.to_string()
+    }
+}
+
+pub fn format_bytes(bytes: u64) -> String {
+    if bytes >= 1024 * MB {
+        format!("{:.1} GB", bytes as f64 / (1024 * MB) as f64)
+    } else {
+        format!("{} MB", bytes / MB)
+    }
+}
+
+/// Free space on the volume containing `path` (or its nearest existing
+/// ancestor, so it works before the target directory is created).
+#[cfg(unix)]
+pub fn available_disk_bytes(path: &Path) -> Option<u64> {
+    use std::ffi::CString;
+    use std::os::unix::ffi::OsStrExt;
+
+    let existing = path.ancestors().find(|p| p.exists())?;
+    let c_path = CString::new(existing.as_os_str().as_bytes()).ok()?;
+    let mut stat: libc::statvfs = unsafe { std::mem::zeroed() };
+    if unsafe { libc::statvfs(c_path.as_ptr(), &mut stat) } != 0 {
+        return None;
+    }
+    #[allow(clippy::unnecessary_cast)]
+    Some(stat.f_bavail as u64 * stat.f_frsize as u64)
+}
+
+#[cfg(not(unix))]
+pub fn available_disk_bytes(_path: &Path) -> Option<u64> {
+    None
+}
+
+#[cfg(target_os = "linux")]
+pub fn available_memory_bytes() -> Option<u64> {
+    let meminfo = fs::read_to_string("/proc/meminfo").ok()?;
+    let line = meminfo.lines().find(|line| line.starts_with("MemAvailable:"))?;
+    let kb: u64 = line.split_whitespace().nth(1)?.parse().ok()?;
+    Some(kb * 1024)
+}
+
+/// Free + inactive + purgeable pages from `vm_stat`, which is roughly what
+/// macOS can hand to a new allocation without swapping.
+#[cfg(target_os = "macos")]
+pub fn available_memory_bytes() -> Option<u64> {
+    let output = std::process::Command::new("vm_stat").output().ok()?;
+    let text = String::from_utf8_lossy(&output.stdout);
+    let page_size = text
+        .lines()
+        .next()
+        .and_then(|line| line.split("page size of ").nth(1))
+        .and_then(|rest| rest.split_whitespace().next())
+        .and_then(|n| n.parse::<u64>().ok())
+        .unwrap_or(4096);
+    let pages = |label: &str| -> u64 {
+        text.lines()
+            .find(|line| line.starts_with(label))
+            .and_then(|line| line.split(':').nth(1))
+            .and_then(|n| n.trim().trim_end_matches('.').parse::<u64>().ok())
+            .unwrap_or(0)
+    };
+    Some((pages("Pages free") + pages("Pages inactive") + pages("Pages purgeable")) * page_size)
+}
+
+#[cfg(not(any(target_os = "linux", target_os = "macos")))]
+pub fn available_memory_bytes() -> Option<u64> {
+    None
+}
+
+fn dir_size(path: &Path) -> u64 {
+    let Ok(entries) = fs::read_dir(path) else {
+        return 0;
+    };
+    entries
+        .filter_map(|entry| entry.ok())
+        .map(|entry| match entry.metadata() {
+            Ok(meta) if meta.is_dir() => dir_size(&entry.path()),
+            Ok(meta) => meta.len(),
+            Err(_) => 0,
+        })
+        .sum()
+}
+
+fn cleanup_suggestions(app: &tauri::AppHandle, report: &PreflightReport) -> Vec<String> {
+    let mut suggestions = Vec::new();
+    for check in report.checks.iter().filter(|check| !check.ok) {
+        let missing = check
+            .required_bytes
+            .saturating_sub(check.available_bytes.unwrap_or_default());
+        match check.resource {
+            PreflightResource::Disk => {
+                suggestions.push(format!(
+                    "Free at least {} on {}.",
+                    format_bytes(missing),
+                    check.path.as_deref().unwrap_or("the target volume")
+                ));
+                if let Ok(dir) = sessions::sessions_dir(app) {
+                    let size = dir_size(&dir);
+                    if size >= 50 * MB {
+                        suggestions.push(format!(
+                            "Old sessions use {} in {}; delete ones you no longer need.",
+                            format_bytes(size),
+                            dir.display()
+                        ));
+                    }
+                }
+                if report.operation != PreflightOperation::ModelDownload {
+                    suggestions.push(
+                        "Remove unused whisper models from whisper_cpp/models.".to_string(),
+                    );
+                }
+            }
+            PreflightResource::Memory => {
+                suggestions.push(format!(
+                    "Close other applications to free about {} of memory.",
+                    format_bytes(missing)
+                ));
+                if report.operation == PreflightOperation::Retranscription {
+                    suggestions.push("Pick a smaller whisper model for re-transcription.".to_string());
+                }
+            }
+        }
+    }
+    suggestions
+}
+
+/// Check disk at `target` and system memory against the estimates for
+/// `operation`.
+pub fn check(app: &tauri::AppHandle, operation: PreflightOperation, target: &Path) -> PreflightReport {
+    check_with(app, operation, target, operation.requirements())
+}
+
+fn check_with(
+    app: &tauri::AppHandle,
+    operation: PreflightOperation,
+    target: &Path,
+    (disk_required, memory_required): (u64, u64),
+) -> PreflightReport {
+    let disk_available = available_disk_bytes(target);
+    let memory_available = available_memory_bytes();
+    let checks = vec![
+        PreflightCheck {
+            resource: PreflightResource::Disk,
+            required_bytes: disk_required,
+            available_bytes: disk_available,
+            path: Some(target.display().to_string()),
+            ok: disk_available.is_none_or(|available| available >= disk_required),
+        },
+        PreflightCheck {
+            resource: PreflightResource::Memory,
+            required_bytes: memory_required,
+            available_bytes: memory_available,
+            path: None,
+            ok: memory_available.is_none_or(|available| available >= memory_required),
+        },
+    ];
+    let mut report = PreflightReport {
+        operation,
+        ok: checks.iter().all(|check| check.ok),
+        checks,
+        suggestions: Vec::new(),
+    };
+    report.suggestions = cleanup_suggestions(app, &report);
+    report
+}
+
+/// Refuse to start `operation` when the preflight fails.
+pub fn ensure(app: &tauri::AppHandle, operation: PreflightOperation, target: &Path) -> Result<(), PreflightError> {
+    ensure_with(app, operation, target, operation.requirements())
+}
+
+/// `ensure` with a disk requirement the caller knows better than the
+/// fixed estimate, e.g. the size of the model about to be downloaded.
+pub fn ensure_disk(
+    app: &tauri::AppHandle,
+    operation: PreflightOperation,
+    target: &Path,
+    disk_required: u64,
+) -> Result<(), PreflightError> {
+    let (_, memory_required) = operation.requirements();
+    ensure_with(app, operation, target, (disk_required, memory_required))
+}
+
+fn ensure_with(
+    app: &tauri::AppHandle,
+    operation: PreflightOperation,
+    target: &Path,
+    requirements: (u64, u64),
+) -> Result<(), PreflightError> {
+    let report = check_with(app, operation, target, requirements);
+    if report.ok {
+        return Ok(());
+    }
+    let err = PreflightError(report);
+    log_line(&format!("[preflight] {:?} refused: {}", operation, err));
+    Err(err)
+}
+
+/// Where `operation` writes its data.
+pub fn default_target(app: &tauri::AppHandle, operation: PreflightOperation) -> PathBuf {
+    match operation {
+        PreflightOperation::ModelDownload => PathBuf::from(env!("CARGO_MANIFEST_DIR"))
+            .parent()
+            .map(PathBuf::from)
+            .unwrap_or_default(),
+        _ => sessions::sessions_dir(app).unwrap_or_else(|_| std::env::temp_dir()),
+    }
+}
+
+/// Let the frontend check ahead of time (e.g. to grey out "Start listening").
+#[tauri::command]
+#[specta::specta]
+pub fn preflight_check(app: tauri::AppHandle, operation: PreflightOperation) -> PreflightReport {
+    let target = default_target(&app, operation);
+    check(&app, operation, &target)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::format_bytes;
+
+    #[test]
+    fn formats_sizes_for_messages() {
+        assert_eq!(format_bytes(300 * 1024 * 1024), "300 MB");
+        assert_eq!(format_bytes(1536 * 1024 * 1024), "1.5 GB");
+    }
+}
@@ -0,0 +1,181 @@
+//! Optional clean-up of captured audio before whisper sees it: a high-pass
+//! filter against fan and desk rumble, a gate that pulls the background
+//! down between words, and normalization of quiet microphones. Steady fan
+//! noise in otherwise silent stretches is what whisper hallucinates
+//! `[Music]` from. Configured per input under `transcription.preprocessing`.
+
+use crate::settings::{AudioFilters, PreprocessingSettings};
+use crate::{vad, wav};
+
+const GATE_FRAME_MS: usize = 20;
+/// Share of frames treated as background when estimating the noise floor.
+const FLOOR_PERCENTILE: f32 = 0.1;
+/// Frames this much above the floor (about 6 dB) pass the gate.
+const GATE_RATIO: f32 = 2.0;
+/// Gain of gated frames, about -20 dB.
+const GATE_GAIN: f32 = 0.1;
+/// Frames kept open after a loud one, so word endings aren't clipped.
+const GATE_HOLD_FRAMES: usize = 5;
+const TARGET_PEAK: f32 = 0.9;
+/// Normalization never boosts more than this (20 dB).
+const MAX_GAIN: f32 = 10.0;
+
+/// Filters for `input` (an input track or device name), else the defaults.
+pub fn filters_for<'a>(settings: &'a PreprocessingSettings, input: Option<&str>) -> &'a AudioFilters {
+    input
+        .map(str::trim)
+        .filter(|input| !input.is_empty())
+        .and_then(|input| {
+            settings
+                .inputs
+                .iter()
+                .find(|entry| entry.input.trim().eq_ignore_ascii_case(input))
+        })
+        .map(|entry| &entry.filters)
+        .unwrap_or(&settings.default)
+}
+
+/// Second-order Butterworth high-pass (RBJ cookbook biquad).
+fn high_pass(samples: &mut [f32], sample_rate: u32, cutoff_hz: f32) {
+    let cutoff = cutoff_hz.clamp(1.0, sample_rate as f32 * 0.45);
+    let w0 = 2.0 * std::f32::consts::PI * cutoff / sample_rate as f32;
+    let alpha = w0.sin() / std::f32::consts::SQRT_2;
+    let cos = w0.cos();
+    let a0 = 1.0 + alpha;
+    let (b0, b1, b2) = ((1.0 + cos) / 2.0 / a0, -(1.0 + cos) / a0, (1.0 + cos) / 2.0 / a0);
+    let (a1, a2) = (-2.0 * cos / a0, (1.0 - alpha) / a0);
+    let (mut x1, mut x2, mut y1, mut y2) = (0.0, 0.0, 0.0, 0.0);
+    for sample in samples.iter_mut() {
+        let x0 = *sample;
+        let y0 = b0 * x0 + b1 * x1 + b2 * x2 - a1 * y1 - a2 * y2;
+        (x2, x1, y2, y1) = (x1, x0, y1, y0);
+        *sample = y0;
+    }
+}
+
+fn rms(frame: &[f32]) -> f32 {
+    (frame.iter().map(|sample| sample * sample).sum::<f32>() / frame.len() as f32).sqrt()
+}
+
+/// Attenuate frames near the clip's own noise floor, ramping the gain
+/// across each frame so the gate doesn't click.
+fn suppress_noise(samples: &mut [f32], sample_rate: u32) {
+    let frame_len = (sample_rate as usize * GATE_FRAME_MS / 1000).max(1);
+    let energies: Vec<f32> = samples.chunks(frame_len).map(rms).collect();
+    if energies.len() < 2 {
+        return;
+    }
+    let mut sorted = energies.clone();
+    sorted.sort_by(|a, b| a.total_cmp(b));
+    let floor = sorted[((sorted.len() - 1) as f32 * FLOOR_PERCENTILE) as usize];
+    let threshold = (floor * GATE_RATIO).max(1e-4);
+    let mut gain = 1.0;
+    let mut hold = 0;
+    for (frame, energy) in samples.chunks_mut(frame_len).zip(energies) {
+        if energy > threshold {
+            hold = GATE_HOLD_FRAMES;
+        } else {
+            hold = hold.saturating_sub(1);
+        }
+        let target = if hold > 0 { 1.0 } else { GATE_GAIN };
+        let step = (target - gain) / frame.len() as f32;
+        for sample in frame.iter_mut() {
+            gain += step;
+            *sample *= gain;
+        }
+        gain = target;
+    }
+}
+
+/// Bring the peak up to `TARGET_PEAK`. Clips without speech are left
+/// alone, so background noise isn't boosted into something whisper hears.
+fn normalize(samples: &mut [f32], sample_rate: u32) {
+    let peak = samples.iter().fold(0.0f32, |peak, sample| peak.max(sample.abs()));
+    if peak < 1e-4 || !vad::has_speech(samples, sample_rate) {
+        return;
+    }
+    let gain = (TARGET_PEAK / peak).min(MAX_GAIN);
+    samples.iter_mut().for_each(|sample| *sample *= gain);
+}
+
+/// Run the enabled filters over `samples` in place.
+pub fn process(filters: &AudioFilters, sample_rate: u32, samples: &mut [f32]) {
+    if !filters.enabled {
+        return;
+    }
+    if let Some(cutoff) = filters.high_pass_hz.filter(|hz| *hz > 0.0) {
+        high_pass(samples, sample_rate, cutoff);
+    }
+    if filters.noise_suppression {
+        suppress_noise(samples, sample_rate);
+    }
+    if filters.normalize {
+        normalize(samples, sample_rate);
+    }
+}
+
+/// `process` for a WAV segment. Audio that isn't 16-bit mono PCM is
+/// passed through untouched.
+pub fn process_wav(settings: &PreprocessingSettings, input: Option<&str>, wav_bytes: Vec<u8>) -> Vec<u8> {
+    let filters = filters_for(settings, input);
+    if !filters.enabled {
+        return wav_bytes;
+    }
+    match wav::decode_pcm16_mono(&wav_bytes) {
+        Ok((sample_rate, mut samples)) => {
+            process(filters, sample_rate, &mut samples);
+            wav::encode_pcm16_mono(sample_rate, &samples)
+        }
+        Err(_) => wav_bytes,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{filters_for, process, rms};
+    use crate::settings::{AudioFilters, InputFilters, PreprocessingSettings};
+
+    #[test]
+    fn filters_rumble_and_gates_background_between_words() {
+        let rate = 16_000;
+        let filters = AudioFilters {
+            enabled: true,
+            high_pass_hz: Some(100.0),
+            noise_suppression: true,
+            normalize: false,
+        };
+
+        // 30 Hz rumble is mostly removed, 1 kHz speech band passes.
+        let tone = |hz: f32| -> Vec<f32> {
+            (0..rate).map(|i| 0.3 * (2.0 * std::f32::consts::PI * hz * i as f32 / rate as f32).sin()).collect()
+        };
+        let mut rumble = tone(30.0);
+        process(&AudioFilters { noise_suppression: false, ..filters.clone() }, rate, &mut rumble);
+        assert!(rms(&rumble[rate as usize / 2..]) < 0.03);
+        let mut voice = tone(1000.0);
+        process(&AudioFilters { noise_suppression: false, ..filters.clone() }, rate, &mut voice);
+        assert!(rms(&voice[rate as usize / 2..]) > 0.19);
+
+        // Hiss with a burst of "speech" in the middle: the hiss is pulled
+        // down, the burst isn't.
+        let mut clip: Vec<f32> = (0..rate * 2).map(|i| if i % 2 == 0 { 0.01 } else { -0.01 }).collect();
+        for (i, sample) in clip.iter_mut().enumerate().skip(rate as usize).take(rate as usize / 4) {
+            *sample = 0.3 * (i as f32 * 0.4).sin();
+        }
+        let before = rms(&clip[..rate as usize / 2]);
+        process(&filters, rate, &mut clip);
+        assert!(rms(&clip[..rate as usize / 2]) < before * 0.2);
+        assert!(rms(&clip[rate as usize + 1000..rate as usize + 3000]) > 0.15);
+
+        let settings = PreprocessingSettings {
+            default: AudioFilters::default(),
+            inputs: vec![InputFilters {
+                input: "Laptop Mic".to_string(),
+                filters: filters.clone(),
+            }],
+        };
+        assert!(filters_for(&settings, Some("laptop mic")).enabled);
+        assert!(!filters_for(&settings, Some("Guest")).enabled);
+        assert!(!filters_for(&settings, None).enabled);
+    }
+}
@@ -0,0 +1,280 @@
+//! One way to run an external tool: whisper-cli, the setup scripts, the
+//! node agent, the canvas server. `ProcessRunner` wraps a prepared
+//! `Command` with a timeout, a cancel flag, input on stdin, per-line
+//! callbacks for stdout and stderr, and extra environment. Children start
+//! in their own process group, so stopping one also stops whatever it
+//! started (the agent's MCP servers, a script's downloads).
+
+use std::io::{BufRead, BufReader, Read, Write};
+use std::os::unix::process::CommandExt;
+use std::process::{Child, Command, Output, Stdio};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Arc;
+use std::thread::JoinHandle;
+use std::time::{Duration, Instant};
+
+use crate::log_line;
+
+const POLL_INTERVAL: Duration = Duration::from_millis(50);
+/// How long `graceful_kill` waits after SIGTERM before SIGKILL.
+const KILL_GRACE: Duration = Duration::from_secs(2);
+
+type LineSink = Box<dyn FnMut(&str) + Send>;
+
+pub struct ProcessRunner {
+    /// Names the process in errors and log lines, e.g. "llm agent".
+    label: String,
+    command: Command,
+    timeout: Option<Duration>,
+    cancel: Option<Arc<AtomicBool>>,
+    input: Option<Vec<u8>>,
+    on_stdout: Option<LineSink>,
+    on_stderr: Option<LineSink>,
+}
+
+impl ProcessRunner {
+    pub fn new(label: impl Into<String>, command: Command) -> Self {
+        Self {
+            label: label.into(),
+            command,
+            timeout: None,
+            cancel: None,
+            input: None,
+            on_stdout: None,
+            on_stderr: None,
+        }
+    }
+
+    pub fn envs<K: AsRef<std::ffi::OsStr>, V: AsRef<std::ffi::OsStr>>(
+        mut self,
+        envs: impl IntoIterator<Item = (K, V)>,
+    ) -> Self {
+        self.command.envs(envs);
+        self
+    }
+
+    /// Stop the process if it is still running after `timeout`.
+    pub fn timeout(mut self, timeout: Duration) -> Self {
+        self.timeout = Some(timeout);
+        self
+    }
+
+    /// Stop the process once `cancel` is set.
+    pub fn cancel(mut self, cancel: Arc<AtomicBool>) -> Self {
+        self.cancel = Some(cancel);
+        self
+    }
+
+    /// Written to stdin, which is then closed.
+    pub fn input(mut self, input: impl Into<Vec<u8>>) -> Self {
+        self.input = Some(input.into());
+        self
+    }
+
+    /// Called with each stdout line as it arrives; the output is still
+    /// collected.
+    pub fn on_stdout_line(mut self, sink: impl FnMut(&str) + Send + 'static) -> Self {
+        self.on_stdout = Some(Box::new(sink));
+        self
+    }
+
+    pub fn on_stderr_line(mut self, sink: impl FnMut(&str) + Send + 'static) -> Self {
+        self.on_stderr = Some(Box::new(sink));
+        self
+    }
+
+    /// Run to completion and collect its output. A non-zero exit is not an
+    /// error here; timeouts, cancellation and failing to start are.
+    pub fn run(mut self) -> Result<Output, String> {
+        self.command
+            .stdin(if self.input.is_some() { Stdio::piped() } else { Stdio::null() })
+            .stdout(Stdio::piped())
+            .stderr(Stdio::piped())
+            .process_group(0);
+        let mut child = self
+            .command
+            .spawn()
+            .map_err(|err| format!("failed to start {}: {}", self.label, err))?;
+        if let (Some(input), Some(mut stdin)) = (self.input.take(), child.stdin.take()) {
+            if let Err(err) = stdin.write_all(&input) {
+                graceful_kill(&mut child);
+                return Err(format!("failed to write {} input: {}", self.label, err));
+            }
+        }
+        let stdout = collect_lines(child.stdout.take(), self.on_stdout.take());
+        let stderr = collect_lines(child.stderr.take(), self.on_stderr.take());
+
+        let started = Instant::now();
+        let status = loop {
+            let stopped = if self.cancel.as_ref().is_some_and(|cancel| cancel.load(Ordering::SeqCst)) {
+                Some(format!("{} cancelled", self.label))
+            } else {
+                self.timeout
+                    .filter(|timeout| started.elapsed() > *timeout)
+                    .map(|timeout| format!("{} timed out after {}ms", self.label, timeout.as_millis()))
+            };
+            if let Some(err) = stopped {
+                graceful_kill(&mut child);
+                log_line(&format!("[process] {}", err));
+                let _ = (stdout.join(), stderr.join());
+                return Err(err);
+            }
+            match child.try_wait() {
+                Ok(Some(status)) => {
+                    // Whatever it left running would hold the pipes open.
+                    unsafe {
+                        libc::kill(-(child.id() as i32), libc::SIGTERM);
+                    }
+                    break status;
+                }
+                Ok(None) => std::thread::sleep(POLL_INTERVAL),
+                Err(err) => {
+                    graceful_kill(&mut child);
+                    return Err(format!("failed to poll {}: {}", self.label, err));
+                }
+            }
+        };
+        Ok(Output {
+            status,
+            stdout: stdout.join().unwrap_or_default(),
+            stderr: stderr.join().unwrap_or_default(),
+        })
+    }
+
+    /// Start the process and leave it running. Output goes to the line
+    /// callbacks, if any, and is otherwise discarded. The caller stops it
+    /// with `graceful_kill`.
+    pub fn spawn_detached(mut self) -> Result<Child, String> {
+        let piped = |sink: &Option<LineSink>| if sink.is_some() { Stdio::piped() } else { Stdio::null() };
+        self.command
+            .stdin(Stdio::null())
+            .stdout(piped(&self.on_stdout))
+            .stderr(piped(&self.on_stderr))
+            .process_group(0);
+        let mut child = self
+            .command
+            .spawn()
+            .map_err(|err| format!("failed to start {}: {}", self.label, err))?;
+        log_line(&format!("[process] started {} (pid: {})", self.label, child.id()));
+        forward_lines(child.stdout.take(), self.on_stdout.take());
+        forward_lines(child.stderr.take(), self.on_stderr.take());
+        Ok(child)
+    }
+}
+
+/// Hand each line of `pipe` to `sink` on its own thread until it closes,
+/// keeping nothing.
+fn forward_lines<R: Read + Send + 'static>(pipe: Option<R>, sink: Option<LineSink>) {
+    let (Some(pipe), Some(mut sink)) = (pipe, sink) else {
+        return;
+    };
+    std::thread::spawn(move || {
+        for line in BufReader::new(pipe).lines().map_while(Result::ok) {
+            sink(line.trim_end_matches('\r'));
+        }
+    });
+}
+
+/// Read `pipe` to the end on its own thread, handing each line to `sink`.
+fn collect_lines<R: Read + Send + 'static>(pipe: Option<R>, mut sink: Option<LineSink>) -> JoinHandle<Vec<u8>> {
+    std::thread::spawn(move || {
+        let mut collected = Vec::new();
+        let Some(pipe) = pipe else {
+            return collected;
+        };
+        let mut reader = BufReader::new(pipe);
+        let mut line = Vec::new();
+        while matches!(reader.read_until(b'\n', &mut line), Ok(read) if read > 0) {
+            if let Some(sink) = sink.as_mut() {
+                sink(String::from_utf8_lossy(&line).trim_end_matches(['\r', '\n']));
+            }
+            collected.append(&mut line);
+        }
+        collected
+    })
+}
+
+/// Send SIGTERM first to allow graceful MCP client cleanup, then SIGKILL
+/// if the process hasn't exited within the grace period. A child leading
+/// its own process group is signalled with the whole group.
+pub fn graceful_kill(child: &mut Child) {
+    let pid = child.id() as i32;
+    let target = if unsafe { libc::getpgid(pid) } == pid { -pid } else { pid };
+    // Send SIGTERM so the Node.js process can close MCP clients cleanly.
+    unsafe {
+        libc::kill(target, libc::SIGTERM);
+    }
+
+    let deadline = Instant::now() + KILL_GRACE;
+    while Instant::now() < deadline {
+        match child.try_wait() {
+            Ok(Some(_)) => {
+                // Anything it started that ignored SIGTERM goes too.
+                if target < 0 {
+                    unsafe {
+                        libc::kill(target, libc::SIGKILL);
+                    }
+                }
+                return;
+            }
+            Ok(None) => {}
+            Err(_) => break,
+        }
+        std::thread::sleep(POLL_INTERVAL);
+    }
+
+    // Still running — force kill.
+    unsafe {
+        libc::kill(target, libc::SIGKILL);
+    }
+    let _ = child.kill();
+    let _ = child.wait(); // reap to ensure pipe cleanup before returning
+}
+
+#[cfg(test)]
+mod tests {
+    use std::process::Command;
+    use std::sync::atomic::AtomicBool;
+    use std::sync::{Arc, Mutex};
+    use std::time::{Duration, Instant};
+
+    use super::ProcessRunner;
+
+    #[test]
+    fn runs_with_input_and_stops_on_timeout_or_cancel() {
+        let lines = Arc::new(Mutex::new(Vec::new()));
+        let seen = lines.clone();
+        let mut cat = Command::new("sh");
+        cat.args(["-c", "cat; echo \"$GREETING\" >&2"]);
+        let output = ProcessRunner::new("cat", cat)
+            .envs([("GREETING", "hi")])
+            .input("one\ntwo\n")
+            .on_stdout_line(move |line| seen.lock().unwrap().push(line.to_string()))
+            .run()
+            .unwrap();
+        assert!(output.status.success());
+        assert_eq!(output.stdout, b"one\ntwo\n");
+        assert_eq!(output.stderr, b"hi\n");
+        assert_eq!(*lines.lock().unwrap(), vec!["one", "two"]);
+
+        // The background sleep shares the group, so the pipes close when
+        // the group is stopped and the runner doesn't hang on it.
+        let mut sleep = Command::new("sh");
+        sleep.args(["-c", "sleep 30 & sleep 30"]);
+        let started = Instant::now();
+        let err = ProcessRunner::new("sleeper", sleep)
+            .timeout(Duration::from_millis(200))
+            .run()
+            .unwrap_err();
+        assert_eq!(err, "sleeper timed out after 200ms");
+        assert!(started.elapsed() < Duration::from_secs(10));
+
+        let mut sleep = Command::new("sleep");
+        sleep.arg("30");
+        let err = ProcessRunner::new("sleeper", sleep)
+            .cancel(Arc::new(AtomicBool::new(true)))
+            .run()
+            .unwrap_err();
+        assert_eq!(err, "sleeper cancelled");
+    }
+}
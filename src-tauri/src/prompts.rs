@@ -0,0 +1,114 @@
+use serde_json::Value as JsonValue;
+
+use crate::settings::{self, SavedPrompt};
+use crate::{log_line, truncate_for_log, LlmAgentRequest, LlmAgentSettings, LlmAgentState};
+
+const PLACEHOLDER: &str = "{{text}}";
+
+const INSTRUCTIONS: &str = "You transform text the user has selected or copied. \
+Follow the request exactly and reply with the result only: no preamble, no quotes around it, no closing remarks.";
+
+const BUILT_IN: &[(&str, &str)] = &[
+    ("summarize", "Summarize the following text in a few sentences:\n\n{{text}}"),
+    ("translate", "Translate the following text into English. If it is already English, fix its grammar instead:\n\n{{text}}"),
+    ("explain", "Explain the following text in plain language for someone new to the subject:\n\n{{text}}"),
+    ("bullet-points", "Rewrite the following text as a short list of bullet points:\n\n{{text}}"),
+];
+
+/// Built-in prompts followed by the user's saved ones, which replace
+/// built-ins of the same name.
+fn all_prompts(saved: &[SavedPrompt]) -> Vec<SavedPrompt> {
+    let mut prompts: Vec<SavedPrompt> = BUILT_IN
+        .iter()
+        .map(|(name, template)| SavedPrompt {
+            name: name.to_string(),
+            template: template.to_string(),
+        })
+        .collect();
+    for prompt in saved.iter().filter(|prompt| !prompt.name.trim().is_empty()) {
+        prompts.retain(|existing| !existing.name.eq_ignore_ascii_case(prompt.name.trim()));
+        prompts.push(prompt.clone());
+    }
+    prompts
+}
+
+fn render(template: &str, text: &str) -> String {
+    if template.contains(PLACEHOLDER) {
+        template.replace(PLACEHOLDER, text)
+    } else {
+        format!("{}\n\n{}", template.trim_end(), text)
+    }
+}
+
+#[tauri::command]
+#[specta::specta]
+pub fn list_prompts(app: tauri::AppHandle) -> Vec<SavedPrompt> {
+    all_prompts(&settings::load_settings(&app).prompts.saved)
+}
+
+/// Send `text` through the saved prompt `prompt_name` with a single,
+/// tool-less LLM call and return the model's reply, for quick "summarize
+/// this" or "translate this" actions outside the voice loop.
+#[tauri::command]
+#[specta::specta]
+pub async fn run_prompt_on_text(
+    app: tauri::AppHandle,
+    prompt_name: String,
+    text: String,
+    settings: LlmAgentSettings,
+    state: tauri::State<'_, LlmAgentState>,
+) -> Result<String, String> {
+    let text = text.trim();
+    if text.is_empty() {
+        return Err("no text to run the prompt on".to_string());
+    }
+    let prompt = all_prompts(&settings::load_settings(&app).prompts.saved)
+        .into_iter()
+        .find(|prompt| prompt.name.eq_ignore_ascii_case(prompt_name.trim()))
+        .ok_or_else(|| format!("no prompt named {}", prompt_name))?;
+    log_line(&format!(
+        "[prompts] running {} on {}",
+        prompt.name,
+        truncate_for_log(text, 120)
+    ));
+    let payload = LlmAgentRequest {
+        settings,
+        instructions: INSTRUCTIONS.to_string(),
+        prompt: render(&prompt.template, text),
+        mode: Some("text-prompt".to_string()),
+        context: None,
+        trace_id: None,
+    };
+    let output = crate::run_llm_agent(app, payload, state).await?;
+    let result: JsonValue =
+        serde_json::from_str(&output).map_err(|err| format!("invalid prompt result: {}", err))?;
+    if result.get("ok").and_then(JsonValue::as_bool) != Some(true) {
+        let error = result.get("error").and_then(JsonValue::as_str).unwrap_or("prompt failed");
+        return Err(error.to_string());
+    }
+    Ok(result
+        .get("text")
+        .and_then(JsonValue::as_str)
+        .unwrap_or_default()
+        .trim()
+        .to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{all_prompts, render};
+    use crate::settings::SavedPrompt;
+
+    #[test]
+    fn saved_prompts_override_built_ins_and_render() {
+        let saved = vec![SavedPrompt {
+            name: "Summarize".to_string(),
+            template: "TL;DR:".to_string(),
+        }];
+        let prompts = all_prompts(&saved);
+        assert_eq!(prompts.iter().filter(|p| p.name.eq_ignore_ascii_case("summarize")).count(), 1);
+        assert_eq!(prompts.last().unwrap().template, "TL;DR:");
+        assert_eq!(render("TL;DR:", "hello"), "TL;DR:\n\nhello");
+        assert_eq!(render("Say {{text}} twice", "hi"), "Say hi twice");
+    }
+}
@@ -0,0 +1,190 @@
+//! Restoring punctuation and casing in transcripts from models that write
+//! lowercase run-on text (`tiny`, `base`). On when `options.punctuate` is
+//! set, per call or in the saved defaults. A configured local model
+//! command (`transcription.punctuation.modelCommand`) gets the text on
+//! stdin and prints it back punctuated; otherwise, or when it fails, the
+//! rules below run.
+
+use std::process::Command;
+use std::sync::Arc;
+use std::time::Duration;
+
+use regex::{NoExpand, RegexBuilder};
+
+use crate::process_runner::ProcessRunner;
+use crate::settings::PunctuationSettings;
+use crate::{log_line, truncate_for_log, SegmentSink};
+
+/// Words that start a new sentence once the current one has run this long.
+const MIN_SENTENCE_WORDS: usize = 6;
+/// Interjections that open a sentence and take a comma after them.
+const OPENERS: &[&str] = &["okay", "ok", "alright", "anyway", "anyways", "yeah"];
+/// First words that make a sentence a question.
+const QUESTION_WORDS: &[&str] = &[
+    "what", "why", "how", "who", "which", "whose", "is", "are", "am", "was", "were", "do", "does", "did", "can",
+    "could", "would", "should", "will", "won't", "isn't", "aren't", "don't", "doesn't", "didn't", "can't",
+];
+
+/// `word` lowercased without surrounding punctuation.
+fn bare(word: &str) -> String {
+    word.trim_matches(|c: char| !c.is_alphanumeric() && c != '\'').to_lowercase()
+}
+
+fn ends_sentence(word: &str) -> bool {
+    word.ends_with(['.', '?', '!'])
+}
+
+fn capitalize(word: &str) -> String {
+    let mut chars = word.chars();
+    match chars.next() {
+        Some(first) => first.to_uppercase().chain(chars).collect(),
+        None => String::new(),
+    }
+}
+
+/// Text with no capitals at all, or a long stretch without a sentence end.
+fn needs_restoring(text: &str) -> bool {
+    let has_letters = text.chars().any(char::is_alphabetic);
+    let no_capitals = !text.chars().any(char::is_uppercase);
+    let run_on = text.split_whitespace().count() >= 3 * MIN_SENTENCE_WORDS && !text.contains(['.', '?', '!']);
+    has_letters && (no_capitals || run_on)
+}
+
+fn finish_sentence(mut words: Vec<String>) -> String {
+    let first = bare(&words[0]);
+    if words.len() > 1 && OPENERS.contains(&first.as_str()) && !words[0].ends_with([',', '.', '?', '!']) {
+        words[0].push(',');
+    }
+    words[0] = capitalize(&words[0]);
+    let last = words.last_mut().expect("sentences aren't empty");
+    if !ends_sentence(last) {
+        let trimmed = last.trim_end_matches([',', ';', ':']).len();
+        last.truncate(trimmed);
+        // "Okay, so what ..." asks as much as "What ...".
+        let lead = words
+            .iter()
+            .map(|word| bare(word))
+            .find(|word| !OPENERS.contains(&word.as_str()) && word != "so")
+            .unwrap_or_default();
+        let question = QUESTION_WORDS.contains(&lead.as_str());
+        let last = words.last_mut().expect("sentences aren't empty");
+        last.push(if question { '?' } else { '.' });
+    }
+    words.join(" ")
+}
+
+/// Rule-based restoration: sentence breaks before interjections in long
+/// runs, a comma before "but", capital `I`, sentence capitals and end
+/// marks (`?` after a question word), then `vocabulary` spellings. Text
+/// that already looks punctuated is left alone.
+pub fn restore_with_rules(text: &str, vocabulary: &[String]) -> String {
+    if !needs_restoring(text) {
+        return text.to_string();
+    }
+    let mut sentences: Vec<Vec<String>> = vec![Vec::new()];
+    for word in text.split_whitespace() {
+        let key = bare(word);
+        let current = sentences.last_mut().expect("always one sentence");
+        if current.len() >= MIN_SENTENCE_WORDS && OPENERS.contains(&key.as_str()) {
+            sentences.push(Vec::new());
+        } else if key == "but" {
+            let previous = current.last_mut().filter(|previous| !previous.ends_with([',', ';', ':', '.', '?', '!']));
+            if let Some(previous) = previous {
+                previous.push(',');
+            }
+        }
+        let word = match key.as_str() {
+            "i" | "i'm" | "i've" | "i'll" | "i'd" => capitalize(word),
+            _ => word.to_string(),
+        };
+        let done = ends_sentence(&word);
+        sentences.last_mut().expect("always one sentence").push(word);
+        if done {
+            sentences.push(Vec::new());
+        }
+    }
+    let mut restored = sentences
+        .into_iter()
+        .filter(|sentence| !sentence.is_empty())
+        .map(finish_sentence)
+        .collect::<Vec<_>>()
+        .join(" ");
+    for term in vocabulary.iter().map(|term| term.trim()).filter(|term| !term.is_empty()) {
+        let pattern = format!(r"\b{}\b", regex::escape(term));
+        if let Ok(regex) = RegexBuilder::new(&pattern).case_insensitive(true).build() {
+            restored = regex.replace_all(&restored, NoExpand(term)).into_owned();
+        }
+    }
+    restored
+}
+
+/// Run the configured model command on `text`.
+fn run_model(settings: &PunctuationSettings, text: &str) -> Result<String, String> {
+    let (program, args) = settings.model_command.split_first().ok_or("no punctuation model command set")?;
+    let mut command = Command::new(program);
+    command.args(args);
+    let output = ProcessRunner::new("punctuation model", command)
+        .input(text.as_bytes().to_vec())
+        .timeout(Duration::from_millis(settings.timeout_ms.max(100)))
+        .run()?;
+    if !output.status.success() {
+        let stderr = String::from_utf8_lossy(&output.stderr);
+        return Err(format!("punctuation model failed: {}", truncate_for_log(stderr.trim(), 300)));
+    }
+    let restored = String::from_utf8_lossy(&output.stdout).split_whitespace().collect::<Vec<_>>().join(" ");
+    if restored.is_empty() {
+        return Err("punctuation model printed nothing".to_string());
+    }
+    Ok(restored)
+}
+
+/// Restore `text` with the model command when one is set, else the rules.
+pub async fn restore(settings: &PunctuationSettings, vocabulary: &[String], text: String) -> String {
+    if text.trim().is_empty() {
+        return text;
+    }
+    if settings.model_command.is_empty() {
+        return restore_with_rules(&text, vocabulary);
+    }
+    let (model_settings, input) = (settings.clone(), text.clone());
+    let result = tauri::async_runtime::spawn_blocking(move || run_model(&model_settings, &input))
+        .await
+        .map_err(|err| format!("punctuation task failed: {}", err))
+        .and_then(|result| result);
+    match result {
+        Ok(restored) => restored,
+        Err(err) => {
+            log_line(&format!("[punctuation] {}; using the built-in rules", err));
+            restore_with_rules(&text, vocabulary)
+        }
+    }
+}
+
+/// Partials go through the rules only, so a model command runs once per
+/// segment.
+pub fn wrap_sink(sink: SegmentSink, vocabulary: Vec<String>) -> SegmentSink {
+    Arc::new(move |text: &str| sink(&restore_with_rules(text, &vocabulary)))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::restore_with_rules;
+
+    #[test]
+    fn restores_sentences_capitals_and_vocabulary() {
+        let vocabulary = vec!["Excalidraw".to_string(), "Tauri".to_string()];
+        assert_eq!(
+            restore_with_rules(
+                "so i opened excalidraw and drew the tauri window but it looked off okay so what do we change",
+                &vocabulary
+            ),
+            "So I opened Excalidraw and drew the Tauri window, but it looked off. Okay, so what do we change?"
+        );
+        assert_eq!(
+            restore_with_rules("did you ship it. yes i did", &[]),
+            "Did you ship it. Yes I did."
+        );
+        let punctuated = "We shipped it. Did you see?";
+        assert_eq!(restore_with_rules(punctuated, &vocabulary), punctuated);
+    }
+}
@@ -0,0 +1,190 @@
+//! Push-to-talk: a global shortcut that listens while it's held, so the
+//! host doesn't have to click into the window mid-conversation. Key down
+//! and up are sent to the webview as `ptt-down`/`ptt-up`; with
+//! `pushToTalk.nativeRecording` the backend also starts and stops native
+//! recording itself.
+
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Mutex;
+
+use serde::{Deserialize, Serialize};
+use tauri::Manager;
+use tauri_plugin_global_shortcut::{GlobalShortcutExt, Shortcut, ShortcutState};
+use tauri_specta::Event;
+
+use crate::{log_line, recording, settings};
+
+/// The push-to-talk key went down. `recording` is set when the backend
+/// started native recording for it.
+#[derive(Serialize, Deserialize, Clone, Debug, specta::Type, tauri_specta::Event)]
+#[serde(rename_all = "camelCase")]
+pub struct PttDown {
+    pub recording: bool,
+}
+
+/// The push-to-talk key was released.
+#[derive(Serialize, Deserialize, Clone, Debug, specta::Type, tauri_specta::Event)]
+#[serde(rename_all = "camelCase")]
+pub struct PttUp {
+    pub recording: bool,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, specta::Type)]
+#[serde(rename_all = "camelCase")]
+pub struct PushToTalkStatus {
+    pub enabled: bool,
+    pub shortcut: String,
+    pub native_recording: bool,
+    /// The shortcut is registered with the OS.
+    pub registered: bool,
+    /// Why registering failed, e.g. another app owns the shortcut.
+    pub error: Option<String>,
+}
+
+#[derive(Default)]
+pub struct PushToTalkState {
+    registered: Mutex<Option<Shortcut>>,
+    error: Mutex<Option<String>>,
+    /// Auto-repeat sends more presses while the key is held.
+    held: AtomicBool,
+}
+
+fn parse(shortcut: &str) -> Result<Shortcut, String> {
+    shortcut
+        .trim()
+        .parse::<Shortcut>()
+        .map_err(|err| format!("invalid shortcut {:?}: {}", shortcut.trim(), err))
+}
+
+fn on_key(app: &tauri::AppHandle, state: ShortcutState) {
+    let held = &app.state::<PushToTalkState>().held;
+    let native = settings::load_settings(app).push_to_talk.native_recording;
+    match state {
+        ShortcutState::Pressed => {
+            if held.swap(true, Ordering::SeqCst) {
+                return;
+            }
+            let recording = native
+                && match recording::start_recording(app.clone()) {
+                    Ok(_) => true,
+                    Err(err) => {
+                        log_line(&format!("[ptt] native recording not started: {}", err));
+                        false
+                    }
+                };
+            let _ = PttDown { recording }.emit(app);
+        }
+        ShortcutState::Released => {
+            if !held.swap(false, Ordering::SeqCst) {
+                return;
+            }
+            if native {
+                if let Err(err) = recording::stop_recording(app.clone()) {
+                    log_line(&format!("[ptt] {}", err));
+                }
+            }
+            let _ = PttUp { recording: native }.emit(app);
+        }
+    }
+}
+
+/// Register the configured shortcut, replacing the previous one. Called
+/// at startup and whenever settings are saved.
+pub fn reload(app: &tauri::AppHandle) {
+    let config = settings::load_settings(app).push_to_talk;
+    let state = app.state::<PushToTalkState>();
+    let mut registered = state.registered.lock().unwrap();
+    let wanted = if config.enabled { parse(&config.shortcut).map(Some) } else { Ok(None) };
+    if wanted.as_ref().is_ok_and(|wanted| *wanted == *registered) {
+        return;
+    }
+    if let Some(previous) = registered.take() {
+        let _ = app.global_shortcut().unregister(previous);
+        state.held.store(false, Ordering::SeqCst);
+    }
+    let result = wanted.and_then(|wanted| match wanted {
+        Some(shortcut) => app
+            .global_shortcut()
+            .on_shortcut(shortcut, |app, _, event| on_key(app, event.state))
+            .map(|_| Some(shortcut))
+            .map_err(|err| format!("could not register {}: {}", config.shortcut.trim(), err)),
+        None => Ok(None),
+    });
+    match result {
+        Ok(shortcut) => {
+            if shortcut.is_some() {
+                log_line(&format!("[ptt] registered {}", config.shortcut.trim()));
+            }
+            *registered = shortcut;
+            *state.error.lock().unwrap() = None;
+        }
+        Err(err) => {
+            log_line(&format!("[ptt] {}", err));
+            *state.error.lock().unwrap() = Some(err);
+        }
+    }
+}
+
+fn status(app: &tauri::AppHandle) -> PushToTalkStatus {
+    let config = settings::load_settings(app).push_to_talk;
+    let state = app.state::<PushToTalkState>();
+    let registered = state.registered.lock().unwrap().is_some();
+    let error = state.error.lock().unwrap().clone();
+    PushToTalkStatus {
+        enabled: config.enabled,
+        shortcut: config.shortcut,
+        native_recording: config.native_recording,
+        registered,
+        error,
+    }
+}
+
+#[tauri::command]
+#[specta::specta]
+pub fn get_push_to_talk_status(app: tauri::AppHandle) -> PushToTalkStatus {
+    status(&app)
+}
+
+/// Turn push-to-talk on with `shortcut` (e.g. `CmdOrCtrl+Shift+Space`).
+#[tauri::command]
+#[specta::specta]
+pub fn register_push_to_talk(
+    app: tauri::AppHandle,
+    shortcut: String,
+    native_recording: bool,
+) -> Result<PushToTalkStatus, String> {
+    parse(&shortcut)?;
+    let mut current = settings::load_settings(&app);
+    current.push_to_talk.enabled = true;
+    current.push_to_talk.shortcut = shortcut.trim().to_string();
+    current.push_to_talk.native_recording = native_recording;
+    settings::save_settings(&app, &current)?;
+    reload(&app);
+    let status = status(&app);
+    match status.error.clone() {
+        Some(err) => Err(err),
+        None => Ok(status),
+    }
+}
+
+#[tauri::command]
+#[specta::specta]
+pub fn unregister_push_to_talk(app: tauri::AppHandle) -> Result<PushToTalkStatus, String> {
+    let mut current = settings::load_settings(&app);
+    current.push_to_talk.enabled = false;
+    settings::save_settings(&app, &current)?;
+    reload(&app);
+    Ok(status(&app))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::parse;
+
+    #[test]
+    fn parses_configurable_shortcuts() {
+        assert!(parse("CmdOrCtrl+Shift+Space").is_ok());
+        assert!(parse(" F13 ").is_ok());
+        assert!(parse("Shift+Nope").is_err());
+    }
+}
@@ -0,0 +1,104 @@
+use std::sync::atomic::{AtomicBool, Ordering};
+
+use serde::{Deserialize, Serialize};
+use tauri::{
+    menu::{Menu, MenuItem, PredefinedMenuItem},
+    tray::{MouseButton, MouseButtonState, TrayIconBuilder, TrayIconEvent},
+    Manager,
+};
+use tauri_specta::Event;
+
+use crate::log_line;
+
+const TRAY_ID: &str = "main";
+const MENU_TOGGLE: &str = "quick_capture_toggle";
+const MENU_SHOW: &str = "quick_capture_show";
+const MENU_QUIT: &str = "quick_capture_quit";
+
+#[derive(Serialize, Deserialize, Clone, Copy, Debug, PartialEq, Eq, specta::Type)]
+#[serde(rename_all = "kebab-case")]
+pub enum QuickCapturePhase {
+    Start,
+    Stop,
+}
+
+/// Sent to the main webview, which owns the microphone, to record one
+/// utterance and hand it to the agent as a direct command.
+#[derive(Serialize, Deserialize, Clone, specta::Type, tauri_specta::Event)]
+#[serde(rename_all = "camelCase")]
+pub struct QuickCapture {
+    pub phase: QuickCapturePhase,
+}
+
+#[derive(Default)]
+pub struct QuickCaptureState {
+    active: AtomicBool,
+}
+
+fn set_capturing(app: &tauri::AppHandle, capturing: bool) {
+    let state = app.state::<QuickCaptureState>();
+    if state.active.swap(capturing, Ordering::SeqCst) == capturing {
+        return;
+    }
+    let phase = if capturing {
+        QuickCapturePhase::Start
+    } else {
+        QuickCapturePhase::Stop
+    };
+    log_line(&format!("[quick-capture] {:?}", phase));
+    if let Some(tray) = app.tray_by_id(TRAY_ID) {
+        let tooltip = if capturing {
+            "HeyJamie — listening…"
+        } else {
+            "HeyJamie — hold to talk"
+        };
+        let _ = tray.set_tooltip(Some(tooltip));
+    }
+    if let Err(err) = (QuickCapture { phase }).emit_to(app, "main") {
+        log_line(&format!("[quick-capture] failed to notify main window: {}", err));
+    }
+}
+
+/// Tray icon for background use: press and hold the icon to talk, release
+/// to send. Menus don't report held keys, so the menu entry toggles capture
+/// instead (click once to start, again to send).
+pub fn install_tray(app: &tauri::AppHandle) -> tauri::Result<()> {
+    let toggle = MenuItem::with_id(app, MENU_TOGGLE, "Quick Capture", true, None::<&str>)?;
+    let show = MenuItem::with_id(app, MENU_SHOW, "Show HeyJamie", true, None::<&str>)?;
+    let quit = MenuItem::with_id(app, MENU_QUIT, "Quit", true, None::<&str>)?;
+    let menu = Menu::with_items(app, &[&toggle, &show, &PredefinedMenuItem::separator(app)?, &quit])?;
+
+    let mut builder = TrayIconBuilder::with_id(TRAY_ID)
+        .tooltip("HeyJamie — hold to talk")
+        .menu(&menu)
+        .show_menu_on_left_click(false)
+        .on_tray_icon_event(|tray, event| {
+            if let TrayIconEvent::Click {
+                button: MouseButton::Left,
+                button_state,
+                ..
+            } = event
+            {
+                set_capturing(tray.app_handle(), button_state == MouseButtonState::Down);
+            }
+        })
+        .on_menu_event(|app, event| match event.id().as_ref() {
+            MENU_TOGGLE => {
+                let capturing = app.state::<QuickCaptureState>().active.load(Ordering::SeqCst);
+                set_capturing(app, !capturing);
+            }
+            MENU_SHOW => {
+                if let Some(window) = app.get_webview_window("main") {
+                    let _ = window.show();
+                    let _ = window.set_focus();
+                }
+            }
+            MENU_QUIT => app.exit(0),
+            _ => {}
+        });
+    if let Some(icon) = app.default_window_icon() {
+        builder = builder.icon(icon.clone());
+    }
+    builder.build(app)?;
+    Ok(())
+}
@@ -0,0 +1,405 @@
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::mpsc::{self, Receiver, RecvTimeoutError, Sender};
+use std::sync::{Arc, Mutex};
+use std::thread::{self, JoinHandle};
+use std::time::{Duration, Instant};
+
+use cpal::traits::{DeviceTrait, StreamTrait};
+use serde::{Deserialize, Serialize};
+use tauri::Manager;
+use tauri_specta::Event;
+
+use crate::loopback::{self, CaptureSource};
+use crate::mic_level::{LevelMeter, MicLevel};
+use crate::transcription_queue::Lane;
+use crate::{audio_devices, focus_gate, log_line, power, preprocess, session_audio, settings, system_load, wav};
+
+/// Rate whisper expects; segments are downsampled to it.
+const OUTPUT_SAMPLE_RATE: u32 = 16_000;
+const SEGMENT_SECONDS: u32 = 8;
+/// Shorter leftovers at stop are dropped.
+const MIN_SEGMENT_SECONDS: u32 = 2;
+const LEVEL_INTERVAL: Duration = Duration::from_millis(100);
+
+/// Emitted to the main window for every segment of a native recording,
+/// in capture order.
+#[derive(Serialize, Deserialize, Clone, Debug, specta::Type, tauri_specta::Event)]
+#[serde(rename_all = "camelCase")]
+pub struct RecordedTranscript {
+    pub index: u32,
+    pub text: String,
+    pub error: Option<String>,
+}
+
+/// Input RMS for the level meter, sent about ten times a second.
+#[derive(Serialize, Deserialize, Clone, Debug, specta::Type, tauri_specta::Event)]
+pub struct RecordingLevel(pub f32);
+
+#[derive(Serialize, Deserialize, Clone, Debug, specta::Type)]
+#[serde(rename_all = "camelCase")]
+pub struct RecordingInfo {
+    /// Device names, joined with " + " when system audio is mixed in.
+    pub device: String,
+    pub sample_rate: u32,
+    pub source: CaptureSource,
+}
+
+/// Audio from one of the open streams, in its own sample rate.
+type Chunk = (usize, Vec<f32>);
+
+struct ActiveRecording {
+    stop: Arc<AtomicBool>,
+    /// Returns the transcriber thread, which outlives the capture.
+    capture: JoinHandle<Option<JoinHandle<()>>>,
+}
+
+/// The microphone capture started by `start_recording`, if any.
+#[derive(Default)]
+pub struct RecordingState {
+    active: Mutex<Option<ActiveRecording>>,
+    /// Transcriber of the last stopped recording, still working through
+    /// its backlog.
+    draining: Mutex<Option<JoinHandle<()>>>,
+}
+
+/// Whether native capture is running.
+pub fn is_recording(app: &tauri::AppHandle) -> bool {
+    app.state::<RecordingState>().active.lock().unwrap().is_some()
+}
+
+/// Average `samples` down to `to` Hz, the same way the webview capture did.
+pub fn downsample(samples: &[f32], from: u32, to: u32) -> Vec<f32> {
+    if from == to || samples.is_empty() {
+        return samples.to_vec();
+    }
+    let ratio = from as f64 / to as f64;
+    let length = (samples.len() as f64 / ratio).round() as usize;
+    let mut output = Vec::with_capacity(length);
+    let mut start = 0;
+    for index in 0..length {
+        let end = (((index + 1) as f64 * ratio).round() as usize).min(samples.len());
+        let window = &samples[start.min(end)..end];
+        output.push(if window.is_empty() {
+            0.0
+        } else {
+            window.iter().sum::<f32>() / window.len() as f32
+        });
+        start = end;
+    }
+    output
+}
+
+fn rms(samples: &[f32]) -> f32 {
+    if samples.is_empty() {
+        return 0.0;
+    }
+    (samples.iter().map(|sample| sample * sample).sum::<f32>() / samples.len() as f32).sqrt()
+}
+
+/// Longer segments while on battery or under load, like the webview capture.
+fn segment_seconds(app: &tauri::AppHandle) -> u32 {
+    [
+        power::current(app).segment_seconds,
+        system_load::current(app).and_then(|pressure| pressure.segment_seconds),
+    ]
+    .into_iter()
+    .flatten()
+    .max()
+    .unwrap_or(SEGMENT_SECONDS)
+}
+
+fn build_stream<T>(
+    device: &cpal::Device,
+    config: &cpal::StreamConfig,
+    source: usize,
+    chunks: Sender<Chunk>,
+) -> Result<cpal::Stream, cpal::BuildStreamError>
+where
+    T: cpal::SizedSample,
+    f32: cpal::FromSample<T>,
+{
+    let channels = config.channels.max(1) as usize;
+    device.build_input_stream(
+        config,
+        move |data: &[T], _: &cpal::InputCallbackInfo| {
+            let mono = data
+                .chunks(channels)
+                .map(|frame| frame.iter().map(|sample| sample.to_sample::<f32>()).sum::<f32>() / channels as f32)
+                .collect();
+            let _ = chunks.send((source, mono));
+        },
+        |err| log_line(&format!("[recording] stream error: {}", err)),
+        None,
+    )
+}
+
+/// Start a stream on `device`. `label` names it in errors ("microphone",
+/// "system audio").
+fn open_stream(
+    device: &cpal::Device,
+    supported: cpal::SupportedStreamConfig,
+    label: &str,
+    source: usize,
+    chunks: Sender<Chunk>,
+) -> Result<(cpal::Stream, u32), String> {
+    let config: cpal::StreamConfig = supported.clone().into();
+    let stream = match supported.sample_format() {
+        cpal::SampleFormat::F32 => build_stream::<f32>(device, &config, source, chunks),
+        cpal::SampleFormat::I16 => build_stream::<i16>(device, &config, source, chunks),
+        cpal::SampleFormat::U16 => build_stream::<u16>(device, &config, source, chunks),
+        cpal::SampleFormat::I32 => build_stream::<i32>(device, &config, source, chunks),
+        format => return Err(format!("unsupported {} sample format {}", label, format)),
+    }
+    .map_err(|err| format!("failed to open the {}: {}", label, err))?;
+    stream
+        .play()
+        .map_err(|err| format!("failed to start the {}: {}", label, err))?;
+    Ok((stream, config.sample_rate.0))
+}
+
+/// An open input: its stream, sample rate and device name.
+type OpenStream = (cpal::Stream, u32, String);
+
+/// Open the microphone (the chosen one, else the default) and/or system
+/// audio, indexed like the chunks they send.
+fn open_streams(
+    app: &tauri::AppHandle,
+    source: CaptureSource,
+    chunks: Sender<Chunk>,
+) -> Result<(Vec<OpenStream>, RecordingInfo), String> {
+    let mut devices = Vec::new();
+    if source.microphone() {
+        let device = audio_devices::input_device(app)?;
+        let supported = device
+            .default_input_config()
+            .map_err(|err| format!("failed to read the microphone format: {}", err))?;
+        devices.push((device, supported, "microphone"));
+    }
+    if source.system() {
+        let (device, supported) = loopback::system_device()?;
+        devices.push((device, supported, "system audio"));
+    }
+    let mut streams = Vec::new();
+    for (index, (device, supported, label)) in devices.into_iter().enumerate() {
+        let (stream, rate) = open_stream(&device, supported, label, index, chunks.clone())?;
+        streams.push((stream, rate, device.name().unwrap_or_else(|_| label.to_string())));
+    }
+    let info = RecordingInfo {
+        device: streams.iter().map(|(_, _, name)| name.as_str()).collect::<Vec<_>>().join(" + "),
+        sample_rate: streams[0].1,
+        source,
+    };
+    Ok((streams, info))
+}
+
+/// Take the first `len` samples of every source, summed. Shorter sources
+/// count as silence for the rest, so a loopback stream that goes quiet
+/// between sounds doesn't drift out of step with the microphone.
+fn mix(buffers: &mut [Vec<f32>], len: usize) -> Vec<f32> {
+    let mut mixed = vec![0.0; len];
+    for buffer in buffers.iter_mut() {
+        let take = len.min(buffer.len());
+        for (out, sample) in mixed.iter_mut().zip(buffer.drain(..take)) {
+            *out += sample;
+        }
+    }
+    if buffers.len() > 1 {
+        mixed.iter_mut().for_each(|sample| *sample = sample.clamp(-1.0, 1.0));
+    }
+    mixed
+}
+
+/// Owns the cpal stream, which is not `Send` on every platform, and cuts
+/// the incoming audio into segments until `stop` is set.
+fn capture(
+    app: tauri::AppHandle,
+    stop: Arc<AtomicBool>,
+    started: mpsc::SyncSender<Result<RecordingInfo, String>>,
+) -> Option<JoinHandle<()>> {
+    let (chunk_tx, chunks) = mpsc::channel();
+    let transcription = settings::load_settings(&app).transcription;
+    let (streams, info) = match open_streams(&app, transcription.capture_source, chunk_tx) {
+        Ok(opened) => opened,
+        Err(err) => {
+            let _ = started.send(Err(err));
+            return None;
+        }
+    };
+    let _ = started.send(Ok(info));
+    let rates: Vec<u32> = streams.iter().map(|(_, rate, _)| *rate).collect();
+    // The microphone opens first; it's silenced while the focus gate is shut.
+    let mic_index = transcription.capture_source.microphone().then_some(0);
+    // Each device is cleaned up with its own filters before mixing.
+    let filters: Vec<_> = streams
+        .iter()
+        .map(|(_, _, name)| preprocess::filters_for(&transcription.preprocessing, Some(name)).clone())
+        .collect();
+    let cut = |buffers: &mut [Vec<f32>], len: usize| {
+        for (buffer, filters) in buffers.iter_mut().zip(&filters) {
+            let take = len.min(buffer.len());
+            preprocess::process(filters, OUTPUT_SAMPLE_RATE, &mut buffer[..take]);
+        }
+        mix(buffers, len)
+    };
+
+    let (segment_tx, segments) = mpsc::channel();
+    let transcriber = app.clone();
+    let transcriber = thread::spawn(move || transcribe_segments(transcriber, segments));
+
+    // Each source is downsampled as it arrives so they can be mixed.
+    let mut buffers: Vec<Vec<f32>> = vec![Vec::new(); rates.len()];
+    let buffered = |buffers: &[Vec<f32>]| buffers.iter().map(Vec::len).max().unwrap_or(0);
+    let mut level_at = Instant::now();
+    let mut mic_meter = LevelMeter::new(level_at);
+    while !stop.load(Ordering::SeqCst) {
+        let (index, mut chunk) = match chunks.recv_timeout(LEVEL_INTERVAL) {
+            Ok(chunk) => chunk,
+            Err(RecvTimeoutError::Timeout) => continue,
+            Err(RecvTimeoutError::Disconnected) => break,
+        };
+        // Metered before the focus gate so a gated mic doesn't read as dead.
+        if Some(index) == mic_index {
+            mic_meter.push(&chunk);
+        }
+        if level_at.elapsed() >= LEVEL_INTERVAL {
+            level_at = Instant::now();
+            let _ = RecordingLevel(rms(&chunk)).emit_to(&app, "main");
+            if mic_index.is_some() {
+                let _ = mic_meter.take(level_at).emit_to(&app, "main");
+            }
+        }
+        if Some(index) == mic_index && !focus_gate::is_open(&app) {
+            chunk.fill(0.0);
+        }
+        buffers[index].extend(downsample(&chunk, rates[index], OUTPUT_SAMPLE_RATE));
+        let segment_len = (OUTPUT_SAMPLE_RATE * segment_seconds(&app)) as usize;
+        if buffered(&buffers) >= segment_len {
+            // The transcriber drops segments without speech.
+            let _ = segment_tx.send(cut(&mut buffers, segment_len));
+        }
+    }
+    drop(streams);
+    for (index, chunk) in chunks.try_iter() {
+        buffers[index].extend(downsample(&chunk, rates[index], OUTPUT_SAMPLE_RATE));
+    }
+    let remaining = buffered(&buffers);
+    if remaining >= (OUTPUT_SAMPLE_RATE * MIN_SEGMENT_SECONDS) as usize {
+        let _ = segment_tx.send(cut(&mut buffers, remaining));
+    }
+    let _ = RecordingLevel(0.0).emit_to(&app, "main");
+    let _ = MicLevel::default().emit_to(&app, "main");
+    Some(transcriber)
+}
+
+/// Transcribe segments one at a time so transcripts arrive in order. Runs
+/// until the capture thread drops its sender and the backlog is done.
+fn transcribe_segments(app: tauri::AppHandle, segments: Receiver<Vec<f32>>) {
+    for (index, samples) in segments.iter().enumerate() {
+        let wav_bytes = wav::encode_pcm16_mono(OUTPUT_SAMPLE_RATE, &samples);
+        let sink = crate::partial_sink(&app, format!("recording-{}", index));
+        let result =
+            tauri::async_runtime::block_on(crate::transcribe_wav(&app, wav_bytes.clone(), None, Some(sink), Lane::Live));
+        if result.as_ref().is_ok_and(|text| !text.trim().is_empty()) {
+            session_audio::keep_segment(&app, &wav_bytes);
+        }
+        let transcript = match result {
+            Ok(text) => RecordedTranscript {
+                index: index as u32,
+                text,
+                error: None,
+            },
+            Err(err) => {
+                log_line(&format!("[recording] segment {} failed: {}", index, err));
+                RecordedTranscript {
+                    index: index as u32,
+                    text: String::new(),
+                    error: Some(err),
+                }
+            }
+        };
+        if let Err(err) = transcript.emit_to(&app, "main") {
+            log_line(&format!("[recording] failed to notify main window: {}", err));
+        }
+    }
+}
+
+/// Capture the default microphone, system audio or both natively, per
+/// `transcription.captureSource`. Segments are transcribed in the
+/// background and arrive as `recorded-transcript` events.
+#[tauri::command]
+#[specta::specta]
+pub fn start_recording(app: tauri::AppHandle) -> Result<RecordingInfo, String> {
+    let state = app.state::<RecordingState>();
+    let mut active = state.active.lock().unwrap();
+    if active.is_some() {
+        return Err("already recording".to_string());
+    }
+    let stop = Arc::new(AtomicBool::new(false));
+    let (started_tx, started) = mpsc::sync_channel(1);
+    let capture = {
+        let (app, stop) = (app.clone(), stop.clone());
+        thread::spawn(move || capture(app, stop, started_tx))
+    };
+    let info = started
+        .recv()
+        .map_err(|_| "the recording thread exited unexpectedly".to_string())??;
+    log_line(&format!(
+        "[recording] started on {} at {} Hz ({:?})",
+        info.device, info.sample_rate, info.source
+    ));
+    *active = Some(ActiveRecording { stop, capture });
+    Ok(info)
+}
+
+/// Stop capturing. The last partial segment is still transcribed and
+/// delivered as an event after this returns.
+#[tauri::command]
+#[specta::specta]
+pub fn stop_recording(app: tauri::AppHandle) -> Result<(), String> {
+    let recording = app.state::<RecordingState>().active.lock().unwrap().take();
+    let Some(recording) = recording else {
+        return Ok(());
+    };
+    recording.stop.store(true, Ordering::SeqCst);
+    let transcriber = recording
+        .capture
+        .join()
+        .map_err(|_| "the recording thread panicked".to_string())?;
+    *app.state::<RecordingState>().draining.lock().unwrap() = transcriber;
+    log_line("[recording] stopped");
+    Ok(())
+}
+
+/// Stop capturing and wait until every segment has been transcribed and
+/// delivered. Used when quitting.
+pub fn finish(app: &tauri::AppHandle) {
+    if let Err(err) = stop_recording(app.clone()) {
+        log_line(&format!("[recording] {}", err));
+    }
+    let transcriber = app.state::<RecordingState>().draining.lock().unwrap().take();
+    if let Some(transcriber) = transcriber {
+        let _ = transcriber.join();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{downsample, mix};
+
+    #[test]
+    fn downsample_averages_windows() {
+        let samples = [0.0, 0.3, 0.6, 0.9, 0.2, 0.4];
+        let output = downsample(&samples, 48_000, 16_000);
+        assert_eq!(output.len(), 2);
+        assert!((output[0] - 0.3).abs() < 1e-6);
+        assert!((output[1] - 0.5).abs() < 1e-6);
+        assert_eq!(downsample(&samples, 16_000, 16_000), samples.to_vec());
+    }
+
+    #[test]
+    fn mix_sums_sources_and_pads_the_shorter_one() {
+        let mut buffers = vec![vec![0.5, 0.5, 0.5, 0.25], vec![0.75, -0.25]];
+        assert_eq!(mix(&mut buffers, 3), vec![1.0, 0.25, 0.5]);
+        assert_eq!(buffers, vec![vec![0.25], Vec::<f32>::new()]);
+    }
+}
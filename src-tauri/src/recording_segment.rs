@@ -0,0 +1,135 @@
+use std::sync::Mutex;
+
+use serde::{Deserialize, Serialize};
+use tauri::Manager;
+use tauri_specta::Event;
+
+use crate::{log_line, now_millis, trace};
+
+#[derive(Serialize, Deserialize, Clone, Copy, PartialEq, Eq, Debug, specta::Type)]
+#[serde(rename_all = "camelCase")]
+pub enum HeldKind {
+    /// A page Jamie would have opened in the browser.
+    Url,
+    /// A window Jamie would have shown (settings, main, canvas).
+    Window,
+    /// An agent run that drives the visible browser or canvas.
+    Agent,
+    /// A spoken direct command, kept to run after the segment.
+    Command,
+}
+
+/// Something Jamie held back while tape was rolling.
+#[derive(Serialize, Deserialize, Clone, Debug, specta::Type)]
+#[serde(rename_all = "camelCase")]
+pub struct HeldResponse {
+    pub kind: HeldKind,
+    pub detail: String,
+    pub held_at: u64,
+    /// Trace of the interaction that was held, to match it up in the log.
+    pub trace_id: Option<String>,
+}
+
+/// Emitted as `recording-segment` whenever a segment starts or ends or
+/// something is held.
+#[derive(Serialize, Deserialize, Clone, Debug, Default, specta::Type, tauri_specta::Event)]
+#[serde(rename_all = "camelCase")]
+pub struct RecordingSegment {
+    pub active: bool,
+    pub held: Vec<HeldResponse>,
+}
+
+/// While a recording segment is active Jamie stays silent and invisible:
+/// window, browser and visible agent actions are held for later review.
+#[derive(Default)]
+pub struct RecordingSegmentState {
+    inner: Mutex<RecordingSegment>,
+}
+
+impl RecordingSegmentState {
+    pub fn is_active(&self) -> bool {
+        self.inner.lock().unwrap().active
+    }
+}
+
+fn notify(app: &tauri::AppHandle, status: RecordingSegment) {
+    if let Err(err) = status.emit_to(app, "main") {
+        log_line(&format!("[recording-segment] failed to notify main window: {}", err));
+    }
+}
+
+/// Hold `detail` for review if a segment is active. Returns whether the
+/// caller must skip the action.
+pub fn hold(app: &tauri::AppHandle, kind: HeldKind, detail: &str) -> bool {
+    let status = {
+        let state = app.state::<RecordingSegmentState>();
+        let mut inner = state.inner.lock().unwrap();
+        if !inner.active {
+            return false;
+        }
+        inner.held.push(HeldResponse {
+            kind,
+            detail: detail.to_string(),
+            held_at: now_millis(),
+            trace_id: trace::current(),
+        });
+        inner.clone()
+    };
+    log_line(&format!("[recording-segment] held {:?}: {}", kind, detail));
+    notify(app, status);
+    true
+}
+
+pub fn set_active(app: &tauri::AppHandle, active: bool) -> RecordingSegment {
+    let status = {
+        let state = app.state::<RecordingSegmentState>();
+        let mut inner = state.inner.lock().unwrap();
+        inner.active = active;
+        inner.clone()
+    };
+    log_line(&format!(
+        "[recording-segment] {} ({} held)",
+        if active { "rolling" } else { "clear" },
+        status.held.len()
+    ));
+    notify(app, status.clone());
+    status
+}
+
+pub fn toggle(app: &tauri::AppHandle) -> RecordingSegment {
+    let active = app.state::<RecordingSegmentState>().is_active();
+    set_active(app, !active)
+}
+
+#[tauri::command]
+#[specta::specta]
+pub fn set_recording_segment(app: tauri::AppHandle, active: bool) -> RecordingSegment {
+    set_active(&app, active)
+}
+
+#[tauri::command]
+#[specta::specta]
+pub fn get_recording_segment(state: tauri::State<'_, RecordingSegmentState>) -> RecordingSegment {
+    state.inner.lock().unwrap().clone()
+}
+
+/// Hold a frontend-side response (e.g. a direct command) for later review.
+/// Returns false, and holds nothing, when no segment is active.
+#[tauri::command]
+#[specta::specta]
+pub fn hold_for_review(app: tauri::AppHandle, kind: HeldKind, detail: String) -> bool {
+    hold(&app, kind, &detail)
+}
+
+/// Hand back everything held so far and clear the list.
+#[tauri::command]
+#[specta::specta]
+pub fn take_held_responses(app: tauri::AppHandle) -> Vec<HeldResponse> {
+    let (held, status) = {
+        let state = app.state::<RecordingSegmentState>();
+        let mut inner = state.inner.lock().unwrap();
+        (std::mem::take(&mut inner.held), inner.clone())
+    };
+    notify(&app, status);
+    held
+}
@@ -0,0 +1,180 @@
+//! Optional masking of sensitive text in transcripts (`redaction` in
+//! settings). Applied by `clean_transcript_fragment`, so masked text is
+//! what gets logged, stored, shown and sent to the agent.
+
+use std::borrow::Cow;
+use std::sync::{Arc, OnceLock, RwLock};
+
+use regex::{Regex, RegexBuilder};
+
+use crate::log_line;
+use crate::settings::{self, RedactionSettings};
+
+/// Common English profanity, matched as whole words.
+const PROFANITY: &[&str] = &[
+    "asshole",
+    "assholes",
+    "bastard",
+    "bastards",
+    "bitch",
+    "bitches",
+    "bullshit",
+    "cunt",
+    "cunts",
+    "dickhead",
+    "fuck",
+    "fucked",
+    "fucker",
+    "fuckers",
+    "fucking",
+    "fucks",
+    "motherfucker",
+    "motherfuckers",
+    "motherfucking",
+    "shit",
+    "shits",
+    "shitty",
+    "wanker",
+];
+
+const EMAIL: &str = r"\b[A-Za-z0-9._%+-]+@[A-Za-z0-9.-]+\.[A-Za-z]{2,}\b";
+/// 13 to 19 digits, optionally grouped by spaces or dashes.
+const CARD_NUMBER: &str = r"\b\d(?:[ -]?\d){12,18}\b";
+/// North American and international numbers, with or without separators.
+const PHONE_NUMBER: &str = r"(?:\+\d{1,3}[\s.-]?)?(?:\(\d{2,4}\)|\b\d{2,4})[\s.-]?\d{3,4}[\s.-]?\d{3,4}\b";
+
+/// The rules in effect, ready to apply.
+pub struct Redactor {
+    patterns: Vec<Regex>,
+    mask: String,
+}
+
+impl Redactor {
+    pub fn apply<'a>(&self, text: &'a str) -> Cow<'a, str> {
+        let mut text = Cow::Borrowed(text);
+        for pattern in &self.patterns {
+            let masked = match pattern.replace_all(&text, regex::NoExpand(&self.mask)) {
+                Cow::Owned(masked) => Some(masked),
+                Cow::Borrowed(_) => None,
+            };
+            if let Some(masked) = masked {
+                text = Cow::Owned(masked);
+            }
+        }
+        text
+    }
+}
+
+fn case_insensitive(pattern: &str) -> Result<Regex, String> {
+    RegexBuilder::new(pattern)
+        .case_insensitive(true)
+        .build()
+        .map_err(|err| format!("invalid redaction pattern `{}`: {}", pattern, err))
+}
+
+/// `\b(?:a|b|c)\b` for the non-empty `words`.
+fn word_list(words: &[&str]) -> Option<String> {
+    let words: Vec<String> = words
+        .iter()
+        .map(|word| word.trim())
+        .filter(|word| !word.is_empty())
+        .map(regex::escape)
+        .collect();
+    (!words.is_empty()).then(|| format!(r"\b(?:{})\b", words.join("|")))
+}
+
+/// Compile `settings`; `None` when redaction is off. Card numbers go before
+/// phone numbers so a card isn't half-masked as a phone number.
+pub fn compile(settings: &RedactionSettings) -> Result<Option<Redactor>, String> {
+    if !settings.enabled {
+        return Ok(None);
+    }
+    let mut sources: Vec<String> = Vec::new();
+    if settings.card_numbers {
+        sources.push(CARD_NUMBER.to_string());
+    }
+    if settings.emails {
+        sources.push(EMAIL.to_string());
+    }
+    if settings.phone_numbers {
+        sources.push(PHONE_NUMBER.to_string());
+    }
+    if settings.profanity {
+        sources.extend(word_list(PROFANITY));
+    }
+    let words: Vec<&str> = settings.words.iter().map(String::as_str).collect();
+    sources.extend(word_list(&words));
+    sources.extend(settings.patterns.iter().filter(|pattern| !pattern.trim().is_empty()).cloned());
+    let patterns = sources.iter().map(|source| case_insensitive(source)).collect::<Result<Vec<_>, _>>()?;
+    let mask = Some(settings.mask.trim()).filter(|mask| !mask.is_empty()).unwrap_or("[redacted]");
+    Ok(Some(Redactor {
+        patterns,
+        mask: mask.to_string(),
+    }))
+}
+
+fn active_slot() -> &'static RwLock<Option<Arc<Redactor>>> {
+    static ACTIVE: OnceLock<RwLock<Option<Arc<Redactor>>>> = OnceLock::new();
+    ACTIVE.get_or_init(|| RwLock::new(None))
+}
+
+/// Mask `text` with the rules in effect; unchanged until `reload` runs or
+/// when redaction is off.
+pub fn apply(text: &str) -> Cow<'_, str> {
+    match active_slot().read().unwrap().as_ref() {
+        Some(redactor) => redactor.apply(text),
+        None => Cow::Borrowed(text),
+    }
+}
+
+/// Recompile from settings. Called at startup and whenever settings are
+/// saved.
+pub fn reload(app: &tauri::AppHandle) {
+    let redactor = match compile(&settings::load_settings(app).redaction) {
+        Ok(redactor) => redactor.map(Arc::new),
+        Err(err) => {
+            log_line(&format!("[redaction] {}", err));
+            None
+        }
+    };
+    *active_slot().write().unwrap() = redactor;
+}
+
+#[cfg(test)]
+mod tests {
+    use super::compile;
+    use crate::settings::RedactionSettings;
+
+    #[test]
+    fn masks_enabled_categories_and_custom_rules() {
+        let settings = RedactionSettings {
+            enabled: true,
+            words: vec!["Project Falcon".to_string()],
+            patterns: vec![r"\bACME-\d+\b".to_string()],
+            ..RedactionSettings::default()
+        };
+        let redactor = compile(&settings).unwrap().unwrap();
+        let masked = redactor.apply(
+            "Email jane.doe@example.com or call (415) 555-0132 about Project Falcon, \
+             card 4111 1111 1111 1111, ticket acme-42. What the fuck.",
+        );
+        assert_eq!(
+            masked,
+            "Email [redacted] or call [redacted] about [redacted], \
+             card [redacted], ticket [redacted]. What the [redacted]."
+        );
+        assert_eq!(redactor.apply("We shipped 3 releases in 2024."), "We shipped 3 releases in 2024.");
+
+        let off = RedactionSettings {
+            profanity: false,
+            ..settings.clone()
+        };
+        assert!(compile(&off).unwrap().unwrap().apply("oh shit").contains("shit"));
+        assert!(compile(&RedactionSettings::default()).unwrap().is_none());
+        assert!(compile(&RedactionSettings {
+            patterns: vec!["(".to_string()],
+            ..settings
+        })
+        .is_err());
+    }
+}
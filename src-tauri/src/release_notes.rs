@@ -0,0 +1,204 @@
+use std::{
+    fs,
+    path::{Path, PathBuf},
+};
+
+use serde::{Deserialize, Serialize};
+use tauri::Manager;
+
+use crate::{log_line, now_millis};
+
+/// Release metadata compiled into the binary so notes are available offline
+/// and always match the running build.
+const BUNDLED_RELEASE_NOTES: &str = include_str!("../release-notes.json");
+
+#[derive(Serialize, Deserialize, Clone, specta::Type)]
+#[serde(rename_all = "camelCase")]
+pub struct ReleaseEntry {
+    pub version: String,
+    pub date: String,
+    pub title: String,
+    #[serde(default)]
+    pub highlights: Vec<String>,
+    #[serde(default)]
+    pub config_changes: Vec<String>,
+}
+
+/// One config rewrite performed on this machine, appended to
+/// `migrations.json` next to the migrated file.
+#[derive(Serialize, Deserialize, Clone, specta::Type)]
+#[serde(rename_all = "camelCase")]
+pub struct AppliedMigration {
+    pub id: String,
+    pub description: String,
+    #[serde(default)]
+    pub file: String,
+    #[serde(default)]
+    pub app_version: String,
+    #[serde(default)]
+    pub applied_at: u128,
+}
+
+impl AppliedMigration {
+    pub fn new(id: &str, description: &str) -> Self {
+        Self {
+            id: id.to_string(),
+            description: description.to_string(),
+            file: String::new(),
+            app_version: env!("CARGO_PKG_VERSION").to_string(),
+            applied_at: 0,
+        }
+    }
+}
+
+#[derive(Serialize, Deserialize, Default)]
+#[serde(rename_all = "camelCase", default)]
+struct ReleaseNotesState {
+    last_seen_version: Option<String>,
+    last_seen_at: Option<u128>,
+}
+
+#[derive(Serialize, specta::Type)]
+#[serde(rename_all = "camelCase")]
+pub struct ReleaseNotesResponse {
+    current_version: String,
+    since_version: Option<String>,
+    releases: Vec<ReleaseEntry>,
+    migrations: Vec<AppliedMigration>,
+}
+
+fn migrations_log_path(config_file: &Path) -> PathBuf {
+    config_file.with_file_name("migrations.json")
+}
+
+fn read_migrations(path: &Path) -> Vec<AppliedMigration> {
+    fs::read_to_string(path)
+        .ok()
+        .and_then(|content| serde_json::from_str(&content).ok())
+        .unwrap_or_default()
+}
+
+/// Append migrations applied to `config_file` to the migrations log.
+pub fn record_migrations(config_file: &Path, migrations: Vec<AppliedMigration>) {
+    let log_path = migrations_log_path(config_file);
+    let mut existing = read_migrations(&log_path);
+    let now = now_millis() as u128;
+    let file_name = config_file
+        .file_name()
+        .map(|name| name.to_string_lossy().to_string())
+        .unwrap_or_default();
+    for mut migration in migrations {
+        migration.file = file_name.clone();
+        migration.applied_at = now;
+        existing.push(migration);
+    }
+    match serde_json::to_string_pretty(&existing) {
+        Ok(content) => {
+            if let Err(err) = fs::write(&log_path, content.as_bytes()) {
+                log_line(&format!("[migrations] failed to write log: {}", err));
+            }
+        }
+        Err(err) => log_line(&format!("[migrations] failed to format log: {}", err)),
+    }
+}
+
+/// Parse `major.minor.patch`, ignoring any pre-release/build suffix.
+fn parse_version(version: &str) -> (u64, u64, u64) {
+    let core = version
+        .trim()
+        .trim_start_matches('v')
+        .split(['-', '+'])
+        .next()
+        .unwrap_or("");
+    let mut parts = core.split('.').map(|p| p.parse::<u64>().unwrap_or(0));
+    (
+        parts.next().unwrap_or(0),
+        parts.next().unwrap_or(0),
+        parts.next().unwrap_or(0),
+    )
+}
+
+fn config_dir(app: &tauri::AppHandle) -> Result<PathBuf, String> {
+    let dir = app
+        .path()
+        .app_config_dir()
+        .map_err(|err| format!("failed to resolve app config dir: {}", err))?;
+    fs::create_dir_all(&dir).map_err(|err| format!("failed to create config dir: {}", err))?;
+    Ok(dir)
+}
+
+fn state_path(app: &tauri::AppHandle) -> Result<PathBuf, String> {
+    Ok(config_dir(app)?.join("release-notes-state.json"))
+}
+
+fn load_state(app: &tauri::AppHandle) -> ReleaseNotesState {
+    state_path(app)
+        .ok()
+        .and_then(|path| fs::read_to_string(path).ok())
+        .and_then(|content| serde_json::from_str(&content).ok())
+        .unwrap_or_default()
+}
+
+/// Release notes newer than `since_version` (or the last version the user
+/// acknowledged) plus every config migration applied since then.
+#[tauri::command]
+#[specta::specta]
+pub fn get_release_notes(
+    app: tauri::AppHandle,
+    since_version: Option<String>,
+) -> Result<ReleaseNotesResponse, String> {
+    let explicit = since_version.filter(|v| !v.trim().is_empty());
+    let state = load_state(&app);
+    // Without an explicit version, migrations are filtered by when the user
+    // last acknowledged the notes: rewrites can happen without a version bump.
+    let seen_at = if explicit.is_none() { state.last_seen_at } else { None };
+    let since_version = explicit.or(state.last_seen_version);
+    let since = since_version.as_deref().map(parse_version);
+
+    let mut releases: Vec<ReleaseEntry> = serde_json::from_str(BUNDLED_RELEASE_NOTES)
+        .map_err(|err| format!("invalid bundled release notes: {}", err))?;
+    releases.retain(|entry| since.is_none_or(|since| parse_version(&entry.version) > since));
+    releases.sort_by_key(|entry| std::cmp::Reverse(parse_version(&entry.version)));
+
+    let mut migrations = read_migrations(&migrations_log_path(&config_dir(&app)?.join("mcp.json")));
+    migrations.retain(|m| match (seen_at, since) {
+        (Some(seen_at), _) => m.applied_at > seen_at,
+        (None, Some(since)) => parse_version(&m.app_version) > since,
+        (None, None) => true,
+    });
+
+    Ok(ReleaseNotesResponse {
+        current_version: app.package_info().version.to_string(),
+        since_version,
+        releases,
+        migrations,
+    })
+}
+
+/// Record that the user has seen the notes for the running version.
+#[tauri::command]
+#[specta::specta]
+pub fn mark_release_notes_seen(app: tauri::AppHandle) -> Result<(), String> {
+    let state = ReleaseNotesState {
+        last_seen_version: Some(app.package_info().version.to_string()),
+        last_seen_at: Some(now_millis() as u128),
+    };
+    let content = serde_json::to_string_pretty(&state)
+        .map_err(|err| format!("failed to format release notes state: {}", err))?;
+    fs::write(state_path(&app)?, content.as_bytes())
+        .map_err(|err| format!("failed to write release notes state: {}", err))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::parse_version;
+
+    #[test]
+    fn parses_versions_with_prefixes_and_suffixes() {
+        assert_eq!(parse_version("0.1.0"), (0, 1, 0));
+        assert_eq!(parse_version("v1.2.3"), (1, 2, 3));
+        assert_eq!(parse_version("2.0.0-beta.1"), (2, 0, 0));
+        assert_eq!(parse_version("1.4"), (1, 4, 0));
+        assert!(parse_version("0.10.0") > parse_version("0.9.9"));
+    }
+}
@@ -0,0 +1,181 @@
+use std::time::Duration;
+
+use serde_json::Value as JsonValue;
+
+use crate::settings::{RemoteWhisperKind, RemoteWhisperServer, TranscriptionOptions};
+use crate::{clean_transcript_fragment, log_line, truncate_for_log};
+
+/// Send a WAV segment to an external whisper server, retrying with a short
+/// linear backoff. Returns the cleaned transcript in the same shape as the
+/// local CLI path so callers can't tell which backend produced it.
+/// `language` is a whisper code or `auto`, which leaves detection to the server.
+/// With `translate` the server returns an English translation instead.
+/// OpenAI-compatible servers only take the temperature from `options`.
+pub async fn transcribe(
+    server: &RemoteWhisperServer,
+    wav_bytes: &[u8],
+    language: &str,
+    prompt: Option<&str>,
+    translate: bool,
+    options: &TranscriptionOptions,
+) -> Result<String, String> {
+    let endpoint = endpoint_url(server, translate)?;
+    let client = reqwest::Client::builder()
+        .timeout(Duration::from_millis(server.timeout_ms.max(1_000)))
+        .build()
+        .map_err(|err| format!("failed to build HTTP client: {}", err))?;
+
+    let attempts = server.retries.saturating_add(1);
+    let mut last_error = String::new();
+    for attempt in 1..=attempts {
+        match send_once(&client, server, &endpoint, wav_bytes, language, prompt, translate, options).await {
+            Ok(text) => {
+                log_line(&format!(
+                    "[remote-whisper] {} succeeded (attempt {}/{})",
+                    endpoint, attempt, attempts
+                ));
+                return Ok(text);
+            }
+            Err(err) => {
+                log_line(&format!(
+                    "[remote-whisper] {} failed (attempt {}/{}): {}",
+                    endpoint, attempt, attempts, err
+                ));
+                last_error = err;
+            }
+        }
+        if attempt < attempts {
+            tokio::time::sleep(Duration::from_millis(500 * attempt as u64)).await;
+        }
+    }
+
+    Err(format!("remote whisper server failed: {}", last_error))
+}
+
+/// Accept either a base URL (`http://desktop.local:8080`) or a full endpoint
+/// URL and return the endpoint for the configured server kind. OpenAI
+/// serves translations from their own endpoint.
+fn endpoint_url(server: &RemoteWhisperServer, translate: bool) -> Result<String, String> {
+    const OPENAI_TRANSCRIPTIONS: &str = "/v1/audio/transcriptions";
+    const OPENAI_TRANSLATIONS: &str = "/v1/audio/translations";
+    let mut base = server.url.trim().trim_end_matches('/');
+    if base.is_empty() {
+        return Err("remote whisper server URL is empty".to_string());
+    }
+    let path = match server.kind {
+        RemoteWhisperKind::WhisperCpp => "/inference",
+        RemoteWhisperKind::OpenaiCompatible if translate => OPENAI_TRANSLATIONS,
+        RemoteWhisperKind::OpenaiCompatible => OPENAI_TRANSCRIPTIONS,
+    };
+    if server.kind == RemoteWhisperKind::OpenaiCompatible {
+        base = base
+            .strip_suffix(OPENAI_TRANSCRIPTIONS)
+            .or_else(|| base.strip_suffix(OPENAI_TRANSLATIONS))
+            .unwrap_or(base);
+    }
+    if base.ends_with(path) {
+        Ok(base.to_string())
+    } else {
+        Ok(format!("{}{}", base, path))
+    }
+}
+
+#[allow(clippy::too_many_arguments)]
+async fn send_once(
+    client: &reqwest::Client,
+    server: &RemoteWhisperServer,
+    endpoint: &str,
+    wav_bytes: &[u8],
+    language: &str,
+    prompt: Option<&str>,
+    translate: bool,
+    options: &TranscriptionOptions,
+) -> Result<String, String> {
+    let file_part = reqwest::multipart::Part::bytes(wav_bytes.to_vec())
+        .file_name("segment.wav")
+        .mime_str("audio/wav")
+        .map_err(|err| format!("failed to build multipart body: {}", err))?;
+    let mut form = reqwest::multipart::Form::new()
+        .part("file", file_part)
+        .text("response_format", "json");
+    let temperature = options.temperature.unwrap_or(0.0).to_string();
+    form = match server.kind {
+        RemoteWhisperKind::WhisperCpp => {
+            let mut form = form.text("temperature", temperature);
+            for (name, value) in [
+                ("beam_size", options.beam_size.map(|value| value.to_string())),
+                ("logprob_thold", options.logprob_thold.map(|value| value.to_string())),
+                ("no_speech_thold", options.no_speech_thold.map(|value| value.to_string())),
+                ("temperature_inc", options.temperature_inc.map(|value| value.to_string())),
+                ("entropy_thold", options.entropy_thold.map(|value| value.to_string())),
+                ("max_len", options.max_segment_len.map(|value| value.to_string())),
+            ] {
+                if let Some(value) = value {
+                    form = form.text(name, value);
+                }
+            }
+            form
+        }
+        RemoteWhisperKind::OpenaiCompatible => {
+            let form = form.text(
+                "model",
+                server
+                    .model
+                    .clone()
+                    .filter(|m| !m.trim().is_empty())
+                    .unwrap_or_else(|| "whisper-1".to_string()),
+            );
+            match options.temperature {
+                Some(_) => form.text("temperature", temperature),
+                None => form,
+            }
+        }
+    };
+
+    // OpenAI's translations endpoint has no `language` field.
+    let openai_translation = translate && server.kind == RemoteWhisperKind::OpenaiCompatible;
+    if language != "auto" && !openai_translation {
+        form = form.text("language", language.to_string());
+    }
+    if translate && server.kind == RemoteWhisperKind::WhisperCpp {
+        form = form.text("translate", "true");
+    }
+    // Both server kinds take the initial prompt as `prompt`.
+    if let Some(prompt) = prompt {
+        form = form.text("prompt", prompt.to_string());
+    }
+
+    let mut request = client.post(endpoint).multipart(form);
+    if let Some(key) = server.api_key.as_deref().filter(|k| !k.trim().is_empty()) {
+        request = request.bearer_auth(key.trim());
+    }
+
+    let response = request
+        .send()
+        .await
+        .map_err(|err| format!("request failed: {}", err))?;
+    let status = response.status();
+    let body = response
+        .text()
+        .await
+        .map_err(|err| format!("failed to read response body: {}", err))?;
+    if !status.is_success() {
+        return Err(format!("HTTP {}: {}", status, truncate_for_log(body.trim(), 300)));
+    }
+
+    let parsed: JsonValue = serde_json::from_str(&body)
+        .map_err(|err| format!("invalid JSON response: {}", err))?;
+    let text = parsed
+        .get("text")
+        .and_then(|v| v.as_str())
+        .ok_or_else(|| "response missing \"text\" field".to_string())?;
+
+    Ok(clean_remote_text(text))
+}
+
+pub(crate) fn clean_remote_text(text: &str) -> String {
+    text.lines()
+        .filter_map(clean_transcript_fragment)
+        .collect::<Vec<_>>()
+        .join(" ")
+}
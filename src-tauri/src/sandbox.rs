@@ -0,0 +1,192 @@
+//! Optional seatbelt sandbox for the node processes HeyJamie starts: the
+//! llm-agent runs, the canvas server and MCP health probes. MCP servers
+//! started by the agent inherit it. On macOS, when `sandbox.enabled` is on,
+//! they run under `sandbox-exec` with a profile that hides the contents of
+//! the home folder except for approved directories, only lets them write
+//! to those and temp, and limits the network per `sandbox.network`.
+//! Elsewhere the setting is ignored.
+
+use std::path::{Path, PathBuf};
+use std::process::Command;
+#[cfg(not(target_os = "macos"))]
+use std::sync::Once;
+
+use serde_json::Value as JsonValue;
+use tauri::Manager;
+
+use crate::settings::{self, SandboxNetwork, SandboxSettings};
+use crate::{load_mcp_config_value, log_line};
+
+#[cfg(target_os = "macos")]
+const SANDBOX_EXEC: &str = "/usr/bin/sandbox-exec";
+
+/// Folders under the home folder node and npx need for themselves.
+const TOOL_DIRS: &[&str] = &[".npm", ".nvm", ".volta", ".fnm", ".cache", ".node-gyp"];
+
+/// A string literal in the profile language.
+fn quote(path: &str) -> String {
+    format!("\"{}\"", path.replace('\\', "\\\\").replace('"', "\\\""))
+}
+
+/// Seatbelt profile allowing everything except reading files under `home`
+/// and writing anywhere, outside `settings.allowed_dirs`, `required` and
+/// temp. Later rules win over earlier ones.
+fn profile(settings: &SandboxSettings, required: &[PathBuf], home: Option<&Path>) -> String {
+    let mut dirs: Vec<String> = required
+        .iter()
+        .map(|dir| dir.display().to_string())
+        .chain(settings.allowed_dirs.iter().map(|dir| match (dir.strip_prefix("~/"), home) {
+            (Some(rest), Some(home)) => home.join(rest).display().to_string(),
+            _ => dir.clone(),
+        }))
+        .map(|dir| dir.trim_end_matches('/').to_string())
+        .filter(|dir| !dir.is_empty())
+        .collect();
+    dirs.sort();
+    dirs.dedup();
+    let allowed: String = dirs.iter().map(|dir| format!(" (subpath {})", quote(dir))).collect();
+
+    let mut lines = vec!["(version 1)".to_string(), "(allow default)".to_string()];
+    if let Some(home) = home {
+        // Metadata stays readable so module lookups up the tree see
+        // "not found" rather than failing.
+        lines.push(format!("(deny file-read-data (subpath {}))", quote(&home.display().to_string())));
+    }
+    lines.push("(deny file-write*)".to_string());
+    lines.push(format!(
+        "(allow file-write* (subpath \"/private/tmp\") (subpath \"/private/var/folders\") (subpath \"/dev\"){})",
+        allowed
+    ));
+    if !allowed.is_empty() {
+        lines.push(format!("(allow file-read-data{})", allowed));
+    }
+    match settings.network {
+        SandboxNetwork::Allow => {}
+        SandboxNetwork::LocalOnly => {
+            lines.push("(deny network-outbound)".to_string());
+            lines.push("(allow network-outbound (remote ip \"localhost:*\") (remote unix-socket))".to_string());
+        }
+        SandboxNetwork::Deny => {
+            lines.push("(deny network*)".to_string());
+            lines.push("(allow network* (remote unix-socket))".to_string());
+        }
+    }
+    lines.join("\n")
+}
+
+/// Working directories of the configured MCP servers and the processes
+/// launched for them.
+fn mcp_dirs(config: &JsonValue, home: Option<&Path>) -> Vec<PathBuf> {
+    config
+        .get("mcpServers")
+        .and_then(|servers| servers.as_object())
+        .map(|servers| {
+            servers
+                .values()
+                .flat_map(|entry| [entry.get("cwd"), entry.pointer("/launch/cwd")])
+                .filter_map(|cwd| cwd.and_then(|cwd| cwd.as_str()))
+                .map(|cwd| match (cwd.strip_prefix("~/"), home) {
+                    (Some(rest), Some(home)) => home.join(rest),
+                    _ => PathBuf::from(cwd),
+                })
+                .collect()
+        })
+        .unwrap_or_default()
+}
+
+/// What every sandboxed process needs: the repo (scripts and
+/// node_modules), the app's config and data, node's own folders and the
+/// MCP servers' working directories.
+fn required_dirs(app: &tauri::AppHandle, home: Option<&Path>) -> Vec<PathBuf> {
+    let mut dirs = Vec::new();
+    if let Some(root) = Path::new(env!("CARGO_MANIFEST_DIR")).parent() {
+        dirs.push(root.to_path_buf());
+    }
+    let path = app.path();
+    dirs.extend([path.app_config_dir(), path.app_data_dir(), path.app_cache_dir()].into_iter().flatten());
+    if let Some(home) = home {
+        dirs.extend(TOOL_DIRS.iter().map(|dir| home.join(dir)));
+    }
+    dirs.extend(mcp_dirs(&load_mcp_config_value(app).unwrap_or(JsonValue::Null), home));
+    dirs
+}
+
+/// A `Command` for `program`, wrapped in the sandbox when it's on.
+#[cfg(target_os = "macos")]
+pub fn command(app: &tauri::AppHandle, program: &str) -> Command {
+    let settings = settings::load_settings(app).sandbox;
+    if !settings.enabled {
+        return Command::new(program);
+    }
+    if !Path::new(SANDBOX_EXEC).exists() {
+        log_line("[sandbox] sandbox-exec not found; running unsandboxed");
+        return Command::new(program);
+    }
+    let home = dirs::home_dir();
+    let profile = profile(&settings, &required_dirs(app, home.as_deref()), home.as_deref());
+    let mut cmd = Command::new(SANDBOX_EXEC);
+    cmd.arg("-p").arg(profile).arg(program);
+    cmd
+}
+
+#[cfg(not(target_os = "macos"))]
+pub fn command(app: &tauri::AppHandle, program: &str) -> Command {
+    static WARN: Once = Once::new();
+    if settings::load_settings(app).sandbox.enabled {
+        WARN.call_once(|| log_line("[sandbox] only available on macOS; running node processes unsandboxed"));
+    }
+    Command::new(program)
+}
+
+/// The profile processes would run under now, for checking what it allows.
+#[tauri::command]
+#[specta::specta]
+pub fn get_sandbox_profile(app: tauri::AppHandle) -> String {
+    let home = dirs::home_dir();
+    let settings = settings::load_settings(&app).sandbox;
+    let profile = profile(&settings, &required_dirs(&app, home.as_deref()), home.as_deref());
+    if cfg!(target_os = "macos") {
+        profile
+    } else {
+        format!(";; sandbox-exec is macOS-only; this profile is not applied here.\n{}", profile)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::path::{Path, PathBuf};
+
+    use super::{mcp_dirs, profile};
+    use crate::settings::{SandboxNetwork, SandboxSettings};
+
+    #[test]
+    fn profile_hides_home_but_allows_approved_dirs() {
+        let home = Path::new("/Users/jamie");
+        let settings = SandboxSettings {
+            enabled: true,
+            allowed_dirs: vec!["~/Notes/".to_string(), "/Volumes/Shared \"Drive\"".to_string()],
+            network: SandboxNetwork::LocalOnly,
+        };
+        let restricted = profile(&settings, &[PathBuf::from("/Users/jamie/HeyJamie")], Some(home));
+        let lines: Vec<&str> = restricted.lines().collect();
+        assert_eq!(lines[0], "(version 1)");
+        assert_eq!(lines[2], "(deny file-read-data (subpath \"/Users/jamie\"))");
+        assert!(lines[5].starts_with("(allow file-read-data"));
+        assert!(lines[5].contains("(subpath \"/Users/jamie/Notes\")"));
+        assert!(lines[5].contains("(subpath \"/Users/jamie/HeyJamie\")"));
+        assert!(lines[5].contains("(subpath \"/Volumes/Shared \\\"Drive\\\"\")"));
+        assert_eq!(lines[6], "(deny network-outbound)");
+
+        let open = SandboxSettings::default();
+        assert!(!profile(&open, &[], Some(home)).contains("network"));
+
+        let config = serde_json::json!({ "mcpServers": {
+            "canvas": { "cwd": "~/mcp/canvas" },
+            "web": { "url": "x", "launch": { "command": "uvx", "cwd": "/srv/web" } }
+        } });
+        assert_eq!(
+            mcp_dirs(&config, Some(home)),
+            vec![PathBuf::from("/Users/jamie/mcp/canvas"), PathBuf::from("/srv/web")]
+        );
+    }
+}
@@ -0,0 +1,253 @@
+use std::fs;
+use std::path::PathBuf;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+
+use serde::{Deserialize, Serialize};
+use tauri::Manager;
+use tauri_specta::Event;
+
+use crate::log_line;
+use crate::transcription_queue::Lane;
+
+/// Scenarios shipped with the app, by name.
+const BUNDLED: &[(&str, &str)] = &[("first-episode", include_str!("../scenarios/first-episode.json"))];
+
+const CANCEL_POLL_MS: u64 = 200;
+
+/// One scripted moment of a demo session.
+#[derive(Serialize, Deserialize, Clone, Debug, specta::Type)]
+#[serde(rename_all = "camelCase", tag = "kind")]
+pub enum ScenarioAction {
+    /// A line as if whisper had just produced it.
+    Transcript { text: String, source: Option<String> },
+    /// A WAV next to the scenario file, transcribed with the real pipeline.
+    /// Progress events carry the resulting `transcript` instead.
+    Audio { file: String },
+    /// A canned agent reply shown in place of a live LLM run.
+    Agent { narrative: String, url: Option<String> },
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug)]
+#[serde(rename_all = "camelCase")]
+struct ScenarioStep {
+    /// When to play the step, from the start of the scenario.
+    at_ms: u64,
+    #[serde(flatten)]
+    action: ScenarioAction,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug)]
+#[serde(rename_all = "camelCase")]
+struct Scenario {
+    title: String,
+    #[serde(default)]
+    description: String,
+    steps: Vec<ScenarioStep>,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, specta::Type)]
+#[serde(rename_all = "camelCase")]
+pub struct ScenarioSummary {
+    pub name: String,
+    pub title: String,
+    pub description: String,
+    pub steps: u32,
+}
+
+/// Emitted to the main window for every step of a running scenario, then
+/// once with `done` set.
+#[derive(Serialize, Deserialize, Clone, Debug, specta::Type, tauri_specta::Event)]
+#[serde(rename_all = "camelCase")]
+pub struct ScenarioProgress {
+    pub name: String,
+    pub index: u32,
+    pub total: u32,
+    pub action: Option<ScenarioAction>,
+    pub done: bool,
+    pub error: Option<String>,
+}
+
+/// Cancel flag of the scenario being played, if any.
+#[derive(Default)]
+pub struct ScenarioState {
+    running: Mutex<Option<Arc<AtomicBool>>>,
+}
+
+/// `<app data>/scenarios`, where custom `<name>.json` scenarios and their
+/// audio live.
+fn custom_dir(app: &tauri::AppHandle) -> Result<PathBuf, String> {
+    Ok(app
+        .path()
+        .app_data_dir()
+        .map_err(|err| format!("failed to resolve app data dir: {}", err))?
+        .join("scenarios"))
+}
+
+fn parse(content: &str) -> Result<Scenario, String> {
+    serde_json::from_str(content).map_err(|err| format!("invalid scenario: {}", err))
+}
+
+/// Custom scenarios shadow bundled ones with the same name.
+fn load(app: &tauri::AppHandle, name: &str) -> Result<Scenario, String> {
+    let valid = !name.is_empty() && name.chars().all(|c| c.is_ascii_alphanumeric() || c == '-' || c == '_');
+    if !valid {
+        return Err(format!("invalid scenario name: {}", name));
+    }
+    if let Ok(content) = fs::read_to_string(custom_dir(app)?.join(format!("{}.json", name))) {
+        return parse(&content);
+    }
+    let (_, content) = BUNDLED
+        .iter()
+        .find(|(bundled, _)| *bundled == name)
+        .ok_or_else(|| format!("no scenario named {}", name))?;
+    parse(content)
+}
+
+fn summary(name: &str, scenario: &Scenario) -> ScenarioSummary {
+    ScenarioSummary {
+        name: name.to_string(),
+        title: scenario.title.clone(),
+        description: scenario.description.clone(),
+        steps: scenario.steps.len() as u32,
+    }
+}
+
+async fn play_step(app: &tauri::AppHandle, action: ScenarioAction) -> Result<ScenarioAction, String> {
+    let ScenarioAction::Audio { file } = action else {
+        return Ok(action);
+    };
+    if file.contains("..") {
+        return Err(format!("invalid scenario audio path: {}", file));
+    }
+    let path = custom_dir(app)?.join(&file);
+    let wav_bytes = fs::read(&path).map_err(|err| format!("failed to read {}: {}", path.display(), err))?;
+    let text = crate::transcribe_wav(app, wav_bytes, None, None, Lane::Live).await?;
+    Ok(ScenarioAction::Transcript { text, source: None })
+}
+
+async fn play(app: tauri::AppHandle, name: String, scenario: Scenario, cancel: Arc<AtomicBool>) {
+    let total = scenario.steps.len() as u32;
+    let notify = |progress: ScenarioProgress| {
+        if let Err(err) = progress.emit_to(&app, "main") {
+            log_line(&format!("[scenario] failed to notify main window: {}", err));
+        }
+    };
+    let mut elapsed_ms = 0;
+    let mut error = None;
+    for (index, step) in scenario.steps.into_iter().enumerate() {
+        let mut wait_ms = step.at_ms.saturating_sub(elapsed_ms);
+        while wait_ms > 0 && !cancel.load(Ordering::SeqCst) {
+            let slice = wait_ms.min(CANCEL_POLL_MS);
+            tokio::time::sleep(Duration::from_millis(slice)).await;
+            wait_ms -= slice;
+        }
+        elapsed_ms = elapsed_ms.max(step.at_ms);
+        if cancel.load(Ordering::SeqCst) {
+            error = Some("stopped".to_string());
+            break;
+        }
+        match play_step(&app, step.action).await {
+            Ok(action) => notify(ScenarioProgress {
+                name: name.clone(),
+                index: index as u32,
+                total,
+                action: Some(action),
+                done: false,
+                error: None,
+            }),
+            Err(err) => {
+                log_line(&format!("[scenario] {} step {} failed: {}", name, index, err));
+                error = Some(err);
+                break;
+            }
+        }
+    }
+    *app.state::<ScenarioState>().running.lock().unwrap() = None;
+    log_line(&format!("[scenario] {} finished ({:?})", name, error));
+    notify(ScenarioProgress {
+        name,
+        index: total,
+        total,
+        action: None,
+        done: true,
+        error,
+    });
+}
+
+/// Bundled demo sessions plus any in `<app data>/scenarios`.
+#[tauri::command]
+#[specta::specta]
+pub fn list_scenarios(app: tauri::AppHandle) -> Vec<ScenarioSummary> {
+    let mut scenarios: Vec<ScenarioSummary> = BUNDLED
+        .iter()
+        .filter_map(|(name, content)| parse(content).ok().map(|scenario| summary(name, &scenario)))
+        .collect();
+    let custom = custom_dir(&app).ok().and_then(|dir| fs::read_dir(dir).ok());
+    for entry in custom.into_iter().flatten().filter_map(|entry| entry.ok()) {
+        let path = entry.path();
+        if path.extension().and_then(|ext| ext.to_str()) != Some("json") {
+            continue;
+        }
+        let Some(name) = path.file_stem().and_then(|stem| stem.to_str()).map(str::to_string) else {
+            continue;
+        };
+        match fs::read_to_string(&path).map_err(|err| err.to_string()).and_then(|content| parse(&content)) {
+            Ok(scenario) => {
+                scenarios.retain(|existing| existing.name != name);
+                scenarios.push(summary(&name, &scenario));
+            }
+            Err(err) => log_line(&format!("[scenario] skipping {}: {}", path.display(), err)),
+        }
+    }
+    scenarios
+}
+
+/// Play a scripted session: its lines and canned agent replies arrive as
+/// `scenario-progress` events at their scripted times, so Jamie can be
+/// shown without a microphone or API key.
+#[tauri::command]
+#[specta::specta]
+pub fn run_scenario(app: tauri::AppHandle, name: String) -> Result<ScenarioSummary, String> {
+    let scenario = load(&app, &name)?;
+    let cancel = Arc::new(AtomicBool::new(false));
+    {
+        let state = app.state::<ScenarioState>();
+        let mut running = state.running.lock().unwrap();
+        if running.is_some() {
+            return Err("a scenario is already playing".to_string());
+        }
+        *running = Some(cancel.clone());
+    }
+    let info = summary(&name, &scenario);
+    log_line(&format!("[scenario] playing {}", name));
+    tauri::async_runtime::spawn(play(app, name, scenario, cancel));
+    Ok(info)
+}
+
+#[tauri::command]
+#[specta::specta]
+pub fn cancel_scenario(app: tauri::AppHandle) {
+    let state = app.state::<ScenarioState>();
+    let running = state.running.lock().unwrap();
+    if let Some(cancel) = running.as_ref() {
+        cancel.store(true, Ordering::SeqCst);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{parse, ScenarioAction, BUNDLED};
+
+    #[test]
+    fn bundled_scenarios_parse() {
+        for (name, content) in BUNDLED {
+            let scenario = parse(content).unwrap_or_else(|err| panic!("{}: {}", name, err));
+            assert!(!scenario.steps.is_empty());
+            assert!(scenario.steps.windows(2).all(|pair| pair[0].at_ms <= pair[1].at_ms));
+        }
+        let scenario = parse(r#"{"title":"t","steps":[{"atMs":0,"kind":"audio","file":"a.wav"}]}"#).unwrap();
+        assert!(matches!(scenario.steps[0].action, ScenarioAction::Audio { .. }));
+    }
+}
@@ -0,0 +1,312 @@
+//! User scripts: `.rhai` files in `<config>/scripts`, loaded when
+//! `scripting.enabled` is on. A script handles a lifecycle hook by
+//! defining a function of that name taking one map:
+//!
+//! - `on_segment(segment)`: a transcript segment was recorded
+//!   (`index`, `offsetMs`, `text`, `source`, `sessionId`)
+//! - `on_topic_shift(shift)`: the conversation moved on
+//!   (`sessionId`, `fromTopic`, `toTopic`, `offsetMs`)
+//! - `on_agent_result(result)`: an agent run finished
+//!   (`mode`, `prompt`, `traceId`, `output`)
+//!
+//! Scripts get no file, process or network access. Besides `print`, which
+//! goes to the log, they can call `session_id()`, `bookmark(label)`,
+//! `append_note(text)` (to the session's `script-notes.md`) and
+//! `open_url(url)` (http and https only). Each call is capped in operations and size so a
+//! runaway script can't stall the app.
+
+use std::fs;
+use std::io::Write;
+use std::path::PathBuf;
+use std::sync::{Arc, Mutex};
+
+use rhai::{CallFnOptions, Dynamic, Engine, Scope, AST};
+use serde::{Deserialize, Serialize};
+use serde_json::Value as JsonValue;
+use tauri::Manager;
+
+use crate::bookmarks::{self, BookmarkSource};
+use crate::sessions::{self, SessionState};
+use crate::{guest_mode, log_line, settings, truncate_for_log};
+
+pub const ON_SEGMENT: &str = "on_segment";
+pub const ON_TOPIC_SHIFT: &str = "on_topic_shift";
+pub const ON_AGENT_RESULT: &str = "on_agent_result";
+const HOOKS: &[&str] = &[ON_SEGMENT, ON_TOPIC_SHIFT, ON_AGENT_RESULT];
+
+const MAX_OPERATIONS: u64 = 200_000;
+const MAX_CALL_LEVELS: usize = 32;
+const MAX_STRING_SIZE: usize = 64 * 1024;
+const MAX_COLLECTION_SIZE: usize = 10_000;
+
+#[derive(Serialize, Deserialize, Clone, Debug, specta::Type)]
+#[serde(rename_all = "camelCase")]
+pub struct ScriptInfo {
+    /// File name, e.g. `bookmark-questions.rhai`.
+    pub name: String,
+    /// Hooks it defines.
+    pub hooks: Vec<String>,
+    /// Why it didn't load.
+    pub error: Option<String>,
+}
+
+struct Script {
+    name: String,
+    ast: AST,
+    hooks: Vec<String>,
+}
+
+struct Loaded {
+    engine: Engine,
+    scripts: Vec<Script>,
+    info: Vec<ScriptInfo>,
+}
+
+#[derive(Default)]
+pub struct ScriptingState {
+    loaded: Mutex<Option<Arc<Loaded>>>,
+}
+
+/// An engine with limits and `print`/`debug` sent to the log, without the
+/// HeyJamie API.
+fn sandboxed_engine() -> Engine {
+    let mut engine = Engine::new();
+    engine
+        .set_max_operations(MAX_OPERATIONS)
+        .set_max_call_levels(MAX_CALL_LEVELS)
+        .set_max_string_size(MAX_STRING_SIZE)
+        .set_max_array_size(MAX_COLLECTION_SIZE)
+        .set_max_map_size(MAX_COLLECTION_SIZE);
+    engine.on_print(|text| log_line(&format!("[scripts] {}", truncate_for_log(text, 500))));
+    engine.on_debug(|text, _, position| {
+        log_line(&format!("[scripts] {} ({})", truncate_for_log(text, 500), position))
+    });
+    engine
+}
+
+/// The functions scripts can call.
+fn register_api(engine: &mut Engine, app: &tauri::AppHandle) {
+    let handle = app.clone();
+    engine.register_fn("session_id", move || {
+        handle.state::<SessionState>().active_id().unwrap_or_default()
+    });
+    let handle = app.clone();
+    engine.register_fn("bookmark", move |label: &str| {
+        let (app, label) = (handle.clone(), Some(label.trim().to_string()).filter(|l| !l.is_empty()));
+        tauri::async_runtime::spawn(async move {
+            if let Err(err) = bookmarks::create_bookmark(&app, label, BookmarkSource::Script).await {
+                log_line(&format!("[scripts] bookmark failed: {}", err));
+            }
+        });
+    });
+    let handle = app.clone();
+    engine.register_fn("append_note", move |text: &str| -> bool {
+        match append_note(&handle, text) {
+            Ok(()) => true,
+            Err(err) => {
+                log_line(&format!("[scripts] append_note failed: {}", err));
+                false
+            }
+        }
+    });
+    let handle = app.clone();
+    engine.register_fn("open_url", move |url: &str| -> bool {
+        match web_url(url).and_then(|url| crate::open_browser_window(handle.clone(), url, true)) {
+            Ok(()) => true,
+            Err(err) => {
+                log_line(&format!("[scripts] open_url failed: {}", err));
+                false
+            }
+        }
+    });
+}
+
+/// `url` if it is a web page. Anything else (`file:`, custom schemes)
+/// could reach a local program through the system URL handler.
+fn web_url(url: &str) -> Result<String, String> {
+    let parsed = reqwest::Url::parse(url.trim()).map_err(|err| format!("invalid url: {}", err))?;
+    if !matches!(parsed.scheme(), "http" | "https") {
+        return Err(format!("only http and https URLs can be opened, not {}:", parsed.scheme()));
+    }
+    Ok(parsed.to_string())
+}
+
+fn append_note(app: &tauri::AppHandle, text: &str) -> Result<(), String> {
+    let session_id = app.state::<SessionState>().active_id().ok_or("no active session")?;
+    let path = sessions::session_dir(app, &session_id)?.join("script-notes.md");
+    let mut file = fs::OpenOptions::new()
+        .create(true)
+        .append(true)
+        .open(&path)
+        .map_err(|err| format!("failed to open {}: {}", path.display(), err))?;
+    writeln!(file, "{}", text.trim_end()).map_err(|err| format!("failed to write {}: {}", path.display(), err))
+}
+
+/// Hooks `ast` defines with the expected single parameter.
+fn hooks_of(ast: &AST) -> Vec<String> {
+    HOOKS
+        .iter()
+        .filter(|hook| ast.iter_functions().any(|function| function.name == **hook && function.params.len() == 1))
+        .map(|hook| hook.to_string())
+        .collect()
+}
+
+/// Call `hook` in `ast` with `payload` as a map. Top-level statements
+/// aren't run again.
+fn call_hook(engine: &Engine, ast: &AST, hook: &str, payload: &JsonValue) -> Result<(), String> {
+    let argument: Dynamic = rhai::serde::to_dynamic(payload).map_err(|err| err.to_string())?;
+    let options = CallFnOptions::new().eval_ast(false);
+    engine
+        .call_fn_with_options::<Dynamic>(options, &mut Scope::new(), ast, hook, (argument,))
+        .map(|_| ())
+        .map_err(|err| err.to_string())
+}
+
+fn scripts_dir(app: &tauri::AppHandle) -> Result<PathBuf, String> {
+    if guest_mode::is_active() {
+        return Err("scripts are off in guest mode".to_string());
+    }
+    let dir = app
+        .path()
+        .app_config_dir()
+        .map_err(|err| format!("failed to resolve app config dir: {}", err))?
+        .join("scripts");
+    fs::create_dir_all(&dir).map_err(|err| format!("failed to create scripts dir: {}", err))?;
+    Ok(dir)
+}
+
+/// Compile every script and run its top level once.
+fn load(app: &tauri::AppHandle) -> Result<Loaded, String> {
+    let mut engine = sandboxed_engine();
+    register_api(&mut engine, app);
+    let mut paths: Vec<PathBuf> = fs::read_dir(scripts_dir(app)?)
+        .map_err(|err| format!("failed to read scripts dir: {}", err))?
+        .filter_map(|entry| entry.ok().map(|entry| entry.path()))
+        .filter(|path| path.extension().is_some_and(|ext| ext == "rhai"))
+        .collect();
+    paths.sort();
+    let mut scripts = Vec::new();
+    let mut info = Vec::new();
+    for path in paths {
+        let name = path.file_name().map(|name| name.to_string_lossy().into_owned()).unwrap_or_default();
+        let compiled = fs::read_to_string(&path)
+            .map_err(|err| err.to_string())
+            .and_then(|source| engine.compile(source).map_err(|err| err.to_string()))
+            .and_then(|ast| engine.run_ast(&ast).map(|_| ast).map_err(|err| err.to_string()));
+        match compiled {
+            Ok(ast) => {
+                let hooks = hooks_of(&ast);
+                log_line(&format!("[scripts] loaded {} ({})", name, hooks.join(", ")));
+                info.push(ScriptInfo {
+                    name: name.clone(),
+                    hooks: hooks.clone(),
+                    error: None,
+                });
+                scripts.push(Script { name, ast, hooks });
+            }
+            Err(err) => {
+                log_line(&format!("[scripts] {} failed to load: {}", name, err));
+                info.push(ScriptInfo {
+                    name,
+                    hooks: Vec::new(),
+                    error: Some(err),
+                });
+            }
+        }
+    }
+    Ok(Loaded { engine, scripts, info })
+}
+
+/// Load scripts again, or drop them when scripting is off or a guest is
+/// using the app (the scripts belong to the owner).
+pub fn reload(app: &tauri::AppHandle) {
+    let loaded = if settings::load_settings(app).scripting.enabled && !guest_mode::is_active() {
+        match load(app) {
+            Ok(loaded) => Some(Arc::new(loaded)),
+            Err(err) => {
+                log_line(&format!("[scripts] {}", err));
+                None
+            }
+        }
+    } else {
+        None
+    };
+    *app.state::<ScriptingState>().loaded.lock().unwrap() = loaded;
+}
+
+/// Run `hook` in every script that defines it, off the calling thread.
+pub fn fire(app: &tauri::AppHandle, hook: &'static str, payload: JsonValue) {
+    if guest_mode::is_active() {
+        return;
+    }
+    let Some(loaded) = app.state::<ScriptingState>().loaded.lock().unwrap().clone() else {
+        return;
+    };
+    if !loaded.scripts.iter().any(|script| script.hooks.iter().any(|h| h == hook)) {
+        return;
+    }
+    tauri::async_runtime::spawn_blocking(move || {
+        for script in loaded.scripts.iter().filter(|script| script.hooks.iter().any(|h| h == hook)) {
+            if let Err(err) = call_hook(&loaded.engine, &script.ast, hook, &payload) {
+                log_line(&format!("[scripts] {} {} failed: {}", script.name, hook, err));
+            }
+        }
+    });
+}
+
+#[tauri::command]
+#[specta::specta]
+pub fn list_scripts(app: tauri::AppHandle) -> Vec<ScriptInfo> {
+    app.state::<ScriptingState>()
+        .loaded
+        .lock()
+        .unwrap()
+        .as_ref()
+        .map(|loaded| loaded.info.clone())
+        .unwrap_or_default()
+}
+
+/// Pick up added or edited scripts.
+#[tauri::command]
+#[specta::specta]
+pub fn reload_scripts(app: tauri::AppHandle) -> Result<Vec<ScriptInfo>, String> {
+    if !settings::load_settings(&app).scripting.enabled {
+        return Err("scripting is off; turn it on in settings".to_string());
+    }
+    reload(&app);
+    Ok(list_scripts(app))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{call_hook, hooks_of, sandboxed_engine, web_url};
+
+    #[test]
+    fn opens_only_web_urls() {
+        assert_eq!(web_url(" https://example.com/a?b=1&c=2 ").unwrap(), "https://example.com/a?b=1&c=2");
+        assert!(web_url("http://localhost:3000").is_ok());
+        assert!(web_url("file:///etc/passwd").is_err());
+        assert!(web_url("javascript:alert(1)").is_err());
+        assert!(web_url("not a url").is_err());
+    }
+
+    #[test]
+    fn finds_hooks_and_stops_runaway_scripts() {
+        let engine = sandboxed_engine();
+        let ast = engine
+            .compile(
+                "fn on_segment(segment) { if segment.text.len() > 100 { throw \"long\"; } }\n\
+                 fn on_topic_shift() {}\n\
+                 fn on_agent_result(result) { loop {} }\n\
+                 fn helper(x) { x }",
+            )
+            .unwrap();
+        assert_eq!(hooks_of(&ast), vec!["on_segment", "on_agent_result"]);
+
+        let short = serde_json::json!({ "text": "hello", "offsetMs": 1200 });
+        assert_eq!(call_hook(&engine, &ast, "on_segment", &short), Ok(()));
+        let long = serde_json::json!({ "text": "x".repeat(200) });
+        assert!(call_hook(&engine, &ast, "on_segment", &long).unwrap_err().contains("long"));
+        assert!(call_hook(&engine, &ast, "on_agent_result", &short).is_err());
+    }
+}
@@ -0,0 +1,240 @@
+//! Segment audio kept per session in `<session>/audio/<offset_ms>.wav`,
+//! as whisper heard it (after preprocessing). Kept while
+//! `sessionAudio.keep` or the retranscribe wrap-up step is on, so a
+//! misheard passage can be played back or retranscribed with a bigger
+//! model later (`transcript_versions`). Old audio is pruned by age and total size at startup and
+//! whenever a session ends.
+
+use std::fs;
+use std::io::Read;
+use std::path::PathBuf;
+
+use base64::{engine::general_purpose, Engine as _};
+use serde::{Deserialize, Serialize};
+use tauri::Manager;
+
+use crate::sessions::{self, SessionState};
+use crate::settings::{self, SessionAudioSettings};
+use crate::{log_line, now_millis};
+
+const AUDIO_DIR: &str = "audio";
+const DAY_MS: u64 = 24 * 60 * 60 * 1000;
+
+#[derive(Serialize, Deserialize, Clone, Debug, specta::Type)]
+#[serde(rename_all = "camelCase")]
+pub struct AudioSegment {
+    /// From the session start; also the file name.
+    pub offset_ms: u64,
+    pub duration_ms: u64,
+    pub bytes: u64,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, specta::Type)]
+#[serde(rename_all = "camelCase")]
+pub struct SessionAudio {
+    pub session_id: String,
+    pub segments: Vec<AudioSegment>,
+    pub total_bytes: u64,
+}
+
+/// Whether segments are being kept.
+fn keeping(app: &tauri::AppHandle) -> bool {
+    let settings = settings::load_settings(app);
+    settings.session_audio.keep || (settings.post_session.enabled && settings.post_session.retranscribe)
+}
+
+/// Length of a canonical (44-byte header) PCM WAV of `bytes` bytes.
+fn duration_ms(header: &[u8], bytes: u64) -> u64 {
+    if header.len() < 44 {
+        return 0;
+    }
+    let channels = u16::from_le_bytes([header[22], header[23]]) as u64;
+    let sample_rate = u32::from_le_bytes([header[24], header[25], header[26], header[27]]) as u64;
+    let bits = u16::from_le_bytes([header[34], header[35]]) as u64;
+    let bytes_per_second = channels * sample_rate * bits / 8;
+    if bytes_per_second == 0 {
+        return 0;
+    }
+    bytes.saturating_sub(44) * 1000 / bytes_per_second
+}
+
+/// Sessions whose audio should go, given `(id, started_at, audio bytes)`
+/// for each: past `retention_days`, then oldest first until the rest fit
+/// in `max_total_mb`. The newest session is always kept.
+fn to_prune(sessions: &[(String, u64, u64)], now: u64, settings: &SessionAudioSettings) -> Vec<String> {
+    let mut sessions: Vec<&(String, u64, u64)> = sessions.iter().filter(|(_, _, bytes)| *bytes > 0).collect();
+    sessions.sort_by_key(|(_, started_at, _)| *started_at);
+    sessions.pop();
+    let mut total: u64 = sessions.iter().map(|(_, _, bytes)| bytes).sum();
+    let cap = settings.max_total_mb as u64 * 1024 * 1024;
+    let mut pruned = Vec::new();
+    for (id, started_at, bytes) in sessions {
+        let expired = settings.retention_days > 0 && now.saturating_sub(*started_at) > settings.retention_days as u64 * DAY_MS;
+        if expired || (cap > 0 && total > cap) {
+            pruned.push(id.clone());
+            total -= bytes;
+        }
+    }
+    pruned
+}
+
+fn audio_dir(app: &tauri::AppHandle, session_id: &str) -> Result<PathBuf, String> {
+    Ok(sessions::session_dir(app, session_id)?.join(AUDIO_DIR))
+}
+
+/// Kept segments of `session_id` by offset, oldest first.
+pub fn segments(app: &tauri::AppHandle, session_id: &str) -> Result<Vec<(u64, PathBuf)>, String> {
+    let mut clips: Vec<(u64, PathBuf)> = fs::read_dir(audio_dir(app, session_id)?)
+        .map_err(|_| "no segment audio was kept for this session".to_string())?
+        .filter_map(|entry| entry.ok())
+        .filter_map(|entry| {
+            let path = entry.path();
+            let offset_ms = path.file_stem()?.to_str()?.parse().ok()?;
+            Some((offset_ms, path))
+        })
+        .collect();
+    clips.sort_by_key(|(offset_ms, _)| *offset_ms);
+    Ok(clips)
+}
+
+fn total_bytes(app: &tauri::AppHandle, session_id: &str) -> u64 {
+    segments(app, session_id)
+        .unwrap_or_default()
+        .iter()
+        .filter_map(|(_, path)| fs::metadata(path).ok())
+        .map(|metadata| metadata.len())
+        .sum()
+}
+
+/// Keep a transcribed segment's audio in the active session, if enabled.
+pub fn keep_segment(app: &tauri::AppHandle, wav_bytes: &[u8]) {
+    if !keeping(app) {
+        return;
+    }
+    let Some(moment) = app.state::<SessionState>().current_moment() else {
+        return;
+    };
+    let result = audio_dir(app, &moment.session_id).and_then(|dir| {
+        fs::create_dir_all(&dir).map_err(|err| format!("failed to create audio dir: {}", err))?;
+        fs::write(dir.join(format!("{}.wav", moment.offset_ms)), wav_bytes)
+            .map_err(|err| format!("failed to keep segment audio: {}", err))
+    });
+    if let Err(err) = result {
+        log_line(&format!("[session-audio] {}", err));
+    }
+}
+
+fn prune(app: &tauri::AppHandle) -> Result<(), String> {
+    let settings = settings::load_settings(app).session_audio;
+    let active = app.state::<SessionState>().active_id();
+    let sessions: Vec<(String, u64, u64)> = sessions::list_sessions(app.clone())?
+        .into_iter()
+        .filter(|info| active.as_deref() != Some(info.id.as_str()))
+        .map(|info| {
+            let bytes = total_bytes(app, &info.id);
+            (info.id, info.started_at, bytes)
+        })
+        .collect();
+    for session_id in to_prune(&sessions, now_millis(), &settings) {
+        fs::remove_dir_all(audio_dir(app, &session_id)?)
+            .map_err(|err| format!("failed to delete audio of {}: {}", session_id, err))?;
+        log_line(&format!("[session-audio] pruned the audio of {}", session_id));
+    }
+    Ok(())
+}
+
+/// Apply the retention settings in the background.
+pub fn spawn_prune(app: &tauri::AppHandle) {
+    let app = app.clone();
+    tauri::async_runtime::spawn_blocking(move || {
+        if let Err(err) = prune(&app) {
+            log_line(&format!("[session-audio] pruning failed: {}", err));
+        }
+    });
+}
+
+/// The audio kept for `session_id`; empty when none was.
+#[tauri::command]
+#[specta::specta]
+pub fn get_session_audio(app: tauri::AppHandle, session_id: String) -> Result<SessionAudio, String> {
+    let segments: Vec<AudioSegment> = if audio_dir(&app, &session_id)?.is_dir() {
+        segments(&app, &session_id)?
+            .into_iter()
+            .map(|(offset_ms, path)| {
+                let bytes = fs::metadata(&path).map(|metadata| metadata.len()).unwrap_or(0);
+                let mut header = Vec::with_capacity(44);
+                if let Ok(file) = fs::File::open(&path) {
+                    let _ = file.take(44).read_to_end(&mut header);
+                }
+                AudioSegment {
+                    offset_ms,
+                    duration_ms: duration_ms(&header, bytes),
+                    bytes,
+                }
+            })
+            .collect()
+    } else {
+        Vec::new()
+    };
+    Ok(SessionAudio {
+        total_bytes: segments.iter().map(|segment| segment.bytes).sum(),
+        session_id,
+        segments,
+    })
+}
+
+/// One kept segment as base64 WAV, for playback.
+#[tauri::command]
+#[specta::specta]
+pub fn read_session_audio_segment(app: tauri::AppHandle, session_id: String, offset_ms: u64) -> Result<String, String> {
+    let path = audio_dir(&app, &session_id)?.join(format!("{}.wav", offset_ms));
+    let bytes = fs::read(&path).map_err(|_| format!("no audio at {} ms in {}", offset_ms, session_id))?;
+    Ok(general_purpose::STANDARD.encode(bytes))
+}
+
+#[tauri::command]
+#[specta::specta]
+pub fn delete_session_audio(app: tauri::AppHandle, session_id: String) -> Result<(), String> {
+    let dir = audio_dir(&app, &session_id)?;
+    if dir.is_dir() {
+        fs::remove_dir_all(&dir).map_err(|err| format!("failed to delete audio of {}: {}", session_id, err))?;
+        log_line(&format!("[session-audio] deleted the audio of {}", session_id));
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{duration_ms, to_prune, DAY_MS};
+    use crate::settings::SessionAudioSettings;
+    use crate::wav;
+
+    #[test]
+    fn prunes_by_age_then_size_but_keeps_the_newest() {
+        let mb = 1024 * 1024;
+        let now = 100 * DAY_MS;
+        let sessions = vec![
+            ("old".to_string(), now - 40 * DAY_MS, 10 * mb),
+            ("big".to_string(), now - 5 * DAY_MS, 300 * mb),
+            ("recent".to_string(), now - 2 * DAY_MS, 100 * mb),
+            ("newest".to_string(), now - DAY_MS, 900 * mb),
+            ("no-audio".to_string(), now - 90 * DAY_MS, 0),
+        ];
+        let settings = SessionAudioSettings {
+            keep: true,
+            retention_days: 30,
+            max_total_mb: 200,
+        };
+        assert_eq!(to_prune(&sessions, now, &settings), vec!["old", "big"]);
+
+        let unlimited = SessionAudioSettings {
+            keep: true,
+            retention_days: 0,
+            max_total_mb: 0,
+        };
+        assert!(to_prune(&sessions, now, &unlimited).is_empty());
+
+        let wav = wav::encode_pcm16_mono(16_000, &[0.0; 8_000]);
+        assert_eq!(duration_ms(&wav[..44], wav.len() as u64), 500);
+    }
+}
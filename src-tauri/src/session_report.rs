@@ -0,0 +1,281 @@
+use std::{collections::BTreeMap, fs, io::Write};
+
+use serde::{Deserialize, Serialize};
+use serde_json::Value as JsonValue;
+use tauri_specta::Event;
+
+use crate::sessions::{self, SessionInfo, TranscriptSegment};
+use crate::{export, log_line, now_millis};
+
+const RUNS_FILE: &str = "agent-runs.jsonl";
+const REPORT_FILE: &str = "report.json";
+
+/// One `run_llm_agent` call made during a session, appended to
+/// `agent-runs.jsonl` in the session folder.
+#[derive(Serialize, Deserialize, Clone, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct AgentRun {
+    pub mode: String,
+    pub finished_at: u64,
+    pub duration_ms: u64,
+    pub ok: bool,
+    pub input_tokens: u64,
+    pub output_tokens: u64,
+    #[serde(default)]
+    pub cost_usd: Option<f64>,
+    pub tool_calls: u32,
+    #[serde(default)]
+    pub artifacts: Vec<String>,
+    /// Trace of the interaction that started the run.
+    #[serde(default)]
+    pub trace_id: Option<String>,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, specta::Type)]
+#[serde(rename_all = "camelCase")]
+pub struct ModeRuns {
+    pub mode: String,
+    pub runs: u32,
+    pub failures: u32,
+}
+
+/// "What did Jamie do this episode", written to `report.json` when the
+/// session ends and sent to the main window.
+#[derive(Serialize, Deserialize, Clone, Debug, specta::Type, tauri_specta::Event)]
+#[serde(rename_all = "camelCase")]
+pub struct SessionReport {
+    pub session_id: String,
+    pub title: Option<String>,
+    pub duration_ms: u64,
+    pub words_transcribed: u32,
+    pub agent_runs: Vec<ModeRuns>,
+    pub input_tokens: u64,
+    pub output_tokens: u64,
+    /// Only set when the provider reported a cost for at least one run.
+    pub cost_usd: Option<f64>,
+    pub tool_calls: u32,
+    /// Diagram and page URLs the agent ended on, plus exported files.
+    pub artifacts: Vec<String>,
+}
+
+fn is_http_url(value: &str) -> bool {
+    value.starts_with("http://") || value.starts_with("https://")
+}
+
+/// Summarize one agent result. The general agent returns `{text}` without
+/// an `ok` flag, so a text reply without an error counts as success.
+pub fn run_from_output(mode: &str, duration_ms: u64, stdout: &str) -> AgentRun {
+    let output = serde_json::from_str::<JsonValue>(stdout).unwrap_or(JsonValue::Null);
+    let ok = output.get("ok").and_then(|v| v.as_bool()).unwrap_or_else(|| {
+        output.get("error").is_none() && output.get("text").and_then(|v| v.as_str()).is_some()
+    });
+    let usage = output.get("usage");
+    let usage_u64 = |key: &str| usage.and_then(|u| u.get(key)).and_then(|v| v.as_u64()).unwrap_or(0);
+    let artifacts = ["diagramUrl", "endUrl"]
+        .iter()
+        .filter_map(|key| output.get(*key).and_then(|v| v.as_str()))
+        .filter(|url| is_http_url(url))
+        .map(str::to_string)
+        .collect();
+    AgentRun {
+        mode: mode.to_string(),
+        finished_at: now_millis(),
+        duration_ms,
+        ok,
+        input_tokens: usage_u64("inputTokens"),
+        output_tokens: usage_u64("outputTokens"),
+        cost_usd: usage.and_then(|u| u.get("costUsd")).and_then(|v| v.as_f64()),
+        tool_calls: output
+            .get("toolCalls")
+            .and_then(|v| v.as_array())
+            .map(|calls| calls.len() as u32)
+            .unwrap_or(0),
+        artifacts: if ok { artifacts } else { Vec::new() },
+        trace_id: None,
+    }
+}
+
+/// Append an agent run to the session it was started in.
+pub fn record_agent_run(app: &tauri::AppHandle, session_id: &str, run: &AgentRun) {
+    let result = sessions::session_dir(app, session_id).and_then(|dir| {
+        let line = serde_json::to_string(run).map_err(|err| format!("failed to format run: {}", err))?;
+        let mut file = fs::OpenOptions::new()
+            .create(true)
+            .append(true)
+            .open(dir.join(RUNS_FILE))
+            .map_err(|err| format!("failed to open agent runs: {}", err))?;
+        writeln!(file, "{}", line).map_err(|err| format!("failed to append agent run: {}", err))
+    });
+    if let Err(err) = result {
+        log_line(&format!("[session-report] {}", err));
+    }
+}
+
+pub fn read_runs(app: &tauri::AppHandle, session_id: &str) -> Vec<AgentRun> {
+    sessions::session_dir(app, session_id)
+        .ok()
+        .and_then(|dir| fs::read_to_string(dir.join(RUNS_FILE)).ok())
+        .map(|content| {
+            content
+                .lines()
+                .filter_map(|line| serde_json::from_str(line).ok())
+                .collect()
+        })
+        .unwrap_or_default()
+}
+
+fn read_exports(app: &tauri::AppHandle, session_id: &str) -> Vec<String> {
+    let Ok(dir) = sessions::session_dir(app, session_id) else {
+        return Vec::new();
+    };
+    let Ok(entries) = fs::read_dir(dir.join("exports")) else {
+        return Vec::new();
+    };
+    let mut files: Vec<String> = entries
+        .filter_map(|entry| entry.ok())
+        .map(|entry| entry.path().display().to_string())
+        .collect();
+    files.sort();
+    files
+}
+
+pub fn build_report(
+    info: &SessionInfo,
+    transcript: &[TranscriptSegment],
+    runs: &[AgentRun],
+    exports: &[String],
+) -> SessionReport {
+    let mut modes: BTreeMap<&str, ModeRuns> = BTreeMap::new();
+    for run in runs {
+        let entry = modes.entry(run.mode.as_str()).or_insert_with(|| ModeRuns {
+            mode: run.mode.clone(),
+            runs: 0,
+            failures: 0,
+        });
+        entry.runs += 1;
+        if !run.ok {
+            entry.failures += 1;
+        }
+    }
+    let mut artifacts: Vec<String> = Vec::new();
+    for artifact in runs.iter().flat_map(|run| run.artifacts.iter()).chain(exports) {
+        if !artifacts.contains(artifact) {
+            artifacts.push(artifact.clone());
+        }
+    }
+    let costs: Vec<f64> = runs.iter().filter_map(|run| run.cost_usd).collect();
+    SessionReport {
+        session_id: info.id.clone(),
+        title: info.title.clone(),
+        duration_ms: info
+            .ended_at
+            .unwrap_or_else(now_millis)
+            .saturating_sub(info.started_at),
+        words_transcribed: transcript
+            .iter()
+            .map(|segment| segment.text.split_whitespace().count() as u32)
+            .sum(),
+        agent_runs: modes.into_values().collect(),
+        input_tokens: runs.iter().map(|run| run.input_tokens).sum(),
+        output_tokens: runs.iter().map(|run| run.output_tokens).sum(),
+        cost_usd: (!costs.is_empty()).then(|| costs.iter().sum()),
+        tool_calls: runs.iter().map(|run| run.tool_calls).sum(),
+        artifacts,
+    }
+}
+
+fn session_report(app: &tauri::AppHandle, info: &SessionInfo) -> Result<SessionReport, String> {
+    Ok(build_report(
+        info,
+        &sessions::read_transcript(app, &info.id)?,
+        &read_runs(app, &info.id),
+        &read_exports(app, &info.id),
+    ))
+}
+
+/// Build, save and announce the report for a session that just ended.
+pub fn finalize(app: &tauri::AppHandle, info: &SessionInfo) {
+    let report = match session_report(app, info) {
+        Ok(report) => report,
+        Err(err) => {
+            log_line(&format!("[session-report] failed to build report: {}", err));
+            return;
+        }
+    };
+    let written = sessions::session_dir(app, &info.id).and_then(|dir| {
+        let content = serde_json::to_string_pretty(&report)
+            .map_err(|err| format!("failed to format report: {}", err))?;
+        fs::write(dir.join(REPORT_FILE), content.as_bytes())
+            .map_err(|err| format!("failed to write report: {}", err))
+    });
+    if let Err(err) = written {
+        log_line(&format!("[session-report] {}", err));
+    }
+    log_line(&format!(
+        "[session-report] {}: {} words, {} agent runs, {} tool calls",
+        info.id,
+        report.words_transcribed,
+        report.agent_runs.iter().map(|m| m.runs).sum::<u32>(),
+        report.tool_calls
+    ));
+    if let Err(err) = report.emit_to(app, "main") {
+        log_line(&format!("[session-report] failed to notify main window: {}", err));
+    }
+}
+
+/// The saved report for `session_id`, or a live one if it hasn't ended.
+#[tauri::command]
+#[specta::specta]
+pub fn get_session_report(app: tauri::AppHandle, session_id: String) -> Result<SessionReport, String> {
+    let saved = sessions::session_dir(&app, &session_id)?.join(REPORT_FILE);
+    if let Some(report) = fs::read_to_string(saved)
+        .ok()
+        .and_then(|content| serde_json::from_str(&content).ok())
+    {
+        return Ok(report);
+    }
+    session_report(&app, &export::load_session(&app, &session_id)?)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{build_report, run_from_output, ModeRuns};
+    use crate::sessions::{SessionInfo, TranscriptSegment};
+
+    #[test]
+    fn totals_runs_by_mode() {
+        let info = SessionInfo {
+            id: "session-1".to_string(),
+            title: None,
+            started_at: 1_000,
+            ended_at: Some(61_000),
+            segment_count: 2,
+        };
+        let transcript = vec![
+            TranscriptSegment { index: 0, offset_ms: 0, text: "hello there".to_string(), source: None },
+            TranscriptSegment { index: 1, offset_ms: 5_000, text: "draw the pipeline".to_string(), source: None },
+        ];
+        let runs = vec![
+            run_from_output(
+                "excalidraw-act",
+                9_000,
+                r#"{"ok":true,"diagramUrl":"http://localhost:3000","toolCalls":[{},{}],"usage":{"inputTokens":100,"outputTokens":20,"costUsd":0.01}}"#,
+            ),
+            run_from_output("excalidraw-act", 3_000, r#"{"ok":false,"error":"timed out","usage":{"inputTokens":50,"outputTokens":0,"costUsd":null}}"#),
+            run_from_output("general", 1_000, r#"{"text":"done","toolCalls":[]}"#),
+        ];
+        let report = build_report(&info, &transcript, &runs, &["/tmp/show-notes.md".to_string()]);
+        assert_eq!(report.duration_ms, 60_000);
+        assert_eq!(report.words_transcribed, 5);
+        assert_eq!(
+            report.agent_runs,
+            vec![
+                ModeRuns { mode: "excalidraw-act".to_string(), runs: 2, failures: 1 },
+                ModeRuns { mode: "general".to_string(), runs: 1, failures: 0 },
+            ]
+        );
+        assert_eq!((report.input_tokens, report.output_tokens, report.tool_calls), (150, 20, 2));
+        assert_eq!(report.cost_usd, Some(0.01));
+        assert_eq!(report.artifacts, vec!["http://localhost:3000", "/tmp/show-notes.md"]);
+    }
+}
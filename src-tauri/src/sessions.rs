@@ -0,0 +1,410 @@
+use std::{
+    fs,
+    io::Write,
+    path::{Path, PathBuf},
+    sync::Mutex,
+};
+
+use serde::{Deserialize, Serialize};
+use tauri::Manager;
+
+use crate::preflight::{self, PreflightOperation};
+use crate::transcript_sync::{self, SyncOpKind};
+use crate::{agenda, captions, dictation, email_digest, guest_mode, history, idle_pause, log_line, now_millis, post_session, scripting, session_audio, session_report, timecode, voice_triggers};
+
+/// Metadata for one recording session, stored as
+/// `<app data>/sessions/<id>/session.json`.
+#[derive(Serialize, Deserialize, Clone, specta::Type)]
+#[serde(rename_all = "camelCase")]
+pub struct SessionInfo {
+    pub id: String,
+    #[serde(default)]
+    pub title: Option<String>,
+    pub started_at: u64,
+    #[serde(default)]
+    pub ended_at: Option<u64>,
+    #[serde(default)]
+    pub segment_count: u32,
+}
+
+/// One transcribed chunk, appended to `transcript.jsonl`. `offset_ms` is
+/// relative to the session start so it lines up with recordings.
+#[derive(Serialize, Deserialize, Clone, specta::Type)]
+#[serde(rename_all = "camelCase")]
+pub struct TranscriptSegment {
+    pub index: u32,
+    pub offset_ms: u64,
+    pub text: String,
+    /// Input track the chunk was captured from when recording several
+    /// microphones, e.g. "Host" or "Guest".
+    #[serde(default)]
+    pub source: Option<String>,
+}
+
+impl TranscriptSegment {
+    /// The text prefixed with its track name, for exports and captions.
+    pub fn attributed_text(&self) -> String {
+        match &self.source {
+            Some(source) => format!("{}: {}", source, self.text),
+            None => self.text.clone(),
+        }
+    }
+}
+
+struct ActiveSession {
+    info: SessionInfo,
+    /// Index of the next segment. Unlike `segment_count` it never goes
+    /// down, so a deleted segment's index isn't reused.
+    next_index: u32,
+    last_segment: Option<TranscriptSegment>,
+    browser_url: Option<String>,
+}
+
+/// Where the active session currently is, captured by bookmarks and other
+/// features that reference "this moment".
+#[derive(Clone)]
+pub struct SessionMoment {
+    pub session_id: String,
+    pub offset_ms: u64,
+    pub last_segment: Option<TranscriptSegment>,
+    pub browser_url: Option<String>,
+}
+
+#[derive(Default)]
+pub struct SessionState {
+    active: Mutex<Option<ActiveSession>>,
+}
+
+impl SessionState {
+    pub fn active_id(&self) -> Option<String> {
+        self.active
+            .lock()
+            .unwrap()
+            .as_ref()
+            .map(|session| session.info.id.clone())
+    }
+
+    pub fn active_title(&self) -> Option<String> {
+        self.active
+            .lock()
+            .unwrap()
+            .as_ref()
+            .and_then(|session| session.info.title.clone())
+    }
+
+    pub fn current_moment(&self) -> Option<SessionMoment> {
+        let active = self.active.lock().unwrap();
+        active.as_ref().map(|session| SessionMoment {
+            session_id: session.info.id.clone(),
+            offset_ms: now_millis().saturating_sub(session.info.started_at),
+            last_segment: session.last_segment.clone(),
+            browser_url: session.browser_url.clone(),
+        })
+    }
+}
+
+/// Root folder holding one subdirectory per session. Guest mode gets its
+/// own, so the owner's history is out of reach and guest sessions are easy
+/// to erase.
+pub fn sessions_dir(app: &tauri::AppHandle) -> Result<PathBuf, String> {
+    let root = if guest_mode::is_active() {
+        guest_mode::guest_dir(app)?
+    } else {
+        app.path()
+            .app_data_dir()
+            .map_err(|err| format!("failed to resolve app data dir: {}", err))?
+    };
+    let dir = root.join("sessions");
+    fs::create_dir_all(&dir).map_err(|err| format!("failed to create sessions dir: {}", err))?;
+    Ok(dir)
+}
+
+/// Directory for `session_id`. Ids come from the frontend, so anything that
+/// could escape the sessions folder is rejected.
+pub fn session_dir(app: &tauri::AppHandle, session_id: &str) -> Result<PathBuf, String> {
+    let valid = !session_id.is_empty()
+        && session_id
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || c == '-' || c == '_');
+    if !valid {
+        return Err(format!("invalid session id: {}", session_id));
+    }
+    Ok(sessions_dir(app)?.join(session_id))
+}
+
+fn write_info(dir: &Path, info: &SessionInfo) -> Result<(), String> {
+    fs::create_dir_all(dir).map_err(|err| format!("failed to create session dir: {}", err))?;
+    let content = serde_json::to_string_pretty(info)
+        .map_err(|err| format!("failed to format session: {}", err))?;
+    fs::write(dir.join("session.json"), content.as_bytes())
+        .map_err(|err| format!("failed to write session: {}", err))
+}
+
+fn read_info(dir: &Path) -> Option<SessionInfo> {
+    fs::read_to_string(dir.join("session.json"))
+        .ok()
+        .and_then(|content| serde_json::from_str(&content).ok())
+}
+
+/// Every transcript segment recorded for `session_id`, in order.
+pub fn read_transcript(app: &tauri::AppHandle, session_id: &str) -> Result<Vec<TranscriptSegment>, String> {
+    let path = session_dir(app, session_id)?.join("transcript.jsonl");
+    let content = match fs::read_to_string(&path) {
+        Ok(content) => content,
+        Err(_) => return Ok(Vec::new()),
+    };
+    Ok(content
+        .lines()
+        .filter_map(|line| serde_json::from_str(line).ok())
+        .collect())
+}
+
+/// Start a new session, or return the one already running so repeated
+/// start/stop of the microphone stays within the same session.
+#[tauri::command]
+#[specta::specta]
+pub fn start_session(
+    app: tauri::AppHandle,
+    state: tauri::State<'_, SessionState>,
+    title: Option<String>,
+) -> Result<SessionInfo, String> {
+    let mut active = state.active.lock().unwrap();
+    if let Some(session) = active.as_ref() {
+        return Ok(session.info.clone());
+    }
+    preflight::ensure(&app, PreflightOperation::Recording, &sessions_dir(&app)?)?;
+    let started_at = now_millis();
+    let info = SessionInfo {
+        id: format!("session-{}", started_at),
+        title: title.filter(|t| !t.trim().is_empty()),
+        started_at,
+        ended_at: None,
+        segment_count: 0,
+    };
+    write_info(&session_dir(&app, &info.id)?, &info)?;
+    log_line(&format!("[sessions] started {}", info.id));
+    history::record_session(&app, &info);
+    timecode::auto_sync(&app, &info);
+    transcript_sync::reset(&app, &info.id);
+    agenda::attach_to_session(&app, &info.id);
+    *active = Some(ActiveSession {
+        info: info.clone(),
+        next_index: 0,
+        last_segment: None,
+        browser_url: None,
+    });
+    Ok(info)
+}
+
+#[tauri::command]
+#[specta::specta]
+pub fn end_session(
+    app: tauri::AppHandle,
+    state: tauri::State<'_, SessionState>,
+) -> Result<Option<SessionInfo>, String> {
+    let Some(mut session) = state.active.lock().unwrap().take() else {
+        return Ok(None);
+    };
+    session.info.ended_at = Some(now_millis());
+    write_info(&session_dir(&app, &session.info.id)?, &session.info)?;
+    log_line(&format!(
+        "[sessions] ended {} ({} segments)",
+        session.info.id, session.info.segment_count
+    ));
+    history::record_session(&app, &session.info);
+    session_report::finalize(&app, &session.info);
+    email_digest::send_on_session_end(&app, &session.info);
+    post_session::run_on_session_end(&app, &session.info);
+    session_audio::spawn_prune(&app);
+    Ok(Some(session.info))
+}
+
+/// End the active session when the app quits: the session is closed and
+/// its report finalized. The digest email and post-session pipeline are
+/// skipped; `run_post_session_pipeline` can run them later.
+pub fn close_on_exit(app: &tauri::AppHandle) {
+    let Some(mut session) = app.state::<SessionState>().active.lock().unwrap().take() else {
+        return;
+    };
+    session.info.ended_at = Some(now_millis());
+    if let Err(err) = session_dir(app, &session.info.id).and_then(|dir| write_info(&dir, &session.info)) {
+        log_line(&format!("[sessions] failed to close {}: {}", session.info.id, err));
+    }
+    log_line(&format!(
+        "[sessions] closed {} on quit ({} segments)",
+        session.info.id, session.info.segment_count
+    ));
+    history::record_session(app, &session.info);
+    session_report::finalize(app, &session.info);
+}
+
+#[tauri::command]
+#[specta::specta]
+pub fn get_active_session(state: tauri::State<'_, SessionState>) -> Option<SessionInfo> {
+    state
+        .active
+        .lock()
+        .unwrap()
+        .as_ref()
+        .map(|session| session.info.clone())
+}
+
+/// All stored sessions, newest first.
+#[tauri::command]
+#[specta::specta]
+pub fn list_sessions(app: tauri::AppHandle) -> Result<Vec<SessionInfo>, String> {
+    let entries = fs::read_dir(sessions_dir(&app)?)
+        .map_err(|err| format!("failed to read sessions dir: {}", err))?;
+    let mut sessions: Vec<SessionInfo> = entries
+        .filter_map(|entry| entry.ok())
+        .filter_map(|entry| read_info(&entry.path()))
+        .collect();
+    sessions.sort_by_key(|session| std::cmp::Reverse(session.started_at));
+    Ok(sessions)
+}
+
+#[tauri::command]
+#[specta::specta]
+pub fn get_session_transcript(
+    app: tauri::AppHandle,
+    session_id: String,
+) -> Result<Vec<TranscriptSegment>, String> {
+    read_transcript(&app, &session_id)
+}
+
+/// Append a transcript chunk to the active session and run voice triggers
+/// on it. `source` names the input track in multi-track recordings. Returns
+/// `None` when no session is running.
+#[tauri::command]
+#[specta::specta]
+pub fn record_transcript_segment(
+    app: tauri::AppHandle,
+    state: tauri::State<'_, SessionState>,
+    text: String,
+    source: Option<String>,
+) -> Result<Option<TranscriptSegment>, String> {
+    let text = text.trim().to_string();
+    if text.is_empty() {
+        return Ok(None);
+    }
+    dictation::type_segment(&app, &text);
+    let (session_id, segment) = {
+        let mut active = state.active.lock().unwrap();
+        let Some(session) = active.as_mut() else {
+            return Ok(None);
+        };
+        let segment = TranscriptSegment {
+            index: session.next_index,
+            offset_ms: now_millis().saturating_sub(session.info.started_at),
+            text,
+            source: source.filter(|s| !s.trim().is_empty()),
+        };
+        let dir = session_dir(&app, &session.info.id)?;
+        let line = serde_json::to_string(&segment)
+            .map_err(|err| format!("failed to format segment: {}", err))?;
+        let mut file = fs::OpenOptions::new()
+            .create(true)
+            .append(true)
+            .open(dir.join("transcript.jsonl"))
+            .map_err(|err| format!("failed to open transcript: {}", err))?;
+        writeln!(file, "{}", line).map_err(|err| format!("failed to append transcript: {}", err))?;
+        session.next_index += 1;
+        session.info.segment_count += 1;
+        let previous_end_ms = session.last_segment.replace(segment.clone()).map(|s| s.offset_ms);
+        write_info(&dir, &session.info)?;
+        captions::append_live(&app, &session.info.id, &segment, previous_end_ms);
+        history::record_segment(&app, &session.info.id, &segment);
+        transcript_sync::record(&app, SyncOpKind::Add, segment.index, Some(segment.clone()));
+        (session.info.id.clone(), segment)
+    };
+    idle_pause::note_activity(&app);
+    voice_triggers::dispatch(&app, &segment);
+    if let Ok(mut payload) = serde_json::to_value(&segment) {
+        payload["sessionId"] = session_id.into();
+        scripting::fire(&app, scripting::ON_SEGMENT, payload);
+    }
+    Ok(Some(segment))
+}
+
+/// Rewrite `transcript.jsonl` of the active session with `edit` applied,
+/// and bring the segment count, live captions and history along.
+fn edit_transcript(
+    app: &tauri::AppHandle,
+    state: &SessionState,
+    index: u32,
+    edit: impl FnOnce(&mut Vec<TranscriptSegment>, usize),
+) -> Result<Option<TranscriptSegment>, String> {
+    let mut active = state.active.lock().unwrap();
+    let session = active.as_mut().ok_or_else(|| "no active session".to_string())?;
+    let mut segments = read_transcript(app, &session.info.id)?;
+    let position = segments
+        .iter()
+        .position(|segment| segment.index == index)
+        .ok_or_else(|| format!("no transcript segment {}", index))?;
+    edit(&mut segments, position);
+    let mut content = String::new();
+    for segment in &segments {
+        let line = serde_json::to_string(segment).map_err(|err| format!("failed to format segment: {}", err))?;
+        content.push_str(&line);
+        content.push('\n');
+    }
+    let dir = session_dir(app, &session.info.id)?;
+    fs::write(dir.join("transcript.jsonl"), content.as_bytes())
+        .map_err(|err| format!("failed to write transcript: {}", err))?;
+    session.info.segment_count = segments.len() as u32;
+    write_info(&dir, &session.info)?;
+    session.last_segment = segments.last().cloned();
+    captions::rewrite_live(app, &session.info.id, &segments);
+    let edited = segments.into_iter().find(|segment| segment.index == index);
+    match &edited {
+        Some(segment) => history::record_segment(app, &session.info.id, segment),
+        None => history::delete_segment(app, &session.info.id, index),
+    }
+    Ok(edited)
+}
+
+/// Replace the text of a segment of the active session, e.g. with a
+/// correction, and pass the revision on to synced consumers. The text is
+/// cleaned and redacted like a transcribed segment.
+#[tauri::command]
+#[specta::specta]
+pub fn revise_transcript_segment(
+    app: tauri::AppHandle,
+    state: tauri::State<'_, SessionState>,
+    index: u32,
+    text: String,
+) -> Result<TranscriptSegment, String> {
+    let text = crate::clean_transcript_fragment(&text)
+        .ok_or_else(|| "a revised segment needs text; delete it instead".to_string())?;
+    let segment = edit_transcript(&app, &state, index, |segments, position| {
+        segments[position].text = text;
+    })?
+    .ok_or_else(|| format!("no transcript segment {}", index))?;
+    transcript_sync::record(&app, SyncOpKind::Revise, index, Some(segment.clone()));
+    Ok(segment)
+}
+
+/// Drop a segment from the active session, e.g. a hallucinated line.
+/// Later segments keep their index.
+#[tauri::command]
+#[specta::specta]
+pub fn delete_transcript_segment(
+    app: tauri::AppHandle,
+    state: tauri::State<'_, SessionState>,
+    index: u32,
+) -> Result<(), String> {
+    edit_transcript(&app, &state, index, |segments, position| {
+        segments.remove(position);
+    })?;
+    transcript_sync::record(&app, SyncOpKind::Delete, index, None);
+    Ok(())
+}
+
+/// Remember the page the browser is showing so bookmarks can link back to it.
+#[tauri::command]
+#[specta::specta]
+pub fn note_session_browser_url(state: tauri::State<'_, SessionState>, url: String) {
+    if let Some(session) = state.active.lock().unwrap().as_mut() {
+        session.browser_url = Some(url).filter(|u| !u.trim().is_empty());
+    }
+}
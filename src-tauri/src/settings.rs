@@ -0,0 +1,1057 @@
+use std::{collections::BTreeMap, fs, path::PathBuf};
+
+use serde::{Deserialize, Serialize};
+use tauri::Manager;
+
+use crate::acceleration::WhisperAcceleration;
+use crate::captions::CaptionFormat;
+use crate::loopback::CaptureSource;
+use crate::webhooks::WebhookTrigger;
+use crate::{guest_mode, log_line};
+
+/// Backend settings persisted as `settings.json` next to `mcp.json` in the
+/// app config dir. Frontend-only preferences still live in `localStorage`;
+/// this store is for values the Rust layer needs without a webview round trip.
+#[derive(Serialize, Deserialize, Clone, Default, specta::Type)]
+#[serde(rename_all = "camelCase", default)]
+pub struct AppSettings {
+    pub transcription: TranscriptionSettings,
+    pub llm: LlmSettings,
+    pub auth: AuthSettings,
+    pub email: EmailSettings,
+    pub captions: CaptionSettings,
+    pub language: LanguageSettings,
+    pub docs: DocsCacheSettings,
+    pub ui: UiSettings,
+    pub power: PowerSettings,
+    pub load: LoadSettings,
+    pub timecode: TimecodeSettings,
+    pub post_session: PostSessionSettings,
+    pub session_audio: SessionAudioSettings,
+    pub prompts: PromptSettings,
+    pub clipboard: ClipboardSettings,
+    pub meeting: MeetingSettings,
+    pub fetch: FetchSettings,
+    pub agent_modes: AgentModeSettings,
+    pub push_to_talk: PushToTalkSettings,
+    pub webhooks: WebhookSettings,
+    pub sandbox: SandboxSettings,
+    pub idle_pause: IdlePauseSettings,
+    pub scripting: ScriptingSettings,
+    pub redaction: RedactionSettings,
+    pub dictation: DictationSettings,
+}
+
+#[derive(Serialize, Deserialize, Clone, Default, specta::Type)]
+#[serde(rename_all = "camelCase", default)]
+pub struct TranscriptionSettings {
+    /// External whisper server tried before the local `whisper-cli`.
+    pub remote_server: Option<RemoteWhisperServer>,
+    /// Additional servers tried in order when `remote_server` is down.
+    pub fallback_servers: Vec<RemoteWhisperServer>,
+    /// Microphones captured side by side, e.g. a host mic and a guest
+    /// channel. Fewer than two means the default input only.
+    pub input_tracks: Vec<InputTrack>,
+    /// Downloaded model file to transcribe with, e.g. `ggml-small.en.bin`.
+    /// Empty uses the model installed by `scripts/setup-whisper.sh`.
+    pub whisper_model: String,
+    /// Spoken language passed to whisper (`es`, `de`, ...) or `auto` to
+    /// detect it. Empty means English. Needs a multilingual model.
+    pub language: String,
+    /// Capture the microphone in the webview and send each segment over
+    /// IPC instead of recording natively. Multi-track input always does.
+    pub webview_capture: bool,
+    /// Microphone, system audio or both. System audio is only captured
+    /// natively, so it overrides `webview_capture` and input tracks.
+    pub capture_source: CaptureSource,
+    /// Tinydiarize model file for `transcribe_audio_diarized`. Empty means
+    /// `ggml-small.en-tdrz.bin`.
+    pub diarization_model: String,
+    /// Text whisper is primed with, e.g. "A podcast about Tauri apps."
+    /// Its spelling and style carry over into the transcript.
+    pub initial_prompt: String,
+    /// Names and terms to spell correctly ("Excalidraw", guest names),
+    /// appended to the initial prompt.
+    pub vocabulary: Vec<String>,
+    /// GPU use by local whisper.
+    pub acceleration: WhisperAcceleration,
+    /// Start `whisper-cli` for every segment instead of keeping a warm
+    /// `whisper-server` worker with the model loaded.
+    pub cold_start: bool,
+    /// Load the selected model file as is, even when a quantized variant
+    /// of it sits next to it.
+    pub exact_model: bool,
+    /// Default decoding parameters; `transcribe_audio` can override any
+    /// of them per call.
+    pub options: TranscriptionOptions,
+    /// Filters applied to captured audio before whisper.
+    pub preprocessing: PreprocessingSettings,
+    /// Windowing of long recordings (see `long_form`).
+    pub long_form: LongFormSettings,
+    /// Only process the microphone while certain apps are frontmost.
+    pub focus_gate: FocusGateSettings,
+    /// Microphone to record from, by device name; empty follows the OS
+    /// default. Set with `set_audio_device`.
+    pub input_device: String,
+    /// Where segments are transcribed (see `transcription_backend`).
+    pub backend: TranscriptionBackendKind,
+    pub cloud: CloudTranscriptionSettings,
+    /// How `options.punctuate` restores punctuation.
+    pub punctuation: PunctuationSettings,
+    /// Segments and files transcribed at once (see `transcription_queue`);
+    /// 0 means 1.
+    pub max_concurrent: u32,
+}
+
+/// Punctuation restoring (`punctuation.rs`).
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, specta::Type)]
+#[serde(rename_all = "camelCase", default)]
+pub struct PunctuationSettings {
+    /// Program and arguments of a local punctuation model that reads text
+    /// on stdin and prints it punctuated. Empty uses the built-in rules.
+    pub model_command: Vec<String>,
+    pub timeout_ms: u64,
+}
+
+impl Default for PunctuationSettings {
+    fn default() -> Self {
+        Self {
+            model_command: Vec::new(),
+            timeout_ms: 5_000,
+        }
+    }
+}
+
+/// Speech-to-text service `transcribe_audio` and live capture use.
+#[derive(Serialize, Deserialize, Clone, Copy, PartialEq, Eq, Debug, Default, specta::Type)]
+#[serde(rename_all = "kebab-case")]
+pub enum TranscriptionBackendKind {
+    /// whisper.cpp on this machine, after any remote whisper servers.
+    #[default]
+    Local,
+    /// OpenAI's audio API.
+    Openai,
+    /// Deepgram's speech-to-text API.
+    Deepgram,
+}
+
+/// Cloud speech-to-text. API keys live in the keychain
+/// (`set_transcription_api_key`).
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, specta::Type)]
+#[serde(rename_all = "camelCase", default)]
+pub struct CloudTranscriptionSettings {
+    /// Empty means `whisper-1`.
+    pub openai_model: String,
+    /// Empty means `nova-2`.
+    pub deepgram_model: String,
+    pub timeout_ms: u64,
+    /// Transcribe locally when the cloud request fails.
+    pub fallback_to_local: bool,
+}
+
+impl Default for CloudTranscriptionSettings {
+    fn default() -> Self {
+        Self {
+            openai_model: String::new(),
+            deepgram_model: String::new(),
+            timeout_ms: 30_000,
+            fallback_to_local: true,
+        }
+    }
+}
+
+/// Mic gating by application focus (see `focus_gate`).
+#[derive(Serialize, Deserialize, Clone, Default, specta::Type)]
+#[serde(rename_all = "camelCase", default)]
+pub struct FocusGateSettings {
+    pub enabled: bool,
+    /// Substrings of app names that keep the microphone on, e.g.
+    /// "Riverside" or "Chrome". HeyJamie itself always does.
+    pub apps: Vec<String>,
+}
+
+/// Long recordings are transcribed in overlapping windows whose
+/// transcripts are stitched at the shared words.
+#[derive(Serialize, Deserialize, Clone, Copy, Debug, PartialEq, specta::Type)]
+#[serde(rename_all = "camelCase", default)]
+pub struct LongFormSettings {
+    pub window_seconds: u32,
+    /// Audio each window shares with the next one.
+    pub overlap_seconds: u32,
+}
+
+impl Default for LongFormSettings {
+    fn default() -> Self {
+        Self {
+            window_seconds: 60,
+            overlap_seconds: 5,
+        }
+    }
+}
+
+impl LongFormSettings {
+    pub fn validate(&self) -> Result<(), String> {
+        if !(10..=600).contains(&self.window_seconds) {
+            return Err(format!("windowSeconds must be between 10 and 600, got {}", self.window_seconds));
+        }
+        if self.overlap_seconds == 0 || self.overlap_seconds * 2 > self.window_seconds {
+            return Err(format!(
+                "overlapSeconds must be between 1 and half the window, got {}",
+                self.overlap_seconds
+            ));
+        }
+        Ok(())
+    }
+}
+
+/// Audio clean-up before transcription (see `preprocess`).
+#[derive(Serialize, Deserialize, Clone, Default, specta::Type)]
+#[serde(rename_all = "camelCase", default)]
+pub struct PreprocessingSettings {
+    /// For inputs without their own entry.
+    pub default: AudioFilters,
+    pub inputs: Vec<InputFilters>,
+}
+
+/// Filters for one input: an input track name, or a device name when
+/// recording natively.
+#[derive(Serialize, Deserialize, Clone, Default, specta::Type)]
+#[serde(rename_all = "camelCase", default)]
+pub struct InputFilters {
+    pub input: String,
+    pub filters: AudioFilters,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, specta::Type)]
+#[serde(rename_all = "camelCase", default)]
+pub struct AudioFilters {
+    pub enabled: bool,
+    /// Cutoff of the rumble filter; unset turns it off.
+    pub high_pass_hz: Option<f32>,
+    /// Pull steady background noise down between words.
+    pub noise_suppression: bool,
+    /// Raise quiet speech to a consistent level.
+    pub normalize: bool,
+}
+
+impl Default for AudioFilters {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            high_pass_hz: Some(100.0),
+            noise_suppression: true,
+            normalize: true,
+        }
+    }
+}
+
+/// whisper decoding parameters. Unset fields keep whisper's defaults.
+#[derive(Serialize, Deserialize, Clone, Copy, Debug, Default, PartialEq, specta::Type)]
+#[serde(rename_all = "camelCase", default)]
+pub struct TranscriptionOptions {
+    /// Segments with a lower average token log probability are decoded
+    /// again at a higher temperature (whisper: -1.0).
+    pub logprob_thold: Option<f32>,
+    /// Segments more likely than this to be silence are dropped (0.6).
+    pub no_speech_thold: Option<f32>,
+    /// Beam search width; unset decodes greedily.
+    pub beam_size: Option<u32>,
+    /// Temperature of the first decode (0.0).
+    pub temperature: Option<f32>,
+    /// Step of the fallback ladder: a segment that fails the log
+    /// probability or entropy check is decoded again at `temperature`
+    /// plus this, and so on up to 1.0 (0.2). 0 turns fallback off.
+    pub temperature_inc: Option<f32>,
+    /// Segments whose token entropy is below this are taken as
+    /// repetition loops and decoded again higher up the ladder (2.4).
+    /// whisper.cpp's stand-in for the compression ratio threshold.
+    pub entropy_thold: Option<f32>,
+    /// Longest segment in characters; unset or 0 means no limit.
+    pub max_segment_len: Option<u32>,
+    /// Restore punctuation and casing afterwards (see `punctuation`).
+    pub punctuate: Option<bool>,
+}
+
+impl TranscriptionOptions {
+    /// `overrides` where they're set, else these.
+    pub fn with(self, overrides: Option<TranscriptionOptions>) -> Self {
+        let Some(overrides) = overrides else {
+            return self;
+        };
+        Self {
+            logprob_thold: overrides.logprob_thold.or(self.logprob_thold),
+            no_speech_thold: overrides.no_speech_thold.or(self.no_speech_thold),
+            beam_size: overrides.beam_size.or(self.beam_size),
+            temperature: overrides.temperature.or(self.temperature),
+            temperature_inc: overrides.temperature_inc.or(self.temperature_inc),
+            entropy_thold: overrides.entropy_thold.or(self.entropy_thold),
+            max_segment_len: overrides.max_segment_len.or(self.max_segment_len),
+            punctuate: overrides.punctuate.or(self.punctuate),
+        }
+    }
+
+    pub fn validate(&self) -> Result<(), String> {
+        let in_range = |name: &str, value: Option<f32>, min: f32, max: f32| match value {
+            Some(value) if !(min..=max).contains(&value) => {
+                Err(format!("{} must be between {} and {}, got {}", name, min, max, value))
+            }
+            _ => Ok(()),
+        };
+        in_range("logprobThold", self.logprob_thold, -5.0, 0.0)?;
+        in_range("noSpeechThold", self.no_speech_thold, 0.0, 1.0)?;
+        in_range("temperature", self.temperature, 0.0, 1.0)?;
+        in_range("temperatureInc", self.temperature_inc, 0.0, 1.0)?;
+        in_range("entropyThold", self.entropy_thold, 0.0, 10.0)?;
+        if self.beam_size.is_some_and(|size| !(1..=16).contains(&size)) {
+            return Err(format!("beamSize must be between 1 and 16, got {}", self.beam_size.unwrap_or_default()));
+        }
+        Ok(())
+    }
+}
+
+/// Roughly whisper's 224-token prompt window.
+const MAX_WHISPER_PROMPT_CHARS: usize = 800;
+
+impl TranscriptionSettings {
+    /// The prompt passed to whisper: the initial prompt followed by the
+    /// vocabulary. whisper keeps the end of an overlong prompt, so the
+    /// start is dropped here too.
+    pub fn whisper_prompt(&self) -> Option<String> {
+        let vocabulary: Vec<&str> = self
+            .vocabulary
+            .iter()
+            .map(|term| term.trim())
+            .filter(|term| !term.is_empty())
+            .collect();
+        let mut parts = Vec::new();
+        if !self.initial_prompt.trim().is_empty() {
+            parts.push(self.initial_prompt.split_whitespace().collect::<Vec<_>>().join(" "));
+        }
+        if !vocabulary.is_empty() {
+            parts.push(format!("{}.", vocabulary.join(", ")));
+        }
+        let prompt: String = parts.join(" ").chars().filter(|c| !c.is_control()).collect();
+        if prompt.chars().count() <= MAX_WHISPER_PROMPT_CHARS {
+            return (!prompt.is_empty()).then_some(prompt);
+        }
+        let skip = prompt.chars().count() - MAX_WHISPER_PROMPT_CHARS;
+        let tail: String = prompt.chars().skip(skip).collect();
+        // Don't start mid-word.
+        Some(match tail.split_once(' ') {
+            Some((_, rest)) => rest.to_string(),
+            None => tail,
+        })
+    }
+}
+
+/// One input device in a multi-track recording.
+#[derive(Serialize, Deserialize, Clone, Default, specta::Type)]
+#[serde(rename_all = "camelCase", default)]
+pub struct InputTrack {
+    /// Speaker label attached to this track's transcript segments.
+    pub name: String,
+    /// Webview `MediaDeviceInfo.deviceId`; empty means the default input.
+    pub device_id: String,
+}
+
+#[derive(Serialize, Deserialize, Clone, Copy, PartialEq, Eq, Debug, Default, specta::Type)]
+#[serde(rename_all = "kebab-case")]
+pub enum RemoteWhisperKind {
+    /// whisper.cpp `whisper-server` (`POST /inference`).
+    #[default]
+    WhisperCpp,
+    /// faster-whisper and other OpenAI-compatible servers
+    /// (`POST /v1/audio/transcriptions`).
+    OpenaiCompatible,
+}
+
+#[derive(Serialize, Deserialize, Clone, specta::Type)]
+#[serde(rename_all = "camelCase", default)]
+pub struct RemoteWhisperServer {
+    pub enabled: bool,
+    pub url: String,
+    pub kind: RemoteWhisperKind,
+    /// Model name sent to OpenAI-compatible servers (ignored by whisper.cpp).
+    pub model: Option<String>,
+    pub api_key: Option<String>,
+    pub timeout_ms: u64,
+    /// Additional attempts after the first request fails.
+    pub retries: u32,
+    /// Run the local CLI when every remote attempt fails.
+    pub fallback_to_local: bool,
+    /// Path probed by the background health check (defaults to `/`).
+    pub health_path: Option<String>,
+}
+
+impl Default for RemoteWhisperServer {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            url: String::new(),
+            kind: RemoteWhisperKind::default(),
+            model: None,
+            api_key: None,
+            timeout_ms: 30_000,
+            retries: 2,
+            fallback_to_local: true,
+            health_path: None,
+        }
+    }
+}
+
+#[derive(Serialize, Deserialize, Clone, Default, specta::Type)]
+#[serde(rename_all = "camelCase", default)]
+pub struct LlmSettings {
+    /// Ordered failover chain for the agent's chat completions provider.
+    /// Empty means the agent talks to OpenRouter directly.
+    pub endpoints: Vec<LlmEndpoint>,
+}
+
+#[derive(Serialize, Deserialize, Clone, Default, specta::Type)]
+#[serde(rename_all = "camelCase", default)]
+pub struct LlmEndpoint {
+    pub name: String,
+    /// OpenAI-compatible API base, e.g. `https://openrouter.ai/api/v1` or
+    /// `http://localhost:11434/v1` for a local Ollama.
+    pub base_url: String,
+    /// Overrides the API key from the frontend settings for this endpoint.
+    pub api_key: Option<String>,
+    /// Overrides the model from the frontend settings for this endpoint.
+    pub model: Option<String>,
+    /// Path probed by the background health check (defaults to `/models`).
+    pub health_path: Option<String>,
+}
+
+#[derive(Serialize, Deserialize, Clone, Default, specta::Type)]
+#[serde(rename_all = "camelCase", default)]
+pub struct AuthSettings {
+    /// OAuth device-flow providers. Entries with the id of a built-in
+    /// provider (`google`, `github`) override it, e.g. to set a client id.
+    pub providers: Vec<OAuthProviderConfig>,
+}
+
+#[derive(Serialize, Deserialize, Clone, Default, specta::Type)]
+#[serde(rename_all = "camelCase", default)]
+pub struct OAuthProviderConfig {
+    pub id: String,
+    pub name: String,
+    pub client_id: String,
+    /// Only for providers that require one for installed apps (Google).
+    pub client_secret: Option<String>,
+    pub device_authorization_url: String,
+    pub token_url: String,
+    pub revoke_url: Option<String>,
+    pub scopes: Vec<String>,
+}
+
+/// SMTP account for session digests. The password lives in the keychain
+/// (`set_smtp_password`), never in this file.
+#[derive(Serialize, Deserialize, Clone, specta::Type)]
+#[serde(rename_all = "camelCase", default)]
+pub struct EmailSettings {
+    pub smtp_host: String,
+    /// 465 uses implicit TLS; anything else upgrades with STARTTLS.
+    pub smtp_port: u16,
+    pub username: String,
+    /// Sender address; defaults to `username`.
+    pub from: Option<String>,
+    pub default_recipients: Vec<String>,
+    /// Mail the digest to `default_recipients` when a session ends.
+    pub send_on_session_end: bool,
+}
+
+impl Default for EmailSettings {
+    fn default() -> Self {
+        Self {
+            smtp_host: String::new(),
+            smtp_port: 587,
+            username: String::new(),
+            from: None,
+            default_recipients: Vec::new(),
+            send_on_session_end: false,
+        }
+    }
+}
+
+#[derive(Serialize, Deserialize, Clone, Default, specta::Type)]
+#[serde(rename_all = "camelCase", default)]
+pub struct CaptionSettings {
+    /// Keep a caption file for the active session up to date as segments
+    /// arrive.
+    pub live_output: bool,
+    /// A `.srt`/`.vtt` file, or a folder that gets `<session id>.<ext>`.
+    pub output_path: String,
+    /// Used when `output_path` is a folder.
+    pub format: CaptionFormat,
+}
+
+/// Which language the agent replies in. Values are `auto` (follow the
+/// speaker), `off`, or a language code such as `es`.
+#[derive(Serialize, Deserialize, Clone, specta::Type)]
+#[serde(rename_all = "camelCase", default)]
+pub struct LanguageSettings {
+    pub response_language: String,
+    /// Per-show overrides, matched against the start of the session title.
+    pub shows: Vec<ShowLanguage>,
+}
+
+impl Default for LanguageSettings {
+    fn default() -> Self {
+        Self {
+            response_language: "auto".to_string(),
+            shows: Vec::new(),
+        }
+    }
+}
+
+#[derive(Serialize, Deserialize, Clone, Default, specta::Type)]
+#[serde(rename_all = "camelCase", default)]
+pub struct ShowLanguage {
+    pub show: String,
+    pub response_language: String,
+}
+
+/// Context7 documentation cache used by the agent.
+#[derive(Serialize, Deserialize, Clone, specta::Type)]
+#[serde(rename_all = "camelCase", default)]
+pub struct DocsCacheSettings {
+    /// How long a cached lookup is served without asking the server again.
+    pub ttl_hours: u64,
+    /// Never contact the Context7 server; answer only from the cache.
+    pub offline: bool,
+}
+
+impl Default for DocsCacheSettings {
+    fn default() -> Self {
+        Self {
+            ttl_hours: 24 * 7,
+            offline: false,
+        }
+    }
+}
+
+/// Webview zoom, applied whenever a window is created.
+#[derive(Serialize, Deserialize, Clone, specta::Type)]
+#[serde(rename_all = "camelCase", default)]
+pub struct UiSettings {
+    /// Zoom for every window without its own entry in `window_scales`.
+    pub scale: f64,
+    /// Per-window overrides keyed by window label (`main`, `settings`, ...).
+    pub window_scales: BTreeMap<String, f64>,
+}
+
+impl Default for UiSettings {
+    fn default() -> Self {
+        Self {
+            scale: 1.0,
+            window_scales: BTreeMap::new(),
+        }
+    }
+}
+
+/// What changes while running on battery.
+#[derive(Serialize, Deserialize, Clone, specta::Type)]
+#[serde(rename_all = "camelCase", default)]
+pub struct PowerSettings {
+    /// Switch to the low-power profile automatically when unplugged.
+    pub enabled: bool,
+    /// Whisper model file, next to the default model, used on battery.
+    /// Falls back to the default model when it isn't installed.
+    pub battery_whisper_model: String,
+    /// Seconds of audio per transcription segment on battery. 0 keeps the
+    /// default.
+    pub battery_segment_seconds: u32,
+    /// Stop probing failover endpoints in the background on battery.
+    pub pause_health_checks: bool,
+}
+
+impl Default for PowerSettings {
+    fn default() -> Self {
+        Self {
+            enabled: true,
+            battery_whisper_model: "ggml-tiny.en.bin".to_string(),
+            battery_segment_seconds: 15,
+            pause_health_checks: true,
+        }
+    }
+}
+
+/// Transcription back-off while the machine is busy or thermally throttled.
+#[derive(Serialize, Deserialize, Clone, specta::Type)]
+#[serde(rename_all = "camelCase", default)]
+pub struct LoadSettings {
+    pub enabled: bool,
+    /// One-minute load average per core that counts as pressure.
+    pub load_per_core: f64,
+    /// Whisper model file, next to the default model, used under pressure.
+    pub whisper_model: String,
+    /// Seconds of audio per transcription segment under pressure. 0 keeps
+    /// the default.
+    pub segment_seconds: u32,
+}
+
+impl Default for LoadSettings {
+    fn default() -> Self {
+        Self {
+            enabled: true,
+            load_per_core: 0.9,
+            whisper_model: "ggml-tiny.en.bin".to_string(),
+            segment_seconds: 12,
+        }
+    }
+}
+
+/// Aligning session offsets with an external recorder's timecode.
+#[derive(Serialize, Deserialize, Clone, specta::Type)]
+#[serde(rename_all = "camelCase", default)]
+pub struct TimecodeSettings {
+    pub ntp_server: String,
+    /// Take an NTP time-of-day reference whenever a session starts.
+    pub auto_sync_ntp: bool,
+    /// Frame rate used for NTP references; LTC carries its own.
+    pub fps: u32,
+    /// Webview `deviceId` of the input carrying LTC; empty disables it.
+    pub ltc_device_id: String,
+}
+
+impl Default for TimecodeSettings {
+    fn default() -> Self {
+        Self {
+            ntp_server: "pool.ntp.org".to_string(),
+            auto_sync_ntp: false,
+            fps: 30,
+            ltc_device_id: String::new(),
+        }
+    }
+}
+
+/// Wrap-up steps run in order after a session ends. Each step is
+/// independent: a failure is recorded and the next step still runs.
+#[derive(Serialize, Deserialize, Clone, specta::Type)]
+#[serde(rename_all = "camelCase", default)]
+pub struct PostSessionSettings {
+    pub enabled: bool,
+    /// Write `transcript.txt` with timestamps and track names.
+    pub finalize_transcript: bool,
+    /// Keep each segment's audio during the session and run it through
+    /// `retranscribe_model` afterwards (`transcript-hq.txt`).
+    pub retranscribe: bool,
+    /// Model file next to the default one, e.g. `ggml-large-v3.bin`.
+    pub retranscribe_model: String,
+    pub show_notes: bool,
+    /// YouTube-style `chapters.txt` from the topic graph.
+    pub chapters: bool,
+    /// Post the notes to the Slack incoming webhook in the keychain.
+    pub deliver_to_slack: bool,
+}
+
+impl Default for PostSessionSettings {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            finalize_transcript: true,
+            retranscribe: false,
+            retranscribe_model: "ggml-large-v3.bin".to_string(),
+            show_notes: true,
+            chapters: true,
+            deliver_to_slack: false,
+        }
+    }
+}
+
+/// Keeping each session's segment audio in `<session>/audio/` to listen
+/// to or retranscribe later. The retranscribe wrap-up step keeps it too.
+#[derive(Serialize, Deserialize, Clone, specta::Type)]
+#[serde(rename_all = "camelCase", default)]
+pub struct SessionAudioSettings {
+    pub keep: bool,
+    /// Audio of sessions older than this is deleted; 0 keeps it forever.
+    pub retention_days: u32,
+    /// Oldest sessions' audio goes first past this many MB; 0 is no cap.
+    pub max_total_mb: u32,
+}
+
+impl Default for SessionAudioSettings {
+    fn default() -> Self {
+        Self {
+            keep: false,
+            retention_days: 30,
+            max_total_mb: 2048,
+        }
+    }
+}
+
+/// Running the agent, canvas server and MCP servers under `sandbox-exec`
+/// on macOS (`sandbox.rs`).
+#[derive(Serialize, Deserialize, Clone, Default, specta::Type)]
+#[serde(rename_all = "camelCase", default)]
+pub struct SandboxSettings {
+    pub enabled: bool,
+    /// Folders under home they may read and write besides the repo, app
+    /// data, node's own folders and the MCP servers' working directories.
+    pub allowed_dirs: Vec<String>,
+    pub network: SandboxNetwork,
+}
+
+#[derive(Serialize, Deserialize, Clone, Copy, PartialEq, Eq, Debug, Default, specta::Type)]
+#[serde(rename_all = "kebab-case")]
+pub enum SandboxNetwork {
+    #[default]
+    Allow,
+    /// Loopback only: local MCP servers, the canvas, a local LLM.
+    LocalOnly,
+    Deny,
+}
+
+/// Masking sensitive text in transcripts (`redaction.rs`). Off by default;
+/// once on, every built-in category is masked unless turned off.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, specta::Type)]
+#[serde(rename_all = "camelCase", default)]
+pub struct RedactionSettings {
+    pub enabled: bool,
+    pub profanity: bool,
+    pub emails: bool,
+    pub phone_numbers: bool,
+    /// 13 to 19 digit numbers, grouped or not.
+    pub card_numbers: bool,
+    /// Extra words or phrases to mask, ignoring case.
+    pub words: Vec<String>,
+    /// Extra case-insensitive regexes to mask.
+    pub patterns: Vec<String>,
+    /// What masked text is replaced with.
+    pub mask: String,
+}
+
+impl Default for RedactionSettings {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            profanity: true,
+            emails: true,
+            phone_numbers: true,
+            card_numbers: true,
+            words: Vec::new(),
+            patterns: Vec::new(),
+            mask: "[redacted]".to_string(),
+        }
+    }
+}
+
+/// User scripts in `<config>/scripts` (`scripting.rs`). Off by default,
+/// since they run with access to the session.
+#[derive(Serialize, Deserialize, Clone, Default, specta::Type)]
+#[serde(rename_all = "camelCase", default)]
+pub struct ScriptingSettings {
+    pub enabled: bool,
+}
+
+/// Pausing a session nobody is using (`idle_pause.rs`).
+#[derive(Serialize, Deserialize, Clone, Copy, Debug, PartialEq, specta::Type)]
+#[serde(rename_all = "camelCase", default)]
+pub struct IdlePauseSettings {
+    pub enabled: bool,
+    /// Minutes without transcribed speech or user input before listening
+    /// is paused.
+    pub idle_minutes: u32,
+}
+
+impl Default for IdlePauseSettings {
+    fn default() -> Self {
+        Self {
+            enabled: true,
+            idle_minutes: 20,
+        }
+    }
+}
+
+/// Prompt templates for `run_prompt_on_text`, on top of the built-in ones.
+#[derive(Serialize, Deserialize, Clone, Default, specta::Type)]
+#[serde(rename_all = "camelCase", default)]
+pub struct PromptSettings {
+    /// A saved prompt replaces a built-in one with the same name.
+    pub saved: Vec<SavedPrompt>,
+}
+
+#[derive(Serialize, Deserialize, Clone, Default, Debug, specta::Type)]
+#[serde(rename_all = "camelCase", default)]
+pub struct SavedPrompt {
+    pub name: String,
+    /// `{{text}}` is replaced with the text; without it the text is
+    /// appended.
+    pub template: String,
+}
+
+/// Opt-in clipboard watcher that offers actions for copied content.
+#[derive(Serialize, Deserialize, Clone, specta::Type)]
+#[serde(rename_all = "camelCase", default)]
+pub struct ClipboardSettings {
+    pub enabled: bool,
+    /// Suggest opening or summarizing copied links.
+    pub urls: bool,
+    /// Suggest summarizing or drawing copied text of at least
+    /// `min_text_chars`.
+    pub text: bool,
+    pub min_text_chars: u32,
+}
+
+impl Default for ClipboardSettings {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            urls: true,
+            text: true,
+            min_text_chars: 400,
+        }
+    }
+}
+
+/// Noticing when a conferencing app takes the microphone.
+#[derive(Serialize, Deserialize, Clone, Default, specta::Type)]
+#[serde(rename_all = "camelCase", default)]
+pub struct MeetingSettings {
+    pub enabled: bool,
+    /// Start listening as soon as a meeting is detected instead of asking.
+    pub auto_start: bool,
+    /// Persona switched to on auto-start; empty keeps the current one.
+    pub persona_id: String,
+}
+
+/// Politeness toward the sites research fetches from, so a busy session
+/// doesn't get the user's IP blocked.
+#[derive(Serialize, Deserialize, Clone, specta::Type)]
+#[serde(rename_all = "camelCase", default)]
+pub struct FetchSettings {
+    /// Minimum gap between requests to one domain; a longer robots.txt
+    /// `Crawl-delay` wins.
+    pub min_interval_ms: u32,
+    /// Requests to one domain in flight at once.
+    pub max_concurrent_per_domain: u32,
+    /// Skip pages robots.txt disallows for automated fetches.
+    pub respect_robots: bool,
+}
+
+impl Default for FetchSettings {
+    fn default() -> Self {
+        Self {
+            min_interval_ms: 1000,
+            max_concurrent_per_domain: 2,
+            respect_robots: true,
+        }
+    }
+}
+
+/// A global shortcut that listens while it's held.
+#[derive(Serialize, Deserialize, Clone, specta::Type)]
+#[serde(rename_all = "camelCase", default)]
+pub struct PushToTalkSettings {
+    pub enabled: bool,
+    /// Accelerator string, e.g. `CmdOrCtrl+Shift+Space` or `F13`.
+    pub shortcut: String,
+    /// Start and stop native recording from the backend instead of
+    /// leaving it to the webview, so it works while the window is hidden.
+    pub native_recording: bool,
+}
+
+impl Default for PushToTalkSettings {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            shortcut: "CmdOrCtrl+Shift+Space".to_string(),
+            native_recording: false,
+        }
+    }
+}
+
+/// Typing transcripts into the focused app (`dictation.rs`).
+#[derive(Serialize, Deserialize, Clone, specta::Type)]
+#[serde(rename_all = "camelCase", default)]
+pub struct DictationSettings {
+    pub enabled: bool,
+    /// Accelerator that toggles dictation, e.g. `CmdOrCtrl+Alt+D`.
+    pub shortcut: String,
+}
+
+impl Default for DictationSettings {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            shortcut: "CmdOrCtrl+Alt+D".to_string(),
+        }
+    }
+}
+
+/// Inbound `POST /trigger/<name>` routes on localhost.
+#[derive(Serialize, Deserialize, Clone, specta::Type)]
+#[serde(rename_all = "camelCase", default)]
+pub struct WebhookSettings {
+    pub enabled: bool,
+    pub port: u16,
+    /// Bearer token every request must send. Empty refuses all requests.
+    pub token: String,
+    pub triggers: Vec<WebhookTrigger>,
+}
+
+impl Default for WebhookSettings {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            port: 7531,
+            token: String::new(),
+            triggers: Vec::new(),
+        }
+    }
+}
+
+/// Agent modes defined by the user, recognized by `run_llm_agent` next to
+/// the built-in ones.
+#[derive(Serialize, Deserialize, Clone, Default, specta::Type)]
+#[serde(rename_all = "camelCase", default)]
+pub struct AgentModeSettings {
+    pub custom: Vec<CustomAgentMode>,
+}
+
+#[derive(Serialize, Deserialize, Clone, Default, Debug, PartialEq, specta::Type)]
+#[serde(rename_all = "camelCase", default)]
+pub struct CustomAgentMode {
+    /// Passed as `mode` to `run_llm_agent`.
+    pub name: String,
+    /// Falls back to the general agent timeout.
+    pub timeout_ms: Option<u32>,
+    /// `{{instructions}}` (the caller's), `{{prompt}}` and `{{context}}`
+    /// are filled in.
+    pub instructions: String,
+    /// Tool name patterns (`*` matches anything) the mode may call; none
+    /// means no tools.
+    pub allowed_tools: Vec<String>,
+    /// JSON Schema the reply must follow; the parsed reply is returned as
+    /// `output`.
+    pub output_schema: Option<serde_json::Value>,
+}
+
+fn settings_path(app: &tauri::AppHandle) -> Result<PathBuf, String> {
+    let dir = app
+        .path()
+        .app_config_dir()
+        .map_err(|err| format!("failed to resolve app config dir: {}", err))?;
+    fs::create_dir_all(&dir).map_err(|err| format!("failed to create config dir: {}", err))?;
+    Ok(dir.join("settings.json"))
+}
+
+/// Load settings from disk, falling back to defaults when the file is missing
+/// or unreadable so a bad edit never blocks transcription.
+pub fn load_settings(app: &tauri::AppHandle) -> AppSettings {
+    if let Some(guest) = guest_mode::settings(app) {
+        return guest;
+    }
+    let path = match settings_path(app) {
+        Ok(p) => p,
+        Err(err) => {
+            log_line(&format!("[settings] {}", err));
+            return AppSettings::default();
+        }
+    };
+    let content = match fs::read_to_string(&path) {
+        Ok(c) => c,
+        Err(_) => return AppSettings::default(),
+    };
+    serde_json::from_str(&content).unwrap_or_else(|err| {
+        log_line(&format!(
+            "[settings] failed to parse {}: {} (using defaults)",
+            path.display(),
+            err
+        ));
+        AppSettings::default()
+    })
+}
+
+pub fn save_settings(app: &tauri::AppHandle, settings: &AppSettings) -> Result<(), String> {
+    if guest_mode::is_active() {
+        guest_mode::store_settings(app, settings);
+        return Ok(());
+    }
+    let path = settings_path(app)?;
+    let pretty = serde_json::to_string_pretty(settings)
+        .map_err(|err| format!("failed to format settings: {}", err))?;
+    fs::write(&path, pretty.as_bytes()).map_err(|err| format!("failed to write settings: {}", err))
+}
+
+#[tauri::command]
+#[specta::specta]
+pub fn get_app_settings(app: tauri::AppHandle) -> AppSettings {
+    load_settings(&app)
+}
+
+#[tauri::command]
+#[specta::specta]
+pub fn save_app_settings(app: tauri::AppHandle, settings: AppSettings) -> Result<AppSettings, String> {
+    crate::agent_modes::validate(&settings.agent_modes.custom)?;
+    settings.transcription.options.validate()?;
+    settings.transcription.long_form.validate()?;
+    crate::redaction::compile(&settings.redaction)?;
+    save_settings(&app, &settings)?;
+    reload_all(&app);
+    Ok(settings)
+}
+
+/// Restart everything that reads the settings once, after they were saved
+/// or the profile switched.
+pub fn reload_all(app: &tauri::AppHandle) {
+    // Language-specific transcript filters follow the transcription language.
+    crate::transcript_filters::reload(app);
+    crate::acceleration::reload(app);
+    crate::whisper_worker::reload(app);
+    crate::transcription_queue::reload(app);
+    crate::push_to_talk::reload(app);
+    crate::dictation::reload(app);
+    crate::webhooks::reload(app);
+    crate::scripting::reload(app);
+    crate::redaction::reload(app);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{TranscriptionOptions, TranscriptionSettings};
+
+    #[test]
+    fn builds_whisper_prompt_from_initial_prompt_and_vocabulary() {
+        let mut settings = TranscriptionSettings::default();
+        assert_eq!(settings.whisper_prompt(), None);
+
+        settings.vocabulary = vec!["Excalidraw".to_string(), " ".to_string(), "Tauri ".to_string()];
+        assert_eq!(settings.whisper_prompt().as_deref(), Some("Excalidraw, Tauri."));
+
+        settings.initial_prompt = "A podcast   about\nbuilding apps.".to_string();
+        assert_eq!(
+            settings.whisper_prompt().as_deref(),
+            Some("A podcast about building apps. Excalidraw, Tauri.")
+        );
+
+        settings.initial_prompt = "word ".repeat(300);
+        let prompt = settings.whisper_prompt().unwrap();
+        assert!(prompt.chars().count() <= 800);
+        assert!(prompt.starts_with("word "));
+        assert!(prompt.ends_with("Excalidraw, Tauri."));
+    }
+
+    #[test]
+    fn overrides_transcription_options_field_by_field() {
+        let defaults = TranscriptionOptions {
+            no_speech_thold: Some(0.5),
+            beam_size: Some(5),
+            ..Default::default()
+        };
+        assert_eq!(defaults.with(None), defaults);
+        let merged = defaults.with(Some(TranscriptionOptions {
+            beam_size: Some(2),
+            temperature: Some(0.2),
+            temperature_inc: Some(0.0),
+            ..Default::default()
+        }));
+        assert_eq!((merged.no_speech_thold, merged.beam_size, merged.temperature), (Some(0.5), Some(2), Some(0.2)));
+        assert_eq!(merged.temperature_inc, Some(0.0));
+        assert!(merged.validate().is_ok());
+        assert!(TranscriptionOptions { no_speech_thold: Some(1.5), ..Default::default() }.validate().is_err());
+        assert!(TranscriptionOptions { beam_size: Some(0), ..Default::default() }.validate().is_err());
+        assert!(TranscriptionOptions { entropy_thold: Some(-1.0), ..Default::default() }.validate().is_err());
+    }
+}
@@ -0,0 +1,217 @@
+use std::fs;
+use std::path::PathBuf;
+
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+
+use crate::settings::{self, AppSettings, RemoteWhisperServer};
+use crate::{log_line, now_millis};
+
+/// Bumped whenever an exported field is renamed or changes meaning; add a
+/// step to `migrate` at the same time.
+const SCHEMA_VERSION: u32 = 1;
+
+/// What `export_settings` writes. Version 0 is a bare `settings.json`
+/// copied by hand, which `import_settings` also accepts.
+#[derive(Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct SettingsFile {
+    schema_version: u32,
+    app_version: String,
+    exported_at: u64,
+    settings: Value,
+}
+
+/// Drop API keys, client secrets and the webhook token. The SMTP password
+/// and the Slack webhook already live in the keychain.
+fn strip_secrets(settings: &mut AppSettings) {
+    let transcription = &mut settings.transcription;
+    for server in transcription.remote_server.iter_mut().chain(transcription.fallback_servers.iter_mut()) {
+        server.api_key = None;
+    }
+    for endpoint in &mut settings.llm.endpoints {
+        endpoint.api_key = None;
+    }
+    for provider in &mut settings.auth.providers {
+        provider.client_secret = None;
+    }
+    settings.webhooks.token.clear();
+}
+
+/// Keep this machine's secrets for entries the import still has, matched
+/// by URL or id, so importing doesn't sign anything out.
+fn keep_local_secrets(imported: &mut AppSettings, local: &AppSettings) {
+    let servers = |settings: &AppSettings| -> Vec<RemoteWhisperServer> {
+        let transcription = &settings.transcription;
+        transcription.remote_server.iter().chain(&transcription.fallback_servers).cloned().collect()
+    };
+    let local_servers = servers(local);
+    let transcription = &mut imported.transcription;
+    for server in transcription.remote_server.iter_mut().chain(transcription.fallback_servers.iter_mut()) {
+        server.api_key = local_servers
+            .iter()
+            .find(|known| known.url == server.url)
+            .and_then(|known| known.api_key.clone());
+    }
+    for endpoint in &mut imported.llm.endpoints {
+        endpoint.api_key = local
+            .llm
+            .endpoints
+            .iter()
+            .find(|known| known.base_url == endpoint.base_url)
+            .and_then(|known| known.api_key.clone());
+    }
+    for provider in &mut imported.auth.providers {
+        provider.client_secret = local
+            .auth
+            .providers
+            .iter()
+            .find(|known| known.id == provider.id)
+            .and_then(|known| known.client_secret.clone());
+    }
+    imported.webhooks.token = local.webhooks.token.clone();
+}
+
+/// Bring an exported document up to `SCHEMA_VERSION` and return its
+/// settings object.
+fn migrate(document: Value) -> Result<Value, String> {
+    let version = match document.get("schemaVersion") {
+        None => 0,
+        Some(version) => version
+            .as_u64()
+            .ok_or_else(|| "schemaVersion must be a number".to_string())? as u32,
+    };
+    if version > SCHEMA_VERSION {
+        return Err(format!(
+            "these settings were exported by a newer HeyJamie (schema {}, this build reads up to {})",
+            version, SCHEMA_VERSION
+        ));
+    }
+    let settings = if version == 0 {
+        document
+    } else {
+        document
+            .get("settings")
+            .cloned()
+            .ok_or_else(|| "the file has no settings".to_string())?
+    };
+    if !settings.is_object() {
+        return Err("settings must be a JSON object".to_string());
+    }
+    Ok(settings)
+}
+
+fn is_http_url(url: &str) -> bool {
+    let url = url.trim();
+    url.starts_with("http://") || url.starts_with("https://")
+}
+
+/// Problems that would break transcription or delivery if saved.
+fn validate(settings: &AppSettings) -> Vec<String> {
+    let mut problems = Vec::new();
+    let transcription = &settings.transcription;
+    for server in transcription.remote_server.iter().chain(&transcription.fallback_servers) {
+        if server.enabled && !is_http_url(&server.url) {
+            problems.push(format!("whisper server \"{}\" is not an http(s) URL", server.url));
+        }
+        if server.enabled && server.timeout_ms == 0 {
+            problems.push(format!("whisper server \"{}\" has a zero timeout", server.url));
+        }
+    }
+    for endpoint in &settings.llm.endpoints {
+        if !is_http_url(&endpoint.base_url) {
+            problems.push(format!("LLM endpoint \"{}\" is not an http(s) URL", endpoint.name));
+        }
+    }
+    if !settings.email.smtp_host.is_empty() && settings.email.smtp_port == 0 {
+        problems.push("the SMTP port can't be 0".to_string());
+    }
+    problems
+}
+
+fn resolve_path(path: &str) -> PathBuf {
+    let path = path.trim();
+    match path.strip_prefix("~/") {
+        Some(rest) => dirs::home_dir().map(|home| home.join(rest)).unwrap_or_else(|| PathBuf::from(path)),
+        None => PathBuf::from(path),
+    }
+}
+
+fn parse_import(content: &str) -> Result<AppSettings, String> {
+    let document: Value =
+        serde_json::from_str(content).map_err(|err| format!("not a settings file: {}", err))?;
+    let settings = migrate(document)?;
+    let settings: AppSettings =
+        serde_json::from_value(settings).map_err(|err| format!("invalid settings: {}", err))?;
+    let problems = validate(&settings);
+    if !problems.is_empty() {
+        return Err(format!("invalid settings: {}", problems.join("; ")));
+    }
+    Ok(settings)
+}
+
+/// Write the settings store to `path` without secrets, for backup or
+/// moving to another machine.
+#[tauri::command]
+#[specta::specta]
+pub fn export_settings(app: tauri::AppHandle, path: String) -> Result<(), String> {
+    let mut current = settings::load_settings(&app);
+    strip_secrets(&mut current);
+    let file = SettingsFile {
+        schema_version: SCHEMA_VERSION,
+        app_version: app.package_info().version.to_string(),
+        exported_at: now_millis(),
+        settings: serde_json::to_value(&current).map_err(|err| format!("failed to format settings: {}", err))?,
+    };
+    let content =
+        serde_json::to_string_pretty(&file).map_err(|err| format!("failed to format settings: {}", err))?;
+    fs::write(resolve_path(&path), content.as_bytes())
+        .map_err(|err| format!("failed to write {}: {}", path, err))?;
+    log_line(&format!("[settings] exported to {}", path));
+    Ok(())
+}
+
+/// Replace the settings store with an exported file after migrating and
+/// validating it. It is saved through `save_app_settings`, so the same
+/// checks apply and every subsystem picks it up. Nothing is saved when the
+/// file is rejected.
+#[tauri::command]
+#[specta::specta]
+pub fn import_settings(app: tauri::AppHandle, path: String) -> Result<AppSettings, String> {
+    let content =
+        fs::read_to_string(resolve_path(&path)).map_err(|err| format!("failed to read {}: {}", path, err))?;
+    let mut imported = parse_import(&content)?;
+    keep_local_secrets(&mut imported, &settings::load_settings(&app));
+    let imported = settings::save_app_settings(app, imported)?;
+    log_line(&format!("[settings] imported from {}", path));
+    Ok(imported)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{keep_local_secrets, parse_import, strip_secrets};
+    use crate::settings::{AppSettings, LlmEndpoint};
+
+    #[test]
+    fn imports_bare_and_versioned_files() {
+        let bare = parse_import(r#"{"email":{"smtpHost":"smtp.example.com","smtpPort":465}}"#).unwrap();
+        assert_eq!(bare.email.smtp_port, 465);
+        let versioned = r#"{"schemaVersion":1,"appVersion":"0.1.0","exportedAt":0,"settings":{"email":{"smtpPort":25}}}"#;
+        assert_eq!(parse_import(versioned).unwrap().email.smtp_port, 25);
+        assert!(parse_import(r#"{"schemaVersion":99,"settings":{}}"#).is_err());
+        assert!(parse_import(r#"{"llm":{"endpoints":[{"name":"x","baseUrl":"localhost"}]}}"#).is_err());
+
+        let mut settings = AppSettings::default();
+        settings.llm.endpoints.push(LlmEndpoint {
+            api_key: Some("secret".to_string()),
+            ..LlmEndpoint::default()
+        });
+        settings.webhooks.token = "bearer".to_string();
+        let local = settings.clone();
+        strip_secrets(&mut settings);
+        assert_eq!(settings.llm.endpoints[0].api_key, None);
+        assert!(settings.webhooks.token.is_empty());
+        keep_local_secrets(&mut settings, &local);
+        assert_eq!(settings.webhooks.token, "bearer");
+    }
+}
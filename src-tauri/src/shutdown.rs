@@ -0,0 +1,159 @@
+//! Quitting in order: the first exit request is held back while pending
+//! transcripts are written, work in flight is stopped, the session report
+//! is finalized and child processes are shut down, each step with a
+//! timeout so a stuck step can't keep the app open.
+
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::mpsc;
+use std::time::{Duration, Instant};
+
+use serde::{Deserialize, Serialize};
+use tauri::Manager;
+use tauri_specta::Event;
+
+use crate::{file_transcription, log_line, mcp_supervisor, recording, sessions, temp_files, whisper_worker};
+
+/// Native recording: the last segment still has to be transcribed.
+const RECORDING_TIMEOUT: Duration = Duration::from_secs(20);
+/// The webview's transcription queue.
+const FRONTEND_TIMEOUT: Duration = Duration::from_secs(20);
+const JOBS_TIMEOUT: Duration = Duration::from_secs(10);
+const SESSION_TIMEOUT: Duration = Duration::from_secs(5);
+/// `graceful_kill` gives each child two seconds.
+const SERVERS_TIMEOUT: Duration = Duration::from_secs(5);
+const POLL_INTERVAL: Duration = Duration::from_millis(50);
+
+/// Emitted to the main window when the app is quitting. The window stops
+/// listening, waits for its transcription queue and then calls
+/// `confirm_shutdown_flushed`.
+#[derive(Serialize, Deserialize, Clone, Debug, specta::Type, tauri_specta::Event)]
+pub struct AppShutdown;
+
+#[derive(Default)]
+pub struct ShutdownState {
+    started: AtomicBool,
+    finished: AtomicBool,
+    frontend_flushed: AtomicBool,
+}
+
+static SHUTTING_DOWN: AtomicBool = AtomicBool::new(false);
+
+/// Set once quitting has begun; new agent runs are refused from then on.
+pub fn is_shutting_down() -> bool {
+    SHUTTING_DOWN.load(Ordering::SeqCst)
+}
+
+/// Poll `done` until it holds or `timeout` passes.
+pub fn wait_until(timeout: Duration, done: impl Fn() -> bool) -> bool {
+    let started = Instant::now();
+    while !done() {
+        if started.elapsed() >= timeout {
+            return false;
+        }
+        std::thread::sleep(POLL_INTERVAL);
+    }
+    true
+}
+
+/// Run one step on its own thread and give up on it after `timeout`.
+fn step(name: &str, timeout: Duration, f: impl FnOnce() + Send + 'static) {
+    let started = Instant::now();
+    let (done_tx, done) = mpsc::channel();
+    std::thread::spawn(move || {
+        f();
+        let _ = done_tx.send(());
+    });
+    match done.recv_timeout(timeout) {
+        Ok(()) => log_line(&format!("[shutdown] {} done in {}ms", name, started.elapsed().as_millis())),
+        Err(_) => log_line(&format!("[shutdown] {} timed out after {}ms; continuing", name, timeout.as_millis())),
+    }
+}
+
+fn flush_frontend(app: &tauri::AppHandle) {
+    if app.get_webview_window("main").is_none() {
+        return;
+    }
+    let state = app.state::<ShutdownState>();
+    state.frontend_flushed.store(false, Ordering::SeqCst);
+    if let Err(err) = AppShutdown.emit_to(app, "main") {
+        log_line(&format!("[shutdown] failed to notify main window: {}", err));
+        return;
+    }
+    wait_until(FRONTEND_TIMEOUT, || state.frontend_flushed.load(Ordering::SeqCst));
+}
+
+/// Everything that has to happen before the process may exit, in
+/// dependency order: audio still being transcribed feeds the transcript,
+/// the transcript feeds the session report, and agent runs use the canvas
+/// server.
+fn run(app: &tauri::AppHandle) {
+    let started = Instant::now();
+    log_line("[shutdown] quitting; finishing pending work");
+    SHUTTING_DOWN.store(true, Ordering::SeqCst);
+
+    let handle = app.clone();
+    step("native recording", RECORDING_TIMEOUT, move || recording::finish(&handle));
+    let handle = app.clone();
+    step("pending transcripts", FRONTEND_TIMEOUT, move || flush_frontend(&handle));
+    let handle = app.clone();
+    step("agent runs", JOBS_TIMEOUT, move || crate::cancel_agent_runs(&handle, JOBS_TIMEOUT));
+    let handle = app.clone();
+    step("file transcription", JOBS_TIMEOUT, move || {
+        file_transcription::cancel_and_wait(&handle, JOBS_TIMEOUT)
+    });
+    let handle = app.clone();
+    step("session", SESSION_TIMEOUT, move || sessions::close_on_exit(&handle));
+    let handle = app.clone();
+    step("MCP servers", SERVERS_TIMEOUT, move || mcp_supervisor::stop_all(&handle));
+    let handle = app.clone();
+    step("whisper worker", SERVERS_TIMEOUT, move || whisper_worker::stop(&handle));
+    temp_files::remove_run_dir();
+
+    log_line(&format!("[shutdown] finished in {}ms", started.elapsed().as_millis()));
+}
+
+/// Hold back exit requests until `run` is done, then quit.
+pub fn on_exit_requested(app: &tauri::AppHandle, api: &tauri::ExitRequestApi) {
+    let state = app.state::<ShutdownState>();
+    if state.finished.load(Ordering::SeqCst) {
+        return;
+    }
+    api.prevent_exit();
+    if state.started.swap(true, Ordering::SeqCst) {
+        return;
+    }
+    let app = app.clone();
+    std::thread::spawn(move || {
+        run(&app);
+        app.state::<ShutdownState>().finished.store(true, Ordering::SeqCst);
+        app.exit(0);
+    });
+}
+
+/// Last resort when the process exits without going through `run`.
+pub fn on_exit(app: &tauri::AppHandle) {
+    mcp_supervisor::stop_all(app);
+    whisper_worker::stop(app);
+    temp_files::remove_run_dir();
+}
+
+/// The main window wrote everything it had queued.
+#[tauri::command]
+#[specta::specta]
+pub fn confirm_shutdown_flushed(state: tauri::State<'_, ShutdownState>) {
+    state.frontend_flushed.store(true, Ordering::SeqCst);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::wait_until;
+    use std::sync::atomic::{AtomicU32, Ordering};
+    use std::time::Duration;
+
+    #[test]
+    fn wait_until_gives_up_after_the_timeout() {
+        let polls = AtomicU32::new(0);
+        assert!(wait_until(Duration::from_secs(1), || polls.fetch_add(1, Ordering::SeqCst) >= 2));
+        assert!(!wait_until(Duration::from_millis(120), || false));
+    }
+}
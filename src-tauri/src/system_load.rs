@@ -0,0 +1,170 @@
+use std::{sync::Mutex, time::Duration};
+
+use serde::{Deserialize, Serialize};
+use tauri::Manager;
+use tauri_specta::Event;
+
+use crate::log_line;
+use crate::settings::{self, LoadSettings};
+
+const LOAD_CHECK_INTERVAL: Duration = Duration::from_secs(10);
+/// Pressure clears only once load drops this far below the threshold, so
+/// transcription doesn't flip between models every check.
+const RECOVERY_MARGIN: f64 = 0.2;
+
+/// Emitted as `load-pressure` when transcription backs off or recovers.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, specta::Type, tauri_specta::Event)]
+#[serde(rename_all = "camelCase")]
+pub struct LoadPressure {
+    pub pressured: bool,
+    /// One-minute load average divided by the number of cores.
+    pub load_per_core: Option<f64>,
+    /// macOS CPU speed limit in percent; below 100 means thermal throttling.
+    pub cpu_speed_limit: Option<u8>,
+    pub reason: Option<String>,
+    /// Seconds of audio per transcription segment; `None` keeps the default.
+    pub segment_seconds: Option<u32>,
+    /// Whisper model file to use instead of the default one.
+    pub whisper_model: Option<String>,
+}
+
+#[derive(Default)]
+pub struct SystemLoadState {
+    current: Mutex<Option<LoadPressure>>,
+}
+
+#[cfg(unix)]
+fn load_per_core() -> Option<f64> {
+    let mut averages = [0f64; 3];
+    if unsafe { libc::getloadavg(averages.as_mut_ptr(), 3) } < 1 {
+        return None;
+    }
+    let cores = std::thread::available_parallelism().ok()?.get();
+    Some(averages[0] / cores as f64)
+}
+
+#[cfg(not(unix))]
+fn load_per_core() -> Option<f64> {
+    None
+}
+
+/// Parse `pmset -g therm`, which reports the same throttling as the
+/// ProcessInfo thermal state, e.g. `CPU_Speed_Limit = 70`.
+#[cfg_attr(not(target_os = "macos"), allow(dead_code))]
+pub fn parse_speed_limit(output: &str) -> Option<u8> {
+    output.lines().find_map(|line| {
+        let (key, value) = line.split_once('=')?;
+        (key.trim() == "CPU_Speed_Limit").then(|| value.trim().parse().ok())?
+    })
+}
+
+#[cfg(target_os = "macos")]
+fn cpu_speed_limit() -> Option<u8> {
+    let output = std::process::Command::new("pmset").args(["-g", "therm"]).output().ok()?;
+    parse_speed_limit(&String::from_utf8_lossy(&output.stdout))
+}
+
+#[cfg(not(target_os = "macos"))]
+fn cpu_speed_limit() -> Option<u8> {
+    None
+}
+
+/// Why transcription should back off, if it should. `was_pressured` keeps
+/// pressure on until load falls below the threshold by `RECOVERY_MARGIN`.
+pub fn pressure_reason(
+    was_pressured: bool,
+    load_per_core: Option<f64>,
+    cpu_speed_limit: Option<u8>,
+    policy: &LoadSettings,
+) -> Option<String> {
+    if !policy.enabled {
+        return None;
+    }
+    if let Some(limit) = cpu_speed_limit.filter(|limit| *limit < 100) {
+        return Some(format!("CPU throttled to {}% by thermal pressure", limit));
+    }
+    let threshold = if was_pressured {
+        policy.load_per_core - RECOVERY_MARGIN
+    } else {
+        policy.load_per_core
+    };
+    load_per_core
+        .filter(|load| *load >= threshold)
+        .map(|load| format!("system load at {:.0}% of CPU capacity", load * 100.0))
+}
+
+fn check(app: &tauri::AppHandle) -> LoadPressure {
+    let policy = settings::load_settings(app).load;
+    let state = app.state::<SystemLoadState>();
+    let was_pressured = state
+        .current
+        .lock()
+        .unwrap()
+        .as_ref()
+        .is_some_and(|current| current.pressured);
+    let load = load_per_core();
+    let speed_limit = cpu_speed_limit();
+    let reason = pressure_reason(was_pressured, load, speed_limit, &policy);
+    let pressured = reason.is_some();
+    let model = policy.whisper_model.trim().to_string();
+    let pressure = LoadPressure {
+        pressured,
+        load_per_core: load,
+        cpu_speed_limit: speed_limit,
+        reason,
+        segment_seconds: (pressured && policy.segment_seconds > 0).then_some(policy.segment_seconds),
+        whisper_model: Some(model).filter(|model| pressured && !model.is_empty()),
+    };
+    *state.current.lock().unwrap() = Some(pressure.clone());
+    if pressured != was_pressured {
+        log_line(&format!(
+            "[system-load] {}",
+            pressure.reason.as_deref().unwrap_or("load back to normal")
+        ));
+        if let Err(err) = pressure.clone().emit_to(app, "main") {
+            log_line(&format!("[system-load] failed to notify main window: {}", err));
+        }
+    }
+    pressure
+}
+
+/// The last reading; not pressured until the monitor has run.
+pub fn current(app: &tauri::AppHandle) -> Option<LoadPressure> {
+    app.state::<SystemLoadState>().current.lock().unwrap().clone()
+}
+
+/// Sample load and thermal state periodically.
+pub fn spawn_monitor(app: tauri::AppHandle) {
+    tauri::async_runtime::spawn(async move {
+        loop {
+            let handle = app.clone();
+            let _ = tauri::async_runtime::spawn_blocking(move || check(&handle)).await;
+            tokio::time::sleep(LOAD_CHECK_INTERVAL).await;
+        }
+    });
+}
+
+#[tauri::command]
+#[specta::specta]
+pub fn get_load_pressure(app: tauri::AppHandle) -> LoadPressure {
+    current(&app).unwrap_or_else(|| check(&app))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{parse_speed_limit, pressure_reason};
+    use crate::settings::LoadSettings;
+
+    #[test]
+    fn backs_off_with_hysteresis() {
+        assert_eq!(parse_speed_limit("Note: No thermal warning level has been recorded\n\tCPU_Speed_Limit \t= 70\n"), Some(70));
+        assert_eq!(parse_speed_limit("No CPU power status has been recorded"), None);
+
+        let policy = LoadSettings::default();
+        assert!(pressure_reason(false, Some(0.5), Some(70), &policy).is_some());
+        assert!(pressure_reason(false, Some(0.8), Some(100), &policy).is_none());
+        assert!(pressure_reason(true, Some(0.8), None, &policy).is_some());
+        assert!(pressure_reason(true, Some(0.6), None, &policy).is_none());
+        assert!(pressure_reason(false, Some(2.0), None, &LoadSettings { enabled: false, ..policy }).is_none());
+    }
+}
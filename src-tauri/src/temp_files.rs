@@ -0,0 +1,187 @@
+//! Temp files for `whisper-cli` runs. Each run of the app writes into its
+//! own `heyjamie/run-<pid>` directory under the system temp dir, files are
+//! removed when their guard is dropped, directories left behind by crashed
+//! runs are reclaimed at startup, and the total is capped.
+
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::time::{Duration, SystemTime};
+
+use crate::{log_line, now_millis};
+
+const ROOT: &str = "heyjamie";
+/// Far more than any number of segments in flight; hitting it means
+/// files aren't being removed.
+const QUOTA_BYTES: u64 = 512 * 1024 * 1024;
+/// Another run's directory is reclaimed once it's this old even if its
+/// PID is alive, since PIDs get reused after a crash.
+const STALE_AGE: Duration = Duration::from_secs(24 * 60 * 60);
+/// Segments written straight into the temp dir by older versions.
+const LEGACY_PREFIX: &str = "heyjamie-";
+
+static SEQUENCE: AtomicU64 = AtomicU64::new(0);
+
+/// A temp file that is removed when dropped.
+pub struct TempFile {
+    path: PathBuf,
+}
+
+impl TempFile {
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+
+    /// The same name with another extension, for output a tool writes
+    /// next to its input (e.g. `whisper-cli -ojf`).
+    pub fn sibling(&self, extension: &str) -> TempFile {
+        TempFile {
+            path: self.path.with_extension(extension),
+        }
+    }
+}
+
+impl Drop for TempFile {
+    fn drop(&mut self) {
+        let _ = fs::remove_file(&self.path);
+    }
+}
+
+fn root() -> PathBuf {
+    std::env::temp_dir().join(ROOT)
+}
+
+fn run_dir() -> PathBuf {
+    root().join(format!("run-{}", std::process::id()))
+}
+
+fn dir_size(dir: &Path) -> u64 {
+    let Ok(entries) = fs::read_dir(dir) else {
+        return 0;
+    };
+    entries
+        .flatten()
+        .filter_map(|entry| {
+            let meta = entry.metadata().ok()?;
+            Some(if meta.is_dir() { dir_size(&entry.path()) } else { meta.len() })
+        })
+        .sum()
+}
+
+fn age(path: &Path) -> Option<Duration> {
+    let modified = fs::metadata(path).and_then(|meta| meta.modified()).ok()?;
+    SystemTime::now().duration_since(modified).ok()
+}
+
+#[cfg(unix)]
+fn process_alive(pid: u32) -> bool {
+    let result = unsafe { libc::kill(pid as i32, 0) };
+    result == 0 || std::io::Error::last_os_error().raw_os_error() == Some(libc::EPERM)
+}
+
+/// Without a cheap check, other runs' directories are only reclaimed by age.
+#[cfg(not(unix))]
+fn process_alive(_pid: u32) -> bool {
+    true
+}
+
+/// Whether `name` is a run directory this run may remove.
+fn is_stale(name: &str, age: Duration, own_pid: u32, alive: impl Fn(u32) -> bool) -> bool {
+    let Some(pid) = name.strip_prefix("run-").and_then(|pid| pid.parse::<u32>().ok()) else {
+        return false;
+    };
+    pid != own_pid && (!alive(pid) || age > STALE_AGE)
+}
+
+/// Remove what crashed runs left behind. Called at startup and before
+/// giving up on the quota.
+pub fn cleanup_stale() {
+    let own_pid = std::process::id();
+    let (mut removed, mut bytes) = (0, 0);
+    if let Ok(entries) = fs::read_dir(root()) {
+        for entry in entries.flatten() {
+            let path = entry.path();
+            let name = entry.file_name().to_string_lossy().into_owned();
+            let age = age(&path).unwrap_or_default();
+            if !is_stale(&name, age, own_pid, process_alive) {
+                continue;
+            }
+            let size = dir_size(&path);
+            if fs::remove_dir_all(&path).is_ok() {
+                removed += 1;
+                bytes += size;
+            }
+        }
+    }
+    if let Ok(entries) = fs::read_dir(std::env::temp_dir()) {
+        for entry in entries.flatten() {
+            let name = entry.file_name().to_string_lossy().into_owned();
+            let legacy = name.starts_with(LEGACY_PREFIX) && name.ends_with(".wav");
+            if !legacy {
+                continue;
+            }
+            let size = entry.metadata().map(|meta| meta.len()).unwrap_or(0);
+            if fs::remove_file(entry.path()).is_ok() {
+                removed += 1;
+                bytes += size;
+            }
+        }
+    }
+    if removed > 0 {
+        log_line(&format!(
+            "[temp-files] removed {} leftovers from earlier runs ({} KB)",
+            removed,
+            bytes / 1024
+        ));
+    }
+}
+
+/// Remove this run's directory on quit.
+pub fn remove_run_dir() {
+    let _ = fs::remove_dir_all(run_dir());
+}
+
+/// Write a WAV segment to this run's directory.
+pub fn write_wav(bytes: &[u8]) -> Result<TempFile, String> {
+    let dir = run_dir();
+    fs::create_dir_all(&dir).map_err(|err| format!("failed to create {}: {}", dir.display(), err))?;
+    let needed = bytes.len() as u64;
+    if dir_size(&root()) + needed > QUOTA_BYTES {
+        cleanup_stale();
+        if dir_size(&root()) + needed > QUOTA_BYTES {
+            return Err(format!(
+                "temp files in {} are over the {} MB quota",
+                root().display(),
+                QUOTA_BYTES / (1024 * 1024)
+            ));
+        }
+    }
+    let sequence = SEQUENCE.fetch_add(1, Ordering::Relaxed);
+    let path = dir.join(format!("segment-{}-{}.wav", now_millis(), sequence));
+    fs::write(&path, bytes).map_err(|err| err.to_string())?;
+    log_line(&format!("wrote wav segment to {}", path.display()));
+    Ok(TempFile { path })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{is_stale, write_wav, STALE_AGE};
+    use std::time::Duration;
+
+    #[test]
+    fn reclaims_only_other_runs_that_are_gone_or_old() {
+        let recent = Duration::from_secs(60);
+        let alive = |pid: u32| pid == 7;
+        assert!(!is_stale("run-1", recent, 1, alive));
+        assert!(!is_stale("run-7", recent, 1, alive));
+        assert!(is_stale("run-8", recent, 1, alive));
+        assert!(is_stale("run-7", STALE_AGE + recent, 1, alive));
+        assert!(!is_stale("models", STALE_AGE + recent, 1, alive));
+
+        let file = write_wav(b"RIFF").unwrap();
+        let path = file.path().to_path_buf();
+        assert!(path.exists());
+        drop(file);
+        assert!(!path.exists());
+    }
+}
@@ -0,0 +1,375 @@
+use std::{
+    fs,
+    net::{ToSocketAddrs, UdpSocket},
+    time::Duration,
+};
+
+use base64::{engine::general_purpose, Engine as _};
+use serde::{Deserialize, Serialize};
+
+use crate::sessions::{self, SessionInfo, SessionState};
+use crate::{log_line, now_millis, settings, wav};
+
+const TIMECODE_FILE: &str = "timecode.json";
+const DAY_MS: u64 = 24 * 60 * 60 * 1000;
+const NTP_TIMEOUT: Duration = Duration::from_secs(3);
+/// Seconds between the NTP epoch (1900) and the Unix epoch.
+const NTP_UNIX_OFFSET_SECS: u64 = 2_208_988_800;
+/// LTC sync word, bits 64–79 in transmission order.
+const LTC_SYNC: [u8; 16] = [0, 0, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 0, 1];
+
+#[derive(Serialize, Deserialize, Clone, Copy, PartialEq, Eq, Debug, specta::Type)]
+#[serde(rename_all = "camelCase")]
+pub enum TimecodeSource {
+    /// Time-of-day from an NTP-disciplined wall clock, for recorders
+    /// jam-synced to the time of day.
+    Ntp,
+    /// LTC read from an input channel fed by the recorder.
+    Ltc,
+}
+
+/// Ties a session offset to the external recorder's timecode, saved as
+/// `timecode.json` in the session folder.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, specta::Type)]
+#[serde(rename_all = "camelCase")]
+pub struct SessionTimecode {
+    pub source: TimecodeSource,
+    /// Session offset the reference was taken at.
+    pub session_offset_ms: u64,
+    /// Recorder timecode at that offset, in ms since midnight.
+    pub timecode_ms: u64,
+    pub fps: u32,
+    /// NTP only: server asked, measured local clock error and round trip.
+    pub server: Option<String>,
+    pub clock_offset_ms: Option<i64>,
+    pub round_trip_ms: Option<u64>,
+}
+
+/// `HH:MM:SS:FF` on the recorder's clock for a session offset.
+pub fn timecode_at(timecode: &SessionTimecode, offset_ms: u64) -> String {
+    let shifted = timecode.timecode_ms as i64 + offset_ms as i64 - timecode.session_offset_ms as i64;
+    let ms = shifted.rem_euclid(DAY_MS as i64) as u64;
+    let total_seconds = ms / 1000;
+    let frames = (ms % 1000) * timecode.fps as u64 / 1000;
+    format!(
+        "{:02}:{:02}:{:02}:{:02}",
+        total_seconds / 3600,
+        (total_seconds % 3600) / 60,
+        total_seconds % 60,
+        frames
+    )
+}
+
+fn ntp_timestamp_ms(bytes: &[u8]) -> i64 {
+    let seconds = u32::from_be_bytes([bytes[0], bytes[1], bytes[2], bytes[3]]) as u64;
+    let fraction = u32::from_be_bytes([bytes[4], bytes[5], bytes[6], bytes[7]]) as u64;
+    ((seconds.saturating_sub(NTP_UNIX_OFFSET_SECS)) * 1000 + ((fraction * 1000) >> 32)) as i64
+}
+
+/// Ask an NTP server for the local clock error (SNTP, RFC 4330). Returns
+/// `(offset_ms, round_trip_ms)`; add the offset to local time for true time.
+fn query_ntp(server: &str) -> Result<(i64, u64), String> {
+    let address = if server.contains(':') { server.to_string() } else { format!("{}:123", server) };
+    let target = address
+        .to_socket_addrs()
+        .map_err(|err| format!("failed to resolve {}: {}", server, err))?
+        .next()
+        .ok_or_else(|| format!("no address for {}", server))?;
+    let socket = UdpSocket::bind(if target.is_ipv4() { "0.0.0.0:0" } else { "[::]:0" })
+        .map_err(|err| format!("failed to open UDP socket: {}", err))?;
+    socket
+        .set_read_timeout(Some(NTP_TIMEOUT))
+        .map_err(|err| format!("failed to set NTP timeout: {}", err))?;
+    let mut request = [0u8; 48];
+    // LI 0, version 4, mode 3 (client).
+    request[0] = 0x23;
+    let sent_at = now_millis() as i64;
+    socket
+        .send_to(&request, target)
+        .map_err(|err| format!("failed to query {}: {}", server, err))?;
+    let mut response = [0u8; 48];
+    let (len, _) = socket
+        .recv_from(&mut response)
+        .map_err(|err| format!("no NTP reply from {}: {}", server, err))?;
+    let received_at = now_millis() as i64;
+    if len < 48 || response[0] & 0x07 != 4 {
+        return Err(format!("invalid NTP reply from {}", server));
+    }
+    let server_received = ntp_timestamp_ms(&response[32..40]);
+    let server_sent = ntp_timestamp_ms(&response[40..48]);
+    let offset = ((server_received - sent_at) + (server_sent - received_at)) / 2;
+    let round_trip = (received_at - sent_at) - (server_sent - server_received);
+    Ok((offset, round_trip.max(0) as u64))
+}
+
+/// Local time zone offset, since time-of-day timecode follows local time.
+#[cfg(unix)]
+fn local_utc_offset_ms(unix_ms: i64) -> i64 {
+    let seconds = (unix_ms / 1000) as libc::time_t;
+    let mut tm: libc::tm = unsafe { std::mem::zeroed() };
+    if unsafe { libc::localtime_r(&seconds, &mut tm) }.is_null() {
+        return 0;
+    }
+    #[allow(clippy::unnecessary_cast)]
+    let offset = tm.tm_gmtoff as i64 * 1000;
+    offset
+}
+
+#[cfg(not(unix))]
+fn local_utc_offset_ms(_unix_ms: i64) -> i64 {
+    0
+}
+
+/// A decoded LTC frame and the sample it starts at.
+#[derive(Debug, PartialEq)]
+pub struct LtcFrame {
+    pub hours: u32,
+    pub minutes: u32,
+    pub seconds: u32,
+    pub frames: u32,
+    pub start_sample: usize,
+    pub fps: u32,
+}
+
+fn bcd(bits: &[u8], units: usize, unit_bits: usize, tens: usize, ten_bits: usize) -> u32 {
+    let value = |start: usize, count: usize| {
+        (0..count).fold(0u32, |acc, i| acc | ((bits[start + i] as u32) << i))
+    };
+    value(units, unit_bits) + 10 * value(tens, ten_bits)
+}
+
+/// Decode the first complete LTC frame in `samples`. LTC is biphase mark
+/// coded: every bit starts with a transition and a 1 has another mid-bit,
+/// so transition gaps are either a whole bit (0) or two halves (1).
+pub fn decode_ltc(samples: &[f32], sample_rate: u32) -> Option<LtcFrame> {
+    let mut crossings = Vec::new();
+    let mut positive = samples.first().map(|s| *s >= 0.0)?;
+    for (index, sample) in samples.iter().enumerate() {
+        if (*sample >= 0.0) != positive {
+            positive = !positive;
+            crossings.push(index);
+        }
+    }
+    let gaps: Vec<(usize, f32)> = crossings
+        .windows(2)
+        .map(|pair| (pair[0], (pair[1] - pair[0]) as f32))
+        .collect();
+    // Two-cluster split between half-bit and whole-bit gaps.
+    let (mut short, mut long) = gaps.iter().fold((f32::MAX, 0f32), |(lo, hi), (_, gap)| (lo.min(*gap), hi.max(*gap)));
+    if gaps.is_empty() || long < short * 1.5 {
+        return None;
+    }
+    for _ in 0..8 {
+        let threshold = (short + long) / 2.0;
+        let (shorts, longs): (Vec<f32>, Vec<f32>) =
+            gaps.iter().map(|(_, gap)| *gap).partition(|gap| *gap < threshold);
+        short = shorts.iter().sum::<f32>() / shorts.len().max(1) as f32;
+        long = longs.iter().sum::<f32>() / longs.len().max(1) as f32;
+    }
+    let threshold = (short + long) / 2.0;
+
+    let mut bits: Vec<u8> = Vec::new();
+    let mut bit_starts: Vec<usize> = Vec::new();
+    let mut pending_half: Option<usize> = None;
+    for (start, gap) in gaps {
+        if gap >= threshold {
+            pending_half = None;
+            bits.push(0);
+            bit_starts.push(start);
+        } else if let Some(half_start) = pending_half.take() {
+            bits.push(1);
+            bit_starts.push(half_start);
+        } else {
+            pending_half = Some(start);
+            continue;
+        }
+        if bits.len() >= 80 && bits[bits.len() - 16..] == LTC_SYNC {
+            let frame = &bits[bits.len() - 80..];
+            let start_sample = bit_starts[bits.len() - 80];
+            let frame_samples = (start + gap as usize).saturating_sub(start_sample).max(1);
+            return Some(LtcFrame {
+                frames: bcd(frame, 0, 4, 8, 2),
+                seconds: bcd(frame, 16, 4, 24, 3),
+                minutes: bcd(frame, 32, 4, 40, 3),
+                hours: bcd(frame, 48, 4, 56, 2),
+                start_sample,
+                fps: (sample_rate as f32 / frame_samples as f32).round() as u32,
+            });
+        }
+    }
+    None
+}
+
+/// The recorder timecode anchor for a session, if one was taken.
+pub fn session_timecode(app: &tauri::AppHandle, session_id: &str) -> Option<SessionTimecode> {
+    let path = sessions::session_dir(app, session_id).ok()?.join(TIMECODE_FILE);
+    serde_json::from_str(&fs::read_to_string(path).ok()?).ok()
+}
+
+fn write_timecode(app: &tauri::AppHandle, session_id: &str, timecode: &SessionTimecode) -> Result<(), String> {
+    let content = serde_json::to_string_pretty(timecode)
+        .map_err(|err| format!("failed to format timecode: {}", err))?;
+    fs::write(sessions::session_dir(app, session_id)?.join(TIMECODE_FILE), content.as_bytes())
+        .map_err(|err| format!("failed to write timecode: {}", err))
+}
+
+fn sync_with_ntp(app: &tauri::AppHandle, info: &SessionInfo, server: &str) -> Result<SessionTimecode, String> {
+    let (clock_offset_ms, round_trip_ms) = query_ntp(server)?;
+    let now = now_millis();
+    let true_now = now as i64 + clock_offset_ms;
+    let timecode = SessionTimecode {
+        source: TimecodeSource::Ntp,
+        session_offset_ms: now.saturating_sub(info.started_at),
+        timecode_ms: (true_now + local_utc_offset_ms(true_now)).rem_euclid(DAY_MS as i64) as u64,
+        fps: settings::load_settings(app).timecode.fps,
+        server: Some(server.to_string()),
+        clock_offset_ms: Some(clock_offset_ms),
+        round_trip_ms: Some(round_trip_ms),
+    };
+    write_timecode(app, &info.id, &timecode)?;
+    log_line(&format!(
+        "[timecode] {} synced with {}: clock off by {} ms (round trip {} ms)",
+        info.id, server, clock_offset_ms, round_trip_ms
+    ));
+    Ok(timecode)
+}
+
+/// Take an NTP reference in the background when a session starts, if enabled.
+pub fn auto_sync(app: &tauri::AppHandle, info: &SessionInfo) {
+    let config = settings::load_settings(app).timecode;
+    if !config.auto_sync_ntp || config.ntp_server.trim().is_empty() {
+        return;
+    }
+    let (app, info) = (app.clone(), info.clone());
+    tauri::async_runtime::spawn_blocking(move || {
+        if let Err(err) = sync_with_ntp(&app, &info, config.ntp_server.trim()) {
+            log_line(&format!("[timecode] NTP sync failed: {}", err));
+        }
+    });
+}
+
+fn active_session(app: &tauri::AppHandle, state: &SessionState) -> Result<SessionInfo, String> {
+    let session_id = state
+        .active_id()
+        .ok_or_else(|| "no active session".to_string())?;
+    crate::export::load_session(app, &session_id)
+}
+
+/// Record an NTP-disciplined time-of-day reference for the active session.
+#[tauri::command]
+#[specta::specta]
+pub async fn sync_session_clock(
+    app: tauri::AppHandle,
+    state: tauri::State<'_, SessionState>,
+    server: Option<String>,
+) -> Result<SessionTimecode, String> {
+    let info = active_session(&app, &state)?;
+    let server = server
+        .filter(|s| !s.trim().is_empty())
+        .unwrap_or_else(|| settings::load_settings(&app).timecode.ntp_server);
+    tauri::async_runtime::spawn_blocking(move || sync_with_ntp(&app, &info, server.trim()))
+        .await
+        .map_err(|err| format!("NTP sync task failed: {}", err))?
+}
+
+/// Anchor the active session to LTC decoded from a WAV clip that ended
+/// just now, captured from the input the recorder's timecode feeds.
+#[tauri::command]
+#[specta::specta]
+pub fn anchor_session_ltc(
+    app: tauri::AppHandle,
+    state: tauri::State<'_, SessionState>,
+    audio_base64: String,
+) -> Result<SessionTimecode, String> {
+    let info = active_session(&app, &state)?;
+    let bytes = general_purpose::STANDARD
+        .decode(audio_base64.as_bytes())
+        .map_err(|err| format!("invalid audio payload: {}", err))?;
+    let (rate, samples) = wav::decode_pcm16_mono(&bytes)?;
+    let frame = decode_ltc(&samples, rate).ok_or_else(|| "no LTC found on that input".to_string())?;
+    let clip_start_offset = now_millis()
+        .saturating_sub(info.started_at)
+        .saturating_sub(samples.len() as u64 * 1000 / rate as u64);
+    let fps = frame.fps.max(1);
+    let timecode = SessionTimecode {
+        source: TimecodeSource::Ltc,
+        session_offset_ms: clip_start_offset + frame.start_sample as u64 * 1000 / rate as u64,
+        timecode_ms: ((frame.hours * 60 + frame.minutes) * 60 + frame.seconds) as u64 * 1000
+            + frame.frames as u64 * 1000 / fps as u64,
+        fps,
+        server: None,
+        clock_offset_ms: None,
+        round_trip_ms: None,
+    };
+    write_timecode(&app, &info.id, &timecode)?;
+    log_line(&format!(
+        "[timecode] {} anchored to LTC {} at {} fps",
+        info.id,
+        timecode_at(&timecode, timecode.session_offset_ms),
+        fps
+    ));
+    Ok(timecode)
+}
+
+#[tauri::command]
+#[specta::specta]
+pub fn get_session_timecode(app: tauri::AppHandle, session_id: String) -> Option<SessionTimecode> {
+    session_timecode(&app, &session_id)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{decode_ltc, timecode_at, SessionTimecode, TimecodeSource, LTC_SYNC};
+
+    fn ltc_bits(hours: u8, minutes: u8, seconds: u8, frames: u8) -> Vec<u8> {
+        let mut bits = vec![0u8; 80];
+        let mut put = |start: usize, count: usize, value: u8| {
+            for i in 0..count {
+                bits[start + i] = (value >> i) & 1;
+            }
+        };
+        put(0, 4, frames % 10);
+        put(8, 2, frames / 10);
+        put(16, 4, seconds % 10);
+        put(24, 3, seconds / 10);
+        put(32, 4, minutes % 10);
+        put(40, 3, minutes / 10);
+        put(48, 4, hours % 10);
+        put(56, 2, hours / 10);
+        bits[64..].copy_from_slice(&LTC_SYNC);
+        bits
+    }
+
+    #[test]
+    fn decodes_biphase_mark_ltc() {
+        let (rate, fps) = (48_000usize, 30usize);
+        let half = rate / fps / 80 / 2;
+        let mut level = 0.5f32;
+        let mut samples = vec![level; 37];
+        for frame in [ltc_bits(1, 2, 3, 4), ltc_bits(1, 2, 3, 5)] {
+            for bit in frame {
+                level = -level;
+                samples.extend(std::iter::repeat_n(level, half));
+                if bit == 1 {
+                    level = -level;
+                }
+                samples.extend(std::iter::repeat_n(level, half));
+            }
+        }
+        let frame = decode_ltc(&samples, rate as u32).expect("frame");
+        assert_eq!((frame.hours, frame.minutes, frame.seconds, frame.frames), (1, 2, 3, 4));
+        assert_eq!(frame.fps, 30);
+
+        let anchor = SessionTimecode {
+            source: TimecodeSource::Ltc,
+            session_offset_ms: 1_000,
+            timecode_ms: 3_723_000,
+            fps: 30,
+            server: None,
+            clock_offset_ms: None,
+            round_trip_ms: None,
+        };
+        assert_eq!(timecode_at(&anchor, 1_500), "01:02:03:15");
+        assert_eq!(timecode_at(&anchor, 0), "01:02:02:00");
+    }
+}
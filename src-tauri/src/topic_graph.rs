@@ -0,0 +1,282 @@
+use std::{collections::BTreeSet, fs, path::PathBuf};
+
+use serde::{Deserialize, Serialize};
+use tauri::Manager;
+use tauri_specta::Event;
+
+use crate::export;
+use crate::log_line;
+use crate::scripting;
+use crate::sessions::{self, SessionState};
+
+/// Keyword overlap (Jaccard) at which a new chapter counts as a return to
+/// an earlier topic rather than a new one.
+const SAME_TOPIC_OVERLAP: f64 = 0.5;
+
+/// One stretch of conversation spent on a topic.
+#[derive(Serialize, Deserialize, Clone, specta::Type)]
+#[serde(rename_all = "camelCase")]
+pub struct TopicVisit {
+    /// Frontend chapter that opened the visit.
+    pub chapter_id: String,
+    pub start_ms: u64,
+    pub end_ms: Option<u64>,
+    /// First transcript segment of the visit, for retrieving its text.
+    pub first_segment: Option<u32>,
+}
+
+#[derive(Serialize, Deserialize, Clone, specta::Type)]
+#[serde(rename_all = "camelCase")]
+pub struct TopicNode {
+    pub id: String,
+    pub label: String,
+    pub keywords: Vec<String>,
+    pub visits: Vec<TopicVisit>,
+    /// Total time across visits; open visits count up to "now" (or the
+    /// session end) when the graph is read.
+    pub duration_ms: u64,
+}
+
+#[derive(Serialize, Deserialize, Clone, specta::Type)]
+#[serde(rename_all = "camelCase")]
+pub struct TopicEdge {
+    pub from: String,
+    pub to: String,
+    pub offset_ms: u64,
+}
+
+/// The conversation moved from one topic node to another. Emitted as
+/// `topic-shift`; the canvas snapshots the board when it fires.
+#[derive(Serialize, Deserialize, Clone, Debug, specta::Type, tauri_specta::Event)]
+#[serde(rename_all = "camelCase")]
+pub struct TopicShift {
+    pub session_id: String,
+    /// Label of the topic being left.
+    pub from_topic: String,
+    pub to_topic: String,
+    pub offset_ms: u64,
+}
+
+/// `<session>/topic-graph.json`.
+#[derive(Serialize, Deserialize, Clone, Default, specta::Type)]
+#[serde(rename_all = "camelCase")]
+pub struct TopicGraph {
+    pub nodes: Vec<TopicNode>,
+    pub edges: Vec<TopicEdge>,
+    pub current: Option<String>,
+}
+
+fn normalize_keywords(keywords: &[String]) -> Vec<String> {
+    keywords
+        .iter()
+        .map(|k| k.trim().to_lowercase())
+        .filter(|k| !k.is_empty())
+        .collect::<BTreeSet<_>>()
+        .into_iter()
+        .collect()
+}
+
+fn overlap(a: &[String], b: &[String]) -> f64 {
+    let a: BTreeSet<&String> = a.iter().collect();
+    let b: BTreeSet<&String> = b.iter().collect();
+    let union = a.union(&b).count();
+    if union == 0 {
+        return 0.0;
+    }
+    a.intersection(&b).count() as f64 / union as f64
+}
+
+impl TopicGraph {
+    fn node_mut(&mut self, id: &str) -> Option<&mut TopicNode> {
+        self.nodes.iter_mut().find(|node| node.id == id)
+    }
+
+    fn find_match(&self, label: &str, keywords: &[String]) -> Option<String> {
+        self.nodes
+            .iter()
+            .find(|node| node.label.eq_ignore_ascii_case(label) || overlap(&node.keywords, keywords) >= SAME_TOPIC_OVERLAP)
+            .map(|node| node.id.clone())
+    }
+
+    /// Record that the conversation moved to `label` at `offset_ms`.
+    /// Repeating a `chapter_id` (e.g. after the chapter title is refined)
+    /// relabels its node instead of adding a transition.
+    pub fn enter(
+        &mut self,
+        chapter_id: &str,
+        label: &str,
+        keywords: &[String],
+        offset_ms: u64,
+        first_segment: Option<u32>,
+    ) -> String {
+        let keywords = normalize_keywords(keywords);
+        if let Some(node) = self
+            .nodes
+            .iter_mut()
+            .find(|node| node.visits.iter().any(|visit| visit.chapter_id == chapter_id))
+        {
+            node.label = label.to_string();
+            return node.id.clone();
+        }
+
+        let target = match self.find_match(label, &keywords) {
+            Some(id) => id,
+            None => {
+                let id = format!("topic-{}", self.nodes.len() + 1);
+                self.nodes.push(TopicNode {
+                    id: id.clone(),
+                    label: label.to_string(),
+                    keywords: Vec::new(),
+                    visits: Vec::new(),
+                    duration_ms: 0,
+                });
+                id
+            }
+        };
+        if let Some(node) = self.node_mut(&target) {
+            let merged: BTreeSet<String> = node.keywords.drain(..).chain(keywords).collect();
+            node.keywords = merged.into_iter().collect();
+        }
+        if self.current.as_deref() == Some(target.as_str()) {
+            return target;
+        }
+
+        if let Some(previous) = self.current.clone() {
+            if let Some(node) = self.node_mut(&previous) {
+                if let Some(visit) = node.visits.last_mut().filter(|visit| visit.end_ms.is_none()) {
+                    visit.end_ms = Some(offset_ms.max(visit.start_ms));
+                    node.duration_ms += offset_ms.saturating_sub(visit.start_ms);
+                }
+            }
+            self.edges.push(TopicEdge {
+                from: previous,
+                to: target.clone(),
+                offset_ms,
+            });
+        }
+        if let Some(node) = self.node_mut(&target) {
+            node.visits.push(TopicVisit {
+                chapter_id: chapter_id.to_string(),
+                start_ms: offset_ms,
+                end_ms: None,
+                first_segment,
+            });
+        }
+        self.current = Some(target.clone());
+        target
+    }
+
+    /// Copy with the open visit's time up to `now_ms` added to its node.
+    pub fn with_open_visit_until(mut self, now_ms: u64) -> Self {
+        if let Some(current) = self.current.clone() {
+            if let Some(node) = self.node_mut(&current) {
+                if let Some(visit) = node.visits.last().filter(|visit| visit.end_ms.is_none()) {
+                    node.duration_ms += now_ms.saturating_sub(visit.start_ms);
+                }
+            }
+        }
+        self
+    }
+}
+
+fn graph_path(app: &tauri::AppHandle, session_id: &str) -> Result<PathBuf, String> {
+    Ok(sessions::session_dir(app, session_id)?.join("topic-graph.json"))
+}
+
+pub fn read_graph(app: &tauri::AppHandle, session_id: &str) -> TopicGraph {
+    graph_path(app, session_id)
+        .ok()
+        .and_then(|path| fs::read_to_string(path).ok())
+        .and_then(|content| serde_json::from_str(&content).ok())
+        .unwrap_or_default()
+}
+
+/// Called by the topic tracker whenever it opens (or retitles) a chapter.
+/// Returns the id of the topic node the chapter belongs to.
+#[tauri::command]
+#[specta::specta]
+pub fn record_topic(
+    app: tauri::AppHandle,
+    state: tauri::State<'_, SessionState>,
+    chapter_id: String,
+    label: String,
+    keywords: Vec<String>,
+) -> Result<String, String> {
+    let moment = state
+        .current_moment()
+        .ok_or_else(|| "no active session".to_string())?;
+    let mut graph = read_graph(&app, &moment.session_id);
+    let previous = graph.current.clone();
+    let node_id = graph.enter(
+        &chapter_id,
+        label.trim(),
+        &keywords,
+        moment.offset_ms,
+        moment.last_segment.as_ref().map(|segment| segment.index + 1),
+    );
+    let content = serde_json::to_string_pretty(&graph)
+        .map_err(|err| format!("failed to format topic graph: {}", err))?;
+    fs::write(graph_path(&app, &moment.session_id)?, content.as_bytes())
+        .map_err(|err| format!("failed to write topic graph: {}", err))?;
+    log_line(&format!("[topics] {} -> {} ({})", moment.session_id, node_id, label.trim()));
+    let label_of = |id: &str| graph.nodes.iter().find(|node| node.id == id).map(|node| node.label.clone());
+    if let Some(from_topic) = previous.filter(|id| *id != node_id).and_then(|id| label_of(&id)) {
+        let shift = TopicShift {
+            session_id: moment.session_id.clone(),
+            from_topic,
+            to_topic: label_of(&node_id).unwrap_or_default(),
+            offset_ms: moment.offset_ms,
+        };
+        if let Ok(payload) = serde_json::to_value(&shift) {
+            scripting::fire(&app, scripting::ON_TOPIC_SHIFT, payload);
+        }
+        let _ = shift.emit(&app);
+    }
+    Ok(node_id)
+}
+
+#[tauri::command]
+#[specta::specta]
+pub fn get_topic_graph(app: tauri::AppHandle, session_id: String) -> Result<TopicGraph, String> {
+    let graph = read_graph(&app, &session_id);
+    let active = app.state::<SessionState>().current_moment();
+    let now_ms = match active {
+        Some(moment) if moment.session_id == session_id => moment.offset_ms,
+        _ => {
+            let info = export::load_session(&app, &session_id)?;
+            info.ended_at
+                .map(|ended| ended.saturating_sub(info.started_at))
+                .unwrap_or_default()
+        }
+    };
+    Ok(graph.with_open_visit_until(now_ms))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::TopicGraph;
+
+    fn words(list: &[&str]) -> Vec<String> {
+        list.iter().map(|w| w.to_string()).collect()
+    }
+
+    #[test]
+    fn returns_to_matching_topic_and_tracks_durations() {
+        let mut graph = TopicGraph::default();
+        let rust = graph.enter("c1", "Rust async", &words(&["rust", "tokio", "async"]), 0, Some(0));
+        let gpus = graph.enter("c2", "GPU prices", &words(&["gpu", "nvidia"]), 60_000, Some(4));
+        let back = graph.enter("c3", "Back to tokio", &words(&["tokio", "async", "runtime"]), 90_000, Some(7));
+        graph.enter("c2", "GPU pricing", &[], 95_000, None);
+
+        assert_eq!(back, rust);
+        assert_eq!(graph.nodes.len(), 2);
+        assert_eq!(graph.edges.len(), 2);
+        assert_eq!(graph.edges[1].from, gpus);
+        assert_eq!(graph.nodes[1].label, "GPU pricing");
+        assert_eq!(graph.nodes[1].duration_ms, 30_000);
+
+        let graph = graph.with_open_visit_until(100_000);
+        assert_eq!(graph.nodes[0].duration_ms, 70_000);
+        assert_eq!(graph.nodes[0].visits.len(), 2);
+    }
+}
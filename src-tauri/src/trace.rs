@@ -0,0 +1,62 @@
+//! Trace IDs tie one user interaction (a voice command, a follow-up) to
+//! every log line, agent run, MCP tool call and event it causes, so its
+//! path through the Rust log, agent stderr and server logs can be followed.
+
+use std::cell::RefCell;
+use std::sync::atomic::{AtomicU32, Ordering};
+
+use crate::now_millis;
+
+thread_local! {
+    static CURRENT: RefCell<Option<String>> = const { RefCell::new(None) };
+}
+
+static COUNTER: AtomicU32 = AtomicU32::new(0);
+
+/// A short ID that is unique for this run of the app and sorts by time.
+pub fn new_id() -> String {
+    let sequence = COUNTER.fetch_add(1, Ordering::Relaxed) & 0xffff;
+    format!("{:x}-{:04x}", now_millis(), sequence)
+}
+
+/// Trace IDs come from the webview; keep them short and log-safe.
+pub fn sanitize(id: &str) -> Option<String> {
+    let id: String = id
+        .trim()
+        .chars()
+        .filter(|c| c.is_ascii_alphanumeric() || *c == '-' || *c == '_')
+        .take(64)
+        .collect();
+    (!id.is_empty()).then_some(id)
+}
+
+/// The trace this thread is working for, if any.
+pub fn current() -> Option<String> {
+    CURRENT.with(|current| current.borrow().clone())
+}
+
+/// Run `f` with `id` as this thread's trace, restoring the previous one.
+pub fn scope<T>(id: &str, f: impl FnOnce() -> T) -> T {
+    let previous = CURRENT.with(|current| current.replace(Some(id.to_string())));
+    let result = f();
+    CURRENT.with(|current| *current.borrow_mut() = previous);
+    result
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{current, new_id, sanitize, scope};
+
+    #[test]
+    fn scopes_nest_and_ids_are_unique() {
+        assert_ne!(new_id(), new_id());
+        assert_eq!(current(), None);
+        scope("outer", || {
+            scope("inner", || assert_eq!(current().as_deref(), Some("inner")));
+            assert_eq!(current().as_deref(), Some("outer"));
+        });
+        assert_eq!(current(), None);
+        assert_eq!(sanitize(" abc-12\n]x ").as_deref(), Some("abc-12x"));
+        assert_eq!(sanitize("  "), None);
+    }
+}
@@ -0,0 +1,328 @@
+use std::fs;
+use std::path::Path;
+use std::process::Command;
+
+use serde::{Deserialize, Serialize};
+use serde_json::Value as JsonValue;
+
+use crate::transcription_queue::{self, Lane};
+use crate::{
+    clean_transcript_fragment, language, log_line, power, redaction, settings, system_load, truncate_for_log, vad,
+    whisper_models,
+};
+
+/// One whisper segment as decoded, before cleaning.
+pub struct RawSegment {
+    pub start_ms: u64,
+    pub end_ms: u64,
+    pub text: String,
+    /// Special tokens included.
+    pub tokens: Vec<RawToken>,
+    pub no_speech_prob: Option<f32>,
+}
+
+/// One decoded token. whisper leaves the times unset when it didn't
+/// compute token timestamps.
+pub struct RawToken {
+    pub text: String,
+    pub probability: f32,
+    pub start_ms: Option<u64>,
+    pub end_ms: Option<u64>,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, specta::Type)]
+#[serde(rename_all = "camelCase")]
+pub struct TokenConfidence {
+    pub text: String,
+    pub probability: f32,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, specta::Type)]
+#[serde(rename_all = "camelCase")]
+pub struct DetailedSegment {
+    pub start_ms: u64,
+    pub end_ms: u64,
+    pub text: String,
+    /// Mean natural log of the token probabilities; closer to 0 is surer.
+    pub avg_logprob: f32,
+    /// Only reported by some whisper builds.
+    pub no_speech_prob: Option<f32>,
+    /// For highlighting low-confidence words.
+    pub tokens: Vec<TokenConfidence>,
+    /// For highlighting each word as the audio plays.
+    pub words: Vec<WordTiming>,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, specta::Type)]
+#[serde(rename_all = "camelCase")]
+pub struct WordTiming {
+    pub text: String,
+    pub start_ms: u64,
+    pub end_ms: u64,
+    /// Lowest probability among its tokens.
+    pub probability: f32,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, Default, PartialEq, specta::Type)]
+#[serde(rename_all = "camelCase")]
+pub struct DetailedTranscript {
+    /// The same text `transcribe_audio` returns.
+    pub text: String,
+    pub segments: Vec<DetailedSegment>,
+}
+
+/// `[_BEG_]`, `[_TT_150]`, `<|endoftext|>` and friends.
+fn is_special_token(text: &str) -> bool {
+    let text = text.trim();
+    (text.starts_with("[_") && text.ends_with(']')) || (text.starts_with("<|") && text.ends_with("|>"))
+}
+
+/// Join tokens into words: a token starting with a space starts a new
+/// word, anything else (word pieces, punctuation) continues the last one.
+/// Missing or out-of-range times are clamped to the segment.
+fn words_of(tokens: &[&RawToken], start_ms: u64, end_ms: u64) -> Vec<WordTiming> {
+    let end_ms = end_ms.max(start_ms);
+    let mut words: Vec<WordTiming> = Vec::new();
+    for token in tokens {
+        let from = token.start_ms.unwrap_or(start_ms).clamp(start_ms, end_ms);
+        let to = token.end_ms.unwrap_or(from).clamp(from, end_ms);
+        match words.last_mut() {
+            Some(word) if !token.text.starts_with(char::is_whitespace) => {
+                word.text.push_str(&token.text);
+                word.end_ms = word.end_ms.max(to);
+                word.probability = word.probability.min(token.probability);
+            }
+            _ => words.push(WordTiming {
+                text: token.text.trim_start().to_string(),
+                start_ms: from,
+                end_ms: to,
+                probability: token.probability,
+            }),
+        }
+    }
+    words.retain(|word| !word.text.trim().is_empty());
+    for word in &mut words {
+        word.text = word.text.trim_end().to_string();
+    }
+    words
+}
+
+fn to_detailed(raw: Vec<RawSegment>) -> DetailedTranscript {
+    let segments: Vec<DetailedSegment> = raw
+        .into_iter()
+        .filter_map(|segment| {
+            let text = clean_transcript_fragment(segment.text.trim())?;
+            let spoken: Vec<&RawToken> = segment
+                .tokens
+                .iter()
+                .filter(|token| !token.text.trim().is_empty() && !is_special_token(&token.text))
+                .collect();
+            // The segment text is already redacted; mask words one by one too.
+            let mut words = words_of(&spoken, segment.start_ms, segment.end_ms);
+            for word in &mut words {
+                word.text = redaction::apply(&word.text).into_owned();
+            }
+            let tokens: Vec<TokenConfidence> = spoken
+                .into_iter()
+                .map(|token| TokenConfidence {
+                    text: redaction::apply(&token.text).into_owned(),
+                    probability: token.probability,
+                })
+                .collect();
+            let avg_logprob = if tokens.is_empty() {
+                0.0
+            } else {
+                tokens.iter().map(|token| token.probability.max(f32::MIN_POSITIVE).ln()).sum::<f32>()
+                    / tokens.len() as f32
+            };
+            Some(DetailedSegment {
+                start_ms: segment.start_ms,
+                end_ms: segment.end_ms,
+                text,
+                avg_logprob,
+                no_speech_prob: segment.no_speech_prob,
+                tokens,
+                words,
+            })
+        })
+        .collect();
+    DetailedTranscript {
+        text: segments.iter().map(|segment| segment.text.as_str()).collect::<Vec<_>>().join(" "),
+        segments,
+    }
+}
+
+/// Read the `-ojf` (full JSON) output of `whisper-cli`.
+fn parse_cli_json(content: &str) -> Result<Vec<RawSegment>, String> {
+    let json: JsonValue =
+        serde_json::from_str(content).map_err(|err| format!("invalid whisper-cli JSON: {}", err))?;
+    let segments = json
+        .get("transcription")
+        .and_then(JsonValue::as_array)
+        .ok_or_else(|| "whisper-cli JSON has no transcription".to_string())?;
+    Ok(segments
+        .iter()
+        .map(|segment| {
+            let offset = |key: &str| segment.pointer(&format!("/offsets/{}", key)).and_then(JsonValue::as_u64).unwrap_or(0);
+            // Unset token times come out negative.
+            let token_offset = |token: &JsonValue, key: &str| {
+                token
+                    .pointer(&format!("/offsets/{}", key))
+                    .and_then(JsonValue::as_i64)
+                    .and_then(|ms| u64::try_from(ms).ok())
+            };
+            let tokens = segment
+                .get("tokens")
+                .and_then(JsonValue::as_array)
+                .map(|tokens| {
+                    tokens
+                        .iter()
+                        .map(|token| RawToken {
+                            text: token.get("text").and_then(JsonValue::as_str).unwrap_or_default().to_string(),
+                            probability: token.get("p").and_then(JsonValue::as_f64).unwrap_or(0.0) as f32,
+                            start_ms: token_offset(token, "from"),
+                            end_ms: token_offset(token, "to"),
+                        })
+                        .collect()
+                })
+                .unwrap_or_default();
+            RawSegment {
+                start_ms: offset("from"),
+                end_ms: offset("to"),
+                text: segment.get("text").and_then(JsonValue::as_str).unwrap_or_default().to_string(),
+                tokens,
+                no_speech_prob: segment.get("no_speech_prob").and_then(JsonValue::as_f64).map(|p| p as f32),
+            }
+        })
+        .collect())
+}
+
+fn transcribe_raw_locally(
+    wav_bytes: &[u8],
+    model_path: &Path,
+    language: &str,
+    prompt: Option<&str>,
+) -> Result<Vec<RawSegment>, String> {
+    #[cfg(feature = "whisper-rs")]
+    match crate::whisper_engine::decode_wav(wav_bytes)
+        .and_then(|samples| crate::whisper_engine::transcribe_detailed(model_path, &samples, language, prompt))
+    {
+        Ok(raw) => return Ok(raw),
+        Err(err) => log_line(&format!("[whisper-rs] {}; falling back to whisper-cli", err)),
+    }
+
+    let cli_path = crate::resolve_whisper_cli()?;
+    let wav_file = crate::temp_files::write_wav(wav_bytes)?;
+    let output_base = wav_file.path().with_extension("");
+    let json_file = wav_file.sibling("json");
+    let mut command = Command::new(&cli_path);
+    crate::acceleration::apply_cli_flags(&mut command);
+    command
+        .arg("-m")
+        .arg(model_path)
+        .arg("-l")
+        .arg(language)
+        .arg("-f")
+        .arg(wav_file.path())
+        .arg("-ojf")
+        .arg("-of")
+        .arg(&output_base)
+        .arg("-np");
+    if let Some(prompt) = prompt {
+        command.arg("--prompt").arg(prompt);
+    }
+    let output = command
+        .output()
+        .map_err(|err| format!("failed to run whisper-cli: {}", err));
+    drop(wav_file);
+    let json = fs::read_to_string(json_file.path());
+    drop(json_file);
+    let output = output?;
+    if !output.status.success() {
+        let stderr = String::from_utf8_lossy(&output.stderr);
+        return Err(whisper_models::explain_load_failure(&stderr, model_path)
+            .unwrap_or_else(|| format!("whisper-cli failed: {}", truncate_for_log(stderr.trim(), 300))));
+    }
+    parse_cli_json(&json.map_err(|err| format!("whisper-cli wrote no JSON: {}", err))?)
+}
+
+/// Like `transcribe_audio`, but returns each segment with its timestamps,
+/// confidence and word timings instead of one joined string, so the
+/// transcript can be synced with playback and unsure words highlighted.
+/// Always local.
+#[tauri::command]
+#[specta::specta]
+pub async fn transcribe_audio_detailed(
+    app: tauri::AppHandle,
+    audio_base64: String,
+    language: Option<String>,
+) -> Result<DetailedTranscript, String> {
+    let wav_bytes = crate::decode_audio_payload(&audio_base64).await?;
+    if !vad::wav_has_speech(&wav_bytes) {
+        return Ok(DetailedTranscript::default());
+    }
+    let settings = settings::load_settings(&app);
+    let language = language::whisper_language(language.as_deref().unwrap_or(&settings.transcription.language));
+    let prompt = settings.transcription.whisper_prompt();
+    let model = system_load::current(&app)
+        .and_then(|pressure| pressure.whisper_model)
+        .or(power::current(&app).whisper_model);
+    let model_path = whisper_models::resolve(&app, model.as_deref())?;
+    log_line(&format!(
+        "transcribe_audio_detailed called (payload bytes: {})",
+        audio_base64.len()
+    ));
+    let _permit = transcription_queue::acquire(&app, Lane::Batch).await;
+    let raw = tauri::async_runtime::spawn_blocking(move || {
+        whisper_models::ensure_intact(&app, &model_path)?;
+        transcribe_raw_locally(&wav_bytes, &model_path, &language, prompt.as_deref())
+    })
+    .await
+    .map_err(|err| format!("transcription task failed: {}", err))??;
+    Ok(to_detailed(raw))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{parse_cli_json, to_detailed, WordTiming};
+
+    #[test]
+    fn reads_segments_and_token_confidence_from_cli_json() {
+        let json = r#"{"transcription":[
+            {"offsets":{"from":0,"to":2400},"text":" Hello world.","tokens":[
+                {"text":"[_BEG_]","p":0.9},{"text":" Hello","p":0.5},{"text":" world.","p":0.25}]},
+            {"offsets":{"from":2400,"to":4000},"text":" [Music]","tokens":[{"text":" [Music]","p":0.8}]}
+        ]}"#;
+        let detailed = to_detailed(parse_cli_json(json).unwrap());
+        assert_eq!(detailed.text, "Hello world.");
+        assert_eq!(detailed.segments.len(), 1);
+        let segment = &detailed.segments[0];
+        assert_eq!((segment.start_ms, segment.end_ms), (0, 2400));
+        assert_eq!(segment.tokens.len(), 2);
+        assert!((segment.avg_logprob - (0.5f32.ln() + 0.25f32.ln()) / 2.0).abs() < 1e-6);
+        assert_eq!(segment.no_speech_prob, None);
+    }
+
+    #[test]
+    fn joins_word_pieces_and_punctuation_into_timed_words() {
+        let json = r#"{"transcription":[
+            {"offsets":{"from":1000,"to":3000},"text":" Hello world.","tokens":[
+                {"text":"[_BEG_]","p":0.9,"offsets":{"from":1000,"to":1000}},
+                {"text":" Hel","p":0.9,"offsets":{"from":1000,"to":1300}},
+                {"text":"lo","p":0.6,"offsets":{"from":1300,"to":1600}},
+                {"text":" world","p":0.8,"offsets":{"from":1700,"to":2500}},
+                {"text":".","p":0.7,"offsets":{"from":-10,"to":-10}}]}
+        ]}"#;
+        let detailed = to_detailed(parse_cli_json(json).unwrap());
+        let word = |text: &str, start_ms, end_ms, probability| WordTiming {
+            text: text.to_string(),
+            start_ms,
+            end_ms,
+            probability,
+        };
+        assert_eq!(
+            detailed.segments[0].words,
+            vec![word("Hello", 1000, 1600, 0.6), word("world.", 1700, 2500, 0.7)]
+        );
+    }
+}
@@ -0,0 +1,243 @@
+//! User-editable rules for dropping whisper hallucinations and non-speech
+//! markers (`transcript-filters.json` in the config dir). The rules for the
+//! configured transcription language are compiled once and consulted by
+//! `clean_transcript_fragment`.
+
+use std::collections::{BTreeMap, HashSet};
+use std::fs;
+use std::path::PathBuf;
+use std::sync::{Arc, OnceLock, RwLock};
+
+use regex::{Regex, RegexBuilder};
+use serde::{Deserialize, Serialize};
+use tauri::Manager;
+
+use crate::{log_line, normalize_fragment_tokens, settings};
+
+const FILTERS_FILE: &str = "transcript-filters.json";
+
+/// One set of blocklists. Words and phrases are compared lowercased, with
+/// brackets and punctuation stripped (`[BLANK_AUDIO]` is `blank_audio`).
+#[derive(Serialize, Deserialize, Clone, Debug, Default, PartialEq, specta::Type)]
+#[serde(rename_all = "camelCase", default)]
+pub struct FilterRules {
+    /// Whole fragments to drop.
+    pub phrases: Vec<String>,
+    /// Fragments made only of these words are dropped, however long.
+    pub noise_words: Vec<String>,
+    /// Fragments of at most `maxFillerWords` words, all from this list,
+    /// are dropped.
+    pub filler_words: Vec<String>,
+    /// Case-insensitive regexes; a fragment matching any is dropped.
+    pub patterns: Vec<String>,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, specta::Type)]
+#[serde(rename_all = "camelCase", default)]
+pub struct TranscriptFilters {
+    /// Applied in every language.
+    pub rules: FilterRules,
+    pub max_filler_words: u32,
+    /// Fragments with fewer letters and digits are dropped; 0 keeps all.
+    pub min_chars: u32,
+    /// Extra rules by whisper language code (e.g. `de`), applied when
+    /// transcribing in that language or with auto-detect.
+    pub languages: BTreeMap<String, FilterRules>,
+}
+
+impl Default for TranscriptFilters {
+    fn default() -> Self {
+        let words = |list: &[&str]| list.iter().map(|word| word.to_string()).collect();
+        Self {
+            rules: FilterRules {
+                phrases: words(&[
+                    "blank_audio",
+                    "blank audio",
+                    "music",
+                    "inaudible",
+                    "speaking in a foreign language",
+                    "foreign language",
+                ]),
+                noise_words: words(&["inaudible", "music"]),
+                filler_words: words(&["you"]),
+                patterns: Vec::new(),
+            },
+            max_filler_words: 3,
+            min_chars: 0,
+            languages: BTreeMap::new(),
+        }
+    }
+}
+
+/// The rules in effect, ready to match.
+pub struct ActiveFilters {
+    phrases: HashSet<String>,
+    noise_words: HashSet<String>,
+    filler_words: HashSet<String>,
+    patterns: Vec<Regex>,
+    max_filler_words: usize,
+    min_chars: usize,
+}
+
+impl ActiveFilters {
+    /// `words` as returned by `normalize_fragment_tokens`.
+    pub fn is_non_speech(&self, words: &[String]) -> bool {
+        self.phrases.contains(&words.join(" ")) || words.iter().all(|word| self.noise_words.contains(word))
+    }
+
+    /// `text` is the whitespace-collapsed fragment.
+    pub fn is_low_information(&self, text: &str, words: &[String]) -> bool {
+        if words.len() <= self.max_filler_words && words.iter().all(|word| self.filler_words.contains(word)) {
+            return true;
+        }
+        if text.chars().filter(|c| c.is_alphanumeric()).count() < self.min_chars {
+            return true;
+        }
+        self.patterns.iter().any(|pattern| pattern.is_match(text))
+    }
+}
+
+fn compile_pattern(pattern: &str) -> Result<Regex, String> {
+    RegexBuilder::new(pattern)
+        .case_insensitive(true)
+        .build()
+        .map_err(|err| format!("invalid pattern `{}`: {}", pattern, err))
+}
+
+/// Rules for `language` (`auto` takes every language's). Invalid patterns
+/// are logged and skipped.
+fn compile(filters: &TranscriptFilters, language: &str) -> ActiveFilters {
+    let language = language.trim().to_lowercase();
+    let mut active = ActiveFilters {
+        phrases: HashSet::new(),
+        noise_words: HashSet::new(),
+        filler_words: HashSet::new(),
+        patterns: Vec::new(),
+        max_filler_words: filters.max_filler_words as usize,
+        min_chars: filters.min_chars as usize,
+    };
+    let extra = filters
+        .languages
+        .iter()
+        .filter(|(code, _)| language.is_empty() || language == "auto" || code.trim().to_lowercase() == language)
+        .map(|(_, rules)| rules);
+    let normalize = |text: &String| normalize_fragment_tokens(text).join(" ");
+    for rules in std::iter::once(&filters.rules).chain(extra) {
+        active.phrases.extend(rules.phrases.iter().map(normalize).filter(|phrase| !phrase.is_empty()));
+        active.noise_words.extend(rules.noise_words.iter().map(normalize).filter(|word| !word.is_empty()));
+        active.filler_words.extend(rules.filler_words.iter().map(normalize).filter(|word| !word.is_empty()));
+        for pattern in &rules.patterns {
+            match compile_pattern(pattern) {
+                Ok(regex) => active.patterns.push(regex),
+                Err(err) => log_line(&format!("[transcript-filters] {}", err)),
+            }
+        }
+    }
+    active
+}
+
+fn active_slot() -> &'static RwLock<Arc<ActiveFilters>> {
+    static ACTIVE: OnceLock<RwLock<Arc<ActiveFilters>>> = OnceLock::new();
+    ACTIVE.get_or_init(|| RwLock::new(Arc::new(compile(&TranscriptFilters::default(), "auto"))))
+}
+
+/// The filters in effect; the built-in ones until `reload` runs.
+pub fn active() -> Arc<ActiveFilters> {
+    active_slot().read().unwrap().clone()
+}
+
+fn filters_path(app: &tauri::AppHandle) -> Result<PathBuf, String> {
+    let dir = app
+        .path()
+        .app_config_dir()
+        .map_err(|err| format!("failed to resolve app config dir: {}", err))?;
+    fs::create_dir_all(&dir).map_err(|err| format!("failed to create config dir: {}", err))?;
+    Ok(dir.join(FILTERS_FILE))
+}
+
+/// The saved filters, or the built-in ones when there are none or they
+/// can't be read.
+fn load(app: &tauri::AppHandle) -> TranscriptFilters {
+    let Ok(path) = filters_path(app) else {
+        return TranscriptFilters::default();
+    };
+    match fs::read_to_string(&path) {
+        Ok(content) => serde_json::from_str(&content).unwrap_or_else(|err| {
+            log_line(&format!(
+                "[transcript-filters] failed to parse {}: {} (using defaults)",
+                path.display(),
+                err
+            ));
+            TranscriptFilters::default()
+        }),
+        Err(_) => TranscriptFilters::default(),
+    }
+}
+
+/// Recompile the filters from disk for the configured transcription
+/// language. Called at startup and whenever filters or settings are saved.
+pub fn reload(app: &tauri::AppHandle) {
+    let language = settings::load_settings(app).transcription.language;
+    *active_slot().write().unwrap() = Arc::new(compile(&load(app), &language));
+}
+
+#[tauri::command]
+#[specta::specta]
+pub fn get_transcript_filters(app: tauri::AppHandle) -> TranscriptFilters {
+    load(&app)
+}
+
+/// Save the filters and apply them to transcription from now on. Rejects
+/// invalid regexes.
+#[tauri::command]
+#[specta::specta]
+pub fn save_transcript_filters(app: tauri::AppHandle, filters: TranscriptFilters) -> Result<TranscriptFilters, String> {
+    for pattern in std::iter::once(&filters.rules)
+        .chain(filters.languages.values())
+        .flat_map(|rules| &rules.patterns)
+    {
+        compile_pattern(pattern)?;
+    }
+    let path = filters_path(&app)?;
+    let pretty =
+        serde_json::to_string_pretty(&filters).map_err(|err| format!("failed to format transcript filters: {}", err))?;
+    fs::write(&path, pretty.as_bytes()).map_err(|err| format!("failed to write transcript filters: {}", err))?;
+    reload(&app);
+    Ok(filters)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{compile, FilterRules, TranscriptFilters};
+    use crate::normalize_fragment_tokens;
+
+    #[test]
+    fn applies_language_rules_patterns_and_min_length() {
+        let mut filters = TranscriptFilters {
+            min_chars: 3,
+            ..TranscriptFilters::default()
+        };
+        filters.languages.insert(
+            "de".to_string(),
+            FilterRules {
+                phrases: vec!["Untertitel der Amara.org-Community".to_string()],
+                patterns: vec![r"^untertitel (im auftrag|von)\b".to_string(), "(".to_string()],
+                ..FilterRules::default()
+            },
+        );
+        let drops = |language: &str, text: &str| {
+            let active = compile(&filters, language);
+            let words = normalize_fragment_tokens(text);
+            active.is_non_speech(&words) || active.is_low_information(text, &words)
+        };
+
+        assert!(drops("de", "[Untertitel der Amara.org-Community]"));
+        assert!(drops("auto", "Untertitel im Auftrag des ZDF"));
+        assert!(!drops("en", "Untertitel der Amara.org-Community"));
+        assert!(drops("en", "[MUSIC] [inaudible]"));
+        assert!(drops("en", "you you"));
+        assert!(!drops("en", "you you you you"));
+        assert!(drops("en", "Hm."));
+        assert!(!drops("en", "Yes, pricing."));
+    }
+}
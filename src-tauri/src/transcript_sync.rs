@@ -0,0 +1,188 @@
+//! Snapshot + diff sync of the live transcript for consumers outside the
+//! webview. Every add, revise or delete gets a sequence number; a consumer
+//! asks for the changes after the last number it applied and gets them as
+//! ops, or a full snapshot when it's on another session or has fallen
+//! behind the retained ops, so a reconnect mid-session always recovers.
+
+use std::collections::{BTreeMap, VecDeque};
+use std::sync::Mutex;
+
+use serde::{Deserialize, Serialize};
+use tauri::Manager;
+
+use crate::sessions::TranscriptSegment;
+
+/// Ops kept for catching up; older gaps are answered with a snapshot.
+const MAX_OPS: usize = 1000;
+
+#[derive(Serialize, Deserialize, Clone, Copy, Debug, PartialEq, Eq)]
+#[serde(rename_all = "kebab-case")]
+pub enum SyncOpKind {
+    Add,
+    Revise,
+    Delete,
+}
+
+#[derive(Serialize, Deserialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct SyncOp {
+    pub seq: u64,
+    pub kind: SyncOpKind,
+    pub index: u32,
+    /// Missing for deletes.
+    pub segment: Option<TranscriptSegment>,
+}
+
+/// Reply to "changes since `seq`". Apply a diff's ops in order; replace
+/// everything with a snapshot. Either way, ask again with the new `seq`.
+#[derive(Serialize, Deserialize, Clone)]
+#[serde(tag = "kind", rename_all = "camelCase", rename_all_fields = "camelCase")]
+pub enum SyncReply {
+    Diff {
+        session_id: String,
+        seq: u64,
+        ops: Vec<SyncOp>,
+    },
+    Snapshot {
+        session_id: Option<String>,
+        seq: u64,
+        segments: Vec<TranscriptSegment>,
+    },
+}
+
+/// The transcript of the latest session and the ops that built it. It
+/// outlives the session so late consumers still get the final state.
+#[derive(Default)]
+struct Journal {
+    session_id: Option<String>,
+    /// Keeps counting across sessions, so a stale `seq` can't match.
+    seq: u64,
+    segments: BTreeMap<u32, TranscriptSegment>,
+    ops: VecDeque<SyncOp>,
+}
+
+impl Journal {
+    fn reset(&mut self, session_id: String) {
+        self.session_id = Some(session_id);
+        self.segments.clear();
+        self.ops.clear();
+    }
+
+    fn push(&mut self, kind: SyncOpKind, index: u32, segment: Option<TranscriptSegment>) {
+        self.seq += 1;
+        match &segment {
+            Some(segment) => self.segments.insert(index, segment.clone()),
+            None => self.segments.remove(&index),
+        };
+        self.ops.push_back(SyncOp {
+            seq: self.seq,
+            kind,
+            index,
+            segment,
+        });
+        while self.ops.len() > MAX_OPS {
+            self.ops.pop_front();
+        }
+    }
+
+    fn changes_since(&self, session_id: Option<&str>, since: u64) -> SyncReply {
+        let first = self.ops.front().map(|op| op.seq).unwrap_or(self.seq + 1);
+        match self.session_id.as_deref() {
+            Some(current) if session_id == Some(current) && since <= self.seq && since + 1 >= first => {
+                SyncReply::Diff {
+                    session_id: current.to_string(),
+                    seq: self.seq,
+                    ops: self.ops.iter().filter(|op| op.seq > since).cloned().collect(),
+                }
+            }
+            _ => SyncReply::Snapshot {
+                session_id: self.session_id.clone(),
+                seq: self.seq,
+                segments: self.segments.values().cloned().collect(),
+            },
+        }
+    }
+}
+
+#[derive(Default)]
+pub struct TranscriptSyncState {
+    journal: Mutex<Journal>,
+}
+
+/// Start journaling a new session.
+pub fn reset(app: &tauri::AppHandle, session_id: &str) {
+    app.state::<TranscriptSyncState>()
+        .journal
+        .lock()
+        .unwrap()
+        .reset(session_id.to_string());
+}
+
+pub fn record(app: &tauri::AppHandle, kind: SyncOpKind, index: u32, segment: Option<TranscriptSegment>) {
+    app.state::<TranscriptSyncState>()
+        .journal
+        .lock()
+        .unwrap()
+        .push(kind, index, segment);
+}
+
+/// What a consumer on `session_id` that applied everything up to `since`
+/// is missing.
+pub fn changes_since(app: &tauri::AppHandle, session_id: Option<&str>, since: u64) -> SyncReply {
+    app.state::<TranscriptSyncState>()
+        .journal
+        .lock()
+        .unwrap()
+        .changes_since(session_id, since)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{Journal, SyncOpKind, SyncReply, MAX_OPS};
+    use crate::sessions::TranscriptSegment;
+
+    fn segment(index: u32, text: &str) -> Option<TranscriptSegment> {
+        Some(TranscriptSegment {
+            index,
+            offset_ms: u64::from(index) * 1000,
+            text: text.to_string(),
+            source: None,
+        })
+    }
+
+    #[test]
+    fn sends_diffs_to_caught_up_consumers_and_snapshots_after_gaps() {
+        let mut journal = Journal::default();
+        journal.reset("s1".to_string());
+        journal.push(SyncOpKind::Add, 0, segment(0, "hello"));
+        journal.push(SyncOpKind::Add, 1, segment(1, "wrold"));
+        journal.push(SyncOpKind::Revise, 1, segment(1, "world"));
+        journal.push(SyncOpKind::Delete, 0, None);
+
+        match journal.changes_since(Some("s1"), 2) {
+            SyncReply::Diff { seq, ops, .. } => {
+                assert_eq!(seq, 4);
+                assert_eq!(ops.iter().map(|op| op.kind).collect::<Vec<_>>(), [SyncOpKind::Revise, SyncOpKind::Delete]);
+            }
+            SyncReply::Snapshot { .. } => panic!("expected a diff"),
+        }
+        assert!(matches!(journal.changes_since(Some("s1"), 4), SyncReply::Diff { ref ops, .. } if ops.is_empty()));
+
+        // A new consumer, another session or a seq from the future resyncs.
+        for (session, since) in [(None, 0), (Some("s0"), 2), (Some("s1"), 9)] {
+            match journal.changes_since(session, since) {
+                SyncReply::Snapshot { seq, segments, .. } => {
+                    assert_eq!(seq, 4);
+                    assert_eq!(segments.len(), 1);
+                    assert_eq!(segments[0].text, "world");
+                }
+                SyncReply::Diff { .. } => panic!("expected a snapshot"),
+            }
+        }
+
+        for index in 2..(MAX_OPS as u32 + 10) {
+            journal.push(SyncOpKind::Add, index, segment(index, "more"));
+        }
+        assert!(matches!(journal.changes_since(Some("s1"), 4), SyncReply::Snapshot { .. }));
+    }
+}
@@ -0,0 +1,253 @@
+//! Alternative transcripts of a stored session, made by running its kept
+//! segment audio through another model or other decoding options. Each
+//! run is a version in `<session>/transcripts/<id>.jsonl`, listed in
+//! `<session>/transcripts/index.json`; the live `transcript.jsonl` is
+//! left as it was. Runs are resumable jobs: an interrupted one continues
+//! after the last segment it finished.
+
+use std::collections::HashMap;
+use std::fs;
+use std::path::PathBuf;
+
+use serde::{Deserialize, Serialize};
+
+use crate::jobs::{self, JobSpec};
+use crate::preflight::{self, PreflightOperation};
+use crate::sessions::{self, TranscriptSegment};
+use crate::settings::{self, TranscriptionOptions};
+use crate::transcription_queue::{self, Lane};
+use crate::{language, log_line, now_millis, session_audio, trace, whisper_models};
+
+const VERSIONS_DIR: &str = "transcripts";
+const INDEX_FILE: &str = "index.json";
+
+#[derive(Serialize, Deserialize, Clone, Debug, specta::Type)]
+#[serde(rename_all = "camelCase")]
+pub struct TranscriptVersion {
+    pub id: String,
+    /// Model file it was transcribed with, e.g. `ggml-large-v3.bin`.
+    pub model: String,
+    pub language: String,
+    pub options: TranscriptionOptions,
+    pub created_at: u64,
+    pub segment_count: u32,
+}
+
+/// What a retranscription job has done so far.
+#[derive(Serialize, Deserialize, Clone, Debug)]
+#[serde(rename_all = "camelCase")]
+struct Checkpoint {
+    options: TranscriptionOptions,
+    /// Text of each finished segment, by offset.
+    done: Vec<(u64, String)>,
+}
+
+/// Segments from per-clip `texts`, skipping empty ones and taking each
+/// track name from the live segment at the same offset.
+fn assemble(texts: &[(u64, String)], live: &[TranscriptSegment]) -> Vec<TranscriptSegment> {
+    let sources: HashMap<u64, &Option<String>> = live.iter().map(|segment| (segment.offset_ms, &segment.source)).collect();
+    texts
+        .iter()
+        .filter(|(_, text)| !text.trim().is_empty())
+        .enumerate()
+        .map(|(index, (offset_ms, text))| TranscriptSegment {
+            index: index as u32,
+            offset_ms: *offset_ms,
+            text: text.trim().to_string(),
+            source: sources.get(offset_ms).and_then(|source| (*source).clone()),
+        })
+        .collect()
+}
+
+fn versions_dir(app: &tauri::AppHandle, session_id: &str) -> Result<PathBuf, String> {
+    Ok(sessions::session_dir(app, session_id)?.join(VERSIONS_DIR))
+}
+
+fn load_index(app: &tauri::AppHandle, session_id: &str) -> Vec<TranscriptVersion> {
+    versions_dir(app, session_id)
+        .ok()
+        .and_then(|dir| fs::read_to_string(dir.join(INDEX_FILE)).ok())
+        .and_then(|content| serde_json::from_str(&content).ok())
+        .unwrap_or_default()
+}
+
+fn save_version(
+    app: &tauri::AppHandle,
+    session_id: &str,
+    version: &TranscriptVersion,
+    segments: &[TranscriptSegment],
+) -> Result<(), String> {
+    let dir = versions_dir(app, session_id)?;
+    fs::create_dir_all(&dir).map_err(|err| format!("failed to create transcripts dir: {}", err))?;
+    let lines = segments
+        .iter()
+        .map(|segment| serde_json::to_string(segment).map_err(|err| format!("failed to format segment: {}", err)))
+        .collect::<Result<Vec<_>, _>>()?;
+    fs::write(dir.join(format!("{}.jsonl", version.id)), lines.join("\n") + "\n")
+        .map_err(|err| format!("failed to write transcript version: {}", err))?;
+    let mut index = load_index(app, session_id);
+    index.push(version.clone());
+    let content =
+        serde_json::to_string_pretty(&index).map_err(|err| format!("failed to format transcript index: {}", err))?;
+    fs::write(dir.join(INDEX_FILE), content.as_bytes()).map_err(|err| format!("failed to write transcript index: {}", err))
+}
+
+fn run(
+    app: &tauri::AppHandle,
+    session_id: &str,
+    model: &str,
+    options: Option<TranscriptionOptions>,
+    job_id: Option<&str>,
+    checkpoint: Option<Checkpoint>,
+) -> Result<(TranscriptVersion, Vec<TranscriptSegment>), String> {
+    let model_path = whisper_models::find(app, model).ok_or_else(|| format!("model {} is not installed", model))?;
+    whisper_models::ensure_intact(app, &model_path)?;
+    let clips = session_audio::segments(app, session_id)?;
+    let transcription = settings::load_settings(app).transcription;
+    let language = language::whisper_language(&transcription.language);
+    let prompt = transcription.whisper_prompt();
+    // A resumed run keeps the options it started with.
+    let mut progress = match checkpoint {
+        Some(checkpoint) => checkpoint,
+        None => Checkpoint {
+            options: transcription.options.with(options),
+            done: Vec::new(),
+        },
+    };
+    progress.options.validate()?;
+    preflight::ensure(app, PreflightOperation::Retranscription, &sessions::session_dir(app, session_id)?)?;
+    log_line(&format!(
+        "[transcript-versions] retranscribing {} with {} ({} of {} segments done)",
+        session_id,
+        model,
+        progress.done.len(),
+        clips.len()
+    ));
+    for (offset_ms, path) in &clips {
+        if progress.done.iter().any(|(done, _)| done == offset_ms) {
+            continue;
+        }
+        let wav_bytes = fs::read(path).map_err(|err| format!("failed to read {}: {}", path.display(), err))?;
+        let _permit = transcription_queue::acquire_blocking(app, Lane::Batch);
+        let text = crate::transcribe_wav_locally(
+            &wav_bytes,
+            &model_path,
+            &language,
+            prompt.as_deref(),
+            false,
+            &progress.options,
+            None,
+        )?;
+        progress.done.push((*offset_ms, text));
+        if let Some(job_id) = job_id {
+            jobs::checkpoint(app, job_id, &progress, Some(progress.done.len() as f32 / clips.len() as f32));
+        }
+    }
+    progress.done.sort_by_key(|(offset_ms, _)| *offset_ms);
+    let segments = assemble(&progress.done, &sessions::read_transcript(app, session_id)?);
+    let version = TranscriptVersion {
+        id: trace::new_id(),
+        model: model.to_string(),
+        language,
+        options: progress.options,
+        created_at: now_millis(),
+        segment_count: segments.len() as u32,
+    };
+    save_version(app, session_id, &version, &segments)?;
+    log_line(&format!("[transcript-versions] saved {} for {}", version.id, session_id));
+    Ok((version, segments))
+}
+
+/// Retranscribe the kept audio of `session_id` with `model`, as a
+/// resumable job, and save it as a new version. Blocks while whisper runs.
+pub fn retranscribe(
+    app: &tauri::AppHandle,
+    session_id: &str,
+    model: &str,
+    options: Option<TranscriptionOptions>,
+) -> Result<(TranscriptVersion, Vec<TranscriptSegment>), String> {
+    let spec = JobSpec::SessionRetranscription {
+        session_id: session_id.to_string(),
+        model: model.to_string(),
+    };
+    let (job_id, checkpoint) = match jobs::start_logged::<Checkpoint>(app, spec) {
+        Some((job_id, checkpoint)) => (Some(job_id), checkpoint),
+        None => (None, None),
+    };
+    let result = run(app, session_id, model, options, job_id.as_deref(), checkpoint);
+    if let Some(job_id) = &job_id {
+        jobs::finish(app, job_id, &result);
+    }
+    result
+}
+
+/// Re-run whisper over the saved audio of `session_id` with `model` (a
+/// model file name; by default the wrap-up step's) and `options` on top of
+/// the saved decoding options. The result is stored as a new version next
+/// to the live transcript and earlier versions.
+#[tauri::command]
+#[specta::specta]
+pub async fn retranscribe_session(
+    app: tauri::AppHandle,
+    session_id: String,
+    model: Option<String>,
+    options: Option<TranscriptionOptions>,
+) -> Result<TranscriptVersion, String> {
+    let model = model
+        .filter(|model| !model.trim().is_empty())
+        .unwrap_or_else(|| settings::load_settings(&app).post_session.retranscribe_model);
+    tauri::async_runtime::spawn_blocking(move || retranscribe(&app, &session_id, &model, options).map(|(version, _)| version))
+        .await
+        .map_err(|err| format!("retranscription task failed: {}", err))?
+}
+
+/// Stored versions of `session_id`, oldest first.
+#[tauri::command]
+#[specta::specta]
+pub fn list_transcript_versions(app: tauri::AppHandle, session_id: String) -> Result<Vec<TranscriptVersion>, String> {
+    versions_dir(&app, &session_id)?;
+    Ok(load_index(&app, &session_id))
+}
+
+#[tauri::command]
+#[specta::specta]
+pub fn get_transcript_version(
+    app: tauri::AppHandle,
+    session_id: String,
+    version_id: String,
+) -> Result<Vec<TranscriptSegment>, String> {
+    if !load_index(&app, &session_id).iter().any(|version| version.id == version_id) {
+        return Err(format!("no transcript version {} in {}", version_id, session_id));
+    }
+    let path = versions_dir(&app, &session_id)?.join(format!("{}.jsonl", version_id));
+    let content = fs::read_to_string(&path).map_err(|err| format!("failed to read {}: {}", path.display(), err))?;
+    Ok(content.lines().filter_map(|line| serde_json::from_str(line).ok()).collect())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::assemble;
+    use crate::sessions::TranscriptSegment;
+
+    #[test]
+    fn versions_keep_track_names_and_skip_silence() {
+        let live = vec![TranscriptSegment {
+            index: 0,
+            offset_ms: 8_000,
+            text: "helo world".to_string(),
+            source: Some("Guest".to_string()),
+        }];
+        let texts = vec![
+            (0, " ".to_string()),
+            (8_000, " hello world ".to_string()),
+            (16_000, "and more".to_string()),
+        ];
+        let segments = assemble(&texts, &live);
+        assert_eq!(segments.len(), 2);
+        assert_eq!(segments[0].index, 0);
+        assert_eq!(segments[0].text, "hello world");
+        assert_eq!(segments[0].source.as_deref(), Some("Guest"));
+        assert_eq!(segments[1].offset_ms, 16_000);
+        assert_eq!(segments[1].source, None);
+    }
+}
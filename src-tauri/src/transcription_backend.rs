@@ -0,0 +1,297 @@
+//! Where a segment is transcribed. `transcription.backend` picks a
+//! `TranscriptionBackend`: local whisper (remote whisper servers first, then
+//! the warm worker or `whisper-cli`), OpenAI's audio API, or Deepgram.
+//! Every `transcribe_audio` call, live segment and file goes through
+//! `transcribe`. Cloud backends fall back to local whisper when
+//! `transcription.cloud.fallbackToLocal` is on.
+
+use std::future::Future;
+use std::pin::Pin;
+use std::time::{Duration, Instant};
+
+use serde_json::Value as JsonValue;
+
+use crate::settings::{
+    CloudTranscriptionSettings, RemoteWhisperKind, RemoteWhisperServer, TranscriptionBackendKind,
+    TranscriptionOptions, TranscriptionSettings,
+};
+use crate::{keychain, log_line, remote_whisper, transcription_metrics, truncate_for_log, SegmentSink};
+
+const OPENAI_BASE: &str = "https://api.openai.com";
+const OPENAI_DEFAULT_MODEL: &str = "whisper-1";
+const DEEPGRAM_LISTEN: &str = "https://api.deepgram.com/v1/listen";
+const DEEPGRAM_DEFAULT_MODEL: &str = "nova-2";
+
+/// One segment to transcribe.
+#[derive(Clone)]
+pub struct TranscriptionRequest {
+    pub wav_bytes: Vec<u8>,
+    /// Whisper language code, or `auto`.
+    pub language: String,
+    pub prompt: Option<String>,
+    /// Return the English translation instead.
+    pub translate: bool,
+    pub options: TranscriptionOptions,
+    /// Gets partial transcripts as they arrive; backends without
+    /// partials call it once with the whole text.
+    pub on_segment: Option<SegmentSink>,
+    /// How long decoding the webview payload took, for metrics.
+    pub payload_ms: Option<u64>,
+    pub started: Instant,
+}
+
+type BackendFuture<'a> = Pin<Box<dyn Future<Output = Result<String, String>> + Send + 'a>>;
+
+pub trait TranscriptionBackend: Send + Sync {
+    fn name(&self) -> &'static str;
+    fn transcribe<'a>(&'a self, app: &'a tauri::AppHandle, request: TranscriptionRequest) -> BackendFuture<'a>;
+}
+
+struct LocalWhisper;
+
+impl TranscriptionBackend for LocalWhisper {
+    fn name(&self) -> &'static str {
+        "local whisper"
+    }
+
+    fn transcribe<'a>(&'a self, app: &'a tauri::AppHandle, request: TranscriptionRequest) -> BackendFuture<'a> {
+        Box::pin(crate::transcribe_with_whisper(app, request))
+    }
+}
+
+struct OpenAi {
+    server: RemoteWhisperServer,
+}
+
+impl TranscriptionBackend for OpenAi {
+    fn name(&self) -> &'static str {
+        "OpenAI"
+    }
+
+    fn transcribe<'a>(&'a self, app: &'a tauri::AppHandle, request: TranscriptionRequest) -> BackendFuture<'a> {
+        Box::pin(async move {
+            let request_started = Instant::now();
+            let text = remote_whisper::transcribe(
+                &self.server,
+                &request.wav_bytes,
+                &request.language,
+                request.prompt.as_deref(),
+                request.translate,
+                &request.options,
+            )
+            .await?;
+            finish(app, &request, "openai", self.server.model.clone(), request_started, text)
+        })
+    }
+}
+
+struct Deepgram {
+    model: String,
+    key: String,
+    timeout: Duration,
+}
+
+impl TranscriptionBackend for Deepgram {
+    fn name(&self) -> &'static str {
+        "Deepgram"
+    }
+
+    fn transcribe<'a>(&'a self, app: &'a tauri::AppHandle, request: TranscriptionRequest) -> BackendFuture<'a> {
+        Box::pin(async move {
+            if request.translate {
+                return Err("Deepgram doesn't translate".to_string());
+            }
+            let request_started = Instant::now();
+            let client = reqwest::Client::builder()
+                .timeout(self.timeout)
+                .build()
+                .map_err(|err| format!("failed to build HTTP client: {}", err))?;
+            let response = client
+                .post(deepgram_url(&self.model, &request.language)?)
+                .header("Authorization", format!("Token {}", self.key))
+                .header("Content-Type", "audio/wav")
+                .body(request.wav_bytes.clone())
+                .send()
+                .await
+                .map_err(|err| format!("Deepgram request failed: {}", err))?;
+            let status = response.status();
+            let body = response
+                .text()
+                .await
+                .map_err(|err| format!("failed to read Deepgram response: {}", err))?;
+            if !status.is_success() {
+                return Err(format!("Deepgram HTTP {}: {}", status, truncate_for_log(body.trim(), 300)));
+            }
+            let parsed: JsonValue =
+                serde_json::from_str(&body).map_err(|err| format!("invalid Deepgram response: {}", err))?;
+            let text = deepgram_transcript(&parsed).ok_or("Deepgram response has no transcript")?;
+            let text = remote_whisper::clean_remote_text(&text);
+            finish(app, &request, "deepgram", Some(self.model.clone()), request_started, text)
+        })
+    }
+}
+
+/// Record metrics for a cloud transcript and hand it to the sink.
+fn finish(
+    app: &tauri::AppHandle,
+    request: &TranscriptionRequest,
+    backend: &str,
+    model: Option<String>,
+    request_started: Instant,
+    text: String,
+) -> Result<String, String> {
+    let timings = transcription_metrics::Timings {
+        backend: backend.to_string(),
+        model,
+        decode_ms: request_started.elapsed().as_millis() as u64,
+        ..Default::default()
+    };
+    transcription_metrics::record(app, &request.wav_bytes, request.payload_ms, request.started, timings);
+    log_line(&format!("{} transcript: {}", backend, text));
+    if let Some(sink) = &request.on_segment {
+        sink(&text);
+    }
+    Ok(text)
+}
+
+/// Deepgram's pre-recorded endpoint for `model`, detecting the language
+/// when whisper would. Both come from settings, so they are encoded.
+fn deepgram_url(model: &str, language: &str) -> Result<reqwest::Url, String> {
+    let language = if language == "auto" {
+        ("detect_language", "true")
+    } else {
+        ("language", language)
+    };
+    reqwest::Url::parse_with_params(
+        DEEPGRAM_LISTEN,
+        [("model", model), ("smart_format", "true"), language],
+    )
+    .map_err(|err| format!("invalid Deepgram URL: {}", err))
+}
+
+/// The first alternative of the first channel.
+fn deepgram_transcript(response: &JsonValue) -> Option<String> {
+    response
+        .pointer("/results/channels/0/alternatives/0/transcript")
+        .and_then(|text| text.as_str())
+        .map(str::to_string)
+}
+
+fn keychain_account(kind: TranscriptionBackendKind) -> Result<&'static str, String> {
+    match kind {
+        TranscriptionBackendKind::Local => Err("local whisper needs no API key".to_string()),
+        TranscriptionBackendKind::Openai => Ok("transcription-openai"),
+        TranscriptionBackendKind::Deepgram => Ok("transcription-deepgram"),
+    }
+}
+
+/// The saved key, else `OPENAI_API_KEY` / `DEEPGRAM_API_KEY`.
+fn api_key(kind: TranscriptionBackendKind) -> Result<String, String> {
+    let (name, env) = match kind {
+        TranscriptionBackendKind::Openai => ("OpenAI", "OPENAI_API_KEY"),
+        TranscriptionBackendKind::Deepgram => ("Deepgram", "DEEPGRAM_API_KEY"),
+        TranscriptionBackendKind::Local => return Ok(String::new()),
+    };
+    keychain::get_secret(keychain_account(kind)?)?
+        .or_else(|| std::env::var(env).ok())
+        .map(|key| key.trim().to_string())
+        .filter(|key| !key.is_empty())
+        .ok_or_else(|| format!("no {} API key saved; set one in settings or {}", name, env))
+}
+
+fn or_default(value: &str, default: &str) -> String {
+    Some(value.trim()).filter(|value| !value.is_empty()).unwrap_or(default).to_string()
+}
+
+fn backend(
+    kind: TranscriptionBackendKind,
+    cloud: &CloudTranscriptionSettings,
+) -> Result<Box<dyn TranscriptionBackend>, String> {
+    Ok(match kind {
+        TranscriptionBackendKind::Local => Box::new(LocalWhisper),
+        TranscriptionBackendKind::Openai => Box::new(OpenAi {
+            server: RemoteWhisperServer {
+                enabled: true,
+                url: OPENAI_BASE.to_string(),
+                kind: RemoteWhisperKind::OpenaiCompatible,
+                model: Some(or_default(&cloud.openai_model, OPENAI_DEFAULT_MODEL)),
+                api_key: Some(api_key(kind)?),
+                timeout_ms: cloud.timeout_ms,
+                retries: 1,
+                fallback_to_local: cloud.fallback_to_local,
+                health_path: None,
+            },
+        }),
+        TranscriptionBackendKind::Deepgram => Box::new(Deepgram {
+            model: or_default(&cloud.deepgram_model, DEEPGRAM_DEFAULT_MODEL),
+            key: api_key(kind)?,
+            timeout: Duration::from_millis(cloud.timeout_ms.max(1_000)),
+        }),
+    })
+}
+
+/// Transcribe with the configured backend.
+pub async fn transcribe(
+    app: &tauri::AppHandle,
+    settings: &TranscriptionSettings,
+    request: TranscriptionRequest,
+) -> Result<String, String> {
+    let result = match backend(settings.backend, &settings.cloud) {
+        Ok(backend) => backend
+            .transcribe(app, request.clone())
+            .await
+            .map_err(|err| format!("{}: {}", backend.name(), err)),
+        Err(err) => Err(err),
+    };
+    match result {
+        Err(err) if settings.backend != TranscriptionBackendKind::Local && settings.cloud.fallback_to_local => {
+            log_line(&format!("[transcription] {}; falling back to local whisper", err));
+            LocalWhisper.transcribe(app, request).await
+        }
+        result => result,
+    }
+}
+
+/// Save the API key for a cloud backend in the keychain. An empty key
+/// removes it.
+#[tauri::command]
+#[specta::specta]
+pub fn set_transcription_api_key(backend: TranscriptionBackendKind, key: String) -> Result<(), String> {
+    let account = keychain_account(backend)?;
+    if key.trim().is_empty() {
+        keychain::delete_secret(account)
+    } else {
+        keychain::set_secret(account, key.trim())
+    }
+}
+
+/// Whether a cloud backend has a key, saved or from the environment.
+#[tauri::command]
+#[specta::specta]
+pub fn has_transcription_api_key(backend: TranscriptionBackendKind) -> bool {
+    backend == TranscriptionBackendKind::Local || api_key(backend).is_ok()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{deepgram_transcript, deepgram_url};
+
+    #[test]
+    fn builds_deepgram_requests_and_reads_transcripts() {
+        assert_eq!(
+            deepgram_url("nova-2", "es").unwrap().as_str(),
+            "https://api.deepgram.com/v1/listen?model=nova-2&smart_format=true&language=es"
+        );
+        assert!(deepgram_url("nova-2", "auto").unwrap().as_str().ends_with("&detect_language=true"));
+        assert_eq!(
+            deepgram_url("nova 2&x=1#y", "es").unwrap().query(),
+            Some("model=nova+2%26x%3D1%23y&smart_format=true&language=es")
+        );
+
+        let response = serde_json::json!({
+            "results": { "channels": [{ "alternatives": [{ "transcript": "Hello there.", "confidence": 0.98 }] }] }
+        });
+        assert_eq!(deepgram_transcript(&response).as_deref(), Some("Hello there."));
+        assert_eq!(deepgram_transcript(&serde_json::json!({ "results": {} })), None);
+    }
+}
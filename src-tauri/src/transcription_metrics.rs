@@ -0,0 +1,262 @@
+//! Where transcription time goes: payload decoding (IPC), temp-file I/O,
+//! model load and whisper itself, per segment and averaged per model.
+
+use std::cell::RefCell;
+use std::collections::{BTreeMap, VecDeque};
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
+
+use serde::{Deserialize, Serialize};
+use tauri::Manager;
+use tauri_specta::Event;
+
+use crate::{log_line, now_millis, wav};
+
+/// Segments kept for `get_transcription_metrics`.
+const RECENT_LIMIT: usize = 100;
+pub const IN_PROCESS: &str = "whisper-rs";
+
+/// One transcribed segment. Emitted as `transcription-metrics`.
+#[derive(Serialize, Deserialize, Clone, Debug, Default, PartialEq, specta::Type, tauri_specta::Event)]
+#[serde(rename_all = "camelCase")]
+pub struct TranscriptionMetrics {
+    pub finished_at: u64,
+    /// `whisper-rs`, `whisper-server`, `whisper-cli` or `remote`.
+    pub backend: String,
+    /// Model file for local whisper, server URL for remote.
+    pub model: Option<String>,
+    pub audio_ms: u64,
+    /// Decoding the base64 payload from the webview and converting it to WAV.
+    pub payload_ms: Option<u64>,
+    /// Writing the segment to a temp file for `whisper-cli`.
+    pub temp_file_ms: Option<u64>,
+    /// Loading the in-process model or starting the warm worker; 0 when
+    /// it was already loaded. `whisper-cli` loads it on every run, inside
+    /// `decode_ms`.
+    pub model_load_ms: Option<u64>,
+    /// Whisper itself, or the request for remote servers.
+    pub decode_ms: u64,
+    /// Everything from receiving the payload to the transcript.
+    pub total_ms: u64,
+    /// `decode_ms / audio_ms`; below 1 is faster than real time.
+    pub real_time_factor: f64,
+}
+
+/// Averages for one backend and model.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, specta::Type)]
+#[serde(rename_all = "camelCase")]
+pub struct ModelMetrics {
+    pub backend: String,
+    pub model: Option<String>,
+    pub segments: u32,
+    pub avg_real_time_factor: f64,
+    pub avg_decode_ms: u64,
+    pub avg_total_ms: u64,
+    /// Only runs that had to load the model.
+    pub avg_model_load_ms: Option<u64>,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, specta::Type)]
+#[serde(rename_all = "camelCase")]
+pub struct TranscriptionMetricsReport {
+    /// Newest first.
+    pub recent: Vec<TranscriptionMetrics>,
+    pub models: Vec<ModelMetrics>,
+}
+
+#[derive(Default)]
+pub struct TranscriptionMetricsState {
+    recent: Mutex<VecDeque<TranscriptionMetrics>>,
+}
+
+/// What one whisper run measured, before the segment-level totals.
+#[derive(Clone, Debug, Default)]
+pub struct Timings {
+    pub backend: String,
+    pub model: Option<String>,
+    pub model_load_ms: Option<u64>,
+    pub temp_file_ms: Option<u64>,
+    pub decode_ms: u64,
+}
+
+thread_local! {
+    static COLLECTING: RefCell<Option<Timings>> = const { RefCell::new(None) };
+}
+
+/// Run `f` and return what the `note_*` calls made during it measured.
+pub fn collect<T>(f: impl FnOnce() -> T) -> (T, Timings) {
+    let previous = COLLECTING.with(|cell| cell.replace(Some(Timings::default())));
+    let result = f();
+    let timings = COLLECTING.with(|cell| cell.replace(previous)).unwrap_or_default();
+    (result, timings)
+}
+
+fn note(update: impl FnOnce(&mut Timings)) {
+    COLLECTING.with(|cell| {
+        if let Some(timings) = cell.borrow_mut().as_mut() {
+            update(timings);
+        }
+    });
+}
+
+fn ms(duration: Duration) -> u64 {
+    duration.as_millis() as u64
+}
+
+#[cfg_attr(not(feature = "whisper-rs"), allow(dead_code))]
+pub fn note_model_load(elapsed: Duration) {
+    note(|timings| timings.model_load_ms = Some(ms(elapsed)));
+}
+
+pub fn note_temp_file(elapsed: Duration) {
+    note(|timings| timings.temp_file_ms = Some(ms(elapsed)));
+}
+
+/// A finished whisper run. For the in-process engine the model load it
+/// may have included is not counted as decoding.
+pub fn note_decode(backend: &str, elapsed: Duration) {
+    note(|timings| {
+        timings.backend = backend.to_string();
+        timings.decode_ms = if backend == IN_PROCESS {
+            ms(elapsed).saturating_sub(*timings.model_load_ms.get_or_insert(0))
+        } else {
+            ms(elapsed)
+        };
+    });
+}
+
+pub fn audio_ms(wav_bytes: &[u8]) -> u64 {
+    wav::decode_pcm16_mono(wav_bytes)
+        .map(|(rate, samples)| samples.len() as u64 * 1000 / rate.max(1) as u64)
+        .unwrap_or_default()
+}
+
+pub fn real_time_factor(decode_ms: u64, audio_ms: u64) -> f64 {
+    if audio_ms == 0 {
+        return 0.0;
+    }
+    ((decode_ms as f64 / audio_ms as f64) * 1000.0).round() / 1000.0
+}
+
+/// Store a transcribed segment's metrics and emit them.
+pub fn record(app: &tauri::AppHandle, wav_bytes: &[u8], payload_ms: Option<u64>, started: Instant, timings: Timings) {
+    let audio_ms = audio_ms(wav_bytes);
+    let metrics = TranscriptionMetrics {
+        finished_at: now_millis(),
+        real_time_factor: real_time_factor(timings.decode_ms, audio_ms),
+        backend: timings.backend,
+        model: timings.model,
+        audio_ms,
+        payload_ms,
+        temp_file_ms: timings.temp_file_ms,
+        model_load_ms: timings.model_load_ms,
+        decode_ms: timings.decode_ms,
+        total_ms: payload_ms.unwrap_or(0) + ms(started.elapsed()),
+    };
+    log_line(&format!(
+        "[metrics] {} {}: {}ms audio, decode {}ms (RTF {}), load {:?}ms, temp file {:?}ms, payload {:?}ms, total {}ms",
+        metrics.backend,
+        metrics.model.as_deref().unwrap_or("-"),
+        metrics.audio_ms,
+        metrics.decode_ms,
+        metrics.real_time_factor,
+        metrics.model_load_ms,
+        metrics.temp_file_ms,
+        metrics.payload_ms,
+        metrics.total_ms
+    ));
+    {
+        let state = app.state::<TranscriptionMetricsState>();
+        let mut recent = state.recent.lock().unwrap();
+        recent.push_front(metrics.clone());
+        recent.truncate(RECENT_LIMIT);
+    }
+    let _ = metrics.emit(app);
+}
+
+fn average(values: impl Iterator<Item = u64>) -> Option<u64> {
+    let (sum, count) = values.fold((0, 0), |(sum, count), value| (sum + value, count + 1));
+    (count > 0).then(|| sum / count)
+}
+
+fn summarize(recent: &[TranscriptionMetrics]) -> Vec<ModelMetrics> {
+    let mut groups: BTreeMap<(String, Option<String>), Vec<&TranscriptionMetrics>> = BTreeMap::new();
+    for metrics in recent {
+        groups
+            .entry((metrics.backend.clone(), metrics.model.clone()))
+            .or_default()
+            .push(metrics);
+    }
+    groups
+        .into_iter()
+        .map(|((backend, model), runs)| {
+            let audio_ms: u64 = runs.iter().map(|run| run.audio_ms).sum();
+            let decode_ms: u64 = runs.iter().map(|run| run.decode_ms).sum();
+            ModelMetrics {
+                backend,
+                model,
+                segments: runs.len() as u32,
+                avg_real_time_factor: real_time_factor(decode_ms, audio_ms),
+                avg_decode_ms: average(runs.iter().map(|run| run.decode_ms)).unwrap_or(0),
+                avg_total_ms: average(runs.iter().map(|run| run.total_ms)).unwrap_or(0),
+                avg_model_load_ms: average(
+                    runs.iter().filter_map(|run| run.model_load_ms).filter(|load| *load > 0),
+                ),
+            }
+        })
+        .collect()
+}
+
+/// Recent segments and per-model averages, to compare model sizes.
+#[tauri::command]
+#[specta::specta]
+pub fn get_transcription_metrics(state: tauri::State<'_, TranscriptionMetricsState>) -> TranscriptionMetricsReport {
+    let recent: Vec<TranscriptionMetrics> = state.recent.lock().unwrap().iter().cloned().collect();
+    TranscriptionMetricsReport {
+        models: summarize(&recent),
+        recent,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{collect, note_decode, note_model_load, summarize, TranscriptionMetrics};
+    use std::time::Duration;
+
+    fn run(model: &str, audio_ms: u64, decode_ms: u64, model_load_ms: Option<u64>) -> TranscriptionMetrics {
+        TranscriptionMetrics {
+            backend: "whisper-rs".to_string(),
+            model: Some(model.to_string()),
+            audio_ms,
+            decode_ms,
+            total_ms: decode_ms + 10,
+            model_load_ms,
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn separates_model_load_and_averages_per_model() {
+        let (_, timings) = collect(|| {
+            note_model_load(Duration::from_millis(400));
+            note_decode("whisper-rs", Duration::from_millis(1_000));
+        });
+        assert_eq!(timings.backend, "whisper-rs");
+        assert_eq!((timings.model_load_ms, timings.decode_ms), (Some(400), 600));
+        // Outside `collect` nothing is recorded.
+        note_decode("whisper-cli", Duration::from_millis(5));
+
+        let models = summarize(&[
+            run("ggml-base.en.bin", 4_000, 1_000, Some(0)),
+            run("ggml-base.en.bin", 2_000, 500, Some(300)),
+            run("ggml-small.en.bin", 3_000, 3_000, None),
+        ]);
+        assert_eq!(models.len(), 2);
+        assert_eq!(models[0].segments, 2);
+        assert_eq!(models[0].avg_real_time_factor, 0.25);
+        assert_eq!(models[0].avg_decode_ms, 750);
+        assert_eq!(models[0].avg_model_load_ms, Some(300));
+        assert_eq!(models[1].avg_real_time_factor, 1.0);
+        assert_eq!(models[1].avg_model_load_ms, None);
+    }
+}
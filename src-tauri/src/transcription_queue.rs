@@ -0,0 +1,219 @@
+//! Bounded parallelism for whisper. Every transcription takes a `Permit`
+//! first, so live segments, dropped files, long recordings and
+//! retranscriptions share `transcription.maxConcurrent` slots instead of
+//! each starting whisper at once and starving one another. Waiting live
+//! segments go before waiting batch work, so a file batch doesn't hold up
+//! the conversation.
+
+use std::collections::VecDeque;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::Mutex;
+
+use serde::{Deserialize, Serialize};
+use tauri::Manager;
+use tokio::sync::oneshot;
+
+use crate::{log_line, settings};
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Lane {
+    /// Segments from the microphone, and other calls someone waits on.
+    Live,
+    /// Files, long-recording windows and retranscriptions.
+    Batch,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, specta::Type)]
+#[serde(rename_all = "camelCase")]
+pub struct TranscriptionQueueStatus {
+    pub max_concurrent: u32,
+    pub running: u32,
+    pub waiting_live: u32,
+    pub waiting_batch: u32,
+}
+
+#[derive(Default)]
+struct Queue {
+    running: usize,
+    live: VecDeque<oneshot::Sender<()>>,
+    batch: VecDeque<oneshot::Sender<()>>,
+}
+
+impl Queue {
+    /// Take a slot now if one is free and nobody ahead is waiting for it.
+    fn try_start(&mut self, lane: Lane, limit: usize) -> bool {
+        let ahead = match lane {
+            Lane::Live => self.live.len(),
+            Lane::Batch => self.live.len() + self.batch.len(),
+        };
+        if self.running < limit && ahead == 0 {
+            self.running += 1;
+            true
+        } else {
+            false
+        }
+    }
+
+    fn wait(&mut self, lane: Lane) -> oneshot::Receiver<()> {
+        let (sender, receiver) = oneshot::channel();
+        match lane {
+            Lane::Live => self.live.push_back(sender),
+            Lane::Batch => self.batch.push_back(sender),
+        }
+        receiver
+    }
+
+    /// Hand free slots to waiters, live first, skipping any that gave up.
+    fn hand_off(&mut self, limit: usize) {
+        while self.running < limit {
+            let Some(next) = self.live.pop_front().or_else(|| self.batch.pop_front()) else {
+                break;
+            };
+            if next.send(()).is_ok() {
+                self.running += 1;
+            }
+        }
+    }
+
+    fn finish(&mut self, limit: usize) {
+        self.running = self.running.saturating_sub(1);
+        self.hand_off(limit);
+    }
+}
+
+pub struct TranscriptionQueueState {
+    queue: Mutex<Queue>,
+    /// `transcription.maxConcurrent`, kept here so releasing a slot never
+    /// reads the settings file.
+    limit: AtomicUsize,
+}
+
+impl Default for TranscriptionQueueState {
+    fn default() -> Self {
+        Self {
+            queue: Mutex::new(Queue::default()),
+            limit: AtomicUsize::new(1),
+        }
+    }
+}
+
+fn limit(app: &tauri::AppHandle) -> usize {
+    app.state::<TranscriptionQueueState>().limit.load(Ordering::Relaxed)
+}
+
+/// Read `transcription.maxConcurrent`. Called at startup and whenever
+/// settings are saved; a raised limit starts waiting jobs right away.
+pub fn reload(app: &tauri::AppHandle) {
+    let limit = settings::load_settings(app).transcription.max_concurrent.max(1) as usize;
+    let state = app.state::<TranscriptionQueueState>();
+    state.limit.store(limit, Ordering::Relaxed);
+    state.queue.lock().unwrap().hand_off(limit);
+}
+
+/// A slot, given back when dropped.
+pub struct Permit {
+    app: tauri::AppHandle,
+}
+
+impl Drop for Permit {
+    fn drop(&mut self) {
+        let limit = limit(&self.app);
+        self.app.state::<TranscriptionQueueState>().queue.lock().unwrap().finish(limit);
+    }
+}
+
+/// A place in line. If the caller gives up after its slot was handed over
+/// but before it noticed, the slot goes back.
+struct Waiting {
+    app: tauri::AppHandle,
+    receiver: Option<oneshot::Receiver<()>>,
+}
+
+impl Drop for Waiting {
+    fn drop(&mut self) {
+        if let Some(mut receiver) = self.receiver.take() {
+            receiver.close();
+            if receiver.try_recv().is_ok() {
+                drop(Permit { app: self.app.clone() });
+            }
+        }
+    }
+}
+
+/// Wait for a free slot in `lane`.
+pub async fn acquire(app: &tauri::AppHandle, lane: Lane) -> Permit {
+    let limit = limit(app);
+    let receiver = {
+        let state = app.state::<TranscriptionQueueState>();
+        let mut queue = state.queue.lock().unwrap();
+        if queue.try_start(lane, limit) {
+            return Permit { app: app.clone() };
+        }
+        let receiver = queue.wait(lane);
+        log_line(&format!(
+            "[transcription-queue] {:?} job waiting ({} running, {} live and {} batch waiting)",
+            lane,
+            queue.running,
+            queue.live.len(),
+            queue.batch.len()
+        ));
+        receiver
+    };
+    let mut waiting = Waiting {
+        app: app.clone(),
+        receiver: Some(receiver),
+    };
+    if let Some(receiver) = waiting.receiver.as_mut() {
+        // Only fails if the queue was dropped, i.e. the app is exiting.
+        let _ = receiver.await;
+    }
+    waiting.receiver = None;
+    Permit { app: app.clone() }
+}
+
+/// `acquire` from blocking code.
+pub fn acquire_blocking(app: &tauri::AppHandle, lane: Lane) -> Permit {
+    tauri::async_runtime::block_on(acquire(app, lane))
+}
+
+#[tauri::command]
+#[specta::specta]
+pub fn get_transcription_queue(app: tauri::AppHandle) -> TranscriptionQueueStatus {
+    let limit = limit(&app);
+    let state = app.state::<TranscriptionQueueState>();
+    let queue = state.queue.lock().unwrap();
+    TranscriptionQueueStatus {
+        max_concurrent: limit as u32,
+        running: queue.running as u32,
+        waiting_live: queue.live.len() as u32,
+        waiting_batch: queue.batch.len() as u32,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{Lane, Queue};
+
+    #[test]
+    fn bounds_running_jobs_and_lets_live_segments_go_first() {
+        let mut queue = Queue::default();
+        assert!(queue.try_start(Lane::Batch, 1));
+        assert!(!queue.try_start(Lane::Live, 1));
+        let mut batch = queue.wait(Lane::Batch);
+        let mut live = queue.wait(Lane::Live);
+        let mut gave_up = queue.wait(Lane::Live);
+        gave_up.close();
+
+        queue.finish(1);
+        assert!(live.try_recv().is_ok());
+        assert!(batch.try_recv().is_err());
+        assert_eq!(queue.running, 1);
+
+        // The live waiter that gave up is skipped.
+        queue.finish(1);
+        assert!(batch.try_recv().is_ok());
+        assert_eq!(queue.running, 1);
+        assert!(!queue.try_start(Lane::Live, 1));
+        assert!(queue.try_start(Lane::Live, 2));
+    }
+}
@@ -0,0 +1,81 @@
+use tauri::Manager;
+
+use crate::log_line;
+use crate::settings::{self, UiSettings};
+
+const MIN_SCALE: f64 = 0.5;
+const MAX_SCALE: f64 = 3.0;
+
+/// Keep a requested zoom within what stays usable; bad input means 100%.
+pub fn clamp_scale(factor: f64) -> f64 {
+    if factor.is_finite() && factor > 0.0 {
+        factor.clamp(MIN_SCALE, MAX_SCALE)
+    } else {
+        1.0
+    }
+}
+
+/// The zoom a window should use: its own override, else the global scale.
+pub fn scale_for(ui: &UiSettings, label: &str) -> f64 {
+    clamp_scale(ui.window_scales.get(label).copied().unwrap_or(ui.scale))
+}
+
+fn set_zoom(window: &tauri::WebviewWindow, factor: f64) {
+    if let Err(err) = window.set_zoom(factor) {
+        log_line(&format!("[ui-scale] failed to zoom {}: {}", window.label(), err));
+    }
+}
+
+/// Apply the saved zoom to a newly created window.
+pub fn apply(app: &tauri::AppHandle, window: &tauri::WebviewWindow) {
+    let factor = scale_for(&settings::load_settings(app).ui, window.label());
+    if factor != 1.0 {
+        set_zoom(window, factor);
+    }
+}
+
+/// Set the zoom for one window, or for every window when `window` is
+/// omitted, and apply it to whatever is open. Returns the stored factor.
+#[tauri::command]
+#[specta::specta]
+pub fn set_ui_scale(app: tauri::AppHandle, window: Option<String>, factor: f64) -> Result<f64, String> {
+    let factor = clamp_scale(factor);
+    let mut settings = settings::load_settings(&app);
+    match window.as_deref() {
+        Some(label) => {
+            settings.ui.window_scales.insert(label.to_string(), factor);
+        }
+        None => {
+            settings.ui.scale = factor;
+            settings.ui.window_scales.clear();
+        }
+    }
+    settings::save_settings(&app, &settings)?;
+    for (label, open) in app.webview_windows() {
+        if window.as_deref().is_none_or(|target| target == label) {
+            set_zoom(&open, scale_for(&settings.ui, &label));
+        }
+    }
+    log_line(&format!(
+        "[ui-scale] {} set to {:.0}%",
+        window.as_deref().unwrap_or("all windows"),
+        factor * 100.0
+    ));
+    Ok(factor)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{clamp_scale, scale_for};
+    use crate::settings::UiSettings;
+
+    #[test]
+    fn resolves_window_overrides_within_bounds() {
+        let mut ui = UiSettings { scale: 1.25, ..UiSettings::default() };
+        ui.window_scales.insert("settings".to_string(), 9.0);
+        assert_eq!(scale_for(&ui, "main"), 1.25);
+        assert_eq!(scale_for(&ui, "settings"), 3.0);
+        assert_eq!(clamp_scale(f64::NAN), 1.0);
+        assert_eq!(clamp_scale(0.1), 0.5);
+    }
+}
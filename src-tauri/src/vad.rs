@@ -0,0 +1,68 @@
+use crate::wav;
+
+const FRAME_MS: usize = 30;
+/// Share of frames treated as background when estimating the noise floor.
+const FLOOR_PERCENTILE: f32 = 0.2;
+/// A frame is voiced when this much louder than the noise floor...
+const FLOOR_RATIO: f32 = 3.0;
+/// ...but never below this, so a silent room doesn't make hiss count...
+const MIN_FRAME_RMS: f32 = 0.005;
+/// ...and never above this, so continuous speech isn't its own floor.
+const MAX_FRAME_RMS: f32 = 0.03;
+/// Less voiced audio than this (a click, a cough) counts as silence.
+const MIN_SPEECH_MS: usize = 250;
+
+fn rms(frame: &[f32]) -> f32 {
+    (frame.iter().map(|sample| sample * sample).sum::<f32>() / frame.len() as f32).sqrt()
+}
+
+/// Milliseconds of `samples` loud enough above the clip's own noise floor
+/// to be speech.
+pub fn speech_ms(samples: &[f32], sample_rate: u32) -> usize {
+    let frame_len = (sample_rate as usize * FRAME_MS / 1000).max(1);
+    let energies: Vec<f32> = samples.chunks(frame_len).map(rms).collect();
+    if energies.is_empty() {
+        return 0;
+    }
+    let mut sorted = energies.clone();
+    sorted.sort_by(|a, b| a.total_cmp(b));
+    let floor = sorted[((sorted.len() - 1) as f32 * FLOOR_PERCENTILE) as usize];
+    let threshold = (floor * FLOOR_RATIO).clamp(MIN_FRAME_RMS, MAX_FRAME_RMS);
+    energies.iter().filter(|energy| **energy > threshold).count() * FRAME_MS
+}
+
+pub fn has_speech(samples: &[f32], sample_rate: u32) -> bool {
+    speech_ms(samples, sample_rate) >= MIN_SPEECH_MS
+}
+
+/// Whether a WAV clip is worth sending to whisper. Silent segments are
+/// where it hallucinates ("you you you"). Undecodable audio is passed
+/// through for whisper to report on.
+pub fn wav_has_speech(wav_bytes: &[u8]) -> bool {
+    match wav::decode_pcm16_mono(wav_bytes) {
+        Ok((sample_rate, samples)) => has_speech(&samples, sample_rate),
+        Err(_) => true,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{speech_ms, MIN_SPEECH_MS};
+
+    #[test]
+    fn finds_speech_above_the_noise_floor() {
+        let rate = 16_000;
+        let hiss: Vec<f32> = (0..rate * 2).map(|i| if i % 2 == 0 { 0.002 } else { -0.002 }).collect();
+        assert!(speech_ms(&hiss, rate as u32) < MIN_SPEECH_MS);
+
+        let mut talk = hiss.clone();
+        for (i, sample) in talk.iter_mut().enumerate().take(rate / 2) {
+            *sample = 0.2 * (i as f32 * 0.1).sin();
+        }
+        assert!(speech_ms(&talk, rate as u32) >= MIN_SPEECH_MS);
+
+        let continuous: Vec<f32> = (0..rate * 2).map(|i| 0.2 * (i as f32 * 0.1).sin()).collect();
+        assert!(speech_ms(&continuous, rate as u32) >= MIN_SPEECH_MS);
+        assert_eq!(speech_ms(&[], rate as u32), 0);
+    }
+}
@@ -0,0 +1,161 @@
+use crate::app_control::{self, AppAction};
+use crate::sessions::TranscriptSegment;
+use crate::{bookmarks, log_line};
+
+/// Spoken forms of the assistant's name that whisper commonly produces.
+const WAKE_NAMES: &[&str] = &["jamie", "jaime", "jamey", "jaimie", "jayme"];
+
+/// A spoken command handled by the backend rather than routed to the agent.
+#[derive(Debug, PartialEq, Eq)]
+pub enum VoiceTrigger {
+    /// "Jamie, bookmark this [as <label>]"
+    Bookmark { label: Option<String> },
+    /// "Jamie, jump back [to <label>]"
+    JumpBack { label: Option<String> },
+    /// "Jamie, open settings" and other `app_control` commands.
+    App(AppAction),
+}
+
+fn normalize_word(word: &str) -> String {
+    word.trim_matches(|c: char| !c.is_alphanumeric()).to_string()
+}
+
+fn join_label(words: &[String]) -> Option<String> {
+    let label = words.join(" ").trim().to_string();
+    if label.is_empty() {
+        None
+    } else {
+        Some(label)
+    }
+}
+
+/// Parse a transcript line into a trigger. The wake name may appear anywhere
+/// in the line ("okay Jamie, bookmark this") so surrounding chatter is fine.
+pub fn parse_trigger(text: &str) -> Option<VoiceTrigger> {
+    let words: Vec<String> = text
+        .split_whitespace()
+        .map(normalize_word)
+        .filter(|w| !w.is_empty())
+        .collect();
+    let lower: Vec<String> = words.iter().map(|w| w.to_lowercase()).collect();
+    let wake = lower.iter().position(|w| WAKE_NAMES.contains(&w.as_str()))?;
+    let rest = &lower[wake + 1..];
+    let original = &words[wake + 1..];
+
+    match rest.first().map(String::as_str) {
+        Some("bookmark") | Some("mark") => {
+            let mut i = 1;
+            if rest.get(i).map(String::as_str) == Some("this")
+                || rest.get(i).map(String::as_str) == Some("that")
+                || rest.get(i).map(String::as_str) == Some("it")
+            {
+                i += 1;
+            }
+            if rest.get(i).map(String::as_str) == Some("moment") {
+                i += 1;
+            }
+            // "mark" alone is too common in speech; require "mark this".
+            if rest[0] == "mark" && i == 1 {
+                return None;
+            }
+            if matches!(rest.get(i).map(String::as_str), Some("as" | "called" | "named")) {
+                i += 1;
+            }
+            Some(VoiceTrigger::Bookmark {
+                label: join_label(&original[i.min(original.len())..]),
+            })
+        }
+        Some("jump") | Some("go") if rest.get(1).map(String::as_str) == Some("back") => {
+            let mut i = 2;
+            if rest.get(i).map(String::as_str) == Some("to") {
+                i += 1;
+            }
+            if rest.get(i).map(String::as_str) == Some("the") {
+                i += 1;
+            }
+            let mut end = original.len();
+            if end > i && rest[end - 1] == "bookmark" {
+                end -= 1;
+            }
+            Some(VoiceTrigger::JumpBack {
+                label: join_label(&original[i.min(end)..end]),
+            })
+        }
+        _ => app_control::parse_command(&original.join(" ")).map(VoiceTrigger::App),
+    }
+}
+
+/// Act on any trigger found in a freshly recorded segment.
+pub fn dispatch(app: &tauri::AppHandle, segment: &TranscriptSegment) {
+    let Some(trigger) = parse_trigger(&segment.text) else {
+        return;
+    };
+    log_line(&format!("[voice-trigger] {:?} from segment {}", trigger, segment.index));
+    let app = app.clone();
+    tauri::async_runtime::spawn(async move {
+        let result = match trigger {
+            VoiceTrigger::Bookmark { label } => {
+                bookmarks::create_bookmark(&app, label, bookmarks::BookmarkSource::Voice)
+                    .await
+                    .map(|_| ())
+            }
+            VoiceTrigger::JumpBack { label } => bookmarks::jump_back(&app, label.as_deref()),
+            VoiceTrigger::App(action) => app_control::perform(&app, action),
+        };
+        if let Err(err) = result {
+            log_line(&format!("[voice-trigger] failed: {}", err));
+        }
+    });
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{parse_trigger, AppAction, VoiceTrigger};
+
+    #[test]
+    fn parses_bookmark_phrases() {
+        assert_eq!(
+            parse_trigger("Jamie, bookmark this."),
+            Some(VoiceTrigger::Bookmark { label: None })
+        );
+        assert_eq!(
+            parse_trigger("Okay Jaime bookmark this as Rust ownership"),
+            Some(VoiceTrigger::Bookmark {
+                label: Some("Rust ownership".to_string())
+            })
+        );
+        assert_eq!(
+            parse_trigger("hey jamie, mark this moment"),
+            Some(VoiceTrigger::Bookmark { label: None })
+        );
+        assert_eq!(parse_trigger("Jamie, mark my words"), None);
+    }
+
+    #[test]
+    fn parses_jump_back_phrases() {
+        assert_eq!(
+            parse_trigger("Jamie, jump back."),
+            Some(VoiceTrigger::JumpBack { label: None })
+        );
+        assert_eq!(
+            parse_trigger("Jamie go back to the pricing bookmark"),
+            Some(VoiceTrigger::JumpBack {
+                label: Some("pricing".to_string())
+            })
+        );
+    }
+
+    #[test]
+    fn ignores_lines_without_wake_name() {
+        assert_eq!(parse_trigger("let's bookmark this for later"), None);
+        assert_eq!(parse_trigger("Jamie, find a cute cat"), None);
+    }
+
+    #[test]
+    fn routes_app_commands() {
+        assert_eq!(
+            parse_trigger("Hey Jamie, open settings."),
+            Some(VoiceTrigger::App(AppAction::OpenSettings))
+        );
+    }
+}
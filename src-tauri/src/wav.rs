@@ -0,0 +1,89 @@
+/// Decode a 16-bit PCM mono WAV (as written by `encode_pcm16_mono` or the
+/// frontend's `encodeWav`) into its sample rate and `f32` samples in `-1.0..1.0`.
+pub fn decode_pcm16_mono(bytes: &[u8]) -> Result<(u32, Vec<f32>), String> {
+    if bytes.len() < 12 || &bytes[0..4] != b"RIFF" || &bytes[8..12] != b"WAVE" {
+        return Err("not a WAV file".to_string());
+    }
+    let u16_at = |at: usize| u16::from_le_bytes([bytes[at], bytes[at + 1]]);
+    let u32_at = |at: usize| u32::from_le_bytes([bytes[at], bytes[at + 1], bytes[at + 2], bytes[at + 3]]);
+    let mut offset = 12;
+    let mut rate = None;
+    while offset + 8 <= bytes.len() {
+        let id = &bytes[offset..offset + 4];
+        let size = u32_at(offset + 4) as usize;
+        let body = offset + 8;
+        let end = body.saturating_add(size).min(bytes.len());
+        match id {
+            b"fmt " if end - body >= 16 => {
+                let (format, channels, bits) = (u16_at(body), u16_at(body + 2), u16_at(body + 14));
+                if format != 1 || channels != 1 || bits != 16 {
+                    return Err(format!(
+                        "unsupported WAV format {} ({} ch, {} bit)",
+                        format, channels, bits
+                    ));
+                }
+                rate = Some(u32_at(body + 4));
+            }
+            b"data" => {
+                let rate = rate.ok_or_else(|| "WAV data before format chunk".to_string())?;
+                let samples = bytes[body..end]
+                    .chunks_exact(2)
+                    .map(|pair| i16::from_le_bytes([pair[0], pair[1]]) as f32 / 32768.0)
+                    .collect();
+                return Ok((rate, samples));
+            }
+            _ => {}
+        }
+        offset = body.saturating_add(size + (size & 1));
+    }
+    Err("WAV has no audio data".to_string())
+}
+
+/// Encode `-1.0..1.0` samples as a 16-bit PCM mono WAV, matching the
+/// frontend's `encodeWav`.
+pub fn encode_pcm16_mono(sample_rate: u32, samples: &[f32]) -> Vec<u8> {
+    let data_len = samples.len() as u32 * 2;
+    let mut wav = Vec::with_capacity(44 + data_len as usize);
+    wav.extend_from_slice(b"RIFF");
+    wav.extend_from_slice(&(36 + data_len).to_le_bytes());
+    wav.extend_from_slice(b"WAVEfmt ");
+    wav.extend_from_slice(&16u32.to_le_bytes());
+    wav.extend_from_slice(&[1, 0, 1, 0]);
+    wav.extend_from_slice(&sample_rate.to_le_bytes());
+    wav.extend_from_slice(&(sample_rate * 2).to_le_bytes());
+    wav.extend_from_slice(&[2, 0, 16, 0]);
+    wav.extend_from_slice(b"data");
+    wav.extend_from_slice(&data_len.to_le_bytes());
+    for sample in samples {
+        let sample = sample.clamp(-1.0, 1.0);
+        let value = if sample < 0.0 { sample * 32768.0 } else { sample * 32767.0 };
+        wav.extend_from_slice(&(value as i16).to_le_bytes());
+    }
+    wav
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{decode_pcm16_mono, encode_pcm16_mono};
+
+    #[test]
+    fn decodes_pcm16_mono() {
+        let samples: [i16; 3] = [0, 16384, -32768];
+        let data: Vec<u8> = samples.iter().flat_map(|s| s.to_le_bytes()).collect();
+        let mut wav = Vec::new();
+        wav.extend_from_slice(b"RIFF");
+        wav.extend_from_slice(&(36 + data.len() as u32).to_le_bytes());
+        wav.extend_from_slice(b"WAVEfmt ");
+        wav.extend_from_slice(&16u32.to_le_bytes());
+        wav.extend_from_slice(&[1, 0, 1, 0]);
+        wav.extend_from_slice(&16_000u32.to_le_bytes());
+        wav.extend_from_slice(&32_000u32.to_le_bytes());
+        wav.extend_from_slice(&[2, 0, 16, 0]);
+        wav.extend_from_slice(b"data");
+        wav.extend_from_slice(&(data.len() as u32).to_le_bytes());
+        wav.extend_from_slice(&data);
+        assert_eq!(decode_pcm16_mono(&wav), Ok((16_000, vec![0.0, 0.5, -1.0])));
+        assert!(decode_pcm16_mono(b"not audio").is_err());
+        assert_eq!(encode_pcm16_mono(16_000, &[0.0, 0.5, -1.0])[..44], wav[..44]);
+    }
+}
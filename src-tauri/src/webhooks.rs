@@ -0,0 +1,418 @@
+//! Inbound webhooks: `POST /trigger/<name>` on localhost fires a
+//! user-configured action, so calendar automations or OBS scripts can
+//! drive HeyJamie. Requests need `Authorization: Bearer <token>`. Markers
+//! and sessions are handled here; prompts need the LLM key only the
+//! webview holds, so they're sent to it as `webhook-triggered`.
+//!
+//! The same listener serves `GET /transcript?session=<id>&since=<seq>`,
+//! the transcript as snapshot + diff (see `transcript_sync`).
+
+use std::io::{Read, Write};
+use std::net::{TcpListener, TcpStream};
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+
+use serde::{Deserialize, Serialize};
+use serde_json::Value as JsonValue;
+use tauri::Manager;
+use tauri_specta::Event;
+
+use crate::bookmarks::{self, BookmarkSource};
+use crate::sessions::{self, SessionState};
+use crate::settings::{self, WebhookSettings};
+use crate::{log_line, prompts, transcript_sync};
+
+const MAX_HEADER_BYTES: usize = 16 * 1024;
+const MAX_BODY_BYTES: usize = 64 * 1024;
+const READ_TIMEOUT: Duration = Duration::from_secs(5);
+const POLL_INTERVAL: Duration = Duration::from_millis(200);
+
+#[derive(Serialize, Deserialize, Clone, Copy, Debug, Default, PartialEq, Eq, specta::Type)]
+#[serde(rename_all = "kebab-case")]
+pub enum WebhookAction {
+    /// Run a saved prompt on the posted `text`, else the transcript so far.
+    #[default]
+    RunPrompt,
+    /// Bookmark the current moment of the active session.
+    AddMarker,
+    /// Start a session and begin listening.
+    StartSession,
+}
+
+/// A route under `/trigger/`, configured in Settings → Webhooks.
+#[derive(Serialize, Deserialize, Clone, Debug, Default, PartialEq, specta::Type)]
+#[serde(rename_all = "camelCase", default)]
+pub struct WebhookTrigger {
+    pub name: String,
+    pub action: WebhookAction,
+    /// Saved prompt for `run-prompt`.
+    pub prompt: String,
+    /// Bookmark label or session title; a posted `label` overrides it.
+    pub label: String,
+}
+
+/// A trigger the webview has to carry out. Emitted as `webhook-triggered`.
+#[derive(Serialize, Deserialize, Clone, Debug, specta::Type, tauri_specta::Event)]
+#[serde(rename_all = "camelCase")]
+pub struct WebhookTriggered {
+    pub trigger: String,
+    pub action: WebhookAction,
+    pub prompt: Option<String>,
+    pub text: Option<String>,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, specta::Type)]
+#[serde(rename_all = "camelCase")]
+pub struct WebhookStatus {
+    pub enabled: bool,
+    /// Base URL of the trigger routes while the listener is up.
+    pub url: Option<String>,
+    /// Why the listener isn't running, e.g. the port is taken.
+    pub error: Option<String>,
+}
+
+/// The running listener; bumping `generation` stops it.
+#[derive(Default)]
+pub struct WebhookState {
+    generation: Arc<AtomicU64>,
+    running: Mutex<Option<u16>>,
+    error: Mutex<Option<String>>,
+}
+
+struct Request {
+    method: String,
+    path: String,
+    token: Option<String>,
+    body: Vec<u8>,
+}
+
+/// Optional JSON body of a trigger request.
+#[derive(Deserialize, Default)]
+#[serde(default)]
+struct TriggerInput {
+    text: Option<String>,
+    label: Option<String>,
+}
+
+/// Method, path and bearer token from the request head.
+fn parse_head(head: &str) -> Option<(String, String, Option<String>, usize)> {
+    let mut lines = head.lines();
+    let mut request_line = lines.next()?.split_whitespace();
+    let method = request_line.next()?.to_string();
+    let path = request_line.next()?.to_string();
+    let mut token = None;
+    let mut length = 0;
+    for line in lines {
+        let Some((name, value)) = line.split_once(':') else {
+            continue;
+        };
+        let value = value.trim();
+        if name.eq_ignore_ascii_case("authorization") {
+            token = value
+                .strip_prefix("Bearer ")
+                .or_else(|| value.strip_prefix("bearer "))
+                .map(|token| token.trim().to_string());
+        } else if name.eq_ignore_ascii_case("content-length") {
+            length = value.parse().ok()?;
+        }
+    }
+    Some((method, path, token, length))
+}
+
+fn read_request(stream: &mut TcpStream) -> Result<Request, (u16, String)> {
+    let mut buffer = Vec::new();
+    let mut chunk = [0u8; 4096];
+    let head_end = loop {
+        if let Some(end) = buffer.windows(4).position(|window| window == b"\r\n\r\n") {
+            break end;
+        }
+        if buffer.len() > MAX_HEADER_BYTES {
+            return Err((431, "headers too large".to_string()));
+        }
+        match stream.read(&mut chunk) {
+            Ok(0) | Err(_) => return Err((400, "incomplete request".to_string())),
+            Ok(read) => buffer.extend_from_slice(&chunk[..read]),
+        }
+    };
+    let head = String::from_utf8_lossy(&buffer[..head_end]).to_string();
+    let (method, path, token, length) =
+        parse_head(&head).ok_or_else(|| (400, "malformed request".to_string()))?;
+    if length > MAX_BODY_BYTES {
+        return Err((413, "body too large".to_string()));
+    }
+    let mut body = buffer[head_end + 4..].to_vec();
+    while body.len() < length {
+        match stream.read(&mut chunk) {
+            Ok(0) | Err(_) => return Err((400, "incomplete body".to_string())),
+            Ok(read) => body.extend_from_slice(&chunk[..read]),
+        }
+    }
+    body.truncate(length);
+    Ok(Request {
+        method,
+        path,
+        token,
+        body,
+    })
+}
+
+/// Compare without returning early, so timing doesn't leak the token.
+fn token_matches(expected: &str, given: &str) -> bool {
+    expected.len() == given.len()
+        && expected
+            .bytes()
+            .zip(given.bytes())
+            .fold(0u8, |diff, (a, b)| diff | (a ^ b))
+            == 0
+}
+
+fn authorize(config: &WebhookSettings, token: Option<&str>) -> Result<(), (u16, String)> {
+    let expected = config.token.trim();
+    if expected.is_empty() || !token.is_some_and(|token| token_matches(expected, token)) {
+        return Err((401, "missing or wrong token".to_string()));
+    }
+    Ok(())
+}
+
+/// The trigger a request names, or the status and reason to refuse it.
+fn route<'a>(
+    config: &'a WebhookSettings,
+    method: &str,
+    path: &str,
+    token: Option<&str>,
+) -> Result<&'a WebhookTrigger, (u16, String)> {
+    let path = path.split('?').next().unwrap_or(path);
+    let Some(name) = path.strip_prefix("/trigger/") else {
+        return Err((404, "not found".to_string()));
+    };
+    if method != "POST" {
+        return Err((405, "use POST".to_string()));
+    }
+    authorize(config, token)?;
+    config
+        .triggers
+        .iter()
+        .find(|trigger| trigger.name.trim().eq_ignore_ascii_case(name.trim_end_matches('/')))
+        .ok_or_else(|| (404, format!("no trigger named {:?}", name)))
+}
+
+/// `session` and `since` from the query of a `/transcript` request.
+fn sync_query(path: &str) -> (Option<String>, u64) {
+    let query = path.split_once('?').map(|(_, query)| query).unwrap_or("");
+    let mut session = None;
+    let mut since = 0;
+    for pair in query.split('&') {
+        match pair.split_once('=') {
+            Some(("session", value)) if !value.is_empty() => session = Some(value.to_string()),
+            Some(("since", value)) => since = value.parse().unwrap_or(0),
+            _ => {}
+        }
+    }
+    (session, since)
+}
+
+fn transcript(app: &tauri::AppHandle, config: &WebhookSettings, request: &Request) -> Result<(u16, JsonValue), (u16, String)> {
+    if request.method != "GET" {
+        return Err((405, "use GET".to_string()));
+    }
+    authorize(config, request.token.as_deref())?;
+    let (session, since) = sync_query(&request.path);
+    let reply = transcript_sync::changes_since(app, session.as_deref(), since);
+    Ok((200, serde_json::to_value(reply).unwrap_or_default()))
+}
+
+fn non_empty(value: Option<String>) -> Option<String> {
+    value.map(|value| value.trim().to_string()).filter(|value| !value.is_empty())
+}
+
+fn fire(app: &tauri::AppHandle, trigger: &WebhookTrigger, input: TriggerInput) -> Result<(u16, JsonValue), String> {
+    let label = non_empty(input.label).or_else(|| non_empty(Some(trigger.label.clone())));
+    match trigger.action {
+        WebhookAction::AddMarker => {
+            let bookmark = tauri::async_runtime::block_on(bookmarks::create_bookmark(
+                app,
+                label,
+                BookmarkSource::Webhook,
+            ))?;
+            Ok((200, serde_json::to_value(bookmark).unwrap_or_default()))
+        }
+        WebhookAction::StartSession => {
+            let session = sessions::start_session(app.clone(), app.state::<SessionState>(), label)?;
+            let _ = WebhookTriggered {
+                trigger: trigger.name.clone(),
+                action: trigger.action,
+                prompt: None,
+                text: None,
+            }
+            .emit(app);
+            Ok((200, serde_json::to_value(session).unwrap_or_default()))
+        }
+        WebhookAction::RunPrompt => {
+            let prompt = prompts::list_prompts(app.clone())
+                .into_iter()
+                .find(|prompt| prompt.name.eq_ignore_ascii_case(trigger.prompt.trim()))
+                .ok_or_else(|| format!("no prompt named {:?}", trigger.prompt))?;
+            let _ = WebhookTriggered {
+                trigger: trigger.name.clone(),
+                action: trigger.action,
+                prompt: Some(prompt.name),
+                text: non_empty(input.text),
+            }
+            .emit(app);
+            Ok((202, serde_json::json!({ "queued": true })))
+        }
+    }
+}
+
+fn respond(stream: &mut TcpStream, status: u16, body: &JsonValue) {
+    let reason = match status {
+        200 => "OK",
+        202 => "Accepted",
+        400 => "Bad Request",
+        401 => "Unauthorized",
+        404 => "Not Found",
+        405 => "Method Not Allowed",
+        413 => "Payload Too Large",
+        431 => "Request Header Fields Too Large",
+        _ => "Error",
+    };
+    let body = body.to_string();
+    let _ = write!(
+        stream,
+        "HTTP/1.1 {} {}\r\nContent-Type: application/json\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{}",
+        status,
+        reason,
+        body.len(),
+        body
+    );
+}
+
+fn handle(app: &tauri::AppHandle, mut stream: TcpStream) {
+    let _ = stream.set_nonblocking(false);
+    let _ = stream.set_read_timeout(Some(READ_TIMEOUT));
+    let config = settings::load_settings(app).webhooks;
+    let result = read_request(&mut stream).and_then(|request| {
+        if request.path.split('?').next() == Some("/transcript") {
+            return transcript(app, &config, &request);
+        }
+        let trigger = route(&config, &request.method, &request.path, request.token.as_deref())?.clone();
+        let input = if request.body.iter().all(|byte| byte.is_ascii_whitespace()) {
+            TriggerInput::default()
+        } else {
+            serde_json::from_slice(&request.body).map_err(|err| (400, format!("invalid JSON body: {}", err)))?
+        };
+        log_line(&format!("[webhooks] trigger {:?} ({:?})", trigger.name, trigger.action));
+        fire(app, &trigger, input).map_err(|err| (500, err))
+    });
+    match result {
+        Ok((status, body)) => respond(&mut stream, status, &body),
+        Err((status, err)) => {
+            if status >= 500 || status == 401 {
+                log_line(&format!("[webhooks] {} {}", status, err));
+            }
+            respond(&mut stream, status, &serde_json::json!({ "error": err }));
+        }
+    }
+}
+
+fn serve(app: tauri::AppHandle, listener: TcpListener, generation: Arc<AtomicU64>, mine: u64) {
+    while generation.load(Ordering::SeqCst) == mine {
+        match listener.accept() {
+            Ok((stream, _)) => {
+                let app = app.clone();
+                std::thread::spawn(move || handle(&app, stream));
+            }
+            Err(err) if err.kind() == std::io::ErrorKind::WouldBlock => std::thread::sleep(POLL_INTERVAL),
+            Err(err) => {
+                log_line(&format!("[webhooks] accept failed: {}", err));
+                std::thread::sleep(POLL_INTERVAL);
+            }
+        }
+    }
+}
+
+/// Start, restart or stop the listener to match settings. Called at
+/// startup and whenever settings are saved.
+pub fn reload(app: &tauri::AppHandle) {
+    let config = settings::load_settings(app).webhooks;
+    let state = app.state::<WebhookState>();
+    let mut running = state.running.lock().unwrap();
+    let wanted = config.enabled.then_some(config.port);
+    if wanted == *running {
+        return;
+    }
+    let mine = state.generation.fetch_add(1, Ordering::SeqCst) + 1;
+    *running = None;
+    *state.error.lock().unwrap() = None;
+    let Some(port) = wanted else {
+        return;
+    };
+    let listener = TcpListener::bind(("127.0.0.1", port))
+        .and_then(|listener| listener.set_nonblocking(true).map(|_| listener))
+        .map_err(|err| format!("could not listen on port {}: {}", port, err));
+    match listener {
+        Ok(listener) => {
+            log_line(&format!("[webhooks] listening on 127.0.0.1:{}", port));
+            let app = app.clone();
+            let generation = state.generation.clone();
+            std::thread::spawn(move || serve(app, listener, generation, mine));
+            *running = wanted;
+        }
+        Err(err) => {
+            log_line(&format!("[webhooks] {}", err));
+            *state.error.lock().unwrap() = Some(err);
+        }
+    }
+}
+
+#[tauri::command]
+#[specta::specta]
+pub fn get_webhook_status(app: tauri::AppHandle) -> WebhookStatus {
+    let enabled = settings::load_settings(&app).webhooks.enabled;
+    let state = app.state::<WebhookState>();
+    let url = state
+        .running
+        .lock()
+        .unwrap()
+        .as_ref()
+        .map(|port| format!("http://127.0.0.1:{}/trigger/", port));
+    let error = state.error.lock().unwrap().clone();
+    WebhookStatus { enabled, url, error }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{parse_head, route, sync_query, WebhookAction, WebhookTrigger};
+    use crate::settings::WebhookSettings;
+
+    #[test]
+    fn routes_authorized_posts_to_configured_triggers() {
+        let head = "POST /trigger/intro HTTP/1.1\r\nHost: localhost\r\nauthorization: Bearer s3cret\r\nContent-Length: 12";
+        let (method, path, token, length) = parse_head(head).unwrap();
+        assert_eq!((method.as_str(), path.as_str(), token.as_deref(), length), ("POST", "/trigger/intro", Some("s3cret"), 12));
+
+        let config = WebhookSettings {
+            enabled: true,
+            token: "s3cret".to_string(),
+            triggers: vec![WebhookTrigger {
+                name: "Intro".to_string(),
+                action: WebhookAction::AddMarker,
+                ..Default::default()
+            }],
+            ..Default::default()
+        };
+        assert_eq!(route(&config, "POST", "/trigger/intro", Some("s3cret")).unwrap().name, "Intro");
+        assert_eq!(route(&config, "POST", "/trigger/intro?x=1", Some("wrong!")).unwrap_err().0, 401);
+        assert_eq!(route(&config, "POST", "/trigger/intro", None).unwrap_err().0, 401);
+        assert_eq!(route(&config, "GET", "/trigger/intro", Some("s3cret")).unwrap_err().0, 405);
+        assert_eq!(route(&config, "POST", "/trigger/outro", Some("s3cret")).unwrap_err().0, 404);
+        assert_eq!(route(&config, "POST", "/status", Some("s3cret")).unwrap_err().0, 404);
+
+        assert_eq!(sync_query("/transcript?session=session-1&since=42"), (Some("session-1".to_string()), 42));
+        assert_eq!(sync_query("/transcript"), (None, 0));
+
+        let open = WebhookSettings { token: String::new(), ..config };
+        assert_eq!(route(&open, "POST", "/trigger/intro", Some("")).unwrap_err().0, 401);
+    }
+}
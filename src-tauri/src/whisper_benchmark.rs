@@ -0,0 +1,165 @@
+//! `benchmark_whisper`: time every installed model on the same reference
+//! clip, so picking a model is a measurement instead of editing
+//! `WHISPER_MODEL_PATH` and restarting. The clip is whisper.cpp's
+//! `samples/jfk.wav`, part of the checkout `scripts/setup-whisper.sh`
+//! makes, unless a path is given.
+
+use std::fs;
+use std::path::PathBuf;
+use std::time::Instant;
+
+use serde::{Deserialize, Serialize};
+
+use crate::settings::TranscriptionOptions;
+use crate::{background, find_whisper_cli, log_line, recording, transcription_metrics, whisper_models};
+
+/// Highest real-time factor a model can have and still be recommended:
+/// live segments need to finish well before the next one arrives, with
+/// room left for the agent.
+const REAL_TIME_RTF: f64 = 0.5;
+const REFERENCE_CLIP: &str = "samples/jfk.wav";
+
+#[derive(Serialize, Deserialize, Clone, Debug, specta::Type)]
+#[serde(rename_all = "camelCase")]
+pub struct ModelBenchmark {
+    /// Model file, e.g. `ggml-small.en.bin`.
+    pub model: String,
+    pub size_mb: u32,
+    /// Decoding time, without loading the model.
+    pub decode_ms: u64,
+    pub model_load_ms: Option<u64>,
+    pub total_ms: u64,
+    /// Decoding time over audio length; below 1 is faster than real time.
+    pub real_time_factor: f64,
+    pub text: String,
+    pub error: Option<String>,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, specta::Type)]
+#[serde(rename_all = "camelCase")]
+pub struct WhisperBenchmark {
+    pub clip: String,
+    pub audio_ms: u64,
+    /// Smallest model first.
+    pub results: Vec<ModelBenchmark>,
+    /// The largest model that kept up comfortably, if any did.
+    pub recommended: Option<String>,
+}
+
+/// The largest model that ran without error at `REAL_TIME_RTF` or better.
+fn recommend(results: &[ModelBenchmark]) -> Option<String> {
+    results
+        .iter()
+        .filter(|result| result.error.is_none() && result.real_time_factor <= REAL_TIME_RTF)
+        .max_by_key(|result| result.size_mb)
+        .map(|result| result.model.clone())
+}
+
+/// `samples/jfk.wav` of the whisper.cpp checkout whisper-cli was built in.
+fn reference_clip() -> Result<PathBuf, String> {
+    let cli = find_whisper_cli().ok_or("whisper.cpp not found; run scripts/setup-whisper.sh or pass a clip")?;
+    // <checkout>/build/bin/whisper-cli
+    cli.ancestors()
+        .nth(3)
+        .map(|checkout| checkout.join(REFERENCE_CLIP))
+        .filter(|clip| clip.exists())
+        .ok_or_else(|| format!("{} not found next to whisper-cli; pass a clip", REFERENCE_CLIP))
+}
+
+fn run(app: &tauri::AppHandle, clip: PathBuf) -> Result<WhisperBenchmark, String> {
+    let wav_bytes = fs::read(&clip).map_err(|err| format!("failed to read {}: {}", clip.display(), err))?;
+    let audio_ms = transcription_metrics::audio_ms(&wav_bytes);
+    if audio_ms == 0 {
+        return Err(format!("{} is not a 16-bit mono WAV", clip.display()));
+    }
+    let _burst = background::burst(app, background::BurstKind::Transcription);
+    let options = TranscriptionOptions::default();
+    let mut results = Vec::new();
+    for model in whisper_models::list_whisper_models(app.clone()) {
+        let Some(path) = whisper_models::find(app, &model.file_name) else {
+            continue;
+        };
+        let started = Instant::now();
+        let (result, timings) = transcription_metrics::collect(|| {
+            whisper_models::ensure_intact(app, &path)?;
+            crate::transcribe_wav_locally(&wav_bytes, &path, "en", None, false, &options, None)
+        });
+        let benchmark = ModelBenchmark {
+            model: model.file_name,
+            size_mb: model.size_mb,
+            decode_ms: timings.decode_ms,
+            model_load_ms: timings.model_load_ms,
+            total_ms: started.elapsed().as_millis() as u64,
+            real_time_factor: transcription_metrics::real_time_factor(timings.decode_ms, audio_ms),
+            text: result.as_ref().cloned().unwrap_or_default(),
+            error: result.err(),
+        };
+        log_line(&format!(
+            "[benchmark] {}: decode {}ms for {}ms of audio (RTF {}){}",
+            benchmark.model,
+            benchmark.decode_ms,
+            audio_ms,
+            benchmark.real_time_factor,
+            benchmark.error.as_deref().map(|err| format!(", failed: {}", err)).unwrap_or_default()
+        ));
+        results.push(benchmark);
+    }
+    if results.is_empty() {
+        return Err("no models are installed; download one first".to_string());
+    }
+    Ok(WhisperBenchmark {
+        clip: clip.display().to_string(),
+        audio_ms,
+        recommended: recommend(&results),
+        results,
+    })
+}
+
+/// Transcribe the reference clip (or `clip_path`) with each installed
+/// model and report decoding time and real-time factor per model.
+/// Refused while recording, since it would hold up live transcription.
+#[tauri::command]
+#[specta::specta]
+pub async fn benchmark_whisper(app: tauri::AppHandle, clip_path: Option<String>) -> Result<WhisperBenchmark, String> {
+    if recording::is_recording(&app) {
+        return Err("stop recording before running the benchmark".to_string());
+    }
+    let clip = match clip_path.filter(|path| !path.trim().is_empty()) {
+        Some(path) => PathBuf::from(path),
+        None => reference_clip()?,
+    };
+    log_line(&format!("[benchmark] running installed models on {}", clip.display()));
+    tauri::async_runtime::spawn_blocking(move || run(&app, clip))
+        .await
+        .map_err(|err| format!("benchmark task failed: {}", err))?
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{recommend, ModelBenchmark};
+
+    fn result(model: &str, size_mb: u32, real_time_factor: f64, error: Option<&str>) -> ModelBenchmark {
+        ModelBenchmark {
+            model: model.to_string(),
+            size_mb,
+            decode_ms: 0,
+            model_load_ms: None,
+            total_ms: 0,
+            real_time_factor,
+            text: String::new(),
+            error: error.map(str::to_string),
+        }
+    }
+
+    #[test]
+    fn recommends_the_largest_model_that_keeps_up() {
+        let results = vec![
+            result("ggml-base.en.bin", 142, 0.05, None),
+            result("ggml-small.en.bin", 466, 0.3, None),
+            result("ggml-medium.en.bin", 1500, 0.9, None),
+            result("ggml-large-v3-turbo.bin", 1620, 0.2, Some("out of memory")),
+        ];
+        assert_eq!(recommend(&results).as_deref(), Some("ggml-small.en.bin"));
+        assert_eq!(recommend(&results[2..]), None);
+    }
+}
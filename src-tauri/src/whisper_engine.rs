@@ -0,0 +1,271 @@
+//! In-process whisper.cpp via `whisper-rs` (the `whisper-rs` cargo feature).
+//! The model stays loaded between segments, so short utterances skip the
+//! `whisper-cli` spawn and model load.
+
+use crate::wav;
+
+/// Sample rate whisper expects; the frontend encodes segments at this rate.
+const WHISPER_SAMPLE_RATE: u32 = 16_000;
+
+/// Decode a segment into the `f32` samples whisper takes.
+#[cfg_attr(not(feature = "whisper-rs"), allow(dead_code))]
+pub fn decode_wav(bytes: &[u8]) -> Result<Vec<f32>, String> {
+    let (rate, samples) = wav::decode_pcm16_mono(bytes)?;
+    if rate != WHISPER_SAMPLE_RATE {
+        return Err(format!("expected {} Hz audio, got {} Hz", WHISPER_SAMPLE_RATE, rate));
+    }
+    Ok(samples)
+}
+
+#[cfg(feature = "whisper-rs")]
+mod engine {
+    use std::{
+        path::{Path, PathBuf},
+        sync::{Mutex, OnceLock},
+    };
+
+    use whisper_rs::{
+        get_lang_str, FullParams, SamplingStrategy, SegmentCallbackData, WhisperContext,
+        WhisperContextParameters,
+    };
+
+    use crate::settings::TranscriptionOptions;
+    use crate::transcript_detail::{RawSegment, RawToken};
+    use crate::{log_line, SegmentSink};
+
+    /// The loaded model and whether it's on the GPU, replaced when a
+    /// different model file is asked for (e.g. the smaller battery-saver
+    /// model) or the acceleration setting changes.
+    static CONTEXT: OnceLock<Mutex<Option<(PathBuf, bool, WhisperContext)>>> = OnceLock::new();
+
+    fn threads() -> usize {
+        std::thread::available_parallelism().map_or(1, |n| n.get().min(4))
+    }
+
+    /// Run `f` with the context for `model_path`, loading it if needed.
+    fn with_context<T>(
+        model_path: &Path,
+        f: impl FnOnce(&WhisperContext) -> Result<T, String>,
+    ) -> Result<T, String> {
+        let mut loaded = CONTEXT.get_or_init(|| Mutex::new(None)).lock().unwrap();
+        let use_gpu = crate::acceleration::use_gpu();
+        if loaded.as_ref().is_none_or(|(path, gpu, _)| path != model_path || *gpu != use_gpu) {
+            let path = model_path
+                .to_str()
+                .ok_or_else(|| format!("model path is not UTF-8: {}", model_path.display()))?;
+            log_line(&format!("[whisper-rs] loading model {} (gpu: {})", path, use_gpu));
+            let load_started = std::time::Instant::now();
+            let mut params = WhisperContextParameters::default();
+            params.use_gpu(use_gpu);
+            let context = WhisperContext::new_with_params(path, params)
+                .map_err(|err| format!("failed to load model: {}", err))?;
+            crate::transcription_metrics::note_model_load(load_started.elapsed());
+            *loaded = Some((model_path.to_path_buf(), use_gpu, context));
+        }
+        let (_, _, context) = loaded.as_ref().expect("model loaded above");
+        f(context)
+    }
+
+    /// The most likely language of `samples` and its probability. Needs a
+    /// multilingual model.
+    pub fn detect_language(model_path: &Path, samples: &[f32]) -> Result<(String, f32), String> {
+        with_context(model_path, |context| {
+            let mut state = context
+                .create_state()
+                .map_err(|err| format!("failed to create whisper state: {}", err))?;
+            state
+                .pcm_to_mel(samples, threads())
+                .map_err(|err| format!("failed to compute mel spectrogram: {}", err))?;
+            let (id, probabilities) = state
+                .lang_detect(0, threads())
+                .map_err(|err| format!("language detection failed: {}", err))?;
+            let code = get_lang_str(id).ok_or_else(|| format!("unknown language id {}", id))?;
+            Ok((code.to_string(), probabilities.get(id as usize).copied().unwrap_or_default()))
+        })
+    }
+
+    pub fn transcribe(
+        model_path: &Path,
+        samples: &[f32],
+        language: &str,
+        prompt: Option<&str>,
+        translate: bool,
+        options: &TranscriptionOptions,
+        on_segment: Option<SegmentSink>,
+    ) -> Result<Vec<String>, String> {
+        with_context(model_path, |context| {
+            run_full(context, samples, language, prompt, translate, options, on_segment)
+        })
+    }
+
+    /// Segments as `(start_ms, end_ms, text, speaker_turn_next)` with
+    /// tinydiarize enabled; needs a `-tdrz` model.
+    pub fn transcribe_turns(
+        model_path: &Path,
+        samples: &[f32],
+        language: &str,
+        prompt: Option<&str>,
+    ) -> Result<Vec<(u64, u64, String, bool)>, String> {
+        with_context(model_path, |context| {
+            let mut state = context
+                .create_state()
+                .map_err(|err| format!("failed to create whisper state: {}", err))?;
+            let mut params = FullParams::new(SamplingStrategy::Greedy { best_of: 1 });
+            params.set_language(Some(language));
+            if let Some(prompt) = prompt {
+                params.set_initial_prompt(prompt);
+            }
+            params.set_tdrz_enable(true);
+            params.set_print_special(false);
+            params.set_print_progress(false);
+            params.set_print_realtime(false);
+            params.set_print_timestamps(false);
+            state
+                .full(params, samples)
+                .map_err(|err| format!("whisper failed: {}", err))?;
+            let segments = state
+                .full_n_segments()
+                .map_err(|err| format!("failed to read segments: {}", err))?;
+            (0..segments)
+                .map(|index| {
+                    let read_err = |err| format!("failed to read segment {}: {}", index, err);
+                    // whisper timestamps are in centiseconds.
+                    let start = state.full_get_segment_t0(index).map_err(read_err)?.max(0) as u64 * 10;
+                    let end = state.full_get_segment_t1(index).map_err(read_err)?.max(0) as u64 * 10;
+                    let text = state.full_get_segment_text_lossy(index).map_err(read_err)?;
+                    Ok((start, end, text, state.full_get_segment_speaker_turn_next(index)))
+                })
+                .collect()
+        })
+    }
+
+    /// Segments with timestamps and per-token probabilities. whisper-rs
+    /// doesn't expose the no-speech probability.
+    pub fn transcribe_detailed(
+        model_path: &Path,
+        samples: &[f32],
+        language: &str,
+        prompt: Option<&str>,
+    ) -> Result<Vec<RawSegment>, String> {
+        with_context(model_path, |context| {
+            let mut state = context
+                .create_state()
+                .map_err(|err| format!("failed to create whisper state: {}", err))?;
+            let mut params = FullParams::new(SamplingStrategy::Greedy { best_of: 1 });
+            params.set_language(Some(language));
+            if let Some(prompt) = prompt {
+                params.set_initial_prompt(prompt);
+            }
+            params.set_print_special(false);
+            params.set_print_progress(false);
+            params.set_print_realtime(false);
+            params.set_print_timestamps(false);
+            params.set_token_timestamps(true);
+            state
+                .full(params, samples)
+                .map_err(|err| format!("whisper failed: {}", err))?;
+            let segments = state
+                .full_n_segments()
+                .map_err(|err| format!("failed to read segments: {}", err))?;
+            (0..segments)
+                .map(|index| {
+                    let read_err = |err| format!("failed to read segment {}: {}", index, err);
+                    let token_count = state.full_n_tokens(index).map_err(read_err)?;
+                    let tokens = (0..token_count)
+                        .map(|token| {
+                            let data = state.full_get_token_data(index, token).map_err(read_err)?;
+                            // Centiseconds, or -1 when unset.
+                            let ms = |t: i64| u64::try_from(t).ok().map(|t| t * 10);
+                            Ok(RawToken {
+                                text: state.full_get_token_text_lossy(index, token).map_err(read_err)?,
+                                probability: data.p,
+                                start_ms: ms(data.t0),
+                                end_ms: ms(data.t1),
+                            })
+                        })
+                        .collect::<Result<Vec<_>, String>>()?;
+                    Ok(RawSegment {
+                        // whisper timestamps are in centiseconds.
+                        start_ms: state.full_get_segment_t0(index).map_err(read_err)?.max(0) as u64 * 10,
+                        end_ms: state.full_get_segment_t1(index).map_err(read_err)?.max(0) as u64 * 10,
+                        text: state.full_get_segment_text_lossy(index).map_err(read_err)?,
+                        tokens,
+                        no_speech_prob: None,
+                    })
+                })
+                .collect()
+        })
+    }
+
+    fn run_full(
+        context: &WhisperContext,
+        samples: &[f32],
+        language: &str,
+        prompt: Option<&str>,
+        translate: bool,
+        options: &TranscriptionOptions,
+        on_segment: Option<SegmentSink>,
+    ) -> Result<Vec<String>, String> {
+        let mut state = context
+            .create_state()
+            .map_err(|err| format!("failed to create whisper state: {}", err))?;
+
+        let strategy = match options.beam_size {
+            Some(beam_size) => SamplingStrategy::BeamSearch {
+                beam_size: beam_size as i32,
+                patience: -1.0,
+            },
+            None => SamplingStrategy::Greedy { best_of: 1 },
+        };
+        let mut params = FullParams::new(strategy);
+        params.set_language(Some(language));
+        params.set_translate(translate);
+        params.set_print_special(false);
+        params.set_print_progress(false);
+        params.set_print_realtime(false);
+        params.set_print_timestamps(false);
+        if let Some(prompt) = prompt {
+            params.set_initial_prompt(prompt);
+        }
+        if let Some(value) = options.logprob_thold {
+            params.set_logprob_thold(value);
+        }
+        if let Some(value) = options.no_speech_thold {
+            params.set_no_speech_thold(value);
+        }
+        if let Some(value) = options.temperature {
+            params.set_temperature(value);
+        }
+        if let Some(value) = options.temperature_inc {
+            params.set_temperature_inc(value);
+        }
+        if let Some(value) = options.entropy_thold {
+            params.set_entropy_thold(value);
+        }
+        if let Some(value) = options.max_segment_len.filter(|value| *value > 0) {
+            // whisper splits segments by length on token timestamps.
+            params.set_token_timestamps(true);
+            params.set_max_len(value as i32);
+        }
+        if let Some(sink) = on_segment {
+            params.set_segment_callback_safe_lossy(move |segment: SegmentCallbackData| sink(&segment.text));
+        }
+        state
+            .full(params, samples)
+            .map_err(|err| format!("whisper failed: {}", err))?;
+
+        let segments = state
+            .full_n_segments()
+            .map_err(|err| format!("failed to read segments: {}", err))?;
+        (0..segments)
+            .map(|index| {
+                state
+                    .full_get_segment_text_lossy(index)
+                    .map_err(|err| format!("failed to read segment {}: {}", index, err))
+            })
+            .collect()
+    }
+}
+
+#[cfg(feature = "whisper-rs")]
+pub use engine::{detect_language, transcribe, transcribe_detailed, transcribe_turns};
@@ -0,0 +1,800 @@
+use std::{
+    collections::{BTreeMap, HashMap, HashSet},
+    fs,
+    io::{Read, Write},
+    path::{Path, PathBuf},
+    sync::Mutex,
+    time::{Duration, SystemTime},
+};
+
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use tauri::Manager;
+use tauri_specta::Event;
+
+use crate::jobs::{self, JobSpec};
+use crate::preflight::{self, PreflightOperation};
+use crate::{auth, background, log_line, model_variants, settings};
+
+pub const DOWNLOAD_BASE: &str = "https://huggingface.co/ggerganov/whisper.cpp/resolve/main";
+/// Progress events are sent at most this often (in bytes).
+const PROGRESS_STEP: u64 = 2 * 1024 * 1024;
+/// Known-good SHA256 per model file, refreshed with
+/// `scripts/update-model-checksums.sh`.
+const BUNDLED_CHECKSUMS: &str = include_str!("../whisper-models.json");
+/// Checksums recorded at download time (from HuggingFace's `x-linked-etag`)
+/// for models the bundled manifest doesn't list, in the models dir.
+const RECORDED_CHECKSUMS: &str = "checksums.json";
+/// ggml model files start with this magic (`ggml`, little-endian).
+const GGML_MAGIC: &[u8; 4] = b"lmgg";
+/// Catalog models are at least this share of their listed size.
+const MIN_SIZE_RATIO: f64 = 0.9;
+/// Longest the startup verification defers each model to live work.
+const VERIFY_MAX_WAIT: Duration = Duration::from_secs(120);
+
+/// ggml models offered for download, with their approximate size in MB.
+const CATALOG: &[(&str, u32)] = &[
+    ("tiny.en", 75),
+    ("tiny", 75),
+    ("base.en", 142),
+    ("base", 142),
+    ("small.en", 466),
+    ("small", 466),
+    ("medium.en", 1500),
+    ("medium", 1500),
+    ("large-v3-turbo", 1620),
+    ("large-v3", 3100),
+];
+
+#[derive(Serialize, Deserialize, Clone, specta::Type)]
+#[serde(rename_all = "camelCase")]
+pub struct WhisperModel {
+    /// Catalog name, e.g. `small.en`.
+    pub name: String,
+    /// `ggml-<name>.bin`, the value stored in `transcription.whisperModel`.
+    pub file_name: String,
+    pub size_mb: u32,
+    pub installed: bool,
+    /// Whether local transcription currently uses this model.
+    pub active: bool,
+    /// Multilingual models (no `.en` suffix) can transcribe other languages.
+    pub multilingual: bool,
+    /// Quantization of the variant loaded in its place, e.g. `q5_1`.
+    pub variant: Option<String>,
+    /// A Core ML encoder sits next to it.
+    pub core_ml: bool,
+}
+
+/// Emitted as `model-download-progress` to every window while a model
+/// downloads; the model manager lives in the settings window.
+#[derive(Serialize, Deserialize, Clone, specta::Type, tauri_specta::Event)]
+#[serde(rename_all = "camelCase")]
+pub struct ModelDownloadProgress {
+    pub name: String,
+    pub downloaded_bytes: u64,
+    pub total_bytes: Option<u64>,
+    pub done: bool,
+}
+
+#[derive(Serialize, Deserialize, Clone, Copy, Debug, PartialEq, Eq, specta::Type)]
+#[serde(rename_all = "camelCase")]
+pub enum IntegrityState {
+    /// Failed verification and was moved aside; a re-download started.
+    Corrupt,
+    Repaired,
+    RepairFailed,
+}
+
+/// Emitted as `model-integrity` when a model fails its checksum and again
+/// when the automatic re-download finishes.
+#[derive(Serialize, Deserialize, Clone, specta::Type, tauri_specta::Event)]
+#[serde(rename_all = "camelCase")]
+pub struct ModelIntegrity {
+    pub file_name: String,
+    pub state: IntegrityState,
+    pub detail: String,
+}
+
+#[derive(Serialize, Deserialize, Clone, Copy, Debug, PartialEq, Eq, specta::Type)]
+#[serde(rename_all = "camelCase")]
+pub enum ModelCheckState {
+    /// Matches its checksum.
+    Ok,
+    /// Looks whole, but there is no checksum to compare against.
+    Unverified,
+    /// Truncated, not a ggml file, or fails its checksum.
+    Damaged,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, specta::Type)]
+#[serde(rename_all = "camelCase")]
+pub struct ModelCheck {
+    pub file_name: String,
+    pub path: String,
+    pub state: ModelCheckState,
+    /// What is wrong with a damaged model.
+    pub detail: Option<String>,
+    /// A catalog model HeyJamie can download again.
+    pub repairable: bool,
+    /// Moved aside and re-downloading (only when repair was asked for).
+    pub repairing: bool,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, specta::Type)]
+#[serde(rename_all = "camelCase")]
+pub struct WhisperSetupReport {
+    pub cli_path: Option<String>,
+    pub models: Vec<ModelCheck>,
+}
+
+/// Names of models currently downloading, so a second click doesn't start
+/// another write to the same file, and models already checked against
+/// their checksum (by size and modification time) so each is hashed once.
+#[derive(Default)]
+pub struct ModelDownloadState {
+    active: Mutex<HashSet<String>>,
+    verified: Mutex<HashMap<PathBuf, (u64, SystemTime)>>,
+}
+
+pub fn file_name(name: &str) -> String {
+    format!("ggml-{}.bin", name)
+}
+
+/// Catalog entry for `name`; anything else (including paths) is rejected.
+fn catalog_entry(name: &str) -> Result<(&'static str, u32), String> {
+    CATALOG
+        .iter()
+        .find(|(entry, _)| *entry == name)
+        .copied()
+        .ok_or_else(|| format!("unknown whisper model: {}", name))
+}
+
+/// A catalog name or a quantized variant of one (`base.en-q5_1`).
+pub fn known_model(name: &str) -> Result<String, String> {
+    if let Ok((name, _)) = catalog_entry(name) {
+        return Ok(name.to_string());
+    }
+    match model_variants::split(&file_name(name)) {
+        Some((base, Some(_))) if catalog_entry(base).is_ok() => Ok(name.to_string()),
+        _ => Err(format!("unknown whisper model: {}", name)),
+    }
+}
+
+/// Disk space a download of `name` needs: its listed size (a quantized
+/// variant's base model, which is larger) plus a tenth for headroom.
+fn download_size_bytes(name: &str) -> u64 {
+    let base = match model_variants::split(&file_name(name)) {
+        Some((base, _)) => base.to_string(),
+        None => name.to_string(),
+    };
+    let size_mb = catalog_entry(&base).map(|(_, size_mb)| size_mb).unwrap_or(0) as u64;
+    size_mb * 1024 * 1024 * 11 / 10
+}
+
+fn parse_checksums(content: &str) -> Option<BTreeMap<String, String>> {
+    let parsed: BTreeMap<String, String> = serde_json::from_str(content).ok()?;
+    Some(
+        parsed
+            .into_iter()
+            .map(|(file, sha)| (file, sha.trim().to_lowercase()))
+            .collect(),
+    )
+}
+
+fn recorded_checksums(app: &tauri::AppHandle) -> BTreeMap<String, String> {
+    models_dir(app)
+        .ok()
+        .and_then(|dir| fs::read_to_string(dir.join(RECORDED_CHECKSUMS)).ok())
+        .and_then(|content| parse_checksums(&content))
+        .unwrap_or_default()
+}
+
+fn record_checksum(app: &tauri::AppHandle, file_name: &str, sha256: &str) -> Result<(), String> {
+    let mut recorded = recorded_checksums(app);
+    recorded.insert(file_name.to_string(), sha256.to_string());
+    let content = serde_json::to_string_pretty(&recorded)
+        .map_err(|err| format!("failed to format checksums: {}", err))?;
+    fs::write(models_dir(app)?.join(RECORDED_CHECKSUMS), content.as_bytes())
+        .map_err(|err| format!("failed to write checksums: {}", err))
+}
+
+/// The SHA256 `file_name` should have: the bundled manifest first, then the
+/// checksum recorded when it was downloaded.
+fn expected_checksum(app: &tauri::AppHandle, file_name: &str) -> Option<String> {
+    parse_checksums(BUNDLED_CHECKSUMS)
+        .and_then(|bundled| bundled.get(file_name).cloned())
+        .or_else(|| recorded_checksums(app).remove(file_name))
+}
+
+fn hex(digest: &[u8]) -> String {
+    digest.iter().map(|byte| format!("{:02x}", byte)).collect()
+}
+
+/// Feed `path` into `hasher`; returns how many bytes it held.
+fn hash_into(path: &Path, hasher: &mut Sha256) -> Result<u64, String> {
+    let mut file = fs::File::open(path).map_err(|err| format!("failed to open {}: {}", path.display(), err))?;
+    let mut buffer = vec![0u8; 1024 * 1024];
+    let mut total = 0u64;
+    loop {
+        let read = file
+            .read(&mut buffer)
+            .map_err(|err| format!("failed to read {}: {}", path.display(), err))?;
+        if read == 0 {
+            break;
+        }
+        hasher.update(&buffer[..read]);
+        total += read as u64;
+    }
+    Ok(total)
+}
+
+fn sha256_file(path: &Path) -> Result<String, String> {
+    let mut hasher = Sha256::new();
+    hash_into(path, &mut hasher)?;
+    Ok(hex(&hasher.finalize()))
+}
+
+/// Whether a model file with this `header` and `len` can be whole: it must
+/// start with the ggml magic, and a catalog model must be close to its
+/// listed size. Catches truncated downloads that have no checksum.
+fn check_shape(file_name: &str, header: &[u8], len: u64) -> Result<(), String> {
+    if !header.starts_with(GGML_MAGIC) {
+        return Err(format!("{} is not a ggml model (it may be an HTML error page or empty)", file_name));
+    }
+    let name = file_name.trim_start_matches("ggml-").trim_end_matches(".bin");
+    if let Ok((_, size_mb)) = catalog_entry(name) {
+        let min_bytes = (size_mb as f64 * MIN_SIZE_RATIO * 1024.0 * 1024.0) as u64;
+        if len < min_bytes {
+            return Err(format!(
+                "{} is truncated ({} MB, expected about {} MB)",
+                file_name,
+                len / (1024 * 1024),
+                size_mb
+            ));
+        }
+    }
+    Ok(())
+}
+
+fn read_header(path: &Path) -> Result<Vec<u8>, String> {
+    let mut header = Vec::with_capacity(GGML_MAGIC.len());
+    fs::File::open(path)
+        .and_then(|file| file.take(GGML_MAGIC.len() as u64).read_to_end(&mut header))
+        .map_err(|err| format!("failed to read {}: {}", path.display(), err))?;
+    Ok(header)
+}
+
+/// Check a model file's header and size, then its expected checksum.
+/// Files without a known checksum pass when they look whole.
+fn verify(app: &tauri::AppHandle, path: &Path) -> Result<(), String> {
+    let Some(file_name) = path.file_name().and_then(|name| name.to_str()) else {
+        return Ok(());
+    };
+    let metadata = fs::metadata(path).map_err(|err| format!("failed to read {}: {}", path.display(), err))?;
+    let key = (metadata.len(), metadata.modified().unwrap_or(SystemTime::UNIX_EPOCH));
+    let state = app.state::<ModelDownloadState>();
+    if state.verified.lock().unwrap().get(path) == Some(&key) {
+        return Ok(());
+    }
+    check_shape(file_name, &read_header(path)?, metadata.len())?;
+    let Some(expected) = expected_checksum(app, file_name) else {
+        return Ok(());
+    };
+    let actual = sha256_file(path)?;
+    if actual != expected {
+        return Err(format!(
+            "{} is corrupt (SHA256 {}, expected {})",
+            file_name, actual, expected
+        ));
+    }
+    state.verified.lock().unwrap().insert(path.to_path_buf(), key);
+    Ok(())
+}
+
+fn notify_integrity(app: &tauri::AppHandle, file_name: &str, state: IntegrityState, detail: String) {
+    log_line(&format!("[models] {} {:?}: {}", file_name, state, detail));
+    let event = ModelIntegrity {
+        file_name: file_name.to_string(),
+        state,
+        detail,
+    };
+    if let Err(err) = event.emit(app) {
+        log_line(&format!("[models] failed to send integrity event: {}", err));
+    }
+}
+
+/// Move a corrupt model aside and download a fresh copy in the background.
+fn start_repair(app: &tauri::AppHandle, path: &Path, reason: String) {
+    let file = path.file_name().and_then(|name| name.to_str()).unwrap_or_default().to_string();
+    let aside = path.with_extension("bin.corrupt");
+    if let Err(err) = fs::rename(path, &aside) {
+        notify_integrity(app, &file, IntegrityState::RepairFailed, format!("{}; failed to move it aside: {}", reason, err));
+        return;
+    }
+    app.state::<ModelDownloadState>().verified.lock().unwrap().remove(path);
+    let name = file.trim_start_matches("ggml-").trim_end_matches(".bin").to_string();
+    let Ok(name) = known_model(&name) else {
+        notify_integrity(app, &file, IntegrityState::RepairFailed, format!("{}; not a catalog model, re-download it manually", reason));
+        return;
+    };
+    notify_integrity(app, &file, IntegrityState::Corrupt, reason);
+    let app = app.clone();
+    tauri::async_runtime::spawn(async move {
+        match download_tracked(&app, &name).await {
+            Ok(path) => {
+                let _ = fs::remove_file(&aside);
+                notify_integrity(&app, &file, IntegrityState::Repaired, path.display().to_string());
+            }
+            Err(err) => notify_integrity(&app, &file, IntegrityState::RepairFailed, err),
+        }
+    });
+}
+
+/// Verify a model right before it is used. A corrupt file is moved aside
+/// and re-downloaded, and this returns an error so the caller doesn't
+/// transcribe garbage.
+pub fn ensure_intact(app: &tauri::AppHandle, path: &Path) -> Result<(), String> {
+    match verify(app, path) {
+        Ok(()) => Ok(()),
+        Err(err) => {
+            start_repair(app, path, err.clone());
+            Err(format!("{}; re-downloading it", err))
+        }
+    }
+}
+
+/// Check every installed catalog model in the background at startup.
+pub fn spawn_verification(app: tauri::AppHandle) {
+    std::thread::spawn(move || {
+        for (name, _) in CATALOG {
+            if let Some(path) = find(&app, &file_name(name)) {
+                background::wait_for_quiet_blocking(&app, "model verification", VERIFY_MAX_WAIT);
+                record_published_checksum(&app, name);
+                let _ = ensure_intact(&app, &path);
+            }
+        }
+    });
+}
+
+/// Record HuggingFace's checksum for a catalog model the manifest doesn't
+/// list and that wasn't downloaded here (e.g. by `setup-whisper.sh`), so it
+/// is verified like the rest. Offline, the model stays unverified.
+fn record_published_checksum(app: &tauri::AppHandle, name: &str) {
+    let file = file_name(name);
+    if expected_checksum(app, &file).is_some() {
+        return;
+    }
+    let url = format!("{}/{}", DOWNLOAD_BASE, file);
+    match tauri::async_runtime::block_on(published_checksum(&url)) {
+        Some(sha) => {
+            if let Err(err) = record_checksum(app, &file, &sha) {
+                log_line(&format!("[models] {}", err));
+            }
+        }
+        None => log_line(&format!("[models] no published checksum for {}; checking its shape only", file)),
+    }
+}
+
+/// The message for a whisper-cli run that failed because the model didn't
+/// load, in place of whisper.cpp's raw stderr.
+pub fn explain_load_failure(stderr: &str, model_path: &Path) -> Option<String> {
+    const SIGNS: &[&str] = &[
+        "bad magic",
+        "failed to load model",
+        "failed to initialize whisper context",
+        "wrong size in model file",
+        "wrong shape in model file",
+        "unknown tensor",
+        "invalid model data",
+    ];
+    let lower = stderr.to_lowercase();
+    if !SIGNS.iter().any(|sign| lower.contains(sign)) {
+        return None;
+    }
+    let name = model_path.file_name().and_then(|name| name.to_str()).unwrap_or("the model");
+    Some(format!(
+        "whisper could not load {}; it is probably truncated or corrupt. \
+         Verify it under Settings → Whisper Models, or re-download it",
+        name
+    ))
+}
+
+/// Model files to check: the active model and every ggml file in the
+/// models dir.
+fn installed_model_paths(app: &tauri::AppHandle) -> Vec<PathBuf> {
+    let mut paths: Vec<PathBuf> = resolve(app, None).ok().into_iter().collect();
+    if let Ok(entries) = models_dir(app).and_then(|dir| {
+        fs::read_dir(&dir).map_err(|err| format!("failed to read {}: {}", dir.display(), err))
+    }) {
+        let mut found: Vec<PathBuf> = entries
+            .filter_map(|entry| entry.ok().map(|entry| entry.path()))
+            .filter(|path| {
+                path.is_file()
+                    && path
+                        .file_name()
+                        .and_then(|name| name.to_str())
+                        .is_some_and(|name| name.starts_with("ggml-") && name.ends_with(".bin"))
+            })
+            .collect();
+        found.sort();
+        paths.extend(found);
+    }
+    let mut seen = HashSet::new();
+    paths.retain(|path| seen.insert(path.clone()));
+    paths
+}
+
+fn check_model(app: &tauri::AppHandle, path: &Path, repair: bool) -> ModelCheck {
+    let file_name = path.file_name().and_then(|name| name.to_str()).unwrap_or_default().to_string();
+    let repairable = known_model(file_name.trim_start_matches("ggml-").trim_end_matches(".bin")).is_ok();
+    let (state, detail) = match verify(app, path) {
+        Ok(()) if expected_checksum(app, &file_name).is_some() => (ModelCheckState::Ok, None),
+        Ok(()) => (ModelCheckState::Unverified, None),
+        Err(err) => (ModelCheckState::Damaged, Some(err)),
+    };
+    let repairing = repair && repairable && state == ModelCheckState::Damaged;
+    if let (true, Some(detail)) = (repairing, &detail) {
+        start_repair(app, path, detail.clone());
+    }
+    ModelCheck {
+        file_name,
+        path: path.display().to_string(),
+        state,
+        detail,
+        repairable,
+        repairing,
+    }
+}
+
+/// Check whisper-cli and every installed model: header, size and SHA256
+/// (bundled or recorded at download). With `repair`, damaged catalog
+/// models are moved aside and downloaded again.
+#[tauri::command]
+#[specta::specta]
+pub async fn verify_whisper_setup(app: tauri::AppHandle, repair: bool) -> Result<WhisperSetupReport, String> {
+    tauri::async_runtime::spawn_blocking(move || {
+        let models: Vec<ModelCheck> = installed_model_paths(&app)
+            .iter()
+            .map(|path| check_model(&app, path, repair))
+            .collect();
+        let damaged = models.iter().filter(|model| model.state == ModelCheckState::Damaged).count();
+        log_line(&format!("[models] verified {} models, {} damaged", models.len(), damaged));
+        WhisperSetupReport {
+            cli_path: crate::find_whisper_cli().map(|path| path.display().to_string()),
+            models,
+        }
+    })
+    .await
+    .map_err(|err| format!("verification task failed: {}", err))
+}
+
+/// `<app data>/models`, where downloaded models live.
+pub fn models_dir(app: &tauri::AppHandle) -> Result<PathBuf, String> {
+    let dir = app
+        .path()
+        .app_data_dir()
+        .map_err(|err| format!("failed to resolve app data dir: {}", err))?
+        .join("models");
+    fs::create_dir_all(&dir).map_err(|err| format!("failed to create models dir: {}", err))?;
+    Ok(dir)
+}
+
+/// A model file from the models dir, or next to the default model that
+/// `scripts/setup-whisper.sh` installs.
+pub fn find(app: &tauri::AppHandle, file_name: &str) -> Option<PathBuf> {
+    models_dir(app)
+        .ok()
+        .map(|dir| dir.join(file_name))
+        .into_iter()
+        .chain(crate::find_whisper_model().map(|default| default.with_file_name(file_name)))
+        .find(|path| path.is_file())
+}
+
+/// The model local transcription should use: `preferred` (e.g. the
+/// battery-saver model) when installed, then the model picked in settings,
+/// then the default lookup, then any downloaded catalog model. A quantized
+/// variant next to it is used in its place (see `model_variants`), except
+/// for a `WHISPER_MODEL_PATH` override.
+pub fn resolve(app: &tauri::AppHandle, preferred: Option<&str>) -> Result<PathBuf, String> {
+    if let Some(name) = preferred {
+        match find(app, name) {
+            Some(path) => return Ok(model_variants::prefer(app, path)),
+            None => log_line(&format!("whisper model {} not installed; using default", name)),
+        }
+    }
+    let selected = settings::load_settings(app).transcription.whisper_model;
+    if !selected.trim().is_empty() {
+        match find(app, selected.trim()) {
+            Some(path) => return Ok(model_variants::prefer(app, path)),
+            None => log_line(&format!("selected whisper model {} not installed", selected)),
+        }
+    }
+    if std::env::var("WHISPER_MODEL_PATH").is_ok() {
+        return crate::resolve_whisper_model();
+    }
+    crate::resolve_whisper_model()
+        .or_else(|err| {
+            CATALOG
+                .iter()
+                .find_map(|(name, _)| find(app, &file_name(name)))
+                .ok_or(err)
+        })
+        .map(|path| model_variants::prefer(app, path))
+}
+
+/// The first installed multilingual catalog model, for language detection
+/// when the active model is English-only.
+pub fn find_multilingual(app: &tauri::AppHandle) -> Option<PathBuf> {
+    CATALOG
+        .iter()
+        .filter(|(name, _)| !name.ends_with(".en"))
+        .find_map(|(name, _)| find(app, &file_name(name)))
+}
+
+#[tauri::command]
+#[specta::specta]
+pub fn list_whisper_models(app: tauri::AppHandle) -> Vec<WhisperModel> {
+    let active = resolve(&app, None).ok();
+    let exact = settings::load_settings(&app).transcription.exact_model;
+    CATALOG
+        .iter()
+        .map(|(name, size_mb)| {
+            let path = find(&app, &file_name(name));
+            let variant = path.as_deref().filter(|_| !exact).and_then(model_variants::variant_of);
+            let loaded = variant.clone().or_else(|| path.clone());
+            WhisperModel {
+                name: name.to_string(),
+                file_name: file_name(name),
+                size_mb: *size_mb,
+                installed: path.is_some(),
+                active: loaded.is_some() && loaded == active,
+                multilingual: !name.ends_with(".en"),
+                variant: variant
+                    .as_deref()
+                    .and_then(|variant| variant.file_name()?.to_str())
+                    .and_then(|variant| model_variants::split(variant)?.1)
+                    .map(str::to_string),
+                core_ml: path.as_deref().is_some_and(model_variants::has_core_ml_encoder),
+            }
+        })
+        .collect()
+}
+
+fn emit_progress(app: &tauri::AppHandle, progress: ModelDownloadProgress) {
+    if let Err(err) = progress.emit(app) {
+        log_line(&format!("[models] failed to send download progress: {}", err));
+    }
+}
+
+/// The SHA256 HuggingFace publishes for an LFS file, read from the
+/// `x-linked-etag` header of the redirect it answers with.
+async fn published_checksum(url: &str) -> Option<String> {
+    let client = reqwest::Client::builder()
+        .redirect(reqwest::redirect::Policy::none())
+        .build()
+        .ok()?;
+    let response = client.head(url).send().await.ok()?;
+    let etag = response.headers().get("x-linked-etag")?.to_str().ok()?;
+    let sha = etag.trim().trim_start_matches("W/").trim_matches('"').to_lowercase();
+    (sha.len() == 64 && sha.chars().all(|c| c.is_ascii_hexdigit())).then_some(sha)
+}
+
+fn partial_path(app: &tauri::AppHandle, name: &str) -> Result<PathBuf, String> {
+    Ok(models_dir(app)?.join(file_name(name)).with_extension("bin.part"))
+}
+
+/// Delete what an unfinished download of `name` left behind.
+pub fn remove_partial(app: &tauri::AppHandle, name: &str) {
+    if let Ok(partial) = partial_path(app, name) {
+        let _ = fs::remove_file(partial);
+    }
+}
+
+/// Download `name`, continuing from a `.part` file an earlier attempt left
+/// when the server honours the range request.
+async fn download(app: &tauri::AppHandle, name: &str, job_id: Option<&str>) -> Result<PathBuf, String> {
+    let dir = models_dir(app)?;
+    preflight::ensure_disk(app, PreflightOperation::ModelDownload, &dir, download_size_bytes(name))?;
+    let target = dir.join(file_name(name));
+    let partial = partial_path(app, name)?;
+    let url = format!("{}/{}", DOWNLOAD_BASE, file_name(name));
+    log_line(&format!("[models] downloading {}", url));
+    let expected = match parse_checksums(BUNDLED_CHECKSUMS).and_then(|bundled| bundled.get(&file_name(name)).cloned()) {
+        Some(sha) => Some(sha),
+        None => published_checksum(&url).await,
+    };
+    let mut hasher = Sha256::new();
+    let mut resume_from = if partial.is_file() {
+        hash_into(&partial, &mut hasher)?
+    } else {
+        0
+    };
+    let client = auth::http_client()?;
+    let send = |from: u64| {
+        let request = client.get(&url);
+        let request = if from > 0 {
+            request.header(reqwest::header::RANGE, format!("bytes={}-", from))
+        } else {
+            request
+        };
+        request.send()
+    };
+    let mut response = send(resume_from)
+        .await
+        .map_err(|err| format!("download of {} failed: {}", name, err))?;
+    if resume_from > 0 && response.status() == reqwest::StatusCode::RANGE_NOT_SATISFIABLE {
+        resume_from = 0;
+        response = send(0)
+            .await
+            .map_err(|err| format!("download of {} failed: {}", name, err))?;
+    }
+    if !response.status().is_success() {
+        return Err(format!("download of {} failed: HTTP {}", name, response.status()));
+    }
+    let resumed = resume_from > 0 && response.status() == reqwest::StatusCode::PARTIAL_CONTENT;
+    let mut file = if resumed {
+        log_line(&format!("[models] resuming {} at {} MB", name, resume_from / (1024 * 1024)));
+        fs::OpenOptions::new()
+            .append(true)
+            .open(&partial)
+            .map_err(|err| format!("failed to open {}: {}", partial.display(), err))?
+    } else {
+        hasher = Sha256::new();
+        fs::File::create(&partial).map_err(|err| format!("failed to create {}: {}", partial.display(), err))?
+    };
+    let mut downloaded_bytes = if resumed { resume_from } else { 0 };
+    let total_bytes = response.content_length().map(|length| length + downloaded_bytes);
+    let mut reported = downloaded_bytes;
+    let mut checkpointed = downloaded_bytes;
+    while let Some(chunk) = response
+        .chunk()
+        .await
+        .map_err(|err| format!("download of {} interrupted: {}", name, err))?
+    {
+        file.write_all(&chunk)
+            .map_err(|err| format!("failed to write {}: {}", partial.display(), err))?;
+        hasher.update(&chunk);
+        downloaded_bytes += chunk.len() as u64;
+        if downloaded_bytes - reported >= PROGRESS_STEP {
+            reported = downloaded_bytes;
+            emit_progress(
+                app,
+                ModelDownloadProgress {
+                    name: name.to_string(),
+                    downloaded_bytes,
+                    total_bytes,
+                    done: false,
+                },
+            );
+        }
+        if let (Some(job_id), Some(total)) = (job_id, total_bytes) {
+            if downloaded_bytes - checkpointed >= total / 20 {
+                checkpointed = downloaded_bytes;
+                jobs::progress(app, job_id, Some(downloaded_bytes as f32 / total as f32));
+            }
+        }
+    }
+    if total_bytes.is_some_and(|total| total != downloaded_bytes) {
+        return Err(format!("download of {} ended early", name));
+    }
+    let actual = hex(&hasher.finalize());
+    if let Some(expected) = expected.as_ref().filter(|expected| **expected != actual) {
+        // Nothing in a corrupt file is worth resuming from.
+        let _ = fs::remove_file(&partial);
+        return Err(format!(
+            "download of {} is corrupt (SHA256 {}, expected {})",
+            name, actual, expected
+        ));
+    }
+    // Without a published checksum, the hash of the complete download still
+    // catches later truncation or corruption.
+    record_checksum(app, &file_name(name), expected.as_deref().unwrap_or(&actual))?;
+    fs::rename(&partial, &target).map_err(|err| format!("failed to install {}: {}", name, err))?;
+    emit_progress(
+        app,
+        ModelDownloadProgress {
+            name: name.to_string(),
+            downloaded_bytes,
+            total_bytes,
+            done: true,
+        },
+    );
+    log_line(&format!("[models] installed {}", target.display()));
+    Ok(target)
+}
+
+async fn download_tracked(app: &tauri::AppHandle, name: &str) -> Result<PathBuf, String> {
+    let state = app.state::<ModelDownloadState>();
+    if !state.active.lock().unwrap().insert(name.to_string()) {
+        return Err(format!("{} is already downloading", name));
+    }
+    // The `.part` file is kept on failure so `resume_job` can continue it.
+    let job_id = jobs::start_logged::<()>(app, JobSpec::ModelDownload { name: name.to_string() }).map(|(id, _)| id);
+    let result = download(app, name, job_id.as_deref()).await;
+    if let Some(job_id) = &job_id {
+        jobs::finish(app, job_id, &result);
+    }
+    state.active.lock().unwrap().remove(name);
+    result
+}
+
+/// Download a catalog model, or a quantized variant of one, into the
+/// models dir, checking it against the bundled or published SHA256.
+/// Returns its path.
+#[tauri::command]
+#[specta::specta]
+pub async fn download_whisper_model(app: tauri::AppHandle, name: String) -> Result<String, String> {
+    let name = known_model(&name)?;
+    download_tracked(&app, &name)
+        .await
+        .map(|path| path.display().to_string())
+}
+
+/// Remove a downloaded model. Models installed by the setup script are
+/// left alone.
+#[tauri::command]
+#[specta::specta]
+pub fn delete_whisper_model(app: tauri::AppHandle, name: String) -> Result<(), String> {
+    let name = known_model(&name)?;
+    let path = models_dir(&app)?.join(file_name(&name));
+    if !path.exists() {
+        return Err(format!("{} was not downloaded by HeyJamie", name));
+    }
+    fs::remove_file(&path).map_err(|err| format!("failed to delete {}: {}", name, err))?;
+    log_line(&format!("[models] deleted {}", path.display()));
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use std::path::Path;
+
+    use super::{
+        catalog_entry, check_shape, download_size_bytes, explain_load_failure, file_name, hex, parse_checksums,
+        BUNDLED_CHECKSUMS, CATALOG,
+    };
+    use sha2::{Digest, Sha256};
+
+    #[test]
+    fn download_preflight_uses_the_model_size() {
+        let mb = 1024 * 1024;
+        assert_eq!(download_size_bytes("large-v3"), 3100 * mb * 11 / 10);
+        assert_eq!(download_size_bytes("base.en-q5_1"), download_size_bytes("base.en"));
+    }
+
+    #[test]
+    fn only_catalog_names_are_accepted() {
+        assert_eq!(catalog_entry("small.en").map(|(name, _)| file_name(name)), Ok("ggml-small.en.bin".to_string()));
+        assert!(catalog_entry("../../etc/passwd").is_err());
+        assert!(catalog_entry("").is_err());
+    }
+
+    #[test]
+    fn bundled_manifest_parses_and_hashes_are_hex() {
+        let bundled = parse_checksums(BUNDLED_CHECKSUMS).unwrap();
+        for (file, sha) in &bundled {
+            let name = file.trim_start_matches("ggml-").trim_end_matches(".bin");
+            assert!(CATALOG.iter().any(|(known, _)| *known == name), "{} is not in the catalog", file);
+            assert!(sha.len() == 64 && sha.chars().all(|c| c.is_ascii_hexdigit()), "{} has a bad hash", file);
+        }
+        assert_eq!(
+            hex(&Sha256::digest(b"abc")),
+            "ba7816bf8f01cfea414140de5dae2223b00361a396177a9cb410ff61f20015ad"
+        );
+    }
+
+    #[test]
+    fn spots_truncated_and_foreign_files() {
+        let mb = 1024 * 1024;
+        assert!(check_shape("ggml-tiny.en.bin", b"lmgg", 74 * mb).is_ok());
+        assert!(check_shape("ggml-tiny.en.bin", b"lmgg", 20 * mb).unwrap_err().contains("truncated"));
+        assert!(check_shape("ggml-tiny.en.bin", b"<!DO", 74 * mb).unwrap_err().contains("not a ggml model"));
+        // Quantized variants are smaller than the listed size.
+        assert!(check_shape("ggml-tiny.en-q5_1.bin", b"lmgg", 30 * mb).is_ok());
+
+        let model = Path::new("/models/ggml-base.bin");
+        let stderr = "whisper_model_load: invalid model data (bad magic)\nerror: failed to initialize whisper context";
+        assert!(explain_load_failure(stderr, model).unwrap().contains("ggml-base.bin"));
+        assert!(explain_load_failure("error: input file not found", model).is_none());
+    }
+}
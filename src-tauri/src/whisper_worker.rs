@@ -0,0 +1,245 @@
+//! A warm `whisper-server` next to `whisper-cli`, so local segments skip
+//! the process spawn and model load. The worker is started ahead of the
+//! first segment, restarted when it dies or the model or acceleration
+//! setting changes, and fed segments over HTTP on localhost.
+
+use std::net::{SocketAddr, TcpListener, TcpStream};
+use std::path::{Path, PathBuf};
+use std::process::{Child, Command};
+use std::sync::atomic::{AtomicU32, Ordering};
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
+
+use serde::{Deserialize, Serialize};
+use tauri::Manager;
+
+use crate::process_runner::ProcessRunner;
+use crate::settings::{self, RemoteWhisperKind, RemoteWhisperServer, TranscriptionOptions};
+use crate::{acceleration, graceful_kill, log_line, now_millis, remote_whisper, whisper_models};
+
+/// Loading a large model on the CPU can take a while.
+const STARTUP_TIMEOUT: Duration = Duration::from_secs(60);
+const REQUEST_TIMEOUT_MS: u64 = 120_000;
+
+struct Worker {
+    child: Child,
+    port: u16,
+    model: PathBuf,
+    use_gpu: bool,
+    started_at: u64,
+}
+
+#[derive(Default)]
+pub struct WhisperWorkerState {
+    worker: Mutex<Option<Worker>>,
+    /// Held while a worker starts, so segments wait for one start instead
+    /// of each loading the model, and `worker` stays free meanwhile.
+    starting: Mutex<()>,
+    restarts: AtomicU32,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, specta::Type)]
+#[serde(rename_all = "camelCase")]
+pub struct WhisperWorkerStatus {
+    pub enabled: bool,
+    /// `whisper-server` was found next to `whisper-cli`.
+    pub available: bool,
+    pub running: bool,
+    /// Model file the worker has loaded.
+    pub model: Option<String>,
+    pub started_at: Option<u64>,
+    /// Times the worker was started again after exiting or for another
+    /// model.
+    pub restarts: u32,
+}
+
+/// `WHISPER_SERVER_PATH`, or `whisper-server` built next to `whisper-cli`.
+fn find_server() -> Option<PathBuf> {
+    if let Ok(path) = std::env::var("WHISPER_SERVER_PATH") {
+        return Some(PathBuf::from(path));
+    }
+    let server = crate::find_whisper_cli()?.with_file_name("whisper-server");
+    server.exists().then_some(server)
+}
+
+pub fn enabled(app: &tauri::AppHandle) -> bool {
+    !cfg!(feature = "whisper-rs") && !settings::load_settings(app).transcription.cold_start
+}
+
+fn free_port() -> Result<u16, String> {
+    TcpListener::bind("127.0.0.1:0")
+        .and_then(|listener| listener.local_addr())
+        .map(|addr| addr.port())
+        .map_err(|err| format!("no free port for whisper-server: {}", err))
+}
+
+fn start(model_path: &Path, use_gpu: bool) -> Result<Worker, String> {
+    let server = find_server().ok_or_else(|| "whisper-server not found next to whisper-cli".to_string())?;
+    let port = free_port()?;
+    let mut command = Command::new(&server);
+    acceleration::apply_cli_flags(&mut command);
+    command
+        .arg("-m")
+        .arg(model_path)
+        .arg("-l")
+        .arg("auto")
+        .arg("--host")
+        .arg("127.0.0.1")
+        .arg("--port")
+        .arg(port.to_string());
+    // Log what it reports about failures; the rest is noise.
+    let mut child = ProcessRunner::new("whisper-server", command)
+        .on_stderr_line(|line| {
+            let lower = line.to_ascii_lowercase();
+            if lower.contains("error") || lower.contains("failed") {
+                log_line(&format!("[whisper-worker] {}", line.trim()));
+            }
+        })
+        .spawn_detached()?;
+
+    // The server loads the model before it starts listening.
+    let addr = SocketAddr::from(([127, 0, 0, 1], port));
+    let started = Instant::now();
+    loop {
+        if let Ok(Some(status)) = child.try_wait() {
+            return Err(format!("whisper-server exited during startup ({})", status));
+        }
+        if TcpStream::connect_timeout(&addr, Duration::from_millis(200)).is_ok() {
+            break;
+        }
+        if started.elapsed() >= STARTUP_TIMEOUT {
+            graceful_kill(&mut child);
+            return Err(format!("whisper-server did not start within {}s", STARTUP_TIMEOUT.as_secs()));
+        }
+        std::thread::sleep(Duration::from_millis(100));
+    }
+    log_line(&format!(
+        "[whisper-worker] ready on port {} with {} in {}ms",
+        port,
+        model_path.display(),
+        started.elapsed().as_millis()
+    ));
+    Ok(Worker {
+        child,
+        port,
+        model: model_path.to_path_buf(),
+        use_gpu,
+        started_at: now_millis(),
+    })
+}
+
+/// The port of the worker if it is running `model_path` with `use_gpu`. A
+/// worker that exited or runs another setup is taken out, and stopped if
+/// still alive.
+fn running_port(state: &WhisperWorkerState, model_path: &Path, use_gpu: bool) -> Option<u16> {
+    let (mut stale, alive) = {
+        let mut slot = state.worker.lock().unwrap();
+        let worker = slot.as_mut()?;
+        let alive = matches!(worker.child.try_wait(), Ok(None));
+        if alive && worker.model == model_path && worker.use_gpu == use_gpu {
+            return Some(worker.port);
+        }
+        (slot.take()?, alive)
+    };
+    if alive {
+        log_line("[whisper-worker] model or acceleration changed; restarting");
+        graceful_kill(&mut stale.child);
+    } else {
+        log_line("[whisper-worker] worker exited; restarting");
+    }
+    state.restarts.fetch_add(1, Ordering::SeqCst);
+    None
+}
+
+/// The port of a running worker for `model_path`, starting or replacing
+/// it when needed, and how long a start took. The model loads without
+/// holding the worker lock, so status checks and `stop` don't wait on it.
+fn ensure(app: &tauri::AppHandle, model_path: &Path) -> Result<(u16, Option<Duration>), String> {
+    let state = app.state::<WhisperWorkerState>();
+    let use_gpu = acceleration::use_gpu();
+    if let Some(port) = running_port(&state, model_path, use_gpu) {
+        return Ok((port, None));
+    }
+    let _starting = state.starting.lock().unwrap();
+    // Another segment may have started it while this one waited.
+    if let Some(port) = running_port(&state, model_path, use_gpu) {
+        return Ok((port, None));
+    }
+    whisper_models::ensure_intact(app, model_path)?;
+    let started = Instant::now();
+    let worker = start(model_path, use_gpu)?;
+    let port = worker.port;
+    *state.worker.lock().unwrap() = Some(worker);
+    Ok((port, Some(started.elapsed())))
+}
+
+/// Transcribe a WAV with the warm worker. Also returns the startup time
+/// when the worker had to be (re)started for this segment.
+pub async fn transcribe(
+    app: &tauri::AppHandle,
+    model_path: &Path,
+    wav_bytes: &[u8],
+    language: &str,
+    prompt: Option<&str>,
+    translate: bool,
+    options: &TranscriptionOptions,
+) -> Result<(String, Option<Duration>), String> {
+    let (handle, model) = (app.clone(), model_path.to_path_buf());
+    let (port, startup) = tauri::async_runtime::spawn_blocking(move || ensure(&handle, &model))
+        .await
+        .map_err(|err| format!("whisper-server task failed: {}", err))??;
+    let server = RemoteWhisperServer {
+        enabled: true,
+        url: format!("http://127.0.0.1:{}", port),
+        kind: RemoteWhisperKind::WhisperCpp,
+        timeout_ms: REQUEST_TIMEOUT_MS,
+        retries: 0,
+        ..Default::default()
+    };
+    let text = remote_whisper::transcribe(&server, wav_bytes, language, prompt, translate, options).await?;
+    Ok((text, startup))
+}
+
+pub fn stop(app: &tauri::AppHandle) {
+    let worker = app.state::<WhisperWorkerState>().worker.lock().unwrap().take();
+    if let Some(mut worker) = worker {
+        log_line("[whisper-worker] stopping");
+        graceful_kill(&mut worker.child);
+    }
+}
+
+/// Start the worker in the background so the first segment is warm, or
+/// stop it when it's turned off. Called at startup and whenever settings
+/// are saved.
+pub fn reload(app: &tauri::AppHandle) {
+    if !enabled(app) || find_server().is_none() {
+        stop(app);
+        return;
+    }
+    let app = app.clone();
+    std::thread::spawn(move || {
+        let result = whisper_models::resolve(&app, None).and_then(|model| ensure(&app, &model));
+        if let Err(err) = result {
+            log_line(&format!("[whisper-worker] not started: {}", err));
+        }
+    });
+}
+
+#[tauri::command]
+#[specta::specta]
+pub fn get_whisper_worker_status(app: tauri::AppHandle) -> WhisperWorkerStatus {
+    let state = app.state::<WhisperWorkerState>();
+    let mut slot = state.worker.lock().unwrap();
+    let running = slot.as_mut().is_some_and(|worker| matches!(worker.child.try_wait(), Ok(None)));
+    let worker = slot.as_ref().filter(|_| running);
+    WhisperWorkerStatus {
+        enabled: enabled(&app),
+        available: find_server().is_some(),
+        running,
+        model: worker
+            .and_then(|worker| worker.model.file_name())
+            .map(|name| name.to_string_lossy().into_owned()),
+        started_at: worker.map(|worker| worker.started_at),
+        restarts: state.restarts.load(Ordering::SeqCst),
+    }
+}
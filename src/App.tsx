@@ -1,6 +1,21 @@
 import * as React from "react";
 import { invoke } from "@tauri-apps/api/core";
+import { getCurrentWebview } from "@tauri-apps/api/webview";
 import { openUrl } from "@tauri-apps/plugin-opener";
+import type {
+  BrowserCleanupAction,
+  BrowserSession,
+  ClipboardAction,
+  ClipboardSuggestion,
+  LoadPressure,
+  McpServer,
+  PowerState,
+  RecordingSegment,
+  SavedPrompt,
+  ScenarioSummary,
+  SessionReport,
+} from "./bindings";
+import { commands, events } from "./bindings";
 import {
   Play,
   Pause,
@@ -21,17 +36,16 @@ import {
   DEFAULT_TOPIC_SHIFT_SENSITIVITY,
   hasOpenRouterKey,
   loadOpenRouterSettings,
+  saveOpenRouterSettings,
 } from "./openrouter";
 import {
   EVALUATION_DELAY_LEVELS,
   TOPIC_SHIFT_SENSITIVITY_LEVELS,
 } from "./browserAutomationOptions";
+import { setGuestModeFlag } from "./guestMode";
+import { audioConstraints, captureClip, deviceIdForName, startTrackCapture } from "./inputTracks";
+import type { TrackCapture } from "./inputTracks";
 import { getPersonaById, NO_PERSONA_ID, PERSONAS } from "./personas";
-import {
-  parseMcpConfig,
-  summarizeMcpServers,
-  type McpServerSummary,
-} from "./mcpConfig";
 import { addUserNote, loadUserNotes, formatUserNotesForPrompt } from "./userNotes";
 import {
   getMockTranscript,
@@ -75,25 +89,6 @@ type SuggestionLogEntry = {
   suggestions: Suggestion[];
 };
 
-type WhisperStatus = {
-  cliFound: boolean;
-  modelFound: boolean;
-  cliPath?: string | null;
-  modelPath?: string | null;
-};
-
-type ExcalidrawStatus = {
-  dirFound: boolean;
-  indexJsFound: boolean;
-  serverJsFound: boolean;
-  installPath?: string | null;
-};
-
-type McpConfigResponse = {
-  path: string;
-  content: string;
-};
-
 type HeyJamieCommand = {
   command: string;
   transcriptLine: string;
@@ -110,6 +105,8 @@ type TranscriptEntry = {
   id: string;
   text: string;
   timestamp: number;
+  /** Input track name in multi-track recordings. */
+  source?: string;
 };
 
 type ChapterSource = "intro" | "topic-shift" | "direct-command" | "url-visit";
@@ -181,7 +178,7 @@ type BrowserOSIntentPlan = {
 };
 
 type BrowserOSTriggerContext = {
-  reason: "direct-command" | "topic-shift";
+  reason: "direct-command" | "topic-shift" | "quick-capture" | "clipboard";
   utterance?: string;
   utteranceIndex?: number;
 };
@@ -395,6 +392,11 @@ const lowSignalWords = new Set([
 
 const heyWakeWordPattern = /\bhey\b/i;
 const heyNameAndCommandPattern = /\bhey\b[\s,.-]*([a-z][a-z'-]{1,20})\b([\s\S]*)$/i;
+// Handled by the backend voice triggers (bookmarks), not the browser agent.
+const backendVoiceCommandPattern = /^(bookmark\b|mark (this|that|it)\b|(jump|go) back\b)/i;
+// App-control phrases from `src-tauri/src/app_control.rs`; keep in step.
+const appControlCommandPattern =
+  /^(open (the )?settings|show (the )?settings|open preferences|show (the )?canvas|open the canvas|show the diagram|open the whiteboard|show yourself|show the app|turn off (the )?persona|clear the persona|no persona|(switch|change) (your |to )?persona|use persona|start (a |the )?(recording )?segment|end (the )?(recording )?segment|we('re| are) (rolling|clear)|unmute|mute|you can talk again|start talking|be quiet|stop talking)\b/i;
 const directCommandImageIntentPattern =
   /\b(image|images|photo|photos|picture|pictures|pic|pics|thumbnail|thumbnails|gallery)\b/i;
 const directCommandSelectionPattern = /\b(pick|choose|select|click|open|tap)\b/i;
@@ -1170,6 +1172,9 @@ function extractHeyJamieCommandFromLine(line: string): string | null {
   if (!command) return null;
   // Ignore punctuation-only remnants (e.g. "Hey, Jamie." → ".")
   if (/^[^a-zA-Z0-9]*$/.test(command)) return null;
+  if (backendVoiceCommandPattern.test(command) || appControlCommandPattern.test(command)) {
+    return null;
+  }
   return command;
 }
 
@@ -1524,6 +1529,7 @@ export function App() {
     "This will clone and build mcp_excalidraw locally."
   );
   const [showExcalidrawSetupCard, setShowExcalidrawSetupCard] = React.useState(false);
+  const [sessionReport, setSessionReport] = React.useState<SessionReport | null>(null);
   const [isListening, setIsListening] = React.useState(false);
   const [speechTag, setSpeechTag] = React.useState("Transcription: unavailable");
   const [speechModelTag, setSpeechModelTag] = React.useState("Model: unknown");
@@ -1550,11 +1556,7 @@ export function App() {
     DEFAULT_EVALUATION_DELAY_MS
   );
   const [quickPersona, setQuickPersona] = React.useState(NO_PERSONA_ID);
-  const [_quickMcpConfigRaw, setQuickMcpConfigRaw] = React.useState("");
-  const [_quickMcpConfig, setQuickMcpConfig] = React.useState<Record<string, unknown> | null>(
-    null
-  );
-  const [quickMcpServers, setQuickMcpServers] = React.useState<McpServerSummary[]>([]);
+  const [quickMcpServers, setQuickMcpServers] = React.useState<McpServer[]>([]);
   const [quickMcpLoading, setQuickMcpLoading] = React.useState(false);
   const [_quickMcpStatus, setQuickMcpStatus] = React.useState("");
   const [quickMcpError, setQuickMcpError] = React.useState("");
@@ -1592,6 +1594,42 @@ export function App() {
   const lastBrowserOSTaskRef = React.useRef("");
   const lastBrowserOSPageUrlRef = React.useRef("");
   const lastHeyJamieCommandKeyRef = React.useRef("");
+  // Set by "Jamie, mute yourself": no volunteered deep dives until unmuted.
+  const agentMutedRef = React.useRef(false);
+  const [agentMuted, setAgentMuted] = React.useState(false);
+  // Tape is rolling: visible actions and direct commands are held for review.
+  const recordingSegmentActiveRef = React.useRef(false);
+  const [recordingSegment, setRecordingSegment] = React.useState<RecordingSegment>({
+    active: false,
+    held: [],
+  });
+  // Longer segments mean fewer whisper runs; widened on battery and under load.
+  const batterySegmentSecondsRef = React.useRef<number | null>(null);
+  const loadSegmentSecondsRef = React.useRef<number | null>(null);
+  const [lowPower, setLowPower] = React.useState(false);
+  const [guestMode, setGuestMode] = React.useState(false);
+  const [scenarios, setScenarios] = React.useState<ScenarioSummary[]>([]);
+  const [activeScenario, setActiveScenario] = React.useState<ScenarioSummary | null>(null);
+  const [prompts, setPrompts] = React.useState<SavedPrompt[]>([]);
+  const [promptResult, setPromptResult] = React.useState<{
+    name: string;
+    text: string;
+  } | null>(null);
+  const [runningPrompt, setRunningPrompt] = React.useState<string | null>(null);
+  const [clipboardSuggestion, setClipboardSuggestion] =
+    React.useState<ClipboardSuggestion | null>(null);
+  const [meetingApp, setMeetingApp] = React.useState<string | null>(null);
+  const [staleBrowsers, setStaleBrowsers] = React.useState<BrowserSession[]>([]);
+  const [idlePausedMinutes, setIdlePausedMinutes] = React.useState<number | null>(null);
+  const [fileBatch, setFileBatch] = React.useState<{ done: number; total: number } | null>(null);
+  const filesDoneRef = React.useRef(0);
+  const meetingAutoStartedRef = React.useRef(false);
+  const pttStartedRef = React.useRef(false);
+  const dictationStartedRef = React.useRef(false);
+  // Shut by the focus gate while an unlisted app is frontmost.
+  const micGatedRef = React.useRef(false);
+  const [loadPressureReason, setLoadPressureReason] = React.useState<string | null>(null);
+  const [micWarning, setMicWarning] = React.useState<"silent" | "clipping" | null>(null);
   const inFlightDirectCommandRef = React.useRef("");
   const pendingBrowserOSReplayRef = React.useRef(false);
   const lastDeepDiveCompletedAtRef = React.useRef(0);
@@ -1610,6 +1648,8 @@ export function App() {
   const browserosRunsFailedRef = React.useRef(0);
   const browserosRunsPendingRef = React.useRef(0);
   const excalidrawCanvasPreloadedRef = React.useRef(false);
+  // Canvas URL for the active board, kept in sync with `canvas-boards` events.
+  const canvasUrlRef = React.useRef("http://localhost:3000");
   const excalidrawRunsStartedRef = React.useRef(0);
   const excalidrawRunsSucceededRef = React.useRef(0);
   const excalidrawRunsFailedRef = React.useRef(0);
@@ -1624,14 +1664,21 @@ export function App() {
   const devPlaybackDelayMsRef = React.useRef(550);
   const audioStreamRef = React.useRef<MediaStream | null>(null);
   const audioContextRef = React.useRef<AudioContext | null>(null);
+  const nativeRecordingRef = React.useRef(false);
   const analyserRef = React.useRef<AnalyserNode | null>(null);
   const processorRef = React.useRef<ScriptProcessorNode | null>(null);
   const silenceNodeRef = React.useRef<GainNode | null>(null);
   const meterFrameRef = React.useRef<number | null>(null);
   const bufferChunksRef = React.useRef<Float32Array[]>([]);
   const bufferLengthRef = React.useRef(0);
-  const pendingQueueRef = React.useRef<Uint8Array[]>([]);
+  const pendingQueueRef = React.useRef<{ wavBytes: Uint8Array; source: string | null }[]>([]);
+  // Multi-track recording: the primary input's track name and the extra inputs.
+  const primaryTrackNameRef = React.useRef<string | null>(null);
+  const extraTracksRef = React.useRef<TrackCapture[]>([]);
   const isTranscribingRef = React.useRef(false);
+  // Stream id of the segment being transcribed; partials for it update the status line.
+  const transcriptionStreamIdRef = React.useRef<string | null>(null);
+  const transcriptionStreamCountRef = React.useRef(0);
   const hadTranscriptionErrorRef = React.useRef(false);
   const skippedQuietSegmentsRef = React.useRef(0);
 
@@ -1706,6 +1753,7 @@ export function App() {
       timestamp: number;
       transcriptIndex: number;
       source: ChapterSource;
+      keywords?: string[];
     }): string | null => {
       const sessionStart = sessionStartMsRef.current ?? params.timestamp;
       if (!sessionStartMsRef.current) {
@@ -1741,6 +1789,13 @@ export function App() {
         chaptersRef.current = next;
         return next;
       });
+      if (added) {
+        void commands
+          .recordTopic(chapterId, params.title, params.keywords ?? [])
+          .catch(() => {
+            // The topic graph is best-effort, like session persistence.
+          });
+      }
       return added ? chapterId : null;
     },
     []
@@ -1766,6 +1821,7 @@ export function App() {
           chaptersRef.current = updated;
           return updated;
         });
+        void commands.recordTopic(chapterId, betterTitle, []).catch(() => {});
       });
     },
     []
@@ -1788,6 +1844,9 @@ export function App() {
     if (sessionStartMsRef.current) return;
     sessionStartMsRef.current = timestamp;
     setSessionStartMs(timestamp);
+    void commands.startSession(null).catch(() => {
+      // Persistence is best-effort; the live session works without it.
+    });
   }, []);
 
   const updateChaptersFromTranscript = React.useCallback(() => {
@@ -1853,6 +1912,7 @@ export function App() {
       timestamp: lastEntry.timestamp,
       transcriptIndex: transcriptEntries.length,
       source: "topic-shift",
+      keywords: [...newAnchorTokens],
     });
     if (topicShiftChapterId) {
       refineChapterTitle(topicShiftChapterId, newTranscriptPayload, title);
@@ -2124,6 +2184,178 @@ export function App() {
     [openSuggestionUrl]
   );
 
+  React.useEffect(() => {
+    const unlistenCreated = events.bookmarkCreated.listen((event) => {
+      log("Bookmark saved.", event.payload);
+    });
+    const unlistenJump = events.bookmarkJump.listen((event) => {
+      const bookmark = event.payload;
+      log("Jumping back to bookmark.", bookmark);
+      if (bookmark.browserUrl) {
+        void openSuggestionUrl(bookmark.browserUrl);
+      }
+    });
+    return () => {
+      void unlistenCreated.then((unlisten) => unlisten());
+      void unlistenJump.then((unlisten) => unlisten());
+    };
+  }, [log, openSuggestionUrl]);
+
+  React.useEffect(() => {
+    const unlisten = events.sessionReport.listen((event) => {
+      log("Session report.", event.payload);
+      setSessionReport(event.payload);
+    });
+    return () => {
+      void unlisten.then((stop) => stop());
+    };
+  }, [log]);
+
+  React.useEffect(() => {
+    const applyRecordingSegment = (status: RecordingSegment) => {
+      recordingSegmentActiveRef.current = status.active;
+      setRecordingSegment(status);
+    };
+    void commands.getRecordingSegment().then(applyRecordingSegment);
+    const unlisten = events.recordingSegment.listen((event) => {
+      log("Recording segment changed.", event.payload);
+      applyRecordingSegment(event.payload);
+    });
+    return () => {
+      void unlisten.then((stop) => stop());
+    };
+  }, [log]);
+
+  React.useEffect(() => {
+    void commands.getGuestMode().then((active) => {
+      // A guest profile never survives a restart; drop its leftovers.
+      setGuestModeFlag(active);
+      setGuestMode(active);
+    });
+    const unlisten = events.guestMode.listen((event) => {
+      log("Guest mode changed.", event.payload);
+      setGuestModeFlag(event.payload.active);
+      // Reload so notes, keys and quick settings come from the new profile.
+      window.location.reload();
+    });
+    return () => {
+      void unlisten.then((stop) => stop());
+    };
+  }, [log]);
+
+  React.useEffect(() => {
+    const unlisten = events.modelIntegrity.listen((event) => {
+      const { fileName, state, detail } = event.payload;
+      log("Whisper model integrity.", event.payload);
+      const live = isListeningRef.current;
+      if (state === "corrupt") {
+        setStatusState("Repairing model", `${fileName} failed its checksum; re-downloading it.`, live);
+      } else if (state === "repaired") {
+        setStatusState(live ? "Listening" : "Not listening", `${fileName} was re-downloaded.`, live);
+      } else {
+        setStatusState("Model damaged", `${fileName}: ${detail}`, live);
+      }
+    });
+    return () => {
+      void unlisten.then((stop) => stop());
+    };
+  }, [log, setStatusState]);
+
+  React.useEffect(() => {
+    const applyPowerState = (state: PowerState) => {
+      batterySegmentSecondsRef.current = state.segmentSeconds;
+      setLowPower(state.lowPower);
+    };
+    void commands.getPowerState().then(applyPowerState);
+    const unlisten = events.powerState.listen((event) => {
+      log("Power state changed.", event.payload);
+      applyPowerState(event.payload);
+    });
+    return () => {
+      void unlisten.then((stop) => stop());
+    };
+  }, [log]);
+
+  React.useEffect(() => {
+    const applyLoadPressure = (pressure: LoadPressure) => {
+      loadSegmentSecondsRef.current = pressure.segmentSeconds;
+      setLoadPressureReason(pressure.pressured ? pressure.reason ?? "system load" : null);
+    };
+    void commands.getLoadPressure().then(applyLoadPressure);
+    const unlisten = events.loadPressure.listen((event) => {
+      log("Load pressure changed.", event.payload);
+      applyLoadPressure(event.payload);
+    });
+    return () => {
+      void unlisten.then((stop) => stop());
+    };
+  }, [log]);
+
+  React.useEffect(() => {
+    const unlisten = events.micLevel.listen((event) => {
+      const { clipping, silent } = event.payload;
+      setMicWarning(silent ? "silent" : clipping ? "clipping" : null);
+    });
+    return () => {
+      void unlisten.then((stop) => stop());
+    };
+  }, []);
+
+  React.useEffect(() => {
+    void commands.listBoards().then(
+      (boards) => {
+        canvasUrlRef.current = boards.url;
+      },
+      () => {}
+    );
+    const unlisten = events.canvasBoards.listen((event) => {
+      log("Canvas board switched.", event.payload.active);
+      canvasUrlRef.current = event.payload.url;
+    });
+    return () => {
+      void unlisten.then((stop) => stop());
+    };
+  }, [log]);
+
+  React.useEffect(() => {
+    const unlisten = events.appControl.listen((event) => {
+      const action = event.payload.action;
+      log("Voice app command.", action);
+      switch (action.kind) {
+        case "showCanvas":
+          void openSuggestionUrl(canvasUrlRef.current);
+          break;
+        case "mute":
+        case "unmute":
+          agentMutedRef.current = action.kind === "mute";
+          setAgentMuted(agentMutedRef.current);
+          break;
+        case "switchPersona": {
+          const normalize = (value: string) => value.toLowerCase().replace(/[^a-z]/g, "");
+          const spoken = normalize(action.persona);
+          const persona = spoken
+            ? PERSONAS.find(
+                (entry) => normalize(entry.name) === spoken || normalize(entry.id) === spoken
+              )
+            : undefined;
+          if (spoken && !persona) {
+            log(`No persona named "${action.persona}".`);
+            break;
+          }
+          const personaId = persona?.id ?? NO_PERSONA_ID;
+          saveOpenRouterSettings({ ...loadOpenRouterSettings(), persona: personaId });
+          setQuickPersona(personaId);
+          break;
+        }
+        default:
+          break;
+      }
+    });
+    return () => {
+      void unlisten.then((stop) => stop());
+    };
+  }, [log, openSuggestionUrl]);
+
   const setSuggestionsLoadingState = React.useCallback(
     (isLoading: boolean, message?: string) => {
       setSuggestionsLoading({
@@ -2233,6 +2465,7 @@ export function App() {
       narrative: string;
       directCommand: string;
       latestSpeech?: string;
+      traceId: string;
     }): Promise<BrowserOSIntentPlan> => {
       const { suggestion, transcriptPayload, narrative } = params;
       const directCommand = params.directCommand.trim();
@@ -2391,6 +2624,7 @@ export function App() {
             instructions: plannerInstructions,
             prompt: JSON.stringify(plannerContext),
             context: plannerContext,
+            traceId: params.traceId,
           },
         });
 
@@ -2600,6 +2834,10 @@ export function App() {
       setSuggestionsLoadingState(true, "Planning browser deep dive...");
       browserosInFlightRef.current = true;
       browserosRunStartedAtRef.current = Date.now();
+      // One trace per follow-up, shared by the planner and every run it
+      // starts, so the whole path can be found in the app and agent logs.
+      const traceId = crypto.randomUUID();
+      logLlm("BrowserOS follow-up started", { traceId, trigger: triggerReason });
 
       browserosRunPromiseRef.current = (async () => {
         let effectiveSuggestion = suggestion;
@@ -2615,6 +2853,7 @@ export function App() {
             narrative,
             directCommand,
             latestSpeech: options?.latestSpeech,
+            traceId,
           });
           effectiveSuggestion = intentPlan.suggestion;
           effectiveNarrative = intentPlan.narrative;
@@ -2653,7 +2892,7 @@ export function App() {
                 // Canvas was pre-opened during initial topic detection — shorter init wait
                 await new Promise((r) => setTimeout(r, 500));
               } else {
-                const canvasUrl = "http://localhost:3000";
+                const canvasUrl = canvasUrlRef.current;
                 await openSuggestionUrl(canvasUrl);
                 // Give the canvas a moment to initialize before automation
                 await new Promise((r) => setTimeout(r, 1500));
@@ -2694,6 +2933,7 @@ export function App() {
                       MAX_BROWSEROS_TRANSCRIPT_CHARS
                     ),
                   },
+                  traceId,
                 },
               });
 
@@ -2905,6 +3145,7 @@ export function App() {
                     MAX_BROWSEROS_TRANSCRIPT_CHARS
                   ),
                 },
+                traceId,
               },
             });
 
@@ -3064,6 +3305,7 @@ export function App() {
           if (isHttpUrl(browserosEndUrl)) {
             lastBrowserOSPageUrlRef.current = browserosEndUrl;
             addUrlToCurrentChapter(browserosEndUrl);
+            void commands.noteSessionBrowserUrl(browserosEndUrl);
           } else if (isHttpUrl(browserosStartUrl)) {
             lastBrowserOSPageUrlRef.current = browserosStartUrl;
             addUrlToCurrentChapter(browserosStartUrl);
@@ -3262,13 +3504,14 @@ export function App() {
     ]
   );
 
-  const addTranscript = React.useCallback((text: string) => {
+  const addTranscript = React.useCallback((text: string, source?: string | null) => {
     const trimmed = text.trim();
     if (!trimmed) return null;
     const entry: TranscriptEntry = {
       id: crypto.randomUUID(),
       text: trimmed,
       timestamp: Date.now(),
+      ...(source ? { source } : {}),
     };
     const transcriptIndex = transcriptsRef.current.length + 1;
     setTranscripts((prev) => {
@@ -3351,6 +3594,102 @@ export function App() {
     []
   );
 
+  // Tray "hold to talk": record one utterance with its own stream (the main
+  // pipeline may be off or busy) and send it straight to the agent as a
+  // direct command, without touching the live transcript.
+  const quickCaptureRef = React.useRef<{
+    stream: MediaStream;
+    context: AudioContext;
+    processor: ScriptProcessorNode;
+    chunks: Float32Array[];
+  } | null>(null);
+
+  React.useEffect(() => {
+    const startCapture = async () => {
+      if (quickCaptureRef.current || !navigator.mediaDevices?.getUserMedia) return;
+      try {
+        const stream = await navigator.mediaDevices.getUserMedia({ audio: true });
+        const context = new AudioContext();
+        const source = context.createMediaStreamSource(stream);
+        const processor = context.createScriptProcessor(4096, 1, 1);
+        const chunks: Float32Array[] = [];
+        processor.onaudioprocess = (event) => {
+          chunks.push(new Float32Array(event.inputBuffer.getChannelData(0)));
+        };
+        const silence = context.createGain();
+        silence.gain.value = 0;
+        source.connect(processor);
+        processor.connect(silence);
+        silence.connect(context.destination);
+        quickCaptureRef.current = { stream, context, processor, chunks };
+        log("Quick capture started.");
+      } catch (error) {
+        log("Quick capture could not open the microphone.", error);
+      }
+    };
+
+    const finishCapture = async () => {
+      const capture = quickCaptureRef.current;
+      if (!capture) return;
+      quickCaptureRef.current = null;
+      capture.processor.disconnect();
+      capture.stream.getTracks().forEach((track) => track.stop());
+      const sampleRate = capture.context.sampleRate;
+      void capture.context.close();
+
+      const length = capture.chunks.reduce((sum, chunk) => sum + chunk.length, 0);
+      if (length < sampleRate * 0.5) {
+        log("Quick capture too short; ignoring.");
+        return;
+      }
+      const merged = new Float32Array(length);
+      let offset = 0;
+      for (const chunk of capture.chunks) {
+        merged.set(chunk, offset);
+        offset += chunk.length;
+      }
+      const wavBytes = encodeWav(
+        downsampleBuffer(merged, sampleRate, OUTPUT_SAMPLE_RATE),
+        OUTPUT_SAMPLE_RATE
+      );
+      try {
+        const response = await commands.transcribeAudio(toBase64(wavBytes), null, null, null, null);
+        const command = (typeof response === "string" ? response : response.text).trim();
+        if (!command) {
+          log("Quick capture heard nothing.");
+          return;
+        }
+        log("Quick capture command.", { command });
+        inFlightDirectCommandRef.current = command;
+        const started = runBrowserOSFollowUp(
+          buildFallbackSuggestionFromDirectCommand(command),
+          command,
+          `Quick capture command: "${command}".`,
+          {
+            directCommand: command,
+            trigger: { reason: "quick-capture", utterance: command },
+          }
+        );
+        if (!started) {
+          inFlightDirectCommandRef.current = "";
+        }
+      } catch (error) {
+        log("Quick capture transcription failed.", error);
+      }
+    };
+
+    const unlisten = events.quickCapture.listen((event) => {
+      if (event.payload.phase === "start") {
+        void startCapture();
+      } else {
+        void finishCapture();
+      }
+    });
+    return () => {
+      void unlisten.then((fn) => fn());
+    };
+  }, [log, runBrowserOSFollowUp]);
+
   const runBrowserOSDeepDive = React.useCallback(async () => {
     const settings = loadOpenRouterSettings();
     if (!hasOpenRouterKey(settings)) {
@@ -3390,6 +3729,16 @@ export function App() {
           }
         }
 
+        if (recordingSegmentActiveRef.current) {
+          logLlm("Recording segment: holding direct command for review.", {
+            command: heyJamieCommand.command,
+          });
+          void commands.holdForReview("command", heyJamieCommand.command).catch(() => {});
+          lastSuggestionTranscriptRef.current = transcriptPayload;
+          lastSuggestionTranscriptLineCountRef.current = transcriptLines.length;
+          return;
+        }
+
         const directSuggestion = buildFallbackSuggestionFromDirectCommand(
           heyJamieCommand.command
         );
@@ -3453,6 +3802,12 @@ export function App() {
       }
     }
 
+    if (agentMutedRef.current || recordingSegmentActiveRef.current) {
+      lastSuggestionTranscriptRef.current = transcriptPayload;
+      lastSuggestionTranscriptLineCountRef.current = transcriptLines.length;
+      return;
+    }
+
     const hasPreviousDeepDive = hasTriggeredDeepDiveRef.current;
     const newTranscriptLines = transcriptTextLines.slice(
       lastSuggestionTranscriptLineCountRef.current
@@ -3505,7 +3860,7 @@ export function App() {
       if (!excalidrawCanvasPreloadedRef.current && hasExcalidrawKeywords(transcriptPayload)) {
         excalidrawCanvasPreloadedRef.current = true;
         logLlm("Pre-opening Excalidraw canvas for detected diagram topic.");
-        void openSuggestionUrl("http://localhost:3000");
+        void openSuggestionUrl(canvasUrlRef.current);
       }
     }
 
@@ -3905,8 +4260,9 @@ export function App() {
   }, [log, setSuggestionsLoadingState]);
 
   const handleTranscriptChunk = React.useCallback(
-    (text: string) => {
-      addTranscript(text);
+    (text: string, source: string | null = null) => {
+      addTranscript(text, source);
+      void commands.recordTranscriptSegment(text, source).catch(() => {});
       const settings = loadOpenRouterSettings();
       if (hasOpenRouterKey(settings)) {
         scheduleBrowserOSDeepDive();
@@ -3920,7 +4276,7 @@ export function App() {
   }, [handleTranscriptChunk]);
 
   const queueSegment = React.useCallback(
-    (buffer: Float32Array, sampleRate: number) => {
+    (buffer: Float32Array, sampleRate: number, source: string | null) => {
       const downsampled = downsampleBuffer(buffer, sampleRate, OUTPUT_SAMPLE_RATE);
       const wavBytes = encodeWav(downsampled, OUTPUT_SAMPLE_RATE);
       log("Queued audio segment.", {
@@ -3928,43 +4284,58 @@ export function App() {
         outputSampleRate: OUTPUT_SAMPLE_RATE,
         samples: downsampled.length,
         wavBytes: wavBytes.length,
+        source,
       });
-      pendingQueueRef.current.push(wavBytes);
+      pendingQueueRef.current.push({ wavBytes, source });
       void drainQueue();
     },
     [log]
   );
 
+  const queueAudibleSegment = React.useCallback(
+    (merged: Float32Array, sampleRate: number, source: string | null) => {
+      const rms = calculateRms(merged);
+      log("Segment RMS", rms.toFixed(4));
+
+      if (rms < MIN_SEGMENT_RMS_FOR_TRANSCRIPTION) {
+        skippedQuietSegmentsRef.current += 1;
+        if (
+          skippedQuietSegmentsRef.current <= 3 ||
+          skippedQuietSegmentsRef.current % 10 === 0
+        ) {
+          log("Skipping low-energy segment before transcription.", {
+            rms: Number(rms.toFixed(4)),
+            threshold: MIN_SEGMENT_RMS_FOR_TRANSCRIPTION,
+            skippedSegments: skippedQuietSegmentsRef.current,
+          });
+        }
+        return;
+      }
+
+      skippedQuietSegmentsRef.current = 0;
+
+      queueSegment(merged, sampleRate, source);
+    },
+    [log, queueSegment]
+  );
+
   const flushBuffer = React.useCallback(() => {
     if (!audioContextRef.current) return;
     const minSamples = audioContextRef.current.sampleRate * MIN_SEGMENT_SECONDS;
     if (bufferLengthRef.current < minSamples) return;
 
     const merged = mergeBuffers(bufferChunksRef.current, bufferLengthRef.current);
-    const rms = calculateRms(merged);
-    log("Segment RMS", rms.toFixed(4));
     bufferChunksRef.current = [];
     bufferLengthRef.current = 0;
+    queueAudibleSegment(merged, audioContextRef.current.sampleRate, primaryTrackNameRef.current);
+  }, [queueAudibleSegment]);
 
-    if (rms < MIN_SEGMENT_RMS_FOR_TRANSCRIPTION) {
-      skippedQuietSegmentsRef.current += 1;
-      if (
-        skippedQuietSegmentsRef.current <= 3 ||
-        skippedQuietSegmentsRef.current % 10 === 0
-      ) {
-        log("Skipping low-energy segment before transcription.", {
-          rms: Number(rms.toFixed(4)),
-          threshold: MIN_SEGMENT_RMS_FOR_TRANSCRIPTION,
-          skippedSegments: skippedQuietSegmentsRef.current,
-        });
-      }
-      return;
-    }
-
-    skippedQuietSegmentsRef.current = 0;
-
-    queueSegment(merged, audioContextRef.current.sampleRate);
-  }, [log, queueSegment]);
+  const currentSegmentSeconds = React.useCallback(() => {
+    const overrides = [batterySegmentSecondsRef.current, loadSegmentSecondsRef.current].filter(
+      (seconds): seconds is number => seconds !== null
+    );
+    return overrides.length ? Math.max(...overrides) : SEGMENT_SECONDS;
+  }, []);
 
   const startAudioPipeline = React.useCallback(() => {
     if (!audioStreamRef.current) return;
@@ -3982,11 +4353,14 @@ export function App() {
     processorRef.current.onaudioprocess = (event) => {
       if (!isListeningRef.current) return;
       const input = event.inputBuffer.getChannelData(0);
-      bufferChunksRef.current.push(new Float32Array(input));
+      // Gated audio is kept as silence so segment timing stays the same.
+      bufferChunksRef.current.push(
+        micGatedRef.current ? new Float32Array(input.length) : new Float32Array(input)
+      );
       bufferLengthRef.current += input.length;
 
       const targetSamples = audioContextRef.current?.sampleRate
-        ? audioContextRef.current.sampleRate * SEGMENT_SECONDS
+        ? audioContextRef.current.sampleRate * currentSegmentSeconds()
         : 0;
 
       if (targetSamples && bufferLengthRef.current >= targetSamples) {
@@ -4004,7 +4378,7 @@ export function App() {
     source.connect(processorRef.current);
     processorRef.current.connect(silenceNodeRef.current);
     silenceNodeRef.current.connect(audioContextRef.current.destination);
-  }, [flushBuffer, log]);
+  }, [currentSegmentSeconds, flushBuffer, log]);
 
   const startAudioMeter = React.useCallback(() => {
     if (!audioStreamRef.current || !audioContextRef.current || !analyserRef.current) {
@@ -4029,11 +4403,62 @@ export function App() {
     tick();
   }, []);
 
+  React.useEffect(() => {
+    let partialText = "";
+    let partialStreamId: string | null = null;
+    const unlisten = events.transcriptionPartial.listen((event) => {
+      const { streamId, text } = event.payload;
+      const nativeSegment = nativeRecordingRef.current && streamId.startsWith("recording-");
+      if (
+        (streamId !== transcriptionStreamIdRef.current && !nativeSegment) ||
+        !isListeningRef.current
+      ) {
+        return;
+      }
+      if (streamId !== partialStreamId) {
+        partialStreamId = streamId;
+        partialText = "";
+      }
+      partialText = `${partialText} ${text}`.trim();
+      setStatusState("Listening", `Transcribing: ${partialText}`, true);
+    });
+    return () => {
+      void unlisten.then((stop) => stop());
+    };
+  }, [setStatusState]);
+
+  React.useEffect(() => {
+    const unlistenTranscript = events.recordedTranscript.listen((event) => {
+      const { text, error } = event.payload;
+      if (error) {
+        log("Transcription failed.", error);
+        setStatusState("Listening (audio only)", `Transcription failed: ${error}`, true);
+        return;
+      }
+      log("Transcription response.", { text, index: event.payload.index });
+      if (text.trim()) {
+        handleTranscriptChunkRef.current(text);
+      }
+      if (isListeningRef.current) {
+        setStatusState("Listening", "Mic is live. Transcribing with whisper.cpp.", true);
+      }
+    });
+    const unlistenLevel = events.recordingLevel.listen((event) => {
+      if (!meterRef.current) return;
+      const level = event.payload ? Math.min(100, Math.max(5, event.payload * 220)) : 0;
+      meterRef.current.style.setProperty("--meter-level", `${level}%`);
+    });
+    return () => {
+      void unlistenTranscript.then((stop) => stop());
+      void unlistenLevel.then((stop) => stop());
+    };
+  }, [log, setStatusState]);
+
   const drainQueue = React.useCallback(async () => {
     if (isTranscribingRef.current || pendingQueueRef.current.length === 0) return;
     isTranscribingRef.current = true;
-    const wavBytes = pendingQueueRef.current.shift();
-    if (!wavBytes) {
+    const segment = pendingQueueRef.current.shift();
+    if (!segment) {
       isTranscribingRef.current = false;
       return;
     }
@@ -4043,14 +4468,25 @@ export function App() {
     }
 
     try {
-      const audioBase64 = toBase64(wavBytes);
-      log("Calling transcribe_audio.", {
-        payloadBytes: wavBytes.length,
+      const audioBase64 = toBase64(segment.wavBytes);
+      transcriptionStreamCountRef.current += 1;
+      const streamId = `segment-${transcriptionStreamCountRef.current}`;
+      transcriptionStreamIdRef.current = streamId;
+      log("Calling transcribe_audio_stream.", {
+        payloadBytes: segment.wavBytes.length,
+        source: segment.source,
+        streamId,
       });
-      const text = await invoke<string>("transcribe_audio", { audioBase64 });
+      const text = await commands.transcribeAudioStream(
+        audioBase64,
+        streamId,
+        null,
+        null,
+        segment.source
+      );
       log("Transcription response.", { text });
       if (text && text.trim()) {
-        handleTranscriptChunk(text);
+        handleTranscriptChunk(text, segment.source);
       }
       hadTranscriptionErrorRef.current = false;
     } catch (error) {
@@ -4063,6 +4499,7 @@ export function App() {
       );
     } finally {
       isTranscribingRef.current = false;
+      transcriptionStreamIdRef.current = null;
       if (isListeningRef.current && !hadTranscriptionErrorRef.current) {
         setStatusState("Listening", "Mic is live. Transcribing with whisper.cpp.", true);
       }
@@ -4076,8 +4513,19 @@ export function App() {
     isListeningRef.current = false;
     setStatusState("Not listening", "Session paused.", false);
     log("Stopping microphone stream.");
+    void commands.setIdleWatch(false);
 
+    if (nativeRecordingRef.current) {
+      nativeRecordingRef.current = false;
+      await commands.stopRecording().catch((error) => log("Failed to stop recording.", error));
+    }
     flushBuffer();
+    extraTracksRef.current.forEach((track) => {
+      track.flush();
+      track.stop();
+    });
+    extraTracksRef.current = [];
+    primaryTrackNameRef.current = null;
     void cancelLlmSuggestions();
 
     if (processorRef.current) {
@@ -4112,6 +4560,22 @@ export function App() {
     if (meterRef.current) meterRef.current.style.setProperty("--meter-level", "0%");
   }, [cancelLlmSuggestions, flushBuffer, log, setStatusState]);
 
+  // When the app quits, hand the last audio to whisper and wait for the
+  // queue so its transcript is saved before the session is closed.
+  React.useEffect(() => {
+    const unlisten = events.appShutdown.listen(async () => {
+      log("App is quitting; flushing pending transcripts.");
+      await stopListening();
+      while (isTranscribingRef.current || pendingQueueRef.current.length > 0) {
+        await new Promise((resolve) => window.setTimeout(resolve, 100));
+      }
+      await commands.confirmShutdownFlushed();
+    });
+    return () => {
+      void unlisten.then((stop) => stop());
+    };
+  }, [log, stopListening]);
+
   const startListening = React.useCallback(async () => {
     if (!whisperReady) {
       setStatusState(
@@ -4121,6 +4585,49 @@ export function App() {
       );
       return;
     }
+    if (isListeningRef.current) return;
+    const preflight = await commands.preflightCheck("recording").catch(() => null);
+    if (preflight && !preflight.ok) {
+      log("Recording preflight failed.", preflight);
+      setStatusState(
+        "Low on resources",
+        preflight.suggestions[0] ?? "Not enough disk space or memory to start recording.",
+        false
+      );
+      return;
+    }
+    const settings = await commands.getAppSettings().catch(() => null);
+    const tracks = settings?.transcription.inputTracks ?? [];
+    const multiTrack = tracks.length >= 2;
+    const anchorLtc = () => {
+      const ltcDeviceId = settings?.timecode.ltcDeviceId;
+      if (!ltcDeviceId) return;
+      void captureClip(ltcDeviceId, 2)
+        .then(({ samples, sampleRate }) =>
+          commands.anchorSessionLtc(toBase64(encodeWav(samples, sampleRate)))
+        )
+        .then((timecode) => log("Session anchored to LTC.", timecode))
+        .catch((error) => log("Failed to read LTC.", error));
+    };
+    const captureSource = settings?.transcription.captureSource ?? "microphone";
+    if ((!multiTrack && !settings?.transcription.webviewCapture) || captureSource !== "microphone") {
+      try {
+        const recording = await commands.startRecording();
+        nativeRecordingRef.current = true;
+        log("Native recording started.", recording);
+      } catch (error) {
+        setStatusState("Microphone blocked", String(error), false);
+        return;
+      }
+      ensureSessionStart(Date.now());
+      setIsListening(true);
+      isListeningRef.current = true;
+      setIdlePausedMinutes(null);
+      void commands.setIdleWatch(true);
+      setStatusState("Listening", "Mic is live. Transcribing with whisper.cpp.", true);
+      anchorLtc();
+      return;
+    }
     if (!navigator.mediaDevices?.getUserMedia) {
       setStatusState(
         "Microphone unavailable",
@@ -4129,10 +4636,16 @@ export function App() {
       );
       return;
     }
-    if (isListeningRef.current) return;
     try {
-      audioStreamRef.current = await navigator.mediaDevices.getUserMedia({ audio: true });
-      log("Microphone stream started.");
+      audioStreamRef.current = await navigator.mediaDevices.getUserMedia(
+        audioConstraints(
+          multiTrack
+            ? tracks[0].deviceId
+            : await deviceIdForName(settings?.transcription.inputDevice ?? "")
+        )
+      );
+      primaryTrackNameRef.current = multiTrack ? tracks[0].name || "Track 1" : null;
+      log("Microphone stream started.", { track: primaryTrackNameRef.current });
     } catch {
       setStatusState(
         "Microphone blocked",
@@ -4145,12 +4658,45 @@ export function App() {
     ensureSessionStart(Date.now());
     setIsListening(true);
     isListeningRef.current = true;
+    setIdlePausedMinutes(null);
+    void commands.setIdleWatch(true);
     setStatusState("Listening", "Mic is live. Transcribing with whisper.cpp.", true);
     startAudioPipeline();
     startAudioMeter();
+    if (multiTrack) {
+      const extras = await Promise.allSettled(
+        tracks.slice(1).map((track, index) =>
+          startTrackCapture({
+            name: track.name || `Track ${index + 2}`,
+            deviceId: track.deviceId,
+            segmentSeconds: currentSegmentSeconds,
+            minSegmentSeconds: MIN_SEGMENT_SECONDS,
+            gated: () => micGatedRef.current,
+            onSegment: (samples, sampleRate, name) => {
+              if (isListeningRef.current) queueAudibleSegment(samples, sampleRate, name);
+            },
+          })
+        )
+      );
+      extras.forEach((result, index) => {
+        if (result.status === "fulfilled") {
+          extraTracksRef.current.push(result.value);
+          log("Input track started.", { track: result.value.name });
+        } else {
+          log("Failed to start input track.", { track: tracks[index + 1].name, error: result.reason });
+        }
+      });
+      if (!isListeningRef.current) {
+        extraTracksRef.current.forEach((track) => track.stop());
+        extraTracksRef.current = [];
+      }
+    }
+    anchorLtc();
   }, [
+    currentSegmentSeconds,
     ensureSessionStart,
     log,
+    queueAudibleSegment,
     setStatusState,
     startAudioMeter,
     startAudioPipeline,
@@ -4170,6 +4716,7 @@ export function App() {
     chaptersRef.current = [];
     sessionStartMsRef.current = null;
     setSessionStartMs(null);
+    void commands.endSession().catch(() => {});
     pendingQueueRef.current = [];
     bufferChunksRef.current = [];
     bufferLengthRef.current = 0;
@@ -4204,6 +4751,397 @@ export function App() {
     stopMockPlayback,
   ]);
 
+  const endGuestMode = React.useCallback(async () => {
+    await stopListening();
+    try {
+      await commands.endSession();
+      await commands.endGuestMode();
+    } catch (error) {
+      log("Failed to end guest mode.", error);
+      setStatusState("Guest mode", `Failed to end guest mode: ${String(error)}`, false);
+    }
+  }, [log, setStatusState, stopListening]);
+
+  React.useEffect(() => {
+    void commands.listScenarios().then(setScenarios);
+    const unlisten = events.scenarioProgress.listen((event) => {
+      const { action, done, error, index, total } = event.payload;
+      if (done) {
+        setActiveScenario(null);
+        setStatusState(
+          "Not listening",
+          error ? `Demo ended: ${error}` : "Demo finished. Start listening to try it yourself.",
+          false
+        );
+        return;
+      }
+      setStatusState("Demo", `Playing step ${index + 1} of ${total}.`, true);
+      if (action?.kind === "transcript") {
+        // The real transcript pipeline, minus the live agent run.
+        addTranscript(action.text, action.source);
+        void commands.recordTranscriptSegment(action.text, action.source).catch(() => {});
+      } else if (action?.kind === "agent") {
+        setSuggestionsNarrative(action.narrative);
+        addSuggestionLogEntry({
+          narrative: action.narrative,
+          toolCalls: [],
+          suggestions: action.url
+            ? [{ id: crypto.randomUUID(), type: "Search", query: action.url, note: "", url: action.url }]
+            : [],
+        });
+      }
+    });
+    return () => {
+      void unlisten.then((stop) => stop());
+    };
+  }, [addSuggestionLogEntry, addTranscript, setStatusState]);
+
+  const startScenario = React.useCallback(
+    async (name: string) => {
+      await stopListening();
+      clearSession();
+      try {
+        setActiveScenario(await commands.runScenario(name));
+        setStatusState("Demo", "Starting demo...", true);
+      } catch (error) {
+        log("Failed to start scenario.", error);
+        setStatusState("Not listening", `Failed to start demo: ${String(error)}`, false);
+      }
+    },
+    [clearSession, log, setStatusState, stopListening]
+  );
+
+  React.useEffect(() => {
+    void commands.listPrompts().then(setPrompts);
+  }, []);
+
+  const runPromptOnSelection = React.useCallback(
+    async (name: string, content?: string) => {
+      const settings = loadOpenRouterSettings();
+      if (!hasOpenRouterKey(settings)) {
+        setPromptResult({ name, text: "Add an OpenRouter API key in Settings first." });
+        return;
+      }
+      // The transcript selection, else whatever was last copied.
+      let text = content?.trim() || (window.getSelection()?.toString().trim() ?? "");
+      if (!text) {
+        text = (await navigator.clipboard.readText().catch(() => "")).trim();
+      }
+      if (!text) {
+        setPromptResult({ name, text: "Select some transcript text or copy text first." });
+        return;
+      }
+      setRunningPrompt(name);
+      try {
+        const result = await commands.runPromptOnText(name, text, {
+          apiKey: settings.apiKey,
+          model: settings.model,
+          reasoning: settings.reasoning,
+        });
+        setPromptResult({ name, text: result });
+      } catch (error) {
+        log("Prompt failed.", error);
+        setPromptResult({ name, text: `Failed: ${String(error)}` });
+      } finally {
+        setRunningPrompt(null);
+      }
+    },
+    [log]
+  );
+
+  React.useEffect(() => {
+    const unlisten = events.clipboardSuggestion.listen((event) => {
+      setClipboardSuggestion(event.payload);
+    });
+    return () => {
+      void unlisten.then((stop) => stop());
+    };
+  }, []);
+
+  React.useEffect(() => {
+    const unlisten = events.staleBrowserSessions.listen((event) => {
+      setStaleBrowsers(event.payload.sessions);
+    });
+    return () => {
+      void unlisten.then((stop) => stop());
+    };
+  }, []);
+
+  const cleanupStaleBrowsers = React.useCallback(
+    async (action: BrowserCleanupAction) => {
+      setStaleBrowsers([]);
+      try {
+        const report = await commands.cleanupBrowserSessions(action);
+        log("Cleaned up automation browsers.", report);
+      } catch (error) {
+        log("Failed to clean up automation browsers.", error);
+      }
+    },
+    [log]
+  );
+
+  const switchPersona = React.useCallback((personaId: string) => {
+    saveOpenRouterSettings({ ...loadOpenRouterSettings(), persona: personaId });
+    setQuickPersona(personaId);
+  }, []);
+
+  React.useEffect(() => {
+    void commands.getMeeting().then(setMeetingApp);
+    const unlisten = events.meetingDetected.listen((event) => {
+      const { app, autoStart, personaId } = event.payload;
+      setMeetingApp(app);
+      if (app && autoStart && !isListeningRef.current) {
+        if (getPersonaById(personaId)) {
+          switchPersona(personaId);
+        }
+        meetingAutoStartedRef.current = true;
+        setStatusState("Meeting", `Started listening for ${app}.`, true);
+        void startListening();
+      } else if (!app && meetingAutoStartedRef.current) {
+        // Only stop what the meeting started.
+        meetingAutoStartedRef.current = false;
+        void stopListening();
+      }
+    });
+    return () => {
+      void unlisten.then((stop) => stop());
+    };
+  }, [setStatusState, startListening, stopListening, switchPersona]);
+
+  React.useEffect(() => {
+    // Like a meeting auto-start, releasing the key only stops what it started.
+    const unlistenDown = events.pttDown.listen((event) => {
+      if (isListeningRef.current) {
+        // Already listening in the webview; don't record the mic twice.
+        if (event.payload.recording && !nativeRecordingRef.current) {
+          void commands.stopRecording();
+        }
+        return;
+      }
+      pttStartedRef.current = true;
+      if (event.payload.recording) {
+        nativeRecordingRef.current = true;
+        ensureSessionStart(Date.now());
+        setIsListening(true);
+        isListeningRef.current = true;
+        setStatusState("Listening", "Push-to-talk held. Transcribing with whisper.cpp.", true);
+        return;
+      }
+      void startListening();
+    });
+    const unlistenUp = events.pttUp.listen(() => {
+      if (!pttStartedRef.current) return;
+      pttStartedRef.current = false;
+      void stopListening();
+    });
+    return () => {
+      void unlistenDown.then((stop) => stop());
+      void unlistenUp.then((stop) => stop());
+    };
+  }, [ensureSessionStart, setStatusState, startListening, stopListening]);
+
+  React.useEffect(() => {
+    // Dictation needs the mic on; turning it off only stops what it started.
+    const unlisten = events.dictation.listen((event) => {
+      if (event.payload.active) {
+        const showDictating = () =>
+          setStatusState("Dictating", "Transcribed lines are typed into the app in front.", true);
+        if (isListeningRef.current) {
+          showDictating();
+          return;
+        }
+        dictationStartedRef.current = true;
+        void startListening().then(() => {
+          if (isListeningRef.current) showDictating();
+        });
+        return;
+      }
+      if (dictationStartedRef.current) {
+        dictationStartedRef.current = false;
+        void stopListening();
+      } else if (isListeningRef.current) {
+        setStatusState("Listening", "Dictation off. Transcribing with whisper.cpp.", true);
+      }
+    });
+    return () => {
+      void unlisten.then((stop) => stop());
+    };
+  }, [setStatusState, startListening, stopListening]);
+
+  React.useEffect(() => {
+    const unlisten = events.webhookTriggered.listen((event) => {
+      const { trigger, action, prompt, text } = event.payload;
+      log(`Webhook trigger ${trigger}.`, event.payload);
+      if (action === "start-session") {
+        if (!isListeningRef.current) void startListening();
+        return;
+      }
+      if (action === "run-prompt" && prompt) {
+        // Without posted text, run the prompt on the transcript so far.
+        const content = text ?? getTranscriptTexts(transcriptsRef.current).join("\n");
+        void runPromptOnSelection(prompt, content);
+      }
+    });
+    return () => {
+      void unlisten.then((stop) => stop());
+    };
+  }, [log, runPromptOnSelection, startListening]);
+
+  React.useEffect(() => {
+    const unlisten = events.deviceChanged.listen((event) => {
+      const { defaultDevice, selected } = event.payload;
+      log("Input devices changed.", event.payload);
+      if (!isListeningRef.current) return;
+      // The running capture keeps its device; the change applies on restart.
+      setStatusState(
+        "Listening",
+        `Input devices changed (default: ${defaultDevice ?? "none"}${
+          selected ? `, using ${selected}` : ""
+        }). Restart listening to switch.`,
+        true
+      );
+    });
+    return () => {
+      void unlisten.then((stop) => stop());
+    };
+  }, [log, setStatusState]);
+
+  React.useEffect(() => {
+    void commands.getMicGate().then((gate) => {
+      micGatedRef.current = !gate.open;
+    });
+    const unlisten = events.micGate.listen((event) => {
+      const { open, frontmost } = event.payload;
+      micGatedRef.current = !open;
+      log(open ? "Microphone gate open." : `Microphone gated: ${frontmost ?? "another app"} is frontmost.`);
+    });
+    return () => {
+      void unlisten.then((stop) => stop());
+    };
+  }, [log]);
+
+  React.useEffect(() => {
+    const unlisten = events.idlePause.listen((event) => {
+      if (!isListeningRef.current) return;
+      const { idleMinutes } = event.payload;
+      log(`No speech or input for ${idleMinutes} minutes; pausing.`);
+      void stopListening().then(() => {
+        setIdlePausedMinutes(idleMinutes);
+        setStatusState("Paused", `Nothing was said for ${idleMinutes} minutes.`, false);
+      });
+    });
+    // Key presses and clicks keep a quiet session going; a few per minute is plenty.
+    let lastNoted = 0;
+    const noteActivity = () => {
+      if (!isListeningRef.current || Date.now() - lastNoted < 15_000) return;
+      lastNoted = Date.now();
+      void commands.noteUserActivity();
+    };
+    window.addEventListener("keydown", noteActivity);
+    window.addEventListener("pointerdown", noteActivity);
+    return () => {
+      void unlisten.then((stop) => stop());
+      window.removeEventListener("keydown", noteActivity);
+      window.removeEventListener("pointerdown", noteActivity);
+    };
+  }, [log, setStatusState, stopListening]);
+
+  React.useEffect(() => {
+    // The app has been idle a while; research the next agenda item.
+    const unlisten = events.agendaDue.listen((event) => {
+      const settings = loadOpenRouterSettings();
+      if (!hasOpenRouterKey(settings)) return;
+      const { id, task } = event.payload;
+      log(`Researching agenda item: ${task}`);
+      void commands
+        .runAgendaItem(id, {
+          apiKey: settings.apiKey,
+          model: settings.model,
+          reasoning: settings.reasoning,
+        })
+        .then((item) => log(`Agenda item ${item.status}: ${task}`, item.lastError ?? undefined))
+        .catch((error) => log(`Agenda item failed: ${String(error)}`));
+    });
+    return () => {
+      void unlisten.then((stop) => stop());
+    };
+  }, [log]);
+
+  React.useEffect(() => {
+    // Dropped audio files and folders are transcribed to text files.
+    const unlistenDrop = getCurrentWebview().onDragDropEvent((event) => {
+      if (event.payload.type !== "drop" || event.payload.paths.length === 0) return;
+      void commands
+        .transcribeFiles(event.payload.paths, null)
+        .then((total) => {
+          filesDoneRef.current = 0;
+          setFileBatch({ done: 0, total });
+          setStatusState("Transcribing files", `Queued ${total} file(s).`, true);
+        })
+        .catch((error) => {
+          setStatusState("Not listening", `Cannot transcribe dropped files: ${String(error)}`, false);
+        });
+    });
+    const unlistenProgress = events.fileTranscription.listen((event) => {
+      const { error, index, path, status, total, transcriptPath } = event.payload;
+      const name = path.split(/[\\/]/).pop() ?? path;
+      if (status === "running") {
+        setStatusState("Transcribing files", `${index + 1} of ${total}: ${name}`, true);
+        return;
+      }
+      if (status === "failed") {
+        log("File transcription failed.", { path, error });
+      } else if (status === "done") {
+        log("File transcribed.", { path, transcriptPath });
+      }
+      filesDoneRef.current += 1;
+      if (filesDoneRef.current < total) {
+        setFileBatch({ done: filesDoneRef.current, total });
+        return;
+      }
+      setFileBatch(null);
+      setStatusState("Not listening", `Transcribed ${total} file(s); transcripts are next to the audio.`, false);
+    });
+    return () => {
+      void unlistenDrop.then((stop) => stop());
+      void unlistenProgress.then((stop) => stop());
+    };
+  }, [log, setStatusState]);
+
+  const runClipboardAction = React.useCallback(
+    (suggestion: ClipboardSuggestion, action: ClipboardAction) => {
+      setClipboardSuggestion(null);
+      const { content, kind } = suggestion;
+      if (action === "open") {
+        void openSuggestionUrl(content);
+        return;
+      }
+      if (action === "summarize" && kind === "text") {
+        void runPromptOnSelection("summarize", content);
+        return;
+      }
+      // Articles and drawings go through the agent like a spoken command.
+      const command =
+        action === "summarize"
+          ? `Open ${content} and summarize the article`
+          : `Draw a diagram on the canvas of: ${content}`;
+      inFlightDirectCommandRef.current = command;
+      const started = runBrowserOSFollowUp(
+        buildFallbackSuggestionFromDirectCommand(command),
+        command,
+        `Clipboard command: "${command}".`,
+        {
+          directCommand: command,
+          trigger: { reason: "clipboard", utterance: command },
+        }
+      );
+      if (!started) {
+        inFlightDirectCommandRef.current = "";
+      }
+    },
+    [openSuggestionUrl, runBrowserOSFollowUp, runPromptOnSelection]
+  );
+
   const restartDevPlayback = React.useCallback(() => {
     stopDevPlayback();
     clearSession();
@@ -4237,7 +5175,7 @@ export function App() {
     }
 
     try {
-      const statusResponse = await invoke<WhisperStatus>("check_whisper");
+      const statusResponse = await commands.checkWhisper();
       const ready = Boolean(statusResponse.cliFound && statusResponse.modelFound);
       setWhisperReady(ready);
       updateSpeechTag(ready);
@@ -4293,7 +5231,7 @@ export function App() {
 
   const refreshExcalidrawStatus = React.useCallback(async () => {
     try {
-      const statusResponse = await invoke<ExcalidrawStatus>("check_excalidraw");
+      const statusResponse = await commands.checkExcalidraw();
       const ready = Boolean(
         statusResponse.dirFound &&
           statusResponse.indexJsFound &&
@@ -4351,43 +5289,22 @@ export function App() {
     setQuickPersona(settings.persona || NO_PERSONA_ID);
   }, []);
 
-  const applyQuickMcpConfigContent = React.useCallback((content: string) => {
-    setQuickMcpConfigRaw(content);
-    const parsed = parseMcpConfig(content);
-    if (!parsed.config || parsed.error) {
-      setQuickMcpConfig(null);
-      setQuickMcpServers([]);
-      setQuickMcpError(`Invalid MCP config: ${parsed.error ?? "Unknown error"}`);
-      return false;
-    }
-    setQuickMcpConfig(parsed.config);
-    setQuickMcpServers(summarizeMcpServers(parsed.config));
-    setQuickMcpError("");
-    return true;
-  }, []);
-
   const loadQuickMcpConfig = React.useCallback(async () => {
     setQuickMcpLoading(true);
     setQuickMcpStatus("Loading MCP servers...");
     setQuickMcpError("");
     try {
-      const response = await invoke<McpConfigResponse>("get_mcp_config");
-      const loaded = applyQuickMcpConfigContent(response.content || "");
-      setQuickMcpStatus(
-        loaded
-          ? `Loaded MCP config from ${response.path}.`
-          : "Loaded MCP config, but it contains invalid JSON."
-      );
+      const listing = await commands.listMcpServers();
+      setQuickMcpServers(listing.servers);
+      setQuickMcpStatus(`Loaded MCP config from ${listing.path}.`);
     } catch (error) {
       setQuickMcpStatus("Failed to load MCP config.");
       setQuickMcpError(String(error));
-      setQuickMcpConfig(null);
       setQuickMcpServers([]);
-      setQuickMcpConfigRaw("");
     } finally {
       setQuickMcpLoading(false);
     }
-  }, [applyQuickMcpConfigContent]);
+  }, []);
 
   React.useEffect(() => {
     updateLlmTag();
@@ -4631,7 +5548,9 @@ export function App() {
     void startListening();
   }, [isListening, startListening, stopListening]);
 
-  const transcriptLines = transcripts.slice(-12).map((entry) => entry.text);
+  const transcriptLines = transcripts
+    .slice(-12)
+    .map((entry) => (entry.source ? `${entry.source}: ${entry.text}` : entry.text));
 
   React.useEffect(() => {
     const el = transcriptScrollRef.current;
@@ -4762,6 +5681,105 @@ export function App() {
             <Badge variant="outline" className="text-[11px]">
               {personaBadgeLabel}
             </Badge>
+            {agentMuted && (
+              <Badge variant="outline" className="text-[11px]">
+                Muted
+              </Badge>
+            )}
+            {recordingSegment.active && (
+              <Badge
+                variant="default"
+                className="text-[11px]"
+                title="Recording segment: Jamie holds visible actions until it ends"
+              >
+                On Air
+              </Badge>
+            )}
+            {guestMode && (
+              <>
+                <Badge
+                  variant="default"
+                  className="text-[11px]"
+                  title="Guest mode: sessions, notes and keys from this profile are erased when it ends"
+                >
+                  Guest
+                </Badge>
+                <Button size="sm" variant="outline" className="h-6 text-[11px]" onClick={() => void endGuestMode()}>
+                  End guest mode
+                </Button>
+              </>
+            )}
+            {fileBatch && (
+              <>
+                <Badge variant="outline" className="text-[11px]">
+                  Files: {fileBatch.done}/{fileBatch.total}
+                </Badge>
+                <Button
+                  size="sm"
+                  variant="outline"
+                  className="h-6 text-[11px]"
+                  onClick={() => void commands.cancelFileTranscription()}
+                >
+                  Stop after current
+                </Button>
+              </>
+            )}
+            {meetingApp && (
+              <>
+                <Badge variant="outline" className="text-[11px]">
+                  Meeting: {meetingApp}
+                </Badge>
+                {!isListening && (
+                  <Button
+                    size="sm"
+                    variant="outline"
+                    className="h-6 text-[11px]"
+                    onClick={() => void startListening()}
+                  >
+                    Start Jamie
+                  </Button>
+                )}
+              </>
+            )}
+            {activeScenario && (
+              <>
+                <Badge variant="default" className="text-[11px]" title={activeScenario.description}>
+                  Demo: {activeScenario.title}
+                </Badge>
+                <Button
+                  size="sm"
+                  variant="outline"
+                  className="h-6 text-[11px]"
+                  onClick={() => void commands.cancelScenario()}
+                >
+                  Stop demo
+                </Button>
+              </>
+            )}
+            {lowPower && (
+              <Badge variant="outline" className="text-[11px]" title="Running on battery">
+                Battery saver
+              </Badge>
+            )}
+            {loadPressureReason && (
+              <Badge variant="outline" className="text-[11px]" title={loadPressureReason}>
+                Reduced quality due to system load
+              </Badge>
+            )}
+            {micWarning === "silent" && (
+              <Badge
+                variant="outline"
+                className="text-[11px]"
+                title="Nothing from the microphone for a few seconds. Check the input in Settings → Microphone."
+              >
+                No mic signal
+              </Badge>
+            )}
+            {micWarning === "clipping" && (
+              <Badge variant="outline" className="text-[11px]" title="Lower the microphone's input gain.">
+                Mic clipping
+              </Badge>
+            )}
             <Badge variant="outline" className="max-w-[30rem] text-[11px]" title={mcpServersBadgeLabel}>
               {mcpServersBadgeLabel}
             </Badge>
@@ -4808,6 +5826,135 @@ export function App() {
           </Card>
         )}
 
+        {!recordingSegment.active && recordingSegment.held.length > 0 && (
+          <Card className="flex-none border-primary/40 bg-muted/30">
+            <CardHeader className="flex flex-row items-center justify-between">
+              <div>
+                <CardTitle className="text-base">Held During Recording</CardTitle>
+                <CardDescription>
+                  {recordingSegment.held.length} responses waited for the segment to end
+                </CardDescription>
+              </div>
+              <Button
+                variant="ghost"
+                size="sm"
+                onClick={() => void commands.takeHeldResponses().catch(() => {})}
+              >
+                Dismiss
+              </Button>
+            </CardHeader>
+            <CardContent className="space-y-1 text-xs text-muted-foreground">
+              {recordingSegment.held.map((item) => (
+                <div key={`${item.heldAt}-${item.detail}`} className="flex items-center gap-2">
+                  <Badge variant="outline" className="text-[10px]">
+                    {item.kind}
+                  </Badge>
+                  <span className="min-w-0 flex-1 truncate" title={item.detail}>
+                    {item.detail}
+                  </span>
+                  {item.kind === "url" && (
+                    <Button variant="outline" size="sm" onClick={() => void openSuggestionUrl(item.detail)}>
+                      Open
+                    </Button>
+                  )}
+                </div>
+              ))}
+            </CardContent>
+          </Card>
+        )}
+
+        {sessionReport && (
+          <Card className="flex-none border-primary/40 bg-muted/30">
+            <CardHeader className="flex flex-row items-center justify-between">
+              <div>
+                <CardTitle className="text-base">
+                  {sessionReport.title ? `${sessionReport.title} — ` : ""}Session Report
+                </CardTitle>
+                <CardDescription>
+                  {formatElapsedTimestamp(sessionReport.durationMs)} recorded ·{" "}
+                  {sessionReport.wordsTranscribed} words transcribed
+                </CardDescription>
+              </div>
+              <Button variant="ghost" size="sm" onClick={() => setSessionReport(null)}>
+                Dismiss
+              </Button>
+            </CardHeader>
+            <CardContent className="space-y-1 text-xs text-muted-foreground">
+              <p>
+                Agent runs:{" "}
+                {sessionReport.agentRuns.length > 0
+                  ? sessionReport.agentRuns
+                      .map(
+                        (entry) =>
+                          `${entry.mode} ×${entry.runs}${entry.failures > 0 ? ` (${entry.failures} failed)` : ""}`
+                      )
+                      .join(", ")
+                  : "none"}
+              </p>
+              <p>
+                {sessionReport.toolCalls} tool calls ·{" "}
+                {(sessionReport.inputTokens + sessionReport.outputTokens).toLocaleString()} tokens
+                {sessionReport.costUsd !== null ? ` · $${sessionReport.costUsd.toFixed(4)}` : ""}
+              </p>
+              {sessionReport.artifacts.length > 0 && (
+                <p className="truncate" title={sessionReport.artifacts.join("\n")}>
+                  Artifacts: {sessionReport.artifacts.join(", ")}
+                </p>
+              )}
+            </CardContent>
+          </Card>
+        )}
+
+        {idlePausedMinutes !== null && (
+          <Card className="flex-none border-amber-500/40 bg-muted/30">
+            <CardHeader className="flex flex-row items-center justify-between">
+              <div>
+                <CardTitle className="text-base">Listening paused</CardTitle>
+                <CardDescription>
+                  Nothing was said and nothing was touched for {idlePausedMinutes} minutes, so Jamie
+                  stopped listening. The session is kept.
+                </CardDescription>
+              </div>
+              <div className="flex gap-2">
+                <Button size="sm" onClick={() => void startListening()}>
+                  Resume
+                </Button>
+                <Button variant="ghost" size="sm" onClick={() => setIdlePausedMinutes(null)}>
+                  Dismiss
+                </Button>
+              </div>
+            </CardHeader>
+          </Card>
+        )}
+
+        {staleBrowsers.length > 0 && (
+          <Card className="flex-none border-amber-500/40 bg-muted/30">
+            <CardHeader className="flex flex-row items-center justify-between">
+              <div>
+                <CardTitle className="text-base">Leftover automation browser</CardTitle>
+                <CardDescription>
+                  {staleBrowsers.length === 1
+                    ? "A Chrome window from an earlier agent run is still open."
+                    : `${staleBrowsers.length} Chrome windows from earlier agent runs are still open.`}
+                </CardDescription>
+              </div>
+              <div className="flex gap-2">
+                {staleBrowsers.some((session) => session.reattachable) && (
+                  <Button variant="outline" size="sm" onClick={() => void cleanupStaleBrowsers("reattach")}>
+                    Reuse
+                  </Button>
+                )}
+                <Button variant="outline" size="sm" onClick={() => void cleanupStaleBrowsers("close")}>
+                  Close
+                </Button>
+                <Button variant="ghost" size="sm" onClick={() => setStaleBrowsers([])}>
+                  Dismiss
+                </Button>
+              </div>
+            </CardHeader>
+          </Card>
+        )}
+
         <div
           className={cn(
             "grid min-h-0 flex-1 gap-6 overflow-hidden",
@@ -4847,6 +5994,22 @@ export function App() {
                     {transcriptLines.length === 0 && !interimText && (
                       <p className="text-muted-foreground">No transcript yet.</p>
                     )}
+                    {transcriptLines.length === 0 && !isListening && !activeScenario && (
+                      <div className="space-y-1">
+                        {scenarios.map((scenario) => (
+                          <Button
+                            key={scenario.name}
+                            size="sm"
+                            variant="outline"
+                            className="h-auto w-full justify-start whitespace-normal py-1 text-left text-xs"
+                            title={scenario.description}
+                            onClick={() => void startScenario(scenario.name)}
+                          >
+                            Watch a demo: {scenario.title}
+                          </Button>
+                        ))}
+                      </div>
+                    )}
                     {transcriptLines.map((line, index) => (
                       <p key={`${line}-${index}`} className="leading-relaxed">
                         {line}
@@ -4856,6 +6019,89 @@ export function App() {
                       <p className="text-muted-foreground">{interimText}</p>
                     )}
                   </div>
+                  {(prompts.length > 0 || clipboardSuggestion) && (
+                    <div className="space-y-2 border-t border-border/60 px-3 py-2">
+                      {clipboardSuggestion && (
+                        <div className="space-y-1 rounded-md bg-muted/40 p-2 text-xs">
+                          <p className="truncate text-muted-foreground" title={clipboardSuggestion.preview}>
+                            Copied {clipboardSuggestion.kind === "url" ? "link" : "text"}:{" "}
+                            {clipboardSuggestion.preview}
+                          </p>
+                          <div className="flex flex-wrap gap-1">
+                            {clipboardSuggestion.actions.map((action) => (
+                              <Button
+                                key={action}
+                                size="sm"
+                                variant="outline"
+                                className="h-6 px-2 text-[11px]"
+                                onClick={() => runClipboardAction(clipboardSuggestion, action)}
+                              >
+                                {action === "open"
+                                  ? "Open it?"
+                                  : action === "summarize"
+                                    ? clipboardSuggestion.kind === "url"
+                                      ? "Summarize this article?"
+                                      : "Summarize this?"
+                                    : "Add to canvas?"}
+                              </Button>
+                            ))}
+                            <Button
+                              size="sm"
+                              variant="ghost"
+                              className="h-6 px-2 text-[11px]"
+                              onClick={() => setClipboardSuggestion(null)}
+                            >
+                              Dismiss
+                            </Button>
+                          </div>
+                        </div>
+                      )}
+                      <div className="flex flex-wrap gap-1">
+                        {prompts.map((prompt) => (
+                          <Button
+                            key={prompt.name}
+                            size="sm"
+                            variant="outline"
+                            className="h-6 px-2 text-[11px]"
+                            title={`Run "${prompt.name}" on the selected or copied text`}
+                            disabled={runningPrompt !== null}
+                            // Keep the transcript selection when clicking.
+                            onMouseDown={(event) => event.preventDefault()}
+                            onClick={() => void runPromptOnSelection(prompt.name)}
+                          >
+                            {runningPrompt === prompt.name && (
+                              <Loader2 className="mr-1 h-3 w-3 animate-spin" />
+                            )}
+                            {prompt.name}
+                          </Button>
+                        ))}
+                      </div>
+                      {promptResult && (
+                        <div className="max-h-40 overflow-y-auto rounded-md bg-muted/40 p-2 text-xs">
+                          <div className="mb-1 flex items-center justify-between">
+                            <span className="font-semibold">{promptResult.name}</span>
+                            <span className="space-x-2">
+                              <button
+                                type="button"
+                                className="text-muted-foreground hover:text-foreground"
+                                onClick={() => void navigator.clipboard.writeText(promptResult.text)}
+                              >
+                                Copy
+                              </button>
+                              <button
+                                type="button"
+                                className="text-muted-foreground hover:text-foreground"
+                                onClick={() => setPromptResult(null)}
+                              >
+                                Close
+                              </button>
+                            </span>
+                          </div>
+                          <p className="whitespace-pre-wrap leading-relaxed">{promptResult.text}</p>
+                        </div>
+                      )}
+                    </div>
+                  )}
                 </section>
 
                 <section className="flex min-h-0 flex-1 flex-col rounded-lg border border-border/60 bg-background/60">
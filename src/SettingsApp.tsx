@@ -42,6 +42,45 @@ import { Input } from "./components/ui/input";
 import { Label } from "./components/ui/label";
 import { Textarea } from "./components/ui/textarea";
 import { Checkbox } from "./components/ui/checkbox";
+import { AccelerationCard } from "./components/settings/AccelerationCard";
+import { AccountsCard } from "./components/settings/AccountsCard";
+import { AgendaCard } from "./components/settings/AgendaCard";
+import { AgentModesCard } from "./components/settings/AgentModesCard";
+import { CaptionsCard } from "./components/settings/CaptionsCard";
+import { ClipboardCard } from "./components/settings/ClipboardCard";
+import { DecodingCard } from "./components/settings/DecodingCard";
+import { DictationCard } from "./components/settings/DictationCard";
+import { DisplayCard } from "./components/settings/DisplayCard";
+import { DocsCacheCard } from "./components/settings/DocsCacheCard";
+import { EmailCard } from "./components/settings/EmailCard";
+import { FetchCard } from "./components/settings/FetchCard";
+import { FocusGateCard } from "./components/settings/FocusGateCard";
+import { GuestModeCard } from "./components/settings/GuestModeCard";
+import { IdlePauseCard } from "./components/settings/IdlePauseCard";
+import { InputTracksCard } from "./components/settings/InputTracksCard";
+import { JobsCard } from "./components/settings/JobsCard";
+import { LanguageCard } from "./components/settings/LanguageCard";
+import { McpHealthCard } from "./components/settings/McpHealthCard";
+import { McpServersCard } from "./components/settings/McpServersCard";
+import { MeetingCard } from "./components/settings/MeetingCard";
+import { MicrophoneCard } from "./components/settings/MicrophoneCard";
+import { PostSessionCard } from "./components/settings/PostSessionCard";
+import { PowerCard } from "./components/settings/PowerCard";
+import { PreprocessingCard } from "./components/settings/PreprocessingCard";
+import { PromptsCard } from "./components/settings/PromptsCard";
+import { PushToTalkCard } from "./components/settings/PushToTalkCard";
+import { RedactionCard } from "./components/settings/RedactionCard";
+import { SandboxCard } from "./components/settings/SandboxCard";
+import { ScriptingCard } from "./components/settings/ScriptingCard";
+import { SessionAudioCard } from "./components/settings/SessionAudioCard";
+import { SettingsBackupCard } from "./components/settings/SettingsBackupCard";
+import { TimecodeCard } from "./components/settings/TimecodeCard";
+import { TranscriptFiltersCard } from "./components/settings/TranscriptFiltersCard";
+import { TranscriptionBackendCard } from "./components/settings/TranscriptionBackendCard";
+import { TranscriptionMetricsCard } from "./components/settings/TranscriptionMetricsCard";
+import { VocabularyCard } from "./components/settings/VocabularyCard";
+import { WebhooksCard } from "./components/settings/WebhooksCard";
+import { WhisperModelsCard } from "./components/settings/WhisperModelsCard";
 import { cn } from "./lib/utils";
 import { PERSONAS, NO_PERSONA_ID } from "./personas";
 import type { UserNote } from "./userNotes";
@@ -86,9 +125,6 @@ export function SettingsApp() {
   const [testPrompt, setTestPrompt] = React.useState(DEFAULT_PROMPT);
   const [testResult, setTestResult] = React.useState("");
   const [isTesting, setIsTesting] = React.useState(false);
-  const [mcpConfigPath, setMcpConfigPath] = React.useState("");
-  const [mcpConfigJson, setMcpConfigJson] = React.useState("");
-  const [mcpConfigStatus, setMcpConfigStatus] = React.useState("");
   const [preferredUrls, setPreferredUrls] = React.useState("");
   const [preferredUrlStatus, setPreferredUrlStatus] = React.useState("");
   const [isDescribingPreferredUrls, setIsDescribingPreferredUrls] =
@@ -281,42 +317,6 @@ export function SettingsApp() {
     setNarrativePrompt(settings.narrativePrompt || DEFAULT_NARRATIVE_PROMPT);
   }, []);
 
-  const hydrateMcpConfig = React.useCallback(async () => {
-    try {
-      const response = await invoke<{ path: string; content: string }>(
-        "get_mcp_config"
-      );
-      setMcpConfigJson(response.content || "");
-      setMcpConfigPath(`Config file location: ${response.path}`);
-    } catch (error) {
-      setMcpConfigStatus(`Failed to load MCP config: ${String(error)}`);
-    }
-  }, []);
-
-  const saveMcpConfig = React.useCallback(async () => {
-    try {
-      const response = await invoke<{ path: string; content: string }>(
-        "save_mcp_config",
-        { content: mcpConfigJson }
-      );
-      setMcpConfigJson(response.content || mcpConfigJson);
-      setMcpConfigPath(`Config file location: ${response.path}`);
-      setMcpConfigStatus("MCP config saved.");
-    } catch (error) {
-      setMcpConfigStatus(`Failed to save MCP config: ${String(error)}`);
-    }
-  }, [mcpConfigJson]);
-
-  const testMcpConfig = React.useCallback(async () => {
-    setMcpConfigStatus("Testing MCP servers...");
-    try {
-      const result = await invoke<string>("test_mcp_config");
-      setMcpConfigStatus(result);
-    } catch (error) {
-      setMcpConfigStatus(`MCP test failed: ${String(error)}`);
-    }
-  }, []);
-
   const handleSaveSettings = React.useCallback(() => {
     const settings: OpenRouterSettings = {
       apiKey: openRouterKey.trim(),
@@ -502,7 +502,6 @@ export function SettingsApp() {
 
   React.useEffect(() => {
     hydrateForm();
-    void hydrateMcpConfig();
     setUserNotes(loadUserNotes());
 
     const cachedModelCatalog = loadOpenRouterModelCatalogCache();
@@ -527,7 +526,7 @@ export function SettingsApp() {
 
     window.addEventListener("storage", handleStorage);
     return () => window.removeEventListener("storage", handleStorage);
-  }, [formatCacheTimestamp, hydrateForm, hydrateMcpConfig, refreshModelCatalog]);
+  }, [formatCacheTimestamp, hydrateForm, refreshModelCatalog]);
 
   React.useEffect(() => {
     const handlePointerDown = (event: MouseEvent) => {
@@ -1056,37 +1055,47 @@ export function SettingsApp() {
             </CardContent>
           </Card>
 
-          <Card>
-            <CardHeader>
-              <CardTitle>MCP Config</CardTitle>
-              <CardDescription>Manage available MCP servers.</CardDescription>
-            </CardHeader>
-            <CardContent className="space-y-4">
-              <p className="text-xs text-muted-foreground">
-                Optional per-server flag: set <code className="rounded bg-muted px-1 py-0.5 text-[11px]">"enabled": false</code> to disable a server without deleting its config.
-              </p>
-              <p className="text-xs text-muted-foreground">{mcpConfigPath}</p>
-              <div className="space-y-2">
-                <Label htmlFor="mcp-config-json">Config JSON</Label>
-                <Textarea
-                  id="mcp-config-json"
-                  rows={8}
-                  value={mcpConfigJson}
-                  onChange={(event) => setMcpConfigJson(event.target.value)}
-                />
-              </div>
-              <div className="flex flex-wrap gap-2">
-                <Button onClick={saveMcpConfig}>Save MCP config</Button>
-                <Button variant="outline" onClick={hydrateMcpConfig}>
-                  Reload
-                </Button>
-                <Button variant="ghost" onClick={testMcpConfig}>
-                  Test MCP servers
-                </Button>
-              </div>
-              <p className="text-xs text-muted-foreground">{mcpConfigStatus}</p>
-            </CardContent>
-          </Card>
+          <AccountsCard />
+          <EmailCard />
+          <PostSessionCard />
+          <SessionAudioCard />
+          <TranscriptionBackendCard />
+          <WhisperModelsCard />
+          <JobsCard />
+          <AccelerationCard />
+          <DecodingCard />
+          <TranscriptionMetricsCard />
+          <MicrophoneCard />
+          <InputTracksCard />
+          <PreprocessingCard />
+          <PushToTalkCard />
+          <DictationCard />
+          <FocusGateCard />
+          <IdlePauseCard />
+          <WebhooksCard />
+          <CaptionsCard />
+          <TimecodeCard />
+          <LanguageCard />
+          <VocabularyCard />
+          <TranscriptFiltersCard />
+          <RedactionCard />
+          <PromptsCard />
+          <AgentModesCard />
+          <AgendaCard />
+          <ClipboardCard />
+          <MeetingCard />
+          <DocsCacheCard />
+          <FetchCard />
+          <DisplayCard />
+          <PowerCard />
+          <SettingsBackupCard />
+          <GuestModeCard />
+
+          <McpServersCard />
+          <McpHealthCard />
+
+          <SandboxCard />
+          <ScriptingCard />
 
           <Card>
             <CardHeader>
@@ -0,0 +1,23 @@
+import { commands } from "./bindings";
+
+/**
+ * Backend command contract this bundle was built against. Bump together with
+ * `API_VERSION` in `src-tauri/src/api_version.rs` when a command's shape changes.
+ */
+export const FRONTEND_API_VERSION = 2;
+
+/**
+ * Tell the backend which contract this webview speaks. A cached bundle that is
+ * too old for the backend reloads once to pick up the bundled assets.
+ */
+export async function negotiateApiVersion(): Promise<void> {
+  try {
+    const handshake = await commands.negotiateApiVersion(FRONTEND_API_VERSION);
+    if (handshake.reloadRequired && !sessionStorage.getItem("heyjamie.apiReload")) {
+      sessionStorage.setItem("heyjamie.apiReload", "1");
+      window.location.reload();
+    }
+  } catch {
+    // Backends from before negotiation don't have the command; they speak v1.
+  }
+}
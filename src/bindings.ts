@@ -0,0 +1,2802 @@
+// @ts-nocheck
+/* eslint-disable */
+// This file was generated by [tauri-specta](https://github.com/oscartbeaumont/tauri-specta). Do not edit this file manually.
+
+/** user-defined commands **/
+
+
+export const commands = {
+async greet(name: string) : Promise<string> {
+    return await TAURI_INVOKE("greet", { name });
+},
+/**
+ * Called by each webview at startup with the contract it was built
+ * against. Webviews that never call this are treated as API v1.
+ */
+async negotiateApiVersion(clientVersion: number) : Promise<ApiHandshake> {
+    return await TAURI_INVOKE("negotiate_api_version", { clientVersion });
+},
+/**
+ * `language` overrides `transcription.language` for this call: a whisper
+ * code such as `es`, or `auto`. `translate` mode runs whisper a second
+ * time to translate the speech to English. The set fields of `options`
+ * override `transcription.options`. `input` names the input track or
+ * device, for its preprocessing filters. API v1 webviews get the text
+ * alone and can't translate.
+ */
+async transcribeAudio(audioBase64: string, language: string | null, mode: TranscribeMode | null, options: TranscriptionOptions | null, input: string | null) : Promise<TranscribeAudioResponse> {
+    return await TAURI_INVOKE("transcribe_audio", { audioBase64, language, mode, options, input });
+},
+/**
+ * Like `transcribe_audio`, but also emits a `transcription-partial` event
+ * tagged with `stream_id` for every segment as it is decoded. Remote
+ * servers answer in one piece, so they produce a single partial.
+ */
+async transcribeAudioStream(audioBase64: string, streamId: string, language: string | null, options: TranscriptionOptions | null, input: string | null) : Promise<string> {
+    return await TAURI_INVOKE("transcribe_audio_stream", { audioBase64, streamId, language, options, input });
+},
+/**
+ * The diarization mode of `transcribe_audio`: the same local transcription,
+ * but returned as segments with speaker labels and timestamps instead of
+ * one joined string. Needs a tinydiarize model (`ggml-small.en-tdrz.bin`)
+ * in the models folder; remote whisper servers are not used.
+ */
+async transcribeAudioDiarized(audioBase64: string, language: string | null) : Promise<SpeakerSegment[]> {
+    return await TAURI_INVOKE("transcribe_audio_diarized", { audioBase64, language });
+},
+/**
+ * Like `transcribe_audio`, but returns each segment with its timestamps,
+ * confidence and word timings instead of one joined string, so the
+ * transcript can be synced with playback and unsure words highlighted.
+ * Always local.
+ */
+async transcribeAudioDetailed(audioBase64: string, language: string | null) : Promise<DetailedTranscript> {
+    return await TAURI_INVOKE("transcribe_audio_detailed", { audioBase64, language });
+},
+/**
+ * Guess the spoken language of a clip with the local whisper model, or
+ * the first downloaded multilingual one if the active model is English-only.
+ */
+async detectLanguage(audioBase64: string) : Promise<DetectedLanguage> {
+    return await TAURI_INVOKE("detect_language", { audioBase64 });
+},
+async getAppSettings() : Promise<AppSettings> {
+    return await TAURI_INVOKE("get_app_settings");
+},
+async saveAppSettings(settings: AppSettings) : Promise<AppSettings> {
+    return await TAURI_INVOKE("save_app_settings", { settings });
+},
+async getEndpointHealth() : Promise<EndpointHealth[]> {
+    return await TAURI_INVOKE("get_endpoint_health");
+},
+/**
+ * Release notes newer than `since_version` (or the last version the user
+ * acknowledged) plus every config migration applied since then.
+ */
+async getReleaseNotes(sinceVersion: string | null) : Promise<ReleaseNotesResponse> {
+    return await TAURI_INVOKE("get_release_notes", { sinceVersion });
+},
+/**
+ * Record that the user has seen the notes for the running version.
+ */
+async markReleaseNotesSeen() : Promise<null> {
+    return await TAURI_INVOKE("mark_release_notes_seen");
+},
+/**
+ * Let the frontend check ahead of time (e.g. to grey out "Start listening").
+ */
+async preflightCheck(operation: PreflightOperation) : Promise<PreflightReport> {
+    return await TAURI_INVOKE("preflight_check", { operation });
+},
+/**
+ * Start a new session, or return the one already running so repeated
+ * start/stop of the microphone stays within the same session.
+ */
+async startSession(title: string | null) : Promise<SessionInfo> {
+    return await TAURI_INVOKE("start_session", { title });
+},
+async endSession() : Promise<SessionInfo | null> {
+    return await TAURI_INVOKE("end_session");
+},
+async getActiveSession() : Promise<SessionInfo | null> {
+    return await TAURI_INVOKE("get_active_session");
+},
+/**
+ * All stored sessions, newest first.
+ */
+async listSessions() : Promise<SessionInfo[]> {
+    return await TAURI_INVOKE("list_sessions");
+},
+async getSessionTranscript(sessionId: string) : Promise<TranscriptSegment[]> {
+    return await TAURI_INVOKE("get_session_transcript", { sessionId });
+},
+/**
+ * Append a transcript chunk to the active session and run voice triggers
+ * on it. `source` names the input track in multi-track recordings. Returns
+ * `None` when no session is running.
+ */
+async recordTranscriptSegment(text: string, source: string | null) : Promise<TranscriptSegment | null> {
+    return await TAURI_INVOKE("record_transcript_segment", { text, source });
+},
+/**
+ * Replace the text of a segment of the active session, e.g. with a
+ * correction, and pass the revision on to synced consumers. The text is
+ * cleaned and redacted like a transcribed segment.
+ */
+async reviseTranscriptSegment(index: number, text: string) : Promise<TranscriptSegment> {
+    return await TAURI_INVOKE("revise_transcript_segment", { index, text });
+},
+/**
+ * Drop a segment from the active session, e.g. a hallucinated line.
+ * Later segments keep their index.
+ */
+async deleteTranscriptSegment(index: number) : Promise<null> {
+    return await TAURI_INVOKE("delete_transcript_segment", { index });
+},
+/**
+ * Remember the page the browser is showing so bookmarks can link back to it.
+ */
+async noteSessionBrowserUrl(url: string) : Promise<void> {
+    await TAURI_INVOKE("note_session_browser_url", { url });
+},
+async bookmarkMoment(label: string | null) : Promise<Bookmark> {
+    return await TAURI_INVOKE("bookmark_moment", { label });
+},
+async getBookmarks(sessionId: string) : Promise<Bookmark[]> {
+    return await TAURI_INVOKE("get_bookmarks", { sessionId });
+},
+/**
+ * Register elements created outside the agent (e.g. direct canvas
+ * commands) against the current transcript position.
+ */
+async linkCanvasElements(elementIds: string[]) : Promise<null> {
+    return await TAURI_INVOKE("link_canvas_elements", { elementIds });
+},
+/**
+ * The conversation moment that produced `element_id`, searching the active
+ * session first and then older sessions.
+ */
+async getCanvasProvenance(elementId: string) : Promise<CanvasLink | null> {
+    return await TAURI_INVOKE("get_canvas_provenance", { elementId });
+},
+/**
+ * Boards of the current session, with the one on the canvas.
+ */
+async listBoards() : Promise<CanvasBoards> {
+    return await TAURI_INVOKE("list_boards");
+},
+/**
+ * Start an empty board and show it; the current scene is kept on the
+ * board it was drawn on.
+ */
+async createBoard(name: string) : Promise<CanvasBoards> {
+    return await TAURI_INVOKE("create_board", { name });
+},
+/**
+ * Show another board of the current session.
+ */
+async switchBoard(name: string) : Promise<CanvasBoards> {
+    return await TAURI_INVOKE("switch_board", { name });
+},
+/**
+ * Board snapshots of a session, oldest first.
+ */
+async listCanvasSnapshots(sessionId: string) : Promise<CanvasSnapshot[]> {
+    return await TAURI_INVOKE("list_canvas_snapshots", { sessionId });
+},
+async getPushToTalkStatus() : Promise<PushToTalkStatus> {
+    return await TAURI_INVOKE("get_push_to_talk_status");
+},
+/**
+ * Turn push-to-talk on with `shortcut` (e.g. `CmdOrCtrl+Shift+Space`).
+ */
+async registerPushToTalk(shortcut: string, nativeRecording: boolean) : Promise<PushToTalkStatus> {
+    return await TAURI_INVOKE("register_push_to_talk", { shortcut, nativeRecording });
+},
+async unregisterPushToTalk() : Promise<PushToTalkStatus> {
+    return await TAURI_INVOKE("unregister_push_to_talk");
+},
+async getDictationStatus() : Promise<DictationStatus> {
+    return await TAURI_INVOKE("get_dictation_status");
+},
+/**
+ * Turn dictation on or off without the shortcut.
+ */
+async setDictationActive(active: boolean) : Promise<DictationStatus> {
+    return await TAURI_INVOKE("set_dictation_active", { active });
+},
+async getWebhookStatus() : Promise<WebhookStatus> {
+    return await TAURI_INVOKE("get_webhook_status");
+},
+async listAgendaItems() : Promise<AgendaItem[]> {
+    return await TAURI_INVOKE("list_agenda_items");
+},
+async addAgendaItem(task: string, dueBy: number | null) : Promise<AgendaItem> {
+    return await TAURI_INVOKE("add_agenda_item", { task, dueBy });
+},
+async removeAgendaItem(id: string) : Promise<null> {
+    return await TAURI_INVOKE("remove_agenda_item", { id });
+},
+/**
+ * Put a failed or finished item back in the queue.
+ */
+async retryAgendaItem(id: string) : Promise<AgendaItem> {
+    return await TAURI_INVOKE("retry_agenda_item", { id });
+},
+async getAgendaStatus() : Promise<AgendaStatusSummary> {
+    return await TAURI_INVOKE("get_agenda_status");
+},
+/**
+ * Results waiting for the next session, as they'll appear in its prep.
+ */
+async getSessionPrep() : Promise<string> {
+    return await TAURI_INVOKE("get_session_prep");
+},
+/**
+ * Research one agenda item with the general agent and its MCP tools.
+ */
+async runAgendaItem(id: string, settings: LlmAgentSettings) : Promise<AgendaItem> {
+    return await TAURI_INVOKE("run_agenda_item", { id, settings });
+},
+/**
+ * Transcribe a whole recording from the webview (any length) with the
+ * long-recording windowing, instead of slicing it in the webview.
+ * `input` names the input track, for its preprocessing filters.
+ */
+async transcribeLongAudio(audioBase64: string, language: string | null, input: string | null) : Promise<string> {
+    return await TAURI_INVOKE("transcribe_long_audio", { audioBase64, language, input });
+},
+async getMicGate() : Promise<MicGate> {
+    return await TAURI_INVOKE("get_mic_gate");
+},
+/**
+ * Called by the main window when it starts (`true`) or stops listening.
+ */
+async setIdleWatch(listening: boolean) : Promise<void> {
+    await TAURI_INVOKE("set_idle_watch", { listening });
+},
+/**
+ * A key press or click in a HeyJamie window.
+ */
+async noteUserActivity() : Promise<void> {
+    await TAURI_INVOKE("note_user_activity");
+},
+/**
+ * Save the API key for a cloud backend in the keychain. An empty key
+ * removes it.
+ */
+async setTranscriptionApiKey(backend: TranscriptionBackendKind, key: string) : Promise<null> {
+    return await TAURI_INVOKE("set_transcription_api_key", { backend, key });
+},
+/**
+ * Whether a cloud backend has a key, saved or from the environment.
+ */
+async hasTranscriptionApiKey(backend: TranscriptionBackendKind) : Promise<boolean> {
+    return await TAURI_INVOKE("has_transcription_api_key", { backend });
+},
+async getTranscriptionQueue() : Promise<TranscriptionQueueStatus> {
+    return await TAURI_INVOKE("get_transcription_queue");
+},
+async listScripts() : Promise<ScriptInfo[]> {
+    return await TAURI_INVOKE("list_scripts");
+},
+/**
+ * Pick up added or edited scripts.
+ */
+async reloadScripts() : Promise<ScriptInfo[]> {
+    return await TAURI_INVOKE("reload_scripts");
+},
+async listAudioDevices() : Promise<AudioDevice[]> {
+    return await TAURI_INVOKE("list_audio_devices");
+},
+/**
+ * Record from `name` from the next recording on; `None` or empty follows
+ * the OS default.
+ */
+async setAudioDevice(name: string | null) : Promise<null> {
+    return await TAURI_INVOKE("set_audio_device", { name });
+},
+async getBackgroundActivity() : Promise<BackgroundActivity> {
+    return await TAURI_INVOKE("get_background_activity");
+},
+/**
+ * Unfinished jobs, oldest first.
+ */
+async listJobs() : Promise<Job[]> {
+    return await TAURI_INVOKE("list_jobs");
+},
+/**
+ * Pick a failed or interrupted job up from its checkpoint. Progress is
+ * reported the way the job usually reports it (`model-download-progress`,
+ * `file-transcription`) and by `job-changed`.
+ */
+async resumeJob(id: string) : Promise<null> {
+    return await TAURI_INVOKE("resume_job", { id });
+},
+/**
+ * Forget a job that isn't running, with its checkpoint and partial output.
+ */
+async discardJob(id: string) : Promise<null> {
+    return await TAURI_INVOKE("discard_job", { id });
+},
+/**
+ * The audio kept for `session_id`; empty when none was.
+ */
+async getSessionAudio(sessionId: string) : Promise<SessionAudio> {
+    return await TAURI_INVOKE("get_session_audio", { sessionId });
+},
+/**
+ * One kept segment as base64 WAV, for playback.
+ */
+async readSessionAudioSegment(sessionId: string, offsetMs: number) : Promise<string> {
+    return await TAURI_INVOKE("read_session_audio_segment", { sessionId, offsetMs });
+},
+async deleteSessionAudio(sessionId: string) : Promise<null> {
+    return await TAURI_INVOKE("delete_session_audio", { sessionId });
+},
+/**
+ * Re-run whisper over the saved audio of `session_id` with `model` (a
+ * model file name; by default the wrap-up step's) and `options` on top of
+ * the saved decoding options. The result is stored as a new version next
+ * to the live transcript and earlier versions.
+ */
+async retranscribeSession(sessionId: string, model: string | null, options: TranscriptionOptions | null) : Promise<TranscriptVersion> {
+    return await TAURI_INVOKE("retranscribe_session", { sessionId, model, options });
+},
+/**
+ * Stored versions of `session_id`, oldest first.
+ */
+async listTranscriptVersions(sessionId: string) : Promise<TranscriptVersion[]> {
+    return await TAURI_INVOKE("list_transcript_versions", { sessionId });
+},
+async getTranscriptVersion(sessionId: string, versionId: string) : Promise<TranscriptSegment[]> {
+    return await TAURI_INVOKE("get_transcript_version", { sessionId, versionId });
+},
+/**
+ * The profile processes would run under now, for checking what it allows.
+ */
+async getSandboxProfile() : Promise<string> {
+    return await TAURI_INVOKE("get_sandbox_profile");
+},
+/**
+ * Start an OAuth device flow. Returns the code to show the user; the
+ * result arrives later as an `account-changed` event.
+ */
+async connectAccount(provider: string) : Promise<DeviceAuthorization> {
+    return await TAURI_INVOKE("connect_account", { provider });
+},
+/**
+ * Forget the stored token (revoking it where the provider supports that)
+ * and cancel any pending sign-in.
+ */
+async disconnectAccount(provider: string) : Promise<null> {
+    return await TAURI_INVOKE("disconnect_account", { provider });
+},
+async listAccounts() : Promise<AccountStatus[]> {
+    return await TAURI_INVOKE("list_accounts");
+},
+async exportSession(sessionId: string, target: ExportTarget) : Promise<ExportResult> {
+    return await TAURI_INVOKE("export_session", { sessionId, target });
+},
+/**
+ * Show a session's folder in Finder, Explorer or the file manager.
+ */
+async revealSessionFolder(sessionId: string) : Promise<null> {
+    return await TAURI_INVOKE("reveal_session_folder", { sessionId });
+},
+/**
+ * Pack a session's folder (transcript, exports, snapshots, audio and
+ * artifacts) into `<session id>.tar.gz` in `destination`, by default the
+ * Downloads folder. Returns the archive's path.
+ */
+async exportSessionBundle(sessionId: string, destination: string | null) : Promise<string> {
+    return await TAURI_INVOKE("export_session_bundle", { sessionId, destination });
+},
+/**
+ * Save a research table with the active session so it can be exported.
+ */
+async recordResearchTable(table: ResearchTable) : Promise<null> {
+    return await TAURI_INVOKE("record_research_table", { table });
+},
+/**
+ * Email a session recap. Empty `recipients` means the configured defaults.
+ */
+async emailSessionSummary(sessionId: string, recipients: string[]) : Promise<null> {
+    return await TAURI_INVOKE("email_session_summary", { sessionId, recipients });
+},
+/**
+ * Store the SMTP password for the configured username in the keychain.
+ * An empty password removes it.
+ */
+async setSmtpPassword(password: string) : Promise<null> {
+    return await TAURI_INVOKE("set_smtp_password", { password });
+},
+/**
+ * Run the enabled steps again for a finished session.
+ */
+async runPostSessionPipeline(sessionId: string) : Promise<PipelineRun> {
+    return await TAURI_INVOKE("run_post_session_pipeline", { sessionId });
+},
+async getPostSessionStatus(sessionId: string) : Promise<PipelineRun | null> {
+    return await TAURI_INVOKE("get_post_session_status", { sessionId });
+},
+/**
+ * Store the Slack incoming webhook URL in the keychain. Empty removes it.
+ */
+async setSlackWebhook(url: string) : Promise<null> {
+    return await TAURI_INVOKE("set_slack_webhook", { url });
+},
+async listWhisperModels() : Promise<WhisperModel[]> {
+    return await TAURI_INVOKE("list_whisper_models");
+},
+/**
+ * Download a catalog model, or a quantized variant of one, into the
+ * models dir, checking it against the bundled or published SHA256.
+ * Returns its path.
+ */
+async downloadWhisperModel(name: string) : Promise<string> {
+    return await TAURI_INVOKE("download_whisper_model", { name });
+},
+/**
+ * Remove a downloaded model. Models installed by the setup script are
+ * left alone.
+ */
+async deleteWhisperModel(name: string) : Promise<null> {
+    return await TAURI_INVOKE("delete_whisper_model", { name });
+},
+/**
+ * Check whisper-cli and every installed model: header, size and SHA256
+ * (bundled or recorded at download). With `repair`, damaged catalog
+ * models are moved aside and downloaded again.
+ */
+async verifyWhisperSetup(repair: boolean) : Promise<WhisperSetupReport> {
+    return await TAURI_INVOKE("verify_whisper_setup", { repair });
+},
+/**
+ * Transcribe the reference clip (or `clip_path`) with each installed
+ * model and report decoding time and real-time factor per model.
+ * Refused while recording, since it would hold up live transcription.
+ */
+async benchmarkWhisper(clipPath: string | null) : Promise<WhisperBenchmark> {
+    return await TAURI_INVOKE("benchmark_whisper", { clipPath });
+},
+/**
+ * Install a faster variant of catalog model `name`: `q8_0`, `q5_1` or
+ * `q5_0` (downloaded, or quantized locally when the download fails), or
+ * `coreml` for the Core ML encoder. Returns where it was put.
+ */
+async prepareModelVariant(name: string, variant: string) : Promise<string> {
+    return await TAURI_INVOKE("prepare_model_variant", { name, variant });
+},
+/**
+ * Called by the topic tracker whenever it opens (or retitles) a chapter.
+ * Returns the id of the topic node the chapter belongs to.
+ */
+async recordTopic(chapterId: string, label: string, keywords: string[]) : Promise<string> {
+    return await TAURI_INVOKE("record_topic", { chapterId, label, keywords });
+},
+async getTopicGraph(sessionId: string) : Promise<TopicGraph> {
+    return await TAURI_INVOKE("get_topic_graph", { sessionId });
+},
+async getDocsCacheStats() : Promise<DocsCacheStats> {
+    return await TAURI_INVOKE("get_docs_cache_stats");
+},
+async clearDocsCache() : Promise<null> {
+    return await TAURI_INVOKE("clear_docs_cache");
+},
+/**
+ * The saved report for `session_id`, or a live one if it hasn't ended.
+ */
+async getSessionReport(sessionId: string) : Promise<SessionReport> {
+    return await TAURI_INVOKE("get_session_report", { sessionId });
+},
+/**
+ * Set the zoom for one window, or for every window when `window` is
+ * omitted, and apply it to whatever is open. Returns the stored factor.
+ */
+async setUiScale(window: string | null, factor: number) : Promise<number> {
+    return await TAURI_INVOKE("set_ui_scale", { window, factor });
+},
+async getPowerState() : Promise<PowerState> {
+    return await TAURI_INVOKE("get_power_state");
+},
+async getLoadPressure() : Promise<LoadPressure> {
+    return await TAURI_INVOKE("get_load_pressure");
+},
+/**
+ * Record an NTP-disciplined time-of-day reference for the active session.
+ */
+async syncSessionClock(server: string | null) : Promise<SessionTimecode> {
+    return await TAURI_INVOKE("sync_session_clock", { server });
+},
+/**
+ * Anchor the active session to LTC decoded from a WAV clip that ended
+ * just now, captured from the input the recorder's timecode feeds.
+ */
+async anchorSessionLtc(audioBase64: string) : Promise<SessionTimecode> {
+    return await TAURI_INVOKE("anchor_session_ltc", { audioBase64 });
+},
+async getSessionTimecode(sessionId: string) : Promise<SessionTimecode | null> {
+    return await TAURI_INVOKE("get_session_timecode", { sessionId });
+},
+async setRecordingSegment(active: boolean) : Promise<RecordingSegment> {
+    return await TAURI_INVOKE("set_recording_segment", { active });
+},
+async getRecordingSegment() : Promise<RecordingSegment> {
+    return await TAURI_INVOKE("get_recording_segment");
+},
+/**
+ * Hold a frontend-side response (e.g. a direct command) for later review.
+ * Returns false, and holds nothing, when no segment is active.
+ */
+async holdForReview(kind: HeldKind, detail: string) : Promise<boolean> {
+    return await TAURI_INVOKE("hold_for_review", { kind, detail });
+},
+/**
+ * Hand back everything held so far and clear the list.
+ */
+async takeHeldResponses() : Promise<HeldResponse[]> {
+    return await TAURI_INVOKE("take_held_responses");
+},
+/**
+ * Capture the default microphone, system audio or both natively, per
+ * `transcription.captureSource`. Segments are transcribed in the
+ * background and arrive as `recorded-transcript` events.
+ */
+async startRecording() : Promise<RecordingInfo> {
+    return await TAURI_INVOKE("start_recording");
+},
+/**
+ * Stop capturing. The last partial segment is still transcribed and
+ * delivered as an event after this returns.
+ */
+async stopRecording() : Promise<null> {
+    return await TAURI_INVOKE("stop_recording");
+},
+/**
+ * Write the settings store to `path` without secrets, for backup or
+ * moving to another machine.
+ */
+async exportSettings(path: string) : Promise<null> {
+    return await TAURI_INVOKE("export_settings", { path });
+},
+/**
+ * Replace the settings store with an exported file after migrating and
+ * validating it. It is saved through `save_app_settings`, so the same
+ * checks apply and every subsystem picks it up. Nothing is saved when the
+ * file is rejected.
+ */
+async importSettings(path: string) : Promise<AppSettings> {
+    return await TAURI_INVOKE("import_settings", { path });
+},
+/**
+ * Switch to a temporary profile with no access to the owner's sessions,
+ * settings or keychain. Everything it creates is erased when it ends.
+ */
+async startGuestMode() : Promise<null> {
+    return await TAURI_INVOKE("start_guest_mode");
+},
+/**
+ * Leave guest mode and erase its sessions and settings.
+ */
+async endGuestMode() : Promise<null> {
+    return await TAURI_INVOKE("end_guest_mode");
+},
+async getGuestMode() : Promise<boolean> {
+    return await TAURI_INVOKE("get_guest_mode");
+},
+/**
+ * Bundled demo sessions plus any in `<app data>/scenarios`.
+ */
+async listScenarios() : Promise<ScenarioSummary[]> {
+    return await TAURI_INVOKE("list_scenarios");
+},
+/**
+ * Play a scripted session: its lines and canned agent replies arrive as
+ * `scenario-progress` events at their scripted times, so Jamie can be
+ * shown without a microphone or API key.
+ */
+async runScenario(name: string) : Promise<ScenarioSummary> {
+    return await TAURI_INVOKE("run_scenario", { name });
+},
+async cancelScenario() : Promise<void> {
+    await TAURI_INVOKE("cancel_scenario");
+},
+async listPrompts() : Promise<SavedPrompt[]> {
+    return await TAURI_INVOKE("list_prompts");
+},
+/**
+ * Send `text` through the saved prompt `prompt_name` with a single,
+ * tool-less LLM call and return the model's reply, for quick "summarize
+ * this" or "translate this" actions outside the voice loop.
+ */
+async runPromptOnText(promptName: string, text: string, settings: LlmAgentSettings) : Promise<string> {
+    return await TAURI_INVOKE("run_prompt_on_text", { promptName, text, settings });
+},
+async getMeeting() : Promise<string | null> {
+    return await TAURI_INVOKE("get_meeting");
+},
+/**
+ * Transcribe local audio files, or every audio file in a folder, without
+ * the microphone path. Each transcript is written next to its file as
+ * `<name>.transcript.txt`, and `file-transcription` events report
+ * progress. `parallel` files (1 to 4, default 1) are worked on at once.
+ * Returns how many files were queued.
+ */
+async transcribeFiles(paths: string[], parallel: number | null) : Promise<number> {
+    return await TAURI_INVOKE("transcribe_files", { paths, parallel });
+},
+/**
+ * Let the files already being transcribed finish and skip the rest.
+ */
+async cancelFileTranscription() : Promise<void> {
+    await TAURI_INVOKE("cancel_file_transcription");
+},
+/**
+ * Drive the voice loop headlessly: silence and a fixture recording go
+ * through the real transcription path, the transcript and a set of
+ * canned utterances through trigger routing, and agent-bound ones to a
+ * stubbed agent. Each stage is checked and written to the integration
+ * test log, which is reset first.
+ */
+async runE2eSelfcheck() : Promise<SelfcheckReport> {
+    return await TAURI_INVOKE("run_e2e_selfcheck");
+},
+/**
+ * Trends across stored sessions started within `range`: talk time per
+ * week, most-discussed topics, and agent usage and latency by mode.
+ */
+async getAnalytics(range: AnalyticsRange) : Promise<Analytics> {
+    return await TAURI_INVOKE("get_analytics", { range });
+},
+/**
+ * Write a session's stored transcript, with its timing, as SRT, WebVTT,
+ * JSON or Markdown. `destination` is a file, a folder that gets
+ * `<session id>.<ext>`, or empty for the session's `exports/` folder.
+ * Returns the path written.
+ */
+async exportTranscript(sessionId: string, format: TranscriptFormat, destination: string | null) : Promise<string> {
+    return await TAURI_INVOKE("export_transcript", { sessionId, format, destination });
+},
+/**
+ * Save a stored session (metadata and full transcript) into the history
+ * database, replacing what it had for it.
+ */
+async saveHistorySession(sessionId: string) : Promise<null> {
+    return await TAURI_INVOKE("save_history_session", { sessionId });
+},
+/**
+ * Sessions in the history, newest first.
+ */
+async listHistory(limit: number | null, offset: number | null) : Promise<HistorySession[]> {
+    return await TAURI_INVOKE("list_history", { limit, offset });
+},
+/**
+ * Transcript segments containing `query` (case-insensitive for ASCII),
+ * newest session first.
+ */
+async searchHistory(query: string, limit: number | null) : Promise<HistoryMatch[]> {
+    return await TAURI_INVOKE("search_history", { query, limit });
+},
+/**
+ * Full-text search over saved transcripts, e.g. "when did we talk about
+ * pricing". Returns matching sessions, best first, with highlighted
+ * snippets and the time of each matching segment to jump to.
+ */
+async searchTranscripts(query: string, limit: number | null) : Promise<TranscriptSearchResult[]> {
+    return await TAURI_INVOKE("search_transcripts", { query, limit });
+},
+async getTranscriptFilters() : Promise<TranscriptFilters> {
+    return await TAURI_INVOKE("get_transcript_filters");
+},
+/**
+ * Save the filters and apply them to transcription from now on. Rejects
+ * invalid regexes.
+ */
+async saveTranscriptFilters(filters: TranscriptFilters) : Promise<TranscriptFilters> {
+    return await TAURI_INVOKE("save_transcript_filters", { filters });
+},
+/**
+ * Delete a session from the history and its folder from disk. The
+ * session being recorded can't be deleted.
+ */
+async deleteHistorySession(sessionId: string) : Promise<null> {
+    return await TAURI_INVOKE("delete_history_session", { sessionId });
+},
+/**
+ * Find Chrome instances launched for automation (by the agent's profile
+ * folder or debug port) and report them, close them, or keep the one the
+ * agent can reattach to and close the rest. Defaults to reporting.
+ */
+async cleanupBrowserSessions(action: BrowserCleanupAction | null) : Promise<BrowserCleanupReport> {
+    return await TAURI_INVOKE("cleanup_browser_sessions", { action });
+},
+async checkWhisper() : Promise<WhisperStatus> {
+    return await TAURI_INVOKE("check_whisper");
+},
+/**
+ * What acceleration this machine and build offer, and what's in use.
+ */
+async getTranscriptionCapabilities() : Promise<TranscriptionCapabilities> {
+    return await TAURI_INVOKE("get_transcription_capabilities");
+},
+/**
+ * Recent segments and per-model averages, to compare model sizes.
+ */
+async getTranscriptionMetrics() : Promise<TranscriptionMetricsReport> {
+    return await TAURI_INVOKE("get_transcription_metrics");
+},
+/**
+ * The main window wrote everything it had queued.
+ */
+async confirmShutdownFlushed() : Promise<void> {
+    await TAURI_INVOKE("confirm_shutdown_flushed");
+},
+async getWhisperWorkerStatus() : Promise<WhisperWorkerStatus> {
+    return await TAURI_INVOKE("get_whisper_worker_status");
+},
+async setupWhisper() : Promise<string> {
+    return await TAURI_INVOKE("setup_whisper");
+},
+async checkExcalidraw() : Promise<ExcalidrawStatus> {
+    return await TAURI_INVOKE("check_excalidraw");
+},
+async setupExcalidraw() : Promise<string> {
+    return await TAURI_INVOKE("setup_excalidraw");
+},
+async logFrontend(message: string) : Promise<void> {
+    await TAURI_INVOKE("log_frontend", { message });
+},
+async browserControl(action: string) : Promise<null> {
+    return await TAURI_INVOKE("browser_control", { action });
+},
+async listMcpServers() : Promise<McpServers> {
+    return await TAURI_INVOKE("list_mcp_servers");
+},
+async addMcpServer(server: McpServer) : Promise<McpServers> {
+    return await TAURI_INVOKE("add_mcp_server", { server });
+},
+/**
+ * Replace the server called `name`; a different `server.name` renames it.
+ */
+async updateMcpServer(name: string, server: McpServer) : Promise<McpServers> {
+    return await TAURI_INVOKE("update_mcp_server", { name, server });
+},
+async removeMcpServer(name: string) : Promise<McpServers> {
+    return await TAURI_INVOKE("remove_mcp_server", { name });
+},
+async setMcpServerEnabled(name: string, enabled: boolean) : Promise<McpServers> {
+    return await TAURI_INVOKE("set_mcp_server_enabled", { name, enabled });
+},
+/**
+ * Supervised processes, then the command servers the agent starts.
+ */
+async mcpServerStatus() : Promise<McpProcessStatus[]> {
+    return await TAURI_INVOKE("mcp_server_status");
+},
+async startMcpServer(name: string) : Promise<McpProcessStatus> {
+    return await TAURI_INVOKE("start_mcp_server", { name });
+},
+async stopMcpServer(name: string) : Promise<McpProcessStatus> {
+    return await TAURI_INVOKE("stop_mcp_server", { name });
+},
+/**
+ * Stop the server and start it again, clearing its crash count.
+ */
+async restartMcpServer(name: string) : Promise<McpProcessStatus> {
+    return await TAURI_INVOKE("restart_mcp_server", { name });
+},
+/**
+ * The raw MCP config, for bundles from before the typed server commands.
+ */
+async getMcpConfig() : Promise<McpConfigResponse> {
+    return await TAURI_INVOKE("get_mcp_config");
+},
+/**
+ * Save a whole MCP config from an older bundle's JSON editor. Only
+ * `mcpServers` is taken, and each server is validated like
+ * `add_mcp_server`.
+ */
+async saveMcpConfig(content: string) : Promise<McpConfigResponse> {
+    return await TAURI_INVOKE("save_mcp_config", { content });
+},
+/**
+ * Undo the last change to the MCP config, including automatic
+ * migrations, which then stay off until redone.
+ */
+async mcpConfigUndo() : Promise<McpConfigResponse> {
+    return await TAURI_INVOKE("mcp_config_undo");
+},
+async mcpConfigRedo() : Promise<McpConfigResponse> {
+    return await TAURI_INVOKE("mcp_config_redo");
+},
+async getMcpConfigHistory() : Promise<McpHistoryStatus> {
+    return await TAURI_INVOKE("get_mcp_config_history");
+},
+async getMcpServerHealth() : Promise<ServerHealth[]> {
+    return await TAURI_INVOKE("get_mcp_server_health");
+},
+/**
+ * Probe every server now, stdio ones included.
+ */
+async checkMcpServerHealth() : Promise<ServerHealth[]> {
+    return await TAURI_INVOKE("check_mcp_server_health");
+},
+async runLlmAgent(payload: LlmAgentRequest) : Promise<string> {
+    return await TAURI_INVOKE("run_llm_agent", { payload });
+},
+async testMcpConfig() : Promise<string> {
+    return await TAURI_INVOKE("test_mcp_config");
+},
+async cancelLlmAgent() : Promise<void> {
+    await TAURI_INVOKE("cancel_llm_agent");
+},
+async openBrowserWindow(url: string, newTab: boolean) : Promise<null> {
+    return await TAURI_INVOKE("open_browser_window", { url, newTab });
+},
+async focusChromeWindow() : Promise<null> {
+    return await TAURI_INVOKE("focus_chrome_window");
+},
+async reloadChromeTab(urlPrefix: string) : Promise<null> {
+    return await TAURI_INVOKE("reload_chrome_tab", { urlPrefix });
+},
+async focusChromeTab(urlPrefix: string) : Promise<null> {
+    return await TAURI_INVOKE("focus_chrome_tab", { urlPrefix });
+},
+async resetTestLog(runLabel: string | null) : Promise<string> {
+    return await TAURI_INVOKE("reset_test_log", { runLabel });
+},
+async appendTestLog(line: string) : Promise<null> {
+    return await TAURI_INVOKE("append_test_log", { line });
+},
+async fetchUrl(url: string) : Promise<string> {
+    return await TAURI_INVOKE("fetch_url", { url });
+},
+async getPersonasDir() : Promise<string> {
+    return await TAURI_INVOKE("get_personas_dir");
+},
+async showSettingsWindow() : Promise<null> {
+    return await TAURI_INVOKE("show_settings_window");
+},
+/**
+ * Old name of `show_settings_window`, kept for cached frontend bundles.
+ */
+async openSettingsWindowCommand() : Promise<null> {
+    return await TAURI_INVOKE("open_settings_window_command");
+},
+async setDevSettingsMenuVisible(visible: boolean) : Promise<null> {
+    return await TAURI_INVOKE("set_dev_settings_menu_visible", { visible });
+}
+}
+
+/** user-defined events **/
+
+
+export const events = __makeEvents__<{
+accountChanged: AccountChanged,
+agendaDue: AgendaDue,
+appControl: AppControl,
+appShutdown: AppShutdown,
+bookmarkCreated: BookmarkCreated,
+bookmarkJump: BookmarkJump,
+browserControl: BrowserControl,
+canvasBoards: CanvasBoards,
+clipboardSuggestion: ClipboardSuggestion,
+deviceChanged: DeviceChanged,
+dictation: Dictation,
+endpointFailover: EndpointFailover,
+fileTranscription: FileTranscription,
+guestMode: GuestMode,
+idlePause: IdlePause,
+jobChanged: JobChanged,
+loadPressure: LoadPressure,
+mcpProcessChanged: McpProcessChanged,
+mcpServerHealth: McpServerHealth,
+meetingDetected: MeetingDetected,
+micGate: MicGate,
+micLevel: MicLevel,
+modelDownloadProgress: ModelDownloadProgress,
+modelIntegrity: ModelIntegrity,
+powerState: PowerState,
+pttDown: PttDown,
+pttUp: PttUp,
+quickCapture: QuickCapture,
+recordedTranscript: RecordedTranscript,
+recordingLevel: RecordingLevel,
+recordingSegment: RecordingSegment,
+scenarioProgress: ScenarioProgress,
+sessionReport: SessionReport,
+staleBrowserSessions: StaleBrowserSessions,
+topicShift: TopicShift,
+transcriptionMetrics: TranscriptionMetrics,
+transcriptionPartial: TranscriptionPartial,
+webhookTriggered: WebhookTriggered
+}>({
+accountChanged: "account-changed",
+agendaDue: "agenda-due",
+appControl: "app-control",
+appShutdown: "app-shutdown",
+bookmarkCreated: "bookmark-created",
+bookmarkJump: "bookmark-jump",
+browserControl: "browser-control",
+canvasBoards: "canvas-boards",
+clipboardSuggestion: "clipboard-suggestion",
+deviceChanged: "device-changed",
+dictation: "dictation",
+endpointFailover: "endpoint-failover",
+fileTranscription: "file-transcription",
+guestMode: "guest-mode",
+idlePause: "idle-pause",
+jobChanged: "job-changed",
+loadPressure: "load-pressure",
+mcpProcessChanged: "mcp-process-changed",
+mcpServerHealth: "mcp-server-health",
+meetingDetected: "meeting-detected",
+micGate: "mic-gate",
+micLevel: "mic-level",
+modelDownloadProgress: "model-download-progress",
+modelIntegrity: "model-integrity",
+powerState: "power-state",
+pttDown: "ptt-down",
+pttUp: "ptt-up",
+quickCapture: "quick-capture",
+recordedTranscript: "recorded-transcript",
+recordingLevel: "recording-level",
+recordingSegment: "recording-segment",
+scenarioProgress: "scenario-progress",
+sessionReport: "session-report",
+staleBrowserSessions: "stale-browser-sessions",
+topicShift: "topic-shift",
+transcriptionMetrics: "transcription-metrics",
+transcriptionPartial: "transcription-partial",
+webhookTriggered: "webhook-triggered"
+})
+
+/** user-defined constants **/
+
+
+
+/** user-defined types **/
+
+/**
+ * Emitted when a device flow finishes (either way) or an account is
+ * disconnected.
+ */
+export type AccountChanged = { provider: string; connected: boolean; error: string | null }
+export type AccountStatus = { provider: string; name: string; configured: boolean; connected: boolean; scopes: string[]; expiresAt: number | null }
+/**
+ * Payload of the `agenda-due` event: the webview should run this item.
+ */
+export type AgendaDue = { id: string; task: string }
+export type AgendaItem = { id: string; task: string; createdAt: number; 
+/**
+ * When the result is needed; earlier deadlines run first.
+ */
+dueBy: number | null; status: AgendaStatus; attempts: number; 
+/**
+ * Not retried before this, after a failure.
+ */
+notBefore: number | null; lastError: string | null; result: string | null; completedAt: number | null; 
+/**
+ * Session whose prep material holds the result.
+ */
+sessionId: string | null }
+export type AgendaStatus = "pending" | "running" | "done" | "failed"
+export type AgendaStatusSummary = { pending: number; running: number; done: number; failed: number; 
+/**
+ * Whether the app counts as idle right now.
+ */
+idle: boolean; 
+/**
+ * Why it doesn't, when it doesn't.
+ */
+busyReason: string | null }
+/**
+ * Agent modes defined by the user, recognized by `run_llm_agent` next to
+ * the built-in ones.
+ */
+export type AgentModeSettings = { custom: CustomAgentMode[] }
+/**
+ * Trends across the sessions started within a range.
+ */
+export type Analytics = { sessions: number; totalDurationMs: number; 
+/**
+ * Oldest week first; weeks without sessions are left out.
+ */
+weeks: WeeklyTalkTime[]; 
+/**
+ * Longest-discussed first.
+ */
+topTopics: TopicTotal[]; 
+/**
+ * Most-used first.
+ */
+agentModes: ModeUsage[]; 
+/**
+ * Across all agent runs; `None` when there were none.
+ */
+avgAgentLatencyMs: number | null }
+/**
+ * How far back `get_analytics` looks, from now.
+ */
+export type AnalyticsRange = "week" | "month" | "quarter" | "year" | "all"
+export type ApiHandshake = { backendVersion: number; minSupportedVersion: number; 
+/**
+ * Version the backend will speak to this webview.
+ */
+negotiatedVersion: number; 
+/**
+ * The frontend is older than anything the backend still serves and
+ * should reload to pick up the bundled assets.
+ */
+reloadRequired: boolean; deprecatedCommands: DeprecatedCommand[] }
+/**
+ * Something the user asked HeyJamie itself to do, handled without an LLM.
+ */
+export type AppAction = { kind: "openSettings" } | { kind: "showCanvas" } | { kind: "showMain" } | 
+/**
+ * Spoken persona name; the frontend resolves it. Empty clears it.
+ */
+{ kind: "switchPersona"; persona: string } | 
+/**
+ * Stop volunteering suggestions; direct commands still run.
+ */
+{ kind: "mute" } | { kind: "unmute" } | 
+/**
+ * Tape is rolling: hold anything visible until the segment ends.
+ */
+{ kind: "startRecordingSegment" } | { kind: "endRecordingSegment" }
+/**
+ * Sent to the main window for actions it owns (persona, mute, canvas).
+ */
+export type AppControl = { action: AppAction }
+/**
+ * Backend settings persisted as `settings.json` next to `mcp.json` in the
+ * app config dir. Frontend-only preferences still live in `localStorage`;
+ * this store is for values the Rust layer needs without a webview round trip.
+ */
+export type AppSettings = { transcription: TranscriptionSettings; llm: LlmSettings; auth: AuthSettings; email: EmailSettings; captions: CaptionSettings; language: LanguageSettings; docs: DocsCacheSettings; ui: UiSettings; power: PowerSettings; load: LoadSettings; timecode: TimecodeSettings; postSession: PostSessionSettings; sessionAudio: SessionAudioSettings; prompts: PromptSettings; clipboard: ClipboardSettings; meeting: MeetingSettings; fetch: FetchSettings; agentModes: AgentModeSettings; pushToTalk: PushToTalkSettings; webhooks: WebhookSettings; sandbox: SandboxSettings; idlePause: IdlePauseSettings; scripting: ScriptingSettings; redaction: RedactionSettings; dictation: DictationSettings }
+/**
+ * Emitted to the main window when the app is quitting. The window stops
+ * listening, waits for its transcription queue and then calls
+ * `confirm_shutdown_flushed`.
+ */
+export type AppShutdown = null
+/**
+ * One config rewrite performed on this machine, appended to
+ * `migrations.json` next to the migrated file.
+ */
+export type AppliedMigration = { id: string; description: string; file?: string; appVersion?: string; appliedAt?: number }
+export type AudioDevice = { name: string; 
+/**
+ * The OS default input.
+ */
+isDefault: boolean; 
+/**
+ * The device in `transcription.inputDevice`.
+ */
+selected: boolean; channels: number | null; sampleRate: number | null }
+export type AudioFilters = { enabled: boolean; 
+/**
+ * Cutoff of the rumble filter; unset turns it off.
+ */
+highPassHz: number | null; 
+/**
+ * Pull steady background noise down between words.
+ */
+noiseSuppression: boolean; 
+/**
+ * Raise quiet speech to a consistent level.
+ */
+normalize: boolean }
+export type AudioSegment = { 
+/**
+ * From the session start; also the file name.
+ */
+offsetMs: number; durationMs: number; bytes: number }
+export type AuthSettings = { 
+/**
+ * OAuth device-flow providers. Entries with the id of a built-in
+ * provider (`google`, `github`) override it, e.g. to set a client id.
+ */
+providers: OAuthProviderConfig[] }
+export type BackgroundActivity = { 
+/**
+ * Background jobs are holding off.
+ */
+paused: boolean; agentRuns: number; transcriptions: number }
+/**
+ * A saved moment in a session. Offsets and the excerpt point into the
+ * session transcript; `canvas_snapshot` is relative to the session dir.
+ */
+export type Bookmark = { id: string; sessionId: string; label: string | null; createdAt: number; offsetMs: number; segmentIndex: number | null; transcriptExcerpt: string | null; browserUrl: string | null; canvasSnapshot: string | null; source: BookmarkSource }
+/**
+ * Emitted when a bookmark is saved, including from a voice trigger.
+ */
+export type BookmarkCreated = Bookmark
+/**
+ * Emitted for "Jamie, jump back"; the frontend restores the referenced
+ * browser page and scrolls the transcript.
+ */
+export type BookmarkJump = Bookmark
+export type BookmarkSource = "manual" | "voice" | "webhook" | "script"
+export type BrowserCleanupAction = 
+/**
+ * Only list what was found.
+ */
+"report" | 
+/**
+ * Close every leftover automation browser.
+ */
+"close" | 
+/**
+ * Keep the one the agent can reuse and close the rest.
+ */
+"reattach"
+export type BrowserCleanupReport = { sessions: BrowserSession[]; closed: number[]; 
+/**
+ * The session kept for the agent to reuse.
+ */
+reattached: number | null }
+/**
+ * Payload of the `browser-control` event forwarded to the main window.
+ */
+export type BrowserControl = string
+/**
+ * A Chrome browser process left over from automation.
+ */
+export type BrowserSession = { pid: number; debugPort: number | null; userDataDir: string | null; 
+/**
+ * Listening on the port the agent connects to, so the next run can
+ * reuse it instead of launching another.
+ */
+reattachable: boolean }
+export type CanvasBoard = { name: string; 
+/**
+ * As of the last switch away from it; the active board is live.
+ */
+elementCount: number; updatedAt: number | null }
+/**
+ * The boards of the current session. Emitted as `canvas-boards` after a
+ * switch so the canvas window can follow `url`.
+ */
+export type CanvasBoards = { active: string; 
+/**
+ * Canvas URL for the active board.
+ */
+url: string; boards: CanvasBoard[] }
+/**
+ * Ties a canvas element to the transcript segment that prompted it.
+ */
+export type CanvasLink = { elementId: string; sessionId: string; segmentIndex: number | null; offsetMs: number; transcriptExcerpt: string | null; source: CanvasLinkSource; createdAt: number }
+export type CanvasLinkSource = "agent" | "direct"
+/**
+ * One saved board, listed in `<session>/canvas-snapshots.json`.
+ */
+export type CanvasSnapshot = { 
+/**
+ * The topic being left when the board was saved.
+ */
+topic: string; board: string; offsetMs: number; takenAt: number; elementCount: number; scenePath: string; 
+/**
+ * Missing when the canvas server couldn't render the board.
+ */
+imagePath: string | null; 
+/**
+ * Hash of the elements; an unchanged board isn't saved twice.
+ */
+sceneHash: string }
+export type CaptionFormat = "srt" | "vtt"
+export type CaptionSettings = { 
+/**
+ * Keep a caption file for the active session up to date as segments
+ * arrive.
+ */
+liveOutput: boolean; 
+/**
+ * A `.srt`/`.vtt` file, or a folder that gets `<session id>.<ext>`.
+ */
+outputPath: string; 
+/**
+ * Used when `output_path` is a folder.
+ */
+format: CaptionFormat }
+export type CaptureSource = 
+/**
+ * The default microphone.
+ */
+"microphone" | 
+/**
+ * Only what's playing through the speakers.
+ */
+"system" | 
+/**
+ * The microphone and system audio mixed, for calls on headphones.
+ */
+"both"
+/**
+ * Pipeline actions the main window can offer for copied content.
+ */
+export type ClipboardAction = 
+/**
+ * Open the URL in the browser.
+ */
+"open" | 
+/**
+ * Summarize the article or text.
+ */
+"summarize" | 
+/**
+ * Draw the text on the Excalidraw canvas.
+ */
+"canvas"
+export type ClipboardKind = "url" | "text"
+/**
+ * Opt-in clipboard watcher that offers actions for copied content.
+ */
+export type ClipboardSettings = { enabled: boolean; 
+/**
+ * Suggest opening or summarizing copied links.
+ */
+urls: boolean; 
+/**
+ * Suggest summarizing or drawing copied text of at least
+ * `min_text_chars`.
+ */
+text: boolean; minTextChars: number }
+/**
+ * Emitted to the main window when something worth acting on is copied.
+ * The content itself is never logged or stored.
+ */
+export type ClipboardSuggestion = { kind: ClipboardKind; content: string; preview: string; actions: ClipboardAction[] }
+/**
+ * Cloud speech-to-text. API keys live in the keychain
+ * (`set_transcription_api_key`).
+ */
+export type CloudTranscriptionSettings = { 
+/**
+ * Empty means `whisper-1`.
+ */
+openaiModel: string; 
+/**
+ * Empty means `nova-2`.
+ */
+deepgramModel: string; timeoutMs: number; 
+/**
+ * Transcribe locally when the cloud request fails.
+ */
+fallbackToLocal: boolean }
+export type CustomAgentMode = { 
+/**
+ * Passed as `mode` to `run_llm_agent`.
+ */
+name: string; 
+/**
+ * Falls back to the general agent timeout.
+ */
+timeoutMs: number | null; 
+/**
+ * `{{instructions}}` (the caller's), `{{prompt}}` and `{{context}}`
+ * are filled in.
+ */
+instructions: string; 
+/**
+ * Tool name patterns (`*` matches anything) the mode may call; none
+ * means no tools.
+ */
+allowedTools: string[]; 
+/**
+ * JSON Schema the reply must follow; the parsed reply is returned as
+ * `output`.
+ */
+outputSchema: JsonValue | null }
+/**
+ * A command name kept as an alias for an older frontend bundle.
+ */
+export type DeprecatedCommand = { command: string; replacement: string; deprecatedIn: number; 
+/**
+ * First API version that no longer registers the alias.
+ */
+removedIn: number }
+export type DetailedSegment = { startMs: number; endMs: number; text: string; 
+/**
+ * Mean natural log of the token probabilities; closer to 0 is surer.
+ */
+avgLogprob: number; 
+/**
+ * Only reported by some whisper builds.
+ */
+noSpeechProb: number | null; 
+/**
+ * For highlighting low-confidence words.
+ */
+tokens: TokenConfidence[]; 
+/**
+ * For highlighting each word as the audio plays.
+ */
+words: WordTiming[] }
+export type DetailedTranscript = { 
+/**
+ * The same text `transcribe_audio` returns.
+ */
+text: string; segments: DetailedSegment[] }
+/**
+ * Spoken language of a clip as guessed by whisper.
+ */
+export type DetectedLanguage = { 
+/**
+ * Whisper language code, e.g. `es`.
+ */
+code: string; probability: number | null }
+export type DeviceAuthorization = { provider: string; 
+/**
+ * Code the user types at `verification_uri`.
+ */
+userCode: string; verificationUri: string; verificationUriComplete: string | null; expiresIn: number }
+/**
+ * Emitted as `device-changed` when an input appears or disappears or the
+ * OS default switches.
+ */
+export type DeviceChanged = { defaultDevice: string | null; devices: string[]; 
+/**
+ * The chosen device, if one is set and it's connected. Recording
+ * falls back to the default while it isn't.
+ */
+selected: string | null }
+/**
+ * Dictation was turned on or off.
+ */
+export type Dictation = { active: boolean }
+/**
+ * Typing transcripts into the focused app (`dictation.rs`).
+ */
+export type DictationSettings = { enabled: boolean; 
+/**
+ * Accelerator that toggles dictation, e.g. `CmdOrCtrl+Alt+D`.
+ */
+shortcut: string }
+export type DictationStatus = { enabled: boolean; shortcut: string; 
+/**
+ * Segments are being typed.
+ */
+active: boolean; 
+/**
+ * The shortcut is registered with the OS.
+ */
+registered: boolean; 
+/**
+ * Why registering failed, e.g. another app owns the shortcut.
+ */
+error: string | null }
+/**
+ * Context7 documentation cache used by the agent.
+ */
+export type DocsCacheSettings = { 
+/**
+ * How long a cached lookup is served without asking the server again.
+ */
+ttlHours: number; 
+/**
+ * Never contact the Context7 server; answer only from the cache.
+ */
+offline: boolean }
+export type DocsCacheStats = { entries: number; bytes: number; libraries: string[] }
+/**
+ * SMTP account for session digests. The password lives in the keychain
+ * (`set_smtp_password`), never in this file.
+ */
+export type EmailSettings = { smtpHost: string; 
+/**
+ * 465 uses implicit TLS; anything else upgrades with STARTTLS.
+ */
+smtpPort: number; username: string; 
+/**
+ * Sender address; defaults to `username`.
+ */
+from: string | null; defaultRecipients: string[]; 
+/**
+ * Mail the digest to `default_recipients` when a session ends.
+ */
+sendOnSessionEnd: boolean }
+/**
+ * Payload of the `endpoint-failover` event.
+ */
+export type EndpointFailover = { service: FailoverService; from: string | null; to: string; reason: string | null; 
+/**
+ * Trace of the request that caused the switch, if any.
+ */
+traceId: string | null }
+export type EndpointHealth = { url: string; healthy: boolean; consecutiveFailures: number; lastError: string | null }
+export type ExcalidrawStatus = { dirFound: boolean; indexJsFound: boolean; serverJsFound: boolean; installPath: string | null }
+export type ExportResult = { target: ExportTarget; 
+/**
+ * File path or document URL.
+ */
+location: string }
+export type ExportTarget = 
+/**
+ * `show-notes.md` in the session's `exports/` folder.
+ */
+"markdown" | 
+/**
+ * Show notes as a new Google Doc.
+ */
+"google-docs" | 
+/**
+ * Research tables as a new Google Sheet (one tab per table).
+ */
+"google-sheets"
+export type FailoverService = "transcription" | "llm"
+/**
+ * Politeness toward the sites research fetches from, so a busy session
+ * doesn't get the user's IP blocked.
+ */
+export type FetchSettings = { 
+/**
+ * Minimum gap between requests to one domain; a longer robots.txt
+ * `Crawl-delay` wins.
+ */
+minIntervalMs: number; 
+/**
+ * Requests to one domain in flight at once.
+ */
+maxConcurrentPerDomain: number; 
+/**
+ * Skip pages robots.txt disallows for automated fetches.
+ */
+respectRobots: boolean }
+/**
+ * Emitted to the main window as each queued file starts and finishes.
+ */
+export type FileTranscription = { path: string; index: number; total: number; status: FileTranscriptionStatus; 
+/**
+ * `<name>.transcript.txt` next to the audio, once done.
+ */
+transcriptPath: string | null; error: string | null }
+export type FileTranscriptionStatus = "running" | "done" | "failed" | 
+/**
+ * Never started because the batch was cancelled.
+ */
+"skipped"
+/**
+ * One set of blocklists. Words and phrases are compared lowercased, with
+ * brackets and punctuation stripped (`[BLANK_AUDIO]` is `blank_audio`).
+ */
+export type FilterRules = { 
+/**
+ * Whole fragments to drop.
+ */
+phrases: string[]; 
+/**
+ * Fragments made only of these words are dropped, however long.
+ */
+noiseWords: string[]; 
+/**
+ * Fragments of at most `maxFillerWords` words, all from this list,
+ * are dropped.
+ */
+fillerWords: string[]; 
+/**
+ * Case-insensitive regexes; a fragment matching any is dropped.
+ */
+patterns: string[] }
+/**
+ * Mic gating by application focus (see `focus_gate`).
+ */
+export type FocusGateSettings = { enabled: boolean; 
+/**
+ * Substrings of app names that keep the microphone on, e.g.
+ * "Riverside" or "Chrome". HeyJamie itself always does.
+ */
+apps: string[] }
+/**
+ * Emitted to every window when guest mode starts or ends so they reload
+ * what they show.
+ */
+export type GuestMode = { active: boolean }
+export type HeldKind = 
+/**
+ * A page Jamie would have opened in the browser.
+ */
+"url" | 
+/**
+ * A window Jamie would have shown (settings, main, canvas).
+ */
+"window" | 
+/**
+ * An agent run that drives the visible browser or canvas.
+ */
+"agent" | 
+/**
+ * A spoken direct command, kept to run after the segment.
+ */
+"command"
+/**
+ * Something Jamie held back while tape was rolling.
+ */
+export type HeldResponse = { kind: HeldKind; detail: string; heldAt: number; 
+/**
+ * Trace of the interaction that was held, to match it up in the log.
+ */
+traceId: string | null }
+export type HistoryMatch = { sessionId: string; title: string | null; startedAt: number; index: number; offsetMs: number; text: string }
+export type HistorySession = { id: string; title: string | null; startedAt: number; endedAt: number | null; segmentCount: number; 
+/**
+ * First words of the transcript.
+ */
+preview: string }
+/**
+ * Emitted to the main window as `idle-pause` when listening should pause.
+ */
+export type IdlePause = { idleMinutes: number }
+/**
+ * Pausing a session nobody is using (`idle_pause.rs`).
+ */
+export type IdlePauseSettings = { enabled: boolean; 
+/**
+ * Minutes without transcribed speech or user input before listening
+ * is paused.
+ */
+idleMinutes: number }
+/**
+ * Filters for one input: an input track name, or a device name when
+ * recording natively.
+ */
+export type InputFilters = { input: string; filters: AudioFilters }
+/**
+ * One input device in a multi-track recording.
+ */
+export type InputTrack = { 
+/**
+ * Speaker label attached to this track's transcript segments.
+ */
+name: string; 
+/**
+ * Webview `MediaDeviceInfo.deviceId`; empty means the default input.
+ */
+deviceId: string }
+export type IntegrityState = 
+/**
+ * Failed verification and was moved aside; a re-download started.
+ */
+"corrupt" | "repaired" | "repairFailed"
+export type Job = { id: string; spec: JobSpec; status: JobStatus; 
+/**
+ * How far the checkpoint got, 0–1, when known.
+ */
+progress: number | null; error: string | null; startedAt: number; updatedAt: number }
+/**
+ * Emitted as `job-changed` when a job starts, fails or finishes.
+ */
+export type JobChanged = { id: string; 
+/**
+ * The job as it is now; `None` once it finished or was discarded.
+ */
+job: Job | null }
+/**
+ * What a job does; starting the same spec again resumes it.
+ */
+export type JobSpec = { kind: "modelDownload"; name: string } | { kind: "fileTranscription"; path: string } | { kind: "sessionRetranscription"; sessionId: string; model: string }
+export type JobStatus = "running" | "failed" | 
+/**
+ * Was running when the app quit or crashed.
+ */
+"interrupted"
+export type JsonValue = null | boolean | number | string | JsonValue[] | Partial<{ [key in string]: JsonValue }>
+/**
+ * Which language the agent replies in. Values are `auto` (follow the
+ * speaker), `off`, or a language code such as `es`.
+ */
+export type LanguageSettings = { responseLanguage: string; 
+/**
+ * Per-show overrides, matched against the start of the session title.
+ */
+shows: ShowLanguage[] }
+export type Liveness = "alive" | "down"
+export type LlmAgentRequest = { settings: LlmAgentSettings; instructions: string; prompt: string; mode?: string | null; context?: JsonValue | null; 
+/**
+ * Set by the webview so every run for one interaction shares a trace.
+ */
+traceId?: string | null }
+export type LlmAgentSettings = { apiKey: string; model: string; reasoning: boolean }
+export type LlmEndpoint = { name: string; 
+/**
+ * OpenAI-compatible API base, e.g. `https://openrouter.ai/api/v1` or
+ * `http://localhost:11434/v1` for a local Ollama.
+ */
+baseUrl: string; 
+/**
+ * Overrides the API key from the frontend settings for this endpoint.
+ */
+apiKey: string | null; 
+/**
+ * Overrides the model from the frontend settings for this endpoint.
+ */
+model: string | null; 
+/**
+ * Path probed by the background health check (defaults to `/models`).
+ */
+healthPath: string | null }
+export type LlmSettings = { 
+/**
+ * Ordered failover chain for the agent's chat completions provider.
+ * Empty means the agent talks to OpenRouter directly.
+ */
+endpoints: LlmEndpoint[] }
+/**
+ * Emitted as `load-pressure` when transcription backs off or recovers.
+ */
+export type LoadPressure = { pressured: boolean; 
+/**
+ * One-minute load average divided by the number of cores.
+ */
+loadPerCore: number | null; 
+/**
+ * macOS CPU speed limit in percent; below 100 means thermal throttling.
+ */
+cpuSpeedLimit: number | null; reason: string | null; 
+/**
+ * Seconds of audio per transcription segment; `None` keeps the default.
+ */
+segmentSeconds: number | null; 
+/**
+ * Whisper model file to use instead of the default one.
+ */
+whisperModel: string | null }
+/**
+ * Transcription back-off while the machine is busy or thermally throttled.
+ */
+export type LoadSettings = { enabled: boolean; 
+/**
+ * One-minute load average per core that counts as pressure.
+ */
+loadPerCore: number; 
+/**
+ * Whisper model file, next to the default model, used under pressure.
+ */
+whisperModel: string; 
+/**
+ * Seconds of audio per transcription segment under pressure. 0 keeps
+ * the default.
+ */
+segmentSeconds: number }
+/**
+ * Long recordings are transcribed in overlapping windows whose
+ * transcripts are stitched at the shared words.
+ */
+export type LongFormSettings = { windowSeconds: number; 
+/**
+ * Audio each window shares with the next one.
+ */
+overlapSeconds: number }
+export type McpConfigResponse = { path: string; content: string }
+/**
+ * What undo and redo would do, for the editor's buttons.
+ */
+export type McpHistoryStatus = { canUndo: boolean; canRedo: boolean; 
+/**
+ * The change undo reverts, e.g. "automatic migration".
+ */
+undoLabel: string | null; redoLabel: string | null }
+/**
+ * A process HeyJamie keeps running for a URL server (see
+ * `mcp_supervisor`).
+ */
+export type McpLaunch = { command: string; args: string[]; cwd: string | null; env: Partial<{ [key in string]: string }> }
+/**
+ * Emitted as `mcp-process-changed` whenever a supervised process starts,
+ * exits or is stopped.
+ */
+export type McpProcessChanged = { process: McpProcessStatus }
+export type McpProcessState = "running" | 
+/**
+ * Starting, or exited on its own and starting again shortly.
+ */
+"restarting" | 
+/**
+ * Stopped by hand, or turned off in the config.
+ */
+"stopped" | 
+/**
+ * Crashed too often and was left stopped.
+ */
+"crashed" | 
+/**
+ * Could not be started, e.g. its files are missing.
+ */
+"failed" | 
+/**
+ * A command server the agent starts for each run.
+ */
+"agent"
+export type McpProcessStatus = { name: string; state: McpProcessState; pid: number | null; 
+/**
+ * Automatic restarts since it was last started by hand.
+ */
+restarts: number; startedAt: number | null; 
+/**
+ * How the last run ended, or why it couldn't start.
+ */
+lastError: string | null }
+export type McpServer = { 
+/**
+ * The key under `mcpServers`; tool names are prefixed with it.
+ */
+name: string; enabled: boolean; 
+/**
+ * Program to start for a stdio server.
+ */
+command: string | null; args: string[]; 
+/**
+ * Working directory; `~/` is expanded when the server starts.
+ */
+cwd: string | null; env: Partial<{ [key in string]: string }>; 
+/**
+ * Endpoint of a remote server, instead of `command`.
+ */
+url: string | null; 
+/**
+ * How to talk to `url`; HTTP when unset.
+ */
+transport: McpTransport | null; 
+/**
+ * Started and kept running by HeyJamie so `url` answers.
+ */
+launch: McpLaunch | null; 
+/**
+ * Only these tools are offered to the agent.
+ */
+allowTools: string[] | null; 
+/**
+ * These tools are hidden from the agent.
+ */
+denyTools: string[] | null }
+/**
+ * Payload of the `mcp-server-health` event, sent when a server goes up
+ * or down.
+ */
+export type McpServerHealth = { server: ServerHealth }
+export type McpServers = { path: string; servers: McpServer[] }
+export type McpTransport = "http" | "sse"
+/**
+ * Emitted to the main window when a conferencing app starts or stops
+ * using the microphone.
+ */
+export type MeetingDetected = { 
+/**
+ * The app in the meeting; `None` once it has ended.
+ */
+app: string | null; 
+/**
+ * Start listening without asking.
+ */
+autoStart: boolean; 
+/**
+ * Persona to switch to when auto-starting; empty keeps the current one.
+ */
+personaId: string }
+/**
+ * Noticing when a conferencing app takes the microphone.
+ */
+export type MeetingSettings = { enabled: boolean; 
+/**
+ * Start listening as soon as a meeting is detected instead of asking.
+ */
+autoStart: boolean; 
+/**
+ * Persona switched to on auto-start; empty keeps the current one.
+ */
+personaId: string }
+/**
+ * Emitted to the main window as `mic-gate` when the gate opens or closes.
+ */
+export type MicGate = { 
+/**
+ * Microphone audio is processed.
+ */
+open: boolean; 
+/**
+ * Name of the frontmost app, when known.
+ */
+frontmost: string | null }
+/**
+ * Emitted to the main window as `mic-level`.
+ */
+export type MicLevel = { 
+/**
+ * RMS since the previous event, 0–1.
+ */
+rms: number; 
+/**
+ * Largest sample magnitude since the previous event, 0–1.
+ */
+peak: number; 
+/**
+ * A sample clipped in the last couple of seconds.
+ */
+clipping: boolean; 
+/**
+ * Nothing above the noise floor for several seconds.
+ */
+silent: boolean }
+export type ModeRuns = { mode: string; runs: number; failures: number }
+export type ModeUsage = { mode: string; runs: number; failures: number; avgLatencyMs: number }
+export type ModelBenchmark = { 
+/**
+ * Model file, e.g. `ggml-small.en.bin`.
+ */
+model: string; sizeMb: number; 
+/**
+ * Decoding time, without loading the model.
+ */
+decodeMs: number; modelLoadMs: number | null; totalMs: number; 
+/**
+ * Decoding time over audio length; below 1 is faster than real time.
+ */
+realTimeFactor: number; text: string; error: string | null }
+export type ModelCheck = { fileName: string; path: string; state: ModelCheckState; 
+/**
+ * What is wrong with a damaged model.
+ */
+detail: string | null; 
+/**
+ * A catalog model HeyJamie can download again.
+ */
+repairable: boolean; 
+/**
+ * Moved aside and re-downloading (only when repair was asked for).
+ */
+repairing: boolean }
+export type ModelCheckState = 
+/**
+ * Matches its checksum.
+ */
+"ok" | 
+/**
+ * Looks whole, but there is no checksum to compare against.
+ */
+"unverified" | 
+/**
+ * Truncated, not a ggml file, or fails its checksum.
+ */
+"damaged"
+/**
+ * Emitted as `model-download-progress` to every window while a model
+ * downloads; the model manager lives in the settings window.
+ */
+export type ModelDownloadProgress = { name: string; downloadedBytes: number; totalBytes: number | null; done: boolean }
+/**
+ * Emitted as `model-integrity` when a model fails its checksum and again
+ * when the automatic re-download finishes.
+ */
+export type ModelIntegrity = { fileName: string; state: IntegrityState; detail: string }
+/**
+ * Averages for one backend and model.
+ */
+export type ModelMetrics = { backend: string; model: string | null; segments: number; avgRealTimeFactor: number; avgDecodeMs: number; avgTotalMs: number; 
+/**
+ * Only runs that had to load the model.
+ */
+avgModelLoadMs: number | null }
+export type OAuthProviderConfig = { id: string; name: string; clientId: string; 
+/**
+ * Only for providers that require one for installed apps (Google).
+ */
+clientSecret: string | null; deviceAuthorizationUrl: string; tokenUrl: string; revokeUrl: string | null; scopes: string[] }
+/**
+ * Outcome of one pipeline run, saved as `<session>/pipeline.json`.
+ */
+export type PipelineRun = { sessionId: string; finishedAt: number; steps: StepResult[] }
+export type PipelineStep = "finalize-transcript" | "retranscribe" | "show-notes" | "chapters" | "slack"
+/**
+ * Wrap-up steps run in order after a session ends. Each step is
+ * independent: a failure is recorded and the next step still runs.
+ */
+export type PostSessionSettings = { enabled: boolean; 
+/**
+ * Write `transcript.txt` with timestamps and track names.
+ */
+finalizeTranscript: boolean; 
+/**
+ * Keep each segment's audio during the session and run it through
+ * `retranscribe_model` afterwards (`transcript-hq.txt`).
+ */
+retranscribe: boolean; 
+/**
+ * Model file next to the default one, e.g. `ggml-large-v3.bin`.
+ */
+retranscribeModel: string; showNotes: boolean; 
+/**
+ * YouTube-style `chapters.txt` from the topic graph.
+ */
+chapters: boolean; 
+/**
+ * Post the notes to the Slack incoming webhook in the keychain.
+ */
+deliverToSlack: boolean }
+/**
+ * What changes while running on battery.
+ */
+export type PowerSettings = { 
+/**
+ * Switch to the low-power profile automatically when unplugged.
+ */
+enabled: boolean; 
+/**
+ * Whisper model file, next to the default model, used on battery.
+ * Falls back to the default model when it isn't installed.
+ */
+batteryWhisperModel: string; 
+/**
+ * Seconds of audio per transcription segment on battery. 0 keeps the
+ * default.
+ */
+batterySegmentSeconds: number; 
+/**
+ * Stop probing failover endpoints in the background on battery.
+ */
+pauseHealthChecks: boolean }
+export type PowerSource = "ac" | "battery" | 
+/**
+ * No battery found (desktops) or the platform can't tell.
+ */
+"unknown"
+/**
+ * Current power source and the workload policy derived from it. Emitted as
+ * `power-state` whenever either changes.
+ */
+export type PowerState = { source: PowerSource; batteryPercent: number | null; lowPower: boolean; 
+/**
+ * Seconds of audio per transcription segment; `None` keeps the default.
+ */
+segmentSeconds: number | null; 
+/**
+ * Whisper model file to use instead of the default one.
+ */
+whisperModel: string | null; healthChecksPaused: boolean }
+export type PreflightCheck = { resource: PreflightResource; requiredBytes: number; 
+/**
+ * `None` when the platform doesn't report it; the check then passes.
+ */
+availableBytes: number | null; 
+/**
+ * Volume checked for disk requirements.
+ */
+path: string | null; ok: boolean }
+/**
+ * Work that needs a resource check before it starts.
+ */
+export type PreflightOperation = "recording" | "model-download" | "retranscription"
+export type PreflightReport = { operation: PreflightOperation; ok: boolean; checks: PreflightCheck[]; suggestions: string[] }
+export type PreflightResource = "disk" | "memory"
+/**
+ * Audio clean-up before transcription (see `preprocess`).
+ */
+export type PreprocessingSettings = { 
+/**
+ * For inputs without their own entry.
+ */
+default: AudioFilters; inputs: InputFilters[] }
+export type ProbeKind = "http" | "stdio" | "tcp"
+/**
+ * Prompt templates for `run_prompt_on_text`, on top of the built-in ones.
+ */
+export type PromptSettings = { 
+/**
+ * A saved prompt replaces a built-in one with the same name.
+ */
+saved: SavedPrompt[] }
+/**
+ * The push-to-talk key went down. `recording` is set when the backend
+ * started native recording for it.
+ */
+export type PttDown = { recording: boolean }
+/**
+ * The push-to-talk key was released.
+ */
+export type PttUp = { recording: boolean }
+/**
+ * Punctuation restoring (`punctuation.rs`).
+ */
+export type PunctuationSettings = { 
+/**
+ * Program and arguments of a local punctuation model that reads text
+ * on stdin and prints it punctuated. Empty uses the built-in rules.
+ */
+modelCommand: string[]; timeoutMs: number }
+/**
+ * A global shortcut that listens while it's held.
+ */
+export type PushToTalkSettings = { enabled: boolean; 
+/**
+ * Accelerator string, e.g. `CmdOrCtrl+Shift+Space` or `F13`.
+ */
+shortcut: string; 
+/**
+ * Start and stop native recording from the backend instead of
+ * leaving it to the webview, so it works while the window is hidden.
+ */
+nativeRecording: boolean }
+export type PushToTalkStatus = { enabled: boolean; shortcut: string; nativeRecording: boolean; 
+/**
+ * The shortcut is registered with the OS.
+ */
+registered: boolean; 
+/**
+ * Why registering failed, e.g. another app owns the shortcut.
+ */
+error: string | null }
+/**
+ * Sent to the main webview, which owns the microphone, to record one
+ * utterance and hand it to the agent as a direct command.
+ */
+export type QuickCapture = { phase: QuickCapturePhase }
+export type QuickCapturePhase = "start" | "stop"
+/**
+ * Emitted to the main window for every segment of a native recording,
+ * in capture order.
+ */
+export type RecordedTranscript = { index: number; text: string; error: string | null }
+export type RecordingInfo = { 
+/**
+ * Device names, joined with " + " when system audio is mixed in.
+ */
+device: string; sampleRate: number; source: CaptureSource }
+/**
+ * Input RMS for the level meter, sent about ten times a second.
+ */
+export type RecordingLevel = number
+/**
+ * Emitted as `recording-segment` whenever a segment starts or ends or
+ * something is held.
+ */
+export type RecordingSegment = { active: boolean; held: HeldResponse[] }
+/**
+ * Masking sensitive text in transcripts (`redaction.rs`). Off by default;
+ * once on, every built-in category is masked unless turned off.
+ */
+export type RedactionSettings = { enabled: boolean; profanity: boolean; emails: boolean; phoneNumbers: boolean; 
+/**
+ * 13 to 19 digit numbers, grouped or not.
+ */
+cardNumbers: boolean; 
+/**
+ * Extra words or phrases to mask, ignoring case.
+ */
+words: string[]; 
+/**
+ * Extra case-insensitive regexes to mask.
+ */
+patterns: string[]; 
+/**
+ * What masked text is replaced with.
+ */
+mask: string }
+export type ReleaseEntry = { version: string; date: string; title: string; highlights?: string[]; configChanges?: string[] }
+export type ReleaseNotesResponse = { currentVersion: string; sinceVersion: string | null; releases: ReleaseEntry[]; migrations: AppliedMigration[] }
+export type RemoteWhisperKind = 
+/**
+ * whisper.cpp `whisper-server` (`POST /inference`).
+ */
+"whisper-cpp" | 
+/**
+ * faster-whisper and other OpenAI-compatible servers
+ * (`POST /v1/audio/transcriptions`).
+ */
+"openai-compatible"
+export type RemoteWhisperServer = { enabled: boolean; url: string; kind: RemoteWhisperKind; 
+/**
+ * Model name sent to OpenAI-compatible servers (ignored by whisper.cpp).
+ */
+model: string | null; apiKey: string | null; timeoutMs: number; 
+/**
+ * Additional attempts after the first request fails.
+ */
+retries: number; 
+/**
+ * Run the local CLI when every remote attempt fails.
+ */
+fallbackToLocal: boolean; 
+/**
+ * Path probed by the background health check (defaults to `/`).
+ */
+healthPath: string | null }
+/**
+ * A table of structured research output (e.g. a product comparison),
+ * stored per session in `research-tables.json`.
+ */
+export type ResearchTable = { title: string; columns: string[]; rows: string[][] }
+export type SandboxNetwork = "allow" | 
+/**
+ * Loopback only: local MCP servers, the canvas, a local LLM.
+ */
+"local-only" | "deny"
+/**
+ * Running the agent, canvas server and MCP servers under `sandbox-exec`
+ * on macOS (`sandbox.rs`).
+ */
+export type SandboxSettings = { enabled: boolean; 
+/**
+ * Folders under home they may read and write besides the repo, app
+ * data, node's own folders and the MCP servers' working directories.
+ */
+allowedDirs: string[]; network: SandboxNetwork }
+export type SavedPrompt = { name: string; 
+/**
+ * `{{text}}` is replaced with the text; without it the text is
+ * appended.
+ */
+template: string }
+/**
+ * One scripted moment of a demo session.
+ */
+export type ScenarioAction = 
+/**
+ * A line as if whisper had just produced it.
+ */
+{ kind: "transcript"; text: string; source: string | null } | 
+/**
+ * A WAV next to the scenario file, transcribed with the real pipeline.
+ * Progress events carry the resulting `transcript` instead.
+ */
+{ kind: "audio"; file: string } | 
+/**
+ * A canned agent reply shown in place of a live LLM run.
+ */
+{ kind: "agent"; narrative: string; url: string | null }
+/**
+ * Emitted to the main window for every step of a running scenario, then
+ * once with `done` set.
+ */
+export type ScenarioProgress = { name: string; index: number; total: number; action: ScenarioAction | null; done: boolean; error: string | null }
+export type ScenarioSummary = { name: string; title: string; description: string; steps: number }
+export type ScriptInfo = { 
+/**
+ * File name, e.g. `bookmark-questions.rhai`.
+ */
+name: string; 
+/**
+ * Hooks it defines.
+ */
+hooks: string[]; 
+/**
+ * Why it didn't load.
+ */
+error: string | null }
+/**
+ * User scripts in `<config>/scripts` (`scripting.rs`). Off by default,
+ * since they run with access to the session.
+ */
+export type ScriptingSettings = { enabled: boolean }
+export type SelfcheckReport = { 
+/**
+ * No stage failed.
+ */
+passed: boolean; stages: SelfcheckStage[]; logPath: string }
+export type SelfcheckStage = { name: string; status: SelfcheckStatus; detail: string; durationMs: number }
+export type SelfcheckStatus = "passed" | "failed" | 
+/**
+ * Prerequisite missing, e.g. no fixture recording.
+ */
+"skipped"
+export type ServerHealth = { name: string; probe: ProbeKind; liveness: Liveness; checkedAt: number; latencyMs: number | null; consecutiveFailures: number; lastError: string | null }
+export type SessionAudio = { sessionId: string; segments: AudioSegment[]; totalBytes: number }
+/**
+ * Keeping each session's segment audio in `<session>/audio/` to listen
+ * to or retranscribe later. The retranscribe wrap-up step keeps it too.
+ */
+export type SessionAudioSettings = { keep: boolean; 
+/**
+ * Audio of sessions older than this is deleted; 0 keeps it forever.
+ */
+retentionDays: number; 
+/**
+ * Oldest sessions' audio goes first past this many MB; 0 is no cap.
+ */
+maxTotalMb: number }
+/**
+ * Metadata for one recording session, stored as
+ * `<app data>/sessions/<id>/session.json`.
+ */
+export type SessionInfo = { id: string; title?: string | null; startedAt: number; endedAt?: number | null; segmentCount?: number }
+/**
+ * "What did Jamie do this episode", written to `report.json` when the
+ * session ends and sent to the main window.
+ */
+export type SessionReport = { sessionId: string; title: string | null; durationMs: number; wordsTranscribed: number; agentRuns: ModeRuns[]; inputTokens: number; outputTokens: number; 
+/**
+ * Only set when the provider reported a cost for at least one run.
+ */
+costUsd: number | null; toolCalls: number; 
+/**
+ * Diagram and page URLs the agent ended on, plus exported files.
+ */
+artifacts: string[] }
+/**
+ * Ties a session offset to the external recorder's timecode, saved as
+ * `timecode.json` in the session folder.
+ */
+export type SessionTimecode = { source: TimecodeSource; 
+/**
+ * Session offset the reference was taken at.
+ */
+sessionOffsetMs: number; 
+/**
+ * Recorder timecode at that offset, in ms since midnight.
+ */
+timecodeMs: number; fps: number; 
+/**
+ * NTP only: server asked, measured local clock error and round trip.
+ */
+server: string | null; clockOffsetMs: number | null; roundTripMs: number | null }
+export type ShowLanguage = { show: string; responseLanguage: string }
+/**
+ * A stretch of speech attributed to one speaker.
+ */
+export type SpeakerSegment = { 
+/**
+ * 1-based. tinydiarize only marks where the speaker changes, so
+ * labels alternate between speakers 1 and 2 on every turn, which
+ * matches a two-person interview.
+ */
+speaker: number; startMs: number; endMs: number; text: string }
+/**
+ * Emitted to the main window at startup when leftover automation
+ * browsers are found.
+ */
+export type StaleBrowserSessions = { sessions: BrowserSession[] }
+export type StepResult = { step: PipelineStep; ok: boolean; 
+/**
+ * Written file, posted channel or the error.
+ */
+detail: string }
+/**
+ * Aligning session offsets with an external recorder's timecode.
+ */
+export type TimecodeSettings = { ntpServer: string; 
+/**
+ * Take an NTP time-of-day reference whenever a session starts.
+ */
+autoSyncNtp: boolean; 
+/**
+ * Frame rate used for NTP references; LTC carries its own.
+ */
+fps: number; 
+/**
+ * Webview `deviceId` of the input carrying LTC; empty disables it.
+ */
+ltcDeviceId: string }
+export type TimecodeSource = 
+/**
+ * Time-of-day from an NTP-disciplined wall clock, for recorders
+ * jam-synced to the time of day.
+ */
+"ntp" | 
+/**
+ * LTC read from an input channel fed by the recorder.
+ */
+"ltc"
+export type TokenConfidence = { text: string; probability: number }
+export type TopicEdge = { from: string; to: string; offsetMs: number }
+/**
+ * `<session>/topic-graph.json`.
+ */
+export type TopicGraph = { nodes: TopicNode[]; edges: TopicEdge[]; current: string | null }
+export type TopicNode = { id: string; label: string; keywords: string[]; visits: TopicVisit[]; 
+/**
+ * Total time across visits; open visits count up to "now" (or the
+ * session end) when the graph is read.
+ */
+durationMs: number }
+/**
+ * The conversation moved from one topic node to another. Emitted as
+ * `topic-shift`; the canvas snapshots the board when it fires.
+ */
+export type TopicShift = { sessionId: string; 
+/**
+ * Label of the topic being left.
+ */
+fromTopic: string; toTopic: string; offsetMs: number }
+export type TopicTotal = { 
+/**
+ * Most recent label used for the topic.
+ */
+label: string; durationMs: number; 
+/**
+ * Sessions the topic came up in.
+ */
+sessions: number }
+/**
+ * One stretch of conversation spent on a topic.
+ */
+export type TopicVisit = { 
+/**
+ * Frontend chapter that opened the visit.
+ */
+chapterId: string; startMs: number; endMs: number | null; 
+/**
+ * First transcript segment of the visit, for retrieving its text.
+ */
+firstSegment: number | null }
+/**
+ * `transcribe_audio`'s response: the bare text for API v1 webviews, a
+ * `Transcription` from v2 on.
+ */
+export type TranscribeAudioResponse = string | Transcription
+/**
+ * What `transcribe_audio` asks whisper for.
+ */
+export type TranscribeMode = "transcribe" | 
+/**
+ * Also translate the speech to English.
+ */
+"translate"
+export type TranscriptFilters = { 
+/**
+ * Applied in every language.
+ */
+rules: FilterRules; maxFillerWords: number; 
+/**
+ * Fragments with fewer letters and digits are dropped; 0 keeps all.
+ */
+minChars: number; 
+/**
+ * Extra rules by whisper language code (e.g. `de`), applied when
+ * transcribing in that language or with auto-detect.
+ */
+languages: Partial<{ [key in string]: FilterRules }> }
+/**
+ * File formats for `export_transcript`.
+ */
+export type TranscriptFormat = "srt" | "vtt" | "json" | "markdown"
+export type TranscriptHit = { index: number; offsetMs: number; 
+/**
+ * Wall-clock time of the segment.
+ */
+at: number; 
+/**
+ * Text around the match, matched words wrapped in `<mark>`/`</mark>`.
+ */
+snippet: string }
+/**
+ * A session with transcript segments matching a search, best first.
+ */
+export type TranscriptSearchResult = { sessionId: string; title: string | null; startedAt: number; hits: TranscriptHit[] }
+/**
+ * One transcribed chunk, appended to `transcript.jsonl`. `offset_ms` is
+ * relative to the session start so it lines up with recordings.
+ */
+export type TranscriptSegment = { index: number; offsetMs: number; text: string; 
+/**
+ * Input track the chunk was captured from when recording several
+ * microphones, e.g. "Host" or "Guest".
+ */
+source?: string | null }
+export type TranscriptVersion = { id: string; 
+/**
+ * Model file it was transcribed with, e.g. `ggml-large-v3.bin`.
+ */
+model: string; language: string; options: TranscriptionOptions; createdAt: number; segmentCount: number }
+export type Transcription = { 
+/**
+ * In the spoken language.
+ */
+text: string; 
+/**
+ * English translation, in `translate` mode.
+ */
+translation: string | null }
+/**
+ * Speech-to-text service `transcribe_audio` and live capture use.
+ */
+export type TranscriptionBackendKind = 
+/**
+ * whisper.cpp on this machine, after any remote whisper servers.
+ */
+"local" | 
+/**
+ * OpenAI's audio API.
+ */
+"openai" | 
+/**
+ * Deepgram's speech-to-text API.
+ */
+"deepgram"
+export type TranscriptionCapabilities = { 
+/**
+ * macOS, where whisper.cpp builds with Metal by default.
+ */
+metal: boolean; 
+/**
+ * An NVIDIA driver answers `nvidia-smi`.
+ */
+cuda: boolean; 
+/**
+ * A Core ML encoder (`<model>-encoder.mlmodelc`) sits next to the
+ * active model; used by whisper.cpp builds with `WHISPER_COREML`.
+ */
+coreMl: boolean; 
+/**
+ * Built with the `whisper-rs` feature.
+ */
+inProcess: boolean; acceleration: WhisperAcceleration; 
+/**
+ * Backend the last local `whisper-cli` run reported, e.g. "Metal" or
+ * "CPU". `None` until something was transcribed locally.
+ */
+lastBackend: string | null }
+/**
+ * One transcribed segment. Emitted as `transcription-metrics`.
+ */
+export type TranscriptionMetrics = { finishedAt: number; 
+/**
+ * `whisper-rs`, `whisper-server`, `whisper-cli` or `remote`.
+ */
+backend: string; 
+/**
+ * Model file for local whisper, server URL for remote.
+ */
+model: string | null; audioMs: number; 
+/**
+ * Decoding the base64 payload from the webview and converting it to WAV.
+ */
+payloadMs: number | null; 
+/**
+ * Writing the segment to a temp file for `whisper-cli`.
+ */
+tempFileMs: number | null; 
+/**
+ * Loading the in-process model or starting the warm worker; 0 when
+ * it was already loaded. `whisper-cli` loads it on every run, inside
+ * `decode_ms`.
+ */
+modelLoadMs: number | null; 
+/**
+ * Whisper itself, or the request for remote servers.
+ */
+decodeMs: number; 
+/**
+ * Everything from receiving the payload to the transcript.
+ */
+totalMs: number; 
+/**
+ * `decode_ms / audio_ms`; below 1 is faster than real time.
+ */
+realTimeFactor: number }
+export type TranscriptionMetricsReport = { 
+/**
+ * Newest first.
+ */
+recent: TranscriptionMetrics[]; models: ModelMetrics[] }
+/**
+ * whisper decoding parameters. Unset fields keep whisper's defaults.
+ */
+export type TranscriptionOptions = { 
+/**
+ * Segments with a lower average token log probability are decoded
+ * again at a higher temperature (whisper: -1.0).
+ */
+logprobThold: number | null; 
+/**
+ * Segments more likely than this to be silence are dropped (0.6).
+ */
+noSpeechThold: number | null; 
+/**
+ * Beam search width; unset decodes greedily.
+ */
+beamSize: number | null; 
+/**
+ * Temperature of the first decode (0.0).
+ */
+temperature: number | null; 
+/**
+ * Step of the fallback ladder: a segment that fails the log
+ * probability or entropy check is decoded again at `temperature`
+ * plus this, and so on up to 1.0 (0.2). 0 turns fallback off.
+ */
+temperatureInc: number | null; 
+/**
+ * Segments whose token entropy is below this are taken as
+ * repetition loops and decoded again higher up the ladder (2.4).
+ * whisper.cpp's stand-in for the compression ratio threshold.
+ */
+entropyThold: number | null; 
+/**
+ * Longest segment in characters; unset or 0 means no limit.
+ */
+maxSegmentLen: number | null; 
+/**
+ * Restore punctuation and casing afterwards (see `punctuation`).
+ */
+punctuate: boolean | null }
+/**
+ * One whisper segment from `transcribe_audio_stream`, sent as soon as it is
+ * decoded so long recordings show text before the whole chunk is done.
+ */
+export type TranscriptionPartial = { streamId: string; index: number; text: string }
+export type TranscriptionQueueStatus = { maxConcurrent: number; running: number; waitingLive: number; waitingBatch: number }
+export type TranscriptionSettings = { 
+/**
+ * External whisper server tried before the local `whisper-cli`.
+ */
+remoteServer: RemoteWhisperServer | null; 
+/**
+ * Additional servers tried in order when `remote_server` is down.
+ */
+fallbackServers: RemoteWhisperServer[]; 
+/**
+ * Microphones captured side by side, e.g. a host mic and a guest
+ * channel. Fewer than two means the default input only.
+ */
+inputTracks: InputTrack[]; 
+/**
+ * Downloaded model file to transcribe with, e.g. `ggml-small.en.bin`.
+ * Empty uses the model installed by `scripts/setup-whisper.sh`.
+ */
+whisperModel: string; 
+/**
+ * Spoken language passed to whisper (`es`, `de`, ...) or `auto` to
+ * detect it. Empty means English. Needs a multilingual model.
+ */
+language: string; 
+/**
+ * Capture the microphone in the webview and send each segment over
+ * IPC instead of recording natively. Multi-track input always does.
+ */
+webviewCapture: boolean; 
+/**
+ * Microphone, system audio or both. System audio is only captured
+ * natively, so it overrides `webview_capture` and input tracks.
+ */
+captureSource: CaptureSource; 
+/**
+ * Tinydiarize model file for `transcribe_audio_diarized`. Empty means
+ * `ggml-small.en-tdrz.bin`.
+ */
+diarizationModel: string; 
+/**
+ * Text whisper is primed with, e.g. "A podcast about Tauri apps."
+ * Its spelling and style carry over into the transcript.
+ */
+initialPrompt: string; 
+/**
+ * Names and terms to spell correctly ("Excalidraw", guest names),
+ * appended to the initial prompt.
+ */
+vocabulary: string[]; 
+/**
+ * GPU use by local whisper.
+ */
+acceleration: WhisperAcceleration; 
+/**
+ * Start `whisper-cli` for every segment instead of keeping a warm
+ * `whisper-server` worker with the model loaded.
+ */
+coldStart: boolean; 
+/**
+ * Load the selected model file as is, even when a quantized variant
+ * of it sits next to it.
+ */
+exactModel: boolean; 
+/**
+ * Default decoding parameters; `transcribe_audio` can override any
+ * of them per call.
+ */
+options: TranscriptionOptions; 
+/**
+ * Filters applied to captured audio before whisper.
+ */
+preprocessing: PreprocessingSettings; 
+/**
+ * Windowing of long recordings (see `long_form`).
+ */
+longForm: LongFormSettings; 
+/**
+ * Only process the microphone while certain apps are frontmost.
+ */
+focusGate: FocusGateSettings; 
+/**
+ * Microphone to record from, by device name; empty follows the OS
+ * default. Set with `set_audio_device`.
+ */
+inputDevice: string; 
+/**
+ * Where segments are transcribed (see `transcription_backend`).
+ */
+backend: TranscriptionBackendKind; cloud: CloudTranscriptionSettings; 
+/**
+ * How `options.punctuate` restores punctuation.
+ */
+punctuation: PunctuationSettings; 
+/**
+ * Segments and files transcribed at once (see `transcription_queue`);
+ * 0 means 1.
+ */
+maxConcurrent: number }
+/**
+ * Webview zoom, applied whenever a window is created.
+ */
+export type UiSettings = { 
+/**
+ * Zoom for every window without its own entry in `window_scales`.
+ */
+scale: number; 
+/**
+ * Per-window overrides keyed by window label (`main`, `settings`, ...).
+ */
+windowScales: Partial<{ [key in string]: number }> }
+export type WebhookAction = 
+/**
+ * Run a saved prompt on the posted `text`, else the transcript so far.
+ */
+"run-prompt" | 
+/**
+ * Bookmark the current moment of the active session.
+ */
+"add-marker" | 
+/**
+ * Start a session and begin listening.
+ */
+"start-session"
+/**
+ * Inbound `POST /trigger/<name>` routes on localhost.
+ */
+export type WebhookSettings = { enabled: boolean; port: number; 
+/**
+ * Bearer token every request must send. Empty refuses all requests.
+ */
+token: string; triggers: WebhookTrigger[] }
+export type WebhookStatus = { enabled: boolean; 
+/**
+ * Base URL of the trigger routes while the listener is up.
+ */
+url: string | null; 
+/**
+ * Why the listener isn't running, e.g. the port is taken.
+ */
+error: string | null }
+/**
+ * A route under `/trigger/`, configured in Settings → Webhooks.
+ */
+export type WebhookTrigger = { name: string; action: WebhookAction; 
+/**
+ * Saved prompt for `run-prompt`.
+ */
+prompt: string; 
+/**
+ * Bookmark label or session title; a posted `label` overrides it.
+ */
+label: string }
+/**
+ * A trigger the webview has to carry out. Emitted as `webhook-triggered`.
+ */
+export type WebhookTriggered = { trigger: string; action: WebhookAction; prompt: string | null; text: string | null }
+export type WeeklyTalkTime = { 
+/**
+ * Monday 00:00 in the local timezone.
+ */
+weekStart: number; sessions: number; durationMs: number; wordsTranscribed: number }
+export type WhisperAcceleration = 
+/**
+ * Use the GPU (Metal, CUDA) when whisper was built with it.
+ */
+"auto" | 
+/**
+ * Always transcribe on the CPU, e.g. when the GPU is needed elsewhere.
+ */
+"cpu"
+export type WhisperBenchmark = { clip: string; audioMs: number; 
+/**
+ * Smallest model first.
+ */
+results: ModelBenchmark[]; 
+/**
+ * The largest model that kept up comfortably, if any did.
+ */
+recommended: string | null }
+export type WhisperModel = { 
+/**
+ * Catalog name, e.g. `small.en`.
+ */
+name: string; 
+/**
+ * `ggml-<name>.bin`, the value stored in `transcription.whisperModel`.
+ */
+fileName: string; sizeMb: number; installed: boolean; 
+/**
+ * Whether local transcription currently uses this model.
+ */
+active: boolean; 
+/**
+ * Multilingual models (no `.en` suffix) can transcribe other languages.
+ */
+multilingual: boolean; 
+/**
+ * Quantization of the variant loaded in its place, e.g. `q5_1`.
+ */
+variant: string | null; 
+/**
+ * A Core ML encoder sits next to it.
+ */
+coreMl: boolean }
+export type WhisperSetupReport = { cliPath: string | null; models: ModelCheck[] }
+export type WhisperStatus = { cliFound: boolean; modelFound: boolean; cliPath: string | null; modelPath: string | null }
+export type WhisperWorkerStatus = { enabled: boolean; 
+/**
+ * `whisper-server` was found next to `whisper-cli`.
+ */
+available: boolean; running: boolean; 
+/**
+ * Model file the worker has loaded.
+ */
+model: string | null; startedAt: number | null; 
+/**
+ * Times the worker was started again after exiting or for another
+ * model.
+ */
+restarts: number }
+export type WordTiming = { text: string; startMs: number; endMs: number; 
+/**
+ * Lowest probability among its tokens.
+ */
+probability: number }
+
+/** tauri-specta globals **/
+
+import {
+	invoke as TAURI_INVOKE,
+	Channel as TAURI_CHANNEL,
+} from "@tauri-apps/api/core";
+import * as TAURI_API_EVENT from "@tauri-apps/api/event";
+import { type WebviewWindow as __WebviewWindow__ } from "@tauri-apps/api/webviewWindow";
+
+type __EventObj__<T> = {
+	listen: (
+		cb: TAURI_API_EVENT.EventCallback<T>,
+	) => ReturnType<typeof TAURI_API_EVENT.listen<T>>;
+	once: (
+		cb: TAURI_API_EVENT.EventCallback<T>,
+	) => ReturnType<typeof TAURI_API_EVENT.once<T>>;
+	emit: null extends T
+		? (payload?: T) => ReturnType<typeof TAURI_API_EVENT.emit>
+		: (payload: T) => ReturnType<typeof TAURI_API_EVENT.emit>;
+};
+
+export type Result<T, E> =
+	| { status: "ok"; data: T }
+	| { status: "error"; error: E };
+
+function __makeEvents__<T extends Record<string, any>>(
+	mappings: Record<keyof T, string>,
+) {
+	return new Proxy(
+		{} as unknown as {
+			[K in keyof T]: __EventObj__<T[K]> & {
+				(handle: __WebviewWindow__): __EventObj__<T[K]>;
+			};
+		},
+		{
+			get: (_, event) => {
+				const name = mappings[event as keyof T];
+
+				return new Proxy((() => {}) as any, {
+					apply: (_, __, [window]: [__WebviewWindow__]) => ({
+						listen: (arg: any) => window.listen(name, arg),
+						once: (arg: any) => window.once(name, arg),
+						emit: (arg: any) => window.emit(name, arg),
+					}),
+					get: (_, command: keyof __EventObj__<any>) => {
+						switch (command) {
+							case "listen":
+								return (arg: any) => TAURI_API_EVENT.listen(name, arg);
+							case "once":
+								return (arg: any) => TAURI_API_EVENT.once(name, arg);
+							case "emit":
+								return (arg: any) => TAURI_API_EVENT.emit(name, arg);
+						}
+					},
+				});
+			},
+		},
+	);
+}
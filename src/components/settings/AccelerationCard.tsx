@@ -0,0 +1,115 @@
+import * as React from "react";
+
+import type {
+  TranscriptionCapabilities,
+  TranscriptionSettings,
+  WhisperAcceleration,
+  WhisperWorkerStatus,
+} from "../../bindings";
+import { commands } from "../../bindings";
+import {
+  Card,
+  CardContent,
+  CardDescription,
+  CardHeader,
+  CardTitle,
+} from "../ui/card";
+import { Checkbox } from "../ui/checkbox";
+import { Label } from "../ui/label";
+
+function describeWorker(worker: WhisperWorkerStatus) {
+  if (!worker.available) {
+    return "whisper-server wasn't found next to whisper-cli; run scripts/setup-whisper.sh again.";
+  }
+  if (!worker.enabled) {
+    return "Each segment starts whisper-cli and loads the model again.";
+  }
+  const restarts = worker.restarts > 0 ? `, restarted ${worker.restarts} times` : "";
+  return worker.running
+    ? `Worker running with ${worker.model ?? "the default model"}${restarts}.`
+    : `Worker starts with the next segment${restarts}.`;
+}
+
+function describeHardware(capabilities: TranscriptionCapabilities) {
+  const available = [
+    capabilities.metal ? "Metal" : null,
+    capabilities.cuda ? "CUDA" : null,
+    capabilities.coreMl ? "Core ML encoder" : null,
+  ].filter(Boolean);
+  return available.length > 0 ? `Available: ${available.join(", ")}.` : "No GPU detected.";
+}
+
+export function AccelerationCard() {
+  const [capabilities, setCapabilities] = React.useState<TranscriptionCapabilities | null>(null);
+  const [worker, setWorker] = React.useState<WhisperWorkerStatus | null>(null);
+  const [status, setStatus] = React.useState("");
+
+  React.useEffect(() => {
+    void commands.getTranscriptionCapabilities().then(setCapabilities);
+    void commands.getWhisperWorkerStatus().then(setWorker);
+  }, []);
+
+  if (!capabilities) {
+    return null;
+  }
+
+  const changeTranscription = async (patch: Partial<TranscriptionSettings>) => {
+    try {
+      const latest = await commands.getAppSettings();
+      await commands.saveAppSettings({
+        ...latest,
+        transcription: { ...latest.transcription, ...patch },
+      });
+      setCapabilities(await commands.getTranscriptionCapabilities());
+      setWorker(await commands.getWhisperWorkerStatus());
+      setStatus("Saved. It applies from the next segment.");
+    } catch (error) {
+      setStatus(String(error));
+    }
+  };
+
+  return (
+    <Card>
+      <CardHeader>
+        <CardTitle>Acceleration</CardTitle>
+        <CardDescription>
+          Local whisper runs on the GPU when it was built with Metal or CUDA support, which cuts
+          transcription latency considerably. {describeHardware(capabilities)}
+        </CardDescription>
+      </CardHeader>
+      <CardContent className="space-y-3">
+        <Label htmlFor="whisper-acceleration">Run whisper on</Label>
+        <select
+          id="whisper-acceleration"
+          className="h-8 w-full rounded-md border bg-background px-2 text-sm"
+          value={capabilities.acceleration}
+          onChange={(event) =>
+            void changeTranscription({ acceleration: event.target.value as WhisperAcceleration })
+          }
+        >
+          <option value="auto">GPU when available</option>
+          <option value="cpu">CPU only</option>
+        </select>
+        {worker && !capabilities.inProcess ? (
+          <>
+            <label className="flex items-center gap-2 text-sm">
+              <Checkbox
+                checked={worker.enabled}
+                disabled={!worker.available}
+                onChange={(event) => void changeTranscription({ coldStart: !event.target.checked })}
+              />
+              Keep whisper loaded between segments
+            </label>
+            <p className="text-xs text-muted-foreground">{describeWorker(worker)}</p>
+          </>
+        ) : null}
+        <p className="text-xs text-muted-foreground">
+          {capabilities.lastBackend
+            ? `Last local transcription used: ${capabilities.lastBackend}.`
+            : "Nothing has been transcribed locally yet."}
+        </p>
+        {status ? <p className="text-xs text-muted-foreground">{status}</p> : null}
+      </CardContent>
+    </Card>
+  );
+}
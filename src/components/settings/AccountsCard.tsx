@@ -0,0 +1,113 @@
+import * as React from "react";
+import { openUrl } from "@tauri-apps/plugin-opener";
+
+import type { AccountStatus, DeviceAuthorization } from "../../bindings";
+import { commands, events } from "../../bindings";
+import { Badge } from "../ui/badge";
+import { Button } from "../ui/button";
+import {
+  Card,
+  CardContent,
+  CardDescription,
+  CardHeader,
+  CardTitle,
+} from "../ui/card";
+
+export function AccountsCard() {
+  const [accounts, setAccounts] = React.useState<AccountStatus[]>([]);
+  const [pending, setPending] = React.useState<DeviceAuthorization | null>(null);
+  const [status, setStatus] = React.useState("");
+
+  const refresh = React.useCallback(async () => {
+    try {
+      setAccounts(await commands.listAccounts());
+    } catch (error) {
+      setStatus(`Failed to load accounts: ${String(error)}`);
+    }
+  }, []);
+
+  React.useEffect(() => {
+    void refresh();
+    const unlisten = events.accountChanged.listen((event) => {
+      const { provider, connected, error } = event.payload;
+      setPending((current) => (current?.provider === provider ? null : current));
+      setStatus(
+        error
+          ? `${provider}: ${error}`
+          : `${provider} ${connected ? "connected" : "disconnected"}.`
+      );
+      void refresh();
+    });
+    return () => {
+      void unlisten.then((fn) => fn());
+    };
+  }, [refresh]);
+
+  const connect = async (provider: string) => {
+    setStatus("");
+    try {
+      const authorization = await commands.connectAccount(provider);
+      setPending(authorization);
+      await openUrl(
+        authorization.verificationUriComplete ?? authorization.verificationUri
+      );
+    } catch (error) {
+      setStatus(String(error));
+    }
+  };
+
+  const disconnect = async (provider: string) => {
+    try {
+      await commands.disconnectAccount(provider);
+    } catch (error) {
+      setStatus(String(error));
+    }
+  };
+
+  return (
+    <Card>
+      <CardHeader>
+        <CardTitle>Accounts</CardTitle>
+        <CardDescription>
+          Sign in to providers instead of pasting API keys. Tokens are kept in the
+          system keychain; reference them in MCP config as{" "}
+          <code className="rounded bg-muted px-1 py-0.5 text-[11px]">{"${account:github}"}</code>.
+        </CardDescription>
+      </CardHeader>
+      <CardContent className="space-y-3">
+        {accounts.map((account) => (
+          <div key={account.provider} className="flex items-center justify-between gap-3">
+            <div className="flex items-center gap-2">
+              <span className="text-sm font-medium">{account.name}</span>
+              {account.connected ? (
+                <Badge>Connected</Badge>
+              ) : !account.configured ? (
+                <Badge variant="outline">No client id</Badge>
+              ) : null}
+            </div>
+            {account.connected ? (
+              <Button variant="outline" size="sm" onClick={() => void disconnect(account.provider)}>
+                Disconnect
+              </Button>
+            ) : (
+              <Button
+                size="sm"
+                disabled={!account.configured || pending?.provider === account.provider}
+                onClick={() => void connect(account.provider)}
+              >
+                Connect
+              </Button>
+            )}
+          </div>
+        ))}
+        {pending ? (
+          <p className="text-sm">
+            Enter code <code className="rounded bg-muted px-1 py-0.5">{pending.userCode}</code> at{" "}
+            {pending.verificationUri} to finish signing in.
+          </p>
+        ) : null}
+        {status ? <p className="text-xs text-muted-foreground">{status}</p> : null}
+      </CardContent>
+    </Card>
+  );
+}
@@ -0,0 +1,140 @@
+import * as React from "react";
+
+import type { AgendaItem, AgendaStatusSummary } from "../../bindings";
+import { commands } from "../../bindings";
+import { Badge } from "../ui/badge";
+import { Button } from "../ui/button";
+import {
+  Card,
+  CardContent,
+  CardDescription,
+  CardHeader,
+  CardTitle,
+} from "../ui/card";
+import { Input } from "../ui/input";
+import { Label } from "../ui/label";
+import { Textarea } from "../ui/textarea";
+
+export function AgendaCard() {
+  const [items, setItems] = React.useState<AgendaItem[]>([]);
+  const [summary, setSummary] = React.useState<AgendaStatusSummary | null>(null);
+  const [task, setTask] = React.useState("");
+  const [dueBy, setDueBy] = React.useState("");
+  const [status, setStatus] = React.useState("");
+
+  const reload = React.useCallback(async () => {
+    setItems(await commands.listAgendaItems());
+    setSummary(await commands.getAgendaStatus());
+  }, []);
+
+  React.useEffect(() => {
+    void reload();
+  }, [reload]);
+
+  const run = async (action: () => Promise<unknown>) => {
+    try {
+      await action();
+      setStatus("");
+    } catch (error) {
+      setStatus(String(error));
+    }
+    await reload();
+  };
+
+  const add = () =>
+    run(async () => {
+      await commands.addAgendaItem(task, dueBy ? new Date(dueBy).getTime() : null);
+      setTask("");
+      setDueBy("");
+    });
+
+  return (
+    <Card>
+      <CardHeader>
+        <CardTitle>Research Agenda</CardTitle>
+        <CardDescription>
+          Queue research for upcoming episodes. Tasks run one at a time while HeyJamie is idle
+          (no session, recording or agent run, on AC power), earliest deadline first, and keep
+          their place across restarts. Results are added to the <code>prep.md</code> of the next
+          session you start.
+        </CardDescription>
+      </CardHeader>
+      <CardContent className="space-y-3">
+        <div className="space-y-2">
+          <Label htmlFor="agenda-task">Task</Label>
+          <Textarea
+            id="agenda-task"
+            rows={2}
+            placeholder="Find three recent studies on sleep and memory"
+            value={task}
+            onChange={(event) => setTask(event.target.value)}
+          />
+        </div>
+        <div className="flex items-center gap-2">
+          <Label htmlFor="agenda-due">Needed by</Label>
+          <Input
+            id="agenda-due"
+            type="datetime-local"
+            value={dueBy}
+            onChange={(event) => setDueBy(event.target.value)}
+          />
+          <Button size="sm" onClick={() => void add()} disabled={!task.trim()}>
+            Add to agenda
+          </Button>
+        </div>
+        {items.map((item) => (
+          <div key={item.id} className="space-y-1 rounded border p-2 text-sm">
+            <div className="flex items-center gap-2">
+              <Badge>{item.status}</Badge>
+              <span className="flex-1">{item.task}</span>
+              {item.status === "failed" || item.status === "done" ? (
+                <Button
+                  size="sm"
+                  variant="ghost"
+                  onClick={() => void run(() => commands.retryAgendaItem(item.id))}
+                >
+                  Run again
+                </Button>
+              ) : null}
+              {item.status !== "running" ? (
+                <Button
+                  size="sm"
+                  variant="ghost"
+                  onClick={() => void run(() => commands.removeAgendaItem(item.id))}
+                >
+                  Remove
+                </Button>
+              ) : null}
+            </div>
+            {item.dueBy ? (
+              <p className="text-xs text-muted-foreground">
+                Needed by {new Date(item.dueBy).toLocaleString()}
+              </p>
+            ) : null}
+            {item.lastError ? (
+              <p className="text-xs text-muted-foreground">
+                Attempt {item.attempts}: {item.lastError}
+              </p>
+            ) : null}
+            {item.result ? (
+              <details className="text-xs">
+                <summary>
+                  {item.sessionId ? `Result (in the prep of ${item.sessionId})` : "Result"}
+                </summary>
+                <pre className="whitespace-pre-wrap">{item.result}</pre>
+              </details>
+            ) : null}
+          </div>
+        ))}
+        {summary ? (
+          <p className="text-xs text-muted-foreground">
+            {summary.pending} pending, {summary.running} running, {summary.done} done,{" "}
+            {summary.failed} failed.{" "}
+            {summary.idle ? "Idle now." : `Waiting: ${summary.busyReason ?? "busy"}.`}
+          </p>
+        ) : null}
+        {status ? <p className="text-xs text-muted-foreground">{status}</p> : null}
+      </CardContent>
+    </Card>
+  );
+}
@@ -0,0 +1,95 @@
+import * as React from "react";
+
+import type { CustomAgentMode } from "../../bindings";
+import { commands } from "../../bindings";
+import { Button } from "../ui/button";
+import {
+  Card,
+  CardContent,
+  CardDescription,
+  CardHeader,
+  CardTitle,
+} from "../ui/card";
+import { Label } from "../ui/label";
+import { Textarea } from "../ui/textarea";
+
+const EXAMPLE: CustomAgentMode[] = [
+  {
+    name: "action-items",
+    timeoutMs: 60000,
+    instructions: "List the action items in this conversation.\n\n{{context}}",
+    allowedTools: [],
+    outputSchema: {
+      type: "object",
+      required: ["items"],
+      properties: { items: { type: "array", items: { type: "string" } } },
+    },
+  },
+];
+
+export function AgentModesCard() {
+  const [json, setJson] = React.useState("");
+  const [status, setStatus] = React.useState("");
+
+  const reload = React.useCallback(async () => {
+    const settings = await commands.getAppSettings();
+    setJson(JSON.stringify(settings.agentModes.custom, null, 2));
+  }, []);
+
+  React.useEffect(() => {
+    void reload();
+  }, [reload]);
+
+  const save = async () => {
+    try {
+      const custom = JSON.parse(json) as CustomAgentMode[];
+      const latest = await commands.getAppSettings();
+      const saved = await commands.saveAppSettings({ ...latest, agentModes: { custom } });
+      setJson(JSON.stringify(saved.agentModes.custom, null, 2));
+      setStatus("Agent modes saved.");
+    } catch (error) {
+      setStatus(String(error));
+    }
+  };
+
+  return (
+    <Card>
+      <CardHeader>
+        <CardTitle>Custom Agent Modes</CardTitle>
+        <CardDescription>
+          Modes you can pass to the agent by name, each with its own instructions, timeout,
+          allowed tools (<code>*</code> wildcards, none means no tools) and optional JSON output
+          schema. Instructions can use <code>{"{{prompt}}"}</code>,{" "}
+          <code>{"{{context}}"}</code> and <code>{"{{instructions}}"}</code>.
+        </CardDescription>
+      </CardHeader>
+      <CardContent className="space-y-3">
+        <div className="space-y-2">
+          <Label htmlFor="agent-modes-json">Modes JSON</Label>
+          <Textarea
+            id="agent-modes-json"
+            rows={10}
+            value={json}
+            onChange={(event) => setJson(event.target.value)}
+          />
+        </div>
+        <div className="flex gap-2">
+          <Button size="sm" onClick={() => void save()}>
+            Save agent modes
+          </Button>
+          <Button size="sm" variant="outline" onClick={() => void reload()}>
+            Reload
+          </Button>
+          <Button
+            size="sm"
+            variant="ghost"
+            onClick={() => setJson(JSON.stringify(EXAMPLE, null, 2))}
+          >
+            Show example
+          </Button>
+        </div>
+        {status ? <p className="text-xs text-muted-foreground">{status}</p> : null}
+      </CardContent>
+    </Card>
+  );
+}
@@ -0,0 +1,94 @@
+import * as React from "react";
+
+import type { AppSettings, CaptionSettings } from "../../bindings";
+import { commands } from "../../bindings";
+import { Button } from "../ui/button";
+import {
+  Card,
+  CardContent,
+  CardDescription,
+  CardHeader,
+  CardTitle,
+} from "../ui/card";
+import { Checkbox } from "../ui/checkbox";
+import { Input } from "../ui/input";
+import { Label } from "../ui/label";
+
+export function CaptionsCard() {
+  const [settings, setSettings] = React.useState<AppSettings | null>(null);
+  const [status, setStatus] = React.useState("");
+
+  React.useEffect(() => {
+    void commands.getAppSettings().then(setSettings);
+  }, []);
+
+  if (!settings) {
+    return null;
+  }
+
+  const captions = settings.captions;
+  const update = (patch: Partial<CaptionSettings>) =>
+    setSettings({ ...settings, captions: { ...captions, ...patch } });
+
+  const save = async () => {
+    try {
+      // Reload first so edits made in other cards aren't overwritten.
+      const latest = await commands.getAppSettings();
+      setSettings(await commands.saveAppSettings({ ...latest, captions }));
+      setStatus("Caption settings saved.");
+    } catch (error) {
+      setStatus(String(error));
+    }
+  };
+
+  return (
+    <Card>
+      <CardHeader>
+        <CardTitle>Live Captions</CardTitle>
+        <CardDescription>
+          Keep an .srt/.vtt file growing while you record so it can go straight into
+          your video editor.
+        </CardDescription>
+      </CardHeader>
+      <CardContent className="space-y-3">
+        <label className="flex items-center gap-2 text-sm">
+          <Checkbox
+            checked={captions.liveOutput}
+            onChange={(event) => update({ liveOutput: event.target.checked })}
+          />
+          Write captions during recording
+        </label>
+        <div className="space-y-1">
+          <Label htmlFor="captions-path">File or folder</Label>
+          <Input
+            id="captions-path"
+            value={captions.outputPath}
+            onChange={(event) => update({ outputPath: event.target.value })}
+            placeholder="/Users/me/Movies/live.srt"
+          />
+          <p className="text-xs text-muted-foreground">
+            A folder gets one file per session named after the session id.
+          </p>
+        </div>
+        <div className="flex items-center gap-2">
+          <Label htmlFor="captions-format">Folder format</Label>
+          <select
+            id="captions-format"
+            className="h-8 rounded-md border bg-background px-2 text-sm"
+            value={captions.format}
+            onChange={(event) =>
+              update({ format: event.target.value as CaptionSettings["format"] })
+            }
+          >
+            <option value="srt">SRT</option>
+            <option value="vtt">WebVTT</option>
+          </select>
+        </div>
+        <Button size="sm" onClick={() => void save()}>
+          Save caption settings
+        </Button>
+        {status ? <p className="text-xs text-muted-foreground">{status}</p> : null}
+      </CardContent>
+    </Card>
+  );
+}
@@ -0,0 +1,98 @@
+import * as React from "react";
+
+import type { AppSettings, ClipboardSettings } from "../../bindings";
+import { commands } from "../../bindings";
+import { Button } from "../ui/button";
+import {
+  Card,
+  CardContent,
+  CardDescription,
+  CardHeader,
+  CardTitle,
+} from "../ui/card";
+import { Checkbox } from "../ui/checkbox";
+import { Input } from "../ui/input";
+import { Label } from "../ui/label";
+
+export function ClipboardCard() {
+  const [settings, setSettings] = React.useState<AppSettings | null>(null);
+  const [status, setStatus] = React.useState("");
+
+  React.useEffect(() => {
+    void commands.getAppSettings().then(setSettings);
+  }, []);
+
+  if (!settings) {
+    return null;
+  }
+
+  const clipboard = settings.clipboard;
+  const update = (patch: Partial<ClipboardSettings>) =>
+    setSettings({ ...settings, clipboard: { ...clipboard, ...patch } });
+
+  const save = async () => {
+    try {
+      const latest = await commands.getAppSettings();
+      setSettings(await commands.saveAppSettings({ ...latest, clipboard }));
+      setStatus("Clipboard settings saved.");
+    } catch (error) {
+      setStatus(String(error));
+    }
+  };
+
+  return (
+    <Card>
+      <CardHeader>
+        <CardTitle>Clipboard Suggestions</CardTitle>
+        <CardDescription>
+          Offer to open, summarize or draw what you copy. Off by default. Copied content stays in
+          memory and is never logged or saved, and links with credentials or text that looks like
+          a key or password are ignored.
+        </CardDescription>
+      </CardHeader>
+      <CardContent className="space-y-3">
+        <label className="flex items-center gap-2 text-sm">
+          <Checkbox
+            checked={clipboard.enabled}
+            onChange={(event) => update({ enabled: event.target.checked })}
+          />
+          Watch the clipboard
+        </label>
+        <label className="flex items-center gap-2 text-sm">
+          <Checkbox
+            checked={clipboard.urls}
+            disabled={!clipboard.enabled}
+            onChange={(event) => update({ urls: event.target.checked })}
+          />
+          Links
+        </label>
+        <label className="flex items-center gap-2 text-sm">
+          <Checkbox
+            checked={clipboard.text}
+            disabled={!clipboard.enabled}
+            onChange={(event) => update({ text: event.target.checked })}
+          />
+          Large blocks of text
+        </label>
+        <div className="flex items-center gap-2">
+          <Label htmlFor="clipboard-min-chars">Minimum text length (characters)</Label>
+          <Input
+            id="clipboard-min-chars"
+            type="number"
+            min={1}
+            className="w-24"
+            disabled={!clipboard.enabled || !clipboard.text}
+            value={clipboard.minTextChars}
+            onChange={(event) =>
+              update({ minTextChars: Math.max(1, Number(event.target.value) || 1) })
+            }
+          />
+        </div>
+        <Button size="sm" onClick={() => void save()}>
+          Save clipboard settings
+        </Button>
+        {status ? <p className="text-xs text-muted-foreground">{status}</p> : null}
+      </CardContent>
+    </Card>
+  );
+}
@@ -0,0 +1,166 @@
+import * as React from "react";
+
+import type { LongFormSettings, PunctuationSettings, TranscriptionOptions } from "../../bindings";
+import { commands } from "../../bindings";
+import { Button } from "../ui/button";
+import {
+  Card,
+  CardContent,
+  CardDescription,
+  CardHeader,
+  CardTitle,
+} from "../ui/card";
+import { Checkbox } from "../ui/checkbox";
+import { Input } from "../ui/input";
+import { Label } from "../ui/label";
+
+type Field = {
+  key: Exclude<keyof TranscriptionOptions, "punctuate">;
+  label: string;
+  placeholder: string;
+  step: string;
+};
+
+const FIELDS: Field[] = [
+  { key: "noSpeechThold", label: "No-speech threshold", placeholder: "0.6", step: "0.05" },
+  { key: "logprobThold", label: "Log probability threshold", placeholder: "-1.0", step: "0.1" },
+  { key: "temperature", label: "Temperature", placeholder: "0.0", step: "0.1" },
+  { key: "temperatureInc", label: "Fallback temperature step", placeholder: "0.2", step: "0.1" },
+  { key: "entropyThold", label: "Entropy threshold", placeholder: "2.4", step: "0.1" },
+  { key: "beamSize", label: "Beam size", placeholder: "greedy", step: "1" },
+  { key: "maxSegmentLen", label: "Max segment length (chars)", placeholder: "no limit", step: "1" },
+];
+
+export function DecodingCard() {
+  const [options, setOptions] = React.useState<TranscriptionOptions | null>(null);
+  const [longForm, setLongForm] = React.useState<LongFormSettings | null>(null);
+  const [punctuation, setPunctuation] = React.useState<PunctuationSettings | null>(null);
+  const [modelCommand, setModelCommand] = React.useState("");
+  const [maxConcurrent, setMaxConcurrent] = React.useState(1);
+  const [status, setStatus] = React.useState("");
+
+  React.useEffect(() => {
+    void commands.getAppSettings().then((settings) => {
+      setOptions(settings.transcription.options);
+      setLongForm(settings.transcription.longForm);
+      setPunctuation(settings.transcription.punctuation);
+      setModelCommand(settings.transcription.punctuation.modelCommand.join(" "));
+      setMaxConcurrent(Math.max(1, settings.transcription.maxConcurrent));
+    });
+  }, []);
+
+  if (!options || !longForm || !punctuation) {
+    return null;
+  }
+
+  const change = (key: Field["key"], value: string) => {
+    setOptions({ ...options, [key]: value.trim() === "" ? null : Number(value) });
+  };
+
+  const save = async () => {
+    try {
+      const latest = await commands.getAppSettings();
+      const nextPunctuation = { ...punctuation, modelCommand: modelCommand.split(/\s+/).filter(Boolean) };
+      const saved = await commands.saveAppSettings({
+        ...latest,
+        transcription: { ...latest.transcription, options, longForm, punctuation: nextPunctuation, maxConcurrent },
+      });
+      setOptions(saved.transcription.options);
+      setLongForm(saved.transcription.longForm);
+      setPunctuation(saved.transcription.punctuation);
+      setModelCommand(saved.transcription.punctuation.modelCommand.join(" "));
+      setMaxConcurrent(Math.max(1, saved.transcription.maxConcurrent));
+      setStatus("Saved. It applies from the next segment.");
+    } catch (error) {
+      setStatus(String(error));
+    }
+  };
+
+  return (
+    <Card>
+      <CardHeader>
+        <CardTitle>Decoding</CardTitle>
+        <CardDescription>
+          Defaults for how whisper decodes each segment. Leave a field empty for whisper's own
+          default. A raised no-speech threshold drops more silence; a beam size trades speed for
+          accuracy. A segment that decodes badly (low log probability, or low entropy from a
+          repeated-token loop) is retried at rising temperatures, one step at a time up to 1.0;
+          raise the entropy threshold or the step if noisy audio loops, or set the step to 0 to
+          turn retries off. Dropped files and long recordings are transcribed in overlapping
+          windows and joined where their words match, so nothing is lost or repeated at the
+          seams. Small models often write lowercase run-on text; restoring punctuation adds
+          sentence breaks and capitals with built-in rules, or with a local model command that
+          reads text on stdin and prints it punctuated. Live segments, files and
+          retranscriptions share a limited number of whisper runs at once, with live segments
+          first in line; raise it only if your machine keeps up.
+        </CardDescription>
+      </CardHeader>
+      <CardContent className="space-y-3">
+        {FIELDS.map((field) => (
+          <div key={field.key} className="flex items-center gap-2">
+            <Label htmlFor={`decoding-${field.key}`}>{field.label}</Label>
+            <Input
+              id={`decoding-${field.key}`}
+              type="number"
+              step={field.step}
+              placeholder={field.placeholder}
+              value={options[field.key] ?? ""}
+              onChange={(event) => change(field.key, event.target.value)}
+            />
+          </div>
+        ))}
+        <div className="flex items-center gap-2">
+          <Label htmlFor="decoding-window">Long-recording window (s)</Label>
+          <Input
+            id="decoding-window"
+            type="number"
+            min={10}
+            max={600}
+            value={longForm.windowSeconds}
+            onChange={(event) => setLongForm({ ...longForm, windowSeconds: Number(event.target.value) })}
+          />
+          <Label htmlFor="decoding-overlap">Overlap (s)</Label>
+          <Input
+            id="decoding-overlap"
+            type="number"
+            min={1}
+            value={longForm.overlapSeconds}
+            onChange={(event) => setLongForm({ ...longForm, overlapSeconds: Number(event.target.value) })}
+          />
+        </div>
+        <div className="flex items-center gap-2">
+          <Label htmlFor="decoding-max-concurrent">Transcriptions at once</Label>
+          <Input
+            id="decoding-max-concurrent"
+            type="number"
+            min={1}
+            max={8}
+            value={maxConcurrent}
+            onChange={(event) => setMaxConcurrent(Number(event.target.value))}
+          />
+        </div>
+        <label className="flex items-center gap-2 text-sm">
+          <Checkbox
+            checked={options.punctuate ?? false}
+            onChange={(event) => setOptions({ ...options, punctuate: event.target.checked || null })}
+          />
+          Restore punctuation and casing
+        </label>
+        <div className="space-y-1">
+          <Label htmlFor="decoding-punctuation-command">Punctuation model command</Label>
+          <Input
+            id="decoding-punctuation-command"
+            placeholder="built-in rules"
+            disabled={!options.punctuate}
+            value={modelCommand}
+            onChange={(event) => setModelCommand(event.target.value)}
+          />
+        </div>
+        <Button size="sm" onClick={() => void save()}>
+          Save decoding options
+        </Button>
+        {status ? <p className="text-xs text-muted-foreground">{status}</p> : null}
+      </CardContent>
+    </Card>
+  );
+}
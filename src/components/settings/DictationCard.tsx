@@ -0,0 +1,84 @@
+import * as React from "react";
+
+import type { DictationSettings, DictationStatus } from "../../bindings";
+import { commands } from "../../bindings";
+import { Button } from "../ui/button";
+import {
+  Card,
+  CardContent,
+  CardDescription,
+  CardHeader,
+  CardTitle,
+} from "../ui/card";
+import { Checkbox } from "../ui/checkbox";
+import { Input } from "../ui/input";
+import { Label } from "../ui/label";
+
+export function DictationCard() {
+  const [dictation, setDictation] = React.useState<DictationSettings | null>(null);
+  const [current, setCurrent] = React.useState<DictationStatus | null>(null);
+  const [status, setStatus] = React.useState("");
+
+  React.useEffect(() => {
+    void commands.getAppSettings().then((settings) => setDictation(settings.dictation));
+    void commands.getDictationStatus().then(setCurrent);
+  }, []);
+
+  if (!dictation || !current) {
+    return null;
+  }
+
+  const save = async () => {
+    try {
+      const latest = await commands.getAppSettings();
+      const saved = await commands.saveAppSettings({ ...latest, dictation });
+      setDictation(saved.dictation);
+      const next = await commands.getDictationStatus();
+      setCurrent(next);
+      setStatus(
+        next.error ?? (saved.dictation.enabled ? `Press ${saved.dictation.shortcut} to dictate.` : "Saved.")
+      );
+    } catch (error) {
+      setStatus(String(error));
+    }
+  };
+
+  return (
+    <Card>
+      <CardHeader>
+        <CardTitle>Dictation</CardTitle>
+        <CardDescription>
+          Press a global shortcut to type what you say into whichever app is in front, and press
+          it again to stop. Each line is typed once it is transcribed. macOS asks for
+          Accessibility access the first time; on Linux this needs X11 and <code>xdotool</code>.
+          Nothing is typed while a HeyJamie window is in front.
+        </CardDescription>
+      </CardHeader>
+      <CardContent className="space-y-3">
+        <label className="flex items-center gap-2 text-sm">
+          <Checkbox
+            checked={dictation.enabled}
+            onChange={(event) => setDictation({ ...dictation, enabled: event.target.checked })}
+          />
+          Enable dictation shortcut
+        </label>
+        <div className="flex items-center gap-2">
+          <Label htmlFor="dictation-shortcut">Shortcut</Label>
+          <Input
+            id="dictation-shortcut"
+            value={dictation.shortcut}
+            placeholder="CmdOrCtrl+Alt+D"
+            onChange={(event) => setDictation({ ...dictation, shortcut: event.target.value })}
+          />
+        </div>
+        <Button size="sm" onClick={() => void save()}>
+          Save dictation
+        </Button>
+        {current.enabled && !current.registered && current.error ? (
+          <p className="text-xs text-muted-foreground">{current.error}</p>
+        ) : null}
+        {status ? <p className="text-xs text-muted-foreground">{status}</p> : null}
+      </CardContent>
+    </Card>
+  );
+}
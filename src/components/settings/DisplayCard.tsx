@@ -0,0 +1,84 @@
+import * as React from "react";
+
+import type { UiSettings } from "../../bindings";
+import { commands } from "../../bindings";
+import {
+  Card,
+  CardContent,
+  CardDescription,
+  CardHeader,
+  CardTitle,
+} from "../ui/card";
+import { Label } from "../ui/label";
+
+const SCALES = [0.75, 0.9, 1, 1.1, 1.25, 1.5, 1.75, 2];
+
+const WINDOWS: { label: string | null; name: string }[] = [
+  { label: null, name: "All windows" },
+  { label: "main", name: "Main window" },
+  { label: "settings", name: "Settings window" },
+];
+
+export function DisplayCard() {
+  const [ui, setUi] = React.useState<UiSettings | null>(null);
+  const [status, setStatus] = React.useState("");
+
+  React.useEffect(() => {
+    void commands.getAppSettings().then((settings) => setUi(settings.ui));
+  }, []);
+
+  if (!ui) {
+    return null;
+  }
+
+  const scaleOf = (label: string | null) =>
+    label === null ? ui.scale : ui.windowScales[label] ?? ui.scale;
+
+  const changeScale = async (label: string | null, factor: number) => {
+    try {
+      const applied = await commands.setUiScale(label, factor);
+      setUi((await commands.getAppSettings()).ui);
+      setStatus(`Zoom set to ${Math.round(applied * 100)}%.`);
+    } catch (error) {
+      setStatus(String(error));
+    }
+  };
+
+  return (
+    <Card>
+      <CardHeader>
+        <CardTitle>Display</CardTitle>
+        <CardDescription>
+          Zoom is remembered per window and applied every time it opens.
+        </CardDescription>
+      </CardHeader>
+      <CardContent className="space-y-3">
+        {WINDOWS.map(({ label, name }) => (
+          <div key={name} className="flex items-center gap-2">
+            <Label htmlFor={`ui-scale-${label ?? "all"}`} className="w-36">
+              {name}
+            </Label>
+            <select
+              id={`ui-scale-${label ?? "all"}`}
+              className="h-8 rounded-md border bg-background px-2 text-sm"
+              value={String(scaleOf(label))}
+              onChange={(event) => void changeScale(label, Number(event.target.value))}
+            >
+              {SCALES.includes(scaleOf(label)) ? null : (
+                <option value={String(scaleOf(label))}>
+                  {Math.round(scaleOf(label) * 100)}%
+                </option>
+              )}
+              {SCALES.map((scale) => (
+                <option key={scale} value={String(scale)}>
+                  {Math.round(scale * 100)}%
+                </option>
+              ))}
+            </select>
+          </div>
+        ))}
+        {status ? <p className="text-xs text-muted-foreground">{status}</p> : null}
+      </CardContent>
+    </Card>
+  );
+}
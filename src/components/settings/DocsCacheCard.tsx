@@ -0,0 +1,101 @@
+import * as React from "react";
+
+import type { AppSettings, DocsCacheSettings, DocsCacheStats } from "../../bindings";
+import { commands } from "../../bindings";
+import { Button } from "../ui/button";
+import {
+  Card,
+  CardContent,
+  CardDescription,
+  CardHeader,
+  CardTitle,
+} from "../ui/card";
+import { Checkbox } from "../ui/checkbox";
+import { Input } from "../ui/input";
+import { Label } from "../ui/label";
+
+export function DocsCacheCard() {
+  const [settings, setSettings] = React.useState<AppSettings | null>(null);
+  const [stats, setStats] = React.useState<DocsCacheStats | null>(null);
+  const [status, setStatus] = React.useState("");
+
+  React.useEffect(() => {
+    void commands.getAppSettings().then(setSettings);
+    void commands.getDocsCacheStats().then(setStats);
+  }, []);
+
+  if (!settings) {
+    return null;
+  }
+
+  const docs = settings.docs;
+  const update = (patch: Partial<DocsCacheSettings>) =>
+    setSettings({ ...settings, docs: { ...docs, ...patch } });
+
+  const save = async () => {
+    try {
+      const latest = await commands.getAppSettings();
+      setSettings(await commands.saveAppSettings({ ...latest, docs }));
+      setStatus("Docs cache settings saved.");
+    } catch (error) {
+      setStatus(String(error));
+    }
+  };
+
+  const clear = async () => {
+    try {
+      await commands.clearDocsCache();
+      setStats(await commands.getDocsCacheStats());
+      setStatus("Docs cache cleared.");
+    } catch (error) {
+      setStatus(String(error));
+    }
+  };
+
+  return (
+    <Card>
+      <CardHeader>
+        <CardTitle>Documentation Cache</CardTitle>
+        <CardDescription>
+          Context7 lookups are kept locally so repeat questions are instant and still work
+          when you're offline.
+        </CardDescription>
+      </CardHeader>
+      <CardContent className="space-y-3">
+        <div className="flex items-center gap-2">
+          <Label htmlFor="docs-ttl">Refresh after (hours)</Label>
+          <Input
+            id="docs-ttl"
+            type="number"
+            min={1}
+            className="w-24"
+            value={docs.ttlHours}
+            onChange={(event) => update({ ttlHours: Math.max(1, Number(event.target.value) || 1) })}
+          />
+        </div>
+        <label className="flex items-center gap-2 text-sm">
+          <Checkbox
+            checked={docs.offline}
+            onChange={(event) => update({ offline: event.target.checked })}
+          />
+          Offline mode (answer from the cache only)
+        </label>
+        {stats ? (
+          <p className="text-xs text-muted-foreground">
+            {stats.entries} cached lookups across {stats.libraries.length} libraries (
+            {Math.round(stats.bytes / 1024)} KB).
+          </p>
+        ) : null}
+        <div className="flex gap-2">
+          <Button size="sm" onClick={() => void save()}>
+            Save docs cache settings
+          </Button>
+          <Button size="sm" variant="outline" onClick={() => void clear()}>
+            Clear cache
+          </Button>
+        </div>
+        {status ? <p className="text-xs text-muted-foreground">{status}</p> : null}
+      </CardContent>
+    </Card>
+  );
+}
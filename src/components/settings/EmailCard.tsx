@@ -0,0 +1,129 @@
+import * as React from "react";
+
+import type { AppSettings, EmailSettings } from "../../bindings";
+import { commands } from "../../bindings";
+import { Button } from "../ui/button";
+import {
+  Card,
+  CardContent,
+  CardDescription,
+  CardHeader,
+  CardTitle,
+} from "../ui/card";
+import { Checkbox } from "../ui/checkbox";
+import { Input } from "../ui/input";
+import { Label } from "../ui/label";
+
+export function EmailCard() {
+  const [settings, setSettings] = React.useState<AppSettings | null>(null);
+  const [password, setPassword] = React.useState("");
+  const [status, setStatus] = React.useState("");
+
+  React.useEffect(() => {
+    void commands.getAppSettings().then(setSettings);
+  }, []);
+
+  if (!settings) {
+    return null;
+  }
+
+  const email = settings.email;
+  const update = (patch: Partial<EmailSettings>) =>
+    setSettings({ ...settings, email: { ...email, ...patch } });
+
+  const save = async () => {
+    try {
+      // Reload first so edits made in other cards aren't overwritten.
+      const latest = await commands.getAppSettings();
+      setSettings(await commands.saveAppSettings({ ...latest, email }));
+      if (password) {
+        await commands.setSmtpPassword(password);
+        setPassword("");
+      }
+      setStatus("Email settings saved.");
+    } catch (error) {
+      setStatus(String(error));
+    }
+  };
+
+  return (
+    <Card>
+      <CardHeader>
+        <CardTitle>Email Recap</CardTitle>
+        <CardDescription>
+          Send a session summary to co-hosts over SMTP. The password is kept in the
+          system keychain.
+        </CardDescription>
+      </CardHeader>
+      <CardContent className="space-y-3">
+        <div className="grid grid-cols-[1fr_6rem] gap-2">
+          <div className="space-y-1">
+            <Label htmlFor="smtp-host">SMTP host</Label>
+            <Input
+              id="smtp-host"
+              value={email.smtpHost}
+              onChange={(event) => update({ smtpHost: event.target.value })}
+              placeholder="smtp.gmail.com"
+            />
+          </div>
+          <div className="space-y-1">
+            <Label htmlFor="smtp-port">Port</Label>
+            <Input
+              id="smtp-port"
+              type="number"
+              value={email.smtpPort}
+              onChange={(event) => update({ smtpPort: Number(event.target.value) || 587 })}
+            />
+          </div>
+        </div>
+        <div className="grid grid-cols-2 gap-2">
+          <div className="space-y-1">
+            <Label htmlFor="smtp-username">Username</Label>
+            <Input
+              id="smtp-username"
+              value={email.username}
+              onChange={(event) => update({ username: event.target.value })}
+            />
+          </div>
+          <div className="space-y-1">
+            <Label htmlFor="smtp-password">Password</Label>
+            <Input
+              id="smtp-password"
+              type="password"
+              value={password}
+              onChange={(event) => setPassword(event.target.value)}
+              placeholder="unchanged"
+            />
+          </div>
+        </div>
+        <div className="space-y-1">
+          <Label htmlFor="smtp-recipients">Default recipients</Label>
+          <Input
+            id="smtp-recipients"
+            value={email.defaultRecipients.join(", ")}
+            onChange={(event) =>
+              update({
+                defaultRecipients: event.target.value
+                  .split(",")
+                  .map((entry) => entry.trim())
+                  .filter(Boolean),
+              })
+            }
+            placeholder="cohost@example.com"
+          />
+        </div>
+        <label className="flex items-center gap-2 text-sm">
+          <Checkbox
+            checked={email.sendOnSessionEnd}
+            onChange={(event) => update({ sendOnSessionEnd: event.target.checked })}
+          />
+          Send the recap automatically when a session ends
+        </label>
+        <Button size="sm" onClick={() => void save()}>
+          Save email settings
+        </Button>
+        {status ? <p className="text-xs text-muted-foreground">{status}</p> : null}
+      </CardContent>
+    </Card>
+  );
+}
@@ -0,0 +1,94 @@
+import * as React from "react";
+
+import type { AppSettings, FetchSettings } from "../../bindings";
+import { commands } from "../../bindings";
+import { Button } from "../ui/button";
+import {
+  Card,
+  CardContent,
+  CardDescription,
+  CardHeader,
+  CardTitle,
+} from "../ui/card";
+import { Checkbox } from "../ui/checkbox";
+import { Input } from "../ui/input";
+import { Label } from "../ui/label";
+
+export function FetchCard() {
+  const [settings, setSettings] = React.useState<AppSettings | null>(null);
+  const [status, setStatus] = React.useState("");
+
+  React.useEffect(() => {
+    void commands.getAppSettings().then(setSettings);
+  }, []);
+
+  if (!settings) {
+    return null;
+  }
+
+  const politeness = settings.fetch;
+  const update = (patch: Partial<FetchSettings>) =>
+    setSettings({ ...settings, fetch: { ...politeness, ...patch } });
+
+  const save = async () => {
+    try {
+      const latest = await commands.getAppSettings();
+      setSettings(await commands.saveAppSettings({ ...latest, fetch: politeness }));
+      setStatus("Fetch settings saved.");
+    } catch (error) {
+      setStatus(String(error));
+    }
+  };
+
+  return (
+    <Card>
+      <CardHeader>
+        <CardTitle>Fetch Politeness</CardTitle>
+        <CardDescription>
+          Limits how hard research hits any one site, for page fetches and the agent's browser
+          alike.
+        </CardDescription>
+      </CardHeader>
+      <CardContent className="space-y-3">
+        <div className="flex items-center gap-2">
+          <Label htmlFor="fetch-interval">Minimum gap per domain (ms)</Label>
+          <Input
+            id="fetch-interval"
+            type="number"
+            min={0}
+            step={100}
+            className="w-28"
+            value={politeness.minIntervalMs}
+            onChange={(event) =>
+              update({ minIntervalMs: Math.max(0, Number(event.target.value) || 0) })
+            }
+          />
+        </div>
+        <div className="flex items-center gap-2">
+          <Label htmlFor="fetch-concurrency">Requests in flight per domain</Label>
+          <Input
+            id="fetch-concurrency"
+            type="number"
+            min={1}
+            className="w-24"
+            value={politeness.maxConcurrentPerDomain}
+            onChange={(event) =>
+              update({ maxConcurrentPerDomain: Math.max(1, Number(event.target.value) || 1) })
+            }
+          />
+        </div>
+        <label className="flex items-center gap-2 text-sm">
+          <Checkbox
+            checked={politeness.respectRobots}
+            onChange={(event) => update({ respectRobots: event.target.checked })}
+          />
+          Respect robots.txt (skip disallowed pages, honor Crawl-delay)
+        </label>
+        <Button size="sm" onClick={() => void save()}>
+          Save fetch settings
+        </Button>
+        {status ? <p className="text-xs text-muted-foreground">{status}</p> : null}
+      </CardContent>
+    </Card>
+  );
+}
@@ -0,0 +1,98 @@
+import * as React from "react";
+
+import type { FocusGateSettings, MicGate } from "../../bindings";
+import { commands } from "../../bindings";
+import { Button } from "../ui/button";
+import {
+  Card,
+  CardContent,
+  CardDescription,
+  CardHeader,
+  CardTitle,
+} from "../ui/card";
+import { Checkbox } from "../ui/checkbox";
+import { Label } from "../ui/label";
+import { Textarea } from "../ui/textarea";
+
+export function FocusGateCard() {
+  const [focusGate, setFocusGate] = React.useState<FocusGateSettings | null>(null);
+  const [apps, setApps] = React.useState("");
+  const [gate, setGate] = React.useState<MicGate | null>(null);
+  const [status, setStatus] = React.useState("");
+
+  React.useEffect(() => {
+    void commands.getAppSettings().then((settings) => {
+      setFocusGate(settings.transcription.focusGate);
+      setApps(settings.transcription.focusGate.apps.join("\n"));
+    });
+    void commands.getMicGate().then(setGate);
+  }, []);
+
+  if (!focusGate) {
+    return null;
+  }
+
+  const save = async () => {
+    try {
+      const latest = await commands.getAppSettings();
+      const next = {
+        ...focusGate,
+        apps: apps
+          .split("\n")
+          .map((app) => app.trim())
+          .filter(Boolean),
+      };
+      const saved = await commands.saveAppSettings({
+        ...latest,
+        transcription: { ...latest.transcription, focusGate: next },
+      });
+      setFocusGate(saved.transcription.focusGate);
+      setApps(saved.transcription.focusGate.apps.join("\n"));
+      setStatus("Saved. The gate follows the frontmost app within a second.");
+    } catch (error) {
+      setStatus(String(error));
+    }
+  };
+
+  return (
+    <Card>
+      <CardHeader>
+        <CardTitle>Focus Gate</CardTitle>
+        <CardDescription>
+          Only listen to the microphone while one of these apps (or HeyJamie) is in front, so
+          Jamie ignores what you say on an unrelated call in another app. Names match
+          partially and ignore case. System audio is never gated. On Linux this needs X11 and{" "}
+          <code>xdotool</code>; where the frontmost app can't be read, the mic stays on.
+        </CardDescription>
+      </CardHeader>
+      <CardContent className="space-y-3">
+        <label className="flex items-center gap-2 text-sm">
+          <Checkbox
+            checked={focusGate.enabled}
+            onChange={(event) => setFocusGate({ ...focusGate, enabled: event.target.checked })}
+          />
+          Gate the microphone by frontmost app
+        </label>
+        <div className="space-y-2">
+          <Label htmlFor="focus-gate-apps">Apps, one per line</Label>
+          <Textarea
+            id="focus-gate-apps"
+            rows={4}
+            placeholder={"Riverside\nChrome"}
+            value={apps}
+            onChange={(event) => setApps(event.target.value)}
+          />
+        </div>
+        <Button size="sm" onClick={() => void save()}>
+          Save focus gate
+        </Button>
+        {gate?.frontmost ? (
+          <p className="text-xs text-muted-foreground">
+            {gate.frontmost} is in front; the microphone is {gate.open ? "on" : "gated"}.
+          </p>
+        ) : null}
+        {status ? <p className="text-xs text-muted-foreground">{status}</p> : null}
+      </CardContent>
+    </Card>
+  );
+}
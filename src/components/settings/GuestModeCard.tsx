@@ -0,0 +1,60 @@
+import * as React from "react";
+
+import { commands, events } from "../../bindings";
+import { setGuestModeFlag } from "../../guestMode";
+import { Button } from "../ui/button";
+import {
+  Card,
+  CardContent,
+  CardDescription,
+  CardHeader,
+  CardTitle,
+} from "../ui/card";
+
+export function GuestModeCard() {
+  const [active, setActive] = React.useState(false);
+  const [status, setStatus] = React.useState("");
+
+  React.useEffect(() => {
+    void commands.getGuestMode().then(setActive);
+    const unlisten = events.guestMode.listen((event) => {
+      setGuestModeFlag(event.payload.active);
+      // Every card loaded the previous profile's values.
+      window.location.reload();
+    });
+    return () => {
+      void unlisten.then((stop) => stop());
+    };
+  }, []);
+
+  const toggle = async () => {
+    try {
+      if (active) {
+        await commands.endGuestMode();
+      } else {
+        await commands.startGuestMode();
+      }
+    } catch (error) {
+      setStatus(String(error));
+    }
+  };
+
+  return (
+    <Card>
+      <CardHeader>
+        <CardTitle>Guest Mode</CardTitle>
+        <CardDescription>
+          Let someone else try HeyJamie on this machine. Guests start from a blank profile with
+          no access to your sessions, notes, shows, accounts or API keys, and everything they
+          create is erased when guest mode ends.
+        </CardDescription>
+      </CardHeader>
+      <CardContent className="space-y-3">
+        <Button size="sm" variant={active ? "outline" : "default"} onClick={() => void toggle()}>
+          {active ? "End guest mode and erase" : "Start guest mode"}
+        </Button>
+        {status ? <p className="text-xs text-muted-foreground">{status}</p> : null}
+      </CardContent>
+    </Card>
+  );
+}
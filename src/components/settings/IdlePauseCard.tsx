@@ -0,0 +1,79 @@
+import * as React from "react";
+
+import type { IdlePauseSettings } from "../../bindings";
+import { commands } from "../../bindings";
+import { Button } from "../ui/button";
+import {
+  Card,
+  CardContent,
+  CardDescription,
+  CardHeader,
+  CardTitle,
+} from "../ui/card";
+import { Checkbox } from "../ui/checkbox";
+import { Input } from "../ui/input";
+import { Label } from "../ui/label";
+
+export function IdlePauseCard() {
+  const [idlePause, setIdlePause] = React.useState<IdlePauseSettings | null>(null);
+  const [status, setStatus] = React.useState("");
+
+  React.useEffect(() => {
+    void commands.getAppSettings().then((settings) => setIdlePause(settings.idlePause));
+  }, []);
+
+  if (!idlePause) {
+    return null;
+  }
+
+  const save = async () => {
+    try {
+      const latest = await commands.getAppSettings();
+      const saved = await commands.saveAppSettings({ ...latest, idlePause });
+      setIdlePause(saved.idlePause);
+      setStatus("Saved.");
+    } catch (error) {
+      setStatus(String(error));
+    }
+  };
+
+  return (
+    <Card>
+      <CardHeader>
+        <CardTitle>Idle Pause</CardTitle>
+        <CardDescription>
+          Stop listening when nothing has been said and you haven't typed or clicked for a
+          while, so a forgotten session doesn't transcribe silence for hours. You get a
+          notification, and a Resume button in the main window picks up the same session. On
+          macOS, input in any app counts; on Linux that needs X11 and <code>xprintidle</code>,
+          otherwise only input in HeyJamie does.
+        </CardDescription>
+      </CardHeader>
+      <CardContent className="space-y-3">
+        <label className="flex items-center gap-2 text-sm">
+          <Checkbox
+            checked={idlePause.enabled}
+            onChange={(event) => setIdlePause({ ...idlePause, enabled: event.target.checked })}
+          />
+          Pause idle sessions
+        </label>
+        <div className="space-y-1">
+          <Label htmlFor="idle-pause-minutes">Minutes before pausing</Label>
+          <Input
+            id="idle-pause-minutes"
+            type="number"
+            min={1}
+            value={idlePause.idleMinutes}
+            onChange={(event) =>
+              setIdlePause({ ...idlePause, idleMinutes: Number(event.target.value) || 0 })
+            }
+          />
+        </div>
+        <Button size="sm" onClick={() => void save()}>
+          Save idle pause
+        </Button>
+        {status ? <p className="text-xs text-muted-foreground">{status}</p> : null}
+      </CardContent>
+    </Card>
+  );
+}
@@ -0,0 +1,161 @@
+import * as React from "react";
+
+import type { AppSettings, CaptureSource, InputTrack } from "../../bindings";
+import { commands } from "../../bindings";
+import { Button } from "../ui/button";
+import {
+  Card,
+  CardContent,
+  CardDescription,
+  CardHeader,
+  CardTitle,
+} from "../ui/card";
+import { Checkbox } from "../ui/checkbox";
+import { Input } from "../ui/input";
+import { Label } from "../ui/label";
+
+export function InputTracksCard() {
+  const [settings, setSettings] = React.useState<AppSettings | null>(null);
+  const [devices, setDevices] = React.useState<MediaDeviceInfo[]>([]);
+  const [status, setStatus] = React.useState("");
+
+  React.useEffect(() => {
+    void commands.getAppSettings().then(setSettings);
+    void navigator.mediaDevices
+      ?.enumerateDevices()
+      .then((all) => setDevices(all.filter((device) => device.kind === "audioinput")))
+      .catch(() => {});
+  }, []);
+
+  if (!settings) {
+    return null;
+  }
+
+  const tracks = settings.transcription.inputTracks;
+  const setTracks = (next: InputTrack[]) =>
+    setSettings({ ...settings, transcription: { ...settings.transcription, inputTracks: next } });
+  const updateTrack = (index: number, patch: Partial<InputTrack>) =>
+    setTracks(tracks.map((track, i) => (i === index ? { ...track, ...patch } : track)));
+
+  const save = async () => {
+    try {
+      const latest = await commands.getAppSettings();
+      setSettings(
+        await commands.saveAppSettings({
+          ...latest,
+          transcription: {
+            ...latest.transcription,
+            inputTracks: tracks,
+            webviewCapture: settings.transcription.webviewCapture,
+            captureSource: settings.transcription.captureSource,
+          },
+        })
+      );
+      setStatus(
+        settings.transcription.captureSource !== "microphone"
+          ? "Saved. System audio is captured the next time you start listening."
+          : tracks.length >= 2
+            ? "Input tracks saved. They are used the next time you start listening."
+            : "Saved. Recording uses the microphone chosen under Microphone."
+      );
+    } catch (error) {
+      setStatus(String(error));
+    }
+  };
+
+  return (
+    <Card>
+      <CardHeader>
+        <CardTitle>Input Tracks</CardTitle>
+        <CardDescription>
+          Record a host mic and a guest input side by side. Each track is transcribed on its
+          own and its lines are labeled with the track name.
+        </CardDescription>
+      </CardHeader>
+      <CardContent className="space-y-3">
+        {tracks.map((track, index) => (
+          <div key={index} className="flex items-center gap-2">
+            <Input
+              className="w-32"
+              value={track.name}
+              placeholder={index === 0 ? "Host" : "Guest"}
+              onChange={(event) => updateTrack(index, { name: event.target.value })}
+            />
+            <select
+              className="h-8 min-w-0 flex-1 rounded-md border bg-background px-2 text-sm"
+              value={track.deviceId}
+              onChange={(event) => updateTrack(index, { deviceId: event.target.value })}
+            >
+              <option value="">Default input</option>
+              {devices.map((device) => (
+                <option key={device.deviceId} value={device.deviceId}>
+                  {device.label || device.deviceId}
+                </option>
+              ))}
+            </select>
+            <Button
+              size="sm"
+              variant="outline"
+              onClick={() => setTracks(tracks.filter((_, i) => i !== index))}
+            >
+              Remove
+            </Button>
+          </div>
+        ))}
+        <label className="flex items-center gap-2 text-sm">
+          <Checkbox
+            checked={settings.transcription.webviewCapture}
+            onChange={(event) =>
+              setSettings({
+                ...settings,
+                transcription: { ...settings.transcription, webviewCapture: event.target.checked },
+              })
+            }
+          />
+          Capture a single mic in the webview instead of natively
+        </label>
+        <Label htmlFor="capture-source">Capture</Label>
+        <select
+          id="capture-source"
+          className="h-8 w-full rounded-md border bg-background px-2 text-sm"
+          value={settings.transcription.captureSource}
+          onChange={(event) =>
+            setSettings({
+              ...settings,
+              transcription: {
+                ...settings.transcription,
+                captureSource: event.target.value as CaptureSource,
+              },
+            })
+          }
+        >
+          <option value="microphone">Microphone</option>
+          <option value="system">System audio (what the speakers play)</option>
+          <option value="both">Microphone and system audio</option>
+        </select>
+        {settings.transcription.captureSource !== "microphone" ? (
+          <p className="text-xs text-muted-foreground">
+            System audio is recorded natively, so the tracks and webview option above are
+            ignored. On macOS, route the call's output through a loopback device such as
+            BlackHole.
+          </p>
+        ) : null}
+        <div className="flex gap-2">
+          <Button
+            size="sm"
+            variant="outline"
+            onClick={() =>
+              setTracks([...tracks, { name: tracks.length === 0 ? "Host" : "Guest", deviceId: "" }])
+            }
+          >
+            Add track
+          </Button>
+          <Button size="sm" onClick={() => void save()}>
+            Save input tracks
+          </Button>
+        </div>
+        {status ? <p className="text-xs text-muted-foreground">{status}</p> : null}
+      </CardContent>
+    </Card>
+  );
+}
@@ -0,0 +1,102 @@
+import * as React from "react";
+
+import type { Job } from "../../bindings";
+import { commands, events } from "../../bindings";
+import { Badge } from "../ui/badge";
+import { Button } from "../ui/button";
+import {
+  Card,
+  CardContent,
+  CardDescription,
+  CardHeader,
+  CardTitle,
+} from "../ui/card";
+
+function describe(job: Job) {
+  switch (job.spec.kind) {
+    case "modelDownload":
+      return `Download of ${job.spec.name}`;
+    case "fileTranscription":
+      return `Transcription of ${job.spec.path}`;
+    case "sessionRetranscription":
+      return `Retranscription of ${job.spec.sessionId} with ${job.spec.model}`;
+  }
+}
+
+export function JobsCard() {
+  const [jobs, setJobs] = React.useState<Job[]>([]);
+  const [status, setStatus] = React.useState("");
+
+  const reload = React.useCallback(async () => {
+    setJobs(await commands.listJobs());
+  }, []);
+
+  React.useEffect(() => {
+    void reload();
+    const unlisten = events.jobChanged.listen(() => void reload());
+    return () => {
+      void unlisten.then((stop) => stop());
+    };
+  }, [reload]);
+
+  const run = async (action: () => Promise<unknown>) => {
+    try {
+      await action();
+      setStatus("");
+    } catch (error) {
+      setStatus(String(error));
+    }
+    await reload();
+  };
+
+  return (
+    <Card>
+      <CardHeader>
+        <CardTitle>Unfinished Jobs</CardTitle>
+        <CardDescription>
+          Model downloads, file transcriptions and session retranscriptions that failed or
+          were cut off when HeyJamie quit. Resuming continues from where they stopped: the
+          bytes already downloaded, or the audio already transcribed.
+        </CardDescription>
+      </CardHeader>
+      <CardContent className="space-y-3">
+        {jobs.length === 0 ? (
+          <p className="text-sm text-muted-foreground">Nothing to resume.</p>
+        ) : null}
+        {jobs.map((job) => (
+          <div key={job.id} className="space-y-1 rounded border p-2 text-sm">
+            <div className="flex items-center gap-2">
+              <Badge>{job.status}</Badge>
+              <span className="flex-1 break-all">{describe(job)}</span>
+              {job.progress !== null ? (
+                <span className="text-xs text-muted-foreground">
+                  {Math.round(job.progress * 100)}%
+                </span>
+              ) : null}
+              {job.status !== "running" ? (
+                <>
+                  <Button
+                    size="sm"
+                    variant="ghost"
+                    onClick={() => void run(() => commands.resumeJob(job.id))}
+                  >
+                    Resume
+                  </Button>
+                  <Button
+                    size="sm"
+                    variant="ghost"
+                    onClick={() => void run(() => commands.discardJob(job.id))}
+                  >
+                    Discard
+                  </Button>
+                </>
+              ) : null}
+            </div>
+            {job.error ? <p className="text-xs text-muted-foreground">{job.error}</p> : null}
+          </div>
+        ))}
+        {status ? <p className="text-xs text-muted-foreground">{status}</p> : null}
+      </CardContent>
+    </Card>
+  );
+}
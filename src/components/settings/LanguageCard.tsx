@@ -0,0 +1,123 @@
+import * as React from "react";
+
+import type { AppSettings } from "../../bindings";
+import { commands } from "../../bindings";
+import {
+  Card,
+  CardContent,
+  CardDescription,
+  CardHeader,
+  CardTitle,
+} from "../ui/card";
+import { Label } from "../ui/label";
+
+const RESPONSE_LANGUAGES: Array<{ value: string; label: string }> = [
+  { value: "auto", label: "Match the speaker" },
+  { value: "off", label: "No preference" },
+  { value: "en", label: "English" },
+  { value: "es", label: "Spanish" },
+  { value: "fr", label: "French" },
+  { value: "de", label: "German" },
+  { value: "it", label: "Italian" },
+  { value: "pt", label: "Portuguese" },
+  { value: "nl", label: "Dutch" },
+  { value: "ja", label: "Japanese" },
+  { value: "ko", label: "Korean" },
+  { value: "zh", label: "Chinese" },
+  { value: "ru", label: "Russian" },
+];
+
+const TRANSCRIPTION_LANGUAGES: Array<{ value: string; label: string }> = [
+  { value: "", label: "English (default)" },
+  { value: "auto", label: "Detect automatically" },
+  ...RESPONSE_LANGUAGES.filter((option) => !["auto", "off", "en"].includes(option.value)),
+];
+
+export function LanguageCard() {
+  const [settings, setSettings] = React.useState<AppSettings | null>(null);
+  const [status, setStatus] = React.useState("");
+
+  React.useEffect(() => {
+    void commands.getAppSettings().then(setSettings);
+  }, []);
+
+  if (!settings) {
+    return null;
+  }
+
+  const changeLanguage = async (responseLanguage: string) => {
+    try {
+      const latest = await commands.getAppSettings();
+      setSettings(
+        await commands.saveAppSettings({
+          ...latest,
+          language: { ...latest.language, responseLanguage },
+        })
+      );
+      setStatus("Reply language saved.");
+    } catch (error) {
+      setStatus(String(error));
+    }
+  };
+
+  const changeTranscriptionLanguage = async (language: string) => {
+    try {
+      const latest = await commands.getAppSettings();
+      setSettings(
+        await commands.saveAppSettings({
+          ...latest,
+          transcription: { ...latest.transcription, language },
+        })
+      );
+      setStatus(
+        language && language !== "en"
+          ? "Transcription language saved. Use a multilingual model (no .en suffix)."
+          : "Transcription language saved."
+      );
+    } catch (error) {
+      setStatus(String(error));
+    }
+  };
+
+  return (
+    <Card>
+      <CardHeader>
+        <CardTitle>Language</CardTitle>
+        <CardDescription>
+          Language whisper transcribes and language Jamie answers in. Per-show reply overrides live under{" "}
+          <code className="rounded bg-muted px-1 py-0.5 text-[11px]">language.shows</code> in
+          settings.json and match the start of the session title.
+        </CardDescription>
+      </CardHeader>
+      <CardContent className="space-y-2">
+        <Label htmlFor="response-language">Reply language</Label>
+        <select
+          id="response-language"
+          className="h-8 w-full rounded-md border bg-background px-2 text-sm"
+          value={settings.language.responseLanguage}
+          onChange={(event) => void changeLanguage(event.target.value)}
+        >
+          {RESPONSE_LANGUAGES.map((option) => (
+            <option key={option.value} value={option.value}>
+              {option.label}
+            </option>
+          ))}
+        </select>
+        <Label htmlFor="transcription-language">Spoken language</Label>
+        <select
+          id="transcription-language"
+          className="h-8 w-full rounded-md border bg-background px-2 text-sm"
+          value={settings.transcription.language}
+          onChange={(event) => void changeTranscriptionLanguage(event.target.value)}
+        >
+          {TRANSCRIPTION_LANGUAGES.map((option) => (
+            <option key={option.value || "default"} value={option.value}>
+              {option.label}
+            </option>
+          ))}
+        </select>
+        {status ? <p className="text-xs text-muted-foreground">{status}</p> : null}
+      </CardContent>
+    </Card>
+  );
+}
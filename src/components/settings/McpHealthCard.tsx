@@ -0,0 +1,83 @@
+import * as React from "react";
+
+import type { ServerHealth } from "../../bindings";
+import { commands, events } from "../../bindings";
+import { Badge } from "../ui/badge";
+import { Button } from "../ui/button";
+import {
+  Card,
+  CardContent,
+  CardDescription,
+  CardHeader,
+  CardTitle,
+} from "../ui/card";
+
+function describe(server: ServerHealth) {
+  if (server.liveness === "alive") {
+    return server.latencyMs === null ? "Responding" : `Responding in ${server.latencyMs} ms`;
+  }
+  const failures = server.consecutiveFailures > 1 ? ` (${server.consecutiveFailures} checks)` : "";
+  return `${server.lastError ?? "Not responding"}${failures}`;
+}
+
+export function McpHealthCard() {
+  const [servers, setServers] = React.useState<ServerHealth[]>([]);
+  const [checking, setChecking] = React.useState(false);
+  const [status, setStatus] = React.useState("");
+
+  React.useEffect(() => {
+    void commands.getMcpServerHealth().then(setServers);
+    const unlisten = events.mcpServerHealth.listen(() => {
+      void commands.getMcpServerHealth().then(setServers);
+    });
+    return () => {
+      void unlisten.then((stop) => stop());
+    };
+  }, []);
+
+  const checkNow = async () => {
+    setChecking(true);
+    setStatus("");
+    try {
+      setServers(await commands.checkMcpServerHealth());
+    } catch (error) {
+      setStatus(String(error));
+    } finally {
+      setChecking(false);
+    }
+  };
+
+  return (
+    <Card>
+      <CardHeader>
+        <CardTitle>MCP Server Health</CardTitle>
+        <CardDescription>
+          URL servers and the Excalidraw canvas are checked every 30 seconds. Command servers are
+          started and pinged every five minutes, so a server that hangs shows up here and not just
+          one that exits.
+        </CardDescription>
+      </CardHeader>
+      <CardContent className="space-y-3">
+        {servers.length === 0 ? (
+          <p className="text-xs text-muted-foreground">No checks have run yet.</p>
+        ) : (
+          <ul className="space-y-2">
+            {servers.map((server) => (
+              <li key={server.name} className="flex items-center gap-2 text-sm">
+                <Badge variant={server.liveness === "alive" ? "default" : "outline"}>
+                  {server.liveness === "alive" ? "Up" : "Down"}
+                </Badge>
+                <span className="font-medium">{server.name}</span>
+                <span className="text-xs text-muted-foreground">{describe(server)}</span>
+              </li>
+            ))}
+          </ul>
+        )}
+        <Button size="sm" variant="outline" disabled={checking} onClick={() => void checkNow()}>
+          {checking ? "Checking…" : "Check now"}
+        </Button>
+        {status ? <p className="text-xs text-muted-foreground">{status}</p> : null}
+      </CardContent>
+    </Card>
+  );
+}
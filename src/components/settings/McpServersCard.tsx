@@ -0,0 +1,460 @@
+import * as React from "react";
+
+import type { McpHistoryStatus, McpProcessStatus, McpServer } from "../../bindings";
+import { commands, events } from "../../bindings";
+import { Button } from "../ui/button";
+import {
+  Card,
+  CardContent,
+  CardDescription,
+  CardHeader,
+  CardTitle,
+} from "../ui/card";
+import { Checkbox } from "../ui/checkbox";
+import { Input } from "../ui/input";
+import { Label } from "../ui/label";
+import { Textarea } from "../ui/textarea";
+
+type Draft = {
+  name: string;
+  kind: "command" | "url";
+  command: string;
+  args: string;
+  cwd: string;
+  env: string;
+  url: string;
+  sse: boolean;
+  launch: string;
+  launchCwd: string;
+  allowTools: string;
+  denyTools: string;
+};
+
+const EMPTY_DRAFT: Draft = {
+  name: "",
+  kind: "command",
+  command: "",
+  args: "",
+  cwd: "",
+  env: "",
+  url: "",
+  sse: false,
+  launch: "",
+  launchCwd: "",
+  allowTools: "",
+  denyTools: "",
+};
+
+function toDraft(server: McpServer): Draft {
+  return {
+    name: server.name,
+    kind: server.url ? "url" : "command",
+    command: server.command ?? "",
+    args: server.args.join("\n"),
+    cwd: server.cwd ?? "",
+    env: Object.entries(server.env)
+      .map(([key, value]) => `${key}=${value}`)
+      .join("\n"),
+    url: server.url ?? "",
+    sse: server.transport === "sse",
+    launch: server.launch ? [server.launch.command, ...server.launch.args].join(" ") : "",
+    launchCwd: server.launch?.cwd ?? "",
+    allowTools: server.allowTools?.join(", ") ?? "",
+    denyTools: server.denyTools?.join(", ") ?? "",
+  };
+}
+
+function lines(text: string): string[] {
+  return text.split("\n").filter((line) => line.trim() !== "");
+}
+
+function toolList(text: string): string[] | null {
+  const tools = text.split(",").map((tool) => tool.trim()).filter(Boolean);
+  return tools.length > 0 ? tools : null;
+}
+
+function fromDraft(draft: Draft, existing: McpServer | null): McpServer {
+  const isCommand = draft.kind === "command";
+  const [launchCommand, ...launchArgs] = draft.launch.split(/\s+/).filter(Boolean);
+  const env: Record<string, string> = {};
+  if (isCommand) {
+    for (const line of lines(draft.env)) {
+      const split = line.indexOf("=");
+      env[split < 0 ? line : line.slice(0, split)] = split < 0 ? "" : line.slice(split + 1);
+    }
+  }
+  return {
+    name: draft.name,
+    enabled: existing?.enabled ?? true,
+    command: isCommand ? draft.command : null,
+    args: isCommand ? lines(draft.args) : [],
+    cwd: isCommand && draft.cwd.trim() ? draft.cwd : null,
+    env,
+    url: isCommand ? null : draft.url,
+    transport: !isCommand && draft.sse ? "sse" : null,
+    launch:
+      !isCommand && launchCommand
+        ? {
+            command: launchCommand,
+            args: launchArgs,
+            cwd: draft.launchCwd.trim() || null,
+            env: existing?.launch?.env ?? {},
+          }
+        : null,
+    allowTools: toolList(draft.allowTools),
+    denyTools: toolList(draft.denyTools),
+  };
+}
+
+function describeProcess(process: McpProcessStatus | undefined): string {
+  if (!process || process.state === "agent" || process.state === "stopped") {
+    return "";
+  }
+  if (process.state === "running") {
+    return process.restarts > 0 ? ` · running (restarted ${process.restarts}×)` : " · running";
+  }
+  return ` · ${process.state}${process.lastError ? `: ${process.lastError}` : ""}`;
+}
+
+function describe(server: McpServer): string {
+  if (server.url) {
+    return `${server.transport ?? "http"} · ${server.url}`;
+  }
+  return `stdio · ${[server.command, ...server.args].join(" ")}`;
+}
+
+export function McpServersCard() {
+  const [servers, setServers] = React.useState<McpServer[]>([]);
+  const [path, setPath] = React.useState("");
+  const [history, setHistory] = React.useState<McpHistoryStatus | null>(null);
+  const [processes, setProcesses] = React.useState<Record<string, McpProcessStatus>>({});
+  // The server being edited (null while adding), or undefined with no form open.
+  const [editing, setEditing] = React.useState<McpServer | null | undefined>(undefined);
+  const [draft, setDraft] = React.useState<Draft>(EMPTY_DRAFT);
+  const [status, setStatus] = React.useState("");
+
+  const refreshHistory = React.useCallback(async () => {
+    try {
+      setHistory(await commands.getMcpConfigHistory());
+    } catch {
+      setHistory(null);
+    }
+  }, []);
+
+  const refreshProcesses = React.useCallback(async () => {
+    const statuses = await commands.mcpServerStatus();
+    setProcesses(Object.fromEntries(statuses.map((process) => [process.name, process])));
+  }, []);
+
+  const load = React.useCallback(async () => {
+    try {
+      const listing = await commands.listMcpServers();
+      setServers(listing.servers);
+      setPath(listing.path);
+      await refreshProcesses();
+    } catch (error) {
+      setStatus(`Failed to load MCP servers: ${String(error)}`);
+    }
+    await refreshHistory();
+  }, [refreshHistory, refreshProcesses]);
+
+  React.useEffect(() => {
+    void load();
+    const unlisten = events.mcpProcessChanged.listen((event) => {
+      const { process } = event.payload;
+      setProcesses((prev) => ({ ...prev, [process.name]: process }));
+    });
+    return () => {
+      void unlisten.then((stop) => stop());
+    };
+  }, [load]);
+
+  const apply = async (change: () => Promise<{ servers: McpServer[]; path: string }>, done: string) => {
+    try {
+      const listing = await change();
+      setServers(listing.servers);
+      setPath(listing.path);
+      setStatus(done);
+      setEditing(undefined);
+    } catch (error) {
+      setStatus(String(error));
+    }
+    await refreshHistory();
+    await refreshProcesses();
+  };
+
+  const control = async (action: "start" | "stop" | "restart", name: string) => {
+    try {
+      const process = await (action === "start"
+        ? commands.startMcpServer(name)
+        : action === "stop"
+          ? commands.stopMcpServer(name)
+          : commands.restartMcpServer(name));
+      setProcesses((prev) => ({ ...prev, [name]: process }));
+      setStatus(process.lastError && process.state === "failed" ? process.lastError : "");
+    } catch (error) {
+      setStatus(String(error));
+    }
+  };
+
+  const startEdit = (server: McpServer | null) => {
+    setEditing(server);
+    setDraft(server ? toDraft(server) : EMPTY_DRAFT);
+  };
+
+  const save = () => {
+    const server = fromDraft(draft, editing ?? null);
+    void apply(
+      () => (editing ? commands.updateMcpServer(editing.name, server) : commands.addMcpServer(server)),
+      `Saved ${server.name.trim()}.`
+    );
+  };
+
+  const step = async (redo: boolean) => {
+    const label = redo ? history?.redoLabel : history?.undoLabel;
+    try {
+      await (redo ? commands.mcpConfigRedo() : commands.mcpConfigUndo());
+      setStatus(`${redo ? "Redid" : "Undid"} ${label ?? "change"}.`);
+    } catch (error) {
+      setStatus(String(error));
+    }
+    await load();
+  };
+
+  const test = async () => {
+    setStatus("Testing MCP servers...");
+    try {
+      setStatus(await commands.testMcpConfig());
+    } catch (error) {
+      setStatus(`MCP test failed: ${String(error)}`);
+    }
+  };
+
+  return (
+    <Card>
+      <CardHeader>
+        <CardTitle>MCP Servers</CardTitle>
+        <CardDescription>
+          Servers the agent can use. A command server is started by HeyJamie and spoken to over
+          stdio; a URL server is already running somewhere, or HeyJamie can keep it running with a
+          start command. Started servers, and the Excalidraw canvas server, are restarted if they
+          crash. Turn a server off to keep its settings without loading it. Tool lists narrow
+          which of its tools the agent sees.
+        </CardDescription>
+      </CardHeader>
+      <CardContent className="space-y-3">
+        {path ? <p className="text-xs text-muted-foreground">Config file location: {path}</p> : null}
+        {servers.map((server) => (
+          <div key={server.name} className="flex items-center gap-2 text-sm">
+            <Checkbox
+              checked={server.enabled}
+              onChange={(event) =>
+                void apply(
+                  () => commands.setMcpServerEnabled(server.name, event.target.checked),
+                  `${server.name} ${event.target.checked ? "on" : "off"}.`
+                )
+              }
+            />
+            <span className="min-w-0 flex-1 truncate">
+              {server.name}
+              <span className="ml-2 text-xs text-muted-foreground">
+                {describe(server)}
+                {describeProcess(processes[server.name])}
+              </span>
+            </span>
+            {processes[server.name] && processes[server.name].state !== "agent" && server.enabled ? (
+              processes[server.name].pid !== null ? (
+                <>
+                  <Button size="sm" variant="ghost" onClick={() => void control("restart", server.name)}>
+                    Restart
+                  </Button>
+                  <Button size="sm" variant="ghost" onClick={() => void control("stop", server.name)}>
+                    Stop
+                  </Button>
+                </>
+              ) : (
+                <Button size="sm" variant="ghost" onClick={() => void control("start", server.name)}>
+                  Start
+                </Button>
+              )
+            ) : null}
+            <Button size="sm" variant="outline" onClick={() => startEdit(server)}>
+              Edit
+            </Button>
+            <Button
+              size="sm"
+              variant="ghost"
+              onClick={() => void apply(() => commands.removeMcpServer(server.name), `Removed ${server.name}.`)}
+            >
+              Remove
+            </Button>
+          </div>
+        ))}
+        {editing !== undefined ? (
+          <div className="space-y-2 rounded border p-3">
+            <div className="flex items-center gap-2">
+              <Label htmlFor="mcp-server-name">Name</Label>
+              <Input
+                id="mcp-server-name"
+                value={draft.name}
+                placeholder="chrome-devtools"
+                onChange={(event) => setDraft({ ...draft, name: event.target.value })}
+              />
+            </div>
+            <div className="flex gap-4 text-sm">
+              <label className="flex items-center gap-2">
+                <input
+                  type="radio"
+                  checked={draft.kind === "command"}
+                  onChange={() => setDraft({ ...draft, kind: "command" })}
+                />
+                Command
+              </label>
+              <label className="flex items-center gap-2">
+                <input
+                  type="radio"
+                  checked={draft.kind === "url"}
+                  onChange={() => setDraft({ ...draft, kind: "url" })}
+                />
+                URL
+              </label>
+            </div>
+            {draft.kind === "command" ? (
+              <>
+                <div className="flex items-center gap-2">
+                  <Label htmlFor="mcp-server-command">Command</Label>
+                  <Input
+                    id="mcp-server-command"
+                    value={draft.command}
+                    placeholder="npx"
+                    onChange={(event) => setDraft({ ...draft, command: event.target.value })}
+                  />
+                </div>
+                <div className="space-y-1">
+                  <Label htmlFor="mcp-server-args">Arguments (one per line)</Label>
+                  <Textarea
+                    id="mcp-server-args"
+                    rows={3}
+                    value={draft.args}
+                    onChange={(event) => setDraft({ ...draft, args: event.target.value })}
+                  />
+                </div>
+                <div className="flex items-center gap-2">
+                  <Label htmlFor="mcp-server-cwd">Working directory</Label>
+                  <Input
+                    id="mcp-server-cwd"
+                    value={draft.cwd}
+                    placeholder="~/mcp_excalidraw"
+                    onChange={(event) => setDraft({ ...draft, cwd: event.target.value })}
+                  />
+                </div>
+                <div className="space-y-1">
+                  <Label htmlFor="mcp-server-env">Environment (KEY=value per line)</Label>
+                  <Textarea
+                    id="mcp-server-env"
+                    rows={2}
+                    value={draft.env}
+                    onChange={(event) => setDraft({ ...draft, env: event.target.value })}
+                  />
+                </div>
+              </>
+            ) : (
+              <>
+                <div className="flex items-center gap-2">
+                  <Label htmlFor="mcp-server-url">URL</Label>
+                  <Input
+                    id="mcp-server-url"
+                    value={draft.url}
+                    placeholder="http://localhost:8000/mcp"
+                    onChange={(event) => setDraft({ ...draft, url: event.target.value })}
+                  />
+                </div>
+                <label className="flex items-center gap-2 text-sm">
+                  <Checkbox
+                    checked={draft.sse}
+                    onChange={(event) => setDraft({ ...draft, sse: event.target.checked })}
+                  />
+                  Server-sent events (SSE) instead of streamable HTTP
+                </label>
+                <div className="flex items-center gap-2">
+                  <Label htmlFor="mcp-server-launch">Start command</Label>
+                  <Input
+                    id="mcp-server-launch"
+                    value={draft.launch}
+                    placeholder="already running"
+                    onChange={(event) => setDraft({ ...draft, launch: event.target.value })}
+                  />
+                </div>
+                <div className="flex items-center gap-2">
+                  <Label htmlFor="mcp-server-launch-cwd">Start in</Label>
+                  <Input
+                    id="mcp-server-launch-cwd"
+                    value={draft.launchCwd}
+                    disabled={!draft.launch.trim()}
+                    onChange={(event) => setDraft({ ...draft, launchCwd: event.target.value })}
+                  />
+                </div>
+              </>
+            )}
+            <div className="flex items-center gap-2">
+              <Label htmlFor="mcp-server-allow">Only these tools</Label>
+              <Input
+                id="mcp-server-allow"
+                value={draft.allowTools}
+                placeholder="all tools"
+                onChange={(event) => setDraft({ ...draft, allowTools: event.target.value })}
+              />
+            </div>
+            <div className="flex items-center gap-2">
+              <Label htmlFor="mcp-server-deny">Hide these tools</Label>
+              <Input
+                id="mcp-server-deny"
+                value={draft.denyTools}
+                placeholder="none"
+                onChange={(event) => setDraft({ ...draft, denyTools: event.target.value })}
+              />
+            </div>
+            <div className="flex gap-2">
+              <Button size="sm" onClick={save}>
+                {editing ? "Save server" : "Add server"}
+              </Button>
+              <Button size="sm" variant="ghost" onClick={() => setEditing(undefined)}>
+                Cancel
+              </Button>
+            </div>
+          </div>
+        ) : null}
+        <div className="flex flex-wrap gap-2">
+          <Button size="sm" onClick={() => startEdit(null)}>
+            Add server
+          </Button>
+          <Button size="sm" variant="outline" onClick={() => void load()}>
+            Reload
+          </Button>
+          <Button
+            size="sm"
+            variant="outline"
+            disabled={!history?.canUndo}
+            title={history?.undoLabel ? `Undo ${history.undoLabel}` : undefined}
+            onClick={() => void step(false)}
+          >
+            Undo
+          </Button>
+          <Button
+            size="sm"
+            variant="outline"
+            disabled={!history?.canRedo}
+            title={history?.redoLabel ? `Redo ${history.redoLabel}` : undefined}
+            onClick={() => void step(true)}
+          >
+            Redo
+          </Button>
+          <Button size="sm" variant="ghost" onClick={() => void test()}>
+            Test MCP servers
+          </Button>
+        </div>
+        {status ? <p className="text-xs text-muted-foreground">{status}</p> : null}
+      </CardContent>
+    </Card>
+  );
+}
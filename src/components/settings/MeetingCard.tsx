@@ -0,0 +1,92 @@
+import * as React from "react";
+
+import type { AppSettings, MeetingSettings } from "../../bindings";
+import { commands } from "../../bindings";
+import { NO_PERSONA_ID, PERSONAS } from "../../personas";
+import { Button } from "../ui/button";
+import {
+  Card,
+  CardContent,
+  CardDescription,
+  CardHeader,
+  CardTitle,
+} from "../ui/card";
+import { Checkbox } from "../ui/checkbox";
+import { Label } from "../ui/label";
+
+export function MeetingCard() {
+  const [settings, setSettings] = React.useState<AppSettings | null>(null);
+  const [status, setStatus] = React.useState("");
+
+  React.useEffect(() => {
+    void commands.getAppSettings().then(setSettings);
+  }, []);
+
+  if (!settings) {
+    return null;
+  }
+
+  const meeting = settings.meeting;
+  const update = (patch: Partial<MeetingSettings>) =>
+    setSettings({ ...settings, meeting: { ...meeting, ...patch } });
+
+  const save = async () => {
+    try {
+      const latest = await commands.getAppSettings();
+      setSettings(await commands.saveAppSettings({ ...latest, meeting }));
+      setStatus("Meeting settings saved.");
+    } catch (error) {
+      setStatus(String(error));
+    }
+  };
+
+  return (
+    <Card>
+      <CardHeader>
+        <CardTitle>Meetings</CardTitle>
+        <CardDescription>
+          Notice when Zoom, Teams, Webex, Slack or a browser call starts using the microphone.
+          On macOS only Zoom meetings can be detected.
+        </CardDescription>
+      </CardHeader>
+      <CardContent className="space-y-3">
+        <label className="flex items-center gap-2 text-sm">
+          <Checkbox
+            checked={meeting.enabled}
+            onChange={(event) => update({ enabled: event.target.checked })}
+          />
+          Detect meetings
+        </label>
+        <label className="flex items-center gap-2 text-sm">
+          <Checkbox
+            checked={meeting.autoStart}
+            disabled={!meeting.enabled}
+            onChange={(event) => update({ autoStart: event.target.checked })}
+          />
+          Start listening automatically (and stop when the meeting ends)
+        </label>
+        <div className="flex items-center gap-2">
+          <Label htmlFor="meeting-persona">Persona for meetings</Label>
+          <select
+            id="meeting-persona"
+            className="h-8 rounded-md border bg-background px-2 text-sm"
+            disabled={!meeting.enabled || !meeting.autoStart}
+            value={meeting.personaId}
+            onChange={(event) => update({ personaId: event.target.value })}
+          >
+            <option value={NO_PERSONA_ID}>Keep current</option>
+            {PERSONAS.map((persona) => (
+              <option key={persona.id} value={persona.id}>
+                {persona.name}
+              </option>
+            ))}
+          </select>
+        </div>
+        <Button size="sm" onClick={() => void save()}>
+          Save meeting settings
+        </Button>
+        {status ? <p className="text-xs text-muted-foreground">{status}</p> : null}
+      </CardContent>
+    </Card>
+  );
+}
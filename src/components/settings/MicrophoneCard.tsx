@@ -0,0 +1,87 @@
+import * as React from "react";
+
+import type { AudioDevice } from "../../bindings";
+import { commands, events } from "../../bindings";
+import { Button } from "../ui/button";
+import {
+  Card,
+  CardContent,
+  CardDescription,
+  CardHeader,
+  CardTitle,
+} from "../ui/card";
+import { Label } from "../ui/label";
+
+export function MicrophoneCard() {
+  const [devices, setDevices] = React.useState<AudioDevice[]>([]);
+  const [status, setStatus] = React.useState("");
+
+  const reload = React.useCallback(async () => {
+    try {
+      setDevices(await commands.listAudioDevices());
+    } catch (error) {
+      setStatus(String(error));
+    }
+  }, []);
+
+  React.useEffect(() => {
+    void reload();
+    const unlisten = events.deviceChanged.listen(() => void reload());
+    return () => {
+      void unlisten.then((stop) => stop());
+    };
+  }, [reload]);
+
+  const selected = devices.find((device) => device.selected)?.name ?? "";
+
+  const choose = async (name: string) => {
+    try {
+      await commands.setAudioDevice(name || null);
+      setStatus(
+        name
+          ? `Recording from ${name} the next time you start listening.`
+          : "Recording follows the system default input."
+      );
+    } catch (error) {
+      setStatus(String(error));
+    }
+    await reload();
+  };
+
+  return (
+    <Card>
+      <CardHeader>
+        <CardTitle>Microphone</CardTitle>
+        <CardDescription>
+          Pick the interface or mic to record from instead of whatever the system default is.
+          If it's disconnected, recording falls back to the default until it's back. Input
+          tracks, when set up, choose their own devices.
+        </CardDescription>
+      </CardHeader>
+      <CardContent className="space-y-3">
+        <div className="flex items-center gap-2">
+          <Label htmlFor="microphone-device">Input</Label>
+          <select
+            id="microphone-device"
+            className="h-8 flex-1 rounded-md border bg-background px-2 text-sm"
+            value={selected}
+            onChange={(event) => void choose(event.target.value)}
+          >
+            <option value="">System default</option>
+            {devices.map((device) => (
+              <option key={device.name} value={device.name}>
+                {device.name}
+                {device.isDefault ? " (default)" : ""}
+                {device.sampleRate ? ` · ${device.sampleRate / 1000} kHz` : ""}
+              </option>
+            ))}
+          </select>
+          <Button size="sm" variant="outline" onClick={() => void reload()}>
+            Refresh
+          </Button>
+        </div>
+        {status ? <p className="text-xs text-muted-foreground">{status}</p> : null}
+      </CardContent>
+    </Card>
+  );
+}
@@ -0,0 +1,126 @@
+import * as React from "react";
+
+import type { AppSettings, PostSessionSettings } from "../../bindings";
+import { commands } from "../../bindings";
+import { Button } from "../ui/button";
+import {
+  Card,
+  CardContent,
+  CardDescription,
+  CardHeader,
+  CardTitle,
+} from "../ui/card";
+import { Checkbox } from "../ui/checkbox";
+import { Input } from "../ui/input";
+import { Label } from "../ui/label";
+
+type StepToggle = keyof Pick<
+  PostSessionSettings,
+  "finalizeTranscript" | "retranscribe" | "showNotes" | "chapters" | "deliverToSlack"
+>;
+
+const STEPS: { key: StepToggle; label: string }[] = [
+  { key: "finalizeTranscript", label: "Finalize transcript (transcript.txt)" },
+  { key: "retranscribe", label: "Retranscribe with a larger model (transcript-hq.txt)" },
+  { key: "showNotes", label: "Generate show notes (show-notes.md)" },
+  { key: "chapters", label: "Export chapters (chapters.txt)" },
+  { key: "deliverToSlack", label: "Post show notes to Slack" },
+];
+
+export function PostSessionCard() {
+  const [settings, setSettings] = React.useState<AppSettings | null>(null);
+  const [webhook, setWebhook] = React.useState("");
+  const [status, setStatus] = React.useState("");
+
+  React.useEffect(() => {
+    void commands.getAppSettings().then(setSettings);
+  }, []);
+
+  if (!settings) {
+    return null;
+  }
+
+  const postSession = settings.postSession;
+  const update = (patch: Partial<PostSessionSettings>) =>
+    setSettings({ ...settings, postSession: { ...postSession, ...patch } });
+
+  const save = async () => {
+    try {
+      // Reload first so edits made in other cards aren't overwritten.
+      const latest = await commands.getAppSettings();
+      setSettings(await commands.saveAppSettings({ ...latest, postSession }));
+      if (webhook) {
+        await commands.setSlackWebhook(webhook);
+        setWebhook("");
+      }
+      setStatus("Wrap-up settings saved.");
+    } catch (error) {
+      setStatus(String(error));
+    }
+  };
+
+  return (
+    <Card>
+      <CardHeader>
+        <CardTitle>Session Wrap-Up</CardTitle>
+        <CardDescription>
+          Steps run in order in the background when a session ends. A failed step is
+          recorded and the rest still run. Files land in the session's exports folder.
+        </CardDescription>
+      </CardHeader>
+      <CardContent className="space-y-3">
+        <label className="flex items-center gap-2 text-sm">
+          <Checkbox
+            checked={postSession.enabled}
+            onChange={(event) => update({ enabled: event.target.checked })}
+          />
+          Run the wrap-up pipeline when a session ends
+        </label>
+        <div className="space-y-2 pl-6">
+          {STEPS.map((step) => (
+            <label key={step.key} className="flex items-center gap-2 text-sm">
+              <Checkbox
+                checked={postSession[step.key]}
+                disabled={!postSession.enabled}
+                onChange={(event) => {
+                  const patch: Partial<PostSessionSettings> = {};
+                  patch[step.key] = event.target.checked;
+                  update(patch);
+                }}
+              />
+              {step.label}
+            </label>
+          ))}
+        </div>
+        <div className="grid grid-cols-2 gap-2">
+          <div className="space-y-1">
+            <Label htmlFor="retranscribe-model">Retranscribe model</Label>
+            <Input
+              id="retranscribe-model"
+              value={postSession.retranscribeModel}
+              onChange={(event) => update({ retranscribeModel: event.target.value })}
+              placeholder="ggml-large-v3.bin"
+            />
+          </div>
+          <div className="space-y-1">
+            <Label htmlFor="slack-webhook">Slack webhook</Label>
+            <Input
+              id="slack-webhook"
+              type="password"
+              value={webhook}
+              onChange={(event) => setWebhook(event.target.value)}
+              placeholder="unchanged"
+            />
+          </div>
+        </div>
+        <p className="text-xs text-muted-foreground">
+          Retranscribing keeps each segment's audio in the session folder while recording.
+        </p>
+        <Button size="sm" onClick={() => void save()}>
+          Save wrap-up settings
+        </Button>
+        {status ? <p className="text-xs text-muted-foreground">{status}</p> : null}
+      </CardContent>
+    </Card>
+  );
+}
@@ -0,0 +1,127 @@
+import * as React from "react";
+
+import type { AppSettings, PowerSettings, PowerState } from "../../bindings";
+import { commands } from "../../bindings";
+import { Button } from "../ui/button";
+import {
+  Card,
+  CardContent,
+  CardDescription,
+  CardHeader,
+  CardTitle,
+} from "../ui/card";
+import { Checkbox } from "../ui/checkbox";
+import { Input } from "../ui/input";
+import { Label } from "../ui/label";
+
+function describeSource(state: PowerState) {
+  const percent = state.batteryPercent === null ? "" : ` (${state.batteryPercent}%)`;
+  switch (state.source) {
+    case "battery":
+      return `On battery${percent}`;
+    case "ac":
+      return `Plugged in${percent}`;
+    default:
+      return "No battery detected";
+  }
+}
+
+export function PowerCard() {
+  const [settings, setSettings] = React.useState<AppSettings | null>(null);
+  const [state, setState] = React.useState<PowerState | null>(null);
+  const [status, setStatus] = React.useState("");
+
+  React.useEffect(() => {
+    void commands.getAppSettings().then(setSettings);
+    void commands.getPowerState().then(setState);
+  }, []);
+
+  if (!settings) {
+    return null;
+  }
+
+  const power = settings.power;
+  const update = (patch: Partial<PowerSettings>) =>
+    setSettings({ ...settings, power: { ...power, ...patch } });
+  const load = settings.load;
+
+  const save = async () => {
+    try {
+      const latest = await commands.getAppSettings();
+      setSettings(await commands.saveAppSettings({ ...latest, power, load }));
+      setState(await commands.getPowerState());
+      setStatus("Power settings saved.");
+    } catch (error) {
+      setStatus(String(error));
+    }
+  };
+
+  return (
+    <Card>
+      <CardHeader>
+        <CardTitle>Battery &amp; Performance</CardTitle>
+        <CardDescription>
+          Use less power during long sessions when your laptop is unplugged or the machine
+          is struggling to keep up.
+        </CardDescription>
+      </CardHeader>
+      <CardContent className="space-y-3">
+        {state ? (
+          <p className="text-xs text-muted-foreground">
+            {describeSource(state)}
+            {state.lowPower ? " — battery saver active." : "."}
+          </p>
+        ) : null}
+        <label className="flex items-center gap-2 text-sm">
+          <Checkbox
+            checked={power.enabled}
+            onChange={(event) => update({ enabled: event.target.checked })}
+          />
+          Switch to battery saver automatically when unplugged
+        </label>
+        <div className="space-y-1">
+          <Label htmlFor="power-model">Whisper model on battery</Label>
+          <Input
+            id="power-model"
+            value={power.batteryWhisperModel}
+            placeholder="ggml-tiny.en.bin"
+            onChange={(event) => update({ batteryWhisperModel: event.target.value })}
+          />
+        </div>
+        <div className="flex items-center gap-2">
+          <Label htmlFor="power-segment">Seconds per transcription chunk</Label>
+          <Input
+            id="power-segment"
+            type="number"
+            min={0}
+            className="w-24"
+            value={power.batterySegmentSeconds}
+            onChange={(event) =>
+              update({ batterySegmentSeconds: Math.max(0, Number(event.target.value) || 0) })
+            }
+          />
+        </div>
+        <label className="flex items-center gap-2 text-sm">
+          <Checkbox
+            checked={power.pauseHealthChecks}
+            onChange={(event) => update({ pauseHealthChecks: event.target.checked })}
+          />
+          Pause background endpoint health checks
+        </label>
+        <label className="flex items-center gap-2 text-sm">
+          <Checkbox
+            checked={load.enabled}
+            onChange={(event) =>
+              setSettings({ ...settings, load: { ...load, enabled: event.target.checked } })
+            }
+          />
+          Also use a lighter model when the CPU is busy or overheating
+        </label>
+        <Button size="sm" onClick={() => void save()}>
+          Save power settings
+        </Button>
+        {status ? <p className="text-xs text-muted-foreground">{status}</p> : null}
+      </CardContent>
+    </Card>
+  );
+}
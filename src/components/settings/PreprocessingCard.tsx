@@ -0,0 +1,190 @@
+import * as React from "react";
+
+import type { AudioFilters, PreprocessingSettings } from "../../bindings";
+import { commands } from "../../bindings";
+import { Button } from "../ui/button";
+import {
+  Card,
+  CardContent,
+  CardDescription,
+  CardHeader,
+  CardTitle,
+} from "../ui/card";
+import { Checkbox } from "../ui/checkbox";
+import { Input } from "../ui/input";
+import { Label } from "../ui/label";
+
+const DEFAULT_FILTERS: AudioFilters = {
+  enabled: true,
+  highPassHz: 100,
+  noiseSuppression: true,
+  normalize: true,
+};
+
+function FiltersRow({
+  id,
+  filters,
+  onChange,
+}: {
+  id: string;
+  filters: AudioFilters;
+  onChange: (filters: AudioFilters) => void;
+}) {
+  return (
+    <div className="flex flex-wrap items-center gap-3 text-sm">
+      <label className="flex items-center gap-2">
+        <Checkbox
+          checked={filters.enabled}
+          onChange={(event) => onChange({ ...filters, enabled: event.target.checked })}
+        />
+        On
+      </label>
+      <div className="flex items-center gap-2">
+        <Label htmlFor={`${id}-high-pass`}>High-pass Hz</Label>
+        <Input
+          id={`${id}-high-pass`}
+          type="number"
+          className="w-24"
+          placeholder="off"
+          value={filters.highPassHz ?? ""}
+          onChange={(event) =>
+            onChange({
+              ...filters,
+              highPassHz: event.target.value.trim() === "" ? null : Number(event.target.value),
+            })
+          }
+        />
+      </div>
+      <label className="flex items-center gap-2">
+        <Checkbox
+          checked={filters.noiseSuppression}
+          onChange={(event) => onChange({ ...filters, noiseSuppression: event.target.checked })}
+        />
+        Noise suppression
+      </label>
+      <label className="flex items-center gap-2">
+        <Checkbox
+          checked={filters.normalize}
+          onChange={(event) => onChange({ ...filters, normalize: event.target.checked })}
+        />
+        Normalize
+      </label>
+    </div>
+  );
+}
+
+export function PreprocessingCard() {
+  const [preprocessing, setPreprocessing] = React.useState<PreprocessingSettings | null>(null);
+  const [trackNames, setTrackNames] = React.useState<string[]>([]);
+  const [newInput, setNewInput] = React.useState("");
+  const [status, setStatus] = React.useState("");
+
+  React.useEffect(() => {
+    void commands.getAppSettings().then((settings) => {
+      setPreprocessing(settings.transcription.preprocessing);
+      setTrackNames(settings.transcription.inputTracks.map((track) => track.name));
+    });
+  }, []);
+
+  if (!preprocessing) {
+    return null;
+  }
+
+  const { inputs } = preprocessing;
+  const addInput = () => {
+    const input = newInput.trim();
+    if (!input || inputs.some((entry) => entry.input.toLowerCase() === input.toLowerCase())) {
+      return;
+    }
+    setPreprocessing({ ...preprocessing, inputs: [...inputs, { input, filters: DEFAULT_FILTERS }] });
+    setNewInput("");
+  };
+
+  const save = async () => {
+    try {
+      const latest = await commands.getAppSettings();
+      const saved = await commands.saveAppSettings({
+        ...latest,
+        transcription: { ...latest.transcription, preprocessing },
+      });
+      setPreprocessing(saved.transcription.preprocessing);
+      setStatus("Saved. It applies from the next segment.");
+    } catch (error) {
+      setStatus(String(error));
+    }
+  };
+
+  return (
+    <Card>
+      <CardHeader>
+        <CardTitle>Audio Preprocessing</CardTitle>
+        <CardDescription>
+          Clean up audio before whisper hears it. The high-pass filter removes fan and desk
+          rumble, noise suppression pulls steady background noise down between words (where
+          whisper hallucinates <code>[Music]</code>), and normalization evens out quiet
+          microphones. Inputs are matched by track name, or by device name when recording
+          natively.
+        </CardDescription>
+      </CardHeader>
+      <CardContent className="space-y-3">
+        <div className="space-y-1">
+          <p className="text-sm font-medium">All other inputs</p>
+          <FiltersRow
+            id="preprocessing-default"
+            filters={preprocessing.default}
+            onChange={(filters) => setPreprocessing({ ...preprocessing, default: filters })}
+          />
+        </div>
+        {inputs.map((entry, index) => (
+          <div key={entry.input} className="space-y-1">
+            <div className="flex items-center gap-2">
+              <p className="text-sm font-medium">{entry.input}</p>
+              <Button
+                size="sm"
+                variant="ghost"
+                onClick={() =>
+                  setPreprocessing({
+                    ...preprocessing,
+                    inputs: inputs.filter((_, i) => i !== index),
+                  })
+                }
+              >
+                Remove
+              </Button>
+            </div>
+            <FiltersRow
+              id={`preprocessing-${index}`}
+              filters={entry.filters}
+              onChange={(filters) =>
+                setPreprocessing({
+                  ...preprocessing,
+                  inputs: inputs.map((other, i) => (i === index ? { ...other, filters } : other)),
+                })
+              }
+            />
+          </div>
+        ))}
+        <div className="flex items-center gap-2">
+          <Input
+            list="preprocessing-tracks"
+            value={newInput}
+            placeholder="Track or device name"
+            onChange={(event) => setNewInput(event.target.value)}
+          />
+          <datalist id="preprocessing-tracks">
+            {trackNames.map((name) => (
+              <option key={name} value={name} />
+            ))}
+          </datalist>
+          <Button size="sm" variant="outline" onClick={addInput}>
+            Add input
+          </Button>
+        </div>
+        <Button size="sm" onClick={() => void save()}>
+          Save preprocessing
+        </Button>
+        {status ? <p className="text-xs text-muted-foreground">{status}</p> : null}
+      </CardContent>
+    </Card>
+  );
+}
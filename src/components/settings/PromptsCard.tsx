@@ -0,0 +1,95 @@
+import * as React from "react";
+
+import type { AppSettings, SavedPrompt } from "../../bindings";
+import { commands } from "../../bindings";
+import { Button } from "../ui/button";
+import {
+  Card,
+  CardContent,
+  CardDescription,
+  CardHeader,
+  CardTitle,
+} from "../ui/card";
+import { Input } from "../ui/input";
+import { Textarea } from "../ui/textarea";
+
+export function PromptsCard() {
+  const [settings, setSettings] = React.useState<AppSettings | null>(null);
+  const [status, setStatus] = React.useState("");
+
+  React.useEffect(() => {
+    void commands.getAppSettings().then(setSettings);
+  }, []);
+
+  if (!settings) {
+    return null;
+  }
+
+  const saved = settings.prompts.saved;
+  const setSaved = (next: SavedPrompt[]) =>
+    setSettings({ ...settings, prompts: { ...settings.prompts, saved: next } });
+  const updateAt = (index: number, patch: Partial<SavedPrompt>) =>
+    setSaved(saved.map((prompt, i) => (i === index ? { ...prompt, ...patch } : prompt)));
+
+  const save = async () => {
+    try {
+      const latest = await commands.getAppSettings();
+      setSettings(await commands.saveAppSettings({ ...latest, prompts: settings.prompts }));
+      setStatus("Prompts saved.");
+    } catch (error) {
+      setStatus(String(error));
+    }
+  };
+
+  return (
+    <Card>
+      <CardHeader>
+        <CardTitle>Quick Prompts</CardTitle>
+        <CardDescription>
+          Shown under the transcript and run on the selected or copied text. Use{" "}
+          <code>{"{{text}}"}</code> where the text goes. A prompt named like a built-in one
+          (summarize, translate, explain, bullet-points) replaces it.
+        </CardDescription>
+      </CardHeader>
+      <CardContent className="space-y-3">
+        {saved.map((prompt, index) => (
+          <div key={index} className="space-y-2 rounded-md border border-border/60 p-2">
+            <div className="flex gap-2">
+              <Input
+                placeholder="Name"
+                value={prompt.name}
+                onChange={(event) => updateAt(index, { name: event.target.value })}
+              />
+              <Button
+                size="sm"
+                variant="outline"
+                onClick={() => setSaved(saved.filter((_, i) => i !== index))}
+              >
+                Remove
+              </Button>
+            </div>
+            <Textarea
+              rows={3}
+              placeholder="Rewrite this as a tweet: {{text}}"
+              value={prompt.template}
+              onChange={(event) => updateAt(index, { template: event.target.value })}
+            />
+          </div>
+        ))}
+        <div className="flex gap-2">
+          <Button
+            size="sm"
+            variant="outline"
+            onClick={() => setSaved([...saved, { name: "", template: "" }])}
+          >
+            Add prompt
+          </Button>
+          <Button size="sm" onClick={() => void save()}>
+            Save prompts
+          </Button>
+        </div>
+        {status ? <p className="text-xs text-muted-foreground">{status}</p> : null}
+      </CardContent>
+    </Card>
+  );
+}
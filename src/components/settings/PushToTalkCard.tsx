@@ -0,0 +1,96 @@
+import * as React from "react";
+
+import type { PushToTalkStatus } from "../../bindings";
+import { commands } from "../../bindings";
+import { Button } from "../ui/button";
+import {
+  Card,
+  CardContent,
+  CardDescription,
+  CardHeader,
+  CardTitle,
+} from "../ui/card";
+import { Checkbox } from "../ui/checkbox";
+import { Input } from "../ui/input";
+import { Label } from "../ui/label";
+
+export function PushToTalkCard() {
+  const [current, setCurrent] = React.useState<PushToTalkStatus | null>(null);
+  const [shortcut, setShortcut] = React.useState("");
+  const [nativeRecording, setNativeRecording] = React.useState(false);
+  const [status, setStatus] = React.useState("");
+
+  React.useEffect(() => {
+    void commands.getPushToTalkStatus().then((loaded) => {
+      setCurrent(loaded);
+      setShortcut(loaded.shortcut);
+      setNativeRecording(loaded.nativeRecording);
+    });
+  }, []);
+
+  if (!current) {
+    return null;
+  }
+
+  const register = async () => {
+    try {
+      setCurrent(await commands.registerPushToTalk(shortcut, nativeRecording));
+      setStatus(`Hold ${shortcut.trim()} to listen.`);
+    } catch (error) {
+      setStatus(String(error));
+    }
+  };
+
+  const unregister = async () => {
+    try {
+      setCurrent(await commands.unregisterPushToTalk());
+      setStatus("Push-to-talk is off.");
+    } catch (error) {
+      setStatus(String(error));
+    }
+  };
+
+  return (
+    <Card>
+      <CardHeader>
+        <CardTitle>Push-to-Talk</CardTitle>
+        <CardDescription>
+          Hold a global shortcut to listen and release it to stop, without switching to the
+          window. It works while another app is in front.
+        </CardDescription>
+      </CardHeader>
+      <CardContent className="space-y-3">
+        <div className="flex items-center gap-2">
+          <Label htmlFor="ptt-shortcut">Shortcut</Label>
+          <Input
+            id="ptt-shortcut"
+            value={shortcut}
+            placeholder="CmdOrCtrl+Shift+Space"
+            onChange={(event) => setShortcut(event.target.value)}
+          />
+        </div>
+        <label className="flex items-center gap-2 text-sm">
+          <Checkbox
+            checked={nativeRecording}
+            onChange={(event) => setNativeRecording(event.target.checked)}
+          />
+          Record natively from the backend, even while the window is hidden
+        </label>
+        <div className="flex gap-2">
+          <Button size="sm" onClick={() => void register()}>
+            {current.enabled ? "Update shortcut" : "Turn on"}
+          </Button>
+          {current.enabled ? (
+            <Button size="sm" variant="outline" onClick={() => void unregister()}>
+              Turn off
+            </Button>
+          ) : null}
+        </div>
+        {current.enabled && !current.registered && current.error ? (
+          <p className="text-xs text-muted-foreground">{current.error}</p>
+        ) : null}
+        {status ? <p className="text-xs text-muted-foreground">{status}</p> : null}
+      </CardContent>
+    </Card>
+  );
+}
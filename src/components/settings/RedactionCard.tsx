@@ -0,0 +1,129 @@
+import * as React from "react";
+
+import type { RedactionSettings } from "../../bindings";
+import { commands } from "../../bindings";
+import { Button } from "../ui/button";
+import {
+  Card,
+  CardContent,
+  CardDescription,
+  CardHeader,
+  CardTitle,
+} from "../ui/card";
+import { Checkbox } from "../ui/checkbox";
+import { Input } from "../ui/input";
+import { Label } from "../ui/label";
+import { Textarea } from "../ui/textarea";
+
+const CATEGORIES: { key: "profanity" | "emails" | "phoneNumbers" | "cardNumbers"; label: string }[] = [
+  { key: "profanity", label: "Profanity" },
+  { key: "emails", label: "Email addresses" },
+  { key: "phoneNumbers", label: "Phone numbers" },
+  { key: "cardNumbers", label: "Card-like numbers" },
+];
+
+const lines = (text: string) =>
+  text
+    .split("\n")
+    .map((line) => line.trim())
+    .filter(Boolean);
+
+export function RedactionCard() {
+  const [redaction, setRedaction] = React.useState<RedactionSettings | null>(null);
+  const [words, setWords] = React.useState("");
+  const [patterns, setPatterns] = React.useState("");
+  const [status, setStatus] = React.useState("");
+
+  const show = (next: RedactionSettings) => {
+    setRedaction(next);
+    setWords(next.words.join("\n"));
+    setPatterns(next.patterns.join("\n"));
+  };
+
+  React.useEffect(() => {
+    void commands.getAppSettings().then((settings) => show(settings.redaction));
+  }, []);
+
+  if (!redaction) {
+    return null;
+  }
+
+  const save = async () => {
+    try {
+      const latest = await commands.getAppSettings();
+      const saved = await commands.saveAppSettings({
+        ...latest,
+        redaction: { ...redaction, words: lines(words), patterns: lines(patterns) },
+      });
+      show(saved.redaction);
+      setStatus("Saved. New transcript lines are masked from now on.");
+    } catch (error) {
+      setStatus(String(error));
+    }
+  };
+
+  return (
+    <Card>
+      <CardHeader>
+        <CardTitle>Redaction</CardTitle>
+        <CardDescription>
+          Mask sensitive text as soon as it is transcribed, before it reaches the log, the
+          session files, the screen or the agent. Lines already recorded aren't changed.
+        </CardDescription>
+      </CardHeader>
+      <CardContent className="space-y-3">
+        <label className="flex items-center gap-2 text-sm">
+          <Checkbox
+            checked={redaction.enabled}
+            onChange={(event) => setRedaction({ ...redaction, enabled: event.target.checked })}
+          />
+          Redact transcripts
+        </label>
+        <div className="grid grid-cols-2 gap-2">
+          {CATEGORIES.map(({ key, label }) => (
+            <label key={key} className="flex items-center gap-2 text-sm">
+              <Checkbox
+                checked={redaction[key]}
+                disabled={!redaction.enabled}
+                onChange={(event) => setRedaction({ ...redaction, [key]: event.target.checked })}
+              />
+              {label}
+            </label>
+          ))}
+        </div>
+        <div className="space-y-2">
+          <Label htmlFor="redaction-words">Words and phrases, one per line</Label>
+          <Textarea
+            id="redaction-words"
+            rows={3}
+            placeholder={"Project Falcon\nAcme Corp"}
+            value={words}
+            onChange={(event) => setWords(event.target.value)}
+          />
+        </div>
+        <div className="space-y-2">
+          <Label htmlFor="redaction-patterns">Regex patterns, one per line</Label>
+          <Textarea
+            id="redaction-patterns"
+            rows={2}
+            placeholder={"\\bACME-\\d+\\b"}
+            value={patterns}
+            onChange={(event) => setPatterns(event.target.value)}
+          />
+        </div>
+        <div className="space-y-1">
+          <Label htmlFor="redaction-mask">Replace with</Label>
+          <Input
+            id="redaction-mask"
+            value={redaction.mask}
+            onChange={(event) => setRedaction({ ...redaction, mask: event.target.value })}
+          />
+        </div>
+        <Button size="sm" onClick={() => void save()}>
+          Save redaction
+        </Button>
+        {status ? <p className="text-xs text-muted-foreground">{status}</p> : null}
+      </CardContent>
+    </Card>
+  );
+}
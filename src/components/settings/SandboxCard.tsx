@@ -0,0 +1,115 @@
+import * as React from "react";
+
+import type { SandboxNetwork, SandboxSettings } from "../../bindings";
+import { commands } from "../../bindings";
+import { Button } from "../ui/button";
+import {
+  Card,
+  CardContent,
+  CardDescription,
+  CardHeader,
+  CardTitle,
+} from "../ui/card";
+import { Checkbox } from "../ui/checkbox";
+import { Label } from "../ui/label";
+import { Textarea } from "../ui/textarea";
+
+export function SandboxCard() {
+  const [sandbox, setSandbox] = React.useState<SandboxSettings | null>(null);
+  const [dirs, setDirs] = React.useState("");
+  const [profile, setProfile] = React.useState("");
+  const [status, setStatus] = React.useState("");
+
+  React.useEffect(() => {
+    void commands.getAppSettings().then((settings) => {
+      setSandbox(settings.sandbox);
+      setDirs(settings.sandbox.allowedDirs.join("\n"));
+    });
+  }, []);
+
+  if (!sandbox) {
+    return null;
+  }
+
+  const save = async () => {
+    try {
+      const latest = await commands.getAppSettings();
+      const allowedDirs = dirs
+        .split("\n")
+        .map((dir) => dir.trim())
+        .filter(Boolean);
+      const saved = await commands.saveAppSettings({
+        ...latest,
+        sandbox: { ...sandbox, allowedDirs },
+      });
+      setSandbox(saved.sandbox);
+      setStatus("Sandbox settings saved. They apply to processes started from now on.");
+    } catch (error) {
+      setStatus(String(error));
+    }
+  };
+
+  return (
+    <Card>
+      <CardHeader>
+        <CardTitle>Process Sandbox</CardTitle>
+        <CardDescription>
+          macOS only. Runs the agent, its MCP servers and the canvas server under{" "}
+          <code>sandbox-exec</code>, so they can't read files in your home folder outside the
+          HeyJamie repo, the app's own folders, node's caches, the servers' working directories
+          and the folders listed here, and can only write to those and temp.
+        </CardDescription>
+      </CardHeader>
+      <CardContent className="space-y-3">
+        <label className="flex items-center gap-2 text-sm">
+          <Checkbox
+            checked={sandbox.enabled}
+            onChange={(event) => setSandbox({ ...sandbox, enabled: event.target.checked })}
+          />
+          Sandbox node processes
+        </label>
+        <div className="space-y-2">
+          <Label htmlFor="sandbox-dirs">Extra folders, one per line</Label>
+          <Textarea
+            id="sandbox-dirs"
+            rows={3}
+            placeholder={"~/Documents/Show Notes"}
+            value={dirs}
+            onChange={(event) => setDirs(event.target.value)}
+          />
+        </div>
+        <div className="flex items-center gap-2">
+          <Label htmlFor="sandbox-network">Network</Label>
+          <select
+            id="sandbox-network"
+            className="h-8 flex-1 rounded-md border bg-background px-2 text-sm"
+            value={sandbox.network}
+            onChange={(event) =>
+              setSandbox({ ...sandbox, network: event.target.value as SandboxNetwork })
+            }
+          >
+            <option value="allow">Allowed</option>
+            <option value="local-only">Localhost only</option>
+            <option value="deny">Blocked (local models only)</option>
+          </select>
+        </div>
+        <div className="flex gap-2">
+          <Button size="sm" onClick={() => void save()}>
+            Save sandbox settings
+          </Button>
+          <Button
+            size="sm"
+            variant="ghost"
+            onClick={() => void commands.getSandboxProfile().then(setProfile)}
+          >
+            Show profile
+          </Button>
+        </div>
+        {profile ? (
+          <pre className="max-h-48 overflow-auto rounded border p-2 text-xs">{profile}</pre>
+        ) : null}
+        {status ? <p className="text-xs text-muted-foreground">{status}</p> : null}
+      </CardContent>
+    </Card>
+  );
+}
@@ -0,0 +1,92 @@
+import * as React from "react";
+
+import type { ScriptInfo } from "../../bindings";
+import { commands } from "../../bindings";
+import { Button } from "../ui/button";
+import {
+  Card,
+  CardContent,
+  CardDescription,
+  CardHeader,
+  CardTitle,
+} from "../ui/card";
+import { Checkbox } from "../ui/checkbox";
+
+export function ScriptingCard() {
+  const [enabled, setEnabled] = React.useState<boolean | null>(null);
+  const [scripts, setScripts] = React.useState<ScriptInfo[]>([]);
+  const [status, setStatus] = React.useState("");
+
+  React.useEffect(() => {
+    void commands.getAppSettings().then((settings) => setEnabled(settings.scripting.enabled));
+    void commands.listScripts().then(setScripts);
+  }, []);
+
+  if (enabled === null) {
+    return null;
+  }
+
+  const toggle = async (next: boolean) => {
+    try {
+      const latest = await commands.getAppSettings();
+      const saved = await commands.saveAppSettings({ ...latest, scripting: { enabled: next } });
+      setEnabled(saved.scripting.enabled);
+      setScripts(await commands.listScripts());
+      setStatus(next ? "Scripts loaded." : "Scripts unloaded.");
+    } catch (error) {
+      setStatus(String(error));
+    }
+  };
+
+  const reload = async () => {
+    try {
+      setScripts(await commands.reloadScripts());
+      setStatus("Reloaded.");
+    } catch (error) {
+      setStatus(String(error));
+    }
+  };
+
+  return (
+    <Card>
+      <CardHeader>
+        <CardTitle>Scripts</CardTitle>
+        <CardDescription>
+          Run your own Rhai scripts from the <code>scripts</code> folder next to{" "}
+          <code>settings.json</code>. Define <code>on_segment</code>, <code>on_topic_shift</code>{" "}
+          or <code>on_agent_result</code> to react to new transcript lines, topic changes and agent
+          results. Scripts can bookmark the moment, append to the session's notes and open URLs,
+          and can't touch other files or the network. Only turn this on for scripts you trust.
+        </CardDescription>
+      </CardHeader>
+      <CardContent className="space-y-3">
+        <label className="flex items-center gap-2 text-sm">
+          <Checkbox checked={enabled} onChange={(event) => void toggle(event.target.checked)} />
+          Run scripts
+        </label>
+        {enabled ? (
+          <>
+            {scripts.length === 0 ? (
+              <p className="text-xs text-muted-foreground">No .rhai files in the scripts folder.</p>
+            ) : (
+              <ul className="space-y-1 text-xs">
+                {scripts.map((script) => (
+                  <li key={script.name}>
+                    <span className="font-medium">{script.name}</span>{" "}
+                    <span className="text-muted-foreground">
+                      {script.error ?? (script.hooks.join(", ") || "no hooks")}
+                    </span>
+                  </li>
+                ))}
+              </ul>
+            )}
+            <Button size="sm" variant="outline" onClick={() => void reload()}>
+              Reload scripts
+            </Button>
+          </>
+        ) : null}
+        {status ? <p className="text-xs text-muted-foreground">{status}</p> : null}
+      </CardContent>
+    </Card>
+  );
+}
@@ -0,0 +1,241 @@
+import * as React from "react";
+
+import type {
+  SessionAudio,
+  SessionAudioSettings,
+  SessionInfo,
+  TranscriptVersion,
+} from "../../bindings";
+import { commands } from "../../bindings";
+import { Button } from "../ui/button";
+import {
+  Card,
+  CardContent,
+  CardDescription,
+  CardHeader,
+  CardTitle,
+} from "../ui/card";
+import { Checkbox } from "../ui/checkbox";
+import { Input } from "../ui/input";
+import { Label } from "../ui/label";
+
+function offset(ms: number) {
+  const seconds = Math.floor(ms / 1000);
+  return `${Math.floor(seconds / 60)}:${String(seconds % 60).padStart(2, "0")}`;
+}
+
+export function SessionAudioCard() {
+  const [sessionAudio, setSessionAudio] = React.useState<SessionAudioSettings | null>(null);
+  const [sessions, setSessions] = React.useState<SessionInfo[]>([]);
+  const [sessionId, setSessionId] = React.useState("");
+  const [audio, setAudio] = React.useState<SessionAudio | null>(null);
+  const [versions, setVersions] = React.useState<TranscriptVersion[]>([]);
+  const [model, setModel] = React.useState("");
+  const [status, setStatus] = React.useState("");
+
+  React.useEffect(() => {
+    void commands.getAppSettings().then((settings) => {
+      setSessionAudio(settings.sessionAudio);
+      setModel(settings.postSession.retranscribeModel);
+    });
+    void commands.listSessions().then(setSessions);
+  }, []);
+
+  const load = React.useCallback(async (id: string) => {
+    setSessionId(id);
+    setAudio(id ? await commands.getSessionAudio(id) : null);
+    setVersions(id ? await commands.listTranscriptVersions(id) : []);
+  }, []);
+
+  if (!sessionAudio) {
+    return null;
+  }
+
+  const run = async (action: () => Promise<string | void>) => {
+    try {
+      setStatus((await action()) ?? "");
+    } catch (error) {
+      setStatus(String(error));
+    }
+  };
+
+  const save = () =>
+    run(async () => {
+      const latest = await commands.getAppSettings();
+      const saved = await commands.saveAppSettings({ ...latest, sessionAudio });
+      setSessionAudio(saved.sessionAudio);
+      return "Saved.";
+    });
+
+  const play = (offsetMs: number) =>
+    run(async () => {
+      const wav = await commands.readSessionAudioSegment(sessionId, offsetMs);
+      await new Audio(`data:audio/wav;base64,${wav}`).play();
+    });
+
+  return (
+    <Card>
+      <CardHeader>
+        <CardTitle>Session Audio</CardTitle>
+        <CardDescription>
+          Keep the audio of every transcribed segment in the session folder, so you can listen
+          again where whisper misheard, or retranscribe the session with a bigger model later.
+          Each retranscription is saved as a new version next to the live transcript.
+          Audio from older sessions is deleted after the retention period or once the total
+          passes the cap, oldest first. The latest session is never deleted.
+        </CardDescription>
+      </CardHeader>
+      <CardContent className="space-y-3">
+        <label className="flex items-center gap-2 text-sm">
+          <Checkbox
+            checked={sessionAudio.keep}
+            onChange={(event) => setSessionAudio({ ...sessionAudio, keep: event.target.checked })}
+          />
+          Keep session audio
+        </label>
+        <div className="grid grid-cols-2 gap-2">
+          <div className="space-y-1">
+            <Label htmlFor="session-audio-days">Keep for days (0 = forever)</Label>
+            <Input
+              id="session-audio-days"
+              type="number"
+              min={0}
+              value={sessionAudio.retentionDays}
+              onChange={(event) =>
+                setSessionAudio({ ...sessionAudio, retentionDays: Number(event.target.value) || 0 })
+              }
+            />
+          </div>
+          <div className="space-y-1">
+            <Label htmlFor="session-audio-cap">Cap in MB (0 = none)</Label>
+            <Input
+              id="session-audio-cap"
+              type="number"
+              min={0}
+              value={sessionAudio.maxTotalMb}
+              onChange={(event) =>
+                setSessionAudio({ ...sessionAudio, maxTotalMb: Number(event.target.value) || 0 })
+              }
+            />
+          </div>
+        </div>
+        <Button size="sm" onClick={() => void save()}>
+          Save session audio settings
+        </Button>
+        <div className="flex items-center gap-2">
+          <Label htmlFor="session-audio-session">Session</Label>
+          <select
+            id="session-audio-session"
+            className="h-8 flex-1 rounded-md border bg-background px-2 text-sm"
+            value={sessionId}
+            onChange={(event) => void load(event.target.value)}
+          >
+            <option value="">Choose a session</option>
+            {sessions.map((session) => (
+              <option key={session.id} value={session.id}>
+                {session.title ?? session.id} · {new Date(session.startedAt).toLocaleString()}
+              </option>
+            ))}
+          </select>
+        </div>
+        {sessionId ? (
+          <div className="flex items-center gap-2">
+            <Button
+              size="sm"
+              variant="outline"
+              onClick={() => void run(() => commands.revealSessionFolder(sessionId))}
+            >
+              Show folder
+            </Button>
+            <Button
+              size="sm"
+              variant="outline"
+              onClick={() =>
+                void run(async () => {
+                  setStatus("Bundling…");
+                  return `Saved ${await commands.exportSessionBundle(sessionId, null)}.`;
+                })
+              }
+            >
+              Export bundle
+            </Button>
+          </div>
+        ) : null}
+        {audio ? (
+          audio.segments.length === 0 ? (
+            <p className="text-xs text-muted-foreground">No audio was kept for this session.</p>
+          ) : (
+            <div className="space-y-2">
+              <p className="text-xs text-muted-foreground">
+                {audio.segments.length} segments, {(audio.totalBytes / (1024 * 1024)).toFixed(1)}{" "}
+                MB.
+              </p>
+              <div className="flex max-h-40 flex-wrap gap-1 overflow-y-auto">
+                {audio.segments.map((segment) => (
+                  <Button
+                    key={segment.offsetMs}
+                    size="sm"
+                    variant="outline"
+                    title={`${(segment.durationMs / 1000).toFixed(1)} s`}
+                    onClick={() => void play(segment.offsetMs)}
+                  >
+                    {offset(segment.offsetMs)}
+                  </Button>
+                ))}
+              </div>
+              <div className="flex items-center gap-2">
+                <Input
+                  aria-label="Model to retranscribe with"
+                  value={model}
+                  onChange={(event) => setModel(event.target.value)}
+                  placeholder="ggml-large-v3.bin"
+                />
+                <Button
+                  size="sm"
+                  onClick={() =>
+                    void run(async () => {
+                      setStatus("Retranscribing…");
+                      const version = await commands.retranscribeSession(
+                        sessionId,
+                        model || null,
+                        null
+                      );
+                      await load(sessionId);
+                      return `Saved version ${version.id} (${version.segmentCount} segments).`;
+                    })
+                  }
+                >
+                  Retranscribe
+                </Button>
+                <Button
+                  size="sm"
+                  variant="outline"
+                  onClick={() =>
+                    void run(async () => {
+                      await commands.deleteSessionAudio(sessionId);
+                      await load(sessionId);
+                      return "Deleted.";
+                    })
+                  }
+                >
+                  Delete audio
+                </Button>
+              </div>
+              {versions.length > 0 ? (
+                <ul className="space-y-1 text-xs text-muted-foreground">
+                  {versions.map((version) => (
+                    <li key={version.id}>
+                      {version.model} · {new Date(version.createdAt).toLocaleString()} ·{" "}
+                      {version.segmentCount} segments
+                    </li>
+                  ))}
+                </ul>
+              ) : null}
+            </div>
+          )
+        ) : null}
+        {status ? <p className="text-xs text-muted-foreground">{status}</p> : null}
+      </CardContent>
+    </Card>
+  );
+}
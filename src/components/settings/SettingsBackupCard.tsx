@@ -0,0 +1,74 @@
+import * as React from "react";
+
+import { commands } from "../../bindings";
+import { Button } from "../ui/button";
+import {
+  Card,
+  CardContent,
+  CardDescription,
+  CardHeader,
+  CardTitle,
+} from "../ui/card";
+import { Input } from "../ui/input";
+import { Label } from "../ui/label";
+
+export function SettingsBackupCard() {
+  const [path, setPath] = React.useState("");
+  const [status, setStatus] = React.useState("");
+
+  const exportSettings = async () => {
+    try {
+      await commands.exportSettings(path);
+      setStatus(`Settings exported to ${path}.`);
+    } catch (error) {
+      setStatus(String(error));
+    }
+  };
+
+  const importSettings = async () => {
+    try {
+      await commands.importSettings(path);
+      setStatus("Settings imported. Reopen settings to see the imported values.");
+    } catch (error) {
+      setStatus(String(error));
+    }
+  };
+
+  return (
+    <Card>
+      <CardHeader>
+        <CardTitle>Backup</CardTitle>
+        <CardDescription>
+          Export these settings to a JSON file to back them up or move them to another
+          machine. API keys and client secrets are left out; importing keeps the ones saved
+          here.
+        </CardDescription>
+      </CardHeader>
+      <CardContent className="space-y-3">
+        <div className="space-y-1">
+          <Label htmlFor="settings-backup-path">File</Label>
+          <Input
+            id="settings-backup-path"
+            value={path}
+            onChange={(event) => setPath(event.target.value)}
+            placeholder="~/Desktop/heyjamie-settings.json"
+          />
+        </div>
+        <div className="flex gap-2">
+          <Button size="sm" disabled={!path.trim()} onClick={() => void exportSettings()}>
+            Export
+          </Button>
+          <Button
+            size="sm"
+            variant="outline"
+            disabled={!path.trim()}
+            onClick={() => void importSettings()}
+          >
+            Import
+          </Button>
+        </div>
+        {status ? <p className="text-xs text-muted-foreground">{status}</p> : null}
+      </CardContent>
+    </Card>
+  );
+}
@@ -0,0 +1,131 @@
+import * as React from "react";
+
+import type { AppSettings, TimecodeSettings } from "../../bindings";
+import { commands } from "../../bindings";
+import { Button } from "../ui/button";
+import {
+  Card,
+  CardContent,
+  CardDescription,
+  CardHeader,
+  CardTitle,
+} from "../ui/card";
+import { Checkbox } from "../ui/checkbox";
+import { Input } from "../ui/input";
+import { Label } from "../ui/label";
+
+const FRAME_RATES = [24, 25, 30];
+
+export function TimecodeCard() {
+  const [settings, setSettings] = React.useState<AppSettings | null>(null);
+  const [devices, setDevices] = React.useState<MediaDeviceInfo[]>([]);
+  const [status, setStatus] = React.useState("");
+
+  React.useEffect(() => {
+    void commands.getAppSettings().then(setSettings);
+    void navigator.mediaDevices
+      ?.enumerateDevices()
+      .then((all) => setDevices(all.filter((device) => device.kind === "audioinput")))
+      .catch(() => {});
+  }, []);
+
+  if (!settings) {
+    return null;
+  }
+
+  const timecode = settings.timecode;
+  const update = (patch: Partial<TimecodeSettings>) =>
+    setSettings({ ...settings, timecode: { ...timecode, ...patch } });
+
+  const save = async () => {
+    try {
+      const latest = await commands.getAppSettings();
+      setSettings(await commands.saveAppSettings({ ...latest, timecode }));
+      setStatus("Timecode settings saved.");
+    } catch (error) {
+      setStatus(String(error));
+    }
+  };
+
+  const syncNow = async () => {
+    try {
+      const result = await commands.syncSessionClock(timecode.ntpServer);
+      setStatus(
+        `Synced with ${result.server}: local clock off by ${result.clockOffsetMs} ms ` +
+          `(round trip ${result.roundTripMs} ms).`
+      );
+    } catch (error) {
+      setStatus(String(error));
+    }
+  };
+
+  return (
+    <Card>
+      <CardHeader>
+        <CardTitle>Timecode</CardTitle>
+        <CardDescription>
+          Line up exported transcripts and key moments with audio from an external recorder,
+          either by time of day (NTP) or by reading LTC from an input.
+        </CardDescription>
+      </CardHeader>
+      <CardContent className="space-y-3">
+        <div className="space-y-1">
+          <Label htmlFor="timecode-ntp">NTP server</Label>
+          <Input
+            id="timecode-ntp"
+            value={timecode.ntpServer}
+            placeholder="pool.ntp.org"
+            onChange={(event) => update({ ntpServer: event.target.value })}
+          />
+        </div>
+        <label className="flex items-center gap-2 text-sm">
+          <Checkbox
+            checked={timecode.autoSyncNtp}
+            onChange={(event) => update({ autoSyncNtp: event.target.checked })}
+          />
+          Sync with NTP when a session starts
+        </label>
+        <div className="flex items-center gap-2">
+          <Label htmlFor="timecode-fps">Frame rate</Label>
+          <select
+            id="timecode-fps"
+            className="h-8 rounded-md border bg-background px-2 text-sm"
+            value={timecode.fps}
+            onChange={(event) => update({ fps: Number(event.target.value) })}
+          >
+            {FRAME_RATES.map((fps) => (
+              <option key={fps} value={fps}>
+                {fps} fps
+              </option>
+            ))}
+          </select>
+        </div>
+        <div className="space-y-1">
+          <Label htmlFor="timecode-ltc">LTC input</Label>
+          <select
+            id="timecode-ltc"
+            className="h-8 w-full rounded-md border bg-background px-2 text-sm"
+            value={timecode.ltcDeviceId}
+            onChange={(event) => update({ ltcDeviceId: event.target.value })}
+          >
+            <option value="">None</option>
+            {devices.map((device) => (
+              <option key={device.deviceId} value={device.deviceId}>
+                {device.label || device.deviceId}
+              </option>
+            ))}
+          </select>
+        </div>
+        <div className="flex gap-2">
+          <Button size="sm" onClick={() => void save()}>
+            Save timecode settings
+          </Button>
+          <Button size="sm" variant="outline" onClick={() => void syncNow()}>
+            Sync current session now
+          </Button>
+        </div>
+        {status ? <p className="text-xs text-muted-foreground">{status}</p> : null}
+      </CardContent>
+    </Card>
+  );
+}
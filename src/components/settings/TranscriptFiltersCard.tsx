@@ -0,0 +1,72 @@
+import * as React from "react";
+
+import type { TranscriptFilters } from "../../bindings";
+import { commands } from "../../bindings";
+import { Button } from "../ui/button";
+import {
+  Card,
+  CardContent,
+  CardDescription,
+  CardHeader,
+  CardTitle,
+} from "../ui/card";
+import { Label } from "../ui/label";
+import { Textarea } from "../ui/textarea";
+
+const format = (filters: TranscriptFilters) => JSON.stringify(filters, null, 2);
+
+export function TranscriptFiltersCard() {
+  const [json, setJson] = React.useState("");
+  const [status, setStatus] = React.useState("");
+
+  const reload = React.useCallback(async () => {
+    setJson(format(await commands.getTranscriptFilters()));
+  }, []);
+
+  React.useEffect(() => {
+    void reload();
+  }, [reload]);
+
+  const save = async () => {
+    try {
+      const filters = JSON.parse(json) as TranscriptFilters;
+      setJson(format(await commands.saveTranscriptFilters(filters)));
+      setStatus("Transcript filters saved.");
+    } catch (error) {
+      setStatus(String(error));
+    }
+  };
+
+  return (
+    <Card>
+      <CardHeader>
+        <CardTitle>Transcript Filters</CardTitle>
+        <CardDescription>
+          Lines whisper makes up in silence ("you", "[Music]") are dropped from the transcript.
+          Add your own phrases, words and regexes, and per-language rules under{" "}
+          <code>languages</code> keyed by language code (e.g. <code>"de"</code>).
+        </CardDescription>
+      </CardHeader>
+      <CardContent className="space-y-3">
+        <div className="space-y-2">
+          <Label htmlFor="transcript-filters-json">Filters JSON</Label>
+          <Textarea
+            id="transcript-filters-json"
+            rows={10}
+            value={json}
+            onChange={(event) => setJson(event.target.value)}
+          />
+        </div>
+        <div className="flex gap-2">
+          <Button size="sm" onClick={() => void save()}>
+            Save filters
+          </Button>
+          <Button size="sm" variant="outline" onClick={() => void reload()}>
+            Reload
+          </Button>
+        </div>
+        {status ? <p className="text-xs text-muted-foreground">{status}</p> : null}
+      </CardContent>
+    </Card>
+  );
+}
@@ -0,0 +1,139 @@
+import * as React from "react";
+
+import type { TranscriptionBackendKind, TranscriptionSettings } from "../../bindings";
+import { commands } from "../../bindings";
+import { Button } from "../ui/button";
+import {
+  Card,
+  CardContent,
+  CardDescription,
+  CardHeader,
+  CardTitle,
+} from "../ui/card";
+import { Checkbox } from "../ui/checkbox";
+import { Input } from "../ui/input";
+import { Label } from "../ui/label";
+
+export function TranscriptionBackendCard() {
+  const [transcription, setTranscription] = React.useState<TranscriptionSettings | null>(null);
+  const [apiKey, setApiKey] = React.useState("");
+  const [hasKey, setHasKey] = React.useState(true);
+  const [status, setStatus] = React.useState("");
+
+  React.useEffect(() => {
+    void commands.getAppSettings().then((settings) => setTranscription(settings.transcription));
+  }, []);
+
+  const backend = transcription?.backend;
+  React.useEffect(() => {
+    if (backend) {
+      void commands.hasTranscriptionApiKey(backend).then(setHasKey);
+    }
+  }, [backend]);
+
+  if (!transcription) {
+    return null;
+  }
+  const cloud = transcription.cloud;
+
+  const save = async () => {
+    try {
+      if (apiKey.trim()) {
+        await commands.setTranscriptionApiKey(transcription.backend, apiKey);
+        setApiKey("");
+      }
+      const latest = await commands.getAppSettings();
+      const saved = await commands.saveAppSettings({
+        ...latest,
+        transcription: { ...latest.transcription, backend: transcription.backend, cloud },
+      });
+      setTranscription(saved.transcription);
+      setHasKey(await commands.hasTranscriptionApiKey(saved.transcription.backend));
+      setStatus("Saved. The next segment uses it.");
+    } catch (error) {
+      setStatus(String(error));
+    }
+  };
+
+  return (
+    <Card>
+      <CardHeader>
+        <CardTitle>Transcription Service</CardTitle>
+        <CardDescription>
+          Where speech is turned into text. Local whisper runs on this machine (after any remote
+          whisper servers). On a slow machine, OpenAI or Deepgram answer faster for a per-minute
+          fee; each segment is uploaded as it is recorded. Keys are kept in the system keychain,
+          or read from <code>OPENAI_API_KEY</code> / <code>DEEPGRAM_API_KEY</code>.
+        </CardDescription>
+      </CardHeader>
+      <CardContent className="space-y-3">
+        <div className="flex items-center gap-2">
+          <Label htmlFor="transcription-backend">Service</Label>
+          <select
+            id="transcription-backend"
+            className="h-8 flex-1 rounded-md border bg-background px-2 text-sm"
+            value={transcription.backend}
+            onChange={(event) =>
+              setTranscription({
+                ...transcription,
+                backend: event.target.value as TranscriptionBackendKind,
+              })
+            }
+          >
+            <option value="local">Local whisper</option>
+            <option value="openai">OpenAI</option>
+            <option value="deepgram">Deepgram</option>
+          </select>
+        </div>
+        {transcription.backend !== "local" ? (
+          <>
+            <div className="space-y-1">
+              <Label htmlFor="transcription-model">Model</Label>
+              <Input
+                id="transcription-model"
+                placeholder={transcription.backend === "openai" ? "whisper-1" : "nova-2"}
+                value={transcription.backend === "openai" ? cloud.openaiModel : cloud.deepgramModel}
+                onChange={(event) =>
+                  setTranscription({
+                    ...transcription,
+                    cloud:
+                      transcription.backend === "openai"
+                        ? { ...cloud, openaiModel: event.target.value }
+                        : { ...cloud, deepgramModel: event.target.value },
+                  })
+                }
+              />
+            </div>
+            <div className="space-y-1">
+              <Label htmlFor="transcription-api-key">
+                API key{hasKey ? " (saved; leave empty to keep it)" : ""}
+              </Label>
+              <Input
+                id="transcription-api-key"
+                type="password"
+                value={apiKey}
+                onChange={(event) => setApiKey(event.target.value)}
+              />
+            </div>
+            <label className="flex items-center gap-2 text-sm">
+              <Checkbox
+                checked={cloud.fallbackToLocal}
+                onChange={(event) =>
+                  setTranscription({
+                    ...transcription,
+                    cloud: { ...cloud, fallbackToLocal: event.target.checked },
+                  })
+                }
+              />
+              Use local whisper when the service fails
+            </label>
+          </>
+        ) : null}
+        <Button size="sm" onClick={() => void save()}>
+          Save transcription service
+        </Button>
+        {status ? <p className="text-xs text-muted-foreground">{status}</p> : null}
+      </CardContent>
+    </Card>
+  );
+}
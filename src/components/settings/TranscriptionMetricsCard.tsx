@@ -0,0 +1,76 @@
+import * as React from "react";
+
+import type { ModelMetrics, TranscriptionMetrics } from "../../bindings";
+import { commands, events } from "../../bindings";
+import {
+  Card,
+  CardContent,
+  CardDescription,
+  CardHeader,
+  CardTitle,
+} from "../ui/card";
+
+function describeModel(model: ModelMetrics) {
+  const load = model.avgModelLoadMs === null ? "" : `, model load ${model.avgModelLoadMs} ms`;
+  return `${model.segments} segments, RTF ${model.avgRealTimeFactor}, decode ${model.avgDecodeMs} ms, total ${model.avgTotalMs} ms${load}`;
+}
+
+function describeSegment(segment: TranscriptionMetrics) {
+  const parts = [
+    segment.payloadMs === null ? null : `payload ${segment.payloadMs} ms`,
+    segment.tempFileMs === null ? null : `temp file ${segment.tempFileMs} ms`,
+    segment.modelLoadMs ? `model load ${segment.modelLoadMs} ms` : null,
+    `whisper ${segment.decodeMs} ms`,
+  ].filter(Boolean);
+  return `${(segment.audioMs / 1000).toFixed(1)} s of audio in ${segment.totalMs} ms: ${parts.join(", ")}`;
+}
+
+export function TranscriptionMetricsCard() {
+  const [models, setModels] = React.useState<ModelMetrics[]>([]);
+  const [latest, setLatest] = React.useState<TranscriptionMetrics | null>(null);
+
+  React.useEffect(() => {
+    const refresh = () =>
+      void commands.getTranscriptionMetrics().then((report) => {
+        setModels(report.models);
+        setLatest(report.recent[0] ?? null);
+      });
+    refresh();
+    const unlisten = events.transcriptionMetrics.listen(refresh);
+    return () => {
+      void unlisten.then((stop) => stop());
+    };
+  }, []);
+
+  return (
+    <Card>
+      <CardHeader>
+        <CardTitle>Transcription Performance</CardTitle>
+        <CardDescription>
+          Averages over the last 100 segments. A real-time factor (RTF) below 1 means whisper
+          decodes faster than the audio plays; the rest of the total is payload decoding, temp
+          files and model loading.
+        </CardDescription>
+      </CardHeader>
+      <CardContent className="space-y-3">
+        {models.length === 0 ? (
+          <p className="text-xs text-muted-foreground">Nothing has been transcribed yet.</p>
+        ) : (
+          <ul className="space-y-2">
+            {models.map((model) => (
+              <li key={`${model.backend}-${model.model}`} className="text-sm">
+                <span className="font-medium">{model.model ?? model.backend}</span>{" "}
+                <span className="text-xs text-muted-foreground">
+                  ({model.backend}) {describeModel(model)}
+                </span>
+              </li>
+            ))}
+          </ul>
+        )}
+        {latest ? (
+          <p className="text-xs text-muted-foreground">Last segment: {describeSegment(latest)}.</p>
+        ) : null}
+      </CardContent>
+    </Card>
+  );
+}
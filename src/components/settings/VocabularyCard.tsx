@@ -0,0 +1,97 @@
+import * as React from "react";
+
+import type { AppSettings } from "../../bindings";
+import { commands } from "../../bindings";
+import { Button } from "../ui/button";
+import {
+  Card,
+  CardContent,
+  CardDescription,
+  CardHeader,
+  CardTitle,
+} from "../ui/card";
+import { Label } from "../ui/label";
+import { Textarea } from "../ui/textarea";
+
+export function VocabularyCard() {
+  const [settings, setSettings] = React.useState<AppSettings | null>(null);
+  const [vocabulary, setVocabulary] = React.useState("");
+  const [status, setStatus] = React.useState("");
+
+  React.useEffect(() => {
+    void commands.getAppSettings().then((loaded) => {
+      setSettings(loaded);
+      setVocabulary(loaded.transcription.vocabulary.join("\n"));
+    });
+  }, []);
+
+  if (!settings) {
+    return null;
+  }
+
+  const initialPrompt = settings.transcription.initialPrompt;
+
+  const save = async () => {
+    try {
+      const latest = await commands.getAppSettings();
+      const terms = vocabulary
+        .split(/[\n,]/)
+        .map((term) => term.trim())
+        .filter(Boolean);
+      setSettings(
+        await commands.saveAppSettings({
+          ...latest,
+          transcription: { ...latest.transcription, initialPrompt, vocabulary: terms },
+        })
+      );
+      setVocabulary(terms.join("\n"));
+      setStatus("Vocabulary saved.");
+    } catch (error) {
+      setStatus(String(error));
+    }
+  };
+
+  return (
+    <Card>
+      <CardHeader>
+        <CardTitle>Vocabulary</CardTitle>
+        <CardDescription>
+          Whisper is primed with this text so names and product words are spelled the way you
+          write them. Whisper only reads about 800 characters, so a longer prompt loses its
+          beginning.
+        </CardDescription>
+      </CardHeader>
+      <CardContent className="space-y-3">
+        <div className="space-y-2">
+          <Label htmlFor="whisper-initial-prompt">Initial prompt</Label>
+          <Textarea
+            id="whisper-initial-prompt"
+            rows={2}
+            placeholder="A podcast about building desktop apps with Tauri."
+            value={initialPrompt}
+            onChange={(event) =>
+              setSettings({
+                ...settings,
+                transcription: { ...settings.transcription, initialPrompt: event.target.value },
+              })
+            }
+          />
+        </div>
+        <div className="space-y-2">
+          <Label htmlFor="whisper-vocabulary">Names and terms (one per line)</Label>
+          <Textarea
+            id="whisper-vocabulary"
+            rows={4}
+            placeholder={"Excalidraw\nTauri\nHeyJamie"}
+            value={vocabulary}
+            onChange={(event) => setVocabulary(event.target.value)}
+          />
+        </div>
+        <Button size="sm" onClick={() => void save()}>
+          Save vocabulary
+        </Button>
+        {status ? <p className="text-xs text-muted-foreground">{status}</p> : null}
+      </CardContent>
+    </Card>
+  );
+}
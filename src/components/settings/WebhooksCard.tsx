@@ -0,0 +1,138 @@
+import * as React from "react";
+
+import type { WebhookSettings, WebhookStatus, WebhookTrigger } from "../../bindings";
+import { commands } from "../../bindings";
+import { Button } from "../ui/button";
+import {
+  Card,
+  CardContent,
+  CardDescription,
+  CardHeader,
+  CardTitle,
+} from "../ui/card";
+import { Checkbox } from "../ui/checkbox";
+import { Input } from "../ui/input";
+import { Label } from "../ui/label";
+import { Textarea } from "../ui/textarea";
+
+const EXAMPLE: WebhookTrigger[] = [
+  { name: "start-show", action: "start-session", prompt: "", label: "Live show" },
+  { name: "chapter", action: "add-marker", prompt: "", label: "New segment" },
+  { name: "summary", action: "run-prompt", prompt: "Summarize", label: "" },
+];
+
+function newToken() {
+  const bytes = crypto.getRandomValues(new Uint8Array(24));
+  return Array.from(bytes, (byte) => byte.toString(16).padStart(2, "0")).join("");
+}
+
+export function WebhooksCard() {
+  const [webhooks, setWebhooks] = React.useState<WebhookSettings | null>(null);
+  const [json, setJson] = React.useState("");
+  const [current, setCurrent] = React.useState<WebhookStatus | null>(null);
+  const [status, setStatus] = React.useState("");
+
+  const reload = React.useCallback(async () => {
+    const settings = await commands.getAppSettings();
+    setWebhooks(settings.webhooks);
+    setJson(JSON.stringify(settings.webhooks.triggers, null, 2));
+    setCurrent(await commands.getWebhookStatus());
+  }, []);
+
+  React.useEffect(() => {
+    void reload();
+  }, [reload]);
+
+  if (!webhooks) {
+    return null;
+  }
+
+  const save = async () => {
+    try {
+      const triggers = JSON.parse(json) as WebhookTrigger[];
+      const latest = await commands.getAppSettings();
+      const saved = await commands.saveAppSettings({ ...latest, webhooks: { ...webhooks, triggers } });
+      setWebhooks(saved.webhooks);
+      setJson(JSON.stringify(saved.webhooks.triggers, null, 2));
+      setCurrent(await commands.getWebhookStatus());
+      setStatus("Webhooks saved.");
+    } catch (error) {
+      setStatus(String(error));
+    }
+  };
+
+  return (
+    <Card>
+      <CardHeader>
+        <CardTitle>Webhooks</CardTitle>
+        <CardDescription>
+          Let other tools drive HeyJamie with <code>POST /trigger/&lt;name&gt;</code> on
+          localhost and an <code>Authorization: Bearer</code> token. Each trigger starts a
+          session, adds a marker or runs a saved prompt on a posted <code>text</code> (else the
+          transcript so far); a posted <code>label</code> overrides the trigger's.
+        </CardDescription>
+      </CardHeader>
+      <CardContent className="space-y-3">
+        <label className="flex items-center gap-2 text-sm">
+          <Checkbox
+            checked={webhooks.enabled}
+            onChange={(event) => setWebhooks({ ...webhooks, enabled: event.target.checked })}
+          />
+          Listen for webhook triggers
+        </label>
+        <div className="flex items-center gap-2">
+          <Label htmlFor="webhooks-port">Port</Label>
+          <Input
+            id="webhooks-port"
+            type="number"
+            min={1024}
+            max={65535}
+            value={webhooks.port}
+            onChange={(event) => setWebhooks({ ...webhooks, port: Number(event.target.value) })}
+          />
+        </div>
+        <div className="flex items-center gap-2">
+          <Label htmlFor="webhooks-token">Token</Label>
+          <Input
+            id="webhooks-token"
+            value={webhooks.token}
+            onChange={(event) => setWebhooks({ ...webhooks, token: event.target.value })}
+          />
+          <Button
+            size="sm"
+            variant="outline"
+            onClick={() => setWebhooks({ ...webhooks, token: newToken() })}
+          >
+            Generate
+          </Button>
+        </div>
+        <div className="space-y-2">
+          <Label htmlFor="webhooks-json">Triggers JSON</Label>
+          <Textarea
+            id="webhooks-json"
+            rows={8}
+            value={json}
+            onChange={(event) => setJson(event.target.value)}
+          />
+        </div>
+        <div className="flex gap-2">
+          <Button size="sm" onClick={() => void save()}>
+            Save webhooks
+          </Button>
+          <Button
+            size="sm"
+            variant="ghost"
+            onClick={() => setJson(JSON.stringify(EXAMPLE, null, 2))}
+          >
+            Show example
+          </Button>
+        </div>
+        {current?.url ? (
+          <p className="text-xs text-muted-foreground">Listening at {current.url}</p>
+        ) : null}
+        {current?.error ? <p className="text-xs text-muted-foreground">{current.error}</p> : null}
+        {status ? <p className="text-xs text-muted-foreground">{status}</p> : null}
+      </CardContent>
+    </Card>
+  );
+}
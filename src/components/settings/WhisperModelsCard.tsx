@@ -0,0 +1,293 @@
+import * as React from "react";
+
+import type {
+  ModelDownloadProgress,
+  WhisperBenchmark,
+  WhisperModel,
+  WhisperSetupReport,
+} from "../../bindings";
+import { commands, events } from "../../bindings";
+import { Button } from "../ui/button";
+import {
+  Card,
+  CardContent,
+  CardDescription,
+  CardHeader,
+  CardTitle,
+} from "../ui/card";
+import { Checkbox } from "../ui/checkbox";
+
+function formatProgress(progress: ModelDownloadProgress): string {
+  const mb = Math.round(progress.downloadedBytes / (1024 * 1024));
+  if (progress.totalBytes) {
+    return `${Math.floor((progress.downloadedBytes / progress.totalBytes) * 100)}% (${mb} MB)`;
+  }
+  return `${mb} MB`;
+}
+
+export function WhisperModelsCard() {
+  const [models, setModels] = React.useState<WhisperModel[]>([]);
+  const [progress, setProgress] = React.useState<Record<string, ModelDownloadProgress>>({});
+  const [status, setStatus] = React.useState("");
+  const [benchmark, setBenchmark] = React.useState<WhisperBenchmark | null>(null);
+  const [benchmarking, setBenchmarking] = React.useState(false);
+  const [exactModel, setExactModel] = React.useState(false);
+  const [coreMlAvailable, setCoreMlAvailable] = React.useState(false);
+  const [report, setReport] = React.useState<WhisperSetupReport | null>(null);
+  const [verifying, setVerifying] = React.useState(false);
+
+  const refresh = React.useCallback(() => {
+    void commands.listWhisperModels().then(setModels);
+  }, []);
+
+  React.useEffect(() => {
+    refresh();
+    void commands.getAppSettings().then((settings) => setExactModel(settings.transcription.exactModel));
+    void commands.getTranscriptionCapabilities().then((capabilities) => setCoreMlAvailable(capabilities.metal));
+    const unlisten = events.modelDownloadProgress.listen((event) => {
+      if (event.payload.done) return;
+      setProgress((prev) => ({ ...prev, [event.payload.name]: event.payload }));
+    });
+    const unlistenIntegrity = events.modelIntegrity.listen((event) => {
+      setStatus(`${event.payload.fileName}: ${event.payload.detail}`);
+      refresh();
+    });
+    return () => {
+      void unlisten.then((stop) => stop());
+      void unlistenIntegrity.then((stop) => stop());
+    };
+  }, [refresh]);
+
+  const download = async (name: string) => {
+    setStatus(`Downloading ${name}...`);
+    setProgress((prev) => ({
+      ...prev,
+      [name]: { name, downloadedBytes: 0, totalBytes: null, done: false },
+    }));
+    try {
+      await commands.downloadWhisperModel(name);
+      setStatus(`${name} downloaded.`);
+    } catch (error) {
+      setStatus(String(error));
+    } finally {
+      setProgress((prev) => {
+        const next = { ...prev };
+        delete next[name];
+        return next;
+      });
+      refresh();
+    }
+  };
+
+  const remove = async (name: string) => {
+    try {
+      await commands.deleteWhisperModel(name);
+      setStatus(`${name} deleted.`);
+    } catch (error) {
+      setStatus(String(error));
+    }
+    refresh();
+  };
+
+  const select = async (model: WhisperModel) => {
+    try {
+      // Reload first so edits made in other cards aren't overwritten.
+      const latest = await commands.getAppSettings();
+      await commands.saveAppSettings({
+        ...latest,
+        transcription: { ...latest.transcription, whisperModel: model.fileName },
+      });
+      setStatus(`Transcribing with ${model.name}.`);
+    } catch (error) {
+      setStatus(String(error));
+    }
+    refresh();
+  };
+
+  const prepare = async (name: string, variant: string) => {
+    setStatus(`Preparing ${variant} for ${name}...`);
+    try {
+      const path = await commands.prepareModelVariant(name, variant);
+      setStatus(`Installed ${path}.`);
+    } catch (error) {
+      setStatus(String(error));
+    }
+    refresh();
+  };
+
+  const saveExactModel = async (exact: boolean) => {
+    setExactModel(exact);
+    try {
+      const latest = await commands.getAppSettings();
+      await commands.saveAppSettings({
+        ...latest,
+        transcription: { ...latest.transcription, exactModel: exact },
+      });
+    } catch (error) {
+      setStatus(String(error));
+    }
+    refresh();
+  };
+
+  const runBenchmark = async () => {
+    setBenchmarking(true);
+    setStatus("Benchmarking installed models...");
+    try {
+      const result = await commands.benchmarkWhisper(null);
+      setBenchmark(result);
+      setStatus(
+        result.recommended
+          ? `${result.recommended} is the largest model that keeps up in real time.`
+          : "None of the installed models kept up comfortably."
+      );
+    } catch (error) {
+      setStatus(String(error));
+    } finally {
+      setBenchmarking(false);
+    }
+  };
+
+  const verify = async (repair: boolean) => {
+    setVerifying(true);
+    setStatus(repair ? "Re-downloading damaged models..." : "Verifying installed models...");
+    try {
+      const result = await commands.verifyWhisperSetup(repair);
+      setReport(result);
+      const damaged = result.models.filter((model) => model.state === "damaged").length;
+      setStatus(
+        !result.cliPath
+          ? "whisper-cli was not found; run the setup script."
+          : damaged === 0
+            ? "Installed models look intact."
+            : repair
+              ? "Damaged models are downloading again."
+              : `${damaged} damaged model${damaged === 1 ? "" : "s"}.`
+      );
+    } catch (error) {
+      setStatus(String(error));
+    } finally {
+      setVerifying(false);
+      refresh();
+    }
+  };
+
+  const repairable = report?.models.some((model) => model.state === "damaged" && model.repairable && !model.repairing);
+
+  return (
+    <Card>
+      <CardHeader>
+        <CardTitle>Whisper Models</CardTitle>
+        <CardDescription>
+          Download a larger or multilingual model and switch to it without touching the
+          setup script. Models are stored in the app data folder. A quantized variant
+          (q8_0, q5_1, q5_0) next to a model is loaded in its place, and a Core ML encoder speeds
+          up whisper.cpp builds made with Core ML on Apple Silicon. The benchmark times each
+          installed model on the same clip to show which ones your machine runs in real time.
+          Verifying checks each model's header, size and checksum, so a half-finished download is
+          caught before whisper fails on it.
+        </CardDescription>
+      </CardHeader>
+      <CardContent className="space-y-2">
+        {models.map((model) => {
+          const downloading = progress[model.name];
+          return (
+            <div key={model.name} className="flex items-center gap-2 text-sm">
+              <span className="min-w-0 flex-1">
+                {model.name}
+                <span className="ml-2 text-xs text-muted-foreground">
+                  {model.sizeMb} MB{model.multilingual ? " · multilingual" : ""}
+                  {model.variant ? ` · ${model.variant}` : ""}
+                  {model.coreMl ? " · Core ML" : ""}
+                  {model.active ? " · in use" : ""}
+                </span>
+              </span>
+              {downloading ? (
+                <span className="text-xs text-muted-foreground">{formatProgress(downloading)}</span>
+              ) : model.installed ? (
+                <>
+                  <Button
+                    size="sm"
+                    variant="outline"
+                    disabled={model.active}
+                    onClick={() => void select(model)}
+                  >
+                    Use
+                  </Button>
+                  <Button size="sm" variant="ghost" onClick={() => void remove(model.name)}>
+                    Delete
+                  </Button>
+                  {!model.variant ? (
+                    <Button size="sm" variant="ghost" onClick={() => void prepare(model.name, "q5_1")}>
+                      Quantize
+                    </Button>
+                  ) : null}
+                  {coreMlAvailable && !model.coreMl ? (
+                    <Button size="sm" variant="ghost" onClick={() => void prepare(model.name, "coreml")}>
+                      Core ML
+                    </Button>
+                  ) : null}
+                </>
+              ) : (
+                <Button size="sm" variant="outline" onClick={() => void download(model.name)}>
+                  Download
+                </Button>
+              )}
+            </div>
+          );
+        })}
+        <label className="flex items-center gap-2 text-sm">
+          <Checkbox
+            checked={exactModel}
+            onChange={(event) => void saveExactModel(event.target.checked)}
+          />
+          Load the exact model file, not a quantized variant next to it
+        </label>
+        <Button
+          size="sm"
+          variant="outline"
+          disabled={benchmarking}
+          onClick={() => void runBenchmark()}
+        >
+          Benchmark installed models
+        </Button>
+        <div className="flex gap-2">
+          <Button size="sm" variant="outline" disabled={verifying} onClick={() => void verify(false)}>
+            Verify models
+          </Button>
+          {repairable ? (
+            <Button size="sm" variant="outline" disabled={verifying} onClick={() => void verify(true)}>
+              Re-download damaged models
+            </Button>
+          ) : null}
+        </div>
+        {report ? (
+          <ul className="space-y-1 text-xs text-muted-foreground">
+            {report.models.map((model) => (
+              <li key={model.path}>
+                {model.fileName}:{" "}
+                {model.state === "ok"
+                  ? "checksum matches"
+                  : model.state === "unverified"
+                    ? "looks whole (no checksum known)"
+                    : `${model.detail ?? "damaged"}${model.repairing ? " (re-downloading)" : ""}`}
+              </li>
+            ))}
+          </ul>
+        ) : null}
+        {benchmark ? (
+          <ul className="space-y-1 text-xs text-muted-foreground">
+            {benchmark.results.map((result) => (
+              <li key={result.model}>
+                {result.model}:{" "}
+                {result.error
+                  ? result.error
+                  : `${(result.decodeMs / 1000).toFixed(1)} s for ${(benchmark.audioMs / 1000).toFixed(1)} s of audio (RTF ${result.realTimeFactor})`}
+              </li>
+            ))}
+          </ul>
+        ) : null}
+        {status ? <p className="text-xs text-muted-foreground">{status}</p> : null}
+      </CardContent>
+    </Card>
+  );
+}
@@ -0,0 +1,26 @@
+const FLAG_KEY = "heyjamie.guestMode";
+const GUEST_PREFIX = "heyjamie.guest.";
+
+export function isGuestMode(): boolean {
+  return localStorage.getItem(FLAG_KEY) === "true";
+}
+
+/**
+ * localStorage key for personal data (notes, API key). Guests get their own
+ * throwaway copy so the owner's values are neither read nor overwritten.
+ */
+export function scopedStorageKey(key: string): string {
+  return isGuestMode() ? key.replace(/^heyjamie\./, GUEST_PREFIX) : key;
+}
+
+/** Mirror the backend's guest mode; leaving it erases the guest's storage. */
+export function setGuestModeFlag(active: boolean): void {
+  if (active) {
+    localStorage.setItem(FLAG_KEY, "true");
+    return;
+  }
+  localStorage.removeItem(FLAG_KEY);
+  Object.keys(localStorage)
+    .filter((key) => key.startsWith(GUEST_PREFIX))
+    .forEach((key) => localStorage.removeItem(key));
+}
@@ -0,0 +1,117 @@
+/** One extra microphone captured alongside the primary input. */
+export type TrackCapture = {
+  name: string;
+  sampleRate: number;
+  /** Hand over whatever is buffered, e.g. when recording stops. */
+  flush: () => void;
+  stop: () => void;
+};
+
+type TrackCaptureOptions = {
+  name: string;
+  deviceId: string;
+  segmentSeconds: () => number;
+  minSegmentSeconds: number;
+  onSegment: (samples: Float32Array, sampleRate: number, name: string) => void;
+  /** While true, the input is replaced with silence (the focus gate). */
+  gated?: () => boolean;
+};
+
+export function audioConstraints(deviceId: string): MediaStreamConstraints {
+  return { audio: deviceId ? { deviceId: { exact: deviceId } } : true };
+}
+
+/**
+ * The media device id of the input named `name` (`transcription.inputDevice`,
+ * a native device name), or "" for the default when it isn't connected.
+ * Labels only appear once microphone access has been granted.
+ */
+export async function deviceIdForName(name: string): Promise<string> {
+  const wanted = name.trim().toLowerCase();
+  if (!wanted || !navigator.mediaDevices?.enumerateDevices) return "";
+  const devices = await navigator.mediaDevices.enumerateDevices().catch(() => []);
+  const match = devices.find(
+    (device) => device.kind === "audioinput" && device.label.toLowerCase().includes(wanted)
+  );
+  return match?.deviceId ?? "";
+}
+
+/**
+ * Capture `deviceId` in its own audio graph and hand each segment to
+ * `onSegment` tagged with the track name, mirroring the primary pipeline.
+ */
+export async function startTrackCapture(options: TrackCaptureOptions): Promise<TrackCapture> {
+  const stream = await navigator.mediaDevices.getUserMedia(audioConstraints(options.deviceId));
+  const context = new AudioContext();
+  const source = context.createMediaStreamSource(stream);
+  const processor = context.createScriptProcessor(4096, 1, 1);
+  const silence = context.createGain();
+  silence.gain.value = 0;
+
+  let chunks: Float32Array[] = [];
+  let length = 0;
+
+  const flush = () => {
+    if (length < context.sampleRate * options.minSegmentSeconds) return;
+    const merged = new Float32Array(length);
+    let offset = 0;
+    for (const chunk of chunks) {
+      merged.set(chunk, offset);
+      offset += chunk.length;
+    }
+    chunks = [];
+    length = 0;
+    options.onSegment(merged, context.sampleRate, options.name);
+  };
+
+  processor.onaudioprocess = (event) => {
+    const input = event.inputBuffer.getChannelData(0);
+    chunks.push(options.gated?.() ? new Float32Array(input.length) : new Float32Array(input));
+    length += input.length;
+    if (length >= context.sampleRate * options.segmentSeconds()) {
+      flush();
+    }
+  };
+
+  source.connect(processor);
+  processor.connect(silence);
+  silence.connect(context.destination);
+
+  return {
+    name: options.name,
+    sampleRate: context.sampleRate,
+    flush,
+    stop: () => {
+      processor.disconnect();
+      processor.onaudioprocess = null;
+      silence.disconnect();
+      stream.getTracks().forEach((track) => track.stop());
+      void context.close();
+    },
+  };
+}
+
+/** Record `seconds` of audio from one input, e.g. to read LTC off it. */
+export async function captureClip(
+  deviceId: string,
+  seconds: number
+): Promise<{ samples: Float32Array; sampleRate: number }> {
+  const chunks: Float32Array[] = [];
+  let done: () => void = () => {};
+  const finished = new Promise<void>((resolve) => {
+    done = resolve;
+  });
+  const capture = await startTrackCapture({
+    name: "clip",
+    deviceId,
+    segmentSeconds: () => seconds,
+    minSegmentSeconds: 0,
+    onSegment: (samples) => {
+      chunks.push(samples);
+      done();
+    },
+  });
+  await finished;
+  capture.stop();
+  return { samples: chunks[0], sampleRate: capture.sampleRate };
+}
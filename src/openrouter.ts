@@ -1,3 +1,5 @@
+import { isGuestMode, scopedStorageKey } from "./guestMode";
+
 export type OpenRouterSettings = {
   apiKey: string;
   model: string;
@@ -72,7 +74,8 @@ const STORAGE_KEY = "heyjamie.openrouter";
 function normalizeSettings(
   settings: Partial<OpenRouterSettings> | null | undefined
 ): OpenRouterSettings {
-  const normalizedApiKey = settings?.apiKey?.trim() || ENV_OPENROUTER_API_KEY || "";
+  const envApiKey = isGuestMode() ? "" : ENV_OPENROUTER_API_KEY;
+  const normalizedApiKey = settings?.apiKey?.trim() || envApiKey || "";
   const normalizedModel =
     ENV_OPENROUTER_MODEL || settings?.model?.trim() || DEFAULT_OPENROUTER_MODEL;
   const rawSensitivity = Number(settings?.topicShiftSensitivity);
@@ -119,7 +122,7 @@ function normalizeSettings(
 }
 
 export function loadOpenRouterSettings(): OpenRouterSettings {
-  const raw = localStorage.getItem(STORAGE_KEY);
+  const raw = localStorage.getItem(scopedStorageKey(STORAGE_KEY));
   if (!raw) {
     return normalizeSettings({});
   }
@@ -133,7 +136,7 @@ export function loadOpenRouterSettings(): OpenRouterSettings {
 
 export function saveOpenRouterSettings(settings: OpenRouterSettings): void {
   const normalized = normalizeSettings(settings);
-  localStorage.setItem(STORAGE_KEY, JSON.stringify(normalized));
+  localStorage.setItem(scopedStorageKey(STORAGE_KEY), JSON.stringify(normalized));
 }
 
 export function clearOpenRouterKey(): void {
@@ -2,8 +2,11 @@ import React from "react";
 import ReactDOM from "react-dom/client";
 
 import "./index.css";
+import { negotiateApiVersion } from "./apiVersion";
 import { SettingsApp } from "./SettingsApp";
 
+void negotiateApiVersion();
+
 ReactDOM.createRoot(document.getElementById("root")!).render(
   <React.StrictMode>
     <SettingsApp />
@@ -1,3 +1,5 @@
+import { scopedStorageKey } from "./guestMode";
+
 export type UserNote = {
   id: string;
   text: string;
@@ -7,7 +9,7 @@ export type UserNote = {
 const STORAGE_KEY = "heyjamie.userNotes";
 
 export function loadUserNotes(): UserNote[] {
-  const raw = localStorage.getItem(STORAGE_KEY);
+  const raw = localStorage.getItem(scopedStorageKey(STORAGE_KEY));
   if (!raw) return [];
   try {
     const parsed = JSON.parse(raw);
@@ -26,7 +28,7 @@ export function loadUserNotes(): UserNote[] {
 }
 
 export function saveUserNotes(notes: UserNote[]): void {
-  localStorage.setItem(STORAGE_KEY, JSON.stringify(notes));
+  localStorage.setItem(scopedStorageKey(STORAGE_KEY), JSON.stringify(notes));
 }
 
 export function addUserNote(text: string): UserNote | null {